@@ -6,11 +6,19 @@
 //! so that cloning the store for savepoints and undo is O(1).
 //! The store uses `RwLock` for interior mutability and thread safety,
 //! allowing `&HashMapStore` to be shared across threads via `Arc`.
+//!
+//! Entity and junction tables are `JournaledMap`s: while a change journal is
+//! open, they remember the ids written, so that the journal can hand out the
+//! changed rows only (`HashMapStoreDelta`) instead of a copy of the store.
 
 use crate::entities::*;
-use crate::snapshot::{StoreSnapshot, StoreSnapshotTrait};
-use crate::types::EntityId;
+use crate::event::{DirectAccessEntity, EntityEvent, Event, Origin};
+use crate::snapshot::{StoreJournal, StoreSnapshot, StoreSnapshotTrait};
+use crate::types::{EntityId, IndexKey};
 use im::HashMap;
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::ops::Deref;
 use std::sync::RwLock;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -20,43 +28,53 @@ use std::sync::RwLock;
 #[derive(Debug, Default)]
 pub struct HashMapStore {
     // ── Entity tables ──────────────────────────────────────────────────
-    pub roots: RwLock<HashMap<EntityId, Root>>,
-    pub workspaces: RwLock<HashMap<EntityId, Workspace>>,
-    pub systems: RwLock<HashMap<EntityId, System>>,
-    pub entitys: RwLock<HashMap<EntityId, Entity>>,
-    pub fields: RwLock<HashMap<EntityId, Field>>,
-    pub features: RwLock<HashMap<EntityId, Feature>>,
-    pub files: RwLock<HashMap<EntityId, File>>,
-    pub use_cases: RwLock<HashMap<EntityId, UseCase>>,
-    pub dtos: RwLock<HashMap<EntityId, Dto>>,
-    pub dto_fields: RwLock<HashMap<EntityId, DtoField>>,
-    pub globals: RwLock<HashMap<EntityId, Global>>,
-    pub relationships: RwLock<HashMap<EntityId, Relationship>>,
-    pub user_interfaces: RwLock<HashMap<EntityId, UserInterface>>,
+    pub roots: RwLock<JournaledMap<Root>>,
+    pub workspaces: RwLock<JournaledMap<Workspace>>,
+    pub systems: RwLock<JournaledMap<System>>,
+    pub entitys: RwLock<JournaledMap<Entity>>,
+    pub fields: RwLock<JournaledMap<Field>>,
+    pub features: RwLock<JournaledMap<Feature>>,
+    pub files: RwLock<JournaledMap<File>>,
+    pub use_cases: RwLock<JournaledMap<UseCase>>,
+    pub dtos: RwLock<JournaledMap<Dto>>,
+    pub dto_fields: RwLock<JournaledMap<DtoField>>,
+    pub globals: RwLock<JournaledMap<Global>>,
+    pub relationships: RwLock<JournaledMap<Relationship>>,
+    pub user_interfaces: RwLock<JournaledMap<UserInterface>>,
+    pub custom_files: RwLock<JournaledMap<CustomFile>>,
 
     // ── Junction tables (one per forward relationship, shared for backward cleanup) ─
-    pub jn_system_from_root_system: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_workspace_from_root_workspace: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_entity_from_workspace_entities: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_feature_from_workspace_features: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_global_from_workspace_global: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_user_interface_from_workspace_user_interface: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_file_from_system_files: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_field_from_entity_fields: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_entity_from_entity_inherits_from: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_relationship_from_entity_relationships: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_entity_from_field_entity: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_use_case_from_feature_use_cases: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_entity_from_file_entity: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_feature_from_file_feature: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_field_from_file_field: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_use_case_from_file_use_case: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_dto_from_use_case_dto_in: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_dto_from_use_case_dto_out: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_entity_from_use_case_entities: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_dto_field_from_dto_fields: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_entity_from_relationship_left_entity: RwLock<HashMap<EntityId, Vec<EntityId>>>,
-    pub jn_entity_from_relationship_right_entity: RwLock<HashMap<EntityId, Vec<EntityId>>>,
+    pub jn_system_from_root_system: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_workspace_from_root_workspace: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_custom_file_from_workspace_custom_files: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_entity_from_workspace_entities: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_feature_from_workspace_features: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_global_from_workspace_global: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_user_interface_from_workspace_user_interface: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_file_from_system_files: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_field_from_entity_fields: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_entity_from_entity_inherits_from: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_relationship_from_entity_relationships: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_entity_from_field_entity: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_dto_from_feature_dtos: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_use_case_from_feature_use_cases: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_entity_from_file_entity: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_feature_from_file_feature: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_field_from_file_field: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_use_case_from_file_use_case: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_dto_from_use_case_dto_in: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_dto_from_use_case_dto_out: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_entity_from_use_case_entities: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_dto_from_use_case_shared_dto_in: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_dto_from_use_case_shared_dto_out: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_dto_field_from_dto_fields: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_dto_from_dto_field_dto: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_entity_from_relationship_left_entity: RwLock<JournaledMap<Vec<EntityId>>>,
+    pub jn_entity_from_relationship_right_entity: RwLock<JournaledMap<Vec<EntityId>>>,
+
+    // ── Secondary indexes (one per indexed or unique field) ────────
+    pub idx_entity_name: RwLock<HashMap<String, Vec<EntityId>>>,
+    pub idx_file_relative_path: RwLock<HashMap<String, Vec<EntityId>>>,
 
     // ── ID counters (one per entity type) ──────────────────────────
     pub counters: RwLock<std::collections::HashMap<String, EntityId>>,
@@ -64,6 +82,9 @@ pub struct HashMapStore {
     // ── Savepoints ──────────────────────────────────────────────────
     savepoints: RwLock<std::collections::HashMap<u64, HashMapStoreSnapshot>>,
     next_savepoint_id: RwLock<u64>,
+
+    // ── Change journals (open until the write transaction ends) ────────
+    journals: RwLock<Vec<(HashMapStoreSnapshot, StoreJournal)>>,
 }
 
 impl HashMapStore {
@@ -74,319 +95,1656 @@ impl HashMapStore {
     /// Clone the entire store for savepoint support. O(1) thanks to im::HashMap.
     pub fn snapshot(&self) -> HashMapStoreSnapshot {
         HashMapStoreSnapshot {
-            roots: self.roots.read().unwrap().clone(),
-            workspaces: self.workspaces.read().unwrap().clone(),
-            systems: self.systems.read().unwrap().clone(),
-            entitys: self.entitys.read().unwrap().clone(),
-            fields: self.fields.read().unwrap().clone(),
-            features: self.features.read().unwrap().clone(),
-            files: self.files.read().unwrap().clone(),
-            use_cases: self.use_cases.read().unwrap().clone(),
-            dtos: self.dtos.read().unwrap().clone(),
-            dto_fields: self.dto_fields.read().unwrap().clone(),
-            globals: self.globals.read().unwrap().clone(),
-            relationships: self.relationships.read().unwrap().clone(),
-            user_interfaces: self.user_interfaces.read().unwrap().clone(),
-            jn_system_from_root_system: self.jn_system_from_root_system.read().unwrap().clone(),
+            roots: self.roots.read().unwrap().map.clone(),
+            workspaces: self.workspaces.read().unwrap().map.clone(),
+            systems: self.systems.read().unwrap().map.clone(),
+            entitys: self.entitys.read().unwrap().map.clone(),
+            fields: self.fields.read().unwrap().map.clone(),
+            features: self.features.read().unwrap().map.clone(),
+            files: self.files.read().unwrap().map.clone(),
+            use_cases: self.use_cases.read().unwrap().map.clone(),
+            dtos: self.dtos.read().unwrap().map.clone(),
+            dto_fields: self.dto_fields.read().unwrap().map.clone(),
+            globals: self.globals.read().unwrap().map.clone(),
+            relationships: self.relationships.read().unwrap().map.clone(),
+            user_interfaces: self.user_interfaces.read().unwrap().map.clone(),
+            custom_files: self.custom_files.read().unwrap().map.clone(),
+            jn_system_from_root_system: self.jn_system_from_root_system.read().unwrap().map.clone(),
             jn_workspace_from_root_workspace: self
                 .jn_workspace_from_root_workspace
                 .read()
                 .unwrap()
+                .map
+                .clone(),
+            jn_custom_file_from_workspace_custom_files: self
+                .jn_custom_file_from_workspace_custom_files
+                .read()
+                .unwrap()
+                .map
                 .clone(),
             jn_entity_from_workspace_entities: self
                 .jn_entity_from_workspace_entities
                 .read()
                 .unwrap()
+                .map
                 .clone(),
             jn_feature_from_workspace_features: self
                 .jn_feature_from_workspace_features
                 .read()
                 .unwrap()
+                .map
                 .clone(),
             jn_global_from_workspace_global: self
                 .jn_global_from_workspace_global
                 .read()
                 .unwrap()
-                .clone(),
-            jn_user_interface_from_workspace_user_interface: self
-                .jn_user_interface_from_workspace_user_interface
+                .map
+                .clone(),
+            jn_user_interface_from_workspace_user_interface: self
+                .jn_user_interface_from_workspace_user_interface
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_file_from_system_files: self.jn_file_from_system_files.read().unwrap().map.clone(),
+            jn_field_from_entity_fields: self
+                .jn_field_from_entity_fields
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_entity_from_entity_inherits_from: self
+                .jn_entity_from_entity_inherits_from
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_relationship_from_entity_relationships: self
+                .jn_relationship_from_entity_relationships
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_entity_from_field_entity: self
+                .jn_entity_from_field_entity
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_dto_from_feature_dtos: self.jn_dto_from_feature_dtos.read().unwrap().map.clone(),
+            jn_use_case_from_feature_use_cases: self
+                .jn_use_case_from_feature_use_cases
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_entity_from_file_entity: self.jn_entity_from_file_entity.read().unwrap().map.clone(),
+            jn_feature_from_file_feature: self
+                .jn_feature_from_file_feature
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_field_from_file_field: self.jn_field_from_file_field.read().unwrap().map.clone(),
+            jn_use_case_from_file_use_case: self
+                .jn_use_case_from_file_use_case
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_dto_from_use_case_dto_in: self
+                .jn_dto_from_use_case_dto_in
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_dto_from_use_case_dto_out: self
+                .jn_dto_from_use_case_dto_out
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_entity_from_use_case_entities: self
+                .jn_entity_from_use_case_entities
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_dto_from_use_case_shared_dto_in: self
+                .jn_dto_from_use_case_shared_dto_in
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_dto_from_use_case_shared_dto_out: self
+                .jn_dto_from_use_case_shared_dto_out
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_dto_field_from_dto_fields: self
+                .jn_dto_field_from_dto_fields
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_dto_from_dto_field_dto: self.jn_dto_from_dto_field_dto.read().unwrap().map.clone(),
+            jn_entity_from_relationship_left_entity: self
+                .jn_entity_from_relationship_left_entity
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            jn_entity_from_relationship_right_entity: self
+                .jn_entity_from_relationship_right_entity
+                .read()
+                .unwrap()
+                .map
+                .clone(),
+            idx_entity_name: self.idx_entity_name.read().unwrap().clone(),
+            idx_file_relative_path: self.idx_file_relative_path.read().unwrap().clone(),
+            counters: self.counters.read().unwrap().clone(),
+        }
+    }
+
+    /// Restore from a savepoint snapshot.
+    pub fn restore(&self, snap: &HashMapStoreSnapshot) {
+        self.roots.write().unwrap().replace(snap.roots.clone());
+        self.workspaces
+            .write()
+            .unwrap()
+            .replace(snap.workspaces.clone());
+        self.systems.write().unwrap().replace(snap.systems.clone());
+        self.entitys.write().unwrap().replace(snap.entitys.clone());
+        self.fields.write().unwrap().replace(snap.fields.clone());
+        self.features
+            .write()
+            .unwrap()
+            .replace(snap.features.clone());
+        self.files.write().unwrap().replace(snap.files.clone());
+        self.use_cases
+            .write()
+            .unwrap()
+            .replace(snap.use_cases.clone());
+        self.dtos.write().unwrap().replace(snap.dtos.clone());
+        self.dto_fields
+            .write()
+            .unwrap()
+            .replace(snap.dto_fields.clone());
+        self.globals.write().unwrap().replace(snap.globals.clone());
+        self.relationships
+            .write()
+            .unwrap()
+            .replace(snap.relationships.clone());
+        self.user_interfaces
+            .write()
+            .unwrap()
+            .replace(snap.user_interfaces.clone());
+        self.custom_files
+            .write()
+            .unwrap()
+            .replace(snap.custom_files.clone());
+        self.jn_system_from_root_system
+            .write()
+            .unwrap()
+            .replace(snap.jn_system_from_root_system.clone());
+        self.jn_workspace_from_root_workspace
+            .write()
+            .unwrap()
+            .replace(snap.jn_workspace_from_root_workspace.clone());
+        self.jn_custom_file_from_workspace_custom_files
+            .write()
+            .unwrap()
+            .replace(snap.jn_custom_file_from_workspace_custom_files.clone());
+        self.jn_entity_from_workspace_entities
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_workspace_entities.clone());
+        self.jn_feature_from_workspace_features
+            .write()
+            .unwrap()
+            .replace(snap.jn_feature_from_workspace_features.clone());
+        self.jn_global_from_workspace_global
+            .write()
+            .unwrap()
+            .replace(snap.jn_global_from_workspace_global.clone());
+        self.jn_user_interface_from_workspace_user_interface
+            .write()
+            .unwrap()
+            .replace(snap.jn_user_interface_from_workspace_user_interface.clone());
+        self.jn_file_from_system_files
+            .write()
+            .unwrap()
+            .replace(snap.jn_file_from_system_files.clone());
+        self.jn_field_from_entity_fields
+            .write()
+            .unwrap()
+            .replace(snap.jn_field_from_entity_fields.clone());
+        self.jn_entity_from_entity_inherits_from
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_entity_inherits_from.clone());
+        self.jn_relationship_from_entity_relationships
+            .write()
+            .unwrap()
+            .replace(snap.jn_relationship_from_entity_relationships.clone());
+        self.jn_entity_from_field_entity
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_field_entity.clone());
+        self.jn_dto_from_feature_dtos
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_feature_dtos.clone());
+        self.jn_use_case_from_feature_use_cases
+            .write()
+            .unwrap()
+            .replace(snap.jn_use_case_from_feature_use_cases.clone());
+        self.jn_entity_from_file_entity
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_file_entity.clone());
+        self.jn_feature_from_file_feature
+            .write()
+            .unwrap()
+            .replace(snap.jn_feature_from_file_feature.clone());
+        self.jn_field_from_file_field
+            .write()
+            .unwrap()
+            .replace(snap.jn_field_from_file_field.clone());
+        self.jn_use_case_from_file_use_case
+            .write()
+            .unwrap()
+            .replace(snap.jn_use_case_from_file_use_case.clone());
+        self.jn_dto_from_use_case_dto_in
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_use_case_dto_in.clone());
+        self.jn_dto_from_use_case_dto_out
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_use_case_dto_out.clone());
+        self.jn_entity_from_use_case_entities
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_use_case_entities.clone());
+        self.jn_dto_from_use_case_shared_dto_in
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_use_case_shared_dto_in.clone());
+        self.jn_dto_from_use_case_shared_dto_out
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_use_case_shared_dto_out.clone());
+        self.jn_dto_field_from_dto_fields
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_field_from_dto_fields.clone());
+        self.jn_dto_from_dto_field_dto
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_dto_field_dto.clone());
+        self.jn_entity_from_relationship_left_entity
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_relationship_left_entity.clone());
+        self.jn_entity_from_relationship_right_entity
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_relationship_right_entity.clone());
+        *self.idx_entity_name.write().unwrap() = snap.idx_entity_name.clone();
+        *self.idx_file_relative_path.write().unwrap() = snap.idx_file_relative_path.clone();
+        *self.counters.write().unwrap() = snap.counters.clone();
+    }
+
+    /// Create a savepoint by snapshotting the entire store. Returns a savepoint id.
+    /// O(1) thanks to im::HashMap structural sharing.
+    pub fn create_savepoint(&self) -> u64 {
+        let snap = self.snapshot();
+        let mut id_counter = self.next_savepoint_id.write().unwrap();
+        let id = *id_counter;
+        *id_counter += 1;
+        self.savepoints.write().unwrap().insert(id, snap);
+        id
+    }
+
+    /// Restore the store to a previously created savepoint.
+    pub fn restore_savepoint(&self, savepoint_id: u64) {
+        let snap = self
+            .savepoints
+            .read()
+            .unwrap()
+            .get(&savepoint_id)
+            .expect("savepoint not found")
+            .clone();
+        self.restore(&snap);
+    }
+
+    /// Discard a savepoint without restoring (used on successful commit).
+    pub fn discard_savepoint(&self, savepoint_id: u64) {
+        self.savepoints.write().unwrap().remove(&savepoint_id);
+    }
+
+    /// Rebuild the secondary indexes from the entity tables, e.g. after loading them.
+    pub fn rebuild_indexes(&self) {
+        {
+            let mut index: HashMap<String, Vec<EntityId>> = HashMap::new();
+            for entity in self.entitys.read().unwrap().values() {
+                index_insert_into(&mut index, entity.name.clone(), entity.id);
+            }
+            *self.idx_entity_name.write().unwrap() = index;
+        }
+        {
+            let mut index: HashMap<String, Vec<EntityId>> = HashMap::new();
+            for entity in self.files.read().unwrap().values() {
+                index_insert_into(&mut index, entity.relative_path.clone(), entity.id);
+            }
+            *self.idx_file_relative_path.write().unwrap() = index;
+        }
+    }
+
+    /// Get-and-increment counter for an entity type.
+    pub(crate) fn next_id(&self, entity_name: &str) -> EntityId {
+        let mut counters = self.counters.write().unwrap();
+        let counter = counters.entry(entity_name.to_string()).or_insert(1);
+        let id = *counter;
+        *counter += 1;
+        id
+    }
+
+    /// Restore entity and junction data but preserve current counters.
+    /// Used for undo snapshots where IDs must remain monotonically increasing.
+    pub fn restore_without_counters(&self, snap: &HashMapStoreSnapshot) {
+        self.roots.write().unwrap().replace(snap.roots.clone());
+        self.workspaces
+            .write()
+            .unwrap()
+            .replace(snap.workspaces.clone());
+        self.systems.write().unwrap().replace(snap.systems.clone());
+        self.entitys.write().unwrap().replace(snap.entitys.clone());
+        self.fields.write().unwrap().replace(snap.fields.clone());
+        self.features
+            .write()
+            .unwrap()
+            .replace(snap.features.clone());
+        self.files.write().unwrap().replace(snap.files.clone());
+        self.use_cases
+            .write()
+            .unwrap()
+            .replace(snap.use_cases.clone());
+        self.dtos.write().unwrap().replace(snap.dtos.clone());
+        self.dto_fields
+            .write()
+            .unwrap()
+            .replace(snap.dto_fields.clone());
+        self.globals.write().unwrap().replace(snap.globals.clone());
+        self.relationships
+            .write()
+            .unwrap()
+            .replace(snap.relationships.clone());
+        self.user_interfaces
+            .write()
+            .unwrap()
+            .replace(snap.user_interfaces.clone());
+        self.custom_files
+            .write()
+            .unwrap()
+            .replace(snap.custom_files.clone());
+        self.jn_system_from_root_system
+            .write()
+            .unwrap()
+            .replace(snap.jn_system_from_root_system.clone());
+        self.jn_workspace_from_root_workspace
+            .write()
+            .unwrap()
+            .replace(snap.jn_workspace_from_root_workspace.clone());
+        self.jn_custom_file_from_workspace_custom_files
+            .write()
+            .unwrap()
+            .replace(snap.jn_custom_file_from_workspace_custom_files.clone());
+        self.jn_entity_from_workspace_entities
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_workspace_entities.clone());
+        self.jn_feature_from_workspace_features
+            .write()
+            .unwrap()
+            .replace(snap.jn_feature_from_workspace_features.clone());
+        self.jn_global_from_workspace_global
+            .write()
+            .unwrap()
+            .replace(snap.jn_global_from_workspace_global.clone());
+        self.jn_user_interface_from_workspace_user_interface
+            .write()
+            .unwrap()
+            .replace(snap.jn_user_interface_from_workspace_user_interface.clone());
+        self.jn_file_from_system_files
+            .write()
+            .unwrap()
+            .replace(snap.jn_file_from_system_files.clone());
+        self.jn_field_from_entity_fields
+            .write()
+            .unwrap()
+            .replace(snap.jn_field_from_entity_fields.clone());
+        self.jn_entity_from_entity_inherits_from
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_entity_inherits_from.clone());
+        self.jn_relationship_from_entity_relationships
+            .write()
+            .unwrap()
+            .replace(snap.jn_relationship_from_entity_relationships.clone());
+        self.jn_entity_from_field_entity
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_field_entity.clone());
+        self.jn_dto_from_feature_dtos
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_feature_dtos.clone());
+        self.jn_use_case_from_feature_use_cases
+            .write()
+            .unwrap()
+            .replace(snap.jn_use_case_from_feature_use_cases.clone());
+        self.jn_entity_from_file_entity
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_file_entity.clone());
+        self.jn_feature_from_file_feature
+            .write()
+            .unwrap()
+            .replace(snap.jn_feature_from_file_feature.clone());
+        self.jn_field_from_file_field
+            .write()
+            .unwrap()
+            .replace(snap.jn_field_from_file_field.clone());
+        self.jn_use_case_from_file_use_case
+            .write()
+            .unwrap()
+            .replace(snap.jn_use_case_from_file_use_case.clone());
+        self.jn_dto_from_use_case_dto_in
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_use_case_dto_in.clone());
+        self.jn_dto_from_use_case_dto_out
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_use_case_dto_out.clone());
+        self.jn_entity_from_use_case_entities
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_use_case_entities.clone());
+        self.jn_dto_from_use_case_shared_dto_in
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_use_case_shared_dto_in.clone());
+        self.jn_dto_from_use_case_shared_dto_out
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_use_case_shared_dto_out.clone());
+        self.jn_dto_field_from_dto_fields
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_field_from_dto_fields.clone());
+        self.jn_dto_from_dto_field_dto
+            .write()
+            .unwrap()
+            .replace(snap.jn_dto_from_dto_field_dto.clone());
+        self.jn_entity_from_relationship_left_entity
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_relationship_left_entity.clone());
+        self.jn_entity_from_relationship_right_entity
+            .write()
+            .unwrap()
+            .replace(snap.jn_entity_from_relationship_right_entity.clone());
+        *self.idx_entity_name.write().unwrap() = snap.idx_entity_name.clone();
+        *self.idx_file_relative_path.write().unwrap() = snap.idx_file_relative_path.clone();
+        // counters intentionally NOT restored — IDs must remain monotonically increasing
+    }
+
+    /// Create a type-erased store snapshot for undo.
+    pub fn store_snapshot(&self) -> StoreSnapshot {
+        StoreSnapshot::new(self.snapshot())
+    }
+
+    /// Restore from a type-erased store snapshot (preserves counters).
+    pub fn restore_store_snapshot(&self, snap: &StoreSnapshot) {
+        let s = snap
+            .downcast_ref::<HashMapStoreSnapshot>()
+            .expect("StoreSnapshot must contain HashMapStoreSnapshot");
+        self.restore_without_counters(s);
+    }
+
+    /// Open a change journal. From now on, the tables remember the ids written,
+    /// and the journal receives the rows changed since this call when
+    /// `close_journals` commits. O(1) to open.
+    pub fn open_journal(&self) -> StoreJournal {
+        let mut journals = self.journals.write().unwrap();
+        if journals.is_empty() {
+            self.roots.write().unwrap().start_journal();
+            self.workspaces.write().unwrap().start_journal();
+            self.systems.write().unwrap().start_journal();
+            self.entitys.write().unwrap().start_journal();
+            self.fields.write().unwrap().start_journal();
+            self.features.write().unwrap().start_journal();
+            self.files.write().unwrap().start_journal();
+            self.use_cases.write().unwrap().start_journal();
+            self.dtos.write().unwrap().start_journal();
+            self.dto_fields.write().unwrap().start_journal();
+            self.globals.write().unwrap().start_journal();
+            self.relationships.write().unwrap().start_journal();
+            self.user_interfaces.write().unwrap().start_journal();
+            self.custom_files.write().unwrap().start_journal();
+            self.jn_system_from_root_system
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_workspace_from_root_workspace
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_custom_file_from_workspace_custom_files
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_entity_from_workspace_entities
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_feature_from_workspace_features
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_global_from_workspace_global
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_user_interface_from_workspace_user_interface
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_file_from_system_files
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_field_from_entity_fields
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_entity_from_entity_inherits_from
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_relationship_from_entity_relationships
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_entity_from_field_entity
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_dto_from_feature_dtos
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_use_case_from_feature_use_cases
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_entity_from_file_entity
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_feature_from_file_feature
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_field_from_file_field
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_use_case_from_file_use_case
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_dto_from_use_case_dto_in
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_dto_from_use_case_dto_out
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_entity_from_use_case_entities
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_dto_from_use_case_shared_dto_in
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_dto_from_use_case_shared_dto_out
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_dto_field_from_dto_fields
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_dto_from_dto_field_dto
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_entity_from_relationship_left_entity
+                .write()
+                .unwrap()
+                .start_journal();
+            self.jn_entity_from_relationship_right_entity
+                .write()
+                .unwrap()
+                .start_journal();
+        }
+        let journal = StoreJournal::default();
+        journals.push((self.snapshot(), journal.clone()));
+        journal
+    }
+
+    /// Close the open change journals. On commit, each one receives the rows
+    /// changed since it was opened; otherwise they are left empty.
+    pub fn close_journals(&self, commit: bool) {
+        let journals = std::mem::take(&mut *self.journals.write().unwrap());
+        if journals.is_empty() {
+            return;
+        }
+        if commit {
+            for (before, journal) in &journals {
+                journal.resolve(StoreSnapshot::new(self.delta_since(before)));
+            }
+        }
+        self.roots.write().unwrap().stop_journal();
+        self.workspaces.write().unwrap().stop_journal();
+        self.systems.write().unwrap().stop_journal();
+        self.entitys.write().unwrap().stop_journal();
+        self.fields.write().unwrap().stop_journal();
+        self.features.write().unwrap().stop_journal();
+        self.files.write().unwrap().stop_journal();
+        self.use_cases.write().unwrap().stop_journal();
+        self.dtos.write().unwrap().stop_journal();
+        self.dto_fields.write().unwrap().stop_journal();
+        self.globals.write().unwrap().stop_journal();
+        self.relationships.write().unwrap().stop_journal();
+        self.user_interfaces.write().unwrap().stop_journal();
+        self.custom_files.write().unwrap().stop_journal();
+        self.jn_system_from_root_system
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_workspace_from_root_workspace
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_custom_file_from_workspace_custom_files
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_entity_from_workspace_entities
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_feature_from_workspace_features
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_global_from_workspace_global
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_user_interface_from_workspace_user_interface
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_file_from_system_files
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_field_from_entity_fields
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_entity_from_entity_inherits_from
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_relationship_from_entity_relationships
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_entity_from_field_entity
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_dto_from_feature_dtos
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_use_case_from_feature_use_cases
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_entity_from_file_entity
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_feature_from_file_feature
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_field_from_file_field
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_use_case_from_file_use_case
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_dto_from_use_case_dto_in
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_dto_from_use_case_dto_out
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_entity_from_use_case_entities
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_dto_from_use_case_shared_dto_in
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_dto_from_use_case_shared_dto_out
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_dto_field_from_dto_fields
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_dto_from_dto_field_dto
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_entity_from_relationship_left_entity
+            .write()
+            .unwrap()
+            .stop_journal();
+        self.jn_entity_from_relationship_right_entity
+            .write()
+            .unwrap()
+            .stop_journal();
+    }
+
+    /// Rows written while the journals were open and changed since `before`.
+    fn delta_since(&self, before: &HashMapStoreSnapshot) -> HashMapStoreDelta {
+        HashMapStoreDelta {
+            roots: self.roots.read().unwrap().delta(&before.roots),
+            workspaces: self.workspaces.read().unwrap().delta(&before.workspaces),
+            systems: self.systems.read().unwrap().delta(&before.systems),
+            entitys: self.entitys.read().unwrap().delta(&before.entitys),
+            fields: self.fields.read().unwrap().delta(&before.fields),
+            features: self.features.read().unwrap().delta(&before.features),
+            files: self.files.read().unwrap().delta(&before.files),
+            use_cases: self.use_cases.read().unwrap().delta(&before.use_cases),
+            dtos: self.dtos.read().unwrap().delta(&before.dtos),
+            dto_fields: self.dto_fields.read().unwrap().delta(&before.dto_fields),
+            globals: self.globals.read().unwrap().delta(&before.globals),
+            relationships: self
+                .relationships
+                .read()
+                .unwrap()
+                .delta(&before.relationships),
+            user_interfaces: self
+                .user_interfaces
+                .read()
+                .unwrap()
+                .delta(&before.user_interfaces),
+            custom_files: self
+                .custom_files
+                .read()
+                .unwrap()
+                .delta(&before.custom_files),
+            jn_system_from_root_system: self
+                .jn_system_from_root_system
+                .read()
+                .unwrap()
+                .delta(&before.jn_system_from_root_system),
+            jn_workspace_from_root_workspace: self
+                .jn_workspace_from_root_workspace
+                .read()
+                .unwrap()
+                .delta(&before.jn_workspace_from_root_workspace),
+            jn_custom_file_from_workspace_custom_files: self
+                .jn_custom_file_from_workspace_custom_files
+                .read()
+                .unwrap()
+                .delta(&before.jn_custom_file_from_workspace_custom_files),
+            jn_entity_from_workspace_entities: self
+                .jn_entity_from_workspace_entities
+                .read()
+                .unwrap()
+                .delta(&before.jn_entity_from_workspace_entities),
+            jn_feature_from_workspace_features: self
+                .jn_feature_from_workspace_features
+                .read()
+                .unwrap()
+                .delta(&before.jn_feature_from_workspace_features),
+            jn_global_from_workspace_global: self
+                .jn_global_from_workspace_global
+                .read()
+                .unwrap()
+                .delta(&before.jn_global_from_workspace_global),
+            jn_user_interface_from_workspace_user_interface: self
+                .jn_user_interface_from_workspace_user_interface
+                .read()
+                .unwrap()
+                .delta(&before.jn_user_interface_from_workspace_user_interface),
+            jn_file_from_system_files: self
+                .jn_file_from_system_files
+                .read()
+                .unwrap()
+                .delta(&before.jn_file_from_system_files),
+            jn_field_from_entity_fields: self
+                .jn_field_from_entity_fields
                 .read()
                 .unwrap()
-                .clone(),
-            jn_file_from_system_files: self.jn_file_from_system_files.read().unwrap().clone(),
-            jn_field_from_entity_fields: self.jn_field_from_entity_fields.read().unwrap().clone(),
+                .delta(&before.jn_field_from_entity_fields),
             jn_entity_from_entity_inherits_from: self
                 .jn_entity_from_entity_inherits_from
                 .read()
                 .unwrap()
-                .clone(),
+                .delta(&before.jn_entity_from_entity_inherits_from),
             jn_relationship_from_entity_relationships: self
                 .jn_relationship_from_entity_relationships
                 .read()
                 .unwrap()
-                .clone(),
-            jn_entity_from_field_entity: self.jn_entity_from_field_entity.read().unwrap().clone(),
+                .delta(&before.jn_relationship_from_entity_relationships),
+            jn_entity_from_field_entity: self
+                .jn_entity_from_field_entity
+                .read()
+                .unwrap()
+                .delta(&before.jn_entity_from_field_entity),
+            jn_dto_from_feature_dtos: self
+                .jn_dto_from_feature_dtos
+                .read()
+                .unwrap()
+                .delta(&before.jn_dto_from_feature_dtos),
             jn_use_case_from_feature_use_cases: self
                 .jn_use_case_from_feature_use_cases
                 .read()
                 .unwrap()
-                .clone(),
-            jn_entity_from_file_entity: self.jn_entity_from_file_entity.read().unwrap().clone(),
-            jn_feature_from_file_feature: self.jn_feature_from_file_feature.read().unwrap().clone(),
-            jn_field_from_file_field: self.jn_field_from_file_field.read().unwrap().clone(),
+                .delta(&before.jn_use_case_from_feature_use_cases),
+            jn_entity_from_file_entity: self
+                .jn_entity_from_file_entity
+                .read()
+                .unwrap()
+                .delta(&before.jn_entity_from_file_entity),
+            jn_feature_from_file_feature: self
+                .jn_feature_from_file_feature
+                .read()
+                .unwrap()
+                .delta(&before.jn_feature_from_file_feature),
+            jn_field_from_file_field: self
+                .jn_field_from_file_field
+                .read()
+                .unwrap()
+                .delta(&before.jn_field_from_file_field),
             jn_use_case_from_file_use_case: self
                 .jn_use_case_from_file_use_case
                 .read()
                 .unwrap()
-                .clone(),
-            jn_dto_from_use_case_dto_in: self.jn_dto_from_use_case_dto_in.read().unwrap().clone(),
-            jn_dto_from_use_case_dto_out: self.jn_dto_from_use_case_dto_out.read().unwrap().clone(),
+                .delta(&before.jn_use_case_from_file_use_case),
+            jn_dto_from_use_case_dto_in: self
+                .jn_dto_from_use_case_dto_in
+                .read()
+                .unwrap()
+                .delta(&before.jn_dto_from_use_case_dto_in),
+            jn_dto_from_use_case_dto_out: self
+                .jn_dto_from_use_case_dto_out
+                .read()
+                .unwrap()
+                .delta(&before.jn_dto_from_use_case_dto_out),
             jn_entity_from_use_case_entities: self
                 .jn_entity_from_use_case_entities
                 .read()
                 .unwrap()
-                .clone(),
-            jn_dto_field_from_dto_fields: self.jn_dto_field_from_dto_fields.read().unwrap().clone(),
+                .delta(&before.jn_entity_from_use_case_entities),
+            jn_dto_from_use_case_shared_dto_in: self
+                .jn_dto_from_use_case_shared_dto_in
+                .read()
+                .unwrap()
+                .delta(&before.jn_dto_from_use_case_shared_dto_in),
+            jn_dto_from_use_case_shared_dto_out: self
+                .jn_dto_from_use_case_shared_dto_out
+                .read()
+                .unwrap()
+                .delta(&before.jn_dto_from_use_case_shared_dto_out),
+            jn_dto_field_from_dto_fields: self
+                .jn_dto_field_from_dto_fields
+                .read()
+                .unwrap()
+                .delta(&before.jn_dto_field_from_dto_fields),
+            jn_dto_from_dto_field_dto: self
+                .jn_dto_from_dto_field_dto
+                .read()
+                .unwrap()
+                .delta(&before.jn_dto_from_dto_field_dto),
             jn_entity_from_relationship_left_entity: self
                 .jn_entity_from_relationship_left_entity
                 .read()
                 .unwrap()
-                .clone(),
+                .delta(&before.jn_entity_from_relationship_left_entity),
             jn_entity_from_relationship_right_entity: self
                 .jn_entity_from_relationship_right_entity
                 .read()
                 .unwrap()
-                .clone(),
-            counters: self.counters.read().unwrap().clone(),
+                .delta(&before.jn_entity_from_relationship_right_entity),
         }
     }
 
-    /// Restore from a savepoint snapshot.
-    pub fn restore(&self, snap: &HashMapStoreSnapshot) {
-        *self.roots.write().unwrap() = snap.roots.clone();
-        *self.workspaces.write().unwrap() = snap.workspaces.clone();
-        *self.systems.write().unwrap() = snap.systems.clone();
-        *self.entitys.write().unwrap() = snap.entitys.clone();
-        *self.fields.write().unwrap() = snap.fields.clone();
-        *self.features.write().unwrap() = snap.features.clone();
-        *self.files.write().unwrap() = snap.files.clone();
-        *self.use_cases.write().unwrap() = snap.use_cases.clone();
-        *self.dtos.write().unwrap() = snap.dtos.clone();
-        *self.dto_fields.write().unwrap() = snap.dto_fields.clone();
-        *self.globals.write().unwrap() = snap.globals.clone();
-        *self.relationships.write().unwrap() = snap.relationships.clone();
-        *self.user_interfaces.write().unwrap() = snap.user_interfaces.clone();
-        *self.jn_system_from_root_system.write().unwrap() = snap.jn_system_from_root_system.clone();
-        *self.jn_workspace_from_root_workspace.write().unwrap() =
-            snap.jn_workspace_from_root_workspace.clone();
-        *self.jn_entity_from_workspace_entities.write().unwrap() =
-            snap.jn_entity_from_workspace_entities.clone();
-        *self.jn_feature_from_workspace_features.write().unwrap() =
-            snap.jn_feature_from_workspace_features.clone();
-        *self.jn_global_from_workspace_global.write().unwrap() =
-            snap.jn_global_from_workspace_global.clone();
-        *self
-            .jn_user_interface_from_workspace_user_interface
-            .write()
-            .unwrap() = snap.jn_user_interface_from_workspace_user_interface.clone();
-        *self.jn_file_from_system_files.write().unwrap() = snap.jn_file_from_system_files.clone();
-        *self.jn_field_from_entity_fields.write().unwrap() =
-            snap.jn_field_from_entity_fields.clone();
-        *self.jn_entity_from_entity_inherits_from.write().unwrap() =
-            snap.jn_entity_from_entity_inherits_from.clone();
-        *self
-            .jn_relationship_from_entity_relationships
-            .write()
-            .unwrap() = snap.jn_relationship_from_entity_relationships.clone();
-        *self.jn_entity_from_field_entity.write().unwrap() =
-            snap.jn_entity_from_field_entity.clone();
-        *self.jn_use_case_from_feature_use_cases.write().unwrap() =
-            snap.jn_use_case_from_feature_use_cases.clone();
-        *self.jn_entity_from_file_entity.write().unwrap() = snap.jn_entity_from_file_entity.clone();
-        *self.jn_feature_from_file_feature.write().unwrap() =
-            snap.jn_feature_from_file_feature.clone();
-        *self.jn_field_from_file_field.write().unwrap() = snap.jn_field_from_file_field.clone();
-        *self.jn_use_case_from_file_use_case.write().unwrap() =
-            snap.jn_use_case_from_file_use_case.clone();
-        *self.jn_dto_from_use_case_dto_in.write().unwrap() =
-            snap.jn_dto_from_use_case_dto_in.clone();
-        *self.jn_dto_from_use_case_dto_out.write().unwrap() =
-            snap.jn_dto_from_use_case_dto_out.clone();
-        *self.jn_entity_from_use_case_entities.write().unwrap() =
-            snap.jn_entity_from_use_case_entities.clone();
-        *self.jn_dto_field_from_dto_fields.write().unwrap() =
-            snap.jn_dto_field_from_dto_fields.clone();
-        *self
-            .jn_entity_from_relationship_left_entity
-            .write()
-            .unwrap() = snap.jn_entity_from_relationship_left_entity.clone();
-        *self
-            .jn_entity_from_relationship_right_entity
-            .write()
-            .unwrap() = snap.jn_entity_from_relationship_right_entity.clone();
-        *self.counters.write().unwrap() = snap.counters.clone();
+    /// Put the rows of `delta` back to their values before the change, keeping
+    /// the secondary indexes in step. Counters are left alone. O(rows in delta).
+    pub fn undo_delta(&self, delta: &HashMapStoreDelta) {
+        undo_rows(&self.roots, &delta.roots);
+        undo_rows(&self.workspaces, &delta.workspaces);
+        undo_rows(&self.systems, &delta.systems);
+        {
+            let map = self.entitys.read().unwrap();
+            for row in &delta.entitys {
+                if let Some(current) = map.get(&row.id) {
+                    index_remove(&self.idx_entity_name, &current.name, &row.id);
+                }
+            }
+        }
+        undo_rows(&self.entitys, &delta.entitys);
+        for row in &delta.entitys {
+            if let Some(before) = &row.before {
+                index_insert(&self.idx_entity_name, before.name.clone(), row.id);
+            }
+        }
+        undo_rows(&self.fields, &delta.fields);
+        undo_rows(&self.features, &delta.features);
+        {
+            let map = self.files.read().unwrap();
+            for row in &delta.files {
+                if let Some(current) = map.get(&row.id) {
+                    index_remove(
+                        &self.idx_file_relative_path,
+                        &current.relative_path,
+                        &row.id,
+                    );
+                }
+            }
+        }
+        undo_rows(&self.files, &delta.files);
+        for row in &delta.files {
+            if let Some(before) = &row.before {
+                index_insert(
+                    &self.idx_file_relative_path,
+                    before.relative_path.clone(),
+                    row.id,
+                );
+            }
+        }
+        undo_rows(&self.use_cases, &delta.use_cases);
+        undo_rows(&self.dtos, &delta.dtos);
+        undo_rows(&self.dto_fields, &delta.dto_fields);
+        undo_rows(&self.globals, &delta.globals);
+        undo_rows(&self.relationships, &delta.relationships);
+        undo_rows(&self.user_interfaces, &delta.user_interfaces);
+        undo_rows(&self.custom_files, &delta.custom_files);
+        undo_rows(
+            &self.jn_system_from_root_system,
+            &delta.jn_system_from_root_system,
+        );
+        undo_rows(
+            &self.jn_workspace_from_root_workspace,
+            &delta.jn_workspace_from_root_workspace,
+        );
+        undo_rows(
+            &self.jn_custom_file_from_workspace_custom_files,
+            &delta.jn_custom_file_from_workspace_custom_files,
+        );
+        undo_rows(
+            &self.jn_entity_from_workspace_entities,
+            &delta.jn_entity_from_workspace_entities,
+        );
+        undo_rows(
+            &self.jn_feature_from_workspace_features,
+            &delta.jn_feature_from_workspace_features,
+        );
+        undo_rows(
+            &self.jn_global_from_workspace_global,
+            &delta.jn_global_from_workspace_global,
+        );
+        undo_rows(
+            &self.jn_user_interface_from_workspace_user_interface,
+            &delta.jn_user_interface_from_workspace_user_interface,
+        );
+        undo_rows(
+            &self.jn_file_from_system_files,
+            &delta.jn_file_from_system_files,
+        );
+        undo_rows(
+            &self.jn_field_from_entity_fields,
+            &delta.jn_field_from_entity_fields,
+        );
+        undo_rows(
+            &self.jn_entity_from_entity_inherits_from,
+            &delta.jn_entity_from_entity_inherits_from,
+        );
+        undo_rows(
+            &self.jn_relationship_from_entity_relationships,
+            &delta.jn_relationship_from_entity_relationships,
+        );
+        undo_rows(
+            &self.jn_entity_from_field_entity,
+            &delta.jn_entity_from_field_entity,
+        );
+        undo_rows(
+            &self.jn_dto_from_feature_dtos,
+            &delta.jn_dto_from_feature_dtos,
+        );
+        undo_rows(
+            &self.jn_use_case_from_feature_use_cases,
+            &delta.jn_use_case_from_feature_use_cases,
+        );
+        undo_rows(
+            &self.jn_entity_from_file_entity,
+            &delta.jn_entity_from_file_entity,
+        );
+        undo_rows(
+            &self.jn_feature_from_file_feature,
+            &delta.jn_feature_from_file_feature,
+        );
+        undo_rows(
+            &self.jn_field_from_file_field,
+            &delta.jn_field_from_file_field,
+        );
+        undo_rows(
+            &self.jn_use_case_from_file_use_case,
+            &delta.jn_use_case_from_file_use_case,
+        );
+        undo_rows(
+            &self.jn_dto_from_use_case_dto_in,
+            &delta.jn_dto_from_use_case_dto_in,
+        );
+        undo_rows(
+            &self.jn_dto_from_use_case_dto_out,
+            &delta.jn_dto_from_use_case_dto_out,
+        );
+        undo_rows(
+            &self.jn_entity_from_use_case_entities,
+            &delta.jn_entity_from_use_case_entities,
+        );
+        undo_rows(
+            &self.jn_dto_from_use_case_shared_dto_in,
+            &delta.jn_dto_from_use_case_shared_dto_in,
+        );
+        undo_rows(
+            &self.jn_dto_from_use_case_shared_dto_out,
+            &delta.jn_dto_from_use_case_shared_dto_out,
+        );
+        undo_rows(
+            &self.jn_dto_field_from_dto_fields,
+            &delta.jn_dto_field_from_dto_fields,
+        );
+        undo_rows(
+            &self.jn_dto_from_dto_field_dto,
+            &delta.jn_dto_from_dto_field_dto,
+        );
+        undo_rows(
+            &self.jn_entity_from_relationship_left_entity,
+            &delta.jn_entity_from_relationship_left_entity,
+        );
+        undo_rows(
+            &self.jn_entity_from_relationship_right_entity,
+            &delta.jn_entity_from_relationship_right_entity,
+        );
     }
 
-    /// Create a savepoint by snapshotting the entire store. Returns a savepoint id.
-    /// O(1) thanks to im::HashMap structural sharing.
-    pub fn create_savepoint(&self) -> u64 {
-        let snap = self.snapshot();
-        let mut id_counter = self.next_savepoint_id.write().unwrap();
-        let id = *id_counter;
-        *id_counter += 1;
-        self.savepoints.write().unwrap().insert(id, snap);
-        id
+    /// Undo the rows recorded by `journal` and return the events describing the
+    /// change. Does nothing if the journal was never committed.
+    pub fn undo_journal(&self, journal: &StoreJournal) -> Vec<Event> {
+        let Some(delta) = journal.delta() else {
+            return Vec::new();
+        };
+        let delta = delta
+            .downcast_ref::<HashMapStoreDelta>()
+            .expect("StoreJournal must contain HashMapStoreDelta");
+        self.undo_delta(delta);
+        delta.undo_events()
     }
+}
 
-    /// Restore the store to a previously created savepoint.
-    pub fn restore_savepoint(&self, savepoint_id: u64) {
-        let snap = self
-            .savepoints
-            .read()
-            .unwrap()
-            .get(&savepoint_id)
-            .expect("savepoint not found")
-            .clone();
-        self.restore(&snap);
+/// Snapshot of the entire store. O(1) to create thanks to im::HashMap structural sharing.
+#[derive(Debug, Clone, Default)]
+pub struct HashMapStoreSnapshot {
+    pub(crate) roots: HashMap<EntityId, Root>,
+    pub(crate) workspaces: HashMap<EntityId, Workspace>,
+    pub(crate) systems: HashMap<EntityId, System>,
+    pub(crate) entitys: HashMap<EntityId, Entity>,
+    pub(crate) fields: HashMap<EntityId, Field>,
+    pub(crate) features: HashMap<EntityId, Feature>,
+    pub(crate) files: HashMap<EntityId, File>,
+    pub(crate) use_cases: HashMap<EntityId, UseCase>,
+    pub(crate) dtos: HashMap<EntityId, Dto>,
+    pub(crate) dto_fields: HashMap<EntityId, DtoField>,
+    pub(crate) globals: HashMap<EntityId, Global>,
+    pub(crate) relationships: HashMap<EntityId, Relationship>,
+    pub(crate) user_interfaces: HashMap<EntityId, UserInterface>,
+    pub(crate) custom_files: HashMap<EntityId, CustomFile>,
+    pub(crate) jn_system_from_root_system: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_workspace_from_root_workspace: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_custom_file_from_workspace_custom_files: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_entity_from_workspace_entities: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_feature_from_workspace_features: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_global_from_workspace_global: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_user_interface_from_workspace_user_interface: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_file_from_system_files: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_field_from_entity_fields: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_entity_from_entity_inherits_from: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_relationship_from_entity_relationships: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_entity_from_field_entity: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_dto_from_feature_dtos: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_use_case_from_feature_use_cases: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_entity_from_file_entity: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_feature_from_file_feature: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_field_from_file_field: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_use_case_from_file_use_case: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_dto_from_use_case_dto_in: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_dto_from_use_case_dto_out: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_entity_from_use_case_entities: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_dto_from_use_case_shared_dto_in: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_dto_from_use_case_shared_dto_out: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_dto_field_from_dto_fields: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_dto_from_dto_field_dto: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_entity_from_relationship_left_entity: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) jn_entity_from_relationship_right_entity: HashMap<EntityId, Vec<EntityId>>,
+    pub(crate) idx_entity_name: HashMap<String, Vec<EntityId>>,
+    pub(crate) idx_file_relative_path: HashMap<String, Vec<EntityId>>,
+    pub(crate) counters: std::collections::HashMap<String, EntityId>,
+}
+
+impl StoreSnapshotTrait for HashMapStoreSnapshot {
+    fn clone_box(&self) -> Box<dyn StoreSnapshotTrait> {
+        Box::new(self.clone())
     }
 
-    /// Discard a savepoint without restoring (used on successful commit).
-    pub fn discard_savepoint(&self, savepoint_id: u64) {
-        self.savepoints.write().unwrap().remove(&savepoint_id);
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
     }
+}
 
-    /// Get-and-increment counter for an entity type.
-    pub(crate) fn next_id(&self, entity_name: &str) -> EntityId {
-        let mut counters = self.counters.write().unwrap();
-        let counter = counters.entry(entity_name.to_string()).or_insert(1);
-        let id = *counter;
-        *counter += 1;
-        id
+// ─────────────────────────────────────────────────────────────────────────────
+// Change journal
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A table of the store, keyed by entity id. Reads go through `Deref` to the
+/// underlying `im::HashMap`; `insert` and `remove` also remember the id while a
+/// change journal is open.
+#[derive(Debug, Clone)]
+pub struct JournaledMap<V: Clone> {
+    map: HashMap<EntityId, V>,
+    touched: Option<std::collections::HashSet<EntityId>>,
+}
+
+impl<V: Clone> Default for JournaledMap<V> {
+    fn default() -> Self {
+        Self {
+            map: HashMap::new(),
+            touched: None,
+        }
     }
+}
 
-    /// Restore entity and junction data but preserve current counters.
-    /// Used for undo snapshots where IDs must remain monotonically increasing.
-    pub fn restore_without_counters(&self, snap: &HashMapStoreSnapshot) {
-        *self.roots.write().unwrap() = snap.roots.clone();
-        *self.workspaces.write().unwrap() = snap.workspaces.clone();
-        *self.systems.write().unwrap() = snap.systems.clone();
-        *self.entitys.write().unwrap() = snap.entitys.clone();
-        *self.fields.write().unwrap() = snap.fields.clone();
-        *self.features.write().unwrap() = snap.features.clone();
-        *self.files.write().unwrap() = snap.files.clone();
-        *self.use_cases.write().unwrap() = snap.use_cases.clone();
-        *self.dtos.write().unwrap() = snap.dtos.clone();
-        *self.dto_fields.write().unwrap() = snap.dto_fields.clone();
-        *self.globals.write().unwrap() = snap.globals.clone();
-        *self.relationships.write().unwrap() = snap.relationships.clone();
-        *self.user_interfaces.write().unwrap() = snap.user_interfaces.clone();
-        *self.jn_system_from_root_system.write().unwrap() = snap.jn_system_from_root_system.clone();
-        *self.jn_workspace_from_root_workspace.write().unwrap() =
-            snap.jn_workspace_from_root_workspace.clone();
-        *self.jn_entity_from_workspace_entities.write().unwrap() =
-            snap.jn_entity_from_workspace_entities.clone();
-        *self.jn_feature_from_workspace_features.write().unwrap() =
-            snap.jn_feature_from_workspace_features.clone();
-        *self.jn_global_from_workspace_global.write().unwrap() =
-            snap.jn_global_from_workspace_global.clone();
-        *self
-            .jn_user_interface_from_workspace_user_interface
-            .write()
-            .unwrap() = snap.jn_user_interface_from_workspace_user_interface.clone();
-        *self.jn_file_from_system_files.write().unwrap() = snap.jn_file_from_system_files.clone();
-        *self.jn_field_from_entity_fields.write().unwrap() =
-            snap.jn_field_from_entity_fields.clone();
-        *self.jn_entity_from_entity_inherits_from.write().unwrap() =
-            snap.jn_entity_from_entity_inherits_from.clone();
-        *self
-            .jn_relationship_from_entity_relationships
-            .write()
-            .unwrap() = snap.jn_relationship_from_entity_relationships.clone();
-        *self.jn_entity_from_field_entity.write().unwrap() =
-            snap.jn_entity_from_field_entity.clone();
-        *self.jn_use_case_from_feature_use_cases.write().unwrap() =
-            snap.jn_use_case_from_feature_use_cases.clone();
-        *self.jn_entity_from_file_entity.write().unwrap() = snap.jn_entity_from_file_entity.clone();
-        *self.jn_feature_from_file_feature.write().unwrap() =
-            snap.jn_feature_from_file_feature.clone();
-        *self.jn_field_from_file_field.write().unwrap() = snap.jn_field_from_file_field.clone();
-        *self.jn_use_case_from_file_use_case.write().unwrap() =
-            snap.jn_use_case_from_file_use_case.clone();
-        *self.jn_dto_from_use_case_dto_in.write().unwrap() =
-            snap.jn_dto_from_use_case_dto_in.clone();
-        *self.jn_dto_from_use_case_dto_out.write().unwrap() =
-            snap.jn_dto_from_use_case_dto_out.clone();
-        *self.jn_entity_from_use_case_entities.write().unwrap() =
-            snap.jn_entity_from_use_case_entities.clone();
-        *self.jn_dto_field_from_dto_fields.write().unwrap() =
-            snap.jn_dto_field_from_dto_fields.clone();
-        *self
-            .jn_entity_from_relationship_left_entity
-            .write()
-            .unwrap() = snap.jn_entity_from_relationship_left_entity.clone();
-        *self
-            .jn_entity_from_relationship_right_entity
-            .write()
-            .unwrap() = snap.jn_entity_from_relationship_right_entity.clone();
-        // counters intentionally NOT restored — IDs must remain monotonically increasing
+impl<V: Clone> From<HashMap<EntityId, V>> for JournaledMap<V> {
+    fn from(map: HashMap<EntityId, V>) -> Self {
+        Self { map, touched: None }
     }
+}
 
-    /// Create a type-erased store snapshot for undo.
-    pub fn store_snapshot(&self) -> StoreSnapshot {
-        StoreSnapshot::new(self.snapshot())
+impl<V: Clone> Deref for JournaledMap<V> {
+    type Target = HashMap<EntityId, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
     }
+}
 
-    /// Restore from a type-erased store snapshot (preserves counters).
-    pub fn restore_store_snapshot(&self, snap: &StoreSnapshot) {
-        let s = snap
-            .downcast_ref::<HashMapStoreSnapshot>()
-            .expect("StoreSnapshot must contain HashMapStoreSnapshot");
-        self.restore_without_counters(s);
+impl<V: Clone> JournaledMap<V> {
+    pub fn insert(&mut self, id: EntityId, value: V) -> Option<V> {
+        if let Some(touched) = &mut self.touched {
+            touched.insert(id);
+        }
+        self.map.insert(id, value)
+    }
+
+    pub fn remove(&mut self, id: &EntityId) -> Option<V> {
+        if let Some(touched) = &mut self.touched {
+            touched.insert(*id);
+        }
+        self.map.remove(id)
+    }
+
+    /// Replace the whole table, e.g. to restore a snapshot. With a journal open,
+    /// every id of the previous and the new table counts as written.
+    fn replace(&mut self, map: HashMap<EntityId, V>) {
+        if let Some(touched) = &mut self.touched
+            && !self.map.ptr_eq(&map)
+        {
+            touched.extend(self.map.keys().copied());
+            touched.extend(map.keys().copied());
+        }
+        self.map = map;
+    }
+
+    fn start_journal(&mut self) {
+        self.touched = Some(std::collections::HashSet::new());
+    }
+
+    fn stop_journal(&mut self) {
+        self.touched = None;
+    }
+
+    /// Rows written since the journal started whose value differs from `before`.
+    fn delta(&self, before: &HashMap<EntityId, V>) -> Vec<RowDelta<V>>
+    where
+        V: PartialEq,
+    {
+        let mut ids: Vec<EntityId> = self.touched.iter().flatten().copied().collect();
+        ids.sort_unstable();
+        ids.into_iter()
+            .filter_map(|id| {
+                let before = before.get(&id).cloned();
+                let after = self.map.get(&id).cloned();
+                (before != after).then_some(RowDelta { id, before, after })
+            })
+            .collect()
     }
 }
 
-/// Snapshot of the entire store. O(1) to create thanks to im::HashMap structural sharing.
+fn undo_rows<V: Clone>(table: &RwLock<JournaledMap<V>>, rows: &[RowDelta<V>]) {
+    let mut map = table.write().unwrap();
+    for row in rows {
+        match &row.before {
+            Some(value) => {
+                map.insert(row.id, value.clone());
+            }
+            None => {
+                map.remove(&row.id);
+            }
+        }
+    }
+}
+
+/// One row changed while a journal was open; `None` when the row did not exist.
 #[derive(Debug, Clone)]
-pub struct HashMapStoreSnapshot {
-    roots: HashMap<EntityId, Root>,
-    workspaces: HashMap<EntityId, Workspace>,
-    systems: HashMap<EntityId, System>,
-    entitys: HashMap<EntityId, Entity>,
-    fields: HashMap<EntityId, Field>,
-    features: HashMap<EntityId, Feature>,
-    files: HashMap<EntityId, File>,
-    use_cases: HashMap<EntityId, UseCase>,
-    dtos: HashMap<EntityId, Dto>,
-    dto_fields: HashMap<EntityId, DtoField>,
-    globals: HashMap<EntityId, Global>,
-    relationships: HashMap<EntityId, Relationship>,
-    user_interfaces: HashMap<EntityId, UserInterface>,
-    jn_system_from_root_system: HashMap<EntityId, Vec<EntityId>>,
-    jn_workspace_from_root_workspace: HashMap<EntityId, Vec<EntityId>>,
-    jn_entity_from_workspace_entities: HashMap<EntityId, Vec<EntityId>>,
-    jn_feature_from_workspace_features: HashMap<EntityId, Vec<EntityId>>,
-    jn_global_from_workspace_global: HashMap<EntityId, Vec<EntityId>>,
-    jn_user_interface_from_workspace_user_interface: HashMap<EntityId, Vec<EntityId>>,
-    jn_file_from_system_files: HashMap<EntityId, Vec<EntityId>>,
-    jn_field_from_entity_fields: HashMap<EntityId, Vec<EntityId>>,
-    jn_entity_from_entity_inherits_from: HashMap<EntityId, Vec<EntityId>>,
-    jn_relationship_from_entity_relationships: HashMap<EntityId, Vec<EntityId>>,
-    jn_entity_from_field_entity: HashMap<EntityId, Vec<EntityId>>,
-    jn_use_case_from_feature_use_cases: HashMap<EntityId, Vec<EntityId>>,
-    jn_entity_from_file_entity: HashMap<EntityId, Vec<EntityId>>,
-    jn_feature_from_file_feature: HashMap<EntityId, Vec<EntityId>>,
-    jn_field_from_file_field: HashMap<EntityId, Vec<EntityId>>,
-    jn_use_case_from_file_use_case: HashMap<EntityId, Vec<EntityId>>,
-    jn_dto_from_use_case_dto_in: HashMap<EntityId, Vec<EntityId>>,
-    jn_dto_from_use_case_dto_out: HashMap<EntityId, Vec<EntityId>>,
-    jn_entity_from_use_case_entities: HashMap<EntityId, Vec<EntityId>>,
-    jn_dto_field_from_dto_fields: HashMap<EntityId, Vec<EntityId>>,
-    jn_entity_from_relationship_left_entity: HashMap<EntityId, Vec<EntityId>>,
-    jn_entity_from_relationship_right_entity: HashMap<EntityId, Vec<EntityId>>,
-    counters: std::collections::HashMap<String, EntityId>,
+pub struct RowDelta<V> {
+    pub id: EntityId,
+    pub before: Option<V>,
+    pub after: Option<V>,
 }
 
-impl StoreSnapshotTrait for HashMapStoreSnapshot {
+/// Rows changed while a change journal was open, with their values before and
+/// after. Its size follows the number of rows written, not the size of the store.
+#[derive(Debug, Clone, Default)]
+pub struct HashMapStoreDelta {
+    pub roots: Vec<RowDelta<Root>>,
+    pub workspaces: Vec<RowDelta<Workspace>>,
+    pub systems: Vec<RowDelta<System>>,
+    pub entitys: Vec<RowDelta<Entity>>,
+    pub fields: Vec<RowDelta<Field>>,
+    pub features: Vec<RowDelta<Feature>>,
+    pub files: Vec<RowDelta<File>>,
+    pub use_cases: Vec<RowDelta<UseCase>>,
+    pub dtos: Vec<RowDelta<Dto>>,
+    pub dto_fields: Vec<RowDelta<DtoField>>,
+    pub globals: Vec<RowDelta<Global>>,
+    pub relationships: Vec<RowDelta<Relationship>>,
+    pub user_interfaces: Vec<RowDelta<UserInterface>>,
+    pub custom_files: Vec<RowDelta<CustomFile>>,
+    pub jn_system_from_root_system: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_workspace_from_root_workspace: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_custom_file_from_workspace_custom_files: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_entity_from_workspace_entities: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_feature_from_workspace_features: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_global_from_workspace_global: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_user_interface_from_workspace_user_interface: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_file_from_system_files: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_field_from_entity_fields: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_entity_from_entity_inherits_from: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_relationship_from_entity_relationships: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_entity_from_field_entity: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_dto_from_feature_dtos: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_use_case_from_feature_use_cases: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_entity_from_file_entity: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_feature_from_file_feature: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_field_from_file_field: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_use_case_from_file_use_case: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_dto_from_use_case_dto_in: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_dto_from_use_case_dto_out: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_entity_from_use_case_entities: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_dto_from_use_case_shared_dto_in: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_dto_from_use_case_shared_dto_out: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_dto_field_from_dto_fields: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_dto_from_dto_field_dto: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_entity_from_relationship_left_entity: Vec<RowDelta<Vec<EntityId>>>,
+    pub jn_entity_from_relationship_right_entity: Vec<RowDelta<Vec<EntityId>>>,
+}
+
+impl HashMapStoreDelta {
+    /// Number of changed rows, entity and junction rows alike.
+    pub fn len(&self) -> usize {
+        [
+            self.roots.len(),
+            self.workspaces.len(),
+            self.systems.len(),
+            self.entitys.len(),
+            self.fields.len(),
+            self.features.len(),
+            self.files.len(),
+            self.use_cases.len(),
+            self.dtos.len(),
+            self.dto_fields.len(),
+            self.globals.len(),
+            self.relationships.len(),
+            self.user_interfaces.len(),
+            self.custom_files.len(),
+            self.jn_system_from_root_system.len(),
+            self.jn_workspace_from_root_workspace.len(),
+            self.jn_custom_file_from_workspace_custom_files.len(),
+            self.jn_entity_from_workspace_entities.len(),
+            self.jn_feature_from_workspace_features.len(),
+            self.jn_global_from_workspace_global.len(),
+            self.jn_user_interface_from_workspace_user_interface.len(),
+            self.jn_file_from_system_files.len(),
+            self.jn_field_from_entity_fields.len(),
+            self.jn_entity_from_entity_inherits_from.len(),
+            self.jn_relationship_from_entity_relationships.len(),
+            self.jn_entity_from_field_entity.len(),
+            self.jn_dto_from_feature_dtos.len(),
+            self.jn_use_case_from_feature_use_cases.len(),
+            self.jn_entity_from_file_entity.len(),
+            self.jn_feature_from_file_feature.len(),
+            self.jn_field_from_file_field.len(),
+            self.jn_use_case_from_file_use_case.len(),
+            self.jn_dto_from_use_case_dto_in.len(),
+            self.jn_dto_from_use_case_dto_out.len(),
+            self.jn_entity_from_use_case_entities.len(),
+            self.jn_dto_from_use_case_shared_dto_in.len(),
+            self.jn_dto_from_use_case_shared_dto_out.len(),
+            self.jn_dto_field_from_dto_fields.len(),
+            self.jn_dto_from_dto_field_dto.len(),
+            self.jn_entity_from_relationship_left_entity.len(),
+            self.jn_entity_from_relationship_right_entity.len(),
+        ]
+        .iter()
+        .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Events describing the undo of the delta: rows coming back are `Created`,
+    /// rows going away `Removed`, and changed rows or relationships `Updated`.
+    pub fn undo_events(&self) -> Vec<Event> {
+        let mut events = Vec::new();
+        let mut emit = |entity: DirectAccessEntity, ids: Vec<EntityId>| {
+            if !ids.is_empty() {
+                events.push(Event {
+                    origin: Origin::DirectAccess(entity),
+                    ids,
+                    data: None,
+                });
+            }
+        };
+        {
+            let (created, mut updated, removed) = undo_ids(&self.roots);
+            emit(DirectAccessEntity::Root(EntityEvent::Created), created);
+            updated.extend(self.jn_system_from_root_system.iter().map(|row| row.id));
+            updated.extend(
+                self.jn_workspace_from_root_workspace
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::Root(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::Root(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.workspaces);
+            emit(DirectAccessEntity::Workspace(EntityEvent::Created), created);
+            updated.extend(
+                self.jn_custom_file_from_workspace_custom_files
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.extend(
+                self.jn_entity_from_workspace_entities
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.extend(
+                self.jn_feature_from_workspace_features
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.extend(
+                self.jn_global_from_workspace_global
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.extend(
+                self.jn_user_interface_from_workspace_user_interface
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::Workspace(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::Workspace(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.systems);
+            emit(DirectAccessEntity::System(EntityEvent::Created), created);
+            updated.extend(self.jn_file_from_system_files.iter().map(|row| row.id));
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::System(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::System(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.entitys);
+            emit(DirectAccessEntity::Entity(EntityEvent::Created), created);
+            updated.extend(self.jn_field_from_entity_fields.iter().map(|row| row.id));
+            updated.extend(
+                self.jn_entity_from_entity_inherits_from
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.extend(
+                self.jn_relationship_from_entity_relationships
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::Entity(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::Entity(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.fields);
+            emit(DirectAccessEntity::Field(EntityEvent::Created), created);
+            updated.extend(self.jn_entity_from_field_entity.iter().map(|row| row.id));
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::Field(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::Field(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.features);
+            emit(DirectAccessEntity::Feature(EntityEvent::Created), created);
+            updated.extend(self.jn_dto_from_feature_dtos.iter().map(|row| row.id));
+            updated.extend(
+                self.jn_use_case_from_feature_use_cases
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::Feature(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::Feature(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.files);
+            emit(DirectAccessEntity::File(EntityEvent::Created), created);
+            updated.extend(self.jn_entity_from_file_entity.iter().map(|row| row.id));
+            updated.extend(self.jn_feature_from_file_feature.iter().map(|row| row.id));
+            updated.extend(self.jn_field_from_file_field.iter().map(|row| row.id));
+            updated.extend(self.jn_use_case_from_file_use_case.iter().map(|row| row.id));
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::File(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::File(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.use_cases);
+            emit(DirectAccessEntity::UseCase(EntityEvent::Created), created);
+            updated.extend(self.jn_dto_from_use_case_dto_in.iter().map(|row| row.id));
+            updated.extend(self.jn_dto_from_use_case_dto_out.iter().map(|row| row.id));
+            updated.extend(
+                self.jn_entity_from_use_case_entities
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.extend(
+                self.jn_dto_from_use_case_shared_dto_in
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.extend(
+                self.jn_dto_from_use_case_shared_dto_out
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::UseCase(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::UseCase(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.dtos);
+            emit(DirectAccessEntity::Dto(EntityEvent::Created), created);
+            updated.extend(self.jn_dto_field_from_dto_fields.iter().map(|row| row.id));
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::Dto(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::Dto(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.dto_fields);
+            emit(DirectAccessEntity::DtoField(EntityEvent::Created), created);
+            updated.extend(self.jn_dto_from_dto_field_dto.iter().map(|row| row.id));
+            updated.sort_unstable();
+            updated.dedup();
+            emit(DirectAccessEntity::DtoField(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::DtoField(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, updated, removed) = undo_ids(&self.globals);
+            emit(DirectAccessEntity::Global(EntityEvent::Created), created);
+            emit(DirectAccessEntity::Global(EntityEvent::Updated), updated);
+            emit(DirectAccessEntity::Global(EntityEvent::Removed), removed);
+        }
+        {
+            let (created, mut updated, removed) = undo_ids(&self.relationships);
+            emit(
+                DirectAccessEntity::Relationship(EntityEvent::Created),
+                created,
+            );
+            updated.extend(
+                self.jn_entity_from_relationship_left_entity
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.extend(
+                self.jn_entity_from_relationship_right_entity
+                    .iter()
+                    .map(|row| row.id),
+            );
+            updated.sort_unstable();
+            updated.dedup();
+            emit(
+                DirectAccessEntity::Relationship(EntityEvent::Updated),
+                updated,
+            );
+            emit(
+                DirectAccessEntity::Relationship(EntityEvent::Removed),
+                removed,
+            );
+        }
+        {
+            let (created, updated, removed) = undo_ids(&self.user_interfaces);
+            emit(
+                DirectAccessEntity::UserInterface(EntityEvent::Created),
+                created,
+            );
+            emit(
+                DirectAccessEntity::UserInterface(EntityEvent::Updated),
+                updated,
+            );
+            emit(
+                DirectAccessEntity::UserInterface(EntityEvent::Removed),
+                removed,
+            );
+        }
+        {
+            let (created, updated, removed) = undo_ids(&self.custom_files);
+            emit(
+                DirectAccessEntity::CustomFile(EntityEvent::Created),
+                created,
+            );
+            emit(
+                DirectAccessEntity::CustomFile(EntityEvent::Updated),
+                updated,
+            );
+            emit(
+                DirectAccessEntity::CustomFile(EntityEvent::Removed),
+                removed,
+            );
+        }
+        events
+    }
+}
+
+/// Ids created, updated and removed by undoing `rows`.
+fn undo_ids<V>(rows: &[RowDelta<V>]) -> (Vec<EntityId>, Vec<EntityId>, Vec<EntityId>) {
+    let (mut created, mut updated, mut removed) = (Vec::new(), Vec::new(), Vec::new());
+    for row in rows {
+        match (&row.before, &row.after) {
+            (Some(_), None) => created.push(row.id),
+            (Some(_), Some(_)) => updated.push(row.id),
+            (None, _) => removed.push(row.id),
+        }
+    }
+    (created, updated, removed)
+}
+
+impl StoreSnapshotTrait for HashMapStoreDelta {
     fn clone_box(&self) -> Box<dyn StoreSnapshotTrait> {
         Box::new(self.clone())
     }
@@ -401,7 +1759,7 @@ impl StoreSnapshotTrait for HashMapStoreSnapshot {
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub(crate) fn delete_from_backward_junction(
-    junction: &RwLock<HashMap<EntityId, Vec<EntityId>>>,
+    junction: &RwLock<JournaledMap<Vec<EntityId>>>,
     id: &EntityId,
 ) {
     let mut jn = junction.write().unwrap();
@@ -418,7 +1776,7 @@ pub(crate) fn delete_from_backward_junction(
 }
 
 pub(crate) fn junction_get(
-    junction: &RwLock<HashMap<EntityId, Vec<EntityId>>>,
+    junction: &RwLock<JournaledMap<Vec<EntityId>>>,
     id: &EntityId,
 ) -> Vec<EntityId> {
     junction
@@ -430,19 +1788,19 @@ pub(crate) fn junction_get(
 }
 
 pub(crate) fn junction_set(
-    junction: &RwLock<HashMap<EntityId, Vec<EntityId>>>,
+    junction: &RwLock<JournaledMap<Vec<EntityId>>>,
     id: EntityId,
     ids: Vec<EntityId>,
 ) {
     junction.write().unwrap().insert(id, ids);
 }
 
-pub(crate) fn junction_remove(junction: &RwLock<HashMap<EntityId, Vec<EntityId>>>, id: &EntityId) {
+pub(crate) fn junction_remove(junction: &RwLock<JournaledMap<Vec<EntityId>>>, id: &EntityId) {
     junction.write().unwrap().remove(id);
 }
 
 pub(crate) fn junction_get_relationships_from_right_ids(
-    junction: &RwLock<HashMap<EntityId, Vec<EntityId>>>,
+    junction: &RwLock<JournaledMap<Vec<EntityId>>>,
     right_ids: &[EntityId],
 ) -> Vec<(EntityId, Vec<EntityId>)> {
     let jn = junction.read().unwrap();
@@ -452,8 +1810,47 @@ pub(crate) fn junction_get_relationships_from_right_ids(
         .collect()
 }
 
+/// Ids, in ascending order, of the entities whose indexed field equals `key`.
+pub(crate) fn index_get<K, Q>(index: &RwLock<HashMap<K, Vec<EntityId>>>, key: &Q) -> Vec<EntityId>
+where
+    K: IndexKey + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+{
+    index.read().unwrap().get(key).cloned().unwrap_or_default()
+}
+
+fn index_insert_into<K: IndexKey>(index: &mut HashMap<K, Vec<EntityId>>, key: K, id: EntityId) {
+    let ids = index.entry(key).or_default();
+    if let Err(position) = ids.binary_search(&id) {
+        ids.insert(position, id);
+    }
+}
+
+pub(crate) fn index_insert<K: IndexKey>(
+    index: &RwLock<HashMap<K, Vec<EntityId>>>,
+    key: K,
+    id: EntityId,
+) {
+    index_insert_into(&mut index.write().unwrap(), key, id);
+}
+
+pub(crate) fn index_remove<K: IndexKey>(
+    index: &RwLock<HashMap<K, Vec<EntityId>>>,
+    key: &K,
+    id: &EntityId,
+) {
+    let mut index = index.write().unwrap();
+    let Some(ids) = index.get_mut(key) else {
+        return;
+    };
+    ids.retain(|eid| eid != id);
+    if ids.is_empty() {
+        index.remove(key);
+    }
+}
+
 pub(crate) fn junction_move_ids(
-    junction: &RwLock<HashMap<EntityId, Vec<EntityId>>>,
+    junction: &RwLock<JournaledMap<Vec<EntityId>>>,
     id: &EntityId,
     ids_to_move: &[EntityId],
     new_index: i32,
@@ -611,12 +2008,15 @@ macro_rules! impl_write_relationship_methods {
 macro_rules! impl_leaf_entity_table {
     (
         entity: $Entity:ident,
+        query: $Query:ident,
         entity_name: $entity_name:expr,
         store_field: $store_field:ident,
         table_trait: $TableTrait:ident,
         table_ro_trait: $TableROTrait:ident,
         table_struct: $TableStruct:ident,
         table_ro_struct: $TableROStruct:ident,
+        indexes: [ $( ($idx_field:ident, $idx_store:ident, $idx_getter:ident, $idx_key:ty) ),* $(,)? ],
+        unique_indexes: [ $( ($uq_field:ident, $uq_store:ident, $uq_getter:ident, $uq_key:ty) ),* $(,)? ],
         backward_junctions: [ $( ($bj_field:ident) ),* $(,)? ],
     ) => {
         pub struct $TableStruct<'a> {
@@ -656,7 +2056,16 @@ macro_rules! impl_leaf_entity_table {
                         entity.clone()
                     };
 
+                    $(
+                        $crate::database::hashmap_store::index_check_unique(&self.store.$uq_store, &new_entity.$uq_field, new_entity.id, stringify!($Entity), stringify!($uq_field))?;
+                    )*
                     map.insert(new_entity.id, new_entity.clone());
+                    $(
+                        $crate::database::hashmap_store::index_insert(&self.store.$idx_store, new_entity.$idx_field.clone(), new_entity.id);
+                    )*
+                    $(
+                        $crate::database::hashmap_store::index_insert(&self.store.$uq_store, new_entity.$uq_field.clone(), new_entity.id);
+                    )*
                     created.push(new_entity);
                 }
                 Ok(created)
@@ -675,6 +2084,33 @@ macro_rules! impl_leaf_entity_table {
                 Ok(self.store.$store_field.read().unwrap().values().cloned().collect())
             }
 
+            fn query(&self, query: &$Query) -> Result<Vec<$Entity>, $crate::error::RepositoryError> {
+                let map = self.store.$store_field.read().unwrap();
+                let matching = map.values().filter(|entity| query.matches(entity)).cloned().collect();
+                Ok(query.sort_and_page(matching))
+            }
+
+            fn count(&self, query: &$Query) -> Result<usize, $crate::error::RepositoryError> {
+                let map = self.store.$store_field.read().unwrap();
+                Ok(map.values().filter(|entity| query.matches(entity)).count())
+            }
+
+            $(
+                fn $idx_getter(&self, value: $idx_key) -> Result<Vec<$Entity>, $crate::error::RepositoryError> {
+                    let ids = $crate::database::hashmap_store::index_get(&self.store.$idx_store, value);
+                    let map = self.store.$store_field.read().unwrap();
+                    Ok(ids.iter().filter_map(|id| map.get(id).cloned()).collect())
+                }
+            )*
+
+            $(
+                fn $uq_getter(&self, value: $uq_key) -> Result<Option<$Entity>, $crate::error::RepositoryError> {
+                    let ids = $crate::database::hashmap_store::index_get(&self.store.$uq_store, value);
+                    let map = self.store.$store_field.read().unwrap();
+                    Ok(ids.first().and_then(|id| map.get(id).cloned()))
+                }
+            )*
+
             fn update(&mut self, entity: &$Entity) -> Result<$Entity, $crate::error::RepositoryError> {
                 self.update_multi(std::slice::from_ref(entity))
                     .map(|v| v.into_iter().next().unwrap())
@@ -682,9 +2118,33 @@ macro_rules! impl_leaf_entity_table {
 
             fn update_multi(&mut self, entities: &[$Entity]) -> Result<Vec<$Entity>, $crate::error::RepositoryError> {
                 let mut map = self.store.$store_field.write().unwrap();
+                // Drop the previous index keys first, so that values can be swapped within one batch
+                $(
+                    for entity in entities {
+                        if let Some(previous) = map.get(&entity.id) {
+                            $crate::database::hashmap_store::index_remove(&self.store.$idx_store, &previous.$idx_field, &entity.id);
+                        }
+                    }
+                )*
+                $(
+                    for entity in entities {
+                        if let Some(previous) = map.get(&entity.id) {
+                            $crate::database::hashmap_store::index_remove(&self.store.$uq_store, &previous.$uq_field, &entity.id);
+                        }
+                    }
+                )*
                 let mut result = Vec::with_capacity(entities.len());
                 for entity in entities {
+                    $(
+                        $crate::database::hashmap_store::index_check_unique(&self.store.$uq_store, &entity.$uq_field, entity.id, stringify!($Entity), stringify!($uq_field))?;
+                    )*
                     map.insert(entity.id, entity.clone());
+                    $(
+                        $crate::database::hashmap_store::index_insert(&self.store.$idx_store, entity.$idx_field.clone(), entity.id);
+                    )*
+                    $(
+                        $crate::database::hashmap_store::index_insert(&self.store.$uq_store, entity.$uq_field.clone(), entity.id);
+                    )*
                     result.push(entity.clone());
                 }
                 Ok(result)
@@ -705,6 +2165,16 @@ macro_rules! impl_leaf_entity_table {
             fn remove_multi(&mut self, ids: &[$crate::types::EntityId]) -> Result<(), $crate::error::RepositoryError> {
                 let mut map = self.store.$store_field.write().unwrap();
                 for id in ids {
+                    $(
+                        if let Some(removed) = map.get(id) {
+                            $crate::database::hashmap_store::index_remove(&self.store.$idx_store, &removed.$idx_field, id);
+                        }
+                    )*
+                    $(
+                        if let Some(removed) = map.get(id) {
+                            $crate::database::hashmap_store::index_remove(&self.store.$uq_store, &removed.$uq_field, id);
+                        }
+                    )*
                     map.remove(id);
                     $(
                         $crate::database::hashmap_store::delete_from_backward_junction(&self.store.$bj_field, id);
@@ -737,6 +2207,33 @@ macro_rules! impl_leaf_entity_table {
             fn get_all(&self) -> Result<Vec<$Entity>, $crate::error::RepositoryError> {
                 Ok(self.store.$store_field.read().unwrap().values().cloned().collect())
             }
+
+            fn query(&self, query: &$Query) -> Result<Vec<$Entity>, $crate::error::RepositoryError> {
+                let map = self.store.$store_field.read().unwrap();
+                let matching = map.values().filter(|entity| query.matches(entity)).cloned().collect();
+                Ok(query.sort_and_page(matching))
+            }
+
+            fn count(&self, query: &$Query) -> Result<usize, $crate::error::RepositoryError> {
+                let map = self.store.$store_field.read().unwrap();
+                Ok(map.values().filter(|entity| query.matches(entity)).count())
+            }
+
+            $(
+                fn $idx_getter(&self, value: $idx_key) -> Result<Vec<$Entity>, $crate::error::RepositoryError> {
+                    let ids = $crate::database::hashmap_store::index_get(&self.store.$idx_store, value);
+                    let map = self.store.$store_field.read().unwrap();
+                    Ok(ids.iter().filter_map(|id| map.get(id).cloned()).collect())
+                }
+            )*
+
+            $(
+                fn $uq_getter(&self, value: $uq_key) -> Result<Option<$Entity>, $crate::error::RepositoryError> {
+                    let ids = $crate::database::hashmap_store::index_get(&self.store.$uq_store, value);
+                    let map = self.store.$store_field.read().unwrap();
+                    Ok(ids.first().and_then(|id| map.get(id).cloned()))
+                }
+            )*
         }
     };
 }
@@ -2,7 +2,8 @@
 
 use crate::database::db_context::DbContext;
 use crate::database::hashmap_store::HashMapStore;
-use crate::snapshot::StoreSnapshot;
+use crate::event::Event;
+use crate::snapshot::{StoreJournal, StoreSnapshot};
 use anyhow::{Ok, Result, bail};
 use std::sync::Arc;
 
@@ -37,6 +38,8 @@ impl Transaction {
         if !self.is_write {
             bail!("Cannot commit a read transaction");
         }
+        // Hand the rows written to the journals opened by this transaction
+        self.store.close_journals(true);
         // Discard the auto-savepoint — mutations are now permanent
         if let Some(sp) = self.savepoint.take() {
             self.store.discard_savepoint(sp);
@@ -50,6 +53,7 @@ impl Transaction {
         }
         // Restore the auto-savepoint — undo all mutations
         if let Some(sp) = self.savepoint.take() {
+            self.store.close_journals(false);
             self.store.restore_savepoint(sp);
         }
         Ok(())
@@ -90,6 +94,18 @@ impl Transaction {
     pub fn restore_store(&self, snap: &StoreSnapshot) {
         self.store.restore_store_snapshot(snap);
     }
+
+    /// Record the rows written by the rest of this transaction, for undo.
+    /// The journal is filled in on commit. O(1), whatever the size of the store.
+    pub fn open_journal(&self) -> StoreJournal {
+        self.store.open_journal()
+    }
+
+    /// Put back the rows recorded in a journal and return the events describing
+    /// the change. O(rows recorded).
+    pub fn undo_journal(&self, journal: &StoreJournal) -> Vec<Event> {
+        self.store.undo_journal(journal)
+    }
 }
 
 impl Drop for Transaction {
@@ -97,6 +113,7 @@ impl Drop for Transaction {
         // Safety net: if the transaction was not committed or rolled back,
         // restore the auto-savepoint to undo any partial mutations.
         if let Some(sp) = self.savepoint.take() {
+            self.store.close_journals(false);
             self.store.restore_savepoint(sp);
         }
     }
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_direct_access_mod.tera
 
+pub mod custom_file;
 pub mod dto;
 pub mod dto_field;
 pub mod entity;
@@ -0,0 +1,5 @@
+// Generated by Qleany v1.7.0 from common_entity_mod.tera
+
+pub mod custom_file_repository;
+pub(super) mod custom_file_table;
+pub use custom_file_repository::{CustomFileFilter, CustomFileQuery, CustomFileSortField};
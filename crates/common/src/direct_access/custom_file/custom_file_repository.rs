@@ -0,0 +1,548 @@
+// Generated by Qleany v1.7.0 from common_entity_repository.tera
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+use crate::{
+    database::transactions::Transaction,
+    direct_access::repository_factory,
+    entities::CustomFile,
+    event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
+    snapshot::EntityTreeSnapshot,
+    types::{Condition, EntityId, SortOrder, TextCondition},
+};
+
+use crate::direct_access::workspace::WorkspaceRelationshipField;
+use crate::error::RepositoryError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CustomFileRelationshipField {}
+
+impl Display for CustomFileRelationshipField {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Condition on a scalar field of a CustomFile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CustomFileFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Path(TextCondition),
+    TemplateName(TextCondition),
+    Group(TextCondition),
+    Nature(TextCondition),
+    Scope(TextCondition),
+}
+
+impl CustomFileFilter {
+    pub fn matches(&self, entity: &CustomFile) -> bool {
+        match self {
+            CustomFileFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            CustomFileFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            CustomFileFilter::Path(condition) => condition.matches(&entity.path),
+            CustomFileFilter::TemplateName(condition) => condition.matches(&entity.template_name),
+            CustomFileFilter::Group(condition) => condition.matches(&entity.group),
+            CustomFileFilter::Nature(condition) => condition.matches(&entity.nature),
+            CustomFileFilter::Scope(condition) => condition.matches(&entity.scope),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CustomFileSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Path,
+    TemplateName,
+    Group,
+    Nature,
+    Scope,
+}
+
+/// Query on the CustomFiles: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomFileQuery {
+    pub filters: Vec<CustomFileFilter>,
+    pub sort: Vec<(CustomFileSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl CustomFileQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: CustomFileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: CustomFileSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &CustomFile) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<CustomFile>) -> Vec<CustomFile> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    CustomFileSortField::Id => a.id.cmp(&b.id),
+                    CustomFileSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    CustomFileSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    CustomFileSortField::Path => {
+                        a.path.partial_cmp(&b.path).unwrap_or(Ordering::Equal)
+                    }
+                    CustomFileSortField::TemplateName => a
+                        .template_name
+                        .partial_cmp(&b.template_name)
+                        .unwrap_or(Ordering::Equal),
+                    CustomFileSortField::Group => {
+                        a.group.partial_cmp(&b.group).unwrap_or(Ordering::Equal)
+                    }
+                    CustomFileSortField::Nature => {
+                        a.nature.partial_cmp(&b.nature).unwrap_or(Ordering::Equal)
+                    }
+                    CustomFileSortField::Scope => {
+                        a.scope.partial_cmp(&b.scope).unwrap_or(Ordering::Equal)
+                    }
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+pub trait CustomFileTable {
+    fn create(&mut self, entity: &CustomFile) -> Result<CustomFile, RepositoryError>;
+    fn create_multi(&mut self, entities: &[CustomFile])
+    -> Result<Vec<CustomFile>, RepositoryError>;
+    fn get(&self, id: &EntityId) -> Result<Option<CustomFile>, RepositoryError>;
+    fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<CustomFile>>, RepositoryError>;
+    fn get_all(&self) -> Result<Vec<CustomFile>, RepositoryError>;
+    fn query(&self, query: &CustomFileQuery) -> Result<Vec<CustomFile>, RepositoryError>;
+    fn count(&self, query: &CustomFileQuery) -> Result<usize, RepositoryError>;
+    fn update(&mut self, entity: &CustomFile) -> Result<CustomFile, RepositoryError>;
+    fn update_multi(&mut self, entities: &[CustomFile])
+    -> Result<Vec<CustomFile>, RepositoryError>;
+    fn update_with_relationships(
+        &mut self,
+        entity: &CustomFile,
+    ) -> Result<CustomFile, RepositoryError>;
+    fn update_with_relationships_multi(
+        &mut self,
+        entities: &[CustomFile],
+    ) -> Result<Vec<CustomFile>, RepositoryError>;
+    fn remove(&mut self, id: &EntityId) -> Result<(), RepositoryError>;
+    fn remove_multi(&mut self, ids: &[EntityId]) -> Result<(), RepositoryError>;
+}
+
+pub trait CustomFileTableRO {
+    fn get(&self, id: &EntityId) -> Result<Option<CustomFile>, RepositoryError>;
+    fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<CustomFile>>, RepositoryError>;
+    fn get_all(&self) -> Result<Vec<CustomFile>, RepositoryError>;
+    fn query(&self, query: &CustomFileQuery) -> Result<Vec<CustomFile>, RepositoryError>;
+    fn count(&self, query: &CustomFileQuery) -> Result<usize, RepositoryError>;
+}
+
+pub struct CustomFileRepository<'a> {
+    table: Box<dyn CustomFileTable + 'a>,
+    transaction: &'a Transaction,
+}
+
+impl<'a> CustomFileRepository<'a> {
+    pub fn new(table: Box<dyn CustomFileTable + 'a>, transaction: &'a Transaction) -> Self {
+        CustomFileRepository { table, transaction }
+    }
+
+    pub fn create_orphan(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        entity: &CustomFile,
+    ) -> Result<CustomFile, RepositoryError> {
+        let new = self.table.create(entity)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Created)),
+            ids: vec![new.id],
+            data: None,
+        });
+        Ok(new)
+    }
+
+    pub fn create_orphan_multi(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        entities: &[CustomFile],
+    ) -> Result<Vec<CustomFile>, RepositoryError> {
+        let new_entities = self.table.create_multi(entities)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Created)),
+            ids: new_entities.iter().map(|e| e.id).collect(),
+            data: None,
+        });
+        Ok(new_entities)
+    }
+    pub fn create(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        entity: &CustomFile,
+        owner_id: EntityId,
+        index: i32,
+    ) -> Result<CustomFile, RepositoryError> {
+        let new = self.table.create(entity)?;
+        let created_id = new.id;
+
+        let mut relationship_ids = self.get_relationships_from_owner(&owner_id)?;
+        // Insert at index
+        if index >= 0 && (index as usize) < relationship_ids.len() {
+            relationship_ids.insert(index as usize, created_id);
+        } else {
+            relationship_ids.push(created_id);
+        }
+
+        self.set_relationships_in_owner(event_buffer, &owner_id, &relationship_ids)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Created)),
+            ids: vec![created_id],
+            data: None,
+        });
+        Ok(new)
+    }
+
+    pub fn create_multi(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        entities: &[CustomFile],
+        owner_id: EntityId,
+        index: i32,
+    ) -> Result<Vec<CustomFile>, RepositoryError> {
+        let new_entities = self.table.create_multi(entities)?;
+        let created_ids: Vec<EntityId> = new_entities.iter().map(|e| e.id).collect();
+
+        let mut relationship_ids = self.get_relationships_from_owner(&owner_id)?;
+        if index >= 0 && (index as usize) < relationship_ids.len() {
+            for (i, id) in created_ids.iter().enumerate() {
+                relationship_ids.insert(index as usize + i, *id);
+            }
+        } else {
+            relationship_ids.extend(created_ids.iter());
+        }
+
+        self.set_relationships_in_owner(event_buffer, &owner_id, &relationship_ids)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Created)),
+            ids: created_ids,
+            data: None,
+        });
+        Ok(new_entities)
+    }
+
+    pub fn get(&self, id: &EntityId) -> Result<Option<CustomFile>, RepositoryError> {
+        self.table.get(id)
+    }
+    pub fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<CustomFile>>, RepositoryError> {
+        self.table.get_multi(ids)
+    }
+    pub fn get_all(&self) -> Result<Vec<CustomFile>, RepositoryError> {
+        self.table.get_all()
+    }
+    pub fn query(&self, query: &CustomFileQuery) -> Result<Vec<CustomFile>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &CustomFileQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
+
+    pub fn update(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        entity: &CustomFile,
+    ) -> Result<CustomFile, RepositoryError> {
+        let updated = self.table.update(entity)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Updated)),
+            ids: vec![updated.id],
+            data: None,
+        });
+        Ok(updated)
+    }
+
+    pub fn update_multi(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        entities: &[CustomFile],
+    ) -> Result<Vec<CustomFile>, RepositoryError> {
+        let updated = self.table.update_multi(entities)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Updated)),
+            ids: updated.iter().map(|e| e.id).collect(),
+            data: None,
+        });
+        Ok(updated)
+    }
+
+    pub fn update_with_relationships(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        entity: &CustomFile,
+    ) -> Result<CustomFile, RepositoryError> {
+        let updated = self.table.update_with_relationships(entity)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Updated)),
+            ids: vec![updated.id],
+            data: None,
+        });
+        Ok(updated)
+    }
+
+    pub fn update_with_relationships_multi(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        entities: &[CustomFile],
+    ) -> Result<Vec<CustomFile>, RepositoryError> {
+        let updated = self.table.update_with_relationships_multi(entities)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Updated)),
+            ids: updated.iter().map(|e| e.id).collect(),
+            data: None,
+        });
+        Ok(updated)
+    }
+
+    pub fn remove(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        id: &EntityId,
+    ) -> Result<(), RepositoryError> {
+        let _entity = match self.table.get(id)? {
+            Some(e) => e,
+            None => return Ok(()),
+        };
+        // get all strong forward relationship fields
+
+        // remove all strong relationships, initiating a cascade remove
+
+        // Before removal, find which owner(s) reference this entity
+        let affected_owner_ids: Vec<EntityId> = {
+            let owner_repo =
+                repository_factory::write::create_workspace_repository(self.transaction)?;
+            owner_repo
+                .get_relationships_from_right_ids(&WorkspaceRelationshipField::CustomFiles, &[*id])?
+                .into_iter()
+                .map(|(owner_id, _)| owner_id)
+                .collect()
+        };
+        // Save each owner's current relationship IDs (properly ordered via get_relationships_from_owner)
+        let mut owner_rel_before: std::collections::HashMap<EntityId, Vec<EntityId>> =
+            std::collections::HashMap::new();
+        for owner_id in &affected_owner_ids {
+            owner_rel_before.insert(*owner_id, self.get_relationships_from_owner(owner_id)?);
+        }
+
+        // remove entity
+        self.table.remove(id)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Removed)),
+            ids: vec![*id],
+            data: None,
+        });
+        // Update each affected owner's relationship to exclude removed ID (emits Updated event)
+        for owner_id in &affected_owner_ids {
+            if let Some(rel_ids) = owner_rel_before.get(owner_id) {
+                let updated: Vec<EntityId> =
+                    rel_ids.iter().copied().filter(|rid| *rid != *id).collect();
+                self.set_relationships_in_owner(event_buffer, owner_id, &updated)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_multi(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        ids: &[EntityId],
+    ) -> Result<(), RepositoryError> {
+        let entities = self.table.get_multi(ids)?;
+        if entities.is_empty() || entities.iter().all(|e| e.is_none()) {
+            return Ok(());
+        }
+
+        // get all strong forward relationship fields
+
+        // remove all strong relationships, initiating a cascade remove
+
+        // Before removal, find which owner(s) reference these entities
+        let affected_owner_ids: Vec<EntityId> = {
+            let owner_repo =
+                repository_factory::write::create_workspace_repository(self.transaction)?;
+            owner_repo
+                .get_relationships_from_right_ids(&WorkspaceRelationshipField::CustomFiles, ids)?
+                .into_iter()
+                .map(|(owner_id, _)| owner_id)
+                .collect()
+        };
+        // Save each owner's current relationship IDs (properly ordered via get_relationships_from_owner)
+        let mut owner_rel_before: std::collections::HashMap<EntityId, Vec<EntityId>> =
+            std::collections::HashMap::new();
+        for owner_id in &affected_owner_ids {
+            owner_rel_before.insert(*owner_id, self.get_relationships_from_owner(owner_id)?);
+        }
+
+        self.table.remove_multi(ids)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::CustomFile(EntityEvent::Removed)),
+            ids: ids.into(),
+            data: None,
+        });
+        // Update each affected owner's relationship to exclude removed IDs (emits Updated event)
+        {
+            let removed_set: std::collections::HashSet<EntityId> = ids.iter().copied().collect();
+            for owner_id in &affected_owner_ids {
+                if let Some(rel_ids) = owner_rel_before.get(owner_id) {
+                    let updated: Vec<EntityId> = rel_ids
+                        .iter()
+                        .copied()
+                        .filter(|rid| !removed_set.contains(rid))
+                        .collect();
+                    self.set_relationships_in_owner(event_buffer, owner_id, &updated)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+    pub fn get_relationships_from_owner(
+        &self,
+        owner_id: &EntityId,
+    ) -> Result<Vec<EntityId>, RepositoryError> {
+        let repo = repository_factory::write::create_workspace_repository(self.transaction)?;
+        repo.get_relationship(owner_id, &WorkspaceRelationshipField::CustomFiles)
+    }
+
+    pub fn set_relationships_in_owner(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        owner_id: &EntityId,
+        ids: &[EntityId],
+    ) -> Result<(), RepositoryError> {
+        let mut repo = repository_factory::write::create_workspace_repository(self.transaction)?;
+        repo.set_relationship(
+            event_buffer,
+            owner_id,
+            &WorkspaceRelationshipField::CustomFiles,
+            ids,
+        )
+    }
+
+    pub fn snapshot(&self, _ids: &[EntityId]) -> Result<EntityTreeSnapshot, RepositoryError> {
+        let store_snap = self.transaction.snapshot_store();
+        Ok(EntityTreeSnapshot {
+            store_snapshot: Some(store_snap),
+            journal: None,
+        })
+    }
+
+    pub fn restore(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        snap: &EntityTreeSnapshot,
+    ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
+        let store_snap = snap
+            .store_snapshot
+            .as_ref()
+            .ok_or_else(|| RepositoryError::Serialization("missing store snapshot".into()))?;
+        self.transaction.restore_store(store_snap);
+
+        let store = self.transaction.get_store();
+
+        let mut emit = |entity: DirectAccessEntity, ids: Vec<EntityId>| {
+            if !ids.is_empty() {
+                event_buffer.push(Event {
+                    origin: Origin::DirectAccess(entity),
+                    ids,
+                    data: None,
+                });
+            }
+        };
+
+        // Emit Created events for this entity
+        let custom_file_ids: Vec<_> = store.custom_files.read().unwrap().keys().copied().collect();
+        emit(
+            DirectAccessEntity::CustomFile(EntityEvent::Created),
+            custom_file_ids.clone(),
+        );
+
+        // Emit Created events for strong children
+
+        Ok(())
+    }
+}
+
+pub struct CustomFileRepositoryRO<'a> {
+    table: Box<dyn CustomFileTableRO + 'a>,
+}
+impl<'a> CustomFileRepositoryRO<'a> {
+    pub fn new(table: Box<dyn CustomFileTableRO + 'a>) -> Self {
+        CustomFileRepositoryRO { table }
+    }
+    pub fn get(&self, id: &EntityId) -> Result<Option<CustomFile>, RepositoryError> {
+        self.table.get(id)
+    }
+    pub fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<CustomFile>>, RepositoryError> {
+        self.table.get_multi(ids)
+    }
+    pub fn get_all(&self) -> Result<Vec<CustomFile>, RepositoryError> {
+        self.table.get_all()
+    }
+    pub fn query(&self, query: &CustomFileQuery) -> Result<Vec<CustomFile>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &CustomFileQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
+}
@@ -0,0 +1,27 @@
+// Generated by Qleany v1.7.0 from common_entity_table.tera
+
+// ═══════════════════════════════════════════════════════════════════════
+// Leaf entity (no forward relationships) — macro implementation
+// ═══════════════════════════════════════════════════════════════════════
+
+use super::custom_file_repository::CustomFileQuery;
+use super::custom_file_repository::CustomFileTable;
+use super::custom_file_repository::CustomFileTableRO;
+use crate::entities::CustomFile;
+
+crate::impl_leaf_entity_table!(
+    entity: CustomFile,
+    query: CustomFileQuery,
+    entity_name: "custom_file",
+    store_field: custom_files,
+    table_trait: CustomFileTable,
+    table_ro_trait: CustomFileTableRO,
+    table_struct: CustomFileHashMapTable,
+    table_ro_struct: CustomFileHashMapTableRO,
+    indexes: [],
+    unique_indexes: [],
+    backward_junctions: [
+
+        (jn_custom_file_from_workspace_custom_files),
+    ],
+);
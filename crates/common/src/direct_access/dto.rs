@@ -3,3 +3,4 @@
 pub mod dto_repository;
 pub(super) mod dto_table;
 pub use dto_repository::DtoRelationshipField;
+pub use dto_repository::{DtoFilter, DtoQuery, DtoSortField};
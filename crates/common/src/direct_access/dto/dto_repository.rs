@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::Dto,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::use_case::UseCaseRelationshipField;
@@ -26,12 +27,113 @@ impl Display for DtoRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a Dto.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DtoFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Name(TextCondition),
+}
+
+impl DtoFilter {
+    pub fn matches(&self, entity: &Dto) -> bool {
+        match self {
+            DtoFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            DtoFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            DtoFilter::Name(condition) => condition.matches(&entity.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DtoSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Name,
+}
+
+/// Query on the Dtos: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DtoQuery {
+    pub filters: Vec<DtoFilter>,
+    pub sort: Vec<(DtoSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl DtoQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: DtoFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: DtoSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &Dto) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<Dto>) -> Vec<Dto> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    DtoSortField::Id => a.id.cmp(&b.id),
+                    DtoSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    DtoSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    DtoSortField::Name => a.name.partial_cmp(&b.name).unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait DtoTable {
     fn create(&mut self, entity: &Dto) -> Result<Dto, RepositoryError>;
     fn create_multi(&mut self, entities: &[Dto]) -> Result<Vec<Dto>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<Dto>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Dto>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Dto>, RepositoryError>;
+    fn query(&self, query: &DtoQuery) -> Result<Vec<Dto>, RepositoryError>;
+    fn count(&self, query: &DtoQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &Dto) -> Result<Dto, RepositoryError>;
     fn update_multi(&mut self, entities: &[Dto]) -> Result<Vec<Dto>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &Dto) -> Result<Dto, RepositoryError>;
@@ -92,6 +194,8 @@ pub trait DtoTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<Dto>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Dto>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Dto>, RepositoryError>;
+    fn query(&self, query: &DtoQuery) -> Result<Vec<Dto>, RepositoryError>;
+    fn count(&self, query: &DtoQuery) -> Result<usize, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -222,6 +326,12 @@ impl<'a> DtoRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<Dto>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &DtoQuery) -> Result<Vec<Dto>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &DtoQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -588,6 +698,7 @@ impl<'a> DtoRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -596,6 +707,12 @@ impl<'a> DtoRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -630,6 +747,10 @@ impl<'a> DtoRepository<'a> {
                 DirectAccessEntity::DtoField(EntityEvent::Created),
                 child_ids.clone(),
             );
+            emit(
+                DirectAccessEntity::DtoField(EntityEvent::Updated),
+                child_ids,
+            );
         }
 
         Ok(())
@@ -652,6 +773,12 @@ impl<'a> DtoRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<Dto>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &DtoQuery) -> Result<Vec<Dto>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &DtoQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
@@ -5,15 +5,16 @@
 // ═══════════════════════════════════════════════════════════════════════
 
 use crate::database::hashmap_store::{
-    HashMapStore, delete_from_backward_junction, junction_get, junction_remove, junction_set,
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
 };
 use crate::entities::Dto;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::dto_repository::DtoQuery;
 use super::dto_repository::DtoRelationshipField;
 use super::dto_repository::DtoTable;
 use super::dto_repository::DtoTableRO;
@@ -30,7 +31,7 @@ impl<'a> DtoHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &DtoRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             DtoRelationshipField::Fields => &self.store.jn_dto_field_from_dto_fields,
         }
@@ -114,6 +115,30 @@ impl<'a> DtoTable for DtoHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &DtoQuery) -> Result<Vec<Dto>, RepositoryError> {
+        let dto_map = self.store.dtos.read().unwrap();
+        let matching: Vec<Dto> = dto_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(dto_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &DtoQuery) -> Result<usize, RepositoryError> {
+        let dto_map = self.store.dtos.read().unwrap();
+        Ok(dto_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     fn update(&mut self, entity: &Dto) -> Result<Dto, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -171,8 +196,12 @@ impl<'a> DtoTable for DtoHashMapTable<'a> {
 
             // Clean up backward references (uses the owning entity's forward junction)
 
+            delete_from_backward_junction(&self.store.jn_dto_from_dto_field_dto, id);
             delete_from_backward_junction(&self.store.jn_dto_from_use_case_dto_in, id);
             delete_from_backward_junction(&self.store.jn_dto_from_use_case_dto_out, id);
+            delete_from_backward_junction(&self.store.jn_dto_from_feature_dtos, id);
+            delete_from_backward_junction(&self.store.jn_dto_from_use_case_shared_dto_in, id);
+            delete_from_backward_junction(&self.store.jn_dto_from_use_case_shared_dto_out, id);
         }
         Ok(())
     }
@@ -192,7 +221,7 @@ impl<'a> DtoHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &DtoRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             DtoRelationshipField::Fields => &self.store.jn_dto_field_from_dto_fields,
         }
@@ -237,5 +266,29 @@ impl<'a> DtoTableRO for DtoHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &DtoQuery) -> Result<Vec<Dto>, RepositoryError> {
+        let dto_map = self.store.dtos.read().unwrap();
+        let matching: Vec<Dto> = dto_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(dto_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &DtoQuery) -> Result<usize, RepositoryError> {
+        let dto_map = self.store.dtos.read().unwrap();
+        Ok(dto_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     impl_relationship_methods!(DtoHashMapTableRO<'a>, DtoRelationshipField);
 }
@@ -2,3 +2,5 @@
 
 pub mod dto_field_repository;
 pub(super) mod dto_field_table;
+pub use dto_field_repository::DtoFieldRelationshipField;
+pub use dto_field_repository::{DtoFieldFilter, DtoFieldQuery, DtoFieldSortField};
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::DtoField,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::dto::DtoRelationshipField;
@@ -16,7 +17,9 @@ use crate::error::RepositoryError;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum DtoFieldRelationshipField {}
+pub enum DtoFieldRelationshipField {
+    Dto,
+}
 
 impl Display for DtoFieldRelationshipField {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -24,12 +27,201 @@ impl Display for DtoFieldRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a DtoField.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DtoFieldFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Name(TextCondition),
+    FieldType(Condition<crate::entities::DtoFieldType>),
+    Optional(Condition<bool>),
+    IsList(Condition<bool>),
+    EnumName(TextCondition),
+    MinValue(TextCondition),
+    MaxValue(TextCondition),
+    MinLength(Condition<i64>),
+    MaxLength(Condition<i64>),
+    Pattern(TextCondition),
+    NotEmpty(Condition<bool>),
+}
+
+impl DtoFieldFilter {
+    pub fn matches(&self, entity: &DtoField) -> bool {
+        match self {
+            DtoFieldFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            DtoFieldFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            DtoFieldFilter::Name(condition) => condition.matches(&entity.name),
+            DtoFieldFilter::FieldType(condition) => condition.matches(&entity.field_type),
+            DtoFieldFilter::Optional(condition) => condition.matches(&entity.optional),
+            DtoFieldFilter::IsList(condition) => condition.matches(&entity.is_list),
+            DtoFieldFilter::EnumName(condition) => entity
+                .enum_name
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            DtoFieldFilter::MinValue(condition) => entity
+                .min_value
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            DtoFieldFilter::MaxValue(condition) => entity
+                .max_value
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            DtoFieldFilter::MinLength(condition) => entity
+                .min_length
+                .as_ref()
+                .is_some_and(|value| condition.matches(value)),
+            DtoFieldFilter::MaxLength(condition) => entity
+                .max_length
+                .as_ref()
+                .is_some_and(|value| condition.matches(value)),
+            DtoFieldFilter::Pattern(condition) => entity
+                .pattern
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            DtoFieldFilter::NotEmpty(condition) => condition.matches(&entity.not_empty),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DtoFieldSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Name,
+    FieldType,
+    Optional,
+    IsList,
+    EnumName,
+    MinValue,
+    MaxValue,
+    MinLength,
+    MaxLength,
+    Pattern,
+    NotEmpty,
+}
+
+/// Query on the DtoFields: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DtoFieldQuery {
+    pub filters: Vec<DtoFieldFilter>,
+    pub sort: Vec<(DtoFieldSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl DtoFieldQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: DtoFieldFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: DtoFieldSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &DtoField) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<DtoField>) -> Vec<DtoField> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    DtoFieldSortField::Id => a.id.cmp(&b.id),
+                    DtoFieldSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    DtoFieldSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    DtoFieldSortField::Name => {
+                        a.name.partial_cmp(&b.name).unwrap_or(Ordering::Equal)
+                    }
+                    DtoFieldSortField::FieldType => a
+                        .field_type
+                        .partial_cmp(&b.field_type)
+                        .unwrap_or(Ordering::Equal),
+                    DtoFieldSortField::Optional => a
+                        .optional
+                        .partial_cmp(&b.optional)
+                        .unwrap_or(Ordering::Equal),
+                    DtoFieldSortField::IsList => {
+                        a.is_list.partial_cmp(&b.is_list).unwrap_or(Ordering::Equal)
+                    }
+                    DtoFieldSortField::EnumName => a
+                        .enum_name
+                        .partial_cmp(&b.enum_name)
+                        .unwrap_or(Ordering::Equal),
+                    DtoFieldSortField::MinValue => a
+                        .min_value
+                        .partial_cmp(&b.min_value)
+                        .unwrap_or(Ordering::Equal),
+                    DtoFieldSortField::MaxValue => a
+                        .max_value
+                        .partial_cmp(&b.max_value)
+                        .unwrap_or(Ordering::Equal),
+                    DtoFieldSortField::MinLength => a
+                        .min_length
+                        .partial_cmp(&b.min_length)
+                        .unwrap_or(Ordering::Equal),
+                    DtoFieldSortField::MaxLength => a
+                        .max_length
+                        .partial_cmp(&b.max_length)
+                        .unwrap_or(Ordering::Equal),
+                    DtoFieldSortField::Pattern => {
+                        a.pattern.partial_cmp(&b.pattern).unwrap_or(Ordering::Equal)
+                    }
+                    DtoFieldSortField::NotEmpty => a
+                        .not_empty
+                        .partial_cmp(&b.not_empty)
+                        .unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait DtoFieldTable {
     fn create(&mut self, entity: &DtoField) -> Result<DtoField, RepositoryError>;
     fn create_multi(&mut self, entities: &[DtoField]) -> Result<Vec<DtoField>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<DtoField>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<DtoField>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<DtoField>, RepositoryError>;
+    fn query(&self, query: &DtoFieldQuery) -> Result<Vec<DtoField>, RepositoryError>;
+    fn count(&self, query: &DtoFieldQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &DtoField) -> Result<DtoField, RepositoryError>;
     fn update_multi(&mut self, entities: &[DtoField]) -> Result<Vec<DtoField>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &DtoField)
@@ -40,12 +232,86 @@ pub trait DtoFieldTable {
     ) -> Result<Vec<DtoField>, RepositoryError>;
     fn remove(&mut self, id: &EntityId) -> Result<(), RepositoryError>;
     fn remove_multi(&mut self, ids: &[EntityId]) -> Result<(), RepositoryError>;
+    fn get_relationship(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<Vec<EntityId>, RepositoryError>;
+    fn get_relationship_many(
+        &self,
+        ids: &[EntityId],
+        field: &DtoFieldRelationshipField,
+    ) -> Result<std::collections::HashMap<EntityId, Vec<EntityId>>, RepositoryError>;
+    fn get_relationship_count(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<usize, RepositoryError>;
+    fn get_relationship_in_range(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<EntityId>, RepositoryError>;
+    fn get_relationships_from_right_ids(
+        &self,
+        field: &DtoFieldRelationshipField,
+        right_ids: &[EntityId],
+    ) -> Result<Vec<(EntityId, Vec<EntityId>)>, RepositoryError>;
+    fn set_relationship_multi(
+        &mut self,
+        field: &DtoFieldRelationshipField,
+        relationships: Vec<(EntityId, Vec<EntityId>)>,
+    ) -> Result<(), RepositoryError>;
+    fn set_relationship(
+        &mut self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        right_ids: &[EntityId],
+    ) -> Result<(), RepositoryError>;
+    fn move_relationship_ids(
+        &mut self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        ids_to_move: &[EntityId],
+        new_index: i32,
+    ) -> Result<Vec<EntityId>, RepositoryError>;
 }
 
 pub trait DtoFieldTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<DtoField>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<DtoField>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<DtoField>, RepositoryError>;
+    fn query(&self, query: &DtoFieldQuery) -> Result<Vec<DtoField>, RepositoryError>;
+    fn count(&self, query: &DtoFieldQuery) -> Result<usize, RepositoryError>;
+    fn get_relationship(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<Vec<EntityId>, RepositoryError>;
+    fn get_relationship_many(
+        &self,
+        ids: &[EntityId],
+        field: &DtoFieldRelationshipField,
+    ) -> Result<std::collections::HashMap<EntityId, Vec<EntityId>>, RepositoryError>;
+    fn get_relationship_count(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<usize, RepositoryError>;
+    fn get_relationship_in_range(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<EntityId>, RepositoryError>;
+    fn get_relationships_from_right_ids(
+        &self,
+        field: &DtoFieldRelationshipField,
+        right_ids: &[EntityId],
+    ) -> Result<Vec<(EntityId, Vec<EntityId>)>, RepositoryError>;
 }
 
 pub struct DtoFieldRepository<'a> {
@@ -149,6 +415,12 @@ impl<'a> DtoFieldRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<DtoField>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &DtoFieldQuery) -> Result<Vec<DtoField>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &DtoFieldQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -307,6 +579,170 @@ impl<'a> DtoFieldRepository<'a> {
 
         Ok(())
     }
+    pub fn get_relationship(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<Vec<EntityId>, RepositoryError> {
+        self.table.get_relationship(id, field)
+    }
+    pub fn get_relationship_many(
+        &self,
+        ids: &[EntityId],
+        field: &DtoFieldRelationshipField,
+    ) -> Result<std::collections::HashMap<EntityId, Vec<EntityId>>, RepositoryError> {
+        self.table.get_relationship_many(ids, field)
+    }
+    pub fn get_relationship_count(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<usize, RepositoryError> {
+        self.table.get_relationship_count(id, field)
+    }
+    pub fn get_relationship_in_range(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<EntityId>, RepositoryError> {
+        self.table
+            .get_relationship_in_range(id, field, offset, limit)
+    }
+    pub fn get_relationships_from_right_ids(
+        &self,
+        field: &DtoFieldRelationshipField,
+        right_ids: &[EntityId],
+    ) -> Result<Vec<(EntityId, Vec<EntityId>)>, RepositoryError> {
+        self.table
+            .get_relationships_from_right_ids(field, right_ids)
+    }
+
+    pub fn set_relationship_multi(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        field: &DtoFieldRelationshipField,
+        relationships: Vec<(EntityId, Vec<EntityId>)>,
+    ) -> Result<(), RepositoryError> {
+        // Validate that all right_ids exist
+        let all_right_ids: Vec<EntityId> = relationships
+            .iter()
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect();
+        if !all_right_ids.is_empty() {
+            match field {
+                DtoFieldRelationshipField::Dto => {
+                    let child_repo =
+                        repository_factory::write::create_dto_repository(self.transaction)?;
+                    let found = child_repo.get_multi(&all_right_ids)?;
+                    let missing: Vec<_> = all_right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship_multi",
+                            ids: missing,
+                        });
+                    }
+                }
+            }
+        }
+        self.table
+            .set_relationship_multi(field, relationships.clone())?;
+        for (left_id, right_ids) in relationships {
+            event_buffer.push(Event {
+                origin: Origin::DirectAccess(DirectAccessEntity::DtoField(EntityEvent::Updated)),
+                ids: vec![left_id],
+                data: Some(format!(
+                    "{}:{}",
+                    field,
+                    right_ids
+                        .iter()
+                        .map(|id| id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn set_relationship(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        right_ids: &[EntityId],
+    ) -> Result<(), RepositoryError> {
+        // Validate that all right_ids exist
+        if !right_ids.is_empty() {
+            match field {
+                DtoFieldRelationshipField::Dto => {
+                    let child_repo =
+                        repository_factory::write::create_dto_repository(self.transaction)?;
+                    let found = child_repo.get_multi(right_ids)?;
+                    let missing: Vec<_> = right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship",
+                            ids: missing,
+                        });
+                    }
+                }
+            }
+        }
+        self.table.set_relationship(id, field, right_ids)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::DtoField(EntityEvent::Updated)),
+            ids: vec![*id],
+            data: Some(format!(
+                "{}:{}",
+                field,
+                right_ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )),
+        });
+        Ok(())
+    }
+
+    pub fn move_relationship_ids(
+        &mut self,
+        event_buffer: &mut EventBuffer,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        ids_to_move: &[EntityId],
+        new_index: i32,
+    ) -> Result<Vec<EntityId>, RepositoryError> {
+        let reordered = self
+            .table
+            .move_relationship_ids(id, field, ids_to_move, new_index)?;
+        event_buffer.push(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::DtoField(EntityEvent::Updated)),
+            ids: vec![*id],
+            data: Some(format!(
+                "{}:{}",
+                field,
+                reordered
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )),
+        });
+        Ok(reordered)
+    }
     pub fn get_relationships_from_owner(
         &self,
         owner_id: &EntityId,
@@ -329,6 +765,7 @@ impl<'a> DtoFieldRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -337,6 +774,12 @@ impl<'a> DtoFieldRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -361,6 +804,10 @@ impl<'a> DtoFieldRepository<'a> {
             DirectAccessEntity::DtoField(EntityEvent::Created),
             dto_field_ids.clone(),
         );
+        emit(
+            DirectAccessEntity::DtoField(EntityEvent::Updated),
+            dto_field_ids,
+        );
 
         // Emit Created events for strong children
 
@@ -384,4 +831,49 @@ impl<'a> DtoFieldRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<DtoField>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &DtoFieldQuery) -> Result<Vec<DtoField>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &DtoFieldQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
+    pub fn get_relationship(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<Vec<EntityId>, RepositoryError> {
+        self.table.get_relationship(id, field)
+    }
+    pub fn get_relationship_many(
+        &self,
+        ids: &[EntityId],
+        field: &DtoFieldRelationshipField,
+    ) -> Result<std::collections::HashMap<EntityId, Vec<EntityId>>, RepositoryError> {
+        self.table.get_relationship_many(ids, field)
+    }
+    pub fn get_relationship_count(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<usize, RepositoryError> {
+        self.table.get_relationship_count(id, field)
+    }
+    pub fn get_relationship_in_range(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<EntityId>, RepositoryError> {
+        self.table
+            .get_relationship_in_range(id, field, offset, limit)
+    }
+    pub fn get_relationships_from_right_ids(
+        &self,
+        field: &DtoFieldRelationshipField,
+        right_ids: &[EntityId],
+    ) -> Result<Vec<(EntityId, Vec<EntityId>)>, RepositoryError> {
+        self.table
+            .get_relationships_from_right_ids(field, right_ids)
+    }
 }
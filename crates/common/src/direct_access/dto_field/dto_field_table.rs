@@ -1,23 +1,296 @@
 // Generated by Qleany v1.7.0 from common_entity_table.tera
 
 // ═══════════════════════════════════════════════════════════════════════
-// Leaf entity (no forward relationships) — macro implementation
+// Entity WITH forward relationships — explicit struct implementation
 // ═══════════════════════════════════════════════════════════════════════
 
+use crate::database::hashmap_store::{
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
+};
+use crate::entities::DtoField;
+use crate::error::RepositoryError;
+use crate::types::EntityId;
+use crate::{impl_relationship_methods, impl_write_relationship_methods};
+use std::sync::RwLock;
+
+use super::dto_field_repository::DtoFieldQuery;
+use super::dto_field_repository::DtoFieldRelationshipField;
 use super::dto_field_repository::DtoFieldTable;
 use super::dto_field_repository::DtoFieldTableRO;
-use crate::entities::DtoField;
 
-crate::impl_leaf_entity_table!(
-    entity: DtoField,
-    entity_name: "dto_field",
-    store_field: dto_fields,
-    table_trait: DtoFieldTable,
-    table_ro_trait: DtoFieldTableRO,
-    table_struct: DtoFieldHashMapTable,
-    table_ro_struct: DtoFieldHashMapTableRO,
-    backward_junctions: [
-
-        (jn_dto_field_from_dto_fields),
-    ],
-);
+pub struct DtoFieldHashMapTable<'a> {
+    store: &'a HashMapStore,
+}
+
+impl<'a> DtoFieldHashMapTable<'a> {
+    pub fn new(store: &'a HashMapStore) -> Self {
+        Self { store }
+    }
+
+    fn resolve_junction(
+        &self,
+        field: &DtoFieldRelationshipField,
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
+        match field {
+            DtoFieldRelationshipField::Dto => &self.store.jn_dto_from_dto_field_dto,
+        }
+    }
+
+    fn hydrate(&self, entity: &mut DtoField) {
+        entity.dto = junction_get(&self.store.jn_dto_from_dto_field_dto, &entity.id)
+            .into_iter()
+            .next();
+    }
+}
+
+impl<'a> DtoFieldTable for DtoFieldHashMapTable<'a> {
+    fn create(&mut self, entity: &DtoField) -> Result<DtoField, RepositoryError> {
+        self.create_multi(std::slice::from_ref(entity))
+            .map(|v| v.into_iter().next().unwrap())
+    }
+
+    fn create_multi(&mut self, entities: &[DtoField]) -> Result<Vec<DtoField>, RepositoryError> {
+        let mut created = Vec::with_capacity(entities.len());
+        let mut dto_field_map = self.store.dto_fields.write().unwrap();
+
+        for entity in entities {
+            let new_entity = if entity.id == EntityId::default() {
+                let id = self.store.next_id("dto_field");
+                DtoField {
+                    id,
+                    ..entity.clone()
+                }
+            } else {
+                if dto_field_map.contains_key(&entity.id) {
+                    return Err(RepositoryError::DuplicateId {
+                        entity: "DtoField",
+                        id: entity.id,
+                    });
+                }
+                entity.clone()
+            };
+
+            dto_field_map.insert(new_entity.id, new_entity.clone());
+
+            junction_set(
+                &self.store.jn_dto_from_dto_field_dto,
+                new_entity.id,
+                new_entity.dto.into_iter().collect::<Vec<EntityId>>(),
+            );
+
+            created.push(new_entity);
+        }
+        Ok(created)
+    }
+
+    fn get(&self, id: &EntityId) -> Result<Option<DtoField>, RepositoryError> {
+        let dto_field_map = self.store.dto_fields.read().unwrap();
+        match dto_field_map.get(id) {
+            Some(entity) => {
+                let mut e = entity.clone();
+                drop(dto_field_map);
+                self.hydrate(&mut e);
+                Ok(Some(e))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<DtoField>>, RepositoryError> {
+        let mut result = Vec::with_capacity(ids.len());
+        for id in ids {
+            result.push(self.get(id)?);
+        }
+        Ok(result)
+    }
+
+    fn get_all(&self) -> Result<Vec<DtoField>, RepositoryError> {
+        let dto_field_map = self.store.dto_fields.read().unwrap();
+        let entries: Vec<DtoField> = dto_field_map.values().cloned().collect();
+        drop(dto_field_map);
+        let mut result = Vec::with_capacity(entries.len());
+        for mut entity in entries {
+            self.hydrate(&mut entity);
+            result.push(entity);
+        }
+        Ok(result)
+    }
+
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &DtoFieldQuery) -> Result<Vec<DtoField>, RepositoryError> {
+        let dto_field_map = self.store.dto_fields.read().unwrap();
+        let matching: Vec<DtoField> = dto_field_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(dto_field_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &DtoFieldQuery) -> Result<usize, RepositoryError> {
+        let dto_field_map = self.store.dto_fields.read().unwrap();
+        Ok(dto_field_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
+    fn update(&mut self, entity: &DtoField) -> Result<DtoField, RepositoryError> {
+        self.update_multi(std::slice::from_ref(entity))
+            .map(|v| v.into_iter().next().unwrap())
+    }
+
+    // Scalar-only update: writes entity data but does NOT touch junction tables.
+    fn update_multi(&mut self, entities: &[DtoField]) -> Result<Vec<DtoField>, RepositoryError> {
+        let mut dto_field_map = self.store.dto_fields.write().unwrap();
+        for entity in entities {
+            dto_field_map.insert(entity.id, entity.clone());
+        }
+        drop(dto_field_map);
+        let ids: Vec<EntityId> = entities.iter().map(|e| e.id).collect();
+        let result = self.get_multi(&ids)?;
+        Ok(result.into_iter().flatten().collect())
+    }
+
+    fn update_with_relationships(
+        &mut self,
+        entity: &DtoField,
+    ) -> Result<DtoField, RepositoryError> {
+        self.update_with_relationships_multi(std::slice::from_ref(entity))
+            .map(|v| v.into_iter().next().unwrap())
+    }
+
+    fn update_with_relationships_multi(
+        &mut self,
+        entities: &[DtoField],
+    ) -> Result<Vec<DtoField>, RepositoryError> {
+        let mut dto_field_map = self.store.dto_fields.write().unwrap();
+        for entity in entities {
+            dto_field_map.insert(entity.id, entity.clone());
+
+            junction_set(
+                &self.store.jn_dto_from_dto_field_dto,
+                entity.id,
+                entity.dto.into_iter().collect::<Vec<EntityId>>(),
+            );
+        }
+        drop(dto_field_map);
+        let ids: Vec<EntityId> = entities.iter().map(|e| e.id).collect();
+        let result = self.get_multi(&ids)?;
+        Ok(result.into_iter().flatten().collect())
+    }
+
+    fn remove(&mut self, id: &EntityId) -> Result<(), RepositoryError> {
+        self.remove_multi(std::slice::from_ref(id))
+    }
+
+    fn remove_multi(&mut self, ids: &[EntityId]) -> Result<(), RepositoryError> {
+        let mut dto_field_map = self.store.dto_fields.write().unwrap();
+        for id in ids {
+            dto_field_map.remove(id);
+
+            // Remove forward junction entries
+
+            junction_remove(&self.store.jn_dto_from_dto_field_dto, id);
+
+            // Clean up backward references (uses the owning entity's forward junction)
+
+            delete_from_backward_junction(&self.store.jn_dto_field_from_dto_fields, id);
+        }
+        Ok(())
+    }
+
+    impl_write_relationship_methods!(DtoFieldHashMapTable<'a>, DtoFieldRelationshipField);
+}
+
+pub struct DtoFieldHashMapTableRO<'a> {
+    store: &'a HashMapStore,
+}
+
+impl<'a> DtoFieldHashMapTableRO<'a> {
+    pub fn new(store: &'a HashMapStore) -> Self {
+        Self { store }
+    }
+
+    fn resolve_junction(
+        &self,
+        field: &DtoFieldRelationshipField,
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
+        match field {
+            DtoFieldRelationshipField::Dto => &self.store.jn_dto_from_dto_field_dto,
+        }
+    }
+
+    fn hydrate(&self, entity: &mut DtoField) {
+        entity.dto = junction_get(&self.store.jn_dto_from_dto_field_dto, &entity.id)
+            .into_iter()
+            .next();
+    }
+}
+
+impl<'a> DtoFieldTableRO for DtoFieldHashMapTableRO<'a> {
+    fn get(&self, id: &EntityId) -> Result<Option<DtoField>, RepositoryError> {
+        let dto_field_map = self.store.dto_fields.read().unwrap();
+        match dto_field_map.get(id) {
+            Some(entity) => {
+                let mut e = entity.clone();
+                drop(dto_field_map);
+                self.hydrate(&mut e);
+                Ok(Some(e))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<DtoField>>, RepositoryError> {
+        let mut result = Vec::with_capacity(ids.len());
+        for id in ids {
+            result.push(self.get(id)?);
+        }
+        Ok(result)
+    }
+
+    fn get_all(&self) -> Result<Vec<DtoField>, RepositoryError> {
+        let dto_field_map = self.store.dto_fields.read().unwrap();
+        let entries: Vec<DtoField> = dto_field_map.values().cloned().collect();
+        drop(dto_field_map);
+        let mut result = Vec::with_capacity(entries.len());
+        for mut entity in entries {
+            self.hydrate(&mut entity);
+            result.push(entity);
+        }
+        Ok(result)
+    }
+
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &DtoFieldQuery) -> Result<Vec<DtoField>, RepositoryError> {
+        let dto_field_map = self.store.dto_fields.read().unwrap();
+        let matching: Vec<DtoField> = dto_field_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(dto_field_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &DtoFieldQuery) -> Result<usize, RepositoryError> {
+        let dto_field_map = self.store.dto_fields.read().unwrap();
+        Ok(dto_field_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
+    impl_relationship_methods!(DtoFieldHashMapTableRO<'a>, DtoFieldRelationshipField);
+}
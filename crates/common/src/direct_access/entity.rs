@@ -3,3 +3,4 @@
 pub mod entity_repository;
 pub(super) mod entity_table;
 pub use entity_repository::EntityRelationshipField;
+pub use entity_repository::{EntityFilter, EntityQuery, EntitySortField};
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::Entity,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::workspace::WorkspaceRelationshipField;
@@ -28,12 +29,151 @@ impl Display for EntityRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a Entity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EntityFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Name(TextCondition),
+    OnlyForHeritage(Condition<bool>),
+    SingleModel(Condition<bool>),
+    Undoable(Condition<bool>),
+    UndoJournal(Condition<bool>),
+    SourceFile(TextCondition),
+}
+
+impl EntityFilter {
+    pub fn matches(&self, entity: &Entity) -> bool {
+        match self {
+            EntityFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            EntityFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            EntityFilter::Name(condition) => condition.matches(&entity.name),
+            EntityFilter::OnlyForHeritage(condition) => {
+                condition.matches(&entity.only_for_heritage)
+            }
+            EntityFilter::SingleModel(condition) => condition.matches(&entity.single_model),
+            EntityFilter::Undoable(condition) => condition.matches(&entity.undoable),
+            EntityFilter::UndoJournal(condition) => condition.matches(&entity.undo_journal),
+            EntityFilter::SourceFile(condition) => condition.matches(&entity.source_file),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntitySortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Name,
+    OnlyForHeritage,
+    SingleModel,
+    Undoable,
+    UndoJournal,
+    SourceFile,
+}
+
+/// Query on the Entitys: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntityQuery {
+    pub filters: Vec<EntityFilter>,
+    pub sort: Vec<(EntitySortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl EntityQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: EntityFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: EntitySortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &Entity) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<Entity>) -> Vec<Entity> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    EntitySortField::Id => a.id.cmp(&b.id),
+                    EntitySortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    EntitySortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    EntitySortField::Name => a.name.partial_cmp(&b.name).unwrap_or(Ordering::Equal),
+                    EntitySortField::OnlyForHeritage => a
+                        .only_for_heritage
+                        .partial_cmp(&b.only_for_heritage)
+                        .unwrap_or(Ordering::Equal),
+                    EntitySortField::SingleModel => a
+                        .single_model
+                        .partial_cmp(&b.single_model)
+                        .unwrap_or(Ordering::Equal),
+                    EntitySortField::Undoable => a
+                        .undoable
+                        .partial_cmp(&b.undoable)
+                        .unwrap_or(Ordering::Equal),
+                    EntitySortField::UndoJournal => a
+                        .undo_journal
+                        .partial_cmp(&b.undo_journal)
+                        .unwrap_or(Ordering::Equal),
+                    EntitySortField::SourceFile => a
+                        .source_file
+                        .partial_cmp(&b.source_file)
+                        .unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait EntityTable {
     fn create(&mut self, entity: &Entity) -> Result<Entity, RepositoryError>;
     fn create_multi(&mut self, entities: &[Entity]) -> Result<Vec<Entity>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<Entity>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Entity>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Entity>, RepositoryError>;
+    fn query(&self, query: &EntityQuery) -> Result<Vec<Entity>, RepositoryError>;
+    fn count(&self, query: &EntityQuery) -> Result<usize, RepositoryError>;
+    fn get_by_name(&self, name: &str) -> Result<Vec<Entity>, RepositoryError>;
     fn update(&mut self, entity: &Entity) -> Result<Entity, RepositoryError>;
     fn update_multi(&mut self, entities: &[Entity]) -> Result<Vec<Entity>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &Entity) -> Result<Entity, RepositoryError>;
@@ -94,6 +234,9 @@ pub trait EntityTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<Entity>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Entity>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Entity>, RepositoryError>;
+    fn query(&self, query: &EntityQuery) -> Result<Vec<Entity>, RepositoryError>;
+    fn count(&self, query: &EntityQuery) -> Result<usize, RepositoryError>;
+    fn get_by_name(&self, name: &str) -> Result<Vec<Entity>, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -224,6 +367,15 @@ impl<'a> EntityRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<Entity>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &EntityQuery) -> Result<Vec<Entity>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &EntityQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
+    pub fn get_by_name(&self, name: &str) -> Result<Vec<Entity>, RepositoryError> {
+        self.table.get_by_name(name)
+    }
 
     pub fn update(
         &mut self,
@@ -673,6 +825,7 @@ impl<'a> EntityRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -681,6 +834,12 @@ impl<'a> EntityRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -755,6 +914,15 @@ impl<'a> EntityRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<Entity>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &EntityQuery) -> Result<Vec<Entity>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &EntityQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
+    pub fn get_by_name(&self, name: &str) -> Result<Vec<Entity>, RepositoryError> {
+        self.table.get_by_name(name)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
@@ -5,15 +5,17 @@
 // ═══════════════════════════════════════════════════════════════════════
 
 use crate::database::hashmap_store::{
-    HashMapStore, delete_from_backward_junction, junction_get, junction_remove, junction_set,
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
 };
+use crate::database::hashmap_store::{index_get, index_insert, index_remove};
 use crate::entities::Entity;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::entity_repository::EntityQuery;
 use super::entity_repository::EntityRelationshipField;
 use super::entity_repository::EntityTable;
 use super::entity_repository::EntityTableRO;
@@ -30,7 +32,7 @@ impl<'a> EntityHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &EntityRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             EntityRelationshipField::Fields => &self.store.jn_field_from_entity_fields,
             EntityRelationshipField::InheritsFrom => {
@@ -83,6 +85,11 @@ impl<'a> EntityTable for EntityHashMapTable<'a> {
             };
 
             entity_map.insert(new_entity.id, new_entity.clone());
+            index_insert(
+                &self.store.idx_entity_name,
+                new_entity.name.clone(),
+                new_entity.id,
+            );
 
             junction_set(
                 &self.store.jn_field_from_entity_fields,
@@ -141,6 +148,35 @@ impl<'a> EntityTable for EntityHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &EntityQuery) -> Result<Vec<Entity>, RepositoryError> {
+        let entity_map = self.store.entitys.read().unwrap();
+        let matching: Vec<Entity> = entity_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(entity_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &EntityQuery) -> Result<usize, RepositoryError> {
+        let entity_map = self.store.entitys.read().unwrap();
+        Ok(entity_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
+    fn get_by_name(&self, name: &str) -> Result<Vec<Entity>, RepositoryError> {
+        let ids = index_get(&self.store.idx_entity_name, name);
+        Ok(self.get_multi(&ids)?.into_iter().flatten().collect())
+    }
+
     fn update(&mut self, entity: &Entity) -> Result<Entity, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -149,8 +185,15 @@ impl<'a> EntityTable for EntityHashMapTable<'a> {
     // Scalar-only update: writes entity data but does NOT touch junction tables.
     fn update_multi(&mut self, entities: &[Entity]) -> Result<Vec<Entity>, RepositoryError> {
         let mut entity_map = self.store.entitys.write().unwrap();
+        // Drop the previous index keys first, so that values can be swapped within one batch
+        for entity in entities {
+            if let Some(previous) = entity_map.get(&entity.id) {
+                index_remove(&self.store.idx_entity_name, &previous.name, &entity.id);
+            }
+        }
         for entity in entities {
             entity_map.insert(entity.id, entity.clone());
+            index_insert(&self.store.idx_entity_name, entity.name.clone(), entity.id);
         }
         drop(entity_map);
         let ids: Vec<EntityId> = entities.iter().map(|e| e.id).collect();
@@ -168,8 +211,15 @@ impl<'a> EntityTable for EntityHashMapTable<'a> {
         entities: &[Entity],
     ) -> Result<Vec<Entity>, RepositoryError> {
         let mut entity_map = self.store.entitys.write().unwrap();
+        // Drop the previous index keys first, so that values can be swapped within one batch
+        for entity in entities {
+            if let Some(previous) = entity_map.get(&entity.id) {
+                index_remove(&self.store.idx_entity_name, &previous.name, &entity.id);
+            }
+        }
         for entity in entities {
             entity_map.insert(entity.id, entity.clone());
+            index_insert(&self.store.idx_entity_name, entity.name.clone(), entity.id);
 
             junction_set(
                 &self.store.jn_entity_from_entity_inherits_from,
@@ -200,7 +250,9 @@ impl<'a> EntityTable for EntityHashMapTable<'a> {
     fn remove_multi(&mut self, ids: &[EntityId]) -> Result<(), RepositoryError> {
         let mut entity_map = self.store.entitys.write().unwrap();
         for id in ids {
-            entity_map.remove(id);
+            if let Some(removed) = entity_map.remove(id) {
+                index_remove(&self.store.idx_entity_name, &removed.name, id);
+            }
 
             // Remove forward junction entries
 
@@ -237,7 +289,7 @@ impl<'a> EntityHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &EntityRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             EntityRelationshipField::Fields => &self.store.jn_field_from_entity_fields,
             EntityRelationshipField::InheritsFrom => {
@@ -296,5 +348,34 @@ impl<'a> EntityTableRO for EntityHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &EntityQuery) -> Result<Vec<Entity>, RepositoryError> {
+        let entity_map = self.store.entitys.read().unwrap();
+        let matching: Vec<Entity> = entity_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(entity_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &EntityQuery) -> Result<usize, RepositoryError> {
+        let entity_map = self.store.entitys.read().unwrap();
+        Ok(entity_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
+    fn get_by_name(&self, name: &str) -> Result<Vec<Entity>, RepositoryError> {
+        let ids = index_get(&self.store.idx_entity_name, name);
+        Ok(self.get_multi(&ids)?.into_iter().flatten().collect())
+    }
+
     impl_relationship_methods!(EntityHashMapTableRO<'a>, EntityRelationshipField);
 }
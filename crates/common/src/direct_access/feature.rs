@@ -3,3 +3,4 @@
 pub mod feature_repository;
 pub(super) mod feature_table;
 pub use feature_repository::FeatureRelationshipField;
+pub use feature_repository::{FeatureFilter, FeatureQuery, FeatureSortField};
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::Feature,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::workspace::WorkspaceRelationshipField;
@@ -17,6 +18,7 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FeatureRelationshipField {
+    Dtos,
     UseCases,
 }
 
@@ -26,12 +28,122 @@ impl Display for FeatureRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a Feature.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FeatureFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Name(TextCondition),
+    SourceFile(TextCondition),
+}
+
+impl FeatureFilter {
+    pub fn matches(&self, entity: &Feature) -> bool {
+        match self {
+            FeatureFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            FeatureFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            FeatureFilter::Name(condition) => condition.matches(&entity.name),
+            FeatureFilter::SourceFile(condition) => condition.matches(&entity.source_file),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeatureSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Name,
+    SourceFile,
+}
+
+/// Query on the Features: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeatureQuery {
+    pub filters: Vec<FeatureFilter>,
+    pub sort: Vec<(FeatureSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl FeatureQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: FeatureFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: FeatureSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &Feature) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<Feature>) -> Vec<Feature> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    FeatureSortField::Id => a.id.cmp(&b.id),
+                    FeatureSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    FeatureSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    FeatureSortField::Name => {
+                        a.name.partial_cmp(&b.name).unwrap_or(Ordering::Equal)
+                    }
+                    FeatureSortField::SourceFile => a
+                        .source_file
+                        .partial_cmp(&b.source_file)
+                        .unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait FeatureTable {
     fn create(&mut self, entity: &Feature) -> Result<Feature, RepositoryError>;
     fn create_multi(&mut self, entities: &[Feature]) -> Result<Vec<Feature>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<Feature>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Feature>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Feature>, RepositoryError>;
+    fn query(&self, query: &FeatureQuery) -> Result<Vec<Feature>, RepositoryError>;
+    fn count(&self, query: &FeatureQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &Feature) -> Result<Feature, RepositoryError>;
     fn update_multi(&mut self, entities: &[Feature]) -> Result<Vec<Feature>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &Feature) -> Result<Feature, RepositoryError>;
@@ -92,6 +204,8 @@ pub trait FeatureTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<Feature>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Feature>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Feature>, RepositoryError>;
+    fn query(&self, query: &FeatureQuery) -> Result<Vec<Feature>, RepositoryError>;
+    fn count(&self, query: &FeatureQuery) -> Result<usize, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -222,6 +336,12 @@ impl<'a> FeatureRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<Feature>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &FeatureQuery) -> Result<Vec<Feature>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &FeatureQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -450,6 +570,23 @@ impl<'a> FeatureRepository<'a> {
             .collect();
         if !all_right_ids.is_empty() {
             match field {
+                FeatureRelationshipField::Dtos => {
+                    let child_repo =
+                        repository_factory::write::create_dto_repository(self.transaction)?;
+                    let found = child_repo.get_multi(&all_right_ids)?;
+                    let missing: Vec<_> = all_right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship_multi",
+                            ids: missing,
+                        });
+                    }
+                }
                 FeatureRelationshipField::UseCases => {
                     let child_repo =
                         repository_factory::write::create_use_case_repository(self.transaction)?;
@@ -499,6 +636,23 @@ impl<'a> FeatureRepository<'a> {
         // Validate that all right_ids exist
         if !right_ids.is_empty() {
             match field {
+                FeatureRelationshipField::Dtos => {
+                    let child_repo =
+                        repository_factory::write::create_dto_repository(self.transaction)?;
+                    let found = child_repo.get_multi(right_ids)?;
+                    let missing: Vec<_> = right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship",
+                            ids: missing,
+                        });
+                    }
+                }
                 FeatureRelationshipField::UseCases => {
                     let child_repo =
                         repository_factory::write::create_use_case_repository(self.transaction)?;
@@ -588,6 +742,7 @@ impl<'a> FeatureRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -596,6 +751,12 @@ impl<'a> FeatureRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -656,6 +817,12 @@ impl<'a> FeatureRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<Feature>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &FeatureQuery) -> Result<Vec<Feature>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &FeatureQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
@@ -5,15 +5,16 @@
 // ═══════════════════════════════════════════════════════════════════════
 
 use crate::database::hashmap_store::{
-    HashMapStore, delete_from_backward_junction, junction_get, junction_remove, junction_set,
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
 };
 use crate::entities::Feature;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::feature_repository::FeatureQuery;
 use super::feature_repository::FeatureRelationshipField;
 use super::feature_repository::FeatureTable;
 use super::feature_repository::FeatureTableRO;
@@ -30,14 +31,16 @@ impl<'a> FeatureHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &FeatureRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
+            FeatureRelationshipField::Dtos => &self.store.jn_dto_from_feature_dtos,
             FeatureRelationshipField::UseCases => &self.store.jn_use_case_from_feature_use_cases,
         }
     }
 
     fn hydrate(&self, entity: &mut Feature) {
         entity.use_cases = junction_get(&self.store.jn_use_case_from_feature_use_cases, &entity.id);
+        entity.dtos = junction_get(&self.store.jn_dto_from_feature_dtos, &entity.id);
     }
 }
 
@@ -70,6 +73,11 @@ impl<'a> FeatureTable for FeatureHashMapTable<'a> {
 
             feature_map.insert(new_entity.id, new_entity.clone());
 
+            junction_set(
+                &self.store.jn_dto_from_feature_dtos,
+                new_entity.id,
+                new_entity.dtos.clone(),
+            );
             junction_set(
                 &self.store.jn_use_case_from_feature_use_cases,
                 new_entity.id,
@@ -114,6 +122,30 @@ impl<'a> FeatureTable for FeatureHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &FeatureQuery) -> Result<Vec<Feature>, RepositoryError> {
+        let feature_map = self.store.features.read().unwrap();
+        let matching: Vec<Feature> = feature_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(feature_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &FeatureQuery) -> Result<usize, RepositoryError> {
+        let feature_map = self.store.features.read().unwrap();
+        Ok(feature_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     fn update(&mut self, entity: &Feature) -> Result<Feature, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -149,6 +181,11 @@ impl<'a> FeatureTable for FeatureHashMapTable<'a> {
                 entity.id,
                 entity.use_cases.clone(),
             );
+            junction_set(
+                &self.store.jn_dto_from_feature_dtos,
+                entity.id,
+                entity.dtos.clone(),
+            );
         }
         drop(feature_map);
         let ids: Vec<EntityId> = entities.iter().map(|e| e.id).collect();
@@ -168,6 +205,7 @@ impl<'a> FeatureTable for FeatureHashMapTable<'a> {
             // Remove forward junction entries
 
             junction_remove(&self.store.jn_use_case_from_feature_use_cases, id);
+            junction_remove(&self.store.jn_dto_from_feature_dtos, id);
 
             // Clean up backward references (uses the owning entity's forward junction)
 
@@ -192,14 +230,16 @@ impl<'a> FeatureHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &FeatureRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
+            FeatureRelationshipField::Dtos => &self.store.jn_dto_from_feature_dtos,
             FeatureRelationshipField::UseCases => &self.store.jn_use_case_from_feature_use_cases,
         }
     }
 
     fn hydrate(&self, entity: &mut Feature) {
         entity.use_cases = junction_get(&self.store.jn_use_case_from_feature_use_cases, &entity.id);
+        entity.dtos = junction_get(&self.store.jn_dto_from_feature_dtos, &entity.id);
     }
 }
 
@@ -237,5 +277,29 @@ impl<'a> FeatureTableRO for FeatureHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &FeatureQuery) -> Result<Vec<Feature>, RepositoryError> {
+        let feature_map = self.store.features.read().unwrap();
+        let matching: Vec<Feature> = feature_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(feature_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &FeatureQuery) -> Result<usize, RepositoryError> {
+        let feature_map = self.store.features.read().unwrap();
+        Ok(feature_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     impl_relationship_methods!(FeatureHashMapTableRO<'a>, FeatureRelationshipField);
 }
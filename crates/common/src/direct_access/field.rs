@@ -3,3 +3,4 @@
 pub mod field_repository;
 pub(super) mod field_table;
 pub use field_repository::FieldRelationshipField;
+pub use field_repository::{FieldFilter, FieldQuery, FieldSortField};
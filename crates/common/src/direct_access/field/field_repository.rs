@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::Field,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::entity::EntityRelationshipField;
@@ -26,12 +27,248 @@ impl Display for FieldRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a Field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FieldFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Name(TextCondition),
+    FieldType(Condition<crate::entities::FieldType>),
+    Relationship(Condition<crate::entities::FieldRelationshipType>),
+    Optional(Condition<bool>),
+    IsList(Condition<bool>),
+    Strong(Condition<bool>),
+    ListModel(Condition<bool>),
+    ListModelDisplayedField(TextCondition),
+    EnumName(TextCondition),
+    MinValue(TextCondition),
+    MaxValue(TextCondition),
+    MinLength(Condition<i64>),
+    MaxLength(Condition<i64>),
+    Pattern(TextCondition),
+    NotEmpty(Condition<bool>),
+    UniqueInParent(Condition<bool>),
+    Indexed(Condition<bool>),
+    Unique(Condition<bool>),
+}
+
+impl FieldFilter {
+    pub fn matches(&self, entity: &Field) -> bool {
+        match self {
+            FieldFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            FieldFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            FieldFilter::Name(condition) => condition.matches(&entity.name),
+            FieldFilter::FieldType(condition) => condition.matches(&entity.field_type),
+            FieldFilter::Relationship(condition) => condition.matches(&entity.relationship),
+            FieldFilter::Optional(condition) => condition.matches(&entity.optional),
+            FieldFilter::IsList(condition) => condition.matches(&entity.is_list),
+            FieldFilter::Strong(condition) => condition.matches(&entity.strong),
+            FieldFilter::ListModel(condition) => condition.matches(&entity.list_model),
+            FieldFilter::ListModelDisplayedField(condition) => entity
+                .list_model_displayed_field
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            FieldFilter::EnumName(condition) => entity
+                .enum_name
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            FieldFilter::MinValue(condition) => entity
+                .min_value
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            FieldFilter::MaxValue(condition) => entity
+                .max_value
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            FieldFilter::MinLength(condition) => entity
+                .min_length
+                .as_ref()
+                .is_some_and(|value| condition.matches(value)),
+            FieldFilter::MaxLength(condition) => entity
+                .max_length
+                .as_ref()
+                .is_some_and(|value| condition.matches(value)),
+            FieldFilter::Pattern(condition) => entity
+                .pattern
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            FieldFilter::NotEmpty(condition) => condition.matches(&entity.not_empty),
+            FieldFilter::UniqueInParent(condition) => condition.matches(&entity.unique_in_parent),
+            FieldFilter::Indexed(condition) => condition.matches(&entity.indexed),
+            FieldFilter::Unique(condition) => condition.matches(&entity.unique),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Name,
+    FieldType,
+    Relationship,
+    Optional,
+    IsList,
+    Strong,
+    ListModel,
+    ListModelDisplayedField,
+    EnumName,
+    MinValue,
+    MaxValue,
+    MinLength,
+    MaxLength,
+    Pattern,
+    NotEmpty,
+    UniqueInParent,
+    Indexed,
+    Unique,
+}
+
+/// Query on the Fields: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldQuery {
+    pub filters: Vec<FieldFilter>,
+    pub sort: Vec<(FieldSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl FieldQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: FieldFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: FieldSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &Field) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<Field>) -> Vec<Field> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    FieldSortField::Id => a.id.cmp(&b.id),
+                    FieldSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::Name => a.name.partial_cmp(&b.name).unwrap_or(Ordering::Equal),
+                    FieldSortField::FieldType => a
+                        .field_type
+                        .partial_cmp(&b.field_type)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::Relationship => a
+                        .relationship
+                        .partial_cmp(&b.relationship)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::Optional => a
+                        .optional
+                        .partial_cmp(&b.optional)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::IsList => {
+                        a.is_list.partial_cmp(&b.is_list).unwrap_or(Ordering::Equal)
+                    }
+                    FieldSortField::Strong => {
+                        a.strong.partial_cmp(&b.strong).unwrap_or(Ordering::Equal)
+                    }
+                    FieldSortField::ListModel => a
+                        .list_model
+                        .partial_cmp(&b.list_model)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::ListModelDisplayedField => a
+                        .list_model_displayed_field
+                        .partial_cmp(&b.list_model_displayed_field)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::EnumName => a
+                        .enum_name
+                        .partial_cmp(&b.enum_name)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::MinValue => a
+                        .min_value
+                        .partial_cmp(&b.min_value)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::MaxValue => a
+                        .max_value
+                        .partial_cmp(&b.max_value)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::MinLength => a
+                        .min_length
+                        .partial_cmp(&b.min_length)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::MaxLength => a
+                        .max_length
+                        .partial_cmp(&b.max_length)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::Pattern => {
+                        a.pattern.partial_cmp(&b.pattern).unwrap_or(Ordering::Equal)
+                    }
+                    FieldSortField::NotEmpty => a
+                        .not_empty
+                        .partial_cmp(&b.not_empty)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::UniqueInParent => a
+                        .unique_in_parent
+                        .partial_cmp(&b.unique_in_parent)
+                        .unwrap_or(Ordering::Equal),
+                    FieldSortField::Indexed => {
+                        a.indexed.partial_cmp(&b.indexed).unwrap_or(Ordering::Equal)
+                    }
+                    FieldSortField::Unique => {
+                        a.unique.partial_cmp(&b.unique).unwrap_or(Ordering::Equal)
+                    }
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait FieldTable {
     fn create(&mut self, entity: &Field) -> Result<Field, RepositoryError>;
     fn create_multi(&mut self, entities: &[Field]) -> Result<Vec<Field>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<Field>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Field>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Field>, RepositoryError>;
+    fn query(&self, query: &FieldQuery) -> Result<Vec<Field>, RepositoryError>;
+    fn count(&self, query: &FieldQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &Field) -> Result<Field, RepositoryError>;
     fn update_multi(&mut self, entities: &[Field]) -> Result<Vec<Field>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &Field) -> Result<Field, RepositoryError>;
@@ -92,6 +329,8 @@ pub trait FieldTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<Field>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Field>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Field>, RepositoryError>;
+    fn query(&self, query: &FieldQuery) -> Result<Vec<Field>, RepositoryError>;
+    fn count(&self, query: &FieldQuery) -> Result<usize, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -222,6 +461,12 @@ impl<'a> FieldRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<Field>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &FieldQuery) -> Result<Vec<Field>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &FieldQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -571,6 +816,7 @@ impl<'a> FieldRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -579,6 +825,12 @@ impl<'a> FieldRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -627,6 +879,12 @@ impl<'a> FieldRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<Field>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &FieldQuery) -> Result<Vec<Field>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &FieldQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
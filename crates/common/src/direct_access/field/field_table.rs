@@ -5,15 +5,16 @@
 // ═══════════════════════════════════════════════════════════════════════
 
 use crate::database::hashmap_store::{
-    HashMapStore, delete_from_backward_junction, junction_get, junction_remove, junction_set,
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
 };
 use crate::entities::Field;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::field_repository::FieldQuery;
 use super::field_repository::FieldRelationshipField;
 use super::field_repository::FieldTable;
 use super::field_repository::FieldTableRO;
@@ -30,7 +31,7 @@ impl<'a> FieldHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &FieldRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             FieldRelationshipField::Entity => &self.store.jn_entity_from_field_entity,
         }
@@ -116,6 +117,30 @@ impl<'a> FieldTable for FieldHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &FieldQuery) -> Result<Vec<Field>, RepositoryError> {
+        let field_map = self.store.fields.read().unwrap();
+        let matching: Vec<Field> = field_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(field_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &FieldQuery) -> Result<usize, RepositoryError> {
+        let field_map = self.store.fields.read().unwrap();
+        Ok(field_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     fn update(&mut self, entity: &Field) -> Result<Field, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -194,7 +219,7 @@ impl<'a> FieldHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &FieldRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             FieldRelationshipField::Entity => &self.store.jn_entity_from_field_entity,
         }
@@ -241,5 +266,29 @@ impl<'a> FieldTableRO for FieldHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &FieldQuery) -> Result<Vec<Field>, RepositoryError> {
+        let field_map = self.store.fields.read().unwrap();
+        let matching: Vec<Field> = field_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(field_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &FieldQuery) -> Result<usize, RepositoryError> {
+        let field_map = self.store.fields.read().unwrap();
+        Ok(field_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     impl_relationship_methods!(FieldHashMapTableRO<'a>, FieldRelationshipField);
 }
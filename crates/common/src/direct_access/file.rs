@@ -3,3 +3,4 @@
 pub mod file_repository;
 pub(super) mod file_table;
 pub use file_repository::FileRelationshipField;
+pub use file_repository::{FileFilter, FileQuery, FileSortField};
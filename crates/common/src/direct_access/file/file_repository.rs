@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::File,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::system::SystemRelationshipField;
@@ -29,12 +30,177 @@ impl Display for FileRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a File.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FileFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Name(TextCondition),
+    RelativePath(TextCondition),
+    Group(TextCondition),
+    TemplateName(TextCondition),
+    GeneratedCode(TextCondition),
+    Status(Condition<crate::entities::FileStatus>),
+    Nature(Condition<crate::entities::FileNature>),
+    AllFeatures(Condition<bool>),
+    AllEntities(Condition<bool>),
+    AllUseCases(Condition<bool>),
+}
+
+impl FileFilter {
+    pub fn matches(&self, entity: &File) -> bool {
+        match self {
+            FileFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            FileFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            FileFilter::Name(condition) => condition.matches(&entity.name),
+            FileFilter::RelativePath(condition) => condition.matches(&entity.relative_path),
+            FileFilter::Group(condition) => condition.matches(&entity.group),
+            FileFilter::TemplateName(condition) => condition.matches(&entity.template_name),
+            FileFilter::GeneratedCode(condition) => entity
+                .generated_code
+                .as_deref()
+                .is_some_and(|value| condition.matches(value)),
+            FileFilter::Status(condition) => condition.matches(&entity.status),
+            FileFilter::Nature(condition) => condition.matches(&entity.nature),
+            FileFilter::AllFeatures(condition) => condition.matches(&entity.all_features),
+            FileFilter::AllEntities(condition) => condition.matches(&entity.all_entities),
+            FileFilter::AllUseCases(condition) => condition.matches(&entity.all_use_cases),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Name,
+    RelativePath,
+    Group,
+    TemplateName,
+    GeneratedCode,
+    Status,
+    Nature,
+    AllFeatures,
+    AllEntities,
+    AllUseCases,
+}
+
+/// Query on the Files: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileQuery {
+    pub filters: Vec<FileFilter>,
+    pub sort: Vec<(FileSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl FileQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: FileSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &File) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<File>) -> Vec<File> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    FileSortField::Id => a.id.cmp(&b.id),
+                    FileSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    FileSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    FileSortField::Name => a.name.partial_cmp(&b.name).unwrap_or(Ordering::Equal),
+                    FileSortField::RelativePath => a
+                        .relative_path
+                        .partial_cmp(&b.relative_path)
+                        .unwrap_or(Ordering::Equal),
+                    FileSortField::Group => {
+                        a.group.partial_cmp(&b.group).unwrap_or(Ordering::Equal)
+                    }
+                    FileSortField::TemplateName => a
+                        .template_name
+                        .partial_cmp(&b.template_name)
+                        .unwrap_or(Ordering::Equal),
+                    FileSortField::GeneratedCode => a
+                        .generated_code
+                        .partial_cmp(&b.generated_code)
+                        .unwrap_or(Ordering::Equal),
+                    FileSortField::Status => {
+                        a.status.partial_cmp(&b.status).unwrap_or(Ordering::Equal)
+                    }
+                    FileSortField::Nature => {
+                        a.nature.partial_cmp(&b.nature).unwrap_or(Ordering::Equal)
+                    }
+                    FileSortField::AllFeatures => a
+                        .all_features
+                        .partial_cmp(&b.all_features)
+                        .unwrap_or(Ordering::Equal),
+                    FileSortField::AllEntities => a
+                        .all_entities
+                        .partial_cmp(&b.all_entities)
+                        .unwrap_or(Ordering::Equal),
+                    FileSortField::AllUseCases => a
+                        .all_use_cases
+                        .partial_cmp(&b.all_use_cases)
+                        .unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait FileTable {
     fn create(&mut self, entity: &File) -> Result<File, RepositoryError>;
     fn create_multi(&mut self, entities: &[File]) -> Result<Vec<File>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<File>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<File>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<File>, RepositoryError>;
+    fn query(&self, query: &FileQuery) -> Result<Vec<File>, RepositoryError>;
+    fn count(&self, query: &FileQuery) -> Result<usize, RepositoryError>;
+    fn get_by_relative_path(&self, relative_path: &str) -> Result<Vec<File>, RepositoryError>;
     fn update(&mut self, entity: &File) -> Result<File, RepositoryError>;
     fn update_multi(&mut self, entities: &[File]) -> Result<Vec<File>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &File) -> Result<File, RepositoryError>;
@@ -95,6 +261,9 @@ pub trait FileTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<File>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<File>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<File>, RepositoryError>;
+    fn query(&self, query: &FileQuery) -> Result<Vec<File>, RepositoryError>;
+    fn count(&self, query: &FileQuery) -> Result<usize, RepositoryError>;
+    fn get_by_relative_path(&self, relative_path: &str) -> Result<Vec<File>, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -225,6 +394,15 @@ impl<'a> FileRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<File>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &FileQuery) -> Result<Vec<File>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &FileQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
+    pub fn get_by_relative_path(&self, relative_path: &str) -> Result<Vec<File>, RepositoryError> {
+        self.table.get_by_relative_path(relative_path)
+    }
 
     pub fn update(
         &mut self,
@@ -671,6 +849,7 @@ impl<'a> FileRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -679,6 +858,12 @@ impl<'a> FileRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -727,6 +912,15 @@ impl<'a> FileRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<File>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &FileQuery) -> Result<Vec<File>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &FileQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
+    pub fn get_by_relative_path(&self, relative_path: &str) -> Result<Vec<File>, RepositoryError> {
+        self.table.get_by_relative_path(relative_path)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
@@ -5,15 +5,17 @@
 // ═══════════════════════════════════════════════════════════════════════
 
 use crate::database::hashmap_store::{
-    HashMapStore, delete_from_backward_junction, junction_get, junction_remove, junction_set,
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
 };
+use crate::database::hashmap_store::{index_get, index_insert, index_remove};
 use crate::entities::File;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::file_repository::FileQuery;
 use super::file_repository::FileRelationshipField;
 use super::file_repository::FileTable;
 use super::file_repository::FileTableRO;
@@ -30,7 +32,7 @@ impl<'a> FileHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &FileRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             FileRelationshipField::Entity => &self.store.jn_entity_from_file_entity,
             FileRelationshipField::Feature => &self.store.jn_feature_from_file_feature,
@@ -83,6 +85,11 @@ impl<'a> FileTable for FileHashMapTable<'a> {
             };
 
             file_map.insert(new_entity.id, new_entity.clone());
+            index_insert(
+                &self.store.idx_file_relative_path,
+                new_entity.relative_path.clone(),
+                new_entity.id,
+            );
 
             junction_set(
                 &self.store.jn_entity_from_file_entity,
@@ -143,6 +150,35 @@ impl<'a> FileTable for FileHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &FileQuery) -> Result<Vec<File>, RepositoryError> {
+        let file_map = self.store.files.read().unwrap();
+        let matching: Vec<File> = file_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(file_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &FileQuery) -> Result<usize, RepositoryError> {
+        let file_map = self.store.files.read().unwrap();
+        Ok(file_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
+    fn get_by_relative_path(&self, relative_path: &str) -> Result<Vec<File>, RepositoryError> {
+        let ids = index_get(&self.store.idx_file_relative_path, relative_path);
+        Ok(self.get_multi(&ids)?.into_iter().flatten().collect())
+    }
+
     fn update(&mut self, entity: &File) -> Result<File, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -151,8 +187,23 @@ impl<'a> FileTable for FileHashMapTable<'a> {
     // Scalar-only update: writes entity data but does NOT touch junction tables.
     fn update_multi(&mut self, entities: &[File]) -> Result<Vec<File>, RepositoryError> {
         let mut file_map = self.store.files.write().unwrap();
+        // Drop the previous index keys first, so that values can be swapped within one batch
+        for entity in entities {
+            if let Some(previous) = file_map.get(&entity.id) {
+                index_remove(
+                    &self.store.idx_file_relative_path,
+                    &previous.relative_path,
+                    &entity.id,
+                );
+            }
+        }
         for entity in entities {
             file_map.insert(entity.id, entity.clone());
+            index_insert(
+                &self.store.idx_file_relative_path,
+                entity.relative_path.clone(),
+                entity.id,
+            );
         }
         drop(file_map);
         let ids: Vec<EntityId> = entities.iter().map(|e| e.id).collect();
@@ -170,8 +221,23 @@ impl<'a> FileTable for FileHashMapTable<'a> {
         entities: &[File],
     ) -> Result<Vec<File>, RepositoryError> {
         let mut file_map = self.store.files.write().unwrap();
+        // Drop the previous index keys first, so that values can be swapped within one batch
+        for entity in entities {
+            if let Some(previous) = file_map.get(&entity.id) {
+                index_remove(
+                    &self.store.idx_file_relative_path,
+                    &previous.relative_path,
+                    &entity.id,
+                );
+            }
+        }
         for entity in entities {
             file_map.insert(entity.id, entity.clone());
+            index_insert(
+                &self.store.idx_file_relative_path,
+                entity.relative_path.clone(),
+                entity.id,
+            );
 
             junction_set(
                 &self.store.jn_feature_from_file_feature,
@@ -207,7 +273,13 @@ impl<'a> FileTable for FileHashMapTable<'a> {
     fn remove_multi(&mut self, ids: &[EntityId]) -> Result<(), RepositoryError> {
         let mut file_map = self.store.files.write().unwrap();
         for id in ids {
-            file_map.remove(id);
+            if let Some(removed) = file_map.remove(id) {
+                index_remove(
+                    &self.store.idx_file_relative_path,
+                    &removed.relative_path,
+                    id,
+                );
+            }
 
             // Remove forward junction entries
 
@@ -238,7 +310,7 @@ impl<'a> FileHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &FileRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             FileRelationshipField::Entity => &self.store.jn_entity_from_file_entity,
             FileRelationshipField::Feature => &self.store.jn_feature_from_file_feature,
@@ -297,5 +369,34 @@ impl<'a> FileTableRO for FileHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &FileQuery) -> Result<Vec<File>, RepositoryError> {
+        let file_map = self.store.files.read().unwrap();
+        let matching: Vec<File> = file_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(file_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &FileQuery) -> Result<usize, RepositoryError> {
+        let file_map = self.store.files.read().unwrap();
+        Ok(file_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
+    fn get_by_relative_path(&self, relative_path: &str) -> Result<Vec<File>, RepositoryError> {
+        let ids = index_get(&self.store.idx_file_relative_path, relative_path);
+        Ok(self.get_multi(&ids)?.into_iter().flatten().collect())
+    }
+
     impl_relationship_methods!(FileHashMapTableRO<'a>, FileRelationshipField);
 }
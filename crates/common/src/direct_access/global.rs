@@ -2,3 +2,4 @@
 
 pub mod global_repository;
 pub(super) mod global_table;
+pub use global_repository::{GlobalFilter, GlobalQuery, GlobalSortField};
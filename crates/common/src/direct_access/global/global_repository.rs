@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::Global,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::workspace::WorkspaceRelationshipField;
@@ -24,12 +25,161 @@ impl Display for GlobalRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a Global.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GlobalFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Language(TextCondition),
+    ApplicationName(TextCondition),
+    OrganisationName(TextCondition),
+    OrganisationDomain(TextCondition),
+    PrefixPath(TextCondition),
+    Storage(TextCondition),
+    TemplatesPath(TextCondition),
+}
+
+impl GlobalFilter {
+    pub fn matches(&self, entity: &Global) -> bool {
+        match self {
+            GlobalFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            GlobalFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            GlobalFilter::Language(condition) => condition.matches(&entity.language),
+            GlobalFilter::ApplicationName(condition) => condition.matches(&entity.application_name),
+            GlobalFilter::OrganisationName(condition) => {
+                condition.matches(&entity.organisation_name)
+            }
+            GlobalFilter::OrganisationDomain(condition) => {
+                condition.matches(&entity.organisation_domain)
+            }
+            GlobalFilter::PrefixPath(condition) => condition.matches(&entity.prefix_path),
+            GlobalFilter::Storage(condition) => condition.matches(&entity.storage),
+            GlobalFilter::TemplatesPath(condition) => condition.matches(&entity.templates_path),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GlobalSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Language,
+    ApplicationName,
+    OrganisationName,
+    OrganisationDomain,
+    PrefixPath,
+    Storage,
+    TemplatesPath,
+}
+
+/// Query on the Globals: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GlobalQuery {
+    pub filters: Vec<GlobalFilter>,
+    pub sort: Vec<(GlobalSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl GlobalQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: GlobalFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: GlobalSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &Global) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<Global>) -> Vec<Global> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    GlobalSortField::Id => a.id.cmp(&b.id),
+                    GlobalSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    GlobalSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    GlobalSortField::Language => a
+                        .language
+                        .partial_cmp(&b.language)
+                        .unwrap_or(Ordering::Equal),
+                    GlobalSortField::ApplicationName => a
+                        .application_name
+                        .partial_cmp(&b.application_name)
+                        .unwrap_or(Ordering::Equal),
+                    GlobalSortField::OrganisationName => a
+                        .organisation_name
+                        .partial_cmp(&b.organisation_name)
+                        .unwrap_or(Ordering::Equal),
+                    GlobalSortField::OrganisationDomain => a
+                        .organisation_domain
+                        .partial_cmp(&b.organisation_domain)
+                        .unwrap_or(Ordering::Equal),
+                    GlobalSortField::PrefixPath => a
+                        .prefix_path
+                        .partial_cmp(&b.prefix_path)
+                        .unwrap_or(Ordering::Equal),
+                    GlobalSortField::Storage => {
+                        a.storage.partial_cmp(&b.storage).unwrap_or(Ordering::Equal)
+                    }
+                    GlobalSortField::TemplatesPath => a
+                        .templates_path
+                        .partial_cmp(&b.templates_path)
+                        .unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait GlobalTable {
     fn create(&mut self, entity: &Global) -> Result<Global, RepositoryError>;
     fn create_multi(&mut self, entities: &[Global]) -> Result<Vec<Global>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<Global>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Global>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Global>, RepositoryError>;
+    fn query(&self, query: &GlobalQuery) -> Result<Vec<Global>, RepositoryError>;
+    fn count(&self, query: &GlobalQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &Global) -> Result<Global, RepositoryError>;
     fn update_multi(&mut self, entities: &[Global]) -> Result<Vec<Global>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &Global) -> Result<Global, RepositoryError>;
@@ -45,6 +195,8 @@ pub trait GlobalTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<Global>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Global>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Global>, RepositoryError>;
+    fn query(&self, query: &GlobalQuery) -> Result<Vec<Global>, RepositoryError>;
+    fn count(&self, query: &GlobalQuery) -> Result<usize, RepositoryError>;
 }
 
 pub struct GlobalRepository<'a> {
@@ -148,6 +300,12 @@ impl<'a> GlobalRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<Global>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &GlobalQuery) -> Result<Vec<Global>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &GlobalQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -335,6 +493,7 @@ impl<'a> GlobalRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -343,6 +502,12 @@ impl<'a> GlobalRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -390,4 +555,10 @@ impl<'a> GlobalRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<Global>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &GlobalQuery) -> Result<Vec<Global>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &GlobalQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 }
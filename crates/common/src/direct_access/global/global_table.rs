@@ -4,18 +4,22 @@
 // Leaf entity (no forward relationships) — macro implementation
 // ═══════════════════════════════════════════════════════════════════════
 
+use super::global_repository::GlobalQuery;
 use super::global_repository::GlobalTable;
 use super::global_repository::GlobalTableRO;
 use crate::entities::Global;
 
 crate::impl_leaf_entity_table!(
     entity: Global,
+    query: GlobalQuery,
     entity_name: "global",
     store_field: globals,
     table_trait: GlobalTable,
     table_ro_trait: GlobalTableRO,
     table_struct: GlobalHashMapTable,
     table_ro_struct: GlobalHashMapTableRO,
+    indexes: [],
+    unique_indexes: [],
     backward_junctions: [
 
         (jn_global_from_workspace_global),
@@ -3,3 +3,4 @@
 pub mod relationship_repository;
 pub(super) mod relationship_table;
 pub use relationship_repository::RelationshipRelationshipField;
+pub use relationship_repository::{RelationshipFilter, RelationshipQuery, RelationshipSortField};
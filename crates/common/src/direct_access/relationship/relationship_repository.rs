@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::Relationship,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::entity::EntityRelationshipField;
@@ -27,6 +28,147 @@ impl Display for RelationshipRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a Relationship.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RelationshipFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    FieldName(TextCondition),
+    RelationshipType(Condition<crate::entities::RelationshipType>),
+    Strength(Condition<crate::entities::Strength>),
+    Direction(Condition<crate::entities::Direction>),
+    Cardinality(Condition<crate::entities::Cardinality>),
+    Order(Condition<crate::entities::Order>),
+}
+
+impl RelationshipFilter {
+    pub fn matches(&self, entity: &Relationship) -> bool {
+        match self {
+            RelationshipFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            RelationshipFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            RelationshipFilter::FieldName(condition) => condition.matches(&entity.field_name),
+            RelationshipFilter::RelationshipType(condition) => {
+                condition.matches(&entity.relationship_type)
+            }
+            RelationshipFilter::Strength(condition) => condition.matches(&entity.strength),
+            RelationshipFilter::Direction(condition) => condition.matches(&entity.direction),
+            RelationshipFilter::Cardinality(condition) => condition.matches(&entity.cardinality),
+            RelationshipFilter::Order(condition) => entity
+                .order
+                .as_ref()
+                .is_some_and(|value| condition.matches(value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RelationshipSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    FieldName,
+    RelationshipType,
+    Strength,
+    Direction,
+    Cardinality,
+    Order,
+}
+
+/// Query on the Relationships: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RelationshipQuery {
+    pub filters: Vec<RelationshipFilter>,
+    pub sort: Vec<(RelationshipSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl RelationshipQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: RelationshipFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: RelationshipSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &Relationship) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<Relationship>) -> Vec<Relationship> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    RelationshipSortField::Id => a.id.cmp(&b.id),
+                    RelationshipSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    RelationshipSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    RelationshipSortField::FieldName => a
+                        .field_name
+                        .partial_cmp(&b.field_name)
+                        .unwrap_or(Ordering::Equal),
+                    RelationshipSortField::RelationshipType => a
+                        .relationship_type
+                        .partial_cmp(&b.relationship_type)
+                        .unwrap_or(Ordering::Equal),
+                    RelationshipSortField::Strength => a
+                        .strength
+                        .partial_cmp(&b.strength)
+                        .unwrap_or(Ordering::Equal),
+                    RelationshipSortField::Direction => a
+                        .direction
+                        .partial_cmp(&b.direction)
+                        .unwrap_or(Ordering::Equal),
+                    RelationshipSortField::Cardinality => a
+                        .cardinality
+                        .partial_cmp(&b.cardinality)
+                        .unwrap_or(Ordering::Equal),
+                    RelationshipSortField::Order => {
+                        a.order.partial_cmp(&b.order).unwrap_or(Ordering::Equal)
+                    }
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait RelationshipTable {
     fn create(&mut self, entity: &Relationship) -> Result<Relationship, RepositoryError>;
     fn create_multi(
@@ -36,6 +178,8 @@ pub trait RelationshipTable {
     fn get(&self, id: &EntityId) -> Result<Option<Relationship>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Relationship>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Relationship>, RepositoryError>;
+    fn query(&self, query: &RelationshipQuery) -> Result<Vec<Relationship>, RepositoryError>;
+    fn count(&self, query: &RelationshipQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &Relationship) -> Result<Relationship, RepositoryError>;
     fn update_multi(
         &mut self,
@@ -102,6 +246,8 @@ pub trait RelationshipTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<Relationship>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Relationship>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Relationship>, RepositoryError>;
+    fn query(&self, query: &RelationshipQuery) -> Result<Vec<Relationship>, RepositoryError>;
+    fn count(&self, query: &RelationshipQuery) -> Result<usize, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -235,6 +381,12 @@ impl<'a> RelationshipRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<Relationship>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &RelationshipQuery) -> Result<Vec<Relationship>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &RelationshipQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -620,6 +772,7 @@ impl<'a> RelationshipRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -628,6 +781,12 @@ impl<'a> RelationshipRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -688,6 +847,12 @@ impl<'a> RelationshipRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<Relationship>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &RelationshipQuery) -> Result<Vec<Relationship>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &RelationshipQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
@@ -5,15 +5,16 @@
 // ═══════════════════════════════════════════════════════════════════════
 
 use crate::database::hashmap_store::{
-    HashMapStore, delete_from_backward_junction, junction_get, junction_remove, junction_set,
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
 };
 use crate::entities::Relationship;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::relationship_repository::RelationshipQuery;
 use super::relationship_repository::RelationshipRelationshipField;
 use super::relationship_repository::RelationshipTable;
 use super::relationship_repository::RelationshipTableRO;
@@ -30,7 +31,7 @@ impl<'a> RelationshipHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &RelationshipRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             RelationshipRelationshipField::LeftEntity => {
                 &self.store.jn_entity_from_relationship_left_entity
@@ -144,6 +145,30 @@ impl<'a> RelationshipTable for RelationshipHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &RelationshipQuery) -> Result<Vec<Relationship>, RepositoryError> {
+        let relationship_map = self.store.relationships.read().unwrap();
+        let matching: Vec<Relationship> = relationship_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(relationship_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &RelationshipQuery) -> Result<usize, RepositoryError> {
+        let relationship_map = self.store.relationships.read().unwrap();
+        Ok(relationship_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     fn update(&mut self, entity: &Relationship) -> Result<Relationship, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -236,7 +261,7 @@ impl<'a> RelationshipHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &RelationshipRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             RelationshipRelationshipField::LeftEntity => {
                 &self.store.jn_entity_from_relationship_left_entity
@@ -297,6 +322,30 @@ impl<'a> RelationshipTableRO for RelationshipHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &RelationshipQuery) -> Result<Vec<Relationship>, RepositoryError> {
+        let relationship_map = self.store.relationships.read().unwrap();
+        let matching: Vec<Relationship> = relationship_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(relationship_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &RelationshipQuery) -> Result<usize, RepositoryError> {
+        let relationship_map = self.store.relationships.read().unwrap();
+        Ok(relationship_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     impl_relationship_methods!(
         RelationshipHashMapTableRO<'a>,
         RelationshipRelationshipField
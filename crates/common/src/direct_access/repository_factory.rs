@@ -5,6 +5,10 @@ pub mod write {
     use crate::{
         database::transactions::Transaction,
         direct_access::{
+            custom_file::{
+                custom_file_repository::CustomFileRepository,
+                custom_file_table::CustomFileHashMapTable,
+            },
             dto::{dto_repository::DtoRepository, dto_table::DtoHashMapTable},
             dto_field::{
                 dto_field_repository::DtoFieldRepository, dto_field_table::DtoFieldHashMapTable,
@@ -125,12 +129,26 @@ pub mod write {
             transaction,
         ))
     }
+
+    pub fn create_custom_file_repository(
+        transaction: &'_ Transaction,
+    ) -> Result<CustomFileRepository<'_>> {
+        let custom_file_table = CustomFileHashMapTable::new(transaction.get_store());
+        Ok(CustomFileRepository::new(
+            Box::new(custom_file_table),
+            transaction,
+        ))
+    }
 }
 
 pub mod read {
     use crate::{
         database::transactions::Transaction,
         direct_access::{
+            custom_file::{
+                custom_file_repository::CustomFileRepositoryRO,
+                custom_file_table::CustomFileHashMapTableRO,
+            },
             dto::{dto_repository::DtoRepositoryRO, dto_table::DtoHashMapTableRO},
             dto_field::{
                 dto_field_repository::DtoFieldRepositoryRO, dto_field_table::DtoFieldHashMapTableRO,
@@ -247,4 +265,11 @@ pub mod read {
             user_interface_table,
         )))
     }
+
+    pub fn create_custom_file_repository(
+        transaction: &'_ Transaction,
+    ) -> Result<CustomFileRepositoryRO<'_>> {
+        let custom_file_table = CustomFileHashMapTableRO::new(transaction.get_store());
+        Ok(CustomFileRepositoryRO::new(Box::new(custom_file_table)))
+    }
 }
@@ -3,3 +3,4 @@
 pub mod root_repository;
 pub(super) mod root_table;
 pub use root_repository::RootRelationshipField;
+pub use root_repository::{RootFilter, RootQuery, RootSortField};
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::Root,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder},
 };
 
 use crate::error::RepositoryError;
@@ -26,12 +27,109 @@ impl Display for RootRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a Root.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RootFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+}
+
+impl RootFilter {
+    pub fn matches(&self, entity: &Root) -> bool {
+        match self {
+            RootFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            RootFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RootSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Query on the Roots: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RootQuery {
+    pub filters: Vec<RootFilter>,
+    pub sort: Vec<(RootSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl RootQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: RootFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: RootSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &Root) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<Root>) -> Vec<Root> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    RootSortField::Id => a.id.cmp(&b.id),
+                    RootSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    RootSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait RootTable {
     fn create(&mut self, entity: &Root) -> Result<Root, RepositoryError>;
     fn create_multi(&mut self, entities: &[Root]) -> Result<Vec<Root>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<Root>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Root>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Root>, RepositoryError>;
+    fn query(&self, query: &RootQuery) -> Result<Vec<Root>, RepositoryError>;
+    fn count(&self, query: &RootQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &Root) -> Result<Root, RepositoryError>;
     fn update_multi(&mut self, entities: &[Root]) -> Result<Vec<Root>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &Root) -> Result<Root, RepositoryError>;
@@ -92,6 +190,8 @@ pub trait RootTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<Root>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Root>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Root>, RepositoryError>;
+    fn query(&self, query: &RootQuery) -> Result<Vec<Root>, RepositoryError>;
+    fn count(&self, query: &RootQuery) -> Result<usize, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -168,6 +268,12 @@ impl<'a> RootRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<Root>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &RootQuery) -> Result<Vec<Root>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &RootQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -501,6 +607,7 @@ impl<'a> RootRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -509,6 +616,12 @@ impl<'a> RootRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -577,6 +690,12 @@ impl<'a> RootRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<Root>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &RootQuery) -> Result<Vec<Root>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &RootQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
@@ -4,14 +4,16 @@
 // Entity WITH forward relationships — explicit struct implementation
 // ═══════════════════════════════════════════════════════════════════════
 
-use crate::database::hashmap_store::{HashMapStore, junction_get, junction_remove, junction_set};
+use crate::database::hashmap_store::{
+    HashMapStore, JournaledMap, junction_get, junction_remove, junction_set,
+};
 use crate::entities::Root;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::root_repository::RootQuery;
 use super::root_repository::RootRelationshipField;
 use super::root_repository::RootTable;
 use super::root_repository::RootTableRO;
@@ -28,7 +30,7 @@ impl<'a> RootHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &RootRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             RootRelationshipField::System => &self.store.jn_system_from_root_system,
             RootRelationshipField::Workspace => &self.store.jn_workspace_from_root_workspace,
@@ -154,6 +156,30 @@ impl<'a> RootTable for RootHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &RootQuery) -> Result<Vec<Root>, RepositoryError> {
+        let root_map = self.store.roots.read().unwrap();
+        let matching: Vec<Root> = root_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(root_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &RootQuery) -> Result<usize, RepositoryError> {
+        let root_map = self.store.roots.read().unwrap();
+        Ok(root_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     fn update(&mut self, entity: &Root) -> Result<Root, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -265,7 +291,7 @@ impl<'a> RootHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &RootRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             RootRelationshipField::System => &self.store.jn_system_from_root_system,
             RootRelationshipField::Workspace => &self.store.jn_workspace_from_root_workspace,
@@ -316,5 +342,29 @@ impl<'a> RootTableRO for RootHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &RootQuery) -> Result<Vec<Root>, RepositoryError> {
+        let root_map = self.store.roots.read().unwrap();
+        let matching: Vec<Root> = root_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(root_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &RootQuery) -> Result<usize, RepositoryError> {
+        let root_map = self.store.roots.read().unwrap();
+        Ok(root_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     impl_relationship_methods!(RootHashMapTableRO<'a>, RootRelationshipField);
 }
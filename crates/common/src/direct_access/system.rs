@@ -3,3 +3,4 @@
 pub mod system_repository;
 pub(super) mod system_table;
 pub use system_repository::SystemRelationshipField;
+pub use system_repository::{SystemFilter, SystemQuery, SystemSortField};
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::System,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::root::RootRelationshipField;
@@ -26,12 +27,115 @@ impl Display for SystemRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a System.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SystemFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Version(TextCondition),
+}
+
+impl SystemFilter {
+    pub fn matches(&self, entity: &System) -> bool {
+        match self {
+            SystemFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            SystemFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            SystemFilter::Version(condition) => condition.matches(&entity.version),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SystemSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Version,
+}
+
+/// Query on the Systems: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemQuery {
+    pub filters: Vec<SystemFilter>,
+    pub sort: Vec<(SystemSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl SystemQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: SystemFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: SystemSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &System) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<System>) -> Vec<System> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    SystemSortField::Id => a.id.cmp(&b.id),
+                    SystemSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    SystemSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    SystemSortField::Version => {
+                        a.version.partial_cmp(&b.version).unwrap_or(Ordering::Equal)
+                    }
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait SystemTable {
     fn create(&mut self, entity: &System) -> Result<System, RepositoryError>;
     fn create_multi(&mut self, entities: &[System]) -> Result<Vec<System>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<System>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<System>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<System>, RepositoryError>;
+    fn query(&self, query: &SystemQuery) -> Result<Vec<System>, RepositoryError>;
+    fn count(&self, query: &SystemQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &System) -> Result<System, RepositoryError>;
     fn update_multi(&mut self, entities: &[System]) -> Result<Vec<System>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &System) -> Result<System, RepositoryError>;
@@ -92,6 +196,8 @@ pub trait SystemTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<System>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<System>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<System>, RepositoryError>;
+    fn query(&self, query: &SystemQuery) -> Result<Vec<System>, RepositoryError>;
+    fn count(&self, query: &SystemQuery) -> Result<usize, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -222,6 +328,12 @@ impl<'a> SystemRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<System>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &SystemQuery) -> Result<Vec<System>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &SystemQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -581,6 +693,7 @@ impl<'a> SystemRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -589,6 +702,12 @@ impl<'a> SystemRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -646,6 +765,12 @@ impl<'a> SystemRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<System>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &SystemQuery) -> Result<Vec<System>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &SystemQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
@@ -5,15 +5,16 @@
 // ═══════════════════════════════════════════════════════════════════════
 
 use crate::database::hashmap_store::{
-    HashMapStore, delete_from_backward_junction, junction_get, junction_remove, junction_set,
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
 };
 use crate::entities::System;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::system_repository::SystemQuery;
 use super::system_repository::SystemRelationshipField;
 use super::system_repository::SystemTable;
 use super::system_repository::SystemTableRO;
@@ -30,7 +31,7 @@ impl<'a> SystemHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &SystemRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             SystemRelationshipField::Files => &self.store.jn_file_from_system_files,
         }
@@ -114,6 +115,30 @@ impl<'a> SystemTable for SystemHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &SystemQuery) -> Result<Vec<System>, RepositoryError> {
+        let system_map = self.store.systems.read().unwrap();
+        let matching: Vec<System> = system_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(system_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &SystemQuery) -> Result<usize, RepositoryError> {
+        let system_map = self.store.systems.read().unwrap();
+        Ok(system_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     fn update(&mut self, entity: &System) -> Result<System, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -191,7 +216,7 @@ impl<'a> SystemHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &SystemRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             SystemRelationshipField::Files => &self.store.jn_file_from_system_files,
         }
@@ -236,5 +261,29 @@ impl<'a> SystemTableRO for SystemHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &SystemQuery) -> Result<Vec<System>, RepositoryError> {
+        let system_map = self.store.systems.read().unwrap();
+        let matching: Vec<System> = system_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(system_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &SystemQuery) -> Result<usize, RepositoryError> {
+        let system_map = self.store.systems.read().unwrap();
+        Ok(system_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     impl_relationship_methods!(SystemHashMapTableRO<'a>, SystemRelationshipField);
 }
@@ -3,3 +3,4 @@
 pub mod use_case_repository;
 pub(super) mod use_case_table;
 pub use use_case_repository::UseCaseRelationshipField;
+pub use use_case_repository::{UseCaseFilter, UseCaseQuery, UseCaseSortField};
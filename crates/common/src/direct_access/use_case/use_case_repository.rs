@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::UseCase,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::feature::FeatureRelationshipField;
@@ -20,6 +21,8 @@ pub enum UseCaseRelationshipField {
     DtoIn,
     DtoOut,
     Entities,
+    SharedDtoIn,
+    SharedDtoOut,
 }
 
 impl Display for UseCaseRelationshipField {
@@ -28,12 +31,136 @@ impl Display for UseCaseRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a UseCase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UseCaseFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    Name(TextCondition),
+    Undoable(Condition<bool>),
+    ReadOnly(Condition<bool>),
+    LongOperation(Condition<bool>),
+}
+
+impl UseCaseFilter {
+    pub fn matches(&self, entity: &UseCase) -> bool {
+        match self {
+            UseCaseFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            UseCaseFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            UseCaseFilter::Name(condition) => condition.matches(&entity.name),
+            UseCaseFilter::Undoable(condition) => condition.matches(&entity.undoable),
+            UseCaseFilter::ReadOnly(condition) => condition.matches(&entity.read_only),
+            UseCaseFilter::LongOperation(condition) => condition.matches(&entity.long_operation),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UseCaseSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Name,
+    Undoable,
+    ReadOnly,
+    LongOperation,
+}
+
+/// Query on the UseCases: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UseCaseQuery {
+    pub filters: Vec<UseCaseFilter>,
+    pub sort: Vec<(UseCaseSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl UseCaseQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: UseCaseFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: UseCaseSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &UseCase) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<UseCase>) -> Vec<UseCase> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    UseCaseSortField::Id => a.id.cmp(&b.id),
+                    UseCaseSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    UseCaseSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    UseCaseSortField::Name => {
+                        a.name.partial_cmp(&b.name).unwrap_or(Ordering::Equal)
+                    }
+                    UseCaseSortField::Undoable => a
+                        .undoable
+                        .partial_cmp(&b.undoable)
+                        .unwrap_or(Ordering::Equal),
+                    UseCaseSortField::ReadOnly => a
+                        .read_only
+                        .partial_cmp(&b.read_only)
+                        .unwrap_or(Ordering::Equal),
+                    UseCaseSortField::LongOperation => a
+                        .long_operation
+                        .partial_cmp(&b.long_operation)
+                        .unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait UseCaseTable {
     fn create(&mut self, entity: &UseCase) -> Result<UseCase, RepositoryError>;
     fn create_multi(&mut self, entities: &[UseCase]) -> Result<Vec<UseCase>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<UseCase>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<UseCase>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<UseCase>, RepositoryError>;
+    fn query(&self, query: &UseCaseQuery) -> Result<Vec<UseCase>, RepositoryError>;
+    fn count(&self, query: &UseCaseQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &UseCase) -> Result<UseCase, RepositoryError>;
     fn update_multi(&mut self, entities: &[UseCase]) -> Result<Vec<UseCase>, RepositoryError>;
     fn update_with_relationships(&mut self, entity: &UseCase) -> Result<UseCase, RepositoryError>;
@@ -94,6 +221,8 @@ pub trait UseCaseTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<UseCase>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<UseCase>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<UseCase>, RepositoryError>;
+    fn query(&self, query: &UseCaseQuery) -> Result<Vec<UseCase>, RepositoryError>;
+    fn count(&self, query: &UseCaseQuery) -> Result<usize, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -224,6 +353,12 @@ impl<'a> UseCaseRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<UseCase>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &UseCaseQuery) -> Result<Vec<UseCase>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &UseCaseQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -512,6 +647,40 @@ impl<'a> UseCaseRepository<'a> {
                         });
                     }
                 }
+                UseCaseRelationshipField::SharedDtoIn => {
+                    let child_repo =
+                        repository_factory::write::create_dto_repository(self.transaction)?;
+                    let found = child_repo.get_multi(&all_right_ids)?;
+                    let missing: Vec<_> = all_right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship_multi",
+                            ids: missing,
+                        });
+                    }
+                }
+                UseCaseRelationshipField::SharedDtoOut => {
+                    let child_repo =
+                        repository_factory::write::create_dto_repository(self.transaction)?;
+                    let found = child_repo.get_multi(&all_right_ids)?;
+                    let missing: Vec<_> = all_right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship_multi",
+                            ids: missing,
+                        });
+                    }
+                }
             }
         }
         self.table
@@ -595,6 +764,40 @@ impl<'a> UseCaseRepository<'a> {
                         });
                     }
                 }
+                UseCaseRelationshipField::SharedDtoIn => {
+                    let child_repo =
+                        repository_factory::write::create_dto_repository(self.transaction)?;
+                    let found = child_repo.get_multi(right_ids)?;
+                    let missing: Vec<_> = right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship",
+                            ids: missing,
+                        });
+                    }
+                }
+                UseCaseRelationshipField::SharedDtoOut => {
+                    let child_repo =
+                        repository_factory::write::create_dto_repository(self.transaction)?;
+                    let found = child_repo.get_multi(right_ids)?;
+                    let missing: Vec<_> = right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship",
+                            ids: missing,
+                        });
+                    }
+                }
             }
         }
         self.table.set_relationship(id, field, right_ids)?;
@@ -667,6 +870,7 @@ impl<'a> UseCaseRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -675,6 +879,12 @@ impl<'a> UseCaseRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -743,6 +953,12 @@ impl<'a> UseCaseRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<UseCase>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &UseCaseQuery) -> Result<Vec<UseCase>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &UseCaseQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
@@ -5,15 +5,16 @@
 // ═══════════════════════════════════════════════════════════════════════
 
 use crate::database::hashmap_store::{
-    HashMapStore, delete_from_backward_junction, junction_get, junction_remove, junction_set,
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
 };
 use crate::entities::UseCase;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::use_case_repository::UseCaseQuery;
 use super::use_case_repository::UseCaseRelationshipField;
 use super::use_case_repository::UseCaseTable;
 use super::use_case_repository::UseCaseTableRO;
@@ -30,11 +31,15 @@ impl<'a> UseCaseHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &UseCaseRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             UseCaseRelationshipField::DtoIn => &self.store.jn_dto_from_use_case_dto_in,
             UseCaseRelationshipField::DtoOut => &self.store.jn_dto_from_use_case_dto_out,
             UseCaseRelationshipField::Entities => &self.store.jn_entity_from_use_case_entities,
+            UseCaseRelationshipField::SharedDtoIn => &self.store.jn_dto_from_use_case_shared_dto_in,
+            UseCaseRelationshipField::SharedDtoOut => {
+                &self.store.jn_dto_from_use_case_shared_dto_out
+            }
         }
     }
 
@@ -46,6 +51,14 @@ impl<'a> UseCaseHashMapTable<'a> {
         entity.dto_out = junction_get(&self.store.jn_dto_from_use_case_dto_out, &entity.id)
             .into_iter()
             .next();
+        entity.shared_dto_in =
+            junction_get(&self.store.jn_dto_from_use_case_shared_dto_in, &entity.id)
+                .into_iter()
+                .next();
+        entity.shared_dto_out =
+            junction_get(&self.store.jn_dto_from_use_case_shared_dto_out, &entity.id)
+                .into_iter()
+                .next();
     }
 }
 
@@ -124,6 +137,22 @@ impl<'a> UseCaseTable for UseCaseHashMapTable<'a> {
                 new_entity.id,
                 new_entity.entities.clone(),
             );
+            junction_set(
+                &self.store.jn_dto_from_use_case_shared_dto_in,
+                new_entity.id,
+                new_entity
+                    .shared_dto_in
+                    .into_iter()
+                    .collect::<Vec<EntityId>>(),
+            );
+            junction_set(
+                &self.store.jn_dto_from_use_case_shared_dto_out,
+                new_entity.id,
+                new_entity
+                    .shared_dto_out
+                    .into_iter()
+                    .collect::<Vec<EntityId>>(),
+            );
 
             created.push(new_entity);
         }
@@ -163,6 +192,30 @@ impl<'a> UseCaseTable for UseCaseHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &UseCaseQuery) -> Result<Vec<UseCase>, RepositoryError> {
+        let use_case_map = self.store.use_cases.read().unwrap();
+        let matching: Vec<UseCase> = use_case_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(use_case_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &UseCaseQuery) -> Result<usize, RepositoryError> {
+        let use_case_map = self.store.use_cases.read().unwrap();
+        Ok(use_case_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     fn update(&mut self, entity: &UseCase) -> Result<UseCase, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -238,6 +291,16 @@ impl<'a> UseCaseTable for UseCaseHashMapTable<'a> {
                 entity.id,
                 entity.dto_out.into_iter().collect::<Vec<EntityId>>(),
             );
+            junction_set(
+                &self.store.jn_dto_from_use_case_shared_dto_in,
+                entity.id,
+                entity.shared_dto_in.into_iter().collect::<Vec<EntityId>>(),
+            );
+            junction_set(
+                &self.store.jn_dto_from_use_case_shared_dto_out,
+                entity.id,
+                entity.shared_dto_out.into_iter().collect::<Vec<EntityId>>(),
+            );
         }
         drop(use_case_map);
         let ids: Vec<EntityId> = entities.iter().map(|e| e.id).collect();
@@ -259,6 +322,8 @@ impl<'a> UseCaseTable for UseCaseHashMapTable<'a> {
             junction_remove(&self.store.jn_entity_from_use_case_entities, id);
             junction_remove(&self.store.jn_dto_from_use_case_dto_in, id);
             junction_remove(&self.store.jn_dto_from_use_case_dto_out, id);
+            junction_remove(&self.store.jn_dto_from_use_case_shared_dto_in, id);
+            junction_remove(&self.store.jn_dto_from_use_case_shared_dto_out, id);
 
             // Clean up backward references (uses the owning entity's forward junction)
 
@@ -283,11 +348,15 @@ impl<'a> UseCaseHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &UseCaseRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
             UseCaseRelationshipField::DtoIn => &self.store.jn_dto_from_use_case_dto_in,
             UseCaseRelationshipField::DtoOut => &self.store.jn_dto_from_use_case_dto_out,
             UseCaseRelationshipField::Entities => &self.store.jn_entity_from_use_case_entities,
+            UseCaseRelationshipField::SharedDtoIn => &self.store.jn_dto_from_use_case_shared_dto_in,
+            UseCaseRelationshipField::SharedDtoOut => {
+                &self.store.jn_dto_from_use_case_shared_dto_out
+            }
         }
     }
 
@@ -299,6 +368,14 @@ impl<'a> UseCaseHashMapTableRO<'a> {
         entity.dto_out = junction_get(&self.store.jn_dto_from_use_case_dto_out, &entity.id)
             .into_iter()
             .next();
+        entity.shared_dto_in =
+            junction_get(&self.store.jn_dto_from_use_case_shared_dto_in, &entity.id)
+                .into_iter()
+                .next();
+        entity.shared_dto_out =
+            junction_get(&self.store.jn_dto_from_use_case_shared_dto_out, &entity.id)
+                .into_iter()
+                .next();
     }
 }
 
@@ -336,5 +413,29 @@ impl<'a> UseCaseTableRO for UseCaseHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &UseCaseQuery) -> Result<Vec<UseCase>, RepositoryError> {
+        let use_case_map = self.store.use_cases.read().unwrap();
+        let matching: Vec<UseCase> = use_case_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(use_case_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &UseCaseQuery) -> Result<usize, RepositoryError> {
+        let use_case_map = self.store.use_cases.read().unwrap();
+        Ok(use_case_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     impl_relationship_methods!(UseCaseHashMapTableRO<'a>, UseCaseRelationshipField);
 }
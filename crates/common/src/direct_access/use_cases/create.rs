@@ -1,8 +1,8 @@
 // Generated by Qleany v1.7.0 from common_da_use_cases_create.tera
 
-use super::traits::OwnedWriteUoWFactory;
+use super::traits::{OwnedWriteUoW, OwnedWriteUoWFactory};
 use crate::snapshot::EntityTreeSnapshot;
-use crate::types::{EntityId, HasId};
+use crate::types::{EntityId, HasId, Validate};
 use crate::undo_redo::UndoRedoCommand;
 use anyhow::{Ok, Result};
 use std::any::Any;
@@ -16,6 +16,30 @@ pub enum OwnerStrategy {
     Appending,
 }
 
+/// Check the unique_in_parent constraints of `entities` against the current
+/// children of the owner and against each other. Only done on create: updates
+/// are not checked for uniqueness.
+fn check_unique_in_parent<E: Clone + HasId + Validate + Send>(
+    uow: &dyn OwnedWriteUoW<Entity = E>,
+    entities: &[E],
+    owner_id: &EntityId,
+) -> Result<()> {
+    if !E::UNIQUE_IN_PARENT {
+        return Ok(());
+    }
+    let siblings: Vec<E> = uow
+        .get_multi(&uow.get_relationships_from_owner(owner_id)?)?
+        .into_iter()
+        .flatten()
+        .collect();
+    for (i, entity) in entities.iter().enumerate() {
+        for other in siblings.iter().chain(&entities[..i]) {
+            entity.check_unique_among(other)?;
+        }
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Non-undoable create (with owner)
 // ---------------------------------------------------------------------------
@@ -48,8 +72,12 @@ impl<F: OwnedWriteUoWFactory> CreateUseCase<F> {
         owner_id: EntityId,
         index: i32,
     ) -> Result<Vec<F::Entity>> {
+        for entity in entities {
+            entity.validate()?;
+        }
         let mut uow = self.uow_factory.create();
         uow.begin_transaction()?;
+        check_unique_in_parent(&*uow, entities, &owner_id)?;
         let created = uow.create_multi(entities, owner_id, index)?;
         uow.commit()?;
         Ok(created)
@@ -108,8 +136,12 @@ impl<F: OwnedWriteUoWFactory> UndoableCreateUseCase<F> {
         owner_id: EntityId,
         index: i32,
     ) -> Result<Vec<F::Entity>> {
+        for entity in entities {
+            entity.validate()?;
+        }
         let mut uow = self.uow_factory.create();
         uow.begin_transaction()?;
+        check_unique_in_parent(&*uow, entities, &owner_id)?;
 
         self.owner_id = Some(owner_id);
         self.index = index;
@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from common_da_use_cases_create_orphan.tera
 
 use super::traits::WriteUoWFactory;
-use crate::types::HasId;
+use crate::types::{HasId, Validate};
 use crate::undo_redo::UndoRedoCommand;
 use anyhow::{Ok, Result};
 use std::any::Any;
@@ -29,6 +29,9 @@ impl<F: WriteUoWFactory> CreateOrphanUseCase<F> {
     }
 
     pub fn execute_multi(&mut self, entities: &[F::Entity]) -> Result<Vec<F::Entity>> {
+        for entity in entities {
+            entity.validate()?;
+        }
         let mut uow = self.uow_factory.create();
         uow.begin_transaction()?;
         let created = uow.create_orphan_multi(entities)?;
@@ -65,6 +68,9 @@ impl<F: WriteUoWFactory> UndoableCreateOrphanUseCase<F> {
     }
 
     pub fn execute_multi(&mut self, entities: &[F::Entity]) -> Result<Vec<F::Entity>> {
+        for entity in entities {
+            entity.validate()?;
+        }
         let mut uow = self.uow_factory.create();
         uow.begin_transaction()?;
         let created = uow.create_orphan_multi(entities)?;
@@ -39,4 +39,21 @@ impl<F: ReadUoWFactory> GetUseCase<F> {
         uow.end_transaction()?;
         Ok(entities)
     }
+
+    pub fn execute_query(&self, query: &F::Query) -> Result<Vec<F::Entity>> {
+        let uow = self.uow_factory.create();
+        uow.begin_transaction()?;
+        let entities = uow.query(query)?;
+        uow.end_transaction()?;
+        Ok(entities)
+    }
+
+    /// Number of entities matching the filters of `query`, ignoring its page.
+    pub fn execute_count(&self, query: &F::Query) -> Result<usize> {
+        let uow = self.uow_factory.create();
+        uow.begin_transaction()?;
+        let count = uow.count(query)?;
+        uow.end_transaction()?;
+        Ok(count)
+    }
 }
@@ -2,7 +2,7 @@
 
 use crate::database::{CommandUnitOfWork, QueryUnitOfWork};
 use crate::snapshot::EntityTreeSnapshot;
-use crate::types::{EntityId, HasId};
+use crate::types::{EntityId, HasId, Validate};
 use anyhow::Result;
 use std::collections::HashMap;
 
@@ -12,15 +12,19 @@ use std::collections::HashMap;
 
 pub trait ReadUoW: QueryUnitOfWork {
     type Entity;
+    type Query;
 
     fn get(&self, id: &EntityId) -> Result<Option<Self::Entity>>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Self::Entity>>>;
     fn get_all(&self) -> Result<Vec<Self::Entity>>;
+    fn query(&self, query: &Self::Query) -> Result<Vec<Self::Entity>>;
+    fn count(&self, query: &Self::Query) -> Result<usize>;
 }
 
 pub trait ReadUoWFactory {
     type Entity;
-    fn create(&self) -> Box<dyn ReadUoW<Entity = Self::Entity>>;
+    type Query;
+    fn create(&self) -> Box<dyn ReadUoW<Entity = Self::Entity, Query = Self::Query>>;
 }
 
 // ---------------------------------------------------------------------------
@@ -28,7 +32,7 @@ pub trait ReadUoWFactory {
 // ---------------------------------------------------------------------------
 
 pub trait WriteUoW: CommandUnitOfWork {
-    type Entity: Clone + HasId + Send;
+    type Entity: Clone + HasId + Validate + Send;
 
     fn get(&self, id: &EntityId) -> Result<Option<Self::Entity>>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Self::Entity>>>;
@@ -46,7 +50,7 @@ pub trait WriteUoW: CommandUnitOfWork {
 }
 
 pub trait WriteUoWFactory: Send + Sync {
-    type Entity: Clone + HasId + Send;
+    type Entity: Clone + HasId + Validate + Send;
     fn create(&self) -> Box<dyn WriteUoW<Entity = Self::Entity>>;
 }
 
@@ -66,7 +70,7 @@ pub trait OwnedWriteUoW: WriteUoW {
 }
 
 pub trait OwnedWriteUoWFactory: Send + Sync {
-    type Entity: Clone + HasId + Send;
+    type Entity: Clone + HasId + Validate + Send;
     fn create(&self) -> Box<dyn OwnedWriteUoW<Entity = Self::Entity>>;
 }
 
@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from common_da_use_cases_update.tera
 
 use super::traits::WriteUoWFactory;
-use crate::types::HasId;
+use crate::types::{HasId, Validate};
 use crate::undo_redo::UndoRedoCommand;
 use anyhow::{Ok, Result};
 use std::any::Any;
@@ -29,6 +29,9 @@ impl<F: WriteUoWFactory> UpdateUseCase<F> {
     }
 
     pub fn execute_multi(&mut self, entities: &[F::Entity]) -> Result<Vec<F::Entity>> {
+        for entity in entities {
+            entity.validate()?;
+        }
         let mut uow = self.uow_factory.create();
         uow.begin_transaction()?;
         // existence check
@@ -74,6 +77,9 @@ impl<F: WriteUoWFactory> UndoableUpdateUseCase<F> {
     }
 
     pub fn execute_multi(&mut self, entities: &[F::Entity]) -> Result<Vec<F::Entity>> {
+        for entity in entities {
+            entity.validate()?;
+        }
         let mut uow = self.uow_factory.create();
         uow.begin_transaction()?;
         // existence check
@@ -157,6 +163,9 @@ impl<F: WriteUoWFactory> UpdateWithRelationshipsUseCase<F> {
     }
 
     pub fn execute_multi(&mut self, entities: &[F::Entity]) -> Result<Vec<F::Entity>> {
+        for entity in entities {
+            entity.validate()?;
+        }
         let mut uow = self.uow_factory.create();
         uow.begin_transaction()?;
         // existence check
@@ -202,6 +211,9 @@ impl<F: WriteUoWFactory> UndoableUpdateWithRelationshipsUseCase<F> {
     }
 
     pub fn execute_multi(&mut self, entities: &[F::Entity]) -> Result<Vec<F::Entity>> {
+        for entity in entities {
+            entity.validate()?;
+        }
         let mut uow = self.uow_factory.create();
         uow.begin_transaction()?;
         // existence check
@@ -2,3 +2,6 @@
 
 pub mod user_interface_repository;
 pub(super) mod user_interface_table;
+pub use user_interface_repository::{
+    UserInterfaceFilter, UserInterfaceQuery, UserInterfaceSortField,
+};
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::UserInterface,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder},
 };
 
 use crate::direct_access::workspace::WorkspaceRelationshipField;
@@ -24,6 +25,147 @@ impl Display for UserInterfaceRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a UserInterface.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UserInterfaceFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    RustCli(Condition<bool>),
+    RustSlint(Condition<bool>),
+    CppQtQtwidgets(Condition<bool>),
+    CppQtQtquick(Condition<bool>),
+    RustIos(Condition<bool>),
+    RustAndroid(Condition<bool>),
+}
+
+impl UserInterfaceFilter {
+    pub fn matches(&self, entity: &UserInterface) -> bool {
+        match self {
+            UserInterfaceFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            UserInterfaceFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            UserInterfaceFilter::RustCli(condition) => condition.matches(&entity.rust_cli),
+            UserInterfaceFilter::RustSlint(condition) => condition.matches(&entity.rust_slint),
+            UserInterfaceFilter::CppQtQtwidgets(condition) => {
+                condition.matches(&entity.cpp_qt_qtwidgets)
+            }
+            UserInterfaceFilter::CppQtQtquick(condition) => {
+                condition.matches(&entity.cpp_qt_qtquick)
+            }
+            UserInterfaceFilter::RustIos(condition) => condition.matches(&entity.rust_ios),
+            UserInterfaceFilter::RustAndroid(condition) => condition.matches(&entity.rust_android),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UserInterfaceSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    RustCli,
+    RustSlint,
+    CppQtQtwidgets,
+    CppQtQtquick,
+    RustIos,
+    RustAndroid,
+}
+
+/// Query on the UserInterfaces: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserInterfaceQuery {
+    pub filters: Vec<UserInterfaceFilter>,
+    pub sort: Vec<(UserInterfaceSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl UserInterfaceQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: UserInterfaceFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: UserInterfaceSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &UserInterface) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<UserInterface>) -> Vec<UserInterface> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    UserInterfaceSortField::Id => a.id.cmp(&b.id),
+                    UserInterfaceSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    UserInterfaceSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    UserInterfaceSortField::RustCli => a
+                        .rust_cli
+                        .partial_cmp(&b.rust_cli)
+                        .unwrap_or(Ordering::Equal),
+                    UserInterfaceSortField::RustSlint => a
+                        .rust_slint
+                        .partial_cmp(&b.rust_slint)
+                        .unwrap_or(Ordering::Equal),
+                    UserInterfaceSortField::CppQtQtwidgets => a
+                        .cpp_qt_qtwidgets
+                        .partial_cmp(&b.cpp_qt_qtwidgets)
+                        .unwrap_or(Ordering::Equal),
+                    UserInterfaceSortField::CppQtQtquick => a
+                        .cpp_qt_qtquick
+                        .partial_cmp(&b.cpp_qt_qtquick)
+                        .unwrap_or(Ordering::Equal),
+                    UserInterfaceSortField::RustIos => a
+                        .rust_ios
+                        .partial_cmp(&b.rust_ios)
+                        .unwrap_or(Ordering::Equal),
+                    UserInterfaceSortField::RustAndroid => a
+                        .rust_android
+                        .partial_cmp(&b.rust_android)
+                        .unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait UserInterfaceTable {
     fn create(&mut self, entity: &UserInterface) -> Result<UserInterface, RepositoryError>;
     fn create_multi(
@@ -33,6 +175,8 @@ pub trait UserInterfaceTable {
     fn get(&self, id: &EntityId) -> Result<Option<UserInterface>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<UserInterface>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<UserInterface>, RepositoryError>;
+    fn query(&self, query: &UserInterfaceQuery) -> Result<Vec<UserInterface>, RepositoryError>;
+    fn count(&self, query: &UserInterfaceQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &UserInterface) -> Result<UserInterface, RepositoryError>;
     fn update_multi(
         &mut self,
@@ -54,6 +198,8 @@ pub trait UserInterfaceTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<UserInterface>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<UserInterface>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<UserInterface>, RepositoryError>;
+    fn query(&self, query: &UserInterfaceQuery) -> Result<Vec<UserInterface>, RepositoryError>;
+    fn count(&self, query: &UserInterfaceQuery) -> Result<usize, RepositoryError>;
 }
 
 pub struct UserInterfaceRepository<'a> {
@@ -160,6 +306,12 @@ impl<'a> UserInterfaceRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<UserInterface>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &UserInterfaceQuery) -> Result<Vec<UserInterface>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &UserInterfaceQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -350,6 +502,7 @@ impl<'a> UserInterfaceRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -358,6 +511,12 @@ impl<'a> UserInterfaceRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -414,4 +573,10 @@ impl<'a> UserInterfaceRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<UserInterface>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &UserInterfaceQuery) -> Result<Vec<UserInterface>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &UserInterfaceQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 }
@@ -4,18 +4,22 @@
 // Leaf entity (no forward relationships) — macro implementation
 // ═══════════════════════════════════════════════════════════════════════
 
+use super::user_interface_repository::UserInterfaceQuery;
 use super::user_interface_repository::UserInterfaceTable;
 use super::user_interface_repository::UserInterfaceTableRO;
 use crate::entities::UserInterface;
 
 crate::impl_leaf_entity_table!(
     entity: UserInterface,
+    query: UserInterfaceQuery,
     entity_name: "user_interface",
     store_field: user_interfaces,
     table_trait: UserInterfaceTable,
     table_ro_trait: UserInterfaceTableRO,
     table_struct: UserInterfaceHashMapTable,
     table_ro_struct: UserInterfaceHashMapTableRO,
+    indexes: [],
+    unique_indexes: [],
     backward_junctions: [
 
         (jn_user_interface_from_workspace_user_interface),
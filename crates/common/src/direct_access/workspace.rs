@@ -3,3 +3,4 @@
 pub mod workspace_repository;
 pub(super) mod workspace_table;
 pub use workspace_repository::WorkspaceRelationshipField;
+pub use workspace_repository::{WorkspaceFilter, WorkspaceQuery, WorkspaceSortField};
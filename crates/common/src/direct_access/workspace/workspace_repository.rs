@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from common_entity_repository.tera
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
     entities::Workspace,
     event::{DirectAccessEntity, EntityEvent, Event, EventBuffer, Origin},
     snapshot::EntityTreeSnapshot,
-    types::EntityId,
+    types::{Condition, EntityId, SortOrder, TextCondition},
 };
 
 use crate::direct_access::root::RootRelationshipField;
@@ -17,6 +18,7 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WorkspaceRelationshipField {
+    CustomFiles,
     Entities,
     Features,
     Global,
@@ -29,12 +31,118 @@ impl Display for WorkspaceRelationshipField {
     }
 }
 
+/// Condition on a scalar field of a Workspace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WorkspaceFilter {
+    CreatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    UpdatedAt(Condition<chrono::DateTime<chrono::Utc>>),
+    ManifestAbsolutePath(TextCondition),
+}
+
+impl WorkspaceFilter {
+    pub fn matches(&self, entity: &Workspace) -> bool {
+        match self {
+            WorkspaceFilter::CreatedAt(condition) => condition.matches(&entity.created_at),
+            WorkspaceFilter::UpdatedAt(condition) => condition.matches(&entity.updated_at),
+            WorkspaceFilter::ManifestAbsolutePath(condition) => {
+                condition.matches(&entity.manifest_absolute_path)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkspaceSortField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    ManifestAbsolutePath,
+}
+
+/// Query on the Workspaces: all the filters must match, results are sorted by the sort
+/// keys then by id, and `offset`/`limit` select a page of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceQuery {
+    pub filters: Vec<WorkspaceFilter>,
+    pub sort: Vec<(WorkspaceSortField, SortOrder)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl WorkspaceQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: WorkspaceFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn sort_by(mut self, field: WorkspaceSortField, order: SortOrder) -> Self {
+        self.sort.push((field, order));
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entity: &Workspace) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entity))
+    }
+
+    /// Sort matching entities and keep the requested page.
+    pub fn sort_and_page(&self, mut entities: Vec<Workspace>) -> Vec<Workspace> {
+        entities.sort_by(|a, b| {
+            for (field, order) in &self.sort {
+                let ordering = match field {
+                    WorkspaceSortField::Id => a.id.cmp(&b.id),
+                    WorkspaceSortField::CreatedAt => a
+                        .created_at
+                        .partial_cmp(&b.created_at)
+                        .unwrap_or(Ordering::Equal),
+                    WorkspaceSortField::UpdatedAt => a
+                        .updated_at
+                        .partial_cmp(&b.updated_at)
+                        .unwrap_or(Ordering::Equal),
+                    WorkspaceSortField::ManifestAbsolutePath => a
+                        .manifest_absolute_path
+                        .partial_cmp(&b.manifest_absolute_path)
+                        .unwrap_or(Ordering::Equal),
+                };
+                let ordering = match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.id.cmp(&b.id)
+        });
+        entities
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 pub trait WorkspaceTable {
     fn create(&mut self, entity: &Workspace) -> Result<Workspace, RepositoryError>;
     fn create_multi(&mut self, entities: &[Workspace]) -> Result<Vec<Workspace>, RepositoryError>;
     fn get(&self, id: &EntityId) -> Result<Option<Workspace>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Workspace>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Workspace>, RepositoryError>;
+    fn query(&self, query: &WorkspaceQuery) -> Result<Vec<Workspace>, RepositoryError>;
+    fn count(&self, query: &WorkspaceQuery) -> Result<usize, RepositoryError>;
     fn update(&mut self, entity: &Workspace) -> Result<Workspace, RepositoryError>;
     fn update_multi(&mut self, entities: &[Workspace]) -> Result<Vec<Workspace>, RepositoryError>;
     fn update_with_relationships(
@@ -98,6 +206,8 @@ pub trait WorkspaceTableRO {
     fn get(&self, id: &EntityId) -> Result<Option<Workspace>, RepositoryError>;
     fn get_multi(&self, ids: &[EntityId]) -> Result<Vec<Option<Workspace>>, RepositoryError>;
     fn get_all(&self) -> Result<Vec<Workspace>, RepositoryError>;
+    fn query(&self, query: &WorkspaceQuery) -> Result<Vec<Workspace>, RepositoryError>;
+    fn count(&self, query: &WorkspaceQuery) -> Result<usize, RepositoryError>;
     fn get_relationship(
         &self,
         id: &EntityId,
@@ -228,6 +338,12 @@ impl<'a> WorkspaceRepository<'a> {
     pub fn get_all(&self) -> Result<Vec<Workspace>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &WorkspaceQuery) -> Result<Vec<Workspace>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &WorkspaceQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
 
     pub fn update(
         &mut self,
@@ -300,6 +416,7 @@ impl<'a> WorkspaceRepository<'a> {
         let entities = entity.entities.clone();
         let features = entity.features.clone();
         let user_interface = entity.user_interface;
+        let custom_files = entity.custom_files.clone();
 
         // remove all strong relationships, initiating a cascade remove
 
@@ -311,6 +428,8 @@ impl<'a> WorkspaceRepository<'a> {
             .remove_multi(event_buffer, &features)?;
         repository_factory::write::create_user_interface_repository(self.transaction)?
             .remove(event_buffer, &user_interface)?;
+        repository_factory::write::create_custom_file_repository(self.transaction)?
+            .remove_multi(event_buffer, &custom_files)?;
         // Before removal, find which owner(s) reference this entity
         let affected_owner_ids: Vec<EntityId> = {
             let owner_repo = repository_factory::write::create_root_repository(self.transaction)?;
@@ -382,6 +501,14 @@ impl<'a> WorkspaceRepository<'a> {
             .iter()
             .filter_map(|entity| entity.as_ref().map(|entity| entity.user_interface))
             .collect();
+        let mut custom_files_ids: Vec<EntityId> = entities
+            .iter()
+            .flat_map(|entity| entity.as_ref().map(|entity| entity.custom_files.clone()))
+            .flatten()
+            .collect();
+        // remove duplicates
+        custom_files_ids.sort();
+        custom_files_ids.dedup();
 
         // remove all strong relationships, initiating a cascade remove
 
@@ -393,6 +520,8 @@ impl<'a> WorkspaceRepository<'a> {
             .remove_multi(event_buffer, &features_ids)?;
         repository_factory::write::create_user_interface_repository(self.transaction)?
             .remove_multi(event_buffer, &user_interface_ids)?;
+        repository_factory::write::create_custom_file_repository(self.transaction)?
+            .remove_multi(event_buffer, &custom_files_ids)?;
         // Before removal, find which owner(s) reference these entities
         let affected_owner_ids: Vec<EntityId> = {
             let owner_repo = repository_factory::write::create_root_repository(self.transaction)?;
@@ -485,6 +614,23 @@ impl<'a> WorkspaceRepository<'a> {
             .collect();
         if !all_right_ids.is_empty() {
             match field {
+                WorkspaceRelationshipField::CustomFiles => {
+                    let child_repo =
+                        repository_factory::write::create_custom_file_repository(self.transaction)?;
+                    let found = child_repo.get_multi(&all_right_ids)?;
+                    let missing: Vec<_> = all_right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship_multi",
+                            ids: missing,
+                        });
+                    }
+                }
                 WorkspaceRelationshipField::Entities => {
                     let child_repo =
                         repository_factory::write::create_entity_repository(self.transaction)?;
@@ -586,6 +732,23 @@ impl<'a> WorkspaceRepository<'a> {
         // Validate that all right_ids exist
         if !right_ids.is_empty() {
             match field {
+                WorkspaceRelationshipField::CustomFiles => {
+                    let child_repo =
+                        repository_factory::write::create_custom_file_repository(self.transaction)?;
+                    let found = child_repo.get_multi(right_ids)?;
+                    let missing: Vec<_> = right_ids
+                        .iter()
+                        .zip(found.iter())
+                        .filter(|(_, entity)| entity.is_none())
+                        .map(|(id, _)| *id)
+                        .collect();
+                    if !missing.is_empty() {
+                        return Err(RepositoryError::MissingRelationshipTarget {
+                            operation: "set_relationship",
+                            ids: missing,
+                        });
+                    }
+                }
                 WorkspaceRelationshipField::Entities => {
                     let child_repo =
                         repository_factory::write::create_entity_repository(self.transaction)?;
@@ -727,6 +890,7 @@ impl<'a> WorkspaceRepository<'a> {
         let store_snap = self.transaction.snapshot_store();
         Ok(EntityTreeSnapshot {
             store_snapshot: Some(store_snap),
+            journal: None,
         })
     }
 
@@ -735,6 +899,12 @@ impl<'a> WorkspaceRepository<'a> {
         event_buffer: &mut EventBuffer,
         snap: &EntityTreeSnapshot,
     ) -> Result<(), RepositoryError> {
+        if let Some(journal) = &snap.journal {
+            for event in self.transaction.undo_journal(journal) {
+                event_buffer.push(event);
+            }
+            return Ok(());
+        }
         let store_snap = snap
             .store_snapshot
             .as_ref()
@@ -802,6 +972,13 @@ impl<'a> WorkspaceRepository<'a> {
                 child_ids.clone(),
             );
         }
+        {
+            let child_ids: Vec<_> = store.custom_files.read().unwrap().keys().copied().collect();
+            emit(
+                DirectAccessEntity::CustomFile(EntityEvent::Created),
+                child_ids.clone(),
+            );
+        }
 
         Ok(())
     }
@@ -823,6 +1000,12 @@ impl<'a> WorkspaceRepositoryRO<'a> {
     pub fn get_all(&self) -> Result<Vec<Workspace>, RepositoryError> {
         self.table.get_all()
     }
+    pub fn query(&self, query: &WorkspaceQuery) -> Result<Vec<Workspace>, RepositoryError> {
+        self.table.query(query)
+    }
+    pub fn count(&self, query: &WorkspaceQuery) -> Result<usize, RepositoryError> {
+        self.table.count(query)
+    }
     pub fn get_relationship(
         &self,
         id: &EntityId,
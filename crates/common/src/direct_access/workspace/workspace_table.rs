@@ -5,15 +5,16 @@
 // ═══════════════════════════════════════════════════════════════════════
 
 use crate::database::hashmap_store::{
-    HashMapStore, delete_from_backward_junction, junction_get, junction_remove, junction_set,
+    HashMapStore, JournaledMap, delete_from_backward_junction, junction_get, junction_remove,
+    junction_set,
 };
 use crate::entities::Workspace;
 use crate::error::RepositoryError;
 use crate::types::EntityId;
 use crate::{impl_relationship_methods, impl_write_relationship_methods};
-use im::HashMap;
 use std::sync::RwLock;
 
+use super::workspace_repository::WorkspaceQuery;
 use super::workspace_repository::WorkspaceRelationshipField;
 use super::workspace_repository::WorkspaceTable;
 use super::workspace_repository::WorkspaceTableRO;
@@ -30,8 +31,11 @@ impl<'a> WorkspaceHashMapTable<'a> {
     fn resolve_junction(
         &self,
         field: &WorkspaceRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
+            WorkspaceRelationshipField::CustomFiles => {
+                &self.store.jn_custom_file_from_workspace_custom_files
+            }
             WorkspaceRelationshipField::Entities => &self.store.jn_entity_from_workspace_entities,
             WorkspaceRelationshipField::Features => &self.store.jn_feature_from_workspace_features,
             WorkspaceRelationshipField::Global => &self.store.jn_global_from_workspace_global,
@@ -59,6 +63,10 @@ impl<'a> WorkspaceHashMapTable<'a> {
         {
             entity.user_interface = val;
         }
+        entity.custom_files = junction_get(
+            &self.store.jn_custom_file_from_workspace_custom_files,
+            &entity.id,
+        );
     }
 }
 
@@ -122,6 +130,11 @@ impl<'a> WorkspaceTable for WorkspaceHashMapTable<'a> {
 
             workspace_map.insert(new_entity.id, new_entity.clone());
 
+            junction_set(
+                &self.store.jn_custom_file_from_workspace_custom_files,
+                new_entity.id,
+                new_entity.custom_files.clone(),
+            );
             junction_set(
                 &self.store.jn_entity_from_workspace_entities,
                 new_entity.id,
@@ -181,6 +194,30 @@ impl<'a> WorkspaceTable for WorkspaceHashMapTable<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &WorkspaceQuery) -> Result<Vec<Workspace>, RepositoryError> {
+        let workspace_map = self.store.workspaces.read().unwrap();
+        let matching: Vec<Workspace> = workspace_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(workspace_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &WorkspaceQuery) -> Result<usize, RepositoryError> {
+        let workspace_map = self.store.workspaces.read().unwrap();
+        Ok(workspace_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     fn update(&mut self, entity: &Workspace) -> Result<Workspace, RepositoryError> {
         self.update_multi(std::slice::from_ref(entity))
             .map(|v| v.into_iter().next().unwrap())
@@ -262,6 +299,11 @@ impl<'a> WorkspaceTable for WorkspaceHashMapTable<'a> {
                 entity.id,
                 vec![entity.user_interface],
             );
+            junction_set(
+                &self.store.jn_custom_file_from_workspace_custom_files,
+                entity.id,
+                entity.custom_files.clone(),
+            );
         }
         drop(workspace_map);
         let ids: Vec<EntityId> = entities.iter().map(|e| e.id).collect();
@@ -287,6 +329,7 @@ impl<'a> WorkspaceTable for WorkspaceHashMapTable<'a> {
                 &self.store.jn_user_interface_from_workspace_user_interface,
                 id,
             );
+            junction_remove(&self.store.jn_custom_file_from_workspace_custom_files, id);
 
             // Clean up backward references (uses the owning entity's forward junction)
 
@@ -310,8 +353,11 @@ impl<'a> WorkspaceHashMapTableRO<'a> {
     fn resolve_junction(
         &self,
         field: &WorkspaceRelationshipField,
-    ) -> &RwLock<HashMap<EntityId, Vec<EntityId>>> {
+    ) -> &RwLock<JournaledMap<Vec<EntityId>>> {
         match field {
+            WorkspaceRelationshipField::CustomFiles => {
+                &self.store.jn_custom_file_from_workspace_custom_files
+            }
             WorkspaceRelationshipField::Entities => &self.store.jn_entity_from_workspace_entities,
             WorkspaceRelationshipField::Features => &self.store.jn_feature_from_workspace_features,
             WorkspaceRelationshipField::Global => &self.store.jn_global_from_workspace_global,
@@ -339,6 +385,10 @@ impl<'a> WorkspaceHashMapTableRO<'a> {
         {
             entity.user_interface = val;
         }
+        entity.custom_files = junction_get(
+            &self.store.jn_custom_file_from_workspace_custom_files,
+            &entity.id,
+        );
     }
 }
 
@@ -376,5 +426,29 @@ impl<'a> WorkspaceTableRO for WorkspaceHashMapTableRO<'a> {
         Ok(result)
     }
 
+    // Filters on the stored scalar fields, only the returned page is hydrated
+    fn query(&self, query: &WorkspaceQuery) -> Result<Vec<Workspace>, RepositoryError> {
+        let workspace_map = self.store.workspaces.read().unwrap();
+        let matching: Vec<Workspace> = workspace_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .cloned()
+            .collect();
+        drop(workspace_map);
+        let mut result = query.sort_and_page(matching);
+        for entity in result.iter_mut() {
+            self.hydrate(entity);
+        }
+        Ok(result)
+    }
+
+    fn count(&self, query: &WorkspaceQuery) -> Result<usize, RepositoryError> {
+        let workspace_map = self.store.workspaces.read().unwrap();
+        Ok(workspace_map
+            .values()
+            .filter(|entity| query.matches(entity))
+            .count())
+    }
+
     impl_relationship_methods!(WorkspaceHashMapTableRO<'a>, WorkspaceRelationshipField);
 }
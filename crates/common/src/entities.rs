@@ -2,6 +2,7 @@
 
 use crate::types::EntityId;
 use crate::types::HasId;
+use crate::types::Validate;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -21,6 +22,8 @@ impl HasId for Root {
     }
 }
 
+impl Validate for Root {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Workspace {
     pub id: EntityId,
@@ -33,6 +36,11 @@ pub struct Workspace {
     pub entities: Vec<EntityId>,
     pub features: Vec<EntityId>,
     pub user_interface: EntityId,
+    pub custom_files: Vec<EntityId>,
+    pub includes: Vec<String>,
+    pub manifest_text: String,
+    pub included_files: Vec<String>,
+    pub included_texts: Vec<String>,
 }
 
 impl HasId for Workspace {
@@ -41,6 +49,8 @@ impl HasId for Workspace {
     }
 }
 
+impl Validate for Workspace {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct System {
     pub id: EntityId,
@@ -58,6 +68,8 @@ impl HasId for System {
     }
 }
 
+impl Validate for System {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Entity {
     pub id: EntityId,
@@ -72,6 +84,8 @@ pub struct Entity {
     pub relationships: Vec<EntityId>,
     pub single_model: bool,
     pub undoable: bool,
+    pub undo_journal: bool,
+    pub source_file: String,
 }
 
 impl HasId for Entity {
@@ -80,6 +94,8 @@ impl HasId for Entity {
     }
 }
 
+impl Validate for Entity {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Field {
     pub id: EntityId,
@@ -98,6 +114,16 @@ pub struct Field {
     pub list_model_displayed_field: Option<String>,
     pub enum_name: Option<String>,
     pub enum_values: Vec<String>,
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub min_length: Option<i64>,
+    pub max_length: Option<i64>,
+    pub pattern: Option<String>,
+    pub not_empty: bool,
+    pub unique_in_parent: bool,
+    pub indexed: bool,
+    pub unique: bool,
+    pub default_value: Option<String>,
 }
 
 impl HasId for Field {
@@ -105,7 +131,9 @@ impl HasId for Field {
         self.id
     }
 }
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+
+impl Validate for Field {}
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum FieldType {
     #[default]
     Boolean,
@@ -117,8 +145,14 @@ pub enum FieldType {
     DateTime,
     Entity,
     Enum,
-}
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+    Decimal,
+    Date,
+    Time,
+    Duration,
+    Bytes,
+    Json,
+}
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum FieldRelationshipType {
     #[default]
     OneToOne,
@@ -137,6 +171,8 @@ pub struct Feature {
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub name: String,
     pub use_cases: Vec<EntityId>,
+    pub dtos: Vec<EntityId>,
+    pub source_file: String,
 }
 
 impl HasId for Feature {
@@ -145,6 +181,8 @@ impl HasId for Feature {
     }
 }
 
+impl Validate for Feature {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct File {
     pub id: EntityId,
@@ -173,15 +211,19 @@ impl HasId for File {
         self.id
     }
 }
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+
+impl Validate for File {}
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum FileStatus {
     #[default]
     Unknown,
     Unchanged,
     Modified,
     New,
+    Merged,
+    Conflict,
 }
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum FileNature {
     #[default]
     Infrastructure,
@@ -203,6 +245,8 @@ pub struct UseCase {
     pub long_operation: bool,
     pub dto_in: Option<EntityId>,
     pub dto_out: Option<EntityId>,
+    pub shared_dto_in: Option<EntityId>,
+    pub shared_dto_out: Option<EntityId>,
 }
 
 impl HasId for UseCase {
@@ -211,6 +255,8 @@ impl HasId for UseCase {
     }
 }
 
+impl Validate for UseCase {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Dto {
     pub id: EntityId,
@@ -228,6 +274,8 @@ impl HasId for Dto {
     }
 }
 
+impl Validate for Dto {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct DtoField {
     pub id: EntityId,
@@ -241,6 +289,14 @@ pub struct DtoField {
     pub is_list: bool,
     pub enum_name: Option<String>,
     pub enum_values: Vec<String>,
+    pub dto: Option<EntityId>,
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub min_length: Option<i64>,
+    pub max_length: Option<i64>,
+    pub pattern: Option<String>,
+    pub not_empty: bool,
+    pub default_value: Option<String>,
 }
 
 impl HasId for DtoField {
@@ -248,7 +304,9 @@ impl HasId for DtoField {
         self.id
     }
 }
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+
+impl Validate for DtoField {}
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum DtoFieldType {
     #[default]
     Boolean,
@@ -259,6 +317,13 @@ pub enum DtoFieldType {
     Uuid,
     DateTime,
     Enum,
+    Dto,
+    Decimal,
+    Date,
+    Time,
+    Duration,
+    Bytes,
+    Json,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -273,6 +338,8 @@ pub struct Global {
     pub organisation_name: String,
     pub organisation_domain: String,
     pub prefix_path: String,
+    pub storage: String,
+    pub templates_path: String,
 }
 
 impl HasId for Global {
@@ -281,6 +348,8 @@ impl HasId for Global {
     }
 }
 
+impl Validate for Global {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Relationship {
     pub id: EntityId,
@@ -303,7 +372,9 @@ impl HasId for Relationship {
         self.id
     }
 }
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+
+impl Validate for Relationship {}
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum RelationshipType {
     #[default]
     OneToOne,
@@ -312,19 +383,19 @@ pub enum RelationshipType {
     OrderedOneToMany,
     ManyToMany,
 }
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum Strength {
     #[default]
     Weak,
     Strong,
 }
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum Direction {
     #[default]
     Forward,
     Backward,
 }
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum Cardinality {
     #[default]
     ZeroOrOne,
@@ -332,7 +403,7 @@ pub enum Cardinality {
     ZeroOrMore,
     OneOrMore,
 }
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum Order {
     #[default]
     Ordered,
@@ -359,3 +430,27 @@ impl HasId for UserInterface {
         self.id
     }
 }
+
+impl Validate for UserInterface {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CustomFile {
+    pub id: EntityId,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub path: String,
+    pub template_name: String,
+    pub group: String,
+    pub nature: String,
+    pub scope: String,
+}
+
+impl HasId for CustomFile {
+    fn id(&self) -> EntityId {
+        self.id
+    }
+}
+
+impl Validate for CustomFile {}
@@ -22,6 +22,14 @@ pub enum RepositoryError {
     #[error("serialization error: {0}")]
     Serialization(String),
 
+    /// A value of a `unique` field is already held by another entity.
+    #[error("{entity}.{field} value already used by {entity} {existing_id}")]
+    UniqueViolation {
+        entity: &'static str,
+        field: &'static str,
+        existing_id: EntityId,
+    },
+
     /// A one-to-one constraint was violated.
     #[error("constraint violation: {0}")]
     ConstraintViolation(String),
@@ -30,3 +38,38 @@ pub enum RepositoryError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+/// What a field value was rejected for.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationErrorKind {
+    #[error("must be at least {min}")]
+    BelowMin { min: &'static str },
+
+    #[error("must be at most {max}")]
+    AboveMax { max: &'static str },
+
+    #[error("must have at least {min_length} elements or characters")]
+    TooShort { min_length: usize },
+
+    #[error("must have at most {max_length} elements or characters")]
+    TooLong { max_length: usize },
+
+    #[error("must match {pattern}")]
+    PatternMismatch { pattern: &'static str },
+
+    #[error("must not be empty")]
+    Empty,
+
+    #[error("is already used by another entity of the same owner")]
+    NotUnique,
+}
+
+/// A field value violating a constraint declared in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{type_name}.{field} {kind}")]
+pub struct ValidationError {
+    /// Entity or DTO name
+    pub type_name: &'static str,
+    pub field: &'static str,
+    pub kind: ValidationErrorKind,
+}
@@ -55,6 +55,7 @@ pub enum DirectAccessEntity {
     Global(EntityEvent),
     Relationship(EntityEvent),
     UserInterface(EntityEvent),
+    CustomFile(EntityEvent),
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
@@ -71,6 +72,8 @@ pub enum HandlingManifestEvent {
     Close,
     ExportToMermaid,
     Check,
+    Upgrade,
+    Refactor,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
@@ -95,6 +98,7 @@ pub enum CppQtFileGenerationEvent {
 pub enum FileGenerationSharedStepsEvent {
     FillStatusInFiles,
     GetFileDiff,
+    MergeFile,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
@@ -144,6 +148,9 @@ impl Event {
                 DirectAccessEntity::UserInterface(event) => {
                     format!("direct_access_user_interface_{:?}", event)
                 }
+                DirectAccessEntity::CustomFile(event) => {
+                    format!("direct_access_custom_file_{:?}", event)
+                }
             },
             Origin::UndoRedo(event) => format!("undo_redo_{:?}", event),
             Origin::LongOperation(event) => format!("long_operation_{:?}", event),
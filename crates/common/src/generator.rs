@@ -1 +1,7 @@
+pub mod baseline_store;
+pub mod custom_files;
+pub mod field_constraints;
+pub mod field_defaults;
 pub mod file_list_builder;
+pub mod template_overrides;
+pub mod user_regions;
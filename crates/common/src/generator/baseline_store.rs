@@ -0,0 +1,68 @@
+//! Storage of the last generated content of each file, next to the manifest.
+//!
+//! The baseline is the common ancestor used by the three-way merge performed on
+//! regeneration: it records what Qleany wrote the last time a file was generated,
+//! so that user edits (disk vs baseline) and generator changes (new generation vs
+//! baseline) can be told apart.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Directory, relative to the manifest folder, where baselines are stored.
+pub const BASELINE_DIR: &str = ".qleany/baseline";
+
+/// Path of the baseline for a file, mirroring its location relative to the manifest folder.
+pub fn baseline_path(root_path: &Path, relative_path: &str, name: &str) -> PathBuf {
+    let mut path = root_path.join(BASELINE_DIR);
+    if !relative_path.is_empty() {
+        path = path.join(relative_path);
+    }
+    path.join(name)
+}
+
+/// Read the baseline of a file. Returns `None` if the file was never generated
+/// with a baseline (or if the baseline can't be read).
+pub fn read_baseline(root_path: &Path, relative_path: &str, name: &str) -> Option<String> {
+    std::fs::read_to_string(baseline_path(root_path, relative_path, name)).ok()
+}
+
+/// Record `content` as the last generated content of a file.
+pub fn write_baseline(
+    root_path: &Path,
+    relative_path: &str,
+    name: &str,
+    content: &str,
+) -> Result<()> {
+    let path = baseline_path(root_path, relative_path, name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_baseline_path_mirrors_relative_path() {
+        let path = baseline_path(Path::new("/project"), "crates/common/src/", "lib.rs");
+        assert_eq!(
+            path,
+            PathBuf::from("/project/.qleany/baseline/crates/common/src/lib.rs")
+        );
+    }
+
+    #[test]
+    fn test_write_then_read_baseline() {
+        let root = std::env::temp_dir().join(format!("qleany_baseline_{}", std::process::id()));
+        write_baseline(&root, "src/", "main.rs", "fn main() {}").unwrap();
+        assert_eq!(
+            read_baseline(&root, "src/", "main.rs").as_deref(),
+            Some("fn main() {}")
+        );
+        assert_eq!(read_baseline(&root, "src/", "missing.rs"), None);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
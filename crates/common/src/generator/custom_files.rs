@@ -0,0 +1,352 @@
+//! Files declared in the `custom_files` section of the manifest.
+//!
+//! A custom file is rendered from a project template (see `templates_path`) and
+//! listed alongside the built-in files, so it goes through the same check, diff,
+//! merge and write steps. Its path may contain placeholders, expanded once, per
+//! entity, per feature or per use case depending on its scope:
+//!
+//! ```yaml
+//! custom_files:
+//!   - path: "{prefix}/{feature}/src/{feature}_metrics.rs"
+//!     template: feature_metrics
+//!     scope: per_feature
+//! ```
+//!
+//! Every custom file is rendered with the whole model (all entities and all
+//! features) in addition to its own entity, feature or use case.
+
+use crate::entities::{CustomFile, Entity, Feature, File, FileNature, UseCase};
+use crate::generator::file_list_builder::FileListBuilder;
+use heck::{ToSnakeCase, ToUpperCamelCase};
+
+/// Group of the custom files without an explicit group.
+pub const DEFAULT_GROUP: &str = "custom";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomFileScope {
+    Once,
+    PerEntity,
+    PerFeature,
+    PerUseCase,
+}
+
+impl CustomFileScope {
+    /// Parse a manifest scope. An empty scope means `once`.
+    pub fn parse(scope: &str) -> Option<Self> {
+        match scope {
+            "" | "once" => Some(Self::Once),
+            "per_entity" => Some(Self::PerEntity),
+            "per_feature" => Some(Self::PerFeature),
+            "per_use_case" => Some(Self::PerUseCase),
+            _ => None,
+        }
+    }
+
+    /// Placeholders usable in the path of a custom file of this scope.
+    pub fn placeholders(&self) -> &'static [&'static str] {
+        match self {
+            Self::Once => &["prefix"],
+            Self::PerEntity => &["prefix", "entity", "entity_pascal"],
+            Self::PerFeature => &["prefix", "feature", "feature_pascal"],
+            Self::PerUseCase => &[
+                "prefix",
+                "feature",
+                "feature_pascal",
+                "use_case",
+                "use_case_pascal",
+            ],
+        }
+    }
+
+    /// Placeholders of which the path must contain at least one, so that each
+    /// expanded file gets its own path.
+    fn distinguishing_placeholders(&self) -> &'static [&'static str] {
+        match self {
+            Self::Once => &[],
+            Self::PerEntity => &["entity", "entity_pascal"],
+            Self::PerFeature => &["feature", "feature_pascal"],
+            Self::PerUseCase => &["use_case", "use_case_pascal"],
+        }
+    }
+}
+
+/// Parse a manifest nature. An empty nature means `scaffold`.
+pub fn parse_nature(nature: &str) -> Option<FileNature> {
+    match nature {
+        "infrastructure" => Some(FileNature::Infrastructure),
+        "aggregate" => Some(FileNature::Aggregate),
+        "" | "scaffold" => Some(FileNature::Scaffold),
+        _ => None,
+    }
+}
+
+/// Names of the `{placeholders}` of a path pattern, in order of appearance.
+fn placeholders_of(path: &str) -> Vec<&str> {
+    let mut names = vec![];
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        names.push(&rest[start + 1..start + len]);
+        rest = &rest[start + len + 1..];
+    }
+    names
+}
+
+/// Problems of a custom file declaration, empty when it is valid.
+pub fn validate_custom_file(custom_file: &CustomFile) -> Vec<String> {
+    let mut errors = vec![];
+    let path = custom_file.path.trim();
+
+    if path.is_empty() {
+        errors.push("path must not be empty".to_string());
+    } else if path.ends_with('/') {
+        errors.push(format!("path '{}' must end with a file name", path));
+    } else if path.starts_with('/') || path.split('/').any(|part| part == "..") {
+        errors.push(format!(
+            "path '{}' must be relative to the manifest folder",
+            path
+        ));
+    }
+    if custom_file.template_name.trim().is_empty() {
+        errors.push("template must not be empty".to_string());
+    }
+    if parse_nature(&custom_file.nature).is_none() {
+        errors.push(format!(
+            "nature '{}' is not supported (expected 'infrastructure', 'aggregate' or 'scaffold')",
+            custom_file.nature
+        ));
+    }
+
+    let Some(scope) = CustomFileScope::parse(&custom_file.scope) else {
+        errors.push(format!(
+            "scope '{}' is not supported (expected 'once', 'per_entity', 'per_feature' or 'per_use_case')",
+            custom_file.scope
+        ));
+        return errors;
+    };
+
+    let placeholders = placeholders_of(path);
+    for placeholder in &placeholders {
+        if !scope.placeholders().contains(placeholder) {
+            errors.push(format!(
+                "placeholder '{{{}}}' is not available with scope '{}'",
+                placeholder, custom_file.scope
+            ));
+        }
+    }
+    let distinguishing = scope.distinguishing_placeholders();
+    if !distinguishing.is_empty() && !placeholders.iter().any(|p| distinguishing.contains(p)) {
+        errors.push(format!(
+            "path '{}' must contain {{{}}} or {{{}}} with scope '{}'",
+            path, distinguishing[0], distinguishing[1], custom_file.scope
+        ));
+    }
+
+    errors
+}
+
+fn expand_path(pattern: &str, values: &[(&str, String)]) -> String {
+    values
+        .iter()
+        .fold(pattern.trim().to_string(), |path, (name, value)| {
+            path.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+fn add_file<'a>(
+    b: &'a mut FileListBuilder,
+    custom_file: &CustomFile,
+    nature: &FileNature,
+    path: String,
+) -> &'a mut File {
+    let (relative_path, name) = match path.rsplit_once('/') {
+        Some((dir, name)) => (format!("{}/", dir), name.to_string()),
+        None => (String::new(), path),
+    };
+    let group = if custom_file.group.trim().is_empty() {
+        DEFAULT_GROUP
+    } else {
+        custom_file.group.trim()
+    };
+    let file = b.add(
+        name,
+        relative_path,
+        group,
+        custom_file.template_name.trim(),
+        nature.clone(),
+    );
+    file.all_entities = true;
+    file.all_features = true;
+    file
+}
+
+/// Expand the custom files of the manifest into `b`. Entities only meant for
+/// heritage are skipped, like for the built-in per-entity files.
+///
+/// Invalid declarations are skipped, `check` reports them.
+pub fn add_custom_files(
+    b: &mut FileListBuilder,
+    custom_files: &[CustomFile],
+    prefix: &str,
+    entities: &[Entity],
+    features: &[(Feature, Vec<UseCase>)],
+) {
+    for custom_file in custom_files {
+        if !validate_custom_file(custom_file).is_empty() {
+            continue;
+        }
+        let (Some(scope), Some(nature)) = (
+            CustomFileScope::parse(&custom_file.scope),
+            parse_nature(&custom_file.nature),
+        ) else {
+            continue;
+        };
+        let prefix_value = ("prefix", prefix.to_string());
+
+        match scope {
+            CustomFileScope::Once => {
+                let path = expand_path(&custom_file.path, &[prefix_value]);
+                add_file(b, custom_file, &nature, path);
+            }
+            CustomFileScope::PerEntity => {
+                for entity in entities.iter().filter(|e| !e.only_for_heritage) {
+                    let path = expand_path(
+                        &custom_file.path,
+                        &[
+                            prefix_value.clone(),
+                            ("entity", entity.name.to_snake_case()),
+                            ("entity_pascal", entity.name.to_upper_camel_case()),
+                        ],
+                    );
+                    add_file(b, custom_file, &nature, path).entity = Some(entity.id);
+                }
+            }
+            CustomFileScope::PerFeature => {
+                for (feature, _) in features {
+                    let path = expand_path(
+                        &custom_file.path,
+                        &[
+                            prefix_value.clone(),
+                            ("feature", feature.name.to_snake_case()),
+                            ("feature_pascal", feature.name.to_upper_camel_case()),
+                        ],
+                    );
+                    add_file(b, custom_file, &nature, path).feature = Some(feature.id);
+                }
+            }
+            CustomFileScope::PerUseCase => {
+                for (feature, use_cases) in features {
+                    for use_case in use_cases {
+                        let path = expand_path(
+                            &custom_file.path,
+                            &[
+                                prefix_value.clone(),
+                                ("feature", feature.name.to_snake_case()),
+                                ("feature_pascal", feature.name.to_upper_camel_case()),
+                                ("use_case", use_case.name.to_snake_case()),
+                                ("use_case_pascal", use_case.name.to_upper_camel_case()),
+                            ],
+                        );
+                        let file = add_file(b, custom_file, &nature, path);
+                        file.feature = Some(feature.id);
+                        file.use_case = Some(use_case.id);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_file(path: &str, scope: &str) -> CustomFile {
+        CustomFile {
+            path: path.to_string(),
+            template_name: "my_template".to_string(),
+            group: String::new(),
+            nature: "scaffold".to_string(),
+            scope: scope.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn entity(id: u64, name: &str, only_for_heritage: bool) -> Entity {
+        Entity {
+            id,
+            name: name.to_string(),
+            only_for_heritage,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_custom_file() {
+        assert!(validate_custom_file(&custom_file("docs/model.md", "once")).is_empty());
+        assert!(
+            validate_custom_file(&custom_file("{prefix}/{entity}/x.rs", "per_entity")).is_empty()
+        );
+
+        let errors = validate_custom_file(&custom_file("{prefix}/x.rs", "per_entity"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("{entity}"));
+
+        let errors = validate_custom_file(&custom_file("{entity}.rs", "once"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("not available"));
+
+        assert!(!validate_custom_file(&custom_file("../x.rs", "once")).is_empty());
+        assert!(!validate_custom_file(&custom_file("x.rs", "per_field")).is_empty());
+    }
+
+    #[test]
+    fn test_add_custom_files_expands_per_scope() {
+        let entities = vec![entity(1, "EntityBase", true), entity(2, "CarPart", false)];
+        let feature = Feature {
+            id: 10,
+            name: "car_management".to_string(),
+            ..Default::default()
+        };
+        let use_case = UseCase {
+            id: 20,
+            name: "repaint".to_string(),
+            ..Default::default()
+        };
+        let custom_files = vec![
+            custom_file("README.md", "once"),
+            custom_file("{prefix}/{entity}/{entity_pascal}.rs", "per_entity"),
+            custom_file("{prefix}/{feature}/{use_case_pascal}.md", "per_use_case"),
+        ];
+
+        let mut b = FileListBuilder::new();
+        add_custom_files(
+            &mut b,
+            &custom_files,
+            "crates",
+            &entities,
+            &[(feature, vec![use_case])],
+        );
+        let files = b.build();
+
+        let paths: Vec<String> = files
+            .iter()
+            .map(|f| format!("{}{}", f.relative_path, f.name))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "README.md",
+                "crates/car_part/CarPart.rs",
+                "crates/car_management/Repaint.md",
+            ]
+        );
+        assert!(files.iter().all(|f| f.group == DEFAULT_GROUP));
+        assert!(files.iter().all(|f| f.template_name == "my_template"));
+        assert_eq!(files[1].entity, Some(2));
+        assert_eq!(files[2].feature, Some(10));
+        assert_eq!(files[2].use_case, Some(20));
+    }
+}
@@ -0,0 +1,342 @@
+//! Validation constraints declared on entity fields and DTO fields.
+//!
+//! ```yaml
+//! - name: title
+//!   type: string
+//!   constraints:
+//!     not_empty: true
+//!     max_length: 80
+//!     pattern: "^[A-Z]"
+//! ```
+//!
+//! `check` uses [`constraint_errors`] to reject constraints that don't apply to
+//! the field type, and the generators use [`FieldConstraints`] to emit the
+//! validation code, with `min`/`max` already written as literals of the field type.
+
+use crate::entities::{DtoField, DtoFieldType, Field, FieldType};
+use serde::Serialize;
+
+/// What a constraint can be applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Integer,
+    UInteger,
+    Float,
+    Text,
+    Other,
+}
+
+impl From<&FieldType> for ValueKind {
+    fn from(field_type: &FieldType) -> Self {
+        match field_type {
+            FieldType::Integer => ValueKind::Integer,
+            FieldType::UInteger => ValueKind::UInteger,
+            FieldType::Float => ValueKind::Float,
+            FieldType::String => ValueKind::Text,
+            _ => ValueKind::Other,
+        }
+    }
+}
+
+impl From<&DtoFieldType> for ValueKind {
+    fn from(field_type: &DtoFieldType) -> Self {
+        match field_type {
+            DtoFieldType::Integer => ValueKind::Integer,
+            DtoFieldType::UInteger => ValueKind::UInteger,
+            DtoFieldType::Float => ValueKind::Float,
+            DtoFieldType::String => ValueKind::Text,
+            _ => ValueKind::Other,
+        }
+    }
+}
+
+/// Constraints of a field, as exposed to the templates.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FieldConstraints {
+    /// Minimum value, as a literal of the field type (`0`, `0.0`)
+    pub min: Option<String>,
+    /// Maximum value, as a literal of the field type
+    pub max: Option<String>,
+    /// Minimum length of a string (in characters) or of a list
+    pub min_length: Option<i64>,
+    /// Maximum length of a string (in characters) or of a list
+    pub max_length: Option<i64>,
+    pub pattern: Option<String>,
+    pub not_empty: bool,
+    pub unique_in_parent: bool,
+    /// Whether a single value can be rejected, i.e. any constraint but unique_in_parent
+    pub has_checks: bool,
+    /// Valid number literal for generated tests, when the default value is not
+    pub sample_number: Option<String>,
+    /// Valid string for generated tests, when the empty string is not
+    pub sample_text: Option<String>,
+    /// Valid length of a list for generated tests, when an empty list is not
+    pub sample_length: Option<i64>,
+}
+
+impl FieldConstraints {
+    pub fn is_empty(&self) -> bool {
+        *self == FieldConstraints::default()
+    }
+
+    /// Constraints of an entity field, `None` when it has none.
+    pub fn from_field(field: &Field) -> Option<Self> {
+        Self::build(
+            ValueKind::from(&field.field_type),
+            field.is_list,
+            FieldConstraints {
+                min: field.min_value.clone(),
+                max: field.max_value.clone(),
+                min_length: field.min_length,
+                max_length: field.max_length,
+                pattern: field.pattern.clone(),
+                not_empty: field.not_empty,
+                unique_in_parent: field.unique_in_parent,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Constraints of a DTO field, `None` when it has none.
+    pub fn from_dto_field(field: &DtoField) -> Option<Self> {
+        Self::build(
+            ValueKind::from(&field.field_type),
+            field.is_list,
+            FieldConstraints {
+                min: field.min_value.clone(),
+                max: field.max_value.clone(),
+                min_length: field.min_length,
+                max_length: field.max_length,
+                pattern: field.pattern.clone(),
+                not_empty: field.not_empty,
+                unique_in_parent: false,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn build(kind: ValueKind, is_list: bool, mut constraints: FieldConstraints) -> Option<Self> {
+        if constraints.is_empty() {
+            return None;
+        }
+        constraints.has_checks = constraints.min.is_some()
+            || constraints.max.is_some()
+            || constraints.min_length.is_some()
+            || constraints.max_length.is_some()
+            || constraints.pattern.is_some()
+            || constraints.not_empty;
+        constraints.min = constraints.min.map(|v| number_literal(kind, &v));
+        constraints.max = constraints.max.map(|v| number_literal(kind, &v));
+
+        let min_length = constraints
+            .min_length
+            .unwrap_or(0)
+            .max(i64::from(constraints.not_empty));
+        if is_list {
+            constraints.sample_length = (min_length > 0).then_some(min_length);
+        } else if kind == ValueKind::Text {
+            if min_length > 0 || constraints.pattern.is_some() {
+                constraints.sample_text = sample_text(
+                    min_length,
+                    constraints.max_length,
+                    constraints.pattern.as_deref(),
+                );
+            }
+        } else {
+            constraints.sample_number = constraints.min.clone().or(constraints.max.clone());
+        }
+        Some(constraints)
+    }
+}
+
+/// Float literals need a decimal point, in Rust as in C++.
+fn number_literal(kind: ValueKind, value: &str) -> String {
+    let value = value.trim();
+    if kind == ValueKind::Float && !value.contains(['.', 'e', 'E']) {
+        format!("{}.0", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Problems of the constraints of a field, empty when they all apply to it.
+/// The validity of `pattern` as a regular expression is not checked here.
+pub fn constraint_errors(
+    kind: ValueKind,
+    is_list: bool,
+    is_relationship: bool,
+    constraints: &FieldConstraints,
+) -> Vec<String> {
+    let mut errors = vec![];
+    let is_number = !is_list
+        && matches!(
+            kind,
+            ValueKind::Integer | ValueKind::UInteger | ValueKind::Float
+        );
+    let has_length = is_list || kind == ValueKind::Text;
+
+    let mut bounds = vec![];
+    for (name, value) in [("min", &constraints.min), ("max", &constraints.max)] {
+        let Some(value) = value else {
+            continue;
+        };
+        if !is_number {
+            errors.push(format!("'{}' only applies to numeric fields", name));
+            continue;
+        }
+        let parsed = match kind {
+            ValueKind::Integer => value.parse::<i64>().ok().map(|v| v as f64),
+            ValueKind::UInteger => value.parse::<u64>().ok().map(|v| v as f64),
+            _ => value.parse::<f64>().ok().filter(|v| v.is_finite()),
+        };
+        match parsed {
+            Some(parsed) => bounds.push(parsed),
+            None => errors.push(format!(
+                "'{}' value {} is not valid for this field type",
+                name, value
+            )),
+        }
+    }
+    if let [min, max] = bounds[..]
+        && min > max
+    {
+        errors.push("'min' must not be greater than 'max'".to_string());
+    }
+
+    for (name, value) in [
+        ("min_length", constraints.min_length),
+        ("max_length", constraints.max_length),
+    ] {
+        if value.is_some_and(|v| v < 0) {
+            errors.push(format!("'{}' must not be negative", name));
+        }
+        if value.is_some() && !has_length {
+            errors.push(format!("'{}' only applies to string and list fields", name));
+        }
+    }
+    if let (Some(min), Some(max)) = (constraints.min_length, constraints.max_length)
+        && min > max
+    {
+        errors.push("'min_length' must not be greater than 'max_length'".to_string());
+    }
+    if constraints.not_empty && !has_length {
+        errors.push("'not_empty' only applies to string and list fields".to_string());
+    }
+    if constraints.pattern.is_some() && (is_list || kind != ValueKind::Text) {
+        errors.push("'pattern' only applies to string fields".to_string());
+    }
+    if constraints.unique_in_parent && (is_list || is_relationship) {
+        errors.push("'unique_in_parent' does not apply to lists and relationships".to_string());
+    }
+
+    errors
+}
+
+/// Short string made of a head and a repeated tail that satisfies the length
+/// bounds and the pattern, if any. Patterns can't be inverted in general, so this
+/// is best effort: `None` when no candidate matches.
+fn sample_text(min_length: i64, max_length: Option<i64>, pattern: Option<&str>) -> Option<String> {
+    const CANDIDATES: [(&str, &str); 9] = [
+        ("", "a"),
+        ("", "A"),
+        ("", "0"),
+        ("A", "a"),
+        ("a", "0"),
+        ("A", "0"),
+        ("a", "_"),
+        ("a", "-"),
+        ("a@a.", "a"),
+    ];
+    let regex = match pattern {
+        Some(pattern) => Some(regex::Regex::new(pattern).ok()?),
+        None => None,
+    };
+    let min_length = min_length.max(1) as usize;
+    CANDIDATES.iter().find_map(|(head, tail)| {
+        let text = format!(
+            "{}{}",
+            head,
+            tail.repeat(min_length.saturating_sub(head.len()).max(1))
+        );
+        let fits = max_length.is_none_or(|max| text.chars().count() as i64 <= max)
+            && regex.as_ref().is_none_or(|r| r.is_match(&text));
+        fits.then_some(text)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_field_writes_literals_of_the_field_type() {
+        let field = Field {
+            field_type: FieldType::Float,
+            min_value: Some("0".to_string()),
+            max_value: Some("2.5".to_string()),
+            ..Default::default()
+        };
+        let constraints = FieldConstraints::from_field(&field).unwrap();
+        assert_eq!(constraints.min.as_deref(), Some("0.0"));
+        assert_eq!(constraints.max.as_deref(), Some("2.5"));
+
+        assert_eq!(FieldConstraints::from_field(&Field::default()), None);
+    }
+
+    #[test]
+    fn test_samples_satisfy_the_constraints() {
+        let field = Field {
+            field_type: FieldType::String,
+            min_length: Some(3),
+            pattern: Some("^[A-Z][a-z]+$".to_string()),
+            ..Default::default()
+        };
+        let constraints = FieldConstraints::from_field(&field).unwrap();
+        assert_eq!(constraints.sample_text.as_deref(), Some("Aaa"));
+
+        let field = Field {
+            field_type: FieldType::UInteger,
+            is_list: true,
+            not_empty: true,
+            ..Default::default()
+        };
+        let constraints = FieldConstraints::from_field(&field).unwrap();
+        assert_eq!(constraints.sample_length, Some(1));
+        assert_eq!(constraints.sample_number, None);
+    }
+
+    #[test]
+    fn test_constraint_errors() {
+        let text = FieldConstraints {
+            max_length: Some(80),
+            pattern: Some("^[a-z]+$".to_string()),
+            not_empty: true,
+            unique_in_parent: true,
+            ..Default::default()
+        };
+        assert!(constraint_errors(ValueKind::Text, false, false, &text).is_empty());
+        assert_eq!(
+            constraint_errors(ValueKind::Integer, false, false, &text).len(),
+            3
+        );
+
+        let bounds = FieldConstraints {
+            min: Some("10".to_string()),
+            max: Some("1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            constraint_errors(ValueKind::Integer, false, false, &bounds),
+            vec!["'min' must not be greater than 'max'".to_string()]
+        );
+        let negative = FieldConstraints {
+            min: Some("-1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            constraint_errors(ValueKind::UInteger, false, false, &negative).len(),
+            1
+        );
+        assert!(constraint_errors(ValueKind::Integer, true, false, &negative).len() == 1);
+    }
+}
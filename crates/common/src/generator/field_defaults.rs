@@ -0,0 +1,283 @@
+//! Default values declared on entity fields and DTO fields.
+//!
+//! ```yaml
+//! - name: priority
+//!   type: integer
+//!   default: 3
+//! - name: created_on
+//!   type: datetime
+//!   default: now
+//! - name: status
+//!   type: enum
+//!   enum_name: TaskStatus
+//!   enum_values: [Todo, Doing, Done]
+//!   default: Doing
+//! ```
+//!
+//! `check` uses [`FieldDefault::parse`] to reject defaults that don't fit the
+//! field type, and the generators turn the parsed [`FieldDefault`] into Rust
+//! or C++ expressions, so they never see a value in a form they don't expect.
+
+use crate::entities::{DtoField, DtoFieldType, Field, FieldType};
+use crate::enum_variant_parser::{self, EnumVariantKind};
+
+/// Field types, as far as their default is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultKind {
+    Boolean,
+    Integer,
+    UInteger,
+    Float,
+    Text,
+    Uuid,
+    DateTime,
+    Enum,
+    Decimal,
+    Date,
+    Time,
+    Duration,
+    /// Relationships, bytes, json and nested DTOs, which take no default
+    None,
+}
+
+impl From<&FieldType> for DefaultKind {
+    fn from(field_type: &FieldType) -> Self {
+        match field_type {
+            FieldType::Boolean => DefaultKind::Boolean,
+            FieldType::Integer => DefaultKind::Integer,
+            FieldType::UInteger => DefaultKind::UInteger,
+            FieldType::Float => DefaultKind::Float,
+            FieldType::String => DefaultKind::Text,
+            FieldType::Uuid => DefaultKind::Uuid,
+            FieldType::DateTime => DefaultKind::DateTime,
+            FieldType::Enum => DefaultKind::Enum,
+            FieldType::Decimal => DefaultKind::Decimal,
+            FieldType::Date => DefaultKind::Date,
+            FieldType::Time => DefaultKind::Time,
+            FieldType::Duration => DefaultKind::Duration,
+            FieldType::Entity | FieldType::Bytes | FieldType::Json => DefaultKind::None,
+        }
+    }
+}
+
+impl From<&DtoFieldType> for DefaultKind {
+    fn from(field_type: &DtoFieldType) -> Self {
+        match field_type {
+            DtoFieldType::Boolean => DefaultKind::Boolean,
+            DtoFieldType::Integer => DefaultKind::Integer,
+            DtoFieldType::UInteger => DefaultKind::UInteger,
+            DtoFieldType::Float => DefaultKind::Float,
+            DtoFieldType::String => DefaultKind::Text,
+            DtoFieldType::Uuid => DefaultKind::Uuid,
+            DtoFieldType::DateTime => DefaultKind::DateTime,
+            DtoFieldType::Enum => DefaultKind::Enum,
+            DtoFieldType::Decimal => DefaultKind::Decimal,
+            DtoFieldType::Date => DefaultKind::Date,
+            DtoFieldType::Time => DefaultKind::Time,
+            DtoFieldType::Duration => DefaultKind::Duration,
+            DtoFieldType::Bytes | DtoFieldType::Json | DtoFieldType::Dto => DefaultKind::None,
+        }
+    }
+}
+
+/// A default value checked against its field type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldDefault {
+    Boolean(bool),
+    /// Number, as a literal of the field type (`3`, `2.0`)
+    Number(String),
+    Text(String),
+    /// Uuid, datetime (RFC 3339, UTC), decimal, date or time, in its normalized text form
+    Parsed(String),
+    /// Name of a simple variant of the field's enum
+    Variant(String),
+    /// Duration in milliseconds
+    Millis(u64),
+    /// `now`: the current date and time
+    Now,
+    /// `new_uuid`: a new random uuid
+    NewUuid,
+}
+
+impl FieldDefault {
+    /// Default of an entity field, `None` when it has none or when it is invalid.
+    pub fn from_field(field: &Field) -> Option<Self> {
+        let value = field.default_value.as_deref()?;
+        Self::parse(
+            DefaultKind::from(&field.field_type),
+            field.is_list,
+            value,
+            &field.enum_values,
+        )
+        .ok()
+    }
+
+    /// Default of a DTO field, `None` when it has none or when it is invalid.
+    pub fn from_dto_field(field: &DtoField) -> Option<Self> {
+        let value = field.default_value.as_deref()?;
+        Self::parse(
+            DefaultKind::from(&field.field_type),
+            field.is_list,
+            value,
+            &field.enum_values,
+        )
+        .ok()
+    }
+
+    /// Check `value` against the field type.
+    pub fn parse(
+        kind: DefaultKind,
+        is_list: bool,
+        value: &str,
+        enum_values: &[String],
+    ) -> Result<Self, String> {
+        if is_list {
+            return Err("list fields cannot have a default, they start empty".to_string());
+        }
+        let invalid = || {
+            format!(
+                "'default' value '{}' is not valid for this field type",
+                value
+            )
+        };
+        let trimmed = value.trim();
+        match kind {
+            DefaultKind::Boolean => trimmed
+                .parse()
+                .map(FieldDefault::Boolean)
+                .map_err(|_| invalid()),
+            DefaultKind::Integer => trimmed
+                .parse::<i64>()
+                .map(|v| FieldDefault::Number(v.to_string()))
+                .map_err(|_| invalid()),
+            DefaultKind::UInteger => trimmed
+                .parse::<u64>()
+                .map(|v| FieldDefault::Number(v.to_string()))
+                .map_err(|_| invalid()),
+            DefaultKind::Float => match trimmed.parse::<f64>() {
+                Ok(v) if v.is_finite() && !trimmed.contains(['.', 'e', 'E']) => {
+                    Ok(FieldDefault::Number(format!("{}.0", trimmed)))
+                }
+                Ok(v) if v.is_finite() => Ok(FieldDefault::Number(trimmed.to_string())),
+                _ => Err(invalid()),
+            },
+            DefaultKind::Text => Ok(FieldDefault::Text(value.to_string())),
+            DefaultKind::Uuid if trimmed == "new_uuid" => Ok(FieldDefault::NewUuid),
+            DefaultKind::Uuid => uuid::Uuid::parse_str(trimmed)
+                .map(|v| FieldDefault::Parsed(v.hyphenated().to_string()))
+                .map_err(|_| invalid()),
+            DefaultKind::DateTime if trimmed == "now" => Ok(FieldDefault::Now),
+            DefaultKind::DateTime => chrono::DateTime::parse_from_rfc3339(trimmed)
+                .map(|v| v.to_utc())
+                .or_else(|_| {
+                    chrono::NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%dT%H:%M:%S%.f")
+                        .map(|v| v.and_utc())
+                })
+                .map(|v| {
+                    FieldDefault::Parsed(v.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+                })
+                .map_err(|_| invalid()),
+            DefaultKind::Enum => {
+                let variant = enum_values
+                    .iter()
+                    .filter_map(|v| enum_variant_parser::parse_enum_variant(v).ok())
+                    .find(|v| v.name == trimmed);
+                match variant {
+                    Some(v) if v.kind == EnumVariantKind::Simple => {
+                        Ok(FieldDefault::Variant(v.name))
+                    }
+                    Some(_) => Err(format!(
+                        "'default' variant '{}' carries data, only simple variants can be defaults",
+                        trimmed
+                    )),
+                    None => Err(format!(
+                        "'default' value '{}' is not one of the enum values",
+                        trimmed
+                    )),
+                }
+            }
+            DefaultKind::Decimal => {
+                let digits = trimmed.strip_prefix(['-', '+']).unwrap_or(trimmed);
+                let (whole, fraction) = digits.split_once('.').unwrap_or((digits, "0"));
+                let is_number =
+                    |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+                if is_number(whole) && is_number(fraction) {
+                    Ok(FieldDefault::Parsed(trimmed.to_string()))
+                } else {
+                    Err(invalid())
+                }
+            }
+            DefaultKind::Date => chrono::NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+                .map(|v| FieldDefault::Parsed(v.format("%Y-%m-%d").to_string()))
+                .map_err(|_| invalid()),
+            DefaultKind::Time => chrono::NaiveTime::parse_from_str(trimmed, "%H:%M:%S%.f")
+                .or_else(|_| chrono::NaiveTime::parse_from_str(trimmed, "%H:%M"))
+                .map(|v| FieldDefault::Parsed(v.format("%H:%M:%S%.f").to_string()))
+                .map_err(|_| invalid()),
+            DefaultKind::Duration => {
+                trimmed
+                    .parse::<u64>()
+                    .map(FieldDefault::Millis)
+                    .map_err(|_| {
+                        format!(
+                            "'default' value '{}' is not a number of milliseconds",
+                            value
+                        )
+                    })
+            }
+            DefaultKind::None => {
+                Err("relationship, bytes, json and dto fields cannot have a default".to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_normalizes_values() {
+        assert_eq!(
+            FieldDefault::parse(DefaultKind::Float, false, "2", &[]),
+            Ok(FieldDefault::Number("2.0".to_string()))
+        );
+        assert_eq!(
+            FieldDefault::parse(DefaultKind::DateTime, false, "2024-01-15T10:30:00", &[]),
+            Ok(FieldDefault::Parsed("2024-01-15T10:30:00Z".to_string()))
+        );
+        assert_eq!(
+            FieldDefault::parse(DefaultKind::Time, false, "09:30", &[]),
+            Ok(FieldDefault::Parsed("09:30:00".to_string()))
+        );
+        assert_eq!(
+            FieldDefault::parse(DefaultKind::Uuid, false, "new_uuid", &[]),
+            Ok(FieldDefault::NewUuid)
+        );
+        assert_eq!(
+            FieldDefault::parse(DefaultKind::Duration, false, "1500", &[]),
+            Ok(FieldDefault::Millis(1500))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_values_of_another_type() {
+        assert!(FieldDefault::parse(DefaultKind::UInteger, false, "-1", &[]).is_err());
+        assert!(FieldDefault::parse(DefaultKind::Boolean, false, "yes", &[]).is_err());
+        assert!(FieldDefault::parse(DefaultKind::Decimal, false, "1.2.3", &[]).is_err());
+        assert!(FieldDefault::parse(DefaultKind::Date, false, "now", &[]).is_err());
+        assert!(FieldDefault::parse(DefaultKind::Text, true, "a", &[]).is_err());
+        assert!(FieldDefault::parse(DefaultKind::None, false, "{}", &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_enum_variants() {
+        let values = vec!["Empty".to_string(), "Text(String)".to_string()];
+        assert_eq!(
+            FieldDefault::parse(DefaultKind::Enum, false, "Empty", &values),
+            Ok(FieldDefault::Variant("Empty".to_string()))
+        );
+        assert!(FieldDefault::parse(DefaultKind::Enum, false, "Text", &values).is_err());
+        assert!(FieldDefault::parse(DefaultKind::Enum, false, "Other", &values).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Project-local templates overlaid on top of the built-in ones.
+//!
+//! Every `.tera` file found (recursively) in the templates directory replaces the
+//! built-in template of the same name, the name being the file name without its
+//! extensions, like for the built-in templates.
+//!
+//! A template copied out with `qleany templates eject` starts with a header
+//! recording the hash of the built-in template it was copied from:
+//!
+//! ```text
+//! {#- qleany:ejected entity_dtos 3f2a9c0d1b7e4a65 -#}
+//! ```
+//!
+//! This allows reporting overrides whose built-in counterpart changed since they
+//! were ejected, i.e. overrides that may miss upstream fixes.
+
+use anyhow::{Result, bail};
+use std::path::{Path, PathBuf};
+
+/// Marker of the header written on top of ejected templates.
+pub const EJECTED_MARKER: &str = "qleany:ejected";
+
+/// A project-local template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateOverride {
+    pub name: String,
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// Why an override may be out of date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaleReason {
+    /// The built-in template changed since the override was ejected.
+    BuiltinChanged,
+    /// There is no built-in template of that name anymore.
+    BuiltinRemoved,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleOverride {
+    pub name: String,
+    pub path: PathBuf,
+    pub reason: StaleReason,
+}
+
+/// Template name of a template file: its file name without extensions.
+pub fn template_name(path: &Path) -> Option<String> {
+    if path.extension()? != "tera" {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    Some(
+        stem.rsplit_once('.')
+            .map_or(stem, |(stem, _)| stem)
+            .to_string(),
+    )
+}
+
+/// Resolve the `templates_path` of the manifest against the manifest folder.
+/// Returns `None` when no templates directory is configured.
+pub fn resolve_templates_dir(root_path: &Path, templates_path: &str) -> Option<PathBuf> {
+    let templates_path = templates_path.trim();
+    if templates_path.is_empty() {
+        return None;
+    }
+    Some(root_path.join(templates_path))
+}
+
+/// Load all the templates of `dir`, sorted by path.
+pub fn load_template_overrides(dir: &Path) -> Result<Vec<TemplateOverride>> {
+    if !dir.is_dir() {
+        bail!("Templates directory not found: {}", dir.display());
+    }
+
+    fn visit(dir: &Path, overrides: &mut Vec<TemplateOverride>) -> Result<()> {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<_>>()?;
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                visit(&path, overrides)?;
+            } else if let Some(name) = template_name(&path) {
+                let content = std::fs::read_to_string(&path)?;
+                overrides.push(TemplateOverride {
+                    name,
+                    path,
+                    content,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    let mut overrides = Vec::new();
+    visit(dir, &mut overrides)?;
+    Ok(overrides)
+}
+
+/// Stable hash of a template content (64-bit FNV-1a, hex encoded).
+pub fn content_hash(content: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in content.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Content of the file written when ejecting the built-in template `name`.
+pub fn ejected_content(name: &str, builtin: &str) -> String {
+    format!(
+        "{{#- {} {} {} -#}}\n{}",
+        EJECTED_MARKER,
+        name,
+        content_hash(builtin),
+        builtin
+    )
+}
+
+/// Read the header of an ejected template, returning the name and the hash of
+/// the built-in template it was copied from.
+pub fn parse_ejected_header(content: &str) -> Option<(String, String)> {
+    let first_line = content.lines().next()?;
+    let (_, rest) = first_line.split_once(EJECTED_MARKER)?;
+    let mut words = rest.split_whitespace();
+    let name = words.next()?.to_string();
+    let hash = words.next()?.to_string();
+    Some((name, hash))
+}
+
+/// Report the ejected overrides whose built-in counterpart changed or disappeared.
+/// Overrides written from scratch (without header) are not reported.
+pub fn find_stale_overrides<'a>(
+    overrides: &[TemplateOverride],
+    builtin: impl Fn(&str) -> Option<&'a str>,
+) -> Vec<StaleOverride> {
+    overrides
+        .iter()
+        .filter_map(|o| {
+            let (_, hash) = parse_ejected_header(&o.content)?;
+            let reason = match builtin(&o.name) {
+                None => StaleReason::BuiltinRemoved,
+                Some(content) if content_hash(content) != hash => StaleReason::BuiltinChanged,
+                Some(_) => return None,
+            };
+            Some(StaleOverride {
+                name: o.name.clone(),
+                path: o.path.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn override_of(name: &str, content: String) -> TemplateOverride {
+        TemplateOverride {
+            name: name.to_string(),
+            path: PathBuf::from(format!("{}.tera", name)),
+            content,
+        }
+    }
+
+    #[test]
+    fn test_template_name() {
+        assert_eq!(
+            template_name(Path::new("a/entity_dtos.tera")),
+            Some("entity_dtos".to_string())
+        );
+        assert_eq!(
+            template_name(Path::new("CMakeLists.txt.tera")),
+            Some("CMakeLists".to_string())
+        );
+        assert_eq!(template_name(Path::new("README.md")), None);
+    }
+
+    #[test]
+    fn test_ejected_header_round_trip() {
+        let content = ejected_content("entity_dtos", "// {{ s.file.inner.name }}\n");
+        let (name, hash) = parse_ejected_header(&content).unwrap();
+        assert_eq!(name, "entity_dtos");
+        assert_eq!(hash, content_hash("// {{ s.file.inner.name }}\n"));
+        assert!(content.ends_with("-#}\n// {{ s.file.inner.name }}\n"));
+    }
+
+    #[test]
+    fn test_find_stale_overrides() {
+        let overrides = vec![
+            override_of("same", ejected_content("same", "v1")),
+            override_of("changed", ejected_content("changed", "v1")),
+            override_of("removed", ejected_content("removed", "v1")),
+            override_of("handwritten", "no header".to_string()),
+        ];
+        let builtin = |name: &str| match name {
+            "same" => Some("v1"),
+            "changed" | "handwritten" => Some("v2"),
+            _ => None,
+        };
+        let stale = find_stale_overrides(&overrides, builtin);
+        let reasons: Vec<(&str, &StaleReason)> =
+            stale.iter().map(|s| (s.name.as_str(), &s.reason)).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("changed", &StaleReason::BuiltinChanged),
+                ("removed", &StaleReason::BuiltinRemoved),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_template_overrides() {
+        let dir = std::env::temp_dir().join(format!("qleany_overrides_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("common")).unwrap();
+        std::fs::write(dir.join("common/entity_dtos.tera"), "dtos").unwrap();
+        std::fs::write(dir.join("notes.md"), "ignored").unwrap();
+
+        let overrides = load_template_overrides(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].name, "entity_dtos");
+        assert_eq!(overrides[0].content, "dtos");
+    }
+}
@@ -0,0 +1,197 @@
+//! Protected user-code regions inside generated files.
+//!
+//! Templates delimit the parts of a file meant to be written by hand:
+//!
+//! ```text
+//! // qleany:begin user execute
+//! ... default body ...
+//! // qleany:end
+//! ```
+//!
+//! On regeneration, the body of each region found in the file on disk replaces
+//! the default body of the region with the same id in the newly rendered code.
+//! Regions of the file on disk whose id no longer exists in the rendered code are
+//! orphaned and must be reported, since their content can't be re-injected.
+
+/// Marker opening a user region, followed by the region id.
+pub const USER_REGION_BEGIN: &str = "qleany:begin user";
+/// Marker closing a user region.
+pub const USER_REGION_END: &str = "qleany:end";
+
+/// A user region extracted from a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserRegion {
+    pub id: String,
+    /// Lines between the markers, with their line endings.
+    pub body: String,
+}
+
+/// Result of re-injecting user regions into newly rendered code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InjectionOutcome {
+    pub code: String,
+    /// Ids of the regions whose anchor disappeared from the rendered code.
+    pub orphaned: Vec<String>,
+}
+
+fn begin_marker_id(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once(USER_REGION_BEGIN)?;
+    let id = rest.split_whitespace().next()?;
+    Some(id)
+}
+
+fn is_end_marker(line: &str) -> bool {
+    line.split_whitespace().any(|word| word == USER_REGION_END)
+}
+
+/// Extract the user regions of a file, in order of appearance.
+/// An unterminated region is ignored.
+pub fn extract_user_regions(content: &str) -> Vec<UserRegion> {
+    let mut regions = Vec::new();
+    let mut current: Option<UserRegion> = None;
+
+    for line in content.split_inclusive('\n') {
+        match current.as_mut() {
+            None => {
+                if let Some(id) = begin_marker_id(line) {
+                    current = Some(UserRegion {
+                        id: id.to_string(),
+                        body: String::new(),
+                    });
+                }
+            }
+            Some(region) => {
+                if is_end_marker(line) {
+                    regions.extend(current.take());
+                } else {
+                    region.body.push_str(line);
+                }
+            }
+        }
+    }
+
+    regions
+}
+
+/// Replace the body of each region of `generated` with the body of the region
+/// of the same id in `regions`. Regions of `generated` without a counterpart keep
+/// their default body.
+pub fn inject_user_regions(generated: &str, regions: &[UserRegion]) -> InjectionOutcome {
+    let mut code = String::with_capacity(generated.len());
+    let mut injected_ids: Vec<&str> = Vec::new();
+    // Some(true) while skipping a default body that was replaced
+    let mut inside: Option<bool> = None;
+
+    for line in generated.split_inclusive('\n') {
+        match inside {
+            None => {
+                code.push_str(line);
+                if let Some(id) = begin_marker_id(line) {
+                    match regions.iter().find(|r| r.id == id) {
+                        Some(region) => {
+                            code.push_str(&region.body);
+                            injected_ids.push(&region.id);
+                            inside = Some(true);
+                        }
+                        None => inside = Some(false),
+                    }
+                }
+            }
+            Some(replaced) => {
+                if is_end_marker(line) {
+                    code.push_str(line);
+                    inside = None;
+                } else if !replaced {
+                    code.push_str(line);
+                }
+            }
+        }
+    }
+
+    let orphaned = regions
+        .iter()
+        .filter(|r| !injected_ids.contains(&r.id.as_str()))
+        .map(|r| r.id.clone())
+        .collect();
+
+    InjectionOutcome { code, orphaned }
+}
+
+/// Re-inject into `generated` the user regions found in `on_disk`.
+pub fn preserve_user_regions(generated: &str, on_disk: &str) -> InjectionOutcome {
+    let regions = extract_user_regions(on_disk);
+    if regions.is_empty() {
+        return InjectionOutcome {
+            code: generated.to_string(),
+            orphaned: vec![],
+        };
+    }
+    inject_user_regions(generated, &regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GENERATED: &str = "\
+fn execute() {
+    // qleany:begin user execute
+    unimplemented!();
+    // qleany:end
+}
+";
+
+    #[test]
+    fn test_extract_user_regions() {
+        let regions = extract_user_regions(GENERATED);
+        assert_eq!(
+            regions,
+            vec![UserRegion {
+                id: "execute".to_string(),
+                body: "    unimplemented!();\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_inject_replaces_default_body() {
+        let on_disk = "\
+fn execute() {
+    // qleany:begin user execute
+    let a = 1;
+    do_something(a);
+    // qleany:end
+}
+";
+        let regenerated = format!("use foo;\n{}", GENERATED);
+        let outcome = preserve_user_regions(&regenerated, on_disk);
+        assert_eq!(outcome.code, format!("use foo;\n{}", on_disk));
+        assert!(outcome.orphaned.is_empty());
+    }
+
+    #[test]
+    fn test_inject_reports_orphaned_regions() {
+        let on_disk = "\
+// qleany:begin user undo
+restore();
+// qleany:end
+";
+        let outcome = preserve_user_regions(GENERATED, on_disk);
+        assert_eq!(outcome.code, GENERATED);
+        assert_eq!(outcome.orphaned, vec!["undo".to_string()]);
+    }
+
+    #[test]
+    fn test_hash_comment_markers() {
+        let generated = "# qleany:begin user sources\n# qleany:end\n";
+        let on_disk = "# qleany:begin user sources\nextra.cpp\n# qleany:end\n";
+        let outcome = preserve_user_regions(generated, on_disk);
+        assert_eq!(outcome.code, on_disk);
+    }
+
+    #[test]
+    fn test_unterminated_region_is_ignored() {
+        let on_disk = "// qleany:begin user execute\nlost();\n";
+        assert!(extract_user_regions(on_disk).is_empty());
+    }
+}
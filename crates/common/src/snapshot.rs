@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::any::Any;
+use std::sync::{Arc, Mutex};
 
 /// Snapshot of an entity tree, backed by a store-level snapshot for undo.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -9,6 +10,10 @@ pub struct EntityTreeSnapshot {
     /// Store-level fast path for undo.
     #[serde(skip)]
     pub store_snapshot: Option<StoreSnapshot>,
+    /// Change journal, for entities with `undo_journal`: only the rows written
+    /// after the snapshot are kept.
+    #[serde(skip)]
+    pub journal: Option<StoreJournal>,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -44,3 +49,21 @@ impl StoreSnapshot {
         self.inner.as_any().downcast_ref()
     }
 }
+
+/// Rows changed after a journaled snapshot, with their previous values. Filled in
+/// by the store when the write transaction commits; empty if it rolled back.
+#[derive(Debug, Clone, Default)]
+pub struct StoreJournal {
+    delta: Arc<Mutex<Option<StoreSnapshot>>>,
+}
+
+impl StoreJournal {
+    pub fn resolve(&self, delta: StoreSnapshot) {
+        *self.delta.lock().unwrap() = Some(delta);
+    }
+
+    /// The changed rows, `None` until the transaction commits.
+    pub fn delta(&self) -> Option<StoreSnapshot> {
+        self.delta.lock().unwrap().clone()
+    }
+}
@@ -1,8 +1,112 @@
 // Generated by Qleany v1.7.0 from types.tera
 
+use crate::error::ValidationError;
+use serde::{Deserialize, Serialize};
+
 pub type Savepoint = u64;
 pub type EntityId = u64;
 
 pub trait HasId {
     fn id(&self) -> EntityId;
 }
+
+/// Validation of the field constraints declared in the manifest. Implemented for
+/// every entity, entities without constraints keep the defaults.
+pub trait Validate {
+    /// Whether a field of the entity must be unique among the children of its owner.
+    const UNIQUE_IN_PARENT: bool = false;
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    /// Fails when `self` and `sibling` share the value of a unique_in_parent field.
+    fn check_unique_among(&self, _sibling: &Self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+/// Condition on a scalar field of an entity query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Condition<T> {
+    Eq(T),
+    Ne(T),
+    Lt(T),
+    Le(T),
+    Gt(T),
+    Ge(T),
+    In(Vec<T>),
+}
+
+impl<T: PartialOrd> Condition<T> {
+    pub fn matches(&self, value: &T) -> bool {
+        match self {
+            Condition::Eq(other) => value == other,
+            Condition::Ne(other) => value != other,
+            Condition::Lt(other) => value < other,
+            Condition::Le(other) => value <= other,
+            Condition::Gt(other) => value > other,
+            Condition::Ge(other) => value >= other,
+            Condition::In(values) => values.contains(value),
+        }
+    }
+}
+
+/// Condition on a text field of an entity query: the comparisons of [`Condition`],
+/// plus the substring and prefix matches that only make sense on text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TextCondition {
+    Eq(String),
+    Ne(String),
+    Lt(String),
+    Le(String),
+    Gt(String),
+    Ge(String),
+    In(Vec<String>),
+    Contains(String),
+    StartsWith(String),
+}
+
+impl TextCondition {
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            TextCondition::Eq(other) => value == other,
+            TextCondition::Ne(other) => value != other,
+            TextCondition::Lt(other) => value < other.as_str(),
+            TextCondition::Le(other) => value <= other.as_str(),
+            TextCondition::Gt(other) => value > other.as_str(),
+            TextCondition::Ge(other) => value >= other.as_str(),
+            TextCondition::In(values) => values.iter().any(|other| other == value),
+            TextCondition::Contains(part) => value.contains(part.as_str()),
+            TextCondition::StartsWith(prefix) => value.starts_with(prefix.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// Value of a field with a secondary index (`indexed` or `unique` in the manifest).
+pub trait IndexKey: std::hash::Hash + Eq + Clone {
+    /// An unset optional value never conflicts with another in a unique index.
+    fn is_unset(&self) -> bool {
+        false
+    }
+}
+
+impl IndexKey for bool {}
+impl IndexKey for i64 {}
+impl IndexKey for u64 {}
+impl IndexKey for String {}
+impl IndexKey for uuid::Uuid {}
+impl IndexKey for chrono::DateTime<chrono::Utc> {}
+
+impl<T: IndexKey> IndexKey for Option<T> {
+    fn is_unset(&self) -> bool {
+        self.is_none()
+    }
+}
@@ -0,0 +1,192 @@
+// Generated by Qleany v1.7.0 from journal_tests.tera
+#![cfg(test)]
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+// Change journal tests and benchmarks: the cost of a journaled undo follows
+// the number of rows changed, not the number of rows in the store.
+
+use anyhow::Result;
+use common::database::hashmap_store::HashMapStoreDelta;
+use common::database::{db_context::DbContext, transactions::Transaction};
+use common::entities::Root;
+use common::event::{DirectAccessEntity, EntityEvent};
+use common::snapshot::StoreJournal;
+use common::types::EntityId;
+use std::time::{Duration, Instant};
+
+/// Fill the `Root` table with `rows` rows, ids 1 to `rows`.
+fn fill(db_context: &DbContext, rows: usize) -> Result<()> {
+    let mut txn = Transaction::begin_write_transaction(db_context)?;
+    {
+        let mut table = txn.get_store().roots.write().unwrap();
+        for id in 1..=rows as EntityId {
+            table.insert(
+                id,
+                Root {
+                    id,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+    txn.commit()
+}
+
+/// Remove one row and add another under a journal, then undo them.
+/// Returns the journal, the events of the undo and the time spent.
+fn change_and_undo(db_context: &DbContext, rows: usize) -> Result<(StoreJournal, usize, Duration)> {
+    let start = Instant::now();
+
+    let mut txn = Transaction::begin_write_transaction(db_context)?;
+    let journal = txn.open_journal();
+    {
+        let mut table = txn.get_store().roots.write().unwrap();
+        table.remove(&1);
+        let id = rows as EntityId + 1;
+        table.insert(
+            id,
+            Root {
+                id,
+                ..Default::default()
+            },
+        );
+    }
+    txn.commit()?;
+
+    let mut txn = Transaction::begin_write_transaction(db_context)?;
+    let events = txn.undo_journal(&journal);
+    txn.commit()?;
+
+    Ok((journal, events.len(), start.elapsed()))
+}
+
+fn delta_len(journal: &StoreJournal) -> usize {
+    journal
+        .delta()
+        .and_then(|delta| delta.downcast_ref::<HashMapStoreDelta>().map(|d| d.len()))
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_journal_undo_restores_rows() -> Result<()> {
+    let db_context = DbContext::new()?;
+    fill(&db_context, 10)?;
+
+    let (journal, event_count, _) = change_and_undo(&db_context, 10)?;
+
+    assert_eq!(delta_len(&journal), 2);
+    // the removed row is back (Created), the added row is gone (Removed)
+    assert_eq!(event_count, 2);
+    let table = db_context.get_store().roots.read().unwrap();
+    assert!(table.contains_key(&1));
+    assert!(!table.contains_key(&11));
+    assert_eq!(table.len(), 10);
+    Ok(())
+}
+
+#[test]
+fn test_journal_undo_events() -> Result<()> {
+    let db_context = DbContext::new()?;
+    fill(&db_context, 3)?;
+
+    let mut txn = Transaction::begin_write_transaction(&db_context)?;
+    let journal = txn.open_journal();
+    txn.get_store().roots.write().unwrap().remove(&2);
+    txn.commit()?;
+
+    let mut txn = Transaction::begin_write_transaction(&db_context)?;
+    let events = txn.undo_journal(&journal);
+    txn.commit()?;
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0].origin,
+        common::event::Origin::DirectAccess(DirectAccessEntity::Root(EntityEvent::Created))
+    );
+    assert_eq!(events[0].ids, vec![2]);
+    Ok(())
+}
+
+#[test]
+fn test_journal_records_only_writes_after_it_opens() -> Result<()> {
+    let db_context = DbContext::new()?;
+    fill(&db_context, 3)?;
+
+    let mut txn = Transaction::begin_write_transaction(&db_context)?;
+    txn.get_store().roots.write().unwrap().remove(&1);
+    let journal = txn.open_journal();
+    txn.get_store().roots.write().unwrap().remove(&2);
+    txn.commit()?;
+
+    assert_eq!(delta_len(&journal), 1);
+    Ok(())
+}
+
+#[test]
+fn test_journal_is_empty_after_rollback() -> Result<()> {
+    let db_context = DbContext::new()?;
+    fill(&db_context, 3)?;
+
+    let mut txn = Transaction::begin_write_transaction(&db_context)?;
+    let journal = txn.open_journal();
+    txn.get_store().roots.write().unwrap().remove(&1);
+    txn.rollback()?;
+
+    assert!(journal.delta().is_none());
+    let mut txn = Transaction::begin_write_transaction(&db_context)?;
+    assert!(txn.undo_journal(&journal).is_empty());
+    txn.commit()?;
+    assert_eq!(db_context.get_store().roots.read().unwrap().len(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_journal_undo_keeps_later_unrelated_changes() -> Result<()> {
+    let db_context = DbContext::new()?;
+    fill(&db_context, 3)?;
+
+    let mut txn = Transaction::begin_write_transaction(&db_context)?;
+    let journal = txn.open_journal();
+    txn.get_store().roots.write().unwrap().remove(&1);
+    txn.commit()?;
+
+    // a later transaction, not journaled
+    let mut txn = Transaction::begin_write_transaction(&db_context)?;
+    txn.get_store().roots.write().unwrap().remove(&3);
+    txn.commit()?;
+
+    let mut txn = Transaction::begin_write_transaction(&db_context)?;
+    txn.undo_journal(&journal);
+    txn.commit()?;
+
+    let table = db_context.get_store().roots.read().unwrap();
+    assert!(table.contains_key(&1));
+    assert!(!table.contains_key(&3));
+    Ok(())
+}
+
+/// Benchmark: the same two-row change against a store 100 times larger.
+/// The delta and the undo events stay the same size; the timings are printed
+/// (`cargo test -- --nocapture`) rather than asserted.
+#[test]
+fn bench_journal_cost_follows_the_change_not_the_store() -> Result<()> {
+    let mut results = Vec::new();
+    for rows in [100, 10_000] {
+        let db_context = DbContext::new()?;
+        fill(&db_context, rows)?;
+        let (journal, event_count, elapsed) = change_and_undo(&db_context, rows)?;
+        println!(
+            "journaled change and undo, {} rows in the store: {} rows in the delta, {} events, {:?}",
+            rows,
+            delta_len(&journal),
+            event_count,
+            elapsed
+        );
+        results.push((delta_len(&journal), event_count));
+    }
+
+    assert_eq!(results[0], (2, 2));
+    assert_eq!(results[0], results[1]);
+    Ok(())
+}
@@ -6,7 +6,7 @@
 use anyhow::Result;
 use common::database::{db_context::DbContext, transactions::Transaction};
 use common::snapshot::EntityTreeSnapshot;
-use common::types::Savepoint;
+use common::types::{EntityId, Savepoint};
 
 #[test]
 fn test_savepoint_roundtrip() -> Result<()> {
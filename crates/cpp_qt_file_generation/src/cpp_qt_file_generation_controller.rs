@@ -1,6 +1,7 @@
 // Generated by Qleany. Edit at your own risk! Be careful when regenerating this file
 // as changes will be lost.
 
+use crate::FillCodeInCppQtFilesReturnDto;
 use crate::FillCppQtFilesDto;
 use crate::FillCppQtFilesReturnDto;
 use crate::GenerateCppQtCodeDto;
@@ -121,7 +122,7 @@ pub fn get_fill_code_in_cpp_qt_files_progress(
 pub fn get_fill_code_in_cpp_qt_files_result(
     long_operation_manager: &LongOperationManager,
     operation_id: &str,
-) -> Result<Option<()>> {
+) -> Result<Option<FillCodeInCppQtFilesReturnDto>> {
     // Get the operation result as a JSON string
     let result_json = long_operation_manager.get_operation_result(operation_id);
 
@@ -129,7 +130,10 @@ pub fn get_fill_code_in_cpp_qt_files_result(
     if result_json.is_none() {
         return Ok(None);
     }
-    Ok(Some(()))
+    // Parse the JSON string into a FillCodeInCppQtFilesReturnDto
+    let result_dto: FillCodeInCppQtFilesReturnDto = serde_json::from_str(&result_json.unwrap())?;
+
+    Ok(Some(result_dto))
 }
 
 pub fn generate_cpp_qt_prompt(
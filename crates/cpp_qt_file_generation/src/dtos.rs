@@ -44,3 +44,7 @@ pub struct GenerateCppQtPromptDto {
 pub struct GenerateCppQtPromptReturnDto {
     pub prompt_text: String,
 }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FillCodeInCppQtFilesReturnDto {
+    pub orphaned_user_regions: Vec<String>,
+}
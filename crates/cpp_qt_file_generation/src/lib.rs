@@ -4,3 +4,4 @@ mod units_of_work;
 pub(crate) mod use_cases;
 
 pub use dtos::*;
+pub use use_cases::common::cpp_qt_code_generator::{builtin_template, builtin_template_names};
@@ -108,30 +108,10 @@ impl CommandUnitOfWork for FillCodeInCppQtFilesUnitOfWork {
     }
 }
 
-#[macros::uow_action(entity = "System", action = "GetRelationship", thread_safe = true)]
-#[macros::uow_action(entity = "File", action = "GetMulti", thread_safe = true)]
-#[macros::uow_action(entity = "File", action = "UpdateMulti", thread_safe = true)]
-#[macros::uow_action(entity = "File", action = "RemoveMulti", thread_safe = true)]
+#[macros::uow_impl(thread_safe = true)]
 impl FillCodeInCppQtFilesUnitOfWorkTrait for FillCodeInCppQtFilesUnitOfWork {}
 
-#[macros::uow_action(entity = "Root", action = "GetRelationship", thread_safe = true)]
-#[macros::uow_action(entity = "Root", action = "GetAll", thread_safe = true)]
-#[macros::uow_action(entity = "System", action = "Get", thread_safe = true)]
-#[macros::uow_action(entity = "Workspace", action = "Get", thread_safe = true)]
-#[macros::uow_action(entity = "Workspace", action = "GetRelationship", thread_safe = true)]
-#[macros::uow_action(entity = "UserInterface", action = "Get", thread_safe = true)]
-#[macros::uow_action(entity = "File", action = "Get", thread_safe = true)]
-#[macros::uow_action(entity = "Global", action = "Get", thread_safe = true)]
-#[macros::uow_action(entity = "Feature", action = "Get", thread_safe = true)]
-#[macros::uow_action(entity = "Feature", action = "GetMulti", thread_safe = true)]
-#[macros::uow_action(entity = "UseCase", action = "Get", thread_safe = true)]
-#[macros::uow_action(entity = "UseCase", action = "GetMulti", thread_safe = true)]
-#[macros::uow_action(entity = "Dto", action = "Get", thread_safe = true)]
-#[macros::uow_action(entity = "DtoField", action = "GetMulti", thread_safe = true)]
-#[macros::uow_action(entity = "Entity", action = "Get", thread_safe = true)]
-#[macros::uow_action(entity = "Entity", action = "GetMulti", thread_safe = true)]
-#[macros::uow_action(entity = "Field", action = "GetMulti", thread_safe = true)]
-#[macros::uow_action(entity = "Relationship", action = "GetMulti", thread_safe = true)]
+#[macros::uow_impl(thread_safe = true)]
 impl GenerationOps for FillCodeInCppQtFilesUnitOfWork {}
 
 pub struct FillCodeInCppQtFilesUnitOfWorkFactory {
@@ -8,7 +8,7 @@ use anyhow::{Ok, Result};
 use common::database::CommandUnitOfWork;
 use common::database::{db_context::DbContext, transactions::Transaction};
 use common::entities::{
-    Entity, Feature, Field, File, Global, Relationship, Root, UseCase, UserInterface,
+    CustomFile, Entity, Feature, Field, File, Global, Relationship, Root, UseCase, UserInterface,
 };
 use common::event::{AllEvent, DirectAccessEntity, Event, EventBuffer, EventHub, Origin};
 use common::types;
@@ -94,35 +94,8 @@ impl CommandUnitOfWork for FillCppQtFilesUnitOfWork {
     }
 }
 
-//TODO: adapt entities and actions to real use : Create, CreateMulti, Get, GetMulti, Update, UpdateMulti, Delete,
-//DeleteMulti, GetRO, GetMultiRO, GetRelationship, GetRelationshipRO, GetRelationshipsFromRightIds,
-//GetRelationshipsFromRightIdsRO, SetRelationship, SetRelationshipMulti
-//
-// You have here a read-write unit of work.
-//
-// RO means Read Only, so *RO actions should not used be here.
-// Don't forget to set thread_safe = true for long operation's unit of work.
-// Do not mix read-only and write actions in the same unit of work.
-//
-// Exactly the same macros must be set in the use case uow trait file in ../use_cases/fill_cpp_qt_files_uc.rs
-//
-#[macros::uow_action(entity = "Root", action = "GetAll")]
-#[macros::uow_action(entity = "Root", action = "GetRelationship")]
-#[macros::uow_action(entity = "Workspace", action = "GetRelationship")]
-#[macros::uow_action(entity = "System", action = "GetRelationship")]
-#[macros::uow_action(entity = "System", action = "SetRelationship")]
-#[macros::uow_action(entity = "Global", action = "Get")]
-#[macros::uow_action(entity = "UserInterface", action = "Get")]
-#[macros::uow_action(entity = "Entity", action = "GetMulti")]
-#[macros::uow_action(entity = "Field", action = "GetMulti")]
-#[macros::uow_action(entity = "Entity", action = "GetRelationship")]
-#[macros::uow_action(entity = "Relationship", action = "GetMulti")]
-#[macros::uow_action(entity = "Feature", action = "GetMulti")]
-#[macros::uow_action(entity = "Feature", action = "GetRelationship")]
-#[macros::uow_action(entity = "UseCase", action = "GetMulti")]
-#[macros::uow_action(entity = "File", action = "CreateOrphan")]
-#[macros::uow_action(entity = "File", action = "CreateOrphanMulti")]
-#[macros::uow_action(entity = "File", action = "RemoveMulti")]
+// The actions are declared once, on FillCppQtFilesUnitOfWorkTrait in ../use_cases/fill_cpp_qt_files_uc.rs.
+#[macros::uow_impl]
 impl FillCppQtFilesUnitOfWorkTrait for FillCppQtFilesUnitOfWork {}
 
 pub struct FillCppQtFilesUnitOfWorkFactory {
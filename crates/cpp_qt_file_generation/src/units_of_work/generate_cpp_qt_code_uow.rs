@@ -51,46 +51,12 @@ impl QueryUnitOfWork for GenerateCppQtCodeUnitOfWork {
     }
 }
 
-#[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
-#[macros::uow_action(entity = "Root", action = "GetAllRO")]
-#[macros::uow_action(entity = "System", action = "GetRO")]
-#[macros::uow_action(entity = "Workspace", action = "GetRO")]
-#[macros::uow_action(entity = "Workspace", action = "GetRelationshipRO")]
-#[macros::uow_action(entity = "UserInterface", action = "GetRO")]
-#[macros::uow_action(entity = "File", action = "GetRO")]
-#[macros::uow_action(entity = "Global", action = "GetRO")]
-#[macros::uow_action(entity = "Feature", action = "GetRO")]
-#[macros::uow_action(entity = "Feature", action = "GetMultiRO")]
-#[macros::uow_action(entity = "UseCase", action = "GetRO")]
-#[macros::uow_action(entity = "UseCase", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Dto", action = "GetRO")]
-#[macros::uow_action(entity = "DtoField", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Entity", action = "GetRO")]
-#[macros::uow_action(entity = "Entity", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Field", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Relationship", action = "GetMultiRO")]
+#[macros::uow_impl]
 impl GenerationReadOps for GenerateCppQtCodeUnitOfWork {}
 
 impl GenerateCppQtCodeUnitOfWorkTrait for GenerateCppQtCodeUnitOfWork {}
 
-#[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
-#[macros::uow_action(entity = "Root", action = "GetAllRO")]
-#[macros::uow_action(entity = "System", action = "GetRO")]
-#[macros::uow_action(entity = "Workspace", action = "GetRO")]
-#[macros::uow_action(entity = "Workspace", action = "GetRelationshipRO")]
-#[macros::uow_action(entity = "UserInterface", action = "GetRO")]
-#[macros::uow_action(entity = "File", action = "GetRO")]
-#[macros::uow_action(entity = "Global", action = "GetRO")]
-#[macros::uow_action(entity = "Feature", action = "GetRO")]
-#[macros::uow_action(entity = "Feature", action = "GetMultiRO")]
-#[macros::uow_action(entity = "UseCase", action = "GetRO")]
-#[macros::uow_action(entity = "UseCase", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Dto", action = "GetRO")]
-#[macros::uow_action(entity = "DtoField", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Entity", action = "GetRO")]
-#[macros::uow_action(entity = "Entity", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Field", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Relationship", action = "GetMultiRO")]
+#[macros::uow_impl(for = GenerationReadOps)]
 impl GenerationOps for GenerateCppQtCodeUnitOfWork {}
 
 pub struct GenerateCppQtCodeUnitOfWorkFactory {
@@ -46,57 +46,13 @@ impl QueryUnitOfWork for GenerateCppQtPromptUnitOfWork {
     }
 }
 
-//TODO: adapt entities and actions to real use : Create, CreateMulti, Get, GetMulti, Update, UpdateMulti, Delete,
-//DeleteMulti, GetRO, GetMultiRO, GetRelationship, GetRelationshipRO, GetRelationshipsFromRightIds,
-//GetRelationshipsFromRightIdsRO, SetRelationship, SetRelationshipMulti
-//
-// You have here a read-only unit of work.
-//
-// RO means Read Only, so *RO actions should be used here.
-// Do not mix read-only and write actions in the same unit of work.
-//
-// Exactly the same macros must be set in the use case uow trait file in ../use_cases/generate_cpp_qt_prompt_uc.rs
-//
-#[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
-#[macros::uow_action(entity = "Root", action = "GetAllRO")]
-#[macros::uow_action(entity = "System", action = "GetRO")]
-#[macros::uow_action(entity = "Workspace", action = "GetRO")]
-#[macros::uow_action(entity = "Workspace", action = "GetRelationshipRO")]
-#[macros::uow_action(entity = "UserInterface", action = "GetRO")]
-#[macros::uow_action(entity = "File", action = "GetRO")]
-#[macros::uow_action(entity = "Global", action = "GetRO")]
-#[macros::uow_action(entity = "Feature", action = "GetRO")]
-#[macros::uow_action(entity = "Feature", action = "GetMultiRO")]
-#[macros::uow_action(entity = "UseCase", action = "GetRO")]
-#[macros::uow_action(entity = "UseCase", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Dto", action = "GetRO")]
-#[macros::uow_action(entity = "DtoField", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Entity", action = "GetRO")]
-#[macros::uow_action(entity = "Entity", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Field", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Relationship", action = "GetMultiRO")]
+// The actions are declared once, on GenerationReadOps in ../use_cases/common/cpp_qt_code_generator.rs.
+#[macros::uow_impl]
 impl GenerationReadOps for GenerateCppQtPromptUnitOfWork {}
 
 impl GenerateCppQtPromptUnitOfWorkTrait for GenerateCppQtPromptUnitOfWork {}
 
-#[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
-#[macros::uow_action(entity = "Root", action = "GetAllRO")]
-#[macros::uow_action(entity = "System", action = "GetRO")]
-#[macros::uow_action(entity = "Workspace", action = "GetRO")]
-#[macros::uow_action(entity = "Workspace", action = "GetRelationshipRO")]
-#[macros::uow_action(entity = "UserInterface", action = "GetRO")]
-#[macros::uow_action(entity = "File", action = "GetRO")]
-#[macros::uow_action(entity = "Global", action = "GetRO")]
-#[macros::uow_action(entity = "Feature", action = "GetRO")]
-#[macros::uow_action(entity = "Feature", action = "GetMultiRO")]
-#[macros::uow_action(entity = "UseCase", action = "GetRO")]
-#[macros::uow_action(entity = "UseCase", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Dto", action = "GetRO")]
-#[macros::uow_action(entity = "DtoField", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Entity", action = "GetRO")]
-#[macros::uow_action(entity = "Entity", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Field", action = "GetMultiRO")]
-#[macros::uow_action(entity = "Relationship", action = "GetMultiRO")]
+#[macros::uow_impl(for = GenerationReadOps)]
 impl GenerationOps for GenerateCppQtPromptUnitOfWork {}
 
 pub struct GenerateCppQtPromptUnitOfWorkFactory {
@@ -2,23 +2,31 @@ mod cpp_qt_code_generator_tests;
 mod gen_cmake_tests;
 
 use crate::use_cases::common::tools;
-use anyhow::Result;
 use anyhow::anyhow;
+use anyhow::{Context as _, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::entities::{
     Dto, DtoField, DtoFieldType, Entity, Feature, Field, FieldRelationshipType, FieldType, File,
     Global, Relationship, RelationshipType, Root, Strength, System, UseCase, UserInterface,
     Workspace,
 };
+use common::generator::field_constraints::FieldConstraints;
+use common::generator::field_defaults::FieldDefault;
+use common::generator::template_overrides;
 use common::types::EntityId;
 use include_dir::{Dir, include_dir};
 use indexmap::IndexMap;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::OnceLock;
 use tera::{Context, Tera};
 
 // Shared read-API for snapshot building across code and files generation
+// The read-only units of work implement GenerationOps with these actions too, through
+// #[macros::uow_impl(for = GenerationReadOps)]
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
 #[macros::uow_action(entity = "Root", action = "GetAllRO")]
 #[macros::uow_action(entity = "System", action = "GetRO")]
@@ -60,6 +68,7 @@ pub(crate) trait GenerationReadOps: QueryUnitOfWork + GenerationOps {}
 #[allow(dead_code)]
 pub(crate) trait GenerationWriteOps: CommandUnitOfWork + GenerationOps {}
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetRelationship")]
 #[macros::uow_action(entity = "Root", action = "GetAll")]
 #[macros::uow_action(entity = "System", action = "Get")]
@@ -141,6 +150,8 @@ struct EntityVM {
 struct FeatureVM {
     pub inner: Feature,
     pub use_cases: IndexMap<EntityId, UseCaseVM>,
+    /// Every DTO of the feature once, shared or inline, nested ones before the DTOs holding them
+    pub dtos: Vec<DtoVM>,
     pub snake_name: String,
     pub pascal_name: String,
     pub kebab_name: String,
@@ -151,6 +162,7 @@ struct FeatureVM {
 struct UseCaseVM {
     pub inner: UseCase,
     pub entities: IndexMap<EntityId, EntityVM>,
+    /// Inline or shared DTO, whichever the use case declares
     pub dto_in: Option<DtoVM>,
     pub dto_out: Option<DtoVM>,
     pub snake_name: String,
@@ -164,6 +176,8 @@ struct DtoVM {
     pub fields: Vec<DtoFieldVM>,
     pub pascal_name: String,
     pub camel_name: String,
+    /// Whether some field of the DTO, or of a DTO nested in it, has constraints
+    pub has_checks: bool,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -183,6 +197,7 @@ struct FieldVM {
     pub qml_type: String,
     pub qml_default_init: String,
     pub list_model_display_field_camel_name: Option<String>,
+    pub constraints: Option<FieldConstraints>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -198,6 +213,11 @@ struct DtoFieldVM {
     pub qml_base_type: String,
     pub qml_type: String,
     pub qml_default_init: String,
+    /// Name of the nested DTO (only populated when field_type == Dto)
+    pub dto_pascal_name: Option<String>,
+    /// Whether the nested DTO has constraints, to validate it with its holder
+    pub dto_has_checks: bool,
+    pub constraints: Option<FieldConstraints>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -247,8 +267,64 @@ fn load_templates_from_dir(tera: &mut Tera, dir: &Dir) {
     }
 }
 
-pub(crate) fn generate_code_with_snapshot(snapshot: &GenerationSnapshot) -> Result<String> {
-    let tera = get_cpp_qt_tera();
+/// Names of the built-in templates, sorted.
+pub fn builtin_template_names() -> Vec<String> {
+    let mut names: Vec<String> = RUST_TEMPLATES_DIR
+        .files()
+        .chain(RUST_TEMPLATES_DIR.dirs().flat_map(|dir| dir.files()))
+        .filter_map(|file| template_overrides::template_name(file.path()))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Content of the built-in template `name`.
+pub fn builtin_template(name: &str) -> Option<&'static str> {
+    RUST_TEMPLATES_DIR
+        .files()
+        .chain(RUST_TEMPLATES_DIR.dirs().flat_map(|dir| dir.files()))
+        .find(|file| template_overrides::template_name(file.path()).as_deref() == Some(name))
+        .and_then(|file| file.contents_utf8())
+}
+
+/// Templates to render with: the built-in ones, overlaid with the project-local
+/// templates of the manifest's `templates_path`, if any.
+pub(crate) fn get_tera_for_workspace(uow: &dyn GenerationOps) -> Result<Cow<'static, Tera>> {
+    let workspace = tools::get_workspace(uow)?;
+    let global = tools::get_global(uow)?;
+    let Some(templates_dir) = template_overrides::resolve_templates_dir(
+        Path::new(&workspace.manifest_absolute_path),
+        &global.templates_path,
+    ) else {
+        return Ok(Cow::Borrowed(get_cpp_qt_tera()));
+    };
+
+    let overrides = template_overrides::load_template_overrides(&templates_dir)?;
+    for stale in template_overrides::find_stale_overrides(&overrides, builtin_template) {
+        log::warn!(
+            "Template override {} ({}) is out of date: its built-in counterpart {}",
+            stale.name,
+            stale.path.display(),
+            match stale.reason {
+                template_overrides::StaleReason::BuiltinChanged => "changed upstream",
+                template_overrides::StaleReason::BuiltinRemoved => "no longer exists",
+            }
+        );
+    }
+
+    let mut tera = get_cpp_qt_tera().clone();
+    for template in &overrides {
+        tera.add_raw_template(&template.name, &template.content)
+            .with_context(|| format!("Invalid template {}", template.path.display()))?;
+    }
+    Ok(Cow::Owned(tera))
+}
+
+pub(crate) fn generate_code_with_snapshot(
+    tera: &Tera,
+    snapshot: &GenerationSnapshot,
+) -> Result<String> {
     let mut context = Context::new();
     context.insert("s", snapshot);
 
@@ -266,6 +342,44 @@ pub(crate) fn generate_code_with_snapshot(snapshot: &GenerationSnapshot) -> Resu
     Ok(code)
 }
 
+/// Member initializer of a field with a declared default, e.g. ` = TaskStatus::Todo`.
+fn cpp_default_init(default: &FieldDefault, cpp_qt_base_type: &str) -> String {
+    match default {
+        FieldDefault::Boolean(value) => format!(" = {}", value),
+        FieldDefault::Number(literal) => format!(" = {}", literal),
+        FieldDefault::Text(text) => format!(" = QString::fromUtf8({})", cpp_string_literal(text)),
+        FieldDefault::Parsed(text) => match cpp_qt_base_type {
+            "QUuid" => format!(" = QUuid::fromString(QLatin1StringView(\"{}\"))", text),
+            "QDateTime" | "QDate" | "QTime" => format!(
+                " = {}::fromString(QLatin1StringView(\"{}\"), Qt::ISODate)",
+                cpp_qt_base_type, text
+            ),
+            _ => format!(" = QString(QLatin1StringView(\"{}\"))", text),
+        },
+        FieldDefault::Variant(name) => format!(" = {}::{}", cpp_qt_base_type, name),
+        FieldDefault::Millis(millis) => format!(" = {}", millis),
+        FieldDefault::Now => " = QDateTime::currentDateTimeUtc()".to_string(),
+        FieldDefault::NewUuid => " = QUuid::createUuid()".to_string(),
+    }
+}
+
+/// C++ string literal holding `text`, UTF-8 encoded.
+fn cpp_string_literal(text: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
 // Snapshot builder to compose consistent data for templates
 pub(crate) struct SnapshotBuilder;
 
@@ -307,6 +421,12 @@ impl SnapshotBuilder {
                 FieldType::String => "QString".to_string(),
                 FieldType::Uuid => "QUuid".to_string(),
                 FieldType::DateTime => "QDateTime".to_string(),
+                FieldType::Decimal => "QString".to_string(),
+                FieldType::Date => "QDate".to_string(),
+                FieldType::Time => "QTime".to_string(),
+                FieldType::Duration => "qint64".to_string(),
+                FieldType::Bytes => "QByteArray".to_string(),
+                FieldType::Json => "QJsonValue".to_string(),
                 FieldType::Entity => "int".to_string(),
                 FieldType::Enum => f
                     .enum_name
@@ -337,7 +457,9 @@ impl SnapshotBuilder {
             } else {
                 cpp_qt_base_type.clone()
             };
-            let cpp_default_init = if is_list {
+            let cpp_default_init = if let Some(default) = FieldDefault::from_field(f) {
+                cpp_default_init(&default, &cpp_qt_base_type)
+            } else if is_list {
                 "{}".to_string()
             } else if f.optional {
                 " = std::nullopt".to_string()
@@ -349,7 +471,15 @@ impl SnapshotBuilder {
                     }
                     FieldType::Float => " = 0.0".to_string(),
                     FieldType::String => " = QString(QLatin1StringView(\"\"))".to_string(),
-                    FieldType::Uuid | FieldType::DateTime | FieldType::Enum => "{}".to_string(),
+                    FieldType::Decimal => " = QString(QLatin1StringView(\"0\"))".to_string(),
+                    FieldType::Duration => " = 0".to_string(),
+                    FieldType::Uuid
+                    | FieldType::DateTime
+                    | FieldType::Date
+                    | FieldType::Time
+                    | FieldType::Bytes
+                    | FieldType::Json
+                    | FieldType::Enum => "{}".to_string(),
                 }
             };
 
@@ -357,8 +487,10 @@ impl SnapshotBuilder {
                 FieldType::Boolean => "bool",
                 FieldType::Integer | FieldType::UInteger | FieldType::Entity => "int",
                 FieldType::Float => "real",
-                FieldType::String | FieldType::Uuid => "string",
-                FieldType::DateTime => "date",
+                FieldType::String | FieldType::Uuid | FieldType::Decimal => "string",
+                FieldType::DateTime | FieldType::Date => "date",
+                FieldType::Duration => "real",
+                FieldType::Time | FieldType::Bytes | FieldType::Json => "var",
                 FieldType::Enum => "string",
             }
             .to_string();
@@ -379,6 +511,12 @@ impl SnapshotBuilder {
                     FieldType::Integer | FieldType::UInteger | FieldType::Entity => "0",
                     FieldType::Float => "0.0",
                     FieldType::DateTime => "\"2026-01-01T00:00:00Z\"",
+                    FieldType::Date => "\"2026-01-01\"",
+                    FieldType::Time => "\"00:00:00\"",
+                    FieldType::Duration => "0",
+                    FieldType::Decimal => "\"0\"",
+                    FieldType::Bytes => "new ArrayBuffer(0)",
+                    FieldType::Json => "null",
                     FieldType::String | FieldType::Uuid | FieldType::Enum => "\"\"",
                 }
                 .to_string()
@@ -405,12 +543,12 @@ impl SnapshotBuilder {
                     .list_model_displayed_field
                     .as_ref()
                     .map(|field_name| heck::AsLowerCamelCase(field_name).to_string()),
+                constraints: FieldConstraints::from_field(f),
             });
         }
 
         // Build relationships maps.
         // We want to include both forward and backward relationships where this entity is involved.
-        use std::collections::HashSet;
         let mut relationships_map: IndexMap<EntityId, Relationship> = IndexMap::new();
 
         // Start with relationships explicitly listed on this entity
@@ -565,8 +703,16 @@ impl SnapshotBuilder {
         })
     }
 
-    fn get_dto_field_cpp_default_init(dto_field: &DtoField) -> String {
-        if dto_field.optional {
+    fn get_dto_field_cpp_default_init(
+        dto_field: &DtoField,
+        dtos: &HashMap<EntityId, Dto>,
+    ) -> String {
+        if let Some(default) = FieldDefault::from_dto_field(dto_field) {
+            cpp_default_init(
+                &default,
+                &Self::get_dto_field_cpp_qt_base_type(dto_field, dtos),
+            )
+        } else if dto_field.optional {
             " = std::nullopt".to_string()
         } else if dto_field.is_list {
             "{}".to_string()
@@ -576,15 +722,22 @@ impl SnapshotBuilder {
                 DtoFieldType::Integer | DtoFieldType::UInteger => " = 0".to_string(),
                 DtoFieldType::Float => " = 0.0".to_string(),
                 DtoFieldType::String => " = QString(QLatin1StringView(\"\"))".to_string(),
-                DtoFieldType::Uuid | DtoFieldType::DateTime | DtoFieldType::Enum => {
-                    "{}".to_string()
-                }
+                DtoFieldType::Decimal => " = QString(QLatin1StringView(\"0\"))".to_string(),
+                DtoFieldType::Duration => " = 0".to_string(),
+                DtoFieldType::Uuid
+                | DtoFieldType::DateTime
+                | DtoFieldType::Date
+                | DtoFieldType::Time
+                | DtoFieldType::Bytes
+                | DtoFieldType::Json
+                | DtoFieldType::Enum
+                | DtoFieldType::Dto => "{}".to_string(),
             }
         }
     }
 
-    fn get_dto_field_cpp_qt_type(dto_field: &DtoField) -> String {
-        let base_type = Self::get_dto_field_cpp_qt_base_type(dto_field);
+    fn get_dto_field_cpp_qt_type(dto_field: &DtoField, dtos: &HashMap<EntityId, Dto>) -> String {
+        let base_type = Self::get_dto_field_cpp_qt_base_type(dto_field, dtos);
         if dto_field.optional {
             format!("std::optional<{}>", base_type)
         } else if dto_field.is_list {
@@ -593,7 +746,10 @@ impl SnapshotBuilder {
             base_type
         }
     }
-    fn get_dto_field_cpp_qt_base_type(dto_field: &DtoField) -> String {
+    fn get_dto_field_cpp_qt_base_type(
+        dto_field: &DtoField,
+        dtos: &HashMap<EntityId, Dto>,
+    ) -> String {
         match dto_field.field_type {
             DtoFieldType::Boolean => "bool".to_string(),
             DtoFieldType::Integer => "int".to_string(),
@@ -602,10 +758,21 @@ impl SnapshotBuilder {
             DtoFieldType::String => "QString".to_string(),
             DtoFieldType::Uuid => "QUuid".to_string(),
             DtoFieldType::DateTime => "QDateTime".to_string(),
+            DtoFieldType::Decimal => "QString".to_string(),
+            DtoFieldType::Date => "QDate".to_string(),
+            DtoFieldType::Time => "QTime".to_string(),
+            DtoFieldType::Duration => "qint64".to_string(),
+            DtoFieldType::Bytes => "QByteArray".to_string(),
+            DtoFieldType::Json => "QJsonValue".to_string(),
             DtoFieldType::Enum => dto_field
                 .enum_name
                 .clone()
                 .unwrap_or("enum_name not set".to_string()),
+            DtoFieldType::Dto => dto_field
+                .dto
+                .and_then(|dto_id| dtos.get(&dto_id))
+                .map(|dto| heck::AsPascalCase(&dto.name).to_string())
+                .unwrap_or("dto not set".to_string()),
         }
     }
 
@@ -614,9 +781,12 @@ impl SnapshotBuilder {
             DtoFieldType::Boolean => "bool",
             DtoFieldType::Integer | DtoFieldType::UInteger => "int",
             DtoFieldType::Float => "real",
-            DtoFieldType::String | DtoFieldType::Uuid => "string",
-            DtoFieldType::DateTime => "date",
+            DtoFieldType::String | DtoFieldType::Uuid | DtoFieldType::Decimal => "string",
+            DtoFieldType::DateTime | DtoFieldType::Date => "date",
+            DtoFieldType::Duration => "real",
+            DtoFieldType::Time | DtoFieldType::Bytes | DtoFieldType::Json => "var",
             DtoFieldType::Enum => "string",
+            DtoFieldType::Dto => "var",
         }
         .to_string()
     }
@@ -640,12 +810,169 @@ impl SnapshotBuilder {
                 DtoFieldType::Integer | DtoFieldType::UInteger => "0",
                 DtoFieldType::Float => "0.0",
                 DtoFieldType::DateTime => "\"2026-01-01T00:00:00Z\"",
+                DtoFieldType::Date => "\"2026-01-01\"",
+                DtoFieldType::Time => "\"00:00:00\"",
+                DtoFieldType::Duration => "0",
+                DtoFieldType::Decimal => "\"0\"",
+                DtoFieldType::Bytes => "new ArrayBuffer(0)",
+                DtoFieldType::Json => "null",
                 DtoFieldType::String | DtoFieldType::Uuid | DtoFieldType::Enum => "\"\"",
+                DtoFieldType::Dto => "({})",
             }
             .to_string()
         }
     }
 
+    /// Whether a field of the DTO, or of the DTOs nested in it, has constraints.
+    fn dto_has_checks(
+        dto_id: EntityId,
+        dtos: &HashMap<EntityId, Dto>,
+        dto_fields: &HashMap<EntityId, DtoField>,
+        visited: &mut HashSet<EntityId>,
+    ) -> bool {
+        if !visited.insert(dto_id) {
+            return false;
+        }
+        let Some(dto) = dtos.get(&dto_id) else {
+            return false;
+        };
+        dto.fields
+            .iter()
+            .filter_map(|field_id| dto_fields.get(field_id))
+            .any(|df| {
+                FieldConstraints::from_dto_field(df).is_some()
+                    || df.dto.is_some_and(|nested_id| {
+                        Self::dto_has_checks(nested_id, dtos, dto_fields, visited)
+                    })
+            })
+    }
+
+    fn build_dto_field_vm(
+        df: &DtoField,
+        dtos: &HashMap<EntityId, Dto>,
+        dto_fields: &HashMap<EntityId, DtoField>,
+    ) -> DtoFieldVM {
+        DtoFieldVM {
+            inner: df.clone(),
+            pascal_name: heck::AsPascalCase(&df.name).to_string(),
+            camel_name: heck::AsLowerCamelCase(&df.name).to_string(),
+            snake_name: heck::AsSnakeCase(&df.name).to_string(),
+            cpp_qt_base_type: Self::get_dto_field_cpp_qt_base_type(df, dtos),
+            cpp_qt_type: Self::get_dto_field_cpp_qt_type(df, dtos),
+            cpp_default_init: Self::get_dto_field_cpp_default_init(df, dtos),
+            is_list: df.is_list,
+            qml_base_type: Self::get_dto_field_qml_base_type(df),
+            qml_type: Self::get_dto_field_qml_type(df),
+            qml_default_init: Self::get_dto_field_qml_default_init(df),
+            dto_pascal_name: df
+                .dto
+                .filter(|_| df.field_type == DtoFieldType::Dto)
+                .and_then(|dto_id| dtos.get(&dto_id))
+                .map(|dto| heck::AsPascalCase(&dto.name).to_string()),
+            dto_has_checks: df.dto.is_some_and(|dto_id| {
+                Self::dto_has_checks(dto_id, dtos, dto_fields, &mut HashSet::new())
+            }),
+            constraints: FieldConstraints::from_dto_field(df),
+        }
+    }
+
+    fn build_dto_vm(
+        dto: &Dto,
+        dtos: &HashMap<EntityId, Dto>,
+        dto_fields: &HashMap<EntityId, DtoField>,
+    ) -> DtoVM {
+        DtoVM {
+            inner: dto.clone(),
+            fields: dto
+                .fields
+                .iter()
+                .filter_map(|field_id| dto_fields.get(field_id))
+                .map(|df| Self::build_dto_field_vm(df, dtos, dto_fields))
+                .collect(),
+            pascal_name: heck::AsPascalCase(&dto.name).to_string(),
+            camel_name: heck::AsLowerCamelCase(&dto.name).to_string(),
+            has_checks: Self::dto_has_checks(dto.id, dtos, dto_fields, &mut HashSet::new()),
+        }
+    }
+
+    /// Load a DTO with its fields and the DTOs nested in them.
+    fn load_dto(
+        uow: &dyn GenerationOps,
+        dto_id: &EntityId,
+        dtos: &mut HashMap<EntityId, Dto>,
+        dto_fields: &mut HashMap<EntityId, DtoField>,
+    ) -> Result<()> {
+        if dtos.contains_key(dto_id) {
+            return Ok(());
+        }
+        let dto = uow
+            .get_dto(dto_id)?
+            .ok_or_else(|| anyhow!("DTO missing for use case"))?;
+        let fields_vec: Vec<DtoField> = uow
+            .get_dto_field_multi(&dto.fields)?
+            .into_iter()
+            .flatten()
+            .collect();
+        dtos.insert(dto.id, dto);
+        for f in fields_vec {
+            if let Some(nested_id) = f.dto {
+                Self::load_dto(uow, &nested_id, dtos, dto_fields)?;
+            }
+            dto_fields.insert(f.id, f);
+        }
+        Ok(())
+    }
+
+    /// The DTOs of a feature: the shared ones, then the inline ones of its use
+    /// cases. A struct is declared after the ones it holds.
+    fn feature_dto_vms(
+        feature: &Feature,
+        use_cases: &[UseCase],
+        dtos: &HashMap<EntityId, Dto>,
+        dto_fields: &HashMap<EntityId, DtoField>,
+    ) -> Vec<DtoVM> {
+        // post-order: nested DTOs come before their holder
+        fn visit(
+            dto_id: EntityId,
+            dtos: &HashMap<EntityId, Dto>,
+            dto_fields: &HashMap<EntityId, DtoField>,
+            visited: &mut HashSet<EntityId>,
+            ordered: &mut Vec<EntityId>,
+        ) {
+            if !visited.insert(dto_id) {
+                return;
+            }
+            let Some(dto) = dtos.get(&dto_id) else {
+                return;
+            };
+            for df in dto.fields.iter().filter_map(|id| dto_fields.get(id)) {
+                if let Some(nested_id) = df.dto {
+                    visit(nested_id, dtos, dto_fields, visited, ordered);
+                }
+            }
+            ordered.push(dto_id);
+        }
+
+        let mut visited: HashSet<EntityId> = HashSet::new();
+        let mut ordered: Vec<EntityId> = vec![];
+        for dto_id in &feature.dtos {
+            visit(*dto_id, dtos, dto_fields, &mut visited, &mut ordered);
+        }
+        for uc in use_cases {
+            for dto_id in [uc.dto_in, uc.dto_out, uc.shared_dto_in, uc.shared_dto_out]
+                .into_iter()
+                .flatten()
+            {
+                visit(dto_id, dtos, dto_fields, &mut visited, &mut ordered);
+            }
+        }
+        ordered
+            .iter()
+            .filter_map(|dto_id| dtos.get(dto_id))
+            .map(|dto| Self::build_dto_vm(dto, dtos, dto_fields))
+            .collect()
+    }
+
     fn get_entity_owner(uow: &dyn GenerationOps, entity_id: &EntityId) -> Option<EntityId> {
         let entity: Option<common::entities::Entity> = uow.get_entity(entity_id).ok().flatten();
         if let Some(entity) = entity {
@@ -840,6 +1167,9 @@ impl SnapshotBuilder {
                     .flatten()
                     .collect();
 
+                for dto_id in &feat_opt.dtos {
+                    Self::load_dto(uow, dto_id, &mut dtos, &mut dto_fields)?;
+                }
                 features.insert(feat_opt.id, feat_opt);
 
                 for use_case in feature_use_cases {
@@ -853,34 +1183,17 @@ impl SnapshotBuilder {
                         entities.insert(e.id, e);
                     }
 
-                    // DTOs
-                    if let Some(dto_in) = use_case.dto_in {
-                        let dto = uow
-                            .get_dto(&dto_in)?
-                            .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                        let fields_vec: Vec<DtoField> = uow
-                            .get_dto_field_multi(&dto.fields)?
-                            .into_iter()
-                            .flatten()
-                            .collect();
-                        for f in fields_vec {
-                            dto_fields.insert(f.id, f);
-                        }
-                        dtos.insert(dto.id, dto);
-                    }
-                    if let Some(dto_out) = use_case.dto_out {
-                        let dto = uow
-                            .get_dto(&dto_out)?
-                            .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                        let fields_vec: Vec<DtoField> = uow
-                            .get_dto_field_multi(&dto.fields)?
-                            .into_iter()
-                            .flatten()
-                            .collect();
-                        for f in fields_vec {
-                            dto_fields.insert(f.id, f);
-                        }
-                        dtos.insert(dto.id, dto);
+                    // DTOs, inline or shared, with the DTOs nested in them
+                    for dto_id in [
+                        use_case.dto_in,
+                        use_case.dto_out,
+                        use_case.shared_dto_in,
+                        use_case.shared_dto_out,
+                    ]
+                    .into_iter()
+                    .flatten()
+                    {
+                        Self::load_dto(uow, &dto_id, &mut dtos, &mut dto_fields)?;
                     }
 
                     use_cases.insert(use_case.id, use_case);
@@ -899,6 +1212,9 @@ impl SnapshotBuilder {
                 .flatten()
                 .collect();
 
+            for dto_id in &feature.dtos {
+                Self::load_dto(uow, dto_id, &mut dtos, &mut dto_fields)?;
+            }
             features.insert(feature.id, feature);
 
             for use_case in feature_use_cases {
@@ -912,34 +1228,17 @@ impl SnapshotBuilder {
                     entities.insert(e.id, e);
                 }
 
-                // DTOs
-                if let Some(dto_in) = use_case.dto_in {
-                    let dto = uow
-                        .get_dto(&dto_in)?
-                        .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                    let fields_vec: Vec<DtoField> = uow
-                        .get_dto_field_multi(&dto.fields)?
-                        .into_iter()
-                        .flatten()
-                        .collect();
-                    for f in fields_vec {
-                        dto_fields.insert(f.id, f);
-                    }
-                    dtos.insert(dto.id, dto);
-                }
-                if let Some(dto_out) = use_case.dto_out {
-                    let dto = uow
-                        .get_dto(&dto_out)?
-                        .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                    let fields_vec: Vec<DtoField> = uow
-                        .get_dto_field_multi(&dto.fields)?
-                        .into_iter()
-                        .flatten()
-                        .collect();
-                    for f in fields_vec {
-                        dto_fields.insert(f.id, f);
-                    }
-                    dtos.insert(dto.id, dto);
+                // DTOs, inline or shared, with the DTOs nested in them
+                for dto_id in [
+                    use_case.dto_in,
+                    use_case.dto_out,
+                    use_case.shared_dto_in,
+                    use_case.shared_dto_out,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    Self::load_dto(uow, &dto_id, &mut dtos, &mut dto_fields)?;
                 }
 
                 use_cases.insert(use_case.id, use_case);
@@ -960,33 +1259,17 @@ impl SnapshotBuilder {
                 entities.insert(e.id, e);
             }
 
-            if let Some(dto_in) = use_case.dto_in {
-                let dto = uow
-                    .get_dto(&dto_in)?
-                    .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                let fields_vec: Vec<DtoField> = uow
-                    .get_dto_field_multi(&dto.fields)?
-                    .into_iter()
-                    .flatten()
-                    .collect();
-                for f in fields_vec {
-                    dto_fields.insert(f.id, f);
-                }
-                dtos.insert(dto.id, dto);
-            }
-            if let Some(dto_out) = use_case.dto_out {
-                let dto = uow
-                    .get_dto(&dto_out)?
-                    .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                let fields_vec: Vec<DtoField> = uow
-                    .get_dto_field_multi(&dto.fields)?
-                    .into_iter()
-                    .flatten()
-                    .collect();
-                for f in fields_vec {
-                    dto_fields.insert(f.id, f);
-                }
-                dtos.insert(dto.id, dto);
+            // DTOs, inline or shared, with the DTOs nested in them
+            for dto_id in [
+                use_case.dto_in,
+                use_case.dto_out,
+                use_case.shared_dto_in,
+                use_case.shared_dto_out,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                Self::load_dto(uow, &dto_id, &mut dtos, &mut dto_fields)?;
             }
             use_cases.insert(use_case.id, use_case);
         }
@@ -1101,33 +1384,9 @@ impl SnapshotBuilder {
         let mut dto_keys: Vec<EntityId> = dtos.keys().copied().collect();
         dto_keys.sort();
         for did in &dto_keys {
-            let d = &dtos[did];
-            let mut df_vec: Vec<DtoFieldVM> = Vec::new();
-            for dfid in &d.fields {
-                if let Some(df) = dto_fields.get(dfid) {
-                    df_vec.push(DtoFieldVM {
-                        inner: df.clone(),
-                        pascal_name: heck::AsPascalCase(&df.name).to_string(),
-                        camel_name: heck::AsLowerCamelCase(&df.name).to_string(),
-                        snake_name: heck::AsSnakeCase(&df.name).to_string(),
-                        cpp_qt_base_type: SnapshotBuilder::get_dto_field_cpp_qt_base_type(df),
-                        cpp_qt_type: SnapshotBuilder::get_dto_field_cpp_qt_type(df),
-                        cpp_default_init: SnapshotBuilder::get_dto_field_cpp_default_init(df),
-                        is_list: df.is_list,
-                        qml_base_type: SnapshotBuilder::get_dto_field_qml_base_type(df),
-                        qml_type: SnapshotBuilder::get_dto_field_qml_type(df),
-                        qml_default_init: SnapshotBuilder::get_dto_field_qml_default_init(df),
-                    });
-                }
-            }
             dtos_vm.insert(
                 *did,
-                DtoVM {
-                    inner: d.clone(),
-                    fields: df_vec,
-                    pascal_name: heck::AsPascalCase(&d.name).to_string(),
-                    camel_name: heck::AsLowerCamelCase(&d.name).to_string(),
-                },
+                SnapshotBuilder::build_dto_vm(&dtos[did], &dtos, &dto_fields),
             );
         }
 
@@ -1140,6 +1399,16 @@ impl SnapshotBuilder {
                     k,
                     FeatureVM {
                         inner: v.clone(),
+                        dtos: SnapshotBuilder::feature_dto_vms(
+                            &v,
+                            &uow.get_use_case_multi(&v.use_cases)
+                                .unwrap_or_default()
+                                .into_iter()
+                                .flatten()
+                                .collect::<Vec<UseCase>>(),
+                            &dtos,
+                            &dto_fields,
+                        ),
                         use_cases: {
                             let use_cases_ids = v.use_cases;
                             let use_cases: IndexMap<EntityId, UseCase> = uow
@@ -1201,82 +1470,16 @@ impl SnapshotBuilder {
                                                     entity_vms
                                                 }
                                             },
-                                            dto_in: uc.dto_in.and_then(|dto_id| {
-                                                dtos.get(&dto_id).map(|d| DtoVM {
-                                                    inner: d.clone(),
-                                                    fields: {
-                                                        let mut df_vec: Vec<DtoFieldVM> =
-                                                            Vec::new();
-                                                        for dfid in &d.fields {
-                                                            if let Some(df) = dto_fields.get(dfid) {
-                                                                df_vec.push(DtoFieldVM {
-                                                                    inner: df.clone(),
-                                                                    pascal_name:
-                                                                        heck::AsPascalCase(&df.name)
-                                                                            .to_string(),
-                                                                    camel_name:
-                                                                        heck::AsLowerCamelCase(&df.name)
-                                                                            .to_string(),
-                                                                    snake_name: heck::AsSnakeCase(
-                                                                        &df.name,
-                                                                    )
-                                                                    .to_string(),
-                                                                    cpp_qt_base_type: SnapshotBuilder::get_dto_field_cpp_qt_base_type(df),
-                                                                    cpp_qt_type: SnapshotBuilder::get_dto_field_cpp_qt_type(df),
-                                                                    cpp_default_init: SnapshotBuilder::get_dto_field_cpp_default_init(df),
-                                                                    is_list: df.is_list,
-                                                                    qml_base_type: SnapshotBuilder::get_dto_field_qml_base_type(df),
-                                                                    qml_type: SnapshotBuilder::get_dto_field_qml_type(df),
-                                                                    qml_default_init: SnapshotBuilder::get_dto_field_qml_default_init(df),
-                                                                });
-                                                            }
-                                                        }
-                                                        df_vec
-                                                    },
-                                                    pascal_name: heck::AsPascalCase(&d.name)
-                                                        .to_string(),
-                                                    camel_name: heck::AsLowerCamelCase(&d.name)
-                                                        .to_string(),
-                                                })
-                                            }),
-                                            dto_out: uc.dto_out.and_then(|dto_id| {
-                                                dtos.get(&dto_id).map(|d| DtoVM {
-                                                    inner: d.clone(),
-                                                    fields: {
-                                                        let mut df_vec: Vec<DtoFieldVM> =
-                                                            Vec::new();
-                                                        for dfid in &d.fields {
-                                                            if let Some(df) = dto_fields.get(dfid) {
-                                                                df_vec.push(DtoFieldVM {
-                                                                    inner: df.clone(),
-                                                                    pascal_name:
-                                                                        heck::AsPascalCase(&df.name)
-                                                                            .to_string(),
-                                                                    camel_name:
-                                                                        heck::AsLowerCamelCase(&df.name)
-                                                                            .to_string(),
-                                                                    snake_name: heck::AsSnakeCase(
-                                                                        &df.name,
-                                                                    )
-                                                                    .to_string(),
-                                                                    cpp_qt_base_type: SnapshotBuilder::get_dto_field_cpp_qt_base_type(df),
-                                                                    cpp_qt_type: SnapshotBuilder::get_dto_field_cpp_qt_type(df),
-                                                                    cpp_default_init: SnapshotBuilder::get_dto_field_cpp_default_init(df),
-                                                                    is_list: df.is_list,
-                                                                    qml_base_type: SnapshotBuilder::get_dto_field_qml_base_type(df),
-                                                                    qml_type: SnapshotBuilder::get_dto_field_qml_type(df),
-                                                                    qml_default_init: SnapshotBuilder::get_dto_field_qml_default_init(df),
-                                                                });
-                                                            }
-                                                        }
-                                                        df_vec
-                                                    },
-                                                    pascal_name: heck::AsPascalCase(&d.name)
-                                                        .to_string(),
-                                                    camel_name: heck::AsLowerCamelCase(&d.name)
-                                                        .to_string(),
-                                                })
-                                            }),
+                                            dto_in: uc
+.dto_in
+.or(uc.shared_dto_in)
+.and_then(|dto_id| dtos.get(&dto_id))
+.map(|d| SnapshotBuilder::build_dto_vm(d, &dtos, &dto_fields)),
+                                            dto_out: uc
+.dto_out
+.or(uc.shared_dto_out)
+.and_then(|dto_id| dtos.get(&dto_id))
+.map(|d| SnapshotBuilder::build_dto_vm(d, &dtos, &dto_fields)),
                                             pascal_name: heck::AsPascalCase(&uc.name).to_string(),
                                             snake_name: heck::AsSnakeCase(&uc.name).to_string(),
                                             camel_name: heck::AsLowerCamelCase(&uc.name).to_string(),
@@ -1340,90 +1543,16 @@ impl SnapshotBuilder {
                                     .collect()
                             }
                         },
-                        dto_in: uc.dto_in.and_then(|dto_id| {
-                            dtos.get(&dto_id).map(|d| DtoVM {
-                                inner: d.clone(),
-                                fields: {
-                                    let mut df_vec: Vec<DtoFieldVM> = Vec::new();
-                                    for dfid in &d.fields {
-                                        if let Some(df) = dto_fields.get(dfid) {
-                                            df_vec.push(DtoFieldVM {
-                                                inner: df.clone(),
-                                                pascal_name: heck::AsPascalCase(&df.name)
-                                                    .to_string(),
-                                                camel_name: heck::AsLowerCamelCase(&df.name)
-                                                    .to_string(),
-                                                snake_name: heck::AsSnakeCase(&df.name).to_string(),
-                                                cpp_qt_base_type:
-                                                    SnapshotBuilder::get_dto_field_cpp_qt_base_type(
-                                                        df,
-                                                    ),
-                                                cpp_qt_type:
-                                                    SnapshotBuilder::get_dto_field_cpp_qt_type(df),
-                                                cpp_default_init:
-                                                    SnapshotBuilder::get_dto_field_cpp_default_init(
-                                                        df,
-                                                    ),
-                                                is_list: df.is_list,
-                                                qml_base_type:
-                                                    SnapshotBuilder::get_dto_field_qml_base_type(df),
-                                                qml_type:
-                                                    SnapshotBuilder::get_dto_field_qml_type(df),
-                                                qml_default_init:
-                                                    SnapshotBuilder::get_dto_field_qml_default_init(
-                                                        df,
-                                                    ),
-                                            });
-                                        }
-                                    }
-                                    df_vec
-                                },
-                                pascal_name: heck::AsPascalCase(&d.name).to_string(),
-                                camel_name: heck::AsLowerCamelCase(&d.name).to_string(),
-                            })
-                        }),
-                        dto_out: uc.dto_out.and_then(|dto_id| {
-                            dtos.get(&dto_id).map(|d| DtoVM {
-                                inner: d.clone(),
-                                fields: {
-                                    let mut df_vec: Vec<DtoFieldVM> = Vec::new();
-                                    for dfid in &d.fields {
-                                        if let Some(df) = dto_fields.get(dfid) {
-                                            df_vec.push(DtoFieldVM {
-                                                inner: df.clone(),
-                                                pascal_name: heck::AsPascalCase(&df.name)
-                                                    .to_string(),
-                                                camel_name: heck::AsLowerCamelCase(&df.name)
-                                                    .to_string(),
-                                                snake_name: heck::AsSnakeCase(&df.name).to_string(),
-                                                cpp_qt_base_type:
-                                                    SnapshotBuilder::get_dto_field_cpp_qt_base_type(
-                                                        df,
-                                                    ),
-                                                cpp_qt_type:
-                                                    SnapshotBuilder::get_dto_field_cpp_qt_type(df),
-                                                cpp_default_init:
-                                                    SnapshotBuilder::get_dto_field_cpp_default_init(
-                                                        df,
-                                                    ),
-                                                is_list: df.is_list,
-                                                qml_base_type:
-                                                    SnapshotBuilder::get_dto_field_qml_base_type(df),
-                                                qml_type:
-                                                    SnapshotBuilder::get_dto_field_qml_type(df),
-                                                qml_default_init:
-                                                    SnapshotBuilder::get_dto_field_qml_default_init(
-                                                        df,
-                                                    ),
-                                            });
-                                        }
-                                    }
-                                    df_vec
-                                },
-                                pascal_name: heck::AsPascalCase(&d.name).to_string(),
-                                camel_name: heck::AsLowerCamelCase(&d.name).to_string(),
-                            })
-                        }),
+                        dto_in: uc
+                            .dto_in
+                            .or(uc.shared_dto_in)
+                            .and_then(|dto_id| dtos.get(&dto_id))
+                            .map(|d| SnapshotBuilder::build_dto_vm(d, &dtos, &dto_fields)),
+                        dto_out: uc
+                            .dto_out
+                            .or(uc.shared_dto_out)
+                            .and_then(|dto_id| dtos.get(&dto_id))
+                            .map(|d| SnapshotBuilder::build_dto_vm(d, &dtos, &dto_fields)),
                         pascal_name: heck::AsPascalCase(&uc.name).to_string(),
                         snake_name: heck::AsSnakeCase(&uc.name).to_string(),
                         camel_name: heck::AsLowerCamelCase(&uc.name).to_string(),
@@ -1492,6 +1621,8 @@ mod tests {
                     organisation_name: "test".into(),
                     organisation_domain: "test".into(),
                     prefix_path: "".into(),
+                    storage: "memory".into(),
+                    templates_path: String::new(),
                 },
                 application_kebab_name: "test".into(),
                 application_pascal_name: "Test".into(),
@@ -1561,6 +1692,8 @@ mod tests {
             organisation_name: "".to_string(),
             organisation_domain: "".to_string(),
             prefix_path: "".to_string(),
+            storage: "memory".to_string(),
+            templates_path: String::new(),
         };
         let entity = Entity {
             id: entity_id,
@@ -1574,6 +1707,8 @@ mod tests {
             fields: vec![100, 101],
             relationships: vec![],
             undoable: true,
+            undo_journal: false,
+            source_file: String::new(),
         };
         let field_relationship = Field {
             id: 100,
@@ -1590,6 +1725,7 @@ mod tests {
             list_model_displayed_field: None,
             enum_name: None,
             enum_values: vec![],
+            ..Default::default()
         };
         let field_tags = Field {
             id: 101,
@@ -1606,6 +1742,7 @@ mod tests {
             list_model_displayed_field: None,
             enum_name: None,
             enum_values: vec![],
+            ..Default::default()
         };
 
         let snapshot = GenerationSnapshot {
@@ -1658,6 +1795,7 @@ mod tests {
                         qml_type: "var".to_string(),
                         qml_default_init: "null".to_string(),
                         list_model_display_field_camel_name: None,
+                        constraints: None,
                     },
                     FieldVM {
                         inner: field_tags.clone(),
@@ -1675,6 +1813,7 @@ mod tests {
                         qml_type: "var".to_string(),
                         qml_default_init: "[]".to_string(),
                         list_model_display_field_camel_name: None,
+                        constraints: None,
                     },
                 ];
                 m.insert(
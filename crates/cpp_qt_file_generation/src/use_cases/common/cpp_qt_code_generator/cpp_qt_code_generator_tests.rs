@@ -119,6 +119,11 @@ impl GenerationOps for DummyGenerationReadOps {
                 .get(id)
                 .map(|w| vec![w.user_interface])
                 .unwrap_or_default()),
+            WorkspaceRelationshipField::CustomFiles => Ok(self
+                .workspaces
+                .get(id)
+                .map(|w| w.custom_files.clone())
+                .unwrap_or_default()),
         }
     }
 
@@ -283,6 +288,8 @@ fn for_file_feature_without_use_cases_errors() {
         updated_at: chrono::Utc::now(),
         name: "Feat".into(),
         use_cases: vec![],
+        dtos: vec![],
+        source_file: String::new(),
     };
     uow.features.insert(10, feature);
     let global = Global {
@@ -294,6 +301,8 @@ fn for_file_feature_without_use_cases_errors() {
         organisation_name: "Org".into(),
         organisation_domain: "org.com".into(),
         prefix_path: "".into(),
+        storage: "memory".into(),
+        templates_path: String::new(),
     };
     uow.globals.insert(3, global);
     let user_interface = UserInterface {
@@ -317,6 +326,11 @@ fn for_file_feature_without_use_cases_errors() {
         entities: vec![],
         features: vec![10],
         user_interface: 1,
+        custom_files: vec![],
+        includes: vec![],
+        manifest_text: String::new(),
+        included_files: vec![],
+        included_texts: vec![],
     };
     uow.workspaces.insert(2, workspace);
     uow.workspace_features.insert(2, vec![10]);
@@ -368,6 +382,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         long_operation: false,
         dto_in: Some(200),
         dto_out: Some(201),
+        shared_dto_in: None,
+        shared_dto_out: None,
     };
     let feature = Feature {
         id: 10,
@@ -375,6 +391,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         updated_at: chrono::Utc::now(),
         name: "Feat".into(),
         use_cases: vec![100],
+        dtos: vec![],
+        source_file: String::new(),
     };
     uow.features.insert(10, feature);
     uow.use_cases.insert(100, uc.clone());
@@ -391,6 +409,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         fields: vec![400],
         relationships: vec![],
         undoable: false,
+        undo_journal: false,
+        source_file: String::new(),
     };
     let field = Field {
         id: 400,
@@ -407,6 +427,7 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         list_model_displayed_field: None,
         enum_name: None,
         enum_values: vec![],
+        ..Default::default()
     };
     uow.entities.insert(300, ent);
     uow.fields.insert(400, field);
@@ -437,6 +458,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         is_list: false,
         enum_name: None,
         enum_values: vec![],
+        dto: None,
+        ..Default::default()
     };
     let df_out = DtoField {
         id: 501,
@@ -448,6 +471,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         is_list: false,
         enum_name: None,
         enum_values: vec![],
+        dto: None,
+        ..Default::default()
     };
     uow.dto_fields.insert(500, df_in);
     uow.dto_fields.insert(501, df_out);
@@ -460,6 +485,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         organisation_name: "Org".into(),
         organisation_domain: "org.com".into(),
         prefix_path: "".into(),
+        storage: "memory".into(),
+        templates_path: String::new(),
     };
     uow.globals.insert(3, global);
     let user_interface = UserInterface {
@@ -483,6 +510,11 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         entities: vec![],
         features: vec![10],
         user_interface: 1,
+        custom_files: vec![],
+        includes: vec![],
+        manifest_text: String::new(),
+        included_files: vec![],
+        included_texts: vec![],
     };
     uow.workspaces.insert(2, workspace);
     uow.workspace_features.insert(2, vec![10]);
@@ -528,6 +560,8 @@ fn for_file_various_combinations_generate_expected_items() {
         fields: vec![],
         relationships: vec![],
         undoable: false,
+        undo_journal: false,
+        source_file: String::new(),
     };
     let ent_b = Entity {
         id: 2,
@@ -541,6 +575,8 @@ fn for_file_various_combinations_generate_expected_items() {
         fields: vec![],
         relationships: vec![],
         undoable: false,
+        undo_journal: false,
+        source_file: String::new(),
     };
     uow.entities.insert(1, ent_a.clone());
     uow.entities.insert(2, ent_b.clone());
@@ -577,6 +613,8 @@ fn for_file_various_combinations_generate_expected_items() {
         long_operation: false,
         dto_in: Some(10),
         dto_out: Some(11),
+        shared_dto_in: None,
+        shared_dto_out: None,
     };
     uow.use_cases.insert(100, uc.clone());
 
@@ -587,6 +625,8 @@ fn for_file_various_combinations_generate_expected_items() {
         updated_at: chrono::Utc::now(),
         name: "Feat".into(),
         use_cases: vec![100],
+        dtos: vec![],
+        source_file: String::new(),
     };
     uow.features.insert(200, feat.clone());
     let global = Global {
@@ -598,6 +638,8 @@ fn for_file_various_combinations_generate_expected_items() {
         organisation_name: "Org".into(),
         organisation_domain: "org.com".into(),
         prefix_path: "".into(),
+        storage: "memory".into(),
+        templates_path: String::new(),
     };
     uow.globals.insert(3, global);
     let user_interface = UserInterface {
@@ -621,6 +663,11 @@ fn for_file_various_combinations_generate_expected_items() {
         entities: vec![1, 2],
         features: vec![200],
         user_interface: 1,
+        custom_files: vec![],
+        includes: vec![],
+        manifest_text: String::new(),
+        included_files: vec![],
+        included_texts: vec![],
     };
     uow.workspaces.insert(2, workspace);
     uow.workspace_features.insert(2, vec![200]);
@@ -805,3 +852,184 @@ fn for_file_various_combinations_generate_expected_items() {
     // entities from UC plus explicitly provided entity
     assert!(snap.entities.contains_key(&1) && snap.entities.contains_key(&2));
 }
+
+// Seeds an in-memory database with a "Library" owning "Book" entities and the given UI
+fn seed_library_workspace(
+    db_context: &common::database::db_context::DbContext,
+    ui: UserInterface,
+) -> Result<()> {
+    use common::database::transactions::Transaction;
+    use common::direct_access::repository_factory::write;
+    use common::event::EventBuffer;
+
+    let mut transaction = Transaction::begin_write_transaction(db_context)?;
+    let mut events = EventBuffer::new();
+
+    let global = write::create_global_repository(&transaction)?.create_orphan(
+        &mut events,
+        &Global {
+            application_name: "Library App".into(),
+            language: "cpp-qt".into(),
+            organisation_name: "Org".into(),
+            organisation_domain: "org.com".into(),
+            storage: "memory".into(),
+            ..Default::default()
+        },
+    )?;
+    let ui =
+        write::create_user_interface_repository(&transaction)?.create_orphan(&mut events, &ui)?;
+    let mut fields = write::create_field_repository(&transaction)?;
+    let books = fields.create_orphan(
+        &mut events,
+        &Field {
+            name: "books".into(),
+            field_type: FieldType::Entity,
+            is_list: true,
+            strong: true,
+            ..Default::default()
+        },
+    )?;
+    let title = fields.create_orphan(
+        &mut events,
+        &Field {
+            name: "title".into(),
+            field_type: FieldType::String,
+            ..Default::default()
+        },
+    )?;
+    let mut entities = write::create_entity_repository(&transaction)?;
+    let book = entities.create_orphan(
+        &mut events,
+        &Entity {
+            name: "Book".into(),
+            fields: vec![title.id],
+            undoable: true,
+            ..Default::default()
+        },
+    )?;
+    fields.update(
+        &mut events,
+        &Field {
+            entity: Some(book.id),
+            ..books
+        },
+    )?;
+    let library = entities.create_orphan(
+        &mut events,
+        &Entity {
+            name: "Library".into(),
+            fields: vec![books.id],
+            undoable: true,
+            ..Default::default()
+        },
+    )?;
+    let workspace = write::create_workspace_repository(&transaction)?.create_orphan(
+        &mut events,
+        &Workspace {
+            global: global.id,
+            user_interface: ui.id,
+            entities: vec![library.id, book.id],
+            ..Default::default()
+        },
+    )?;
+    let system = write::create_system_repository(&transaction)?
+        .create_orphan(&mut events, &System::default())?;
+    write::create_root_repository(&transaction)?.create_orphan(
+        &mut events,
+        &Root {
+            workspace: Some(workspace.id),
+            system: Some(system.id),
+            ..Default::default()
+        },
+    )?;
+    drop((fields, entities));
+    transaction.commit()
+}
+
+// Fills the file list and renders every listed file of the given template names
+fn fill_and_render(ui: UserInterface, template_names: &[&str]) -> Result<Vec<(String, String)>> {
+    use crate::cpp_qt_file_generation_controller;
+    use crate::{FillCppQtFilesDto, GenerateCppQtCodeDto};
+
+    let db_context = common::database::db_context::DbContext::new()?;
+    let event_hub = std::sync::Arc::new(common::event::EventHub::new());
+    seed_library_workspace(&db_context, ui)?;
+
+    let filled = cpp_qt_file_generation_controller::fill_cpp_qt_files(
+        &db_context,
+        &event_hub,
+        &FillCppQtFilesDto {
+            only_list_already_existing: false,
+        },
+    )?;
+
+    let transaction =
+        common::database::transactions::Transaction::begin_read_transaction(&db_context)?;
+    let file_repository =
+        common::direct_access::repository_factory::write::create_file_repository(&transaction)?;
+    let mut rendered = Vec::new();
+    for (file_id, file_name) in filled.file_ids.iter().zip(&filled.file_names) {
+        let file = file_repository
+            .get(file_id)?
+            .ok_or_else(|| anyhow::anyhow!("File not found"))?;
+        if !template_names.contains(&file.template_name.as_str()) {
+            continue;
+        }
+        let code = cpp_qt_file_generation_controller::generate_cpp_qt_code(
+            &db_context,
+            &event_hub,
+            &GenerateCppQtCodeDto { file_id: *file_id },
+        )?;
+        rendered.push((file_name.clone(), code.generated_code));
+    }
+    Ok(rendered)
+}
+
+#[test]
+fn qt_widgets_models_and_editors_are_listed_per_entity() {
+    let rendered = fill_and_render(
+        UserInterface {
+            cpp_qt_qtwidgets: true,
+            ..Default::default()
+        },
+        &[
+            "qt_widgets_cmake",
+            "qt_widgets_entity_table_model_h",
+            "qt_widgets_entity_table_model_cpp",
+            "qt_widgets_entity_editor_h",
+            "qt_widgets_entity_editor_cpp",
+        ],
+    )
+    .expect("qt widgets files");
+    let mut names: Vec<&str> = rendered.iter().map(|(name, _)| name.as_str()).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            "src/qtwidgets_app/CMakeLists.txt",
+            "src/qtwidgets_app/editors/book_editor.cpp",
+            "src/qtwidgets_app/editors/book_editor.h",
+            "src/qtwidgets_app/editors/library_editor.cpp",
+            "src/qtwidgets_app/editors/library_editor.h",
+            "src/qtwidgets_app/models/book_table_model.cpp",
+            "src/qtwidgets_app/models/book_table_model.h",
+            "src/qtwidgets_app/models/library_table_model.cpp",
+            "src/qtwidgets_app/models/library_table_model.h",
+        ]
+    );
+
+    for (name, code) in &rendered {
+        if name.ends_with("book_table_model.h") {
+            assert!(code.contains("class BookTableModel"), "{name}");
+        }
+        if name.ends_with("book_editor.h") {
+            assert!(code.contains("class BookEditor"), "{name}");
+            assert!(code.contains("m_titleField"), "{name}");
+        }
+        if name.ends_with("CMakeLists.txt") {
+            for source in ["models/book_table_model.cpp", "editors/library_editor.cpp"] {
+                assert!(code.contains(source), "{source} not built");
+            }
+        }
+    }
+}
@@ -44,6 +44,8 @@ fn render_direct_access_lib_lists_entities() {
         organisation_name: "".to_string(),
         organisation_domain: "".to_string(),
         prefix_path: "".to_string(),
+        storage: "memory".to_string(),
+        templates_path: String::new(),
     };
 
     let e1 = Entity {
@@ -58,6 +60,8 @@ fn render_direct_access_lib_lists_entities() {
         fields: vec![],
         relationships: vec![],
         undoable: false,
+        undo_journal: false,
+        source_file: String::new(),
     };
     let e2 = Entity {
         id: 11,
@@ -71,6 +75,8 @@ fn render_direct_access_lib_lists_entities() {
         fields: vec![],
         relationships: vec![],
         undoable: false,
+        undo_journal: false,
+        source_file: String::new(),
     };
 
     let mut entities = IndexMap::new();
@@ -1,5 +1,5 @@
 use crate::use_cases::common::cpp_qt_code_generator::GenerationOps;
-use common::entities::Workspace;
+use common::entities::{Global, Workspace};
 use common::types::EntityId;
 
 pub fn get_system_id(uow: &dyn GenerationOps) -> anyhow::Result<EntityId> {
@@ -66,6 +66,23 @@ pub fn get_workspace(uow: &dyn GenerationOps) -> anyhow::Result<Workspace> {
     Ok(workspace)
 }
 
+pub fn get_global(uow: &dyn GenerationOps) -> anyhow::Result<Global> {
+    use anyhow::anyhow;
+    let workspace_id = get_workspace_id(uow)?;
+    let global_ids = uow.get_workspace_relationship(
+        &workspace_id,
+        &common::direct_access::workspace::WorkspaceRelationshipField::Global,
+    )?;
+    let global_id = global_ids
+        .first()
+        .cloned()
+        .ok_or(anyhow!("No global found"))?;
+    let global = uow
+        .get_global(&global_id)?
+        .ok_or_else(|| anyhow!("Global entity not found"))?;
+    Ok(global)
+}
+
 pub fn strip_leading_and_trailing_slashes(path: &str) -> String {
     let trimmed = path.trim_matches(|c: char| c == '/' || c == '\\' || c.is_whitespace());
     trimmed.to_string()
@@ -1,7 +1,9 @@
 // Generated by Qleany. Edit at your own risk! Be careful when regenerating this file
 // as changes will be lost.
+use crate::FillCodeInCppQtFilesReturnDto;
 use crate::use_cases::common::cpp_qt_code_generator::{
     GenerationOps, GenerationSnapshot, SnapshotBuilder, generate_code_with_snapshot,
+    get_tera_for_workspace,
 };
 use crate::use_cases::common::cpp_qt_formatter::clang_format_files_batch;
 use crate::use_cases::common::qml_formatter::qml_format_files_batch;
@@ -10,6 +12,7 @@ use anyhow::{Result, anyhow};
 use common::database::CommandUnitOfWork;
 use common::direct_access::system::SystemRelationshipField;
 use common::entities::File;
+use common::generator::user_regions;
 use common::long_operation::LongOperation;
 use common::types::EntityId;
 use rayon::prelude::*;
@@ -20,7 +23,8 @@ pub trait FillCodeInCppQtFilesUnitOfWorkFactoryTrait: Send + Sync {
     fn create(&self) -> Box<dyn FillCodeInCppQtFilesUnitOfWorkTrait>;
 }
 
-// Exactly the same macros must be set in ../units_of_work/fill_code_in_cpp_qt_files_uow.rs
+// The unit of work in ../units_of_work/fill_code_in_cpp_qt_files_uow.rs takes these actions through #[macros::uow_impl]
+#[macros::uow_trait]
 #[macros::uow_action(entity = "System", action = "GetRelationship")]
 #[macros::uow_action(entity = "File", action = "GetMulti")]
 #[macros::uow_action(entity = "File", action = "UpdateMulti")]
@@ -40,12 +44,12 @@ impl FillCodeInCppQtFilesUseCase {
     }
 }
 impl LongOperation for FillCodeInCppQtFilesUseCase {
-    type Output = ();
+    type Output = FillCodeInCppQtFilesReturnDto;
     fn execute(
         &self,
         progress_callback: Box<dyn Fn(common::long_operation::OperationProgress) + Send>,
         cancel_flag: Arc<std::sync::atomic::AtomicBool>,
-    ) -> Result<()> {
+    ) -> Result<FillCodeInCppQtFilesReturnDto> {
         use std::fs;
         use std::sync::atomic::Ordering;
 
@@ -70,7 +74,9 @@ impl LongOperation for FillCodeInCppQtFilesUseCase {
                 100.0,
                 Some("No files to process".to_string()),
             ));
-            return Ok(());
+            return Ok(FillCodeInCppQtFilesReturnDto {
+                orphaned_user_regions: vec![],
+            });
         }
 
         // Load all file metadata
@@ -82,6 +88,10 @@ impl LongOperation for FillCodeInCppQtFilesUseCase {
 
         let total = files.len().max(1);
 
+        // User regions are read back from the files on disk, next to the manifest
+        let root_path = PathBuf::from(tools::get_workspace(uow_ops)?.manifest_absolute_path);
+        let tera = get_tera_for_workspace(uow_ops)?;
+
         // Create a temp directory that mirrors the real folder structure so
         // clang-format works correctly with includes.
         let tmp_dir =
@@ -128,21 +138,34 @@ impl LongOperation for FillCodeInCppQtFilesUseCase {
         }
 
         // Phase 2 (parallel): Render templates + write files (CPU+IO-bound)
-        let generated: Vec<(EntityId, String, PathBuf)> = file_snapshots
+        let generated: Vec<(EntityId, String, PathBuf, Vec<String>)> = file_snapshots
             .par_iter()
             .map(|(file, snapshot)| {
                 if cancel_flag.load(Ordering::Relaxed) {
                     return Err(anyhow!("Operation was cancelled"));
                 }
 
-                let code = generate_code_with_snapshot(snapshot)?;
+                let mut code = generate_code_with_snapshot(&tera, snapshot)?;
+
+                // Carry over the user regions of the file on disk
+                let disk_path = root_path.join(&file.relative_path).join(&file.name);
+                let mut orphaned = vec![];
+                if let Ok(on_disk) = fs::read_to_string(&disk_path) {
+                    let outcome = user_regions::preserve_user_regions(&code, &on_disk);
+                    code = outcome.code;
+                    orphaned = outcome
+                        .orphaned
+                        .into_iter()
+                        .map(|id| format!("{}{}: {}", file.relative_path, file.name, id))
+                        .collect();
+                }
 
                 let file_dir = tmp_dir.join(&file.relative_path);
                 fs::create_dir_all(&file_dir)?;
                 let temp_path = file_dir.join(&file.name);
                 fs::write(&temp_path, code.as_bytes())?;
 
-                Ok((file.id, code, temp_path))
+                Ok((file.id, code, temp_path, orphaned))
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -160,17 +183,17 @@ impl LongOperation for FillCodeInCppQtFilesUseCase {
 
         let cpp_qt_files_to_format: Vec<PathBuf> = generated
             .iter()
-            .filter(|(_, _, path)| {
+            .filter(|(_, _, path, _)| {
                 path.extension()
                     .is_some_and(|ext| ext == "h" || ext == "cpp")
             })
-            .map(|(_, _, path)| path.clone())
+            .map(|(_, _, path, _)| path.clone())
             .collect();
 
         let qml_files_to_format: Vec<PathBuf> = generated
             .iter()
-            .filter(|(_, _, path)| path.extension().is_some_and(|ext| ext == "qml"))
-            .map(|(_, _, path)| path.clone())
+            .filter(|(_, _, path, _)| path.extension().is_some_and(|ext| ext == "qml"))
+            .map(|(_, _, path, _)| path.clone())
             .collect();
 
         progress_callback(common::long_operation::OperationProgress::new(
@@ -209,7 +232,7 @@ impl LongOperation for FillCodeInCppQtFilesUseCase {
         ));
 
         let mut files_to_update: Vec<File> = Vec::new();
-        for (idx, (file_id, fallback_code, temp_path)) in generated.iter().enumerate() {
+        for (idx, (file_id, fallback_code, temp_path, _)) in generated.iter().enumerate() {
             if cancel_flag.load(Ordering::Relaxed) {
                 let _ = fs::remove_dir_all(&tmp_dir);
                 uow.rollback()?;
@@ -250,6 +273,14 @@ impl LongOperation for FillCodeInCppQtFilesUseCase {
 
         uow.commit()?;
 
+        let orphaned_user_regions: Vec<String> = generated
+            .into_iter()
+            .flat_map(|(_, _, _, orphaned)| orphaned)
+            .collect();
+        for region in &orphaned_user_regions {
+            log::warn!("Orphaned user region, its content will be lost: {}", region);
+        }
+
         let duration = start_time.elapsed();
         log::debug!(
             "Fill code in C++/Qt files completed in {:?}, total files: {}",
@@ -261,6 +292,8 @@ impl LongOperation for FillCodeInCppQtFilesUseCase {
             100.0,
             Some("completed".to_string()),
         ));
-        Ok(())
+        Ok(FillCodeInCppQtFilesReturnDto {
+            orphaned_user_regions,
+        })
     }
 }
@@ -5,7 +5,8 @@ use common::direct_access::feature::FeatureRelationshipField;
 use common::direct_access::system::SystemRelationshipField;
 use common::direct_access::workspace::WorkspaceRelationshipField;
 use common::entities::UserInterface;
-use common::entities::{Entity, FileNature};
+use common::entities::{CustomFile, Entity, FileNature};
+use common::generator::custom_files;
 use common::generator::file_list_builder::FileListBuilder;
 use common::types::EntityId;
 use common::{
@@ -18,6 +19,7 @@ pub trait FillCppQtFilesUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn FillCppQtFilesUnitOfWorkTrait>;
 }
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetAll")]
 #[macros::uow_action(entity = "Root", action = "GetRelationship")]
 #[macros::uow_action(entity = "Workspace", action = "GetRelationship")]
@@ -32,6 +34,7 @@ pub trait FillCppQtFilesUnitOfWorkFactoryTrait {
 #[macros::uow_action(entity = "Feature", action = "GetMulti")]
 #[macros::uow_action(entity = "Feature", action = "GetRelationship")]
 #[macros::uow_action(entity = "UseCase", action = "GetMulti")]
+#[macros::uow_action(entity = "CustomFile", action = "GetMulti")]
 #[macros::uow_action(entity = "File", action = "CreateOrphan")]
 #[macros::uow_action(entity = "File", action = "CreateOrphanMulti")]
 #[macros::uow_action(entity = "File", action = "RemoveMulti")]
@@ -490,6 +493,13 @@ impl FillCppQtFilesUseCase {
             "operators_h",
             FileNature::Infrastructure,
         );
+        b.add(
+            "entity_query.h",
+            format!("{}/common/direct_access/", prefix),
+            "common_direct_access",
+            "entity_query_h",
+            FileNature::Infrastructure,
+        );
         b.add(
             "CMakeLists.txt",
             format!("{}/common/direct_access/", prefix),
@@ -533,6 +543,13 @@ impl FillCppQtFilesUseCase {
             "uc_helper_get_all_h",
             FileNature::Infrastructure,
         );
+        b.add(
+            "query_uc.h",
+            format!("{}/common/direct_access/use_case_helpers/", prefix),
+            "common_direct_access",
+            "uc_helper_query_h",
+            FileNature::Infrastructure,
+        );
         b.add(
             "update_uc.h",
             format!("{}/common/direct_access/use_case_helpers/", prefix),
@@ -589,6 +606,13 @@ impl FillCppQtFilesUseCase {
             "uc_helper_move_relationship_ids_h",
             FileNature::Infrastructure,
         );
+        b.add(
+            "validation_error.h",
+            format!("{}/common/entities/", prefix),
+            "common_direct_access",
+            "validation_error_h",
+            FileNature::Infrastructure,
+        );
         b.add(
             "CMakeLists.txt",
             format!("{}/common/entities/", prefix),
@@ -1009,14 +1033,15 @@ impl FillCppQtFilesUseCase {
             // for common/entities/
             let relative_path = format!("{}/qtwidgets_app/", prefix);
 
-            b.add(
+            let f = b.add(
                 "CMakeLists.txt",
                 relative_path.clone(),
                 "QtWidgets UI",
                 "qt_widgets_cmake",
                 FileNature::Aggregate,
-            )
-            .all_features = true;
+            );
+            f.all_features = true;
+            f.all_entities = true;
             b.add(
                 "main.cpp",
                 relative_path.clone(),
@@ -1040,6 +1065,52 @@ impl FillCppQtFilesUseCase {
                 FileNature::Scaffold,
             )
             .all_entities = true;
+
+            // table model and editor per entity
+            for entity in &entities {
+                let entity = entity.as_ref().ok_or(anyhow!("Entity not found"))?;
+
+                // skip if entity is "heritage"
+                if entity.only_for_heritage {
+                    continue;
+                }
+
+                let entity_snake_name = heck::AsSnakeCase(&entity.name);
+                let entity_pascal_name = heck::AsPascalCase(&entity.name);
+
+                b.add(
+                    format!("{}_table_model.h", entity_snake_name),
+                    format!("{}models/", relative_path),
+                    format!("QtWidgets UI: {}", entity_pascal_name),
+                    "qt_widgets_entity_table_model_h",
+                    FileNature::Infrastructure,
+                )
+                .entity = Some(entity.id);
+                b.add(
+                    format!("{}_table_model.cpp", entity_snake_name),
+                    format!("{}models/", relative_path),
+                    format!("QtWidgets UI: {}", entity_pascal_name),
+                    "qt_widgets_entity_table_model_cpp",
+                    FileNature::Infrastructure,
+                )
+                .entity = Some(entity.id);
+                b.add(
+                    format!("{}_editor.h", entity_snake_name),
+                    format!("{}editors/", relative_path),
+                    format!("QtWidgets UI: {}", entity_pascal_name),
+                    "qt_widgets_entity_editor_h",
+                    FileNature::Scaffold,
+                )
+                .entity = Some(entity.id);
+                b.add(
+                    format!("{}_editor.cpp", entity_snake_name),
+                    format!("{}editors/", relative_path),
+                    format!("QtWidgets UI: {}", entity_pascal_name),
+                    "qt_widgets_entity_editor_cpp",
+                    FileNature::Scaffold,
+                )
+                .entity = Some(entity.id);
+            }
         }
 
         //----------------------------------------------------------------------
@@ -1563,6 +1634,36 @@ impl FillCppQtFilesUseCase {
             FileNature::Infrastructure,
         );
 
+        // custom files declared in the manifest
+        let custom_files = uow
+            .get_workspace_relationship(&workspace_id, &WorkspaceRelationshipField::CustomFiles)?;
+        let custom_files = uow
+            .get_custom_file_multi(&custom_files)?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<CustomFile>>();
+        if !custom_files.is_empty() {
+            let entities = entities.into_iter().flatten().collect::<Vec<Entity>>();
+            let mut features_with_use_cases = vec![];
+            for feature in features.into_iter().flatten() {
+                let use_cases =
+                    uow.get_feature_relationship(&feature.id, &FeatureRelationshipField::UseCases)?;
+                let use_cases = uow
+                    .get_use_case_multi(&use_cases)?
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<UseCase>>();
+                features_with_use_cases.push((feature, use_cases));
+            }
+            custom_files::add_custom_files(
+                &mut b,
+                &custom_files,
+                &prefix,
+                &entities,
+                &features_with_use_cases,
+            );
+        }
+
         let files = if dto.only_list_already_existing {
             b.build_filtered(|file| {
                 let full_path = format!("{}{}", file.relative_path, file.name);
@@ -1,5 +1,5 @@
 use crate::use_cases::common::cpp_qt_code_generator::{
-    GenerationReadOps, SnapshotBuilder, generate_code_with_snapshot,
+    GenerationReadOps, SnapshotBuilder, generate_code_with_snapshot, get_tera_for_workspace,
 };
 use crate::use_cases::common::cpp_qt_formatter::clang_format_string;
 use crate::use_cases::common::qml_formatter::qml_format_string;
@@ -33,9 +33,10 @@ impl GenerateCppQtCodeUseCase {
         let file = GenerationReadOps::get_file(uow_ref, &dto.file_id)?;
         let (snapshot, _from_cache) =
             SnapshotBuilder::for_file_id(uow_ref, dto.file_id, &Vec::new())?;
+        let tera = get_tera_for_workspace(uow_ref)?;
         uow.end_transaction()?;
 
-        let generated_code = generate_code_with_snapshot(&snapshot)?;
+        let generated_code = generate_code_with_snapshot(&tera, &snapshot)?;
 
         let is_qml = file.as_ref().is_some_and(|f| f.name.ends_with(".qml"));
         let formatted_code = if is_qml {
@@ -1,5 +1,6 @@
 use crate::use_cases::common::cpp_qt_code_generator::{
     GenerationReadOps, GenerationSnapshot, SnapshotBuilder, generate_code_with_snapshot,
+    get_tera_for_workspace,
 };
 use crate::use_cases::common::cpp_qt_formatter::clang_format_files_batch;
 use crate::use_cases::common::qml_formatter::qml_format_files_batch;
@@ -7,6 +8,7 @@ use crate::use_cases::common::tools;
 use crate::{GenerateCppQtFilesDto, GenerateCppQtFilesReturnDto};
 use anyhow::{Result, anyhow};
 use common::entities::{File, Global, Root};
+use common::generator::{baseline_store, user_regions};
 use common::long_operation::LongOperation;
 use common::types::EntityId;
 use rayon::prelude::*;
@@ -17,6 +19,7 @@ pub trait GenerateCppQtFilesUnitOfWorkFactoryTrait: Send + Sync {
     fn create(&self) -> Box<dyn GenerateCppQtFilesUnitOfWorkTrait>;
 }
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
 #[macros::uow_action(entity = "Root", action = "GetMultiRO")]
 #[macros::uow_action(entity = "Global", action = "GetMultiRO")]
@@ -67,6 +70,7 @@ impl LongOperation for GenerateCppQtFilesUseCase {
         let uow = self.uow_factory.create();
         uow.begin_transaction()?;
         let uow_read: &dyn GenerationReadOps = &*uow;
+        let tera = get_tera_for_workspace(uow_read)?;
 
         let mut written_files: Vec<String> = Vec::new();
         let mut cpp_qt_files_to_format: Vec<PathBuf> = Vec::new();
@@ -124,7 +128,19 @@ impl LongOperation for GenerateCppQtFilesUseCase {
                     return Err(anyhow!("Operation was cancelled"));
                 }
 
-                let code = generate_code_with_snapshot(snapshot)?;
+                let mut code = generate_code_with_snapshot(&tera, snapshot)?;
+
+                // Carry over the user regions of the file being replaced
+                let disk_path = root_path
+                    .join(&file_meta.relative_path)
+                    .join(&file_meta.name);
+                if let Ok(on_disk) = fs::read_to_string(&disk_path) {
+                    let outcome = user_regions::preserve_user_regions(&code, &on_disk);
+                    for id in &outcome.orphaned {
+                        log::warn!("Orphaned user region in {}: {}", disk_path.display(), id);
+                    }
+                    code = outcome.code;
+                }
 
                 let mut out_dir = root_path.clone();
                 if !prefix_path.as_os_str().is_empty() {
@@ -194,6 +210,21 @@ impl LongOperation for GenerateCppQtFilesUseCase {
             qml_format_files_batch(&qml_files_to_format);
         }
 
+        // Record what was written as the baseline for future three-way merges,
+        // unless the files were generated aside (e.g. in temp/)
+        if prefix_path.as_os_str().is_empty() {
+            for ((file_meta, _), (_, out_path)) in file_snapshots.iter().zip(&results) {
+                if let Ok(content) = fs::read_to_string(out_path) {
+                    baseline_store::write_baseline(
+                        &root_path,
+                        &file_meta.relative_path,
+                        &file_meta.name,
+                        &content,
+                    )?;
+                }
+            }
+        }
+
         // Final progress
         progress_callback(common::long_operation::OperationProgress::new(
             100.0,
@@ -0,0 +1,5 @@
+// Generated by Qleany v1.7.0 from entity_mod.tera
+
+pub mod custom_file_controller;
+pub mod dtos;
+mod units_of_work;
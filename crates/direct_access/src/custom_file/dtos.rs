@@ -0,0 +1,192 @@
+// Generated by Qleany v1.7.0 from entity_dtos.tera
+
+pub use common::direct_access::custom_file::{
+    CustomFileFilter, CustomFileQuery, CustomFileSortField,
+};
+use common::entities::CustomFile;
+use common::types::EntityId;
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CustomFileDto {
+    pub id: EntityId,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub path: String,
+    pub template_name: String,
+    pub group: String,
+    pub nature: String,
+    pub scope: String,
+}
+
+impl From<CustomFileDto> for CustomFile {
+    fn from(dto: CustomFileDto) -> Self {
+        CustomFile {
+            id: dto.id,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+            path: dto.path,
+            template_name: dto.template_name,
+            group: dto.group,
+            nature: dto.nature,
+            scope: dto.scope,
+        }
+    }
+}
+
+impl From<&CustomFileDto> for CustomFile {
+    fn from(dto: &CustomFileDto) -> Self {
+        CustomFile {
+            id: dto.id,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+            path: dto.path.clone(),
+            template_name: dto.template_name.clone(),
+            group: dto.group.clone(),
+            nature: dto.nature.clone(),
+            scope: dto.scope.clone(),
+        }
+    }
+}
+
+impl From<CustomFile> for CustomFileDto {
+    fn from(entity: CustomFile) -> Self {
+        CustomFileDto {
+            id: entity.id,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+            path: entity.path,
+            template_name: entity.template_name,
+            group: entity.group,
+            nature: entity.nature,
+            scope: entity.scope,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CreateCustomFileDto {
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub path: String,
+    pub template_name: String,
+    pub group: String,
+    pub nature: String,
+    pub scope: String,
+}
+
+impl From<CreateCustomFileDto> for CustomFile {
+    fn from(dto: CreateCustomFileDto) -> Self {
+        CustomFile {
+            id: 0,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+            path: dto.path,
+            template_name: dto.template_name,
+            group: dto.group,
+            nature: dto.nature,
+            scope: dto.scope,
+        }
+    }
+}
+
+impl From<&CreateCustomFileDto> for CustomFile {
+    fn from(dto: &CreateCustomFileDto) -> Self {
+        CustomFile {
+            id: 0,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+            path: dto.path.clone(),
+            template_name: dto.template_name.clone(),
+            group: dto.group.clone(),
+            nature: dto.nature.clone(),
+            scope: dto.scope.clone(),
+        }
+    }
+}
+
+impl From<CustomFile> for CreateCustomFileDto {
+    fn from(entity: CustomFile) -> Self {
+        CreateCustomFileDto {
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+            path: entity.path,
+            template_name: entity.template_name,
+            group: entity.group,
+            nature: entity.nature,
+            scope: entity.scope,
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct UpdateCustomFileDto {
+    pub id: EntityId,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub path: String,
+    pub template_name: String,
+    pub group: String,
+    pub nature: String,
+    pub scope: String,
+}
+
+impl From<UpdateCustomFileDto> for CustomFile {
+    fn from(dto: UpdateCustomFileDto) -> Self {
+        CustomFile {
+            id: dto.id,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+            path: dto.path,
+            template_name: dto.template_name,
+            group: dto.group,
+            nature: dto.nature,
+            scope: dto.scope,
+        }
+    }
+}
+
+impl From<&UpdateCustomFileDto> for CustomFile {
+    fn from(dto: &UpdateCustomFileDto) -> Self {
+        CustomFile {
+            id: dto.id,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+            path: dto.path.clone(),
+            template_name: dto.template_name.clone(),
+            group: dto.group.clone(),
+            nature: dto.nature.clone(),
+            scope: dto.scope.clone(),
+        }
+    }
+}
+
+impl From<CustomFile> for UpdateCustomFileDto {
+    fn from(entity: CustomFile) -> Self {
+        UpdateCustomFileDto {
+            id: entity.id,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+            path: entity.path,
+            template_name: entity.template_name,
+            group: entity.group,
+            nature: entity.nature,
+            scope: entity.scope,
+        }
+    }
+}
+
+impl From<CustomFileDto> for UpdateCustomFileDto {
+    fn from(dto: CustomFileDto) -> Self {
+        UpdateCustomFileDto {
+            id: dto.id,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+            path: dto.path,
+            template_name: dto.template_name,
+            group: dto.group,
+            nature: dto.nature,
+            scope: dto.scope,
+        }
+    }
+}
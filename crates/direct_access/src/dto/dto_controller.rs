@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from entity_controller.tera
 
 use super::{
-    dtos::{CreateDtoDto, DtoDto, UpdateDtoDto},
+    dtos::{CreateDtoDto, DtoDto, DtoQuery, UpdateDtoDto},
     units_of_work::{DtoReadUoWFactory, DtoWriteUoWFactory},
 };
 use crate::DtoRelationshipDto;
@@ -100,6 +100,22 @@ pub fn get_multi(db_context: &DbContext, ids: &[EntityId]) -> Result<Vec<Option<
         .collect())
 }
 
+pub fn query(db_context: &DbContext, query: &DtoQuery) -> Result<Vec<DtoDto>> {
+    let uow_factory = DtoReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    Ok(uc
+        .execute_query(query)?
+        .into_iter()
+        .map(|e| e.into())
+        .collect())
+}
+
+pub fn count(db_context: &DbContext, query: &DtoQuery) -> Result<usize> {
+    let uow_factory = DtoReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    uc.execute_count(query)
+}
+
 pub fn update(
     db_context: &DbContext,
     event_hub: &Arc<EventHub>,
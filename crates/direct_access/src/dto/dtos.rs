@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::dto::{DtoFilter, DtoQuery, DtoSortField};
 use common::entities::Dto;
 use common::types::EntityId;
 use serde::{Deserialize, Serialize};
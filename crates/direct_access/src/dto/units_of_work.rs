@@ -3,6 +3,7 @@
 use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
+use common::direct_access::dto::DtoQuery;
 use common::direct_access::dto::DtoRelationshipField;
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
@@ -289,6 +290,7 @@ impl QueryUnitOfWork for DtoReadUoW {
 
 impl use_cases::ReadUoW for DtoReadUoW {
     type Entity = Dto;
+    type Query = DtoQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<Dto>> {
         let transaction = self.transaction.borrow();
@@ -319,6 +321,26 @@ impl use_cases::ReadUoW for DtoReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &DtoQuery) -> Result<Vec<Dto>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_dto_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &DtoQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_dto_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<DtoRelationshipField> for DtoReadUoW {
@@ -395,7 +417,8 @@ impl DtoReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for DtoReadUoWFactory {
     type Entity = Dto;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Dto>> {
+    type Query = DtoQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Dto, Query = DtoQuery>> {
         Box::new(DtoReadUoW::new(&self.context))
     }
 }
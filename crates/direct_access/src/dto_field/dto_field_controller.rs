@@ -1,10 +1,12 @@
 // Generated by Qleany v1.7.0 from entity_controller.tera
 
 use super::{
-    dtos::{CreateDtoFieldDto, DtoFieldDto, UpdateDtoFieldDto},
+    dtos::{CreateDtoFieldDto, DtoFieldDto, DtoFieldQuery, UpdateDtoFieldDto},
     units_of_work::{DtoFieldReadUoWFactory, DtoFieldWriteUoWFactory},
 };
+use crate::DtoFieldRelationshipDto;
 use anyhow::{Ok, Result};
+use common::direct_access::dto_field::DtoFieldRelationshipField;
 use common::direct_access::use_cases;
 use common::undo_redo::UndoRedoManager;
 use common::{database::db_context::DbContext, event::EventHub, types::EntityId};
@@ -100,6 +102,22 @@ pub fn get_multi(db_context: &DbContext, ids: &[EntityId]) -> Result<Vec<Option<
         .collect())
 }
 
+pub fn query(db_context: &DbContext, query: &DtoFieldQuery) -> Result<Vec<DtoFieldDto>> {
+    let uow_factory = DtoFieldReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    Ok(uc
+        .execute_query(query)?
+        .into_iter()
+        .map(|e| e.into())
+        .collect())
+}
+
+pub fn count(db_context: &DbContext, query: &DtoFieldQuery) -> Result<usize> {
+    let uow_factory = DtoFieldReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    uc.execute_count(query)
+}
+
 pub fn update(
     db_context: &DbContext,
     event_hub: &Arc<EventHub>,
@@ -190,6 +208,86 @@ pub fn remove_multi(
     Ok(())
 }
 
+pub fn get_relationship(
+    db_context: &DbContext,
+    id: &EntityId,
+    field: &DtoFieldRelationshipField,
+) -> Result<Vec<EntityId>> {
+    let uow_factory = DtoFieldReadUoWFactory::new(db_context);
+    let uc = use_cases::GetRelationshipUseCase::<DtoFieldRelationshipField, _>::new(uow_factory);
+    uc.execute(id, field)
+}
+
+pub fn get_relationship_many(
+    db_context: &DbContext,
+    ids: &[EntityId],
+    field: &DtoFieldRelationshipField,
+) -> Result<std::collections::HashMap<EntityId, Vec<EntityId>>> {
+    let uow_factory = DtoFieldReadUoWFactory::new(db_context);
+    let uc =
+        use_cases::GetRelationshipManyUseCase::<DtoFieldRelationshipField, _>::new(uow_factory);
+    uc.execute(ids, field)
+}
+
+pub fn get_relationship_count(
+    db_context: &DbContext,
+    id: &EntityId,
+    field: &DtoFieldRelationshipField,
+) -> Result<usize> {
+    let uow_factory = DtoFieldReadUoWFactory::new(db_context);
+    let uc =
+        use_cases::GetRelationshipCountUseCase::<DtoFieldRelationshipField, _>::new(uow_factory);
+    uc.execute(id, field)
+}
+
+pub fn get_relationship_in_range(
+    db_context: &DbContext,
+    id: &EntityId,
+    field: &DtoFieldRelationshipField,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<EntityId>> {
+    let uow_factory = DtoFieldReadUoWFactory::new(db_context);
+    let uc =
+        use_cases::GetRelationshipInRangeUseCase::<DtoFieldRelationshipField, _>::new(uow_factory);
+    uc.execute(id, field, offset, limit)
+}
+
+pub fn set_relationship(
+    db_context: &DbContext,
+    event_hub: &Arc<EventHub>,
+    undo_redo_manager: &mut UndoRedoManager,
+    stack_id: Option<u64>,
+    dto: &DtoFieldRelationshipDto,
+) -> Result<()> {
+    let uow_factory = DtoFieldWriteUoWFactory::new(db_context, event_hub);
+    let mut uc =
+        use_cases::UndoableSetRelationshipUseCase::<DtoFieldRelationshipField, _>::new(uow_factory);
+    uc.execute(&dto.id, &dto.field, dto.right_ids.as_slice())?;
+    undo_redo_manager.add_command_to_stack(Box::new(uc), stack_id)?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn move_relationship(
+    db_context: &DbContext,
+    event_hub: &Arc<EventHub>,
+    undo_redo_manager: &mut UndoRedoManager,
+    stack_id: Option<u64>,
+    id: &EntityId,
+    field: &DtoFieldRelationshipField,
+    ids_to_move: &[EntityId],
+    new_index: i32,
+) -> Result<Vec<EntityId>> {
+    let uow_factory = DtoFieldWriteUoWFactory::new(db_context, event_hub);
+    let mut uc = use_cases::UndoableMoveRelationshipUseCase::<DtoFieldRelationshipField, _>::new(
+        uow_factory,
+    );
+    let result = uc.execute(id, field, ids_to_move, new_index)?;
+    undo_redo_manager.add_command_to_stack(Box::new(uc), stack_id)?;
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(dead_code)]
@@ -424,4 +522,30 @@ mod tests {
         ctx.undo.undo(None).unwrap();
         assert!(get(&ctx.db, &created.id).unwrap().is_some());
     }
+
+    // -----------------------------------------------------------------------
+    // relationship: get default
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_get_relationship_default() {
+        let mut ctx = TestContext::new();
+        let created = create_one(&mut ctx);
+        let rel_ids =
+            get_relationship(&ctx.db, &created.id, &DtoFieldRelationshipField::Dto).unwrap();
+        assert!(rel_ids.is_empty());
+    }
+
+    // -----------------------------------------------------------------------
+    // relationship: count default
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_get_relationship_count_default() {
+        let mut ctx = TestContext::new();
+        let created = create_one(&mut ctx);
+        let count =
+            get_relationship_count(&ctx.db, &created.id, &DtoFieldRelationshipField::Dto).unwrap();
+        assert_eq!(count, 0);
+    }
 }
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::dto_field::{DtoFieldFilter, DtoFieldQuery, DtoFieldSortField};
 use common::entities::DtoField;
 pub use common::entities::DtoFieldType;
 use common::types::EntityId;
@@ -17,6 +18,14 @@ pub struct DtoFieldDto {
     pub is_list: bool,
     pub enum_name: Option<String>,
     pub enum_values: Vec<String>,
+    pub dto: Option<EntityId>,
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub min_length: Option<i64>,
+    pub max_length: Option<i64>,
+    pub pattern: Option<String>,
+    pub not_empty: bool,
+    pub default_value: Option<String>,
 }
 
 impl From<DtoFieldDto> for DtoField {
@@ -31,6 +40,14 @@ impl From<DtoFieldDto> for DtoField {
             is_list: dto.is_list,
             enum_name: dto.enum_name,
             enum_values: dto.enum_values,
+            dto: dto.dto,
+            min_value: dto.min_value,
+            max_value: dto.max_value,
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern,
+            not_empty: dto.not_empty,
+            default_value: dto.default_value,
         }
     }
 }
@@ -47,6 +64,14 @@ impl From<&DtoFieldDto> for DtoField {
             is_list: dto.is_list,
             enum_name: dto.enum_name.clone(),
             enum_values: dto.enum_values.clone(),
+            dto: dto.dto,
+            min_value: dto.min_value.clone(),
+            max_value: dto.max_value.clone(),
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern.clone(),
+            not_empty: dto.not_empty,
+            default_value: dto.default_value.clone(),
         }
     }
 }
@@ -63,6 +88,14 @@ impl From<DtoField> for DtoFieldDto {
             is_list: entity.is_list,
             enum_name: entity.enum_name,
             enum_values: entity.enum_values,
+            dto: entity.dto,
+            min_value: entity.min_value,
+            max_value: entity.max_value,
+            min_length: entity.min_length,
+            max_length: entity.max_length,
+            pattern: entity.pattern,
+            not_empty: entity.not_empty,
+            default_value: entity.default_value,
         }
     }
 }
@@ -77,6 +110,14 @@ pub struct CreateDtoFieldDto {
     pub is_list: bool,
     pub enum_name: Option<String>,
     pub enum_values: Vec<String>,
+    pub dto: Option<EntityId>,
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub min_length: Option<i64>,
+    pub max_length: Option<i64>,
+    pub pattern: Option<String>,
+    pub not_empty: bool,
+    pub default_value: Option<String>,
 }
 
 impl From<CreateDtoFieldDto> for DtoField {
@@ -91,6 +132,14 @@ impl From<CreateDtoFieldDto> for DtoField {
             is_list: dto.is_list,
             enum_name: dto.enum_name,
             enum_values: dto.enum_values,
+            dto: dto.dto,
+            min_value: dto.min_value,
+            max_value: dto.max_value,
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern,
+            not_empty: dto.not_empty,
+            default_value: dto.default_value,
         }
     }
 }
@@ -107,6 +156,14 @@ impl From<&CreateDtoFieldDto> for DtoField {
             is_list: dto.is_list,
             enum_name: dto.enum_name.clone(),
             enum_values: dto.enum_values.clone(),
+            dto: dto.dto,
+            min_value: dto.min_value.clone(),
+            max_value: dto.max_value.clone(),
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern.clone(),
+            not_empty: dto.not_empty,
+            default_value: dto.default_value.clone(),
         }
     }
 }
@@ -122,6 +179,14 @@ impl From<DtoField> for CreateDtoFieldDto {
             is_list: entity.is_list,
             enum_name: entity.enum_name,
             enum_values: entity.enum_values,
+            dto: entity.dto,
+            min_value: entity.min_value,
+            max_value: entity.max_value,
+            min_length: entity.min_length,
+            max_length: entity.max_length,
+            pattern: entity.pattern,
+            not_empty: entity.not_empty,
+            default_value: entity.default_value,
         }
     }
 }
@@ -136,6 +201,13 @@ pub struct UpdateDtoFieldDto {
     pub is_list: bool,
     pub enum_name: Option<String>,
     pub enum_values: Vec<String>,
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub min_length: Option<i64>,
+    pub max_length: Option<i64>,
+    pub pattern: Option<String>,
+    pub not_empty: bool,
+    pub default_value: Option<String>,
 }
 
 impl From<UpdateDtoFieldDto> for DtoField {
@@ -150,6 +222,14 @@ impl From<UpdateDtoFieldDto> for DtoField {
             is_list: dto.is_list,
             enum_name: dto.enum_name,
             enum_values: dto.enum_values,
+            min_value: dto.min_value,
+            max_value: dto.max_value,
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern,
+            not_empty: dto.not_empty,
+            default_value: dto.default_value,
+            dto: Default::default(),
         }
     }
 }
@@ -166,6 +246,14 @@ impl From<&UpdateDtoFieldDto> for DtoField {
             is_list: dto.is_list,
             enum_name: dto.enum_name.clone(),
             enum_values: dto.enum_values.clone(),
+            min_value: dto.min_value.clone(),
+            max_value: dto.max_value.clone(),
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern.clone(),
+            not_empty: dto.not_empty,
+            default_value: dto.default_value.clone(),
+            dto: Default::default(),
         }
     }
 }
@@ -182,6 +270,13 @@ impl From<DtoField> for UpdateDtoFieldDto {
             is_list: entity.is_list,
             enum_name: entity.enum_name,
             enum_values: entity.enum_values,
+            min_value: entity.min_value,
+            max_value: entity.max_value,
+            min_length: entity.min_length,
+            max_length: entity.max_length,
+            pattern: entity.pattern,
+            not_empty: entity.not_empty,
+            default_value: entity.default_value,
         }
     }
 }
@@ -198,6 +293,21 @@ impl From<DtoFieldDto> for UpdateDtoFieldDto {
             is_list: dto.is_list,
             enum_name: dto.enum_name,
             enum_values: dto.enum_values,
+            min_value: dto.min_value,
+            max_value: dto.max_value,
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern,
+            not_empty: dto.not_empty,
+            default_value: dto.default_value,
         }
     }
 }
+pub use common::direct_access::dto_field::DtoFieldRelationshipField;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DtoFieldRelationshipDto {
+    pub id: EntityId,
+    pub field: DtoFieldRelationshipField,
+    pub right_ids: Vec<EntityId>,
+}
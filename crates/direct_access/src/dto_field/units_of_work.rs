@@ -3,6 +3,8 @@
 use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
+use common::direct_access::dto_field::DtoFieldQuery;
+use common::direct_access::dto_field::DtoFieldRelationshipField;
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
 use common::entities::DtoField;
@@ -191,6 +193,34 @@ impl use_cases::OwnedWriteUoW for DtoFieldWriteUoW {
     }
 }
 
+impl use_cases::WriteRelUoW<DtoFieldRelationshipField> for DtoFieldWriteUoW {
+    fn set_relationship(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        right_ids: &[EntityId],
+    ) -> Result<()> {
+        let transaction = self.transaction.as_ref().expect("Transaction not started");
+        let mut repo = repository_factory::write::create_dto_field_repository(transaction)?;
+        let mut event_buffer = self.event_buffer.borrow_mut();
+        repo.set_relationship(&mut event_buffer, id, field, right_ids)?;
+        Ok(())
+    }
+
+    fn move_relationship(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        ids_to_move: &[EntityId],
+        new_index: i32,
+    ) -> Result<Vec<EntityId>> {
+        let transaction = self.transaction.as_ref().expect("Transaction not started");
+        let mut repo = repository_factory::write::create_dto_field_repository(transaction)?;
+        let mut event_buffer = self.event_buffer.borrow_mut();
+        Ok(repo.move_relationship_ids(&mut event_buffer, id, field, ids_to_move, new_index)?)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Write UoW Factory
 // ---------------------------------------------------------------------------
@@ -223,6 +253,12 @@ impl use_cases::OwnedWriteUoWFactory for DtoFieldWriteUoWFactory {
     }
 }
 
+impl use_cases::WriteRelUoWFactory<DtoFieldRelationshipField> for DtoFieldWriteUoWFactory {
+    fn create(&self) -> Box<dyn use_cases::WriteRelUoW<DtoFieldRelationshipField>> {
+        Box::new(DtoFieldWriteUoW::new(&self.context, &self.event_hub))
+    }
+}
+
 // ===========================================================================
 // Read-Only UoW
 // ===========================================================================
@@ -259,6 +295,7 @@ impl QueryUnitOfWork for DtoFieldReadUoW {
 
 impl use_cases::ReadUoW for DtoFieldReadUoW {
     type Entity = DtoField;
+    type Query = DtoFieldQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<DtoField>> {
         let transaction = self.transaction.borrow();
@@ -289,6 +326,86 @@ impl use_cases::ReadUoW for DtoFieldReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &DtoFieldQuery) -> Result<Vec<DtoField>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_dto_field_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &DtoFieldQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_dto_field_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
+}
+
+impl use_cases::ReadRelUoW<DtoFieldRelationshipField> for DtoFieldReadUoW {
+    fn get_relationship(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<Vec<EntityId>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_dto_field_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.get_relationship(id, field)?)
+    }
+
+    fn get_relationship_many(
+        &self,
+        ids: &[EntityId],
+        field: &DtoFieldRelationshipField,
+    ) -> Result<std::collections::HashMap<EntityId, Vec<EntityId>>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_dto_field_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.get_relationship_many(ids, field)?)
+    }
+
+    fn get_relationship_count(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+    ) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_dto_field_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.get_relationship_count(id, field)?)
+    }
+
+    fn get_relationship_in_range(
+        &self,
+        id: &EntityId,
+        field: &DtoFieldRelationshipField,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<EntityId>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_dto_field_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.get_relationship_in_range(id, field, offset, limit)?)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -309,7 +426,14 @@ impl DtoFieldReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for DtoFieldReadUoWFactory {
     type Entity = DtoField;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = DtoField>> {
+    type Query = DtoFieldQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = DtoField, Query = DtoFieldQuery>> {
+        Box::new(DtoFieldReadUoW::new(&self.context))
+    }
+}
+
+impl use_cases::ReadRelUoWFactory<DtoFieldRelationshipField> for DtoFieldReadUoWFactory {
+    fn create(&self) -> Box<dyn use_cases::ReadRelUoW<DtoFieldRelationshipField>> {
         Box::new(DtoFieldReadUoW::new(&self.context))
     }
 }
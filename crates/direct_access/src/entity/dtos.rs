@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::entity::{EntityFilter, EntityQuery, EntitySortField};
 use common::entities::Entity;
 use common::types::EntityId;
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,8 @@ pub struct EntityDto {
     pub relationships: Vec<EntityId>,
     pub single_model: bool,
     pub undoable: bool,
+    pub undo_journal: bool,
+    pub source_file: String,
 }
 
 impl From<EntityDto> for Entity {
@@ -32,6 +35,8 @@ impl From<EntityDto> for Entity {
             relationships: dto.relationships,
             single_model: dto.single_model,
             undoable: dto.undoable,
+            undo_journal: dto.undo_journal,
+            source_file: dto.source_file,
         }
     }
 }
@@ -49,6 +54,8 @@ impl From<&EntityDto> for Entity {
             relationships: dto.relationships.clone(),
             single_model: dto.single_model,
             undoable: dto.undoable,
+            undo_journal: dto.undo_journal,
+            source_file: dto.source_file.clone(),
         }
     }
 }
@@ -66,6 +73,8 @@ impl From<Entity> for EntityDto {
             relationships: entity.relationships,
             single_model: entity.single_model,
             undoable: entity.undoable,
+            undo_journal: entity.undo_journal,
+            source_file: entity.source_file,
         }
     }
 }
@@ -81,6 +90,8 @@ pub struct CreateEntityDto {
     pub relationships: Vec<EntityId>,
     pub single_model: bool,
     pub undoable: bool,
+    pub undo_journal: bool,
+    pub source_file: String,
 }
 
 impl From<CreateEntityDto> for Entity {
@@ -96,6 +107,8 @@ impl From<CreateEntityDto> for Entity {
             relationships: dto.relationships,
             single_model: dto.single_model,
             undoable: dto.undoable,
+            undo_journal: dto.undo_journal,
+            source_file: dto.source_file,
         }
     }
 }
@@ -113,6 +126,8 @@ impl From<&CreateEntityDto> for Entity {
             relationships: dto.relationships.clone(),
             single_model: dto.single_model,
             undoable: dto.undoable,
+            undo_journal: dto.undo_journal,
+            source_file: dto.source_file.clone(),
         }
     }
 }
@@ -129,6 +144,8 @@ impl From<Entity> for CreateEntityDto {
             relationships: entity.relationships,
             single_model: entity.single_model,
             undoable: entity.undoable,
+            undo_journal: entity.undo_journal,
+            source_file: entity.source_file,
         }
     }
 }
@@ -141,6 +158,8 @@ pub struct UpdateEntityDto {
     pub only_for_heritage: bool,
     pub single_model: bool,
     pub undoable: bool,
+    pub undo_journal: bool,
+    pub source_file: String,
 }
 
 impl From<UpdateEntityDto> for Entity {
@@ -153,6 +172,8 @@ impl From<UpdateEntityDto> for Entity {
             only_for_heritage: dto.only_for_heritage,
             single_model: dto.single_model,
             undoable: dto.undoable,
+            undo_journal: dto.undo_journal,
+            source_file: dto.source_file,
             inherits_from: Default::default(),
             fields: Default::default(),
             relationships: Default::default(),
@@ -170,6 +191,8 @@ impl From<&UpdateEntityDto> for Entity {
             only_for_heritage: dto.only_for_heritage,
             single_model: dto.single_model,
             undoable: dto.undoable,
+            undo_journal: dto.undo_journal,
+            source_file: dto.source_file.clone(),
             inherits_from: Default::default(),
             fields: Default::default(),
             relationships: Default::default(),
@@ -187,6 +210,8 @@ impl From<Entity> for UpdateEntityDto {
             only_for_heritage: entity.only_for_heritage,
             single_model: entity.single_model,
             undoable: entity.undoable,
+            undo_journal: entity.undo_journal,
+            source_file: entity.source_file,
         }
     }
 }
@@ -201,6 +226,8 @@ impl From<EntityDto> for UpdateEntityDto {
             only_for_heritage: dto.only_for_heritage,
             single_model: dto.single_model,
             undoable: dto.undoable,
+            undo_journal: dto.undo_journal,
+            source_file: dto.source_file,
         }
     }
 }
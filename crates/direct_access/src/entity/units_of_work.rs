@@ -3,6 +3,7 @@
 use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
+use common::direct_access::entity::EntityQuery;
 use common::direct_access::entity::EntityRelationshipField;
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
@@ -294,6 +295,7 @@ impl QueryUnitOfWork for EntityReadUoW {
 
 impl use_cases::ReadUoW for EntityReadUoW {
     type Entity = Entity;
+    type Query = EntityQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<Entity>> {
         let transaction = self.transaction.borrow();
@@ -324,6 +326,26 @@ impl use_cases::ReadUoW for EntityReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &EntityQuery) -> Result<Vec<Entity>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_entity_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &EntityQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_entity_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<EntityRelationshipField> for EntityReadUoW {
@@ -404,7 +426,8 @@ impl EntityReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for EntityReadUoWFactory {
     type Entity = Entity;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Entity>> {
+    type Query = EntityQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Entity, Query = EntityQuery>> {
         Box::new(EntityReadUoW::new(&self.context))
     }
 }
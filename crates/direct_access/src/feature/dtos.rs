@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::feature::{FeatureFilter, FeatureQuery, FeatureSortField};
 use common::entities::Feature;
 use common::types::EntityId;
 use serde::{Deserialize, Serialize};
@@ -12,6 +13,8 @@ pub struct FeatureDto {
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub name: String,
     pub use_cases: Vec<EntityId>,
+    pub dtos: Vec<EntityId>,
+    pub source_file: String,
 }
 
 impl From<FeatureDto> for Feature {
@@ -22,6 +25,8 @@ impl From<FeatureDto> for Feature {
             updated_at: dto.updated_at,
             name: dto.name,
             use_cases: dto.use_cases,
+            dtos: dto.dtos,
+            source_file: dto.source_file,
         }
     }
 }
@@ -34,6 +39,8 @@ impl From<&FeatureDto> for Feature {
             updated_at: dto.updated_at,
             name: dto.name.clone(),
             use_cases: dto.use_cases.clone(),
+            dtos: dto.dtos.clone(),
+            source_file: dto.source_file.clone(),
         }
     }
 }
@@ -46,6 +53,8 @@ impl From<Feature> for FeatureDto {
             updated_at: entity.updated_at,
             name: entity.name,
             use_cases: entity.use_cases,
+            dtos: entity.dtos,
+            source_file: entity.source_file,
         }
     }
 }
@@ -56,6 +65,8 @@ pub struct CreateFeatureDto {
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub name: String,
     pub use_cases: Vec<EntityId>,
+    pub dtos: Vec<EntityId>,
+    pub source_file: String,
 }
 
 impl From<CreateFeatureDto> for Feature {
@@ -66,6 +77,8 @@ impl From<CreateFeatureDto> for Feature {
             updated_at: dto.updated_at,
             name: dto.name,
             use_cases: dto.use_cases,
+            dtos: dto.dtos,
+            source_file: dto.source_file,
         }
     }
 }
@@ -78,6 +91,8 @@ impl From<&CreateFeatureDto> for Feature {
             updated_at: dto.updated_at,
             name: dto.name.clone(),
             use_cases: dto.use_cases.clone(),
+            dtos: dto.dtos.clone(),
+            source_file: dto.source_file.clone(),
         }
     }
 }
@@ -89,6 +104,8 @@ impl From<Feature> for CreateFeatureDto {
             updated_at: entity.updated_at,
             name: entity.name,
             use_cases: entity.use_cases,
+            dtos: entity.dtos,
+            source_file: entity.source_file,
         }
     }
 }
@@ -98,6 +115,7 @@ pub struct UpdateFeatureDto {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub name: String,
+    pub source_file: String,
 }
 
 impl From<UpdateFeatureDto> for Feature {
@@ -107,7 +125,9 @@ impl From<UpdateFeatureDto> for Feature {
             created_at: dto.created_at,
             updated_at: dto.updated_at,
             name: dto.name,
+            source_file: dto.source_file,
             use_cases: Default::default(),
+            dtos: Default::default(),
         }
     }
 }
@@ -119,7 +139,9 @@ impl From<&UpdateFeatureDto> for Feature {
             created_at: dto.created_at,
             updated_at: dto.updated_at,
             name: dto.name.clone(),
+            source_file: dto.source_file.clone(),
             use_cases: Default::default(),
+            dtos: Default::default(),
         }
     }
 }
@@ -131,6 +153,7 @@ impl From<Feature> for UpdateFeatureDto {
             created_at: entity.created_at,
             updated_at: entity.updated_at,
             name: entity.name,
+            source_file: entity.source_file,
         }
     }
 }
@@ -142,6 +165,7 @@ impl From<FeatureDto> for UpdateFeatureDto {
             created_at: dto.created_at,
             updated_at: dto.updated_at,
             name: dto.name,
+            source_file: dto.source_file,
         }
     }
 }
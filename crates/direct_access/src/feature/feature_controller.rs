@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from entity_controller.tera
 
 use super::{
-    dtos::{CreateFeatureDto, FeatureDto, UpdateFeatureDto},
+    dtos::{CreateFeatureDto, FeatureDto, FeatureQuery, UpdateFeatureDto},
     units_of_work::{FeatureReadUoWFactory, FeatureWriteUoWFactory},
 };
 use crate::FeatureRelationshipDto;
@@ -102,6 +102,22 @@ pub fn get_multi(db_context: &DbContext, ids: &[EntityId]) -> Result<Vec<Option<
         .collect())
 }
 
+pub fn query(db_context: &DbContext, query: &FeatureQuery) -> Result<Vec<FeatureDto>> {
+    let uow_factory = FeatureReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    Ok(uc
+        .execute_query(query)?
+        .into_iter()
+        .map(|e| e.into())
+        .collect())
+}
+
+pub fn count(db_context: &DbContext, query: &FeatureQuery) -> Result<usize> {
+    let uow_factory = FeatureReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    uc.execute_count(query)
+}
+
 pub fn update(
     db_context: &DbContext,
     event_hub: &Arc<EventHub>,
@@ -478,7 +494,7 @@ mod tests {
         let mut ctx = TestContext::new();
         let created = create_one(&mut ctx);
         let rel_ids =
-            get_relationship(&ctx.db, &created.id, &FeatureRelationshipField::UseCases).unwrap();
+            get_relationship(&ctx.db, &created.id, &FeatureRelationshipField::Dtos).unwrap();
         assert!(rel_ids.is_empty());
     }
 
@@ -491,8 +507,7 @@ mod tests {
         let mut ctx = TestContext::new();
         let created = create_one(&mut ctx);
         let count =
-            get_relationship_count(&ctx.db, &created.id, &FeatureRelationshipField::UseCases)
-                .unwrap();
+            get_relationship_count(&ctx.db, &created.id, &FeatureRelationshipField::Dtos).unwrap();
         assert_eq!(count, 0);
     }
 }
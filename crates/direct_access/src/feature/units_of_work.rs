@@ -3,6 +3,7 @@
 use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
+use common::direct_access::feature::FeatureQuery;
 use common::direct_access::feature::FeatureRelationshipField;
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
@@ -294,6 +295,7 @@ impl QueryUnitOfWork for FeatureReadUoW {
 
 impl use_cases::ReadUoW for FeatureReadUoW {
     type Entity = Feature;
+    type Query = FeatureQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<Feature>> {
         let transaction = self.transaction.borrow();
@@ -324,6 +326,26 @@ impl use_cases::ReadUoW for FeatureReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &FeatureQuery) -> Result<Vec<Feature>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_feature_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &FeatureQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_feature_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<FeatureRelationshipField> for FeatureReadUoW {
@@ -404,7 +426,8 @@ impl FeatureReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for FeatureReadUoWFactory {
     type Entity = Feature;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Feature>> {
+    type Query = FeatureQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Feature, Query = FeatureQuery>> {
         Box::new(FeatureReadUoW::new(&self.context))
     }
 }
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::field::{FieldFilter, FieldQuery, FieldSortField};
 use common::entities::Field;
 pub use common::entities::FieldRelationshipType;
 pub use common::entities::FieldType;
@@ -23,6 +24,16 @@ pub struct FieldDto {
     pub list_model_displayed_field: Option<String>,
     pub enum_name: Option<String>,
     pub enum_values: Vec<String>,
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub min_length: Option<i64>,
+    pub max_length: Option<i64>,
+    pub pattern: Option<String>,
+    pub not_empty: bool,
+    pub unique_in_parent: bool,
+    pub indexed: bool,
+    pub unique: bool,
+    pub default_value: Option<String>,
 }
 
 impl From<FieldDto> for Field {
@@ -42,6 +53,16 @@ impl From<FieldDto> for Field {
             list_model_displayed_field: dto.list_model_displayed_field,
             enum_name: dto.enum_name,
             enum_values: dto.enum_values,
+            min_value: dto.min_value,
+            max_value: dto.max_value,
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern,
+            not_empty: dto.not_empty,
+            unique_in_parent: dto.unique_in_parent,
+            indexed: dto.indexed,
+            unique: dto.unique,
+            default_value: dto.default_value,
         }
     }
 }
@@ -63,6 +84,16 @@ impl From<&FieldDto> for Field {
             list_model_displayed_field: dto.list_model_displayed_field.clone(),
             enum_name: dto.enum_name.clone(),
             enum_values: dto.enum_values.clone(),
+            min_value: dto.min_value.clone(),
+            max_value: dto.max_value.clone(),
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern.clone(),
+            not_empty: dto.not_empty,
+            unique_in_parent: dto.unique_in_parent,
+            indexed: dto.indexed,
+            unique: dto.unique,
+            default_value: dto.default_value.clone(),
         }
     }
 }
@@ -84,6 +115,16 @@ impl From<Field> for FieldDto {
             list_model_displayed_field: entity.list_model_displayed_field,
             enum_name: entity.enum_name,
             enum_values: entity.enum_values,
+            min_value: entity.min_value,
+            max_value: entity.max_value,
+            min_length: entity.min_length,
+            max_length: entity.max_length,
+            pattern: entity.pattern,
+            not_empty: entity.not_empty,
+            unique_in_parent: entity.unique_in_parent,
+            indexed: entity.indexed,
+            unique: entity.unique,
+            default_value: entity.default_value,
         }
     }
 }
@@ -103,6 +144,16 @@ pub struct CreateFieldDto {
     pub list_model_displayed_field: Option<String>,
     pub enum_name: Option<String>,
     pub enum_values: Vec<String>,
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub min_length: Option<i64>,
+    pub max_length: Option<i64>,
+    pub pattern: Option<String>,
+    pub not_empty: bool,
+    pub unique_in_parent: bool,
+    pub indexed: bool,
+    pub unique: bool,
+    pub default_value: Option<String>,
 }
 
 impl From<CreateFieldDto> for Field {
@@ -122,6 +173,16 @@ impl From<CreateFieldDto> for Field {
             list_model_displayed_field: dto.list_model_displayed_field,
             enum_name: dto.enum_name,
             enum_values: dto.enum_values,
+            min_value: dto.min_value,
+            max_value: dto.max_value,
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern,
+            not_empty: dto.not_empty,
+            unique_in_parent: dto.unique_in_parent,
+            indexed: dto.indexed,
+            unique: dto.unique,
+            default_value: dto.default_value,
         }
     }
 }
@@ -143,6 +204,16 @@ impl From<&CreateFieldDto> for Field {
             list_model_displayed_field: dto.list_model_displayed_field.clone(),
             enum_name: dto.enum_name.clone(),
             enum_values: dto.enum_values.clone(),
+            min_value: dto.min_value.clone(),
+            max_value: dto.max_value.clone(),
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern.clone(),
+            not_empty: dto.not_empty,
+            unique_in_parent: dto.unique_in_parent,
+            indexed: dto.indexed,
+            unique: dto.unique,
+            default_value: dto.default_value.clone(),
         }
     }
 }
@@ -163,6 +234,16 @@ impl From<Field> for CreateFieldDto {
             list_model_displayed_field: entity.list_model_displayed_field,
             enum_name: entity.enum_name,
             enum_values: entity.enum_values,
+            min_value: entity.min_value,
+            max_value: entity.max_value,
+            min_length: entity.min_length,
+            max_length: entity.max_length,
+            pattern: entity.pattern,
+            not_empty: entity.not_empty,
+            unique_in_parent: entity.unique_in_parent,
+            indexed: entity.indexed,
+            unique: entity.unique,
+            default_value: entity.default_value,
         }
     }
 }
@@ -181,6 +262,16 @@ pub struct UpdateFieldDto {
     pub list_model_displayed_field: Option<String>,
     pub enum_name: Option<String>,
     pub enum_values: Vec<String>,
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub min_length: Option<i64>,
+    pub max_length: Option<i64>,
+    pub pattern: Option<String>,
+    pub not_empty: bool,
+    pub unique_in_parent: bool,
+    pub indexed: bool,
+    pub unique: bool,
+    pub default_value: Option<String>,
 }
 
 impl From<UpdateFieldDto> for Field {
@@ -199,6 +290,16 @@ impl From<UpdateFieldDto> for Field {
             list_model_displayed_field: dto.list_model_displayed_field,
             enum_name: dto.enum_name,
             enum_values: dto.enum_values,
+            min_value: dto.min_value,
+            max_value: dto.max_value,
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern,
+            not_empty: dto.not_empty,
+            unique_in_parent: dto.unique_in_parent,
+            indexed: dto.indexed,
+            unique: dto.unique,
+            default_value: dto.default_value,
             entity: Default::default(),
         }
     }
@@ -220,6 +321,16 @@ impl From<&UpdateFieldDto> for Field {
             list_model_displayed_field: dto.list_model_displayed_field.clone(),
             enum_name: dto.enum_name.clone(),
             enum_values: dto.enum_values.clone(),
+            min_value: dto.min_value.clone(),
+            max_value: dto.max_value.clone(),
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern.clone(),
+            not_empty: dto.not_empty,
+            unique_in_parent: dto.unique_in_parent,
+            indexed: dto.indexed,
+            unique: dto.unique,
+            default_value: dto.default_value.clone(),
             entity: Default::default(),
         }
     }
@@ -241,6 +352,16 @@ impl From<Field> for UpdateFieldDto {
             list_model_displayed_field: entity.list_model_displayed_field,
             enum_name: entity.enum_name,
             enum_values: entity.enum_values,
+            min_value: entity.min_value,
+            max_value: entity.max_value,
+            min_length: entity.min_length,
+            max_length: entity.max_length,
+            pattern: entity.pattern,
+            not_empty: entity.not_empty,
+            unique_in_parent: entity.unique_in_parent,
+            indexed: entity.indexed,
+            unique: entity.unique,
+            default_value: entity.default_value,
         }
     }
 }
@@ -261,6 +382,16 @@ impl From<FieldDto> for UpdateFieldDto {
             list_model_displayed_field: dto.list_model_displayed_field,
             enum_name: dto.enum_name,
             enum_values: dto.enum_values,
+            min_value: dto.min_value,
+            max_value: dto.max_value,
+            min_length: dto.min_length,
+            max_length: dto.max_length,
+            pattern: dto.pattern,
+            not_empty: dto.not_empty,
+            unique_in_parent: dto.unique_in_parent,
+            indexed: dto.indexed,
+            unique: dto.unique,
+            default_value: dto.default_value,
         }
     }
 }
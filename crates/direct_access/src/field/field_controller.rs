@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from entity_controller.tera
 
 use super::{
-    dtos::{CreateFieldDto, FieldDto, UpdateFieldDto},
+    dtos::{CreateFieldDto, FieldDto, FieldQuery, UpdateFieldDto},
     units_of_work::{FieldReadUoWFactory, FieldWriteUoWFactory},
 };
 use crate::FieldRelationshipDto;
@@ -100,6 +100,22 @@ pub fn get_multi(db_context: &DbContext, ids: &[EntityId]) -> Result<Vec<Option<
         .collect())
 }
 
+pub fn query(db_context: &DbContext, query: &FieldQuery) -> Result<Vec<FieldDto>> {
+    let uow_factory = FieldReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    Ok(uc
+        .execute_query(query)?
+        .into_iter()
+        .map(|e| e.into())
+        .collect())
+}
+
+pub fn count(db_context: &DbContext, query: &FieldQuery) -> Result<usize> {
+    let uow_factory = FieldReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    uc.execute_count(query)
+}
+
 pub fn update(
     db_context: &DbContext,
     event_hub: &Arc<EventHub>,
@@ -3,6 +3,7 @@
 use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
+use common::direct_access::field::FieldQuery;
 use common::direct_access::field::FieldRelationshipField;
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
@@ -294,6 +295,7 @@ impl QueryUnitOfWork for FieldReadUoW {
 
 impl use_cases::ReadUoW for FieldReadUoW {
     type Entity = Field;
+    type Query = FieldQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<Field>> {
         let transaction = self.transaction.borrow();
@@ -324,6 +326,26 @@ impl use_cases::ReadUoW for FieldReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &FieldQuery) -> Result<Vec<Field>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_field_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &FieldQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_field_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<FieldRelationshipField> for FieldReadUoW {
@@ -404,7 +426,8 @@ impl FieldReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for FieldReadUoWFactory {
     type Entity = Field;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Field>> {
+    type Query = FieldQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Field, Query = FieldQuery>> {
         Box::new(FieldReadUoW::new(&self.context))
     }
 }
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::file::{FileFilter, FileQuery, FileSortField};
 use common::entities::File;
 pub use common::entities::FileNature;
 pub use common::entities::FileStatus;
@@ -3,6 +3,7 @@
 use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
+use common::direct_access::file::FileQuery;
 use common::direct_access::file::FileRelationshipField;
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
@@ -289,6 +290,7 @@ impl QueryUnitOfWork for FileReadUoW {
 
 impl use_cases::ReadUoW for FileReadUoW {
     type Entity = File;
+    type Query = FileQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<File>> {
         let transaction = self.transaction.borrow();
@@ -319,6 +321,26 @@ impl use_cases::ReadUoW for FileReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &FileQuery) -> Result<Vec<File>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_file_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &FileQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_file_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<FileRelationshipField> for FileReadUoW {
@@ -399,7 +421,8 @@ impl FileReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for FileReadUoWFactory {
     type Entity = File;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = File>> {
+    type Query = FileQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = File, Query = FileQuery>> {
         Box::new(FileReadUoW::new(&self.context))
     }
 }
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::global::{GlobalFilter, GlobalQuery, GlobalSortField};
 use common::entities::Global;
 use common::types::EntityId;
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,8 @@ pub struct GlobalDto {
     pub organisation_name: String,
     pub organisation_domain: String,
     pub prefix_path: String,
+    pub storage: String,
+    pub templates_path: String,
 }
 
 impl From<GlobalDto> for Global {
@@ -28,6 +31,8 @@ impl From<GlobalDto> for Global {
             organisation_name: dto.organisation_name,
             organisation_domain: dto.organisation_domain,
             prefix_path: dto.prefix_path,
+            storage: dto.storage,
+            templates_path: dto.templates_path,
         }
     }
 }
@@ -43,6 +48,8 @@ impl From<&GlobalDto> for Global {
             organisation_name: dto.organisation_name.clone(),
             organisation_domain: dto.organisation_domain.clone(),
             prefix_path: dto.prefix_path.clone(),
+            storage: dto.storage.clone(),
+            templates_path: dto.templates_path.clone(),
         }
     }
 }
@@ -58,6 +65,8 @@ impl From<Global> for GlobalDto {
             organisation_name: entity.organisation_name,
             organisation_domain: entity.organisation_domain,
             prefix_path: entity.prefix_path,
+            storage: entity.storage,
+            templates_path: entity.templates_path,
         }
     }
 }
@@ -71,6 +80,8 @@ pub struct CreateGlobalDto {
     pub organisation_name: String,
     pub organisation_domain: String,
     pub prefix_path: String,
+    pub storage: String,
+    pub templates_path: String,
 }
 
 impl From<CreateGlobalDto> for Global {
@@ -84,6 +95,8 @@ impl From<CreateGlobalDto> for Global {
             organisation_name: dto.organisation_name,
             organisation_domain: dto.organisation_domain,
             prefix_path: dto.prefix_path,
+            storage: dto.storage,
+            templates_path: dto.templates_path,
         }
     }
 }
@@ -99,6 +112,8 @@ impl From<&CreateGlobalDto> for Global {
             organisation_name: dto.organisation_name.clone(),
             organisation_domain: dto.organisation_domain.clone(),
             prefix_path: dto.prefix_path.clone(),
+            storage: dto.storage.clone(),
+            templates_path: dto.templates_path.clone(),
         }
     }
 }
@@ -113,6 +128,8 @@ impl From<Global> for CreateGlobalDto {
             organisation_name: entity.organisation_name,
             organisation_domain: entity.organisation_domain,
             prefix_path: entity.prefix_path,
+            storage: entity.storage,
+            templates_path: entity.templates_path,
         }
     }
 }
@@ -126,6 +143,8 @@ pub struct UpdateGlobalDto {
     pub organisation_name: String,
     pub organisation_domain: String,
     pub prefix_path: String,
+    pub storage: String,
+    pub templates_path: String,
 }
 
 impl From<UpdateGlobalDto> for Global {
@@ -139,6 +158,8 @@ impl From<UpdateGlobalDto> for Global {
             organisation_name: dto.organisation_name,
             organisation_domain: dto.organisation_domain,
             prefix_path: dto.prefix_path,
+            storage: dto.storage,
+            templates_path: dto.templates_path,
         }
     }
 }
@@ -154,6 +175,8 @@ impl From<&UpdateGlobalDto> for Global {
             organisation_name: dto.organisation_name.clone(),
             organisation_domain: dto.organisation_domain.clone(),
             prefix_path: dto.prefix_path.clone(),
+            storage: dto.storage.clone(),
+            templates_path: dto.templates_path.clone(),
         }
     }
 }
@@ -169,6 +192,8 @@ impl From<Global> for UpdateGlobalDto {
             organisation_name: entity.organisation_name,
             organisation_domain: entity.organisation_domain,
             prefix_path: entity.prefix_path,
+            storage: entity.storage,
+            templates_path: entity.templates_path,
         }
     }
 }
@@ -184,6 +209,8 @@ impl From<GlobalDto> for UpdateGlobalDto {
             organisation_name: dto.organisation_name,
             organisation_domain: dto.organisation_domain,
             prefix_path: dto.prefix_path,
+            storage: dto.storage,
+            templates_path: dto.templates_path,
         }
     }
 }
@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from entity_controller.tera
 
 use super::{
-    dtos::{CreateGlobalDto, GlobalDto, UpdateGlobalDto},
+    dtos::{CreateGlobalDto, GlobalDto, GlobalQuery, UpdateGlobalDto},
     units_of_work::{GlobalReadUoWFactory, GlobalWriteUoWFactory},
 };
 use anyhow::{Ok, Result};
@@ -100,6 +100,22 @@ pub fn get_multi(db_context: &DbContext, ids: &[EntityId]) -> Result<Vec<Option<
         .collect())
 }
 
+pub fn query(db_context: &DbContext, query: &GlobalQuery) -> Result<Vec<GlobalDto>> {
+    let uow_factory = GlobalReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    Ok(uc
+        .execute_query(query)?
+        .into_iter()
+        .map(|e| e.into())
+        .collect())
+}
+
+pub fn count(db_context: &DbContext, query: &GlobalQuery) -> Result<usize> {
+    let uow_factory = GlobalReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    uc.execute_count(query)
+}
+
 pub fn update(
     db_context: &DbContext,
     event_hub: &Arc<EventHub>,
@@ -3,6 +3,7 @@
 use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
+use common::direct_access::global::GlobalQuery;
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
 use common::entities::Global;
@@ -259,6 +260,7 @@ impl QueryUnitOfWork for GlobalReadUoW {
 
 impl use_cases::ReadUoW for GlobalReadUoW {
     type Entity = Global;
+    type Query = GlobalQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<Global>> {
         let transaction = self.transaction.borrow();
@@ -289,6 +291,26 @@ impl use_cases::ReadUoW for GlobalReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &GlobalQuery) -> Result<Vec<Global>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_global_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &GlobalQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_global_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -309,7 +331,8 @@ impl GlobalReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for GlobalReadUoWFactory {
     type Entity = Global;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Global>> {
+    type Query = GlobalQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Global, Query = GlobalQuery>> {
         Box::new(GlobalReadUoW::new(&self.context))
     }
 }
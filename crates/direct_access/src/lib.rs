@@ -43,6 +43,9 @@ pub mod relationship;
 // UserInterface
 pub mod user_interface;
 
+// CustomFile
+pub mod custom_file;
+
 // Re-exports for all project entities
 
 pub use root::dtos::*;
@@ -83,3 +86,9 @@ pub use relationship::relationship_controller;
 
 pub use user_interface::dtos::*;
 pub use user_interface::user_interface_controller;
+
+pub use custom_file::custom_file_controller;
+pub use custom_file::dtos::*;
+
+// Query building blocks shared by all entities
+pub use common::types::{Condition, SortOrder, TextCondition};
@@ -1,5 +1,8 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::relationship::{
+    RelationshipFilter, RelationshipQuery, RelationshipSortField,
+};
 pub use common::entities::Cardinality;
 pub use common::entities::Direction;
 pub use common::entities::Order;
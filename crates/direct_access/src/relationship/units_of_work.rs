@@ -3,6 +3,7 @@
 use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
+use common::direct_access::relationship::RelationshipQuery;
 use common::direct_access::relationship::RelationshipRelationshipField;
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
@@ -297,6 +298,7 @@ impl QueryUnitOfWork for RelationshipReadUoW {
 
 impl use_cases::ReadUoW for RelationshipReadUoW {
     type Entity = Relationship;
+    type Query = RelationshipQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<Relationship>> {
         let transaction = self.transaction.borrow();
@@ -327,6 +329,26 @@ impl use_cases::ReadUoW for RelationshipReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &RelationshipQuery) -> Result<Vec<Relationship>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_relationship_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &RelationshipQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_relationship_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<RelationshipRelationshipField> for RelationshipReadUoW {
@@ -407,7 +429,10 @@ impl RelationshipReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for RelationshipReadUoWFactory {
     type Entity = Relationship;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Relationship>> {
+    type Query = RelationshipQuery;
+    fn create(
+        &self,
+    ) -> Box<dyn use_cases::ReadUoW<Entity = Relationship, Query = RelationshipQuery>> {
         Box::new(RelationshipReadUoW::new(&self.context))
     }
 }
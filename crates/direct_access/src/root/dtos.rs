@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::root::{RootFilter, RootQuery, RootSortField};
 use common::entities::Root;
 use common::types::EntityId;
 use serde::{Deserialize, Serialize};
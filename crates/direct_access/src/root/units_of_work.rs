@@ -4,6 +4,7 @@ use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
 use common::direct_access::repository_factory;
+use common::direct_access::root::RootQuery;
 use common::direct_access::root::RootRelationshipField;
 use common::direct_access::use_cases;
 use common::entities::Root;
@@ -259,6 +260,7 @@ impl QueryUnitOfWork for RootReadUoW {
 
 impl use_cases::ReadUoW for RootReadUoW {
     type Entity = Root;
+    type Query = RootQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<Root>> {
         let transaction = self.transaction.borrow();
@@ -289,6 +291,26 @@ impl use_cases::ReadUoW for RootReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &RootQuery) -> Result<Vec<Root>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_root_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &RootQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_root_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<RootRelationshipField> for RootReadUoW {
@@ -369,7 +391,8 @@ impl RootReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for RootReadUoWFactory {
     type Entity = Root;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Root>> {
+    type Query = RootQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Root, Query = RootQuery>> {
         Box::new(RootReadUoW::new(&self.context))
     }
 }
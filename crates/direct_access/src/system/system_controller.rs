@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from entity_controller.tera
 
 use super::{
-    dtos::{CreateSystemDto, SystemDto, UpdateSystemDto},
+    dtos::{CreateSystemDto, SystemDto, SystemQuery, UpdateSystemDto},
     units_of_work::{SystemReadUoWFactory, SystemWriteUoWFactory},
 };
 use crate::SystemRelationshipDto;
@@ -88,6 +88,22 @@ pub fn get_multi(db_context: &DbContext, ids: &[EntityId]) -> Result<Vec<Option<
         .collect())
 }
 
+pub fn query(db_context: &DbContext, query: &SystemQuery) -> Result<Vec<SystemDto>> {
+    let uow_factory = SystemReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    Ok(uc
+        .execute_query(query)?
+        .into_iter()
+        .map(|e| e.into())
+        .collect())
+}
+
+pub fn count(db_context: &DbContext, query: &SystemQuery) -> Result<usize> {
+    let uow_factory = SystemReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    uc.execute_count(query)
+}
+
 pub fn update(
     db_context: &DbContext,
     event_hub: &Arc<EventHub>,
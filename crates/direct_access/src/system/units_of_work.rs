@@ -4,6 +4,7 @@ use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
 use common::direct_access::repository_factory;
+use common::direct_access::system::SystemQuery;
 use common::direct_access::system::SystemRelationshipField;
 use common::direct_access::use_cases;
 use common::entities::System;
@@ -294,6 +295,7 @@ impl QueryUnitOfWork for SystemReadUoW {
 
 impl use_cases::ReadUoW for SystemReadUoW {
     type Entity = System;
+    type Query = SystemQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<System>> {
         let transaction = self.transaction.borrow();
@@ -324,6 +326,26 @@ impl use_cases::ReadUoW for SystemReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &SystemQuery) -> Result<Vec<System>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_system_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &SystemQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_system_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<SystemRelationshipField> for SystemReadUoW {
@@ -404,7 +426,8 @@ impl SystemReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for SystemReadUoWFactory {
     type Entity = System;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = System>> {
+    type Query = SystemQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = System, Query = SystemQuery>> {
         Box::new(SystemReadUoW::new(&self.context))
     }
 }
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::use_case::{UseCaseFilter, UseCaseQuery, UseCaseSortField};
 use common::entities::UseCase;
 use common::types::EntityId;
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,8 @@ pub struct UseCaseDto {
     pub long_operation: bool,
     pub dto_in: Option<EntityId>,
     pub dto_out: Option<EntityId>,
+    pub shared_dto_in: Option<EntityId>,
+    pub shared_dto_out: Option<EntityId>,
 }
 
 impl From<UseCaseDto> for UseCase {
@@ -32,6 +35,8 @@ impl From<UseCaseDto> for UseCase {
             long_operation: dto.long_operation,
             dto_in: dto.dto_in,
             dto_out: dto.dto_out,
+            shared_dto_in: dto.shared_dto_in,
+            shared_dto_out: dto.shared_dto_out,
         }
     }
 }
@@ -49,6 +54,8 @@ impl From<&UseCaseDto> for UseCase {
             long_operation: dto.long_operation,
             dto_in: dto.dto_in,
             dto_out: dto.dto_out,
+            shared_dto_in: dto.shared_dto_in,
+            shared_dto_out: dto.shared_dto_out,
         }
     }
 }
@@ -66,6 +73,8 @@ impl From<UseCase> for UseCaseDto {
             long_operation: entity.long_operation,
             dto_in: entity.dto_in,
             dto_out: entity.dto_out,
+            shared_dto_in: entity.shared_dto_in,
+            shared_dto_out: entity.shared_dto_out,
         }
     }
 }
@@ -81,6 +90,8 @@ pub struct CreateUseCaseDto {
     pub long_operation: bool,
     pub dto_in: Option<EntityId>,
     pub dto_out: Option<EntityId>,
+    pub shared_dto_in: Option<EntityId>,
+    pub shared_dto_out: Option<EntityId>,
 }
 
 impl From<CreateUseCaseDto> for UseCase {
@@ -96,6 +107,8 @@ impl From<CreateUseCaseDto> for UseCase {
             long_operation: dto.long_operation,
             dto_in: dto.dto_in,
             dto_out: dto.dto_out,
+            shared_dto_in: dto.shared_dto_in,
+            shared_dto_out: dto.shared_dto_out,
         }
     }
 }
@@ -113,6 +126,8 @@ impl From<&CreateUseCaseDto> for UseCase {
             long_operation: dto.long_operation,
             dto_in: dto.dto_in,
             dto_out: dto.dto_out,
+            shared_dto_in: dto.shared_dto_in,
+            shared_dto_out: dto.shared_dto_out,
         }
     }
 }
@@ -129,6 +144,8 @@ impl From<UseCase> for CreateUseCaseDto {
             long_operation: entity.long_operation,
             dto_in: entity.dto_in,
             dto_out: entity.dto_out,
+            shared_dto_in: entity.shared_dto_in,
+            shared_dto_out: entity.shared_dto_out,
         }
     }
 }
@@ -156,6 +173,8 @@ impl From<UpdateUseCaseDto> for UseCase {
             entities: Default::default(),
             dto_in: Default::default(),
             dto_out: Default::default(),
+            shared_dto_in: Default::default(),
+            shared_dto_out: Default::default(),
         }
     }
 }
@@ -173,6 +192,8 @@ impl From<&UpdateUseCaseDto> for UseCase {
             entities: Default::default(),
             dto_in: Default::default(),
             dto_out: Default::default(),
+            shared_dto_in: Default::default(),
+            shared_dto_out: Default::default(),
         }
     }
 }
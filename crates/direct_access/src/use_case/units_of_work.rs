@@ -4,6 +4,7 @@ use anyhow::{Ok, Result};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
 use common::direct_access::repository_factory;
+use common::direct_access::use_case::UseCaseQuery;
 use common::direct_access::use_case::UseCaseRelationshipField;
 use common::direct_access::use_cases;
 use common::entities::UseCase;
@@ -294,6 +295,7 @@ impl QueryUnitOfWork for UseCaseReadUoW {
 
 impl use_cases::ReadUoW for UseCaseReadUoW {
     type Entity = UseCase;
+    type Query = UseCaseQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<UseCase>> {
         let transaction = self.transaction.borrow();
@@ -324,6 +326,26 @@ impl use_cases::ReadUoW for UseCaseReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &UseCaseQuery) -> Result<Vec<UseCase>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_use_case_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &UseCaseQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_use_case_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<UseCaseRelationshipField> for UseCaseReadUoW {
@@ -404,7 +426,8 @@ impl UseCaseReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for UseCaseReadUoWFactory {
     type Entity = UseCase;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = UseCase>> {
+    type Query = UseCaseQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = UseCase, Query = UseCaseQuery>> {
         Box::new(UseCaseReadUoW::new(&self.context))
     }
 }
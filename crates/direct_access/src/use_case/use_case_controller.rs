@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from entity_controller.tera
 
 use super::{
-    dtos::{CreateUseCaseDto, UpdateUseCaseDto, UseCaseDto},
+    dtos::{CreateUseCaseDto, UpdateUseCaseDto, UseCaseDto, UseCaseQuery},
     units_of_work::{UseCaseReadUoWFactory, UseCaseWriteUoWFactory},
 };
 use crate::UseCaseRelationshipDto;
@@ -102,6 +102,22 @@ pub fn get_multi(db_context: &DbContext, ids: &[EntityId]) -> Result<Vec<Option<
         .collect())
 }
 
+pub fn query(db_context: &DbContext, query: &UseCaseQuery) -> Result<Vec<UseCaseDto>> {
+    let uow_factory = UseCaseReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    Ok(uc
+        .execute_query(query)?
+        .into_iter()
+        .map(|e| e.into())
+        .collect())
+}
+
+pub fn count(db_context: &DbContext, query: &UseCaseQuery) -> Result<usize> {
+    let uow_factory = UseCaseReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    uc.execute_count(query)
+}
+
 pub fn update(
     db_context: &DbContext,
     event_hub: &Arc<EventHub>,
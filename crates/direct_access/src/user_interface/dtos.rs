@@ -1,5 +1,8 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::user_interface::{
+    UserInterfaceFilter, UserInterfaceQuery, UserInterfaceSortField,
+};
 use common::entities::UserInterface;
 use common::types::EntityId;
 use serde::{Deserialize, Serialize};
@@ -5,6 +5,7 @@ use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
+use common::direct_access::user_interface::UserInterfaceQuery;
 use common::entities::UserInterface;
 use common::event::{AllEvent, DirectAccessEntity, Event, EventBuffer, EventHub, Origin};
 use common::snapshot::EntityTreeSnapshot;
@@ -262,6 +263,7 @@ impl QueryUnitOfWork for UserInterfaceReadUoW {
 
 impl use_cases::ReadUoW for UserInterfaceReadUoW {
     type Entity = UserInterface;
+    type Query = UserInterfaceQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<UserInterface>> {
         let transaction = self.transaction.borrow();
@@ -292,6 +294,26 @@ impl use_cases::ReadUoW for UserInterfaceReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &UserInterfaceQuery) -> Result<Vec<UserInterface>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_user_interface_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &UserInterfaceQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_user_interface_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -312,7 +334,10 @@ impl UserInterfaceReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for UserInterfaceReadUoWFactory {
     type Entity = UserInterface;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = UserInterface>> {
+    type Query = UserInterfaceQuery;
+    fn create(
+        &self,
+    ) -> Box<dyn use_cases::ReadUoW<Entity = UserInterface, Query = UserInterfaceQuery>> {
         Box::new(UserInterfaceReadUoW::new(&self.context))
     }
 }
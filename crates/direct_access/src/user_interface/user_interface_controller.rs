@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from entity_controller.tera
 
 use super::{
-    dtos::{CreateUserInterfaceDto, UpdateUserInterfaceDto, UserInterfaceDto},
+    dtos::{CreateUserInterfaceDto, UpdateUserInterfaceDto, UserInterfaceDto, UserInterfaceQuery},
     units_of_work::{UserInterfaceReadUoWFactory, UserInterfaceWriteUoWFactory},
 };
 use anyhow::{Ok, Result};
@@ -103,6 +103,22 @@ pub fn get_multi(
         .collect())
 }
 
+pub fn query(db_context: &DbContext, query: &UserInterfaceQuery) -> Result<Vec<UserInterfaceDto>> {
+    let uow_factory = UserInterfaceReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    Ok(uc
+        .execute_query(query)?
+        .into_iter()
+        .map(|e| e.into())
+        .collect())
+}
+
+pub fn count(db_context: &DbContext, query: &UserInterfaceQuery) -> Result<usize> {
+    let uow_factory = UserInterfaceReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    uc.execute_count(query)
+}
+
 pub fn update(
     db_context: &DbContext,
     event_hub: &Arc<EventHub>,
@@ -1,5 +1,6 @@
 // Generated by Qleany v1.7.0 from entity_dtos.tera
 
+pub use common::direct_access::workspace::{WorkspaceFilter, WorkspaceQuery, WorkspaceSortField};
 use common::entities::Workspace;
 use common::types::EntityId;
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,11 @@ pub struct WorkspaceDto {
     pub entities: Vec<EntityId>,
     pub features: Vec<EntityId>,
     pub user_interface: EntityId,
+    pub custom_files: Vec<EntityId>,
+    pub includes: Vec<String>,
+    pub manifest_text: String,
+    pub included_files: Vec<String>,
+    pub included_texts: Vec<String>,
 }
 
 impl From<WorkspaceDto> for Workspace {
@@ -28,6 +34,11 @@ impl From<WorkspaceDto> for Workspace {
             entities: dto.entities,
             features: dto.features,
             user_interface: dto.user_interface,
+            custom_files: dto.custom_files,
+            includes: dto.includes,
+            manifest_text: dto.manifest_text,
+            included_files: dto.included_files,
+            included_texts: dto.included_texts,
         }
     }
 }
@@ -43,6 +54,11 @@ impl From<&WorkspaceDto> for Workspace {
             entities: dto.entities.clone(),
             features: dto.features.clone(),
             user_interface: dto.user_interface,
+            custom_files: dto.custom_files.clone(),
+            includes: dto.includes.clone(),
+            manifest_text: dto.manifest_text.clone(),
+            included_files: dto.included_files.clone(),
+            included_texts: dto.included_texts.clone(),
         }
     }
 }
@@ -58,6 +74,11 @@ impl From<Workspace> for WorkspaceDto {
             entities: entity.entities,
             features: entity.features,
             user_interface: entity.user_interface,
+            custom_files: entity.custom_files,
+            includes: entity.includes,
+            manifest_text: entity.manifest_text,
+            included_files: entity.included_files,
+            included_texts: entity.included_texts,
         }
     }
 }
@@ -71,6 +92,11 @@ pub struct CreateWorkspaceDto {
     pub entities: Vec<EntityId>,
     pub features: Vec<EntityId>,
     pub user_interface: EntityId,
+    pub custom_files: Vec<EntityId>,
+    pub includes: Vec<String>,
+    pub manifest_text: String,
+    pub included_files: Vec<String>,
+    pub included_texts: Vec<String>,
 }
 
 impl From<CreateWorkspaceDto> for Workspace {
@@ -84,6 +110,11 @@ impl From<CreateWorkspaceDto> for Workspace {
             entities: dto.entities,
             features: dto.features,
             user_interface: dto.user_interface,
+            custom_files: dto.custom_files,
+            includes: dto.includes,
+            manifest_text: dto.manifest_text,
+            included_files: dto.included_files,
+            included_texts: dto.included_texts,
         }
     }
 }
@@ -99,6 +130,11 @@ impl From<&CreateWorkspaceDto> for Workspace {
             entities: dto.entities.clone(),
             features: dto.features.clone(),
             user_interface: dto.user_interface,
+            custom_files: dto.custom_files.clone(),
+            includes: dto.includes.clone(),
+            manifest_text: dto.manifest_text.clone(),
+            included_files: dto.included_files.clone(),
+            included_texts: dto.included_texts.clone(),
         }
     }
 }
@@ -113,6 +149,11 @@ impl From<Workspace> for CreateWorkspaceDto {
             entities: entity.entities,
             features: entity.features,
             user_interface: entity.user_interface,
+            custom_files: entity.custom_files,
+            includes: entity.includes,
+            manifest_text: entity.manifest_text,
+            included_files: entity.included_files,
+            included_texts: entity.included_texts,
         }
     }
 }
@@ -122,6 +163,10 @@ pub struct UpdateWorkspaceDto {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub manifest_absolute_path: String,
+    pub includes: Vec<String>,
+    pub manifest_text: String,
+    pub included_files: Vec<String>,
+    pub included_texts: Vec<String>,
 }
 
 impl From<UpdateWorkspaceDto> for Workspace {
@@ -131,10 +176,15 @@ impl From<UpdateWorkspaceDto> for Workspace {
             created_at: dto.created_at,
             updated_at: dto.updated_at,
             manifest_absolute_path: dto.manifest_absolute_path,
+            includes: dto.includes,
+            manifest_text: dto.manifest_text,
+            included_files: dto.included_files,
+            included_texts: dto.included_texts,
             global: Default::default(),
             entities: Default::default(),
             features: Default::default(),
             user_interface: Default::default(),
+            custom_files: Default::default(),
         }
     }
 }
@@ -146,10 +196,15 @@ impl From<&UpdateWorkspaceDto> for Workspace {
             created_at: dto.created_at,
             updated_at: dto.updated_at,
             manifest_absolute_path: dto.manifest_absolute_path.clone(),
+            includes: dto.includes.clone(),
+            manifest_text: dto.manifest_text.clone(),
+            included_files: dto.included_files.clone(),
+            included_texts: dto.included_texts.clone(),
             global: Default::default(),
             entities: Default::default(),
             features: Default::default(),
             user_interface: Default::default(),
+            custom_files: Default::default(),
         }
     }
 }
@@ -161,6 +216,10 @@ impl From<Workspace> for UpdateWorkspaceDto {
             created_at: entity.created_at,
             updated_at: entity.updated_at,
             manifest_absolute_path: entity.manifest_absolute_path,
+            includes: entity.includes,
+            manifest_text: entity.manifest_text,
+            included_files: entity.included_files,
+            included_texts: entity.included_texts,
         }
     }
 }
@@ -172,6 +231,10 @@ impl From<WorkspaceDto> for UpdateWorkspaceDto {
             created_at: dto.created_at,
             updated_at: dto.updated_at,
             manifest_absolute_path: dto.manifest_absolute_path,
+            includes: dto.includes,
+            manifest_text: dto.manifest_text,
+            included_files: dto.included_files,
+            included_texts: dto.included_texts,
         }
     }
 }
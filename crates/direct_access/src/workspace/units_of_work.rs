@@ -5,6 +5,7 @@ use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::database::{db_context::DbContext, transactions::Transaction};
 use common::direct_access::repository_factory;
 use common::direct_access::use_cases;
+use common::direct_access::workspace::WorkspaceQuery;
 use common::direct_access::workspace::WorkspaceRelationshipField;
 use common::entities::Workspace;
 use common::event::{AllEvent, DirectAccessEntity, Event, EventBuffer, EventHub, Origin};
@@ -294,6 +295,7 @@ impl QueryUnitOfWork for WorkspaceReadUoW {
 
 impl use_cases::ReadUoW for WorkspaceReadUoW {
     type Entity = Workspace;
+    type Query = WorkspaceQuery;
 
     fn get(&self, id: &EntityId) -> Result<Option<Workspace>> {
         let transaction = self.transaction.borrow();
@@ -324,6 +326,26 @@ impl use_cases::ReadUoW for WorkspaceReadUoW {
         )?;
         Ok(repo.get_all()?)
     }
+
+    fn query(&self, query: &WorkspaceQuery) -> Result<Vec<Workspace>> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_workspace_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.query(query)?)
+    }
+
+    fn count(&self, query: &WorkspaceQuery) -> Result<usize> {
+        let transaction = self.transaction.borrow();
+        let repo = repository_factory::read::create_workspace_repository(
+            transaction
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active transaction"))?,
+        )?;
+        Ok(repo.count(query)?)
+    }
 }
 
 impl use_cases::ReadRelUoW<WorkspaceRelationshipField> for WorkspaceReadUoW {
@@ -404,7 +426,8 @@ impl WorkspaceReadUoWFactory {
 
 impl use_cases::ReadUoWFactory for WorkspaceReadUoWFactory {
     type Entity = Workspace;
-    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Workspace>> {
+    type Query = WorkspaceQuery;
+    fn create(&self) -> Box<dyn use_cases::ReadUoW<Entity = Workspace, Query = WorkspaceQuery>> {
         Box::new(WorkspaceReadUoW::new(&self.context))
     }
 }
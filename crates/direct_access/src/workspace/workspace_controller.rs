@@ -1,7 +1,7 @@
 // Generated by Qleany v1.7.0 from entity_controller.tera
 
 use super::{
-    dtos::{CreateWorkspaceDto, UpdateWorkspaceDto, WorkspaceDto},
+    dtos::{CreateWorkspaceDto, UpdateWorkspaceDto, WorkspaceDto, WorkspaceQuery},
     units_of_work::{WorkspaceReadUoWFactory, WorkspaceWriteUoWFactory},
 };
 use crate::WorkspaceRelationshipDto;
@@ -102,6 +102,22 @@ pub fn get_multi(db_context: &DbContext, ids: &[EntityId]) -> Result<Vec<Option<
         .collect())
 }
 
+pub fn query(db_context: &DbContext, query: &WorkspaceQuery) -> Result<Vec<WorkspaceDto>> {
+    let uow_factory = WorkspaceReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    Ok(uc
+        .execute_query(query)?
+        .into_iter()
+        .map(|e| e.into())
+        .collect())
+}
+
+pub fn count(db_context: &DbContext, query: &WorkspaceQuery) -> Result<usize> {
+    let uow_factory = WorkspaceReadUoWFactory::new(db_context);
+    let uc = use_cases::GetUseCase::new(uow_factory);
+    uc.execute_count(query)
+}
+
 pub fn update(
     db_context: &DbContext,
     event_hub: &Arc<EventHub>,
@@ -468,8 +484,12 @@ mod tests {
     fn test_get_relationship_default() {
         let mut ctx = TestContext::new();
         let created = create_one(&mut ctx);
-        let rel_ids =
-            get_relationship(&ctx.db, &created.id, &WorkspaceRelationshipField::Entities).unwrap();
+        let rel_ids = get_relationship(
+            &ctx.db,
+            &created.id,
+            &WorkspaceRelationshipField::CustomFiles,
+        )
+        .unwrap();
         assert!(rel_ids.is_empty());
     }
 
@@ -481,9 +501,12 @@ mod tests {
     fn test_get_relationship_count_default() {
         let mut ctx = TestContext::new();
         let created = create_one(&mut ctx);
-        let count =
-            get_relationship_count(&ctx.db, &created.id, &WorkspaceRelationshipField::Entities)
-                .unwrap();
+        let count = get_relationship_count(
+            &ctx.db,
+            &created.id,
+            &WorkspaceRelationshipField::CustomFiles,
+        )
+        .unwrap();
         assert_eq!(count, 0);
     }
 }
@@ -11,3 +11,12 @@ pub struct GetDiffDto {
 pub struct GetDiffReturnDto {
     pub diff_text: String,
 }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeFileDto {
+    pub file_id: u64,
+}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeFileReturnDto {
+    pub merged_code: String,
+    pub conflict_count: u64,
+}
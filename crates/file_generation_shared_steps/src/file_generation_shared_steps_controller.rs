@@ -3,15 +3,20 @@
 
 use crate::GetDiffDto;
 use crate::GetDiffReturnDto;
+use crate::MergeFileDto;
+use crate::MergeFileReturnDto;
 use crate::units_of_work::fill_status_in_files_uow::FillStatusInFilesUnitOfWorkFactory;
 use crate::units_of_work::get_file_diff_uow::GetFileDiffUnitOfWorkFactory;
+use crate::units_of_work::merge_file_uow::MergeFileUnitOfWorkFactory;
 use crate::use_cases::fill_status_in_files_uc::FillStatusInFilesUseCase;
 use crate::use_cases::get_file_diff_uc::GetFileDiffUseCase;
+use crate::use_cases::merge_file_uc::MergeFileUseCase;
 use anyhow::Result;
 use common::event::{Event, Origin};
 
 use common::event::FileGenerationSharedStepsEvent::FillStatusInFiles;
 use common::event::FileGenerationSharedStepsEvent::GetFileDiff;
+use common::event::FileGenerationSharedStepsEvent::MergeFile;
 
 use common::{database::db_context::DbContext, event::EventHub};
 use std::sync::Arc;
@@ -45,3 +50,19 @@ pub fn get_file_diff(
     });
     Ok(return_dto)
 }
+
+pub fn merge_file(
+    db_context: &DbContext,
+    event_hub: &Arc<EventHub>,
+    dto: &MergeFileDto,
+) -> Result<MergeFileReturnDto> {
+    let uow_context = MergeFileUnitOfWorkFactory::new(db_context);
+    let mut uc = MergeFileUseCase::new(Box::new(uow_context));
+    let return_dto = uc.execute(dto)?;
+    event_hub.send_event(Event {
+        origin: Origin::FileGenerationSharedSteps(MergeFile),
+        ids: vec![],
+        data: None,
+    });
+    Ok(return_dto)
+}
@@ -3,3 +3,4 @@
 
 pub(crate) mod fill_status_in_files_uow;
 pub(crate) mod get_file_diff_uow;
+pub(crate) mod merge_file_uow;
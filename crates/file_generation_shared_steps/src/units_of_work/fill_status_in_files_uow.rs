@@ -92,13 +92,7 @@ impl CommandUnitOfWork for FillStatusInFilesUnitOfWork {
     }
 }
 
-#[macros::uow_action(entity = "Root", action = "GetAll")]
-#[macros::uow_action(entity = "Root", action = "GetRelationship")]
-#[macros::uow_action(entity = "Workspace", action = "Get")]
-#[macros::uow_action(entity = "System", action = "Get")]
-#[macros::uow_action(entity = "System", action = "GetRelationship")]
-#[macros::uow_action(entity = "File", action = "GetMulti")]
-#[macros::uow_action(entity = "File", action = "UpdateMulti")]
+#[macros::uow_impl]
 impl FillStatusInFilesUnitOfWorkTrait for FillStatusInFilesUnitOfWork {}
 
 pub struct FillStatusInFilesUnitOfWorkFactory {
@@ -43,10 +43,7 @@ impl QueryUnitOfWork for GetFileDiffUnitOfWork {
     }
 }
 
-#[macros::uow_action(entity = "Root", action = "GetAllRO")]
-#[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
-#[macros::uow_action(entity = "Workspace", action = "GetRO")]
-#[macros::uow_action(entity = "File", action = "GetRO")]
+#[macros::uow_impl]
 impl GetFileDiffUnitOfWorkTrait for GetFileDiffUnitOfWork {}
 
 pub struct GetFileDiffUnitOfWorkFactory {
@@ -0,0 +1,63 @@
+// Generated by Qleany. Edit at your own risk! Be careful when regenerating this file
+// as changes will be lost.
+
+use crate::use_cases::merge_file_uc::{MergeFileUnitOfWorkFactoryTrait, MergeFileUnitOfWorkTrait};
+use anyhow::{Ok, Result};
+use common::database::QueryUnitOfWork;
+use common::database::{db_context::DbContext, transactions::Transaction};
+use common::entities::{File, Root, Workspace};
+use common::types::EntityId;
+use std::cell::RefCell;
+
+// Unit of work for MergeFile
+
+pub struct MergeFileUnitOfWork {
+    context: DbContext,
+    transaction: RefCell<Option<Transaction>>,
+}
+
+impl MergeFileUnitOfWork {
+    pub fn new(db_context: &DbContext) -> Self {
+        MergeFileUnitOfWork {
+            context: db_context.clone(),
+            transaction: RefCell::new(None),
+        }
+    }
+}
+
+impl QueryUnitOfWork for MergeFileUnitOfWork {
+    fn begin_transaction(&self) -> Result<()> {
+        self.transaction
+            .replace(Some(Transaction::begin_read_transaction(&self.context)?));
+        Ok(())
+    }
+
+    fn end_transaction(&self) -> Result<()> {
+        self.transaction
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("No active transaction"))?
+            .end_read_transaction()?;
+        Ok(())
+    }
+}
+
+#[macros::uow_impl]
+impl MergeFileUnitOfWorkTrait for MergeFileUnitOfWork {}
+
+pub struct MergeFileUnitOfWorkFactory {
+    context: DbContext,
+}
+
+impl MergeFileUnitOfWorkFactory {
+    pub fn new(db_context: &DbContext) -> Self {
+        MergeFileUnitOfWorkFactory {
+            context: db_context.clone(),
+        }
+    }
+}
+
+impl MergeFileUnitOfWorkFactoryTrait for MergeFileUnitOfWorkFactory {
+    fn create(&self) -> Box<dyn MergeFileUnitOfWorkTrait> {
+        Box::new(MergeFileUnitOfWork::new(&self.context))
+    }
+}
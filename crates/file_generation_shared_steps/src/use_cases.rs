@@ -4,3 +4,4 @@
 pub(crate) mod common;
 pub(crate) mod fill_status_in_files_uc;
 pub(crate) mod get_file_diff_uc;
+pub(crate) mod merge_file_uc;
@@ -1,5 +1,7 @@
 mod text_processing;
+mod three_way_merge;
 
 #[allow(unused_imports)]
 pub(crate) use text_processing::strip_comment_header;
 pub(crate) use text_processing::strip_generated_by_lines;
+pub(crate) use three_way_merge::three_way_merge;
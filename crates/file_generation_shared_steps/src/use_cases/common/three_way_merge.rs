@@ -0,0 +1,209 @@
+use similar::{Algorithm, DiffOp, capture_diff_slices};
+
+pub(crate) const CONFLICT_START_MARKER: &str = "<<<<<<< on disk";
+pub(crate) const CONFLICT_SEPARATOR_MARKER: &str = "=======";
+pub(crate) const CONFLICT_END_MARKER: &str = ">>>>>>> generated";
+
+/// Result of a line-based three-way merge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MergeOutcome {
+    pub text: String,
+    pub conflict_count: usize,
+}
+
+/// A changed region of one side, expressed as line ranges in the base and in that side.
+#[derive(Debug, Clone, Copy)]
+struct Hunk {
+    base_start: usize,
+    base_end: usize,
+    side_start: usize,
+    side_end: usize,
+    from_ours: bool,
+}
+
+fn hunks(base: &[&str], side: &[&str], from_ours: bool) -> Vec<Hunk> {
+    capture_diff_slices(Algorithm::Myers, base, side)
+        .into_iter()
+        .filter(|op| !matches!(op, DiffOp::Equal { .. }))
+        .map(|op| {
+            let (base_range, side_range) = (op.old_range(), op.new_range());
+            Hunk {
+                base_start: base_range.start,
+                base_end: base_range.end,
+                side_start: side_range.start,
+                side_end: side_range.end,
+                from_ours,
+            }
+        })
+        .collect()
+}
+
+/// Lines of one side covering the base range `[start, end)` of a group of hunks.
+/// Outside of its own hunks, a side is identical to the base, so the range is
+/// obtained by shifting the first and last hunk boundaries.
+fn side_lines<'a>(
+    base: &[&'a str],
+    side: &[&'a str],
+    group: &[Hunk],
+    from_ours: bool,
+    start: usize,
+    end: usize,
+) -> Vec<&'a str> {
+    let own: Vec<&Hunk> = group.iter().filter(|h| h.from_ours == from_ours).collect();
+    match (own.first(), own.last()) {
+        (Some(first), Some(last)) => {
+            let side_start = first.side_start - (first.base_start - start);
+            let side_end = last.side_end + (end - last.base_end);
+            side[side_start..side_end].to_vec()
+        }
+        _ => base[start..end].to_vec(),
+    }
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+    for line in lines {
+        out.push_str(line);
+    }
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Merge the user edits (`ours`, the file on disk) and the generator changes
+/// (`theirs`, the new generation) made since `base` (the last generation).
+///
+/// Regions changed on one side only are taken from that side. Regions changed on
+/// both sides are kept once if both changes are identical, otherwise they are
+/// written between conflict markers.
+pub(crate) fn three_way_merge(base: &str, ours: &str, theirs: &str) -> MergeOutcome {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let our_lines: Vec<&str> = ours.split_inclusive('\n').collect();
+    let their_lines: Vec<&str> = theirs.split_inclusive('\n').collect();
+
+    let mut all_hunks = hunks(&base_lines, &our_lines, true);
+    all_hunks.extend(hunks(&base_lines, &their_lines, false));
+    all_hunks.sort_by_key(|h| (h.base_start, h.base_end));
+
+    let mut text = String::new();
+    let mut conflict_count = 0;
+    let mut position = 0;
+    let mut index = 0;
+
+    while index < all_hunks.len() {
+        // Group hunks whose base ranges overlap or touch
+        let start = all_hunks[index].base_start;
+        let mut end = all_hunks[index].base_end;
+        let mut group_end = index + 1;
+        while group_end < all_hunks.len() && all_hunks[group_end].base_start <= end {
+            end = end.max(all_hunks[group_end].base_end);
+            group_end += 1;
+        }
+        let group = &all_hunks[index..group_end];
+
+        text.extend(base_lines[position..start].iter().copied());
+
+        let ours_changed = group.iter().any(|h| h.from_ours);
+        let theirs_changed = group.iter().any(|h| !h.from_ours);
+        let our_part = side_lines(&base_lines, &our_lines, group, true, start, end);
+        let their_part = side_lines(&base_lines, &their_lines, group, false, start, end);
+
+        if ours_changed && theirs_changed && our_part != their_part {
+            conflict_count += 1;
+            push_lines(&mut text, &[]);
+            text.push_str(CONFLICT_START_MARKER);
+            text.push('\n');
+            push_lines(&mut text, &our_part);
+            text.push_str(CONFLICT_SEPARATOR_MARKER);
+            text.push('\n');
+            push_lines(&mut text, &their_part);
+            text.push_str(CONFLICT_END_MARKER);
+            text.push('\n');
+        } else if ours_changed {
+            text.extend(our_part.iter().copied());
+        } else {
+            text.extend(their_part.iter().copied());
+        }
+
+        position = end;
+        index = group_end;
+    }
+
+    text.extend(base_lines[position..].iter().copied());
+
+    MergeOutcome {
+        text,
+        conflict_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_takes_generator_change_when_user_did_not_edit() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nb\nc\n";
+        let theirs = "a\nB\nc\n";
+        let outcome = three_way_merge(base, ours, theirs);
+        assert_eq!(outcome.text, "a\nB\nc\n");
+        assert_eq!(outcome.conflict_count, 0);
+    }
+
+    #[test]
+    fn test_merge_keeps_user_edit_when_generator_did_not_change() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nb\nmine\nc\n";
+        let outcome = three_way_merge(base, ours, base);
+        assert_eq!(outcome.text, ours);
+        assert_eq!(outcome.conflict_count, 0);
+    }
+
+    #[test]
+    fn test_merge_combines_non_overlapping_changes() {
+        let base = "fn a() {}\n\nfn b() {}\n\nfn c() {}\n";
+        let ours = "fn a() { todo!() }\n\nfn b() {}\n\nfn c() {}\n";
+        let theirs = "fn a() {}\n\nfn b() {}\n\nfn c() {}\n\nfn d() {}\n";
+        let outcome = three_way_merge(base, ours, theirs);
+        assert_eq!(
+            outcome.text,
+            "fn a() { todo!() }\n\nfn b() {}\n\nfn c() {}\n\nfn d() {}\n"
+        );
+        assert_eq!(outcome.conflict_count, 0);
+    }
+
+    #[test]
+    fn test_merge_identical_changes_on_both_sides_do_not_conflict() {
+        let base = "a\nb\nc\n";
+        let both = "a\nx\nc\n";
+        let outcome = three_way_merge(base, both, both);
+        assert_eq!(outcome.text, both);
+        assert_eq!(outcome.conflict_count, 0);
+    }
+
+    #[test]
+    fn test_merge_writes_conflict_markers_when_both_sides_changed() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nmine\nc\n";
+        let theirs = "a\ngenerated\nc\n";
+        let outcome = three_way_merge(base, ours, theirs);
+        assert_eq!(
+            outcome.text,
+            "a\n<<<<<<< on disk\nmine\n=======\ngenerated\n>>>>>>> generated\nc\n"
+        );
+        assert_eq!(outcome.conflict_count, 1);
+    }
+
+    #[test]
+    fn test_merge_conflict_without_trailing_newline() {
+        let base = "a\nb";
+        let ours = "a\nmine";
+        let theirs = "a\ngenerated";
+        let outcome = three_way_merge(base, ours, theirs);
+        assert_eq!(
+            outcome.text,
+            "a\n<<<<<<< on disk\nmine\n=======\ngenerated\n>>>>>>> generated\n"
+        );
+        assert_eq!(outcome.conflict_count, 1);
+    }
+}
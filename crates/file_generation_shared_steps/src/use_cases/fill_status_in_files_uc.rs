@@ -1,15 +1,17 @@
 // Generated by Qleany. Edit at your own risk! Be careful when regenerating this file
 // as changes will be lost.
-use super::common::strip_generated_by_lines;
+use super::common::{strip_generated_by_lines, three_way_merge};
 use anyhow::{Result, anyhow};
 use common::database::CommandUnitOfWork;
 use common::entities::{File, FileStatus, Root, System, Workspace};
+use common::generator::baseline_store;
 use common::types::EntityId;
 use std::path::PathBuf;
 
 pub trait FillStatusInFilesUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn FillStatusInFilesUnitOfWorkTrait>;
 }
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetAll")]
 #[macros::uow_action(entity = "Root", action = "GetRelationship")]
 #[macros::uow_action(entity = "Workspace", action = "Get")]
@@ -101,7 +103,7 @@ impl FillStatusInFilesUseCase {
                     {
                         FileStatus::Unchanged
                     } else {
-                        FileStatus::Modified
+                        merge_status(&root_path, file, &disk_content, generated_code)
                     }
                 }
                 Err(_) => FileStatus::New,
@@ -123,3 +125,28 @@ impl FillStatusInFilesUseCase {
         Ok(())
     }
 }
+
+/// Classify a file whose generated code differs from the disk content.
+///
+/// Without a baseline, or when the disk content is still the last generation,
+/// the file is simply `Modified`. Otherwise the user edited it since the last
+/// generation and a three-way merge decides between `Merged` and `Conflict`.
+fn merge_status(
+    root_path: &std::path::Path,
+    file: &File,
+    disk_content: &str,
+    generated_code: &str,
+) -> FileStatus {
+    let Some(baseline) = baseline_store::read_baseline(root_path, &file.relative_path, &file.name)
+    else {
+        return FileStatus::Modified;
+    };
+    if strip_generated_by_lines(disk_content) == strip_generated_by_lines(&baseline) {
+        return FileStatus::Modified;
+    }
+    if three_way_merge(&baseline, disk_content, generated_code).conflict_count == 0 {
+        FileStatus::Merged
+    } else {
+        FileStatus::Conflict
+    }
+}
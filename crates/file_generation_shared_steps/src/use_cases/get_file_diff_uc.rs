@@ -12,6 +12,7 @@ use std::path::PathBuf;
 pub trait GetFileDiffUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn GetFileDiffUnitOfWorkTrait>;
 }
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetAllRO")]
 #[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
 #[macros::uow_action(entity = "Workspace", action = "GetRO")]
@@ -0,0 +1,98 @@
+// Generated by Qleany. Edit at your own risk! Be careful when regenerating this file
+// as changes will be lost.
+use super::common::three_way_merge;
+use crate::MergeFileDto;
+use crate::MergeFileReturnDto;
+use anyhow::{Result, anyhow};
+use common::database::QueryUnitOfWork;
+use common::entities::{File, Root, Workspace};
+use common::generator::baseline_store;
+use common::types::EntityId;
+use std::path::PathBuf;
+
+pub trait MergeFileUnitOfWorkFactoryTrait {
+    fn create(&self) -> Box<dyn MergeFileUnitOfWorkTrait>;
+}
+#[macros::uow_trait]
+#[macros::uow_action(entity = "Root", action = "GetAllRO")]
+#[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
+#[macros::uow_action(entity = "Workspace", action = "GetRO")]
+#[macros::uow_action(entity = "File", action = "GetRO")]
+pub trait MergeFileUnitOfWorkTrait: QueryUnitOfWork {}
+
+pub struct MergeFileUseCase {
+    uow_factory: Box<dyn MergeFileUnitOfWorkFactoryTrait>,
+}
+
+impl MergeFileUseCase {
+    pub fn new(uow_factory: Box<dyn MergeFileUnitOfWorkFactoryTrait>) -> Self {
+        MergeFileUseCase { uow_factory }
+    }
+
+    pub fn execute(&mut self, dto: &MergeFileDto) -> Result<MergeFileReturnDto> {
+        let uow = self.uow_factory.create();
+        uow.begin_transaction()?;
+
+        // Get the file entity
+        let file: File = uow
+            .get_file(&dto.file_id)?
+            .ok_or_else(|| anyhow!("File entity not found for id {}", dto.file_id))?;
+
+        let generated_code = file
+            .generated_code
+            .clone()
+            .ok_or_else(|| anyhow!("File has no generated code"))?;
+
+        // Get root -> workspace for manifest path
+        let roots = uow.get_all_root()?;
+        let root = roots
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("Root entity not found"))?;
+
+        let workspace_ids = uow.get_root_relationship(
+            &root.id,
+            &common::direct_access::root::RootRelationshipField::Workspace,
+        )?;
+        let workspace_id = workspace_ids
+            .first()
+            .cloned()
+            .ok_or_else(|| anyhow!("No workspace found"))?;
+        let workspace: Workspace = uow
+            .get_workspace(&workspace_id)?
+            .ok_or_else(|| anyhow!("Workspace entity not found"))?;
+        let root_path = PathBuf::from(&workspace.manifest_absolute_path);
+
+        uow.end_transaction()?;
+
+        // Build disk path
+        let mut disk_path = root_path.clone();
+        if !file.relative_path.is_empty() {
+            disk_path = disk_path.join(&file.relative_path);
+        }
+        disk_path = disk_path.join(&file.name);
+
+        // Nothing to merge with: the new generation wins
+        let Ok(disk_content) = std::fs::read_to_string(&disk_path) else {
+            return Ok(MergeFileReturnDto {
+                merged_code: generated_code,
+                conflict_count: 0,
+            });
+        };
+        let Some(baseline) =
+            baseline_store::read_baseline(&root_path, &file.relative_path, &file.name)
+        else {
+            return Err(anyhow!(
+                "No baseline recorded for {}, generate it once before merging",
+                disk_path.display()
+            ));
+        };
+
+        let outcome = three_way_merge(&baseline, &disk_content, &generated_code);
+
+        Ok(MergeFileReturnDto {
+            merged_code: outcome.text,
+            conflict_count: outcome.conflict_count as u64,
+        })
+    }
+}
@@ -91,19 +91,8 @@ impl CommandUnitOfWork for CleanUpBeforeExitUnitOfWork {
     }
 }
 
-//TODO: adapt entities and actions to real use : Create, CreateMulti, Get, GetMulti, Update, UpdateMulti, Delete,
-//DeleteMulti, GetRO, GetMultiRO, GetRelationship, GetRelationshipRO, GetRelationshipsFromRightIds,
-//GetRelationshipsFromRightIdsRO, SetRelationship, SetRelationshipMulti
-//
-// You have here a read-write unit of work.
-//
-// RO means Read Only, so *RO actions should not used be here.
-// Don't forget to set thread_safe = true for long operation's unit of work.
-// Do not mix read-only and write actions in the same unit of work.
-//
-// Exactly the same macros must be set in the use case uow trait file in ../use_cases/clean_up_before_exit_uc.rs
-//
-#[macros::uow_action(entity = "Root", action = "Remove")]
+// The actions are declared once, on CleanUpBeforeExitUnitOfWorkTrait in ../use_cases/clean_up_before_exit_uc.rs.
+#[macros::uow_impl]
 impl CleanUpBeforeExitUnitOfWorkTrait for CleanUpBeforeExitUnitOfWork {}
 
 pub struct CleanUpBeforeExitUnitOfWorkFactory {
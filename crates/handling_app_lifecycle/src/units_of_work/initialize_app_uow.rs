@@ -91,20 +91,8 @@ impl CommandUnitOfWork for InitializeAppUnitOfWork {
     }
 }
 
-//TODO: adapt entities and actions to real use : Create, CreateMulti, Get, GetMulti, Update, UpdateMulti, Delete,
-//DeleteMulti, GetRO, GetMultiRO, GetRelationship, GetRelationshipRO, GetRelationshipsFromRightIds,
-//GetRelationshipsFromRightIdsRO, SetRelationship, SetRelationshipMulti
-//
-// You have here a read-write unit of work.
-//
-// RO means Read Only, so *RO actions should not used be here.
-// Don't forget to set thread_safe = true for long operation's unit of work.
-// Do not mix read-only and write actions in the same unit of work.
-//
-// Exactly the same macros must be set in the use case uow trait file in ../use_cases/initialize_app_uc.rs
-//
-#[macros::uow_action(entity = "Root", action = "CreateOrphan")]
-#[macros::uow_action(entity = "System", action = "CreateOrphan")]
+// The actions are declared once, on InitializeAppUnitOfWorkTrait in ../use_cases/initialize_app_uc.rs.
+#[macros::uow_impl]
 impl InitializeAppUnitOfWorkTrait for InitializeAppUnitOfWork {}
 
 pub struct InitializeAppUnitOfWorkFactory {
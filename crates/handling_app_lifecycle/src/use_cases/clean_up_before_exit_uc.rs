@@ -8,6 +8,7 @@ pub trait CleanUpBeforeExitUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn CleanUpBeforeExitUnitOfWorkTrait>;
 }
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "Remove")]
 pub trait CleanUpBeforeExitUnitOfWorkTrait: CommandUnitOfWork {}
 
@@ -7,6 +7,7 @@ use common::entities::{Root, System};
 pub trait InitializeAppUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn InitializeAppUnitOfWorkTrait>;
 }
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "CreateOrphan")]
 #[macros::uow_action(entity = "System", action = "CreateOrphan")]
 pub trait InitializeAppUnitOfWorkTrait: CommandUnitOfWork {}
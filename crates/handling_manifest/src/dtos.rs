@@ -2,6 +2,43 @@
 
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CheckDiagnosticDto {
+    pub rule: String,
+    pub severity: String,
+    pub message: String,
+    pub location: String,
+    pub file: Option<String>,
+    pub line: Option<u64>,
+    pub column: Option<u64>,
+}
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CheckRuleDto {
+    pub id: String,
+    pub severity: String,
+    pub description: String,
+}
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MigrationChangeDto {
+    pub kind: MigrationChangeKind,
+    pub location: String,
+    pub new_key: Option<String>,
+    pub value: String,
+    pub to_version: i64,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub enum MigrationChangeKind {
+    #[default]
+    Renamed,
+    Removed,
+    Defaulted,
+}
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RenamedFileDto {
+    pub old_path: String,
+    pub new_path: String,
+}
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct LoadDto {
     pub manifest_path: String,
@@ -52,10 +89,42 @@ pub struct ExportToMermaidReturnDto {
 pub struct CheckReturnDto {
     pub warnings: Vec<String>,
     pub critical_errors: Vec<String>,
+    pub diagnostics: Vec<CheckDiagnosticDto>,
 }
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct CheckRuleDto {
-    pub id: String,
-    pub severity: String,
-    pub description: String,
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct UpgradeDto {
+    pub manifest_path: String,
+    pub dry_run: bool,
+}
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct UpgradeReturnDto {
+    pub from_version: i64,
+    pub to_version: i64,
+    pub changes: Vec<MigrationChangeDto>,
+    pub diff: String,
+    pub formatting_preserved: bool,
+}
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RefactorDto {
+    pub operation: RefactorOperation,
+    pub owner_name: String,
+    pub name: String,
+    pub new_name: String,
+    pub field_names: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub enum RefactorOperation {
+    #[default]
+    RenameEntity,
+    RenameField,
+    RenameFeature,
+    RenameUseCase,
+    MoveFieldToParent,
+    ExtractBaseEntity,
+}
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RefactorReturnDto {
+    pub renamed_files: Vec<RenamedFileDto>,
+    pub orphaned_files: Vec<String>,
 }
@@ -5,19 +5,27 @@ use crate::CreateReturnDto;
 use crate::ExportToMermaidReturnDto;
 use crate::LoadDto;
 use crate::LoadReturnDto;
+use crate::RefactorDto;
+use crate::RefactorReturnDto;
 use crate::SaveDto;
+use crate::UpgradeDto;
+use crate::UpgradeReturnDto;
 use crate::units_of_work::check_uow::CheckUnitOfWorkFactory;
 use crate::units_of_work::close_uow::CloseUnitOfWorkFactory;
 use crate::units_of_work::create_uow::CreateUnitOfWorkFactory;
 use crate::units_of_work::export_to_mermaid_uow::ExportToMermaidUnitOfWorkFactory;
 use crate::units_of_work::load_uow::LoadUnitOfWorkFactory;
+use crate::units_of_work::refactor_uow::RefactorUnitOfWorkFactory;
 use crate::units_of_work::save_uow::SaveUnitOfWorkFactory;
+use crate::units_of_work::upgrade_uow::UpgradeUnitOfWorkFactory;
 use crate::use_cases::check_uc::CheckUseCase;
 use crate::use_cases::close_uc::CloseUseCase;
 use crate::use_cases::create_uc::CreateUseCase;
 use crate::use_cases::export_to_mermaid_uc::ExportToMermaidUseCase;
 use crate::use_cases::load_uc::LoadUseCase;
+use crate::use_cases::refactor_uc::RefactorUseCase;
 use crate::use_cases::save_uc::SaveUseCase;
+use crate::use_cases::upgrade_uc::UpgradeUseCase;
 use crate::{CheckReturnDto, CreateDto};
 use anyhow::Result;
 use common::event::{Event, Origin};
@@ -26,8 +34,10 @@ use common::event::HandlingManifestEvent::Check;
 use common::event::HandlingManifestEvent::Close;
 use common::event::HandlingManifestEvent::ExportToMermaid;
 use common::event::HandlingManifestEvent::Load;
+use common::event::HandlingManifestEvent::Refactor;
 use common::event::HandlingManifestEvent::Save;
 
+use common::undo_redo::UndoRedoManager;
 use common::{database::db_context::DbContext, event::EventHub};
 use std::sync::Arc;
 
@@ -48,6 +58,25 @@ pub fn load(
     Ok(return_dto)
 }
 
+/// Load a manifest from its text, e.g. an unsaved editor buffer. The path
+/// still gives the manifest directory and format.
+pub fn load_source(
+    db_context: &DbContext,
+    event_hub: &Arc<EventHub>,
+    dto: &LoadDto,
+    source: &str,
+) -> Result<LoadReturnDto> {
+    let uow_context = LoadUnitOfWorkFactory::new(db_context, event_hub);
+    let mut uc = LoadUseCase::new(Box::new(uow_context));
+    let return_dto = uc.execute_with_source(dto, source)?;
+    event_hub.send_event(Event {
+        origin: Origin::HandlingManifest(Load),
+        ids: vec![return_dto.workspace_id],
+        data: Some(return_dto.manifest_path.clone()),
+    });
+    Ok(return_dto)
+}
+
 pub fn save(db_context: &DbContext, event_hub: &Arc<EventHub>, dto: &SaveDto) -> Result<()> {
     let uow_context = SaveUnitOfWorkFactory::new(db_context, event_hub);
     let mut uc = SaveUseCase::new(Box::new(uow_context));
@@ -68,6 +97,36 @@ pub fn create(db_context: &DbContext, dto: &CreateDto) -> Result<CreateReturnDto
     Ok(return_dto)
 }
 
+/// Migrate a manifest file to the current schema version and report the
+/// changes, without loading it.
+pub fn upgrade(db_context: &DbContext, dto: &UpgradeDto) -> Result<UpgradeReturnDto> {
+    let uow_context = UpgradeUnitOfWorkFactory::new(db_context);
+    let mut uc = UpgradeUseCase::new(Box::new(uow_context));
+    let return_dto = uc.execute(dto)?;
+    Ok(return_dto)
+}
+
+/// Rename or move an element of the loaded manifest and update every reference
+/// to it, as one undoable command. Reports the generated files it renames.
+pub fn refactor(
+    db_context: &DbContext,
+    event_hub: &Arc<EventHub>,
+    undo_redo_manager: &mut UndoRedoManager,
+    stack_id: Option<u64>,
+    dto: &RefactorDto,
+) -> Result<RefactorReturnDto> {
+    let uow_context = RefactorUnitOfWorkFactory::new(db_context, event_hub);
+    let mut uc = RefactorUseCase::new(Box::new(uow_context));
+    let return_dto = uc.execute(dto)?;
+    undo_redo_manager.add_command_to_stack(Box::new(uc), stack_id)?;
+    event_hub.send_event(Event {
+        origin: Origin::HandlingManifest(Refactor),
+        ids: vec![],
+        data: None,
+    });
+    Ok(return_dto)
+}
+
 pub fn close(db_context: &DbContext, event_hub: &Arc<EventHub>) -> Result<()> {
     let uow_context = CloseUnitOfWorkFactory::new(db_context, event_hub);
     let mut uc = CloseUseCase::new(Box::new(uow_context));
@@ -110,6 +169,18 @@ pub fn check(db_context: &DbContext, event_hub: &Arc<EventHub>) -> Result<CheckR
     Ok(return_dto)
 }
 
+/// The files included by a manifest, relative to its directory, as they are
+/// on the disk now. The manifest is not loaded.
+pub fn included_files(manifest_path: &str) -> Result<Vec<String>> {
+    crate::use_cases::common::includes::read_included_files(std::path::Path::new(manifest_path))
+}
+
+/// The check report of a manifest that failed to load, with a single
+/// critical diagnostic for the load error.
+pub fn get_load_failure_report(error: &anyhow::Error) -> CheckReturnDto {
+    crate::use_cases::check_uc::load_failure_report(error)
+}
+
 pub fn get_check_rules() -> Vec<CheckRuleDto> {
     use crate::use_cases::check_uc::{CRITICAL_RULES, WARNING_RULES};
 
@@ -6,4 +6,6 @@ pub(crate) mod close_uow;
 pub(crate) mod create_uow;
 pub(crate) mod export_to_mermaid_uow;
 pub(crate) mod load_uow;
+pub(crate) mod refactor_uow;
 pub(crate) mod save_uow;
+pub(crate) mod upgrade_uow;
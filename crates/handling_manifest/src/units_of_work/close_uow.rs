@@ -90,22 +90,8 @@ impl CommandUnitOfWork for CloseUnitOfWork {
     }
 }
 
-//TODO: adapt entities and actions to real use : Create, CreateMulti, Get, GetMulti, Update, UpdateMulti, Delete,
-//DeleteMulti, GetRO, GetMultiRO, GetRelationship, GetRelationshipRO, GetRelationshipsFromRightIds,
-//GetRelationshipsFromRightIdsRO, SetRelationship, SetRelationshipMulti
-//
-// You have here a read-write unit of work.
-//
-// RO means Read Only, so *RO actions should not used be here.
-// Don't forget to set thread_safe = true for long operation's unit of work.
-// Do not mix read-only and write actions in the same unit of work.
-//
-// Exactly the same macros must be set in the use case uow trait file in ../use_cases/close_uc.rs
-//
-#[macros::uow_action(entity = "System", action = "GetRelationship")]
-#[macros::uow_action(entity = "File", action = "RemoveMulti")]
-#[macros::uow_action(entity = "Workspace", action = "GetAll")]
-#[macros::uow_action(entity = "Workspace", action = "RemoveMulti")]
+// The actions are declared once, on CloseUnitOfWorkTrait in ../use_cases/close_uc.rs.
+#[macros::uow_impl]
 impl CloseUnitOfWorkTrait for CloseUnitOfWork {}
 
 pub struct CloseUnitOfWorkFactory {
@@ -6,7 +6,7 @@ use anyhow::{Ok, Result};
 use common::database::CommandUnitOfWork;
 use common::database::{db_context::DbContext, transactions::Transaction};
 use common::entities::{
-    Dto, DtoField, Entity, Feature, Field, Global, Relationship, Root, System, UseCase,
+    CustomFile, Dto, DtoField, Entity, Feature, Field, Global, Relationship, Root, System, UseCase,
     UserInterface, Workspace,
 };
 use common::event::{AllEvent, DirectAccessEntity, Event, EventBuffer, EventHub, Origin};
@@ -93,39 +93,8 @@ impl CommandUnitOfWork for LoadUnitOfWork {
     }
 }
 
-//TODO: adapt entities and actions to real use : Create, CreateMulti, Get, GetMulti, Update, UpdateMulti, Delete,
-//DeleteMulti, GetRO, GetMultiRO, GetRelationship, GetRelationshipRO, GetRelationshipsFromRightIds,
-//GetRelationshipsFromRightIdsRO, SetRelationship, SetRelationshipMulti
-//
-// You have here a read-write unit of work.
-//
-// RO means Read Only, so *RO actions should not used be here.
-// Don't forget to set thread_safe = true for long operation's unit of work.
-// Do not mix read-only and write actions in the same unit of work.
-//
-// Exactly the same macros must be set in the use case uow trait file in ../use_cases/load_uc.rs
-//
-#[macros::uow_action(entity = "Root", action = "CreateOrphan")]
-#[macros::uow_action(entity = "Root", action = "Get")]
-#[macros::uow_action(entity = "Root", action = "UpdateWithRelationships")]
-#[macros::uow_action(entity = "Workspace", action = "CreateOrphan")]
-#[macros::uow_action(entity = "Workspace", action = "Get")]
-#[macros::uow_action(entity = "Workspace", action = "UpdateWithRelationships")]
-#[macros::uow_action(entity = "System", action = "CreateOrphan")]
-#[macros::uow_action(entity = "System", action = "Get")]
-#[macros::uow_action(entity = "System", action = "UpdateWithRelationships")]
-#[macros::uow_action(entity = "Global", action = "CreateOrphan")]
-#[macros::uow_action(entity = "Feature", action = "CreateOrphan")]
-#[macros::uow_action(entity = "UseCase", action = "CreateOrphan")]
-#[macros::uow_action(entity = "Entity", action = "CreateOrphan")]
-#[macros::uow_action(entity = "Entity", action = "Get")]
-#[macros::uow_action(entity = "Entity", action = "UpdateWithRelationships")]
-#[macros::uow_action(entity = "Field", action = "CreateOrphan")]
-#[macros::uow_action(entity = "Field", action = "GetMulti")]
-#[macros::uow_action(entity = "Dto", action = "CreateOrphan")]
-#[macros::uow_action(entity = "DtoField", action = "CreateOrphan")]
-#[macros::uow_action(entity = "Relationship", action = "CreateOrphanMulti")]
-#[macros::uow_action(entity = "UserInterface", action = "CreateOrphan")]
+// The actions are declared once, on LoadUnitOfWorkTrait in ../use_cases/load_uc.rs.
+#[macros::uow_impl]
 impl LoadUnitOfWorkTrait for LoadUnitOfWork {}
 
 pub struct LoadUnitOfWorkFactory {
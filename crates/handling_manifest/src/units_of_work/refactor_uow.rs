@@ -0,0 +1,116 @@
+// Generated by Qleany v0.0.1 from feature_use_case_uow.tera
+
+use crate::use_cases::refactor_uc::{RefactorUnitOfWorkFactoryTrait, RefactorUnitOfWorkTrait};
+use anyhow::{Ok, Result};
+use common::database::CommandUnitOfWork;
+use common::database::{db_context::DbContext, transactions::Transaction};
+use common::entities::{
+    Entity, Feature, Field, File, Relationship, Root, System, UseCase, Workspace,
+};
+use common::event::{AllEvent, DirectAccessEntity, Event, EventBuffer, EventHub, Origin};
+use common::types;
+use common::types::EntityId;
+use std::cell::RefCell;
+use std::sync::Arc;
+
+// Unit of work for Refactor
+
+pub struct RefactorUnitOfWork {
+    context: DbContext,
+    transaction: Option<Transaction>,
+    event_hub: Arc<EventHub>,
+    event_buffer: RefCell<EventBuffer>,
+}
+
+impl RefactorUnitOfWork {
+    pub fn new(db_context: &DbContext, event_hub: &Arc<EventHub>) -> Self {
+        RefactorUnitOfWork {
+            context: db_context.clone(),
+            transaction: None,
+            event_hub: event_hub.clone(),
+            event_buffer: RefCell::new(EventBuffer::new()),
+        }
+    }
+}
+
+impl CommandUnitOfWork for RefactorUnitOfWork {
+    fn begin_transaction(&mut self) -> Result<()> {
+        self.transaction = Some(Transaction::begin_write_transaction(&self.context)?);
+        self.event_buffer.get_mut().begin_buffering();
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<()> {
+        self.transaction
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("No active transaction"))?
+            .commit()?;
+        for event in self.event_buffer.get_mut().flush() {
+            self.event_hub.send_event(event);
+        }
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<()> {
+        self.transaction
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("No active transaction"))?
+            .rollback()?;
+        self.event_buffer.get_mut().discard();
+        Ok(())
+    }
+
+    fn create_savepoint(&self) -> Result<types::Savepoint> {
+        self.transaction
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No active transaction"))?
+            .create_savepoint()
+    }
+
+    fn restore_to_savepoint(&mut self, savepoint: types::Savepoint) -> Result<()> {
+        let mut transaction = self
+            .transaction
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("No active transaction"))?;
+        transaction.restore_to_savepoint(savepoint)?;
+
+        // Discard buffered events — savepoint restore invalidated them
+        self.event_buffer.get_mut().discard();
+
+        // Send Reset immediately (not buffered — UI must refresh now)
+        self.event_hub.send_event(Event {
+            origin: Origin::DirectAccess(DirectAccessEntity::All(AllEvent::Reset)),
+            ids: vec![],
+            data: None,
+        });
+
+        // Recreate the transaction after restoring to savepoint
+        self.transaction = Some(transaction);
+
+        Ok(())
+    }
+}
+
+// The actions are declared once, on RefactorUnitOfWorkTrait in ../use_cases/refactor_uc.rs.
+#[macros::uow_impl]
+impl RefactorUnitOfWorkTrait for RefactorUnitOfWork {}
+
+pub struct RefactorUnitOfWorkFactory {
+    context: DbContext,
+    event_hub: Arc<EventHub>,
+}
+
+impl RefactorUnitOfWorkFactory {
+    pub fn new(db_context: &DbContext, event_hub: &Arc<EventHub>) -> Self {
+        RefactorUnitOfWorkFactory {
+            context: db_context.clone(),
+            event_hub: event_hub.clone(),
+        }
+    }
+}
+
+impl RefactorUnitOfWorkFactoryTrait for RefactorUnitOfWorkFactory {
+    fn create(&self) -> Box<dyn RefactorUnitOfWorkTrait> {
+        Box::new(RefactorUnitOfWork::new(&self.context, &self.event_hub))
+    }
+}
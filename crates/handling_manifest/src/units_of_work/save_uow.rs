@@ -6,7 +6,8 @@ use anyhow::{Ok, Result};
 use common::database::CommandUnitOfWork;
 use common::database::{db_context::DbContext, transactions::Transaction};
 use common::entities::{
-    Dto, DtoField, Entity, Feature, Field, Global, Root, UseCase, UserInterface, Workspace,
+    CustomFile, Dto, DtoField, Entity, Feature, Field, Global, Root, UseCase, UserInterface,
+    Workspace,
 };
 use common::event::{AllEvent, DirectAccessEntity, Event, EventBuffer, EventHub, Origin};
 use common::types;
@@ -92,36 +93,8 @@ impl CommandUnitOfWork for SaveUnitOfWork {
     }
 }
 
-//TODO: adapt entities and actions to real use : Create, CreateMulti, Get, GetMulti, Update, UpdateMulti, Delete,
-//DeleteMulti, GetRO, GetMultiRO, GetRelationship, GetRelationshipRO, GetRelationshipsFromRightIds,
-//GetRelationshipsFromRightIdsRO, SetRelationship, SetRelationshipMulti
-//
-// You have here a read-write unit of work.
-//
-// RO means Read Only, so *RO actions should not used be here.
-// Don't forget to set thread_safe = true for long operation's unit of work.
-// Do not mix read-only and write actions in the same unit of work.
-//
-// Exactly the same macros must be set in the use case uow trait file in ../use_cases/save_uc.rs
-//
-#[macros::uow_action(entity = "Root", action = "GetAll")]
-#[macros::uow_action(entity = "Root", action = "GetRelationship")]
-#[macros::uow_action(entity = "Workspace", action = "Get")]
-#[macros::uow_action(entity = "Workspace", action = "Update")]
-#[macros::uow_action(entity = "Workspace", action = "GetRelationship")]
-#[macros::uow_action(entity = "Global", action = "Get")]
-#[macros::uow_action(entity = "UserInterface", action = "Get")]
-#[macros::uow_action(entity = "Feature", action = "GetMulti")]
-#[macros::uow_action(entity = "Feature", action = "GetRelationship")]
-#[macros::uow_action(entity = "UseCase", action = "GetMulti")]
-#[macros::uow_action(entity = "UseCase", action = "GetRelationship")]
-#[macros::uow_action(entity = "Entity", action = "GetMulti")]
-#[macros::uow_action(entity = "Entity", action = "GetRelationship")]
-#[macros::uow_action(entity = "Field", action = "GetMulti")]
-#[macros::uow_action(entity = "Field", action = "GetRelationship")]
-#[macros::uow_action(entity = "Dto", action = "GetMulti")]
-#[macros::uow_action(entity = "Dto", action = "GetRelationship")]
-#[macros::uow_action(entity = "DtoField", action = "GetMulti")]
+// The actions are declared once, on SaveUnitOfWorkTrait in ../use_cases/save_uc.rs.
+#[macros::uow_impl]
 impl SaveUnitOfWorkTrait for SaveUnitOfWork {}
 
 pub struct SaveUnitOfWorkFactory {
@@ -0,0 +1,60 @@
+// Generated by Qleany v0.0.1 from feature_use_case_uow.tera
+
+use crate::use_cases::upgrade_uc::{UpgradeUnitOfWorkFactoryTrait, UpgradeUnitOfWorkTrait};
+use anyhow::{Ok, Result};
+use common::database::QueryUnitOfWork;
+use common::database::{db_context::DbContext, transactions::Transaction};
+use std::cell::RefCell;
+
+// Unit of work for Upgrade
+
+pub struct UpgradeUnitOfWork {
+    context: DbContext,
+    transaction: RefCell<Option<Transaction>>,
+}
+
+impl UpgradeUnitOfWork {
+    pub fn new(db_context: &DbContext) -> Self {
+        UpgradeUnitOfWork {
+            context: db_context.clone(),
+            transaction: RefCell::new(None),
+        }
+    }
+}
+
+impl QueryUnitOfWork for UpgradeUnitOfWork {
+    fn begin_transaction(&self) -> Result<()> {
+        self.transaction
+            .replace(Some(Transaction::begin_read_transaction(&self.context)?));
+        Ok(())
+    }
+
+    fn end_transaction(&self) -> Result<()> {
+        self.transaction
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("No active transaction"))?
+            .end_read_transaction()?;
+        Ok(())
+    }
+}
+
+// The upgrade works on the manifest file only: no entity actions
+impl UpgradeUnitOfWorkTrait for UpgradeUnitOfWork {}
+
+pub struct UpgradeUnitOfWorkFactory {
+    context: DbContext,
+}
+
+impl UpgradeUnitOfWorkFactory {
+    pub fn new(db_context: &DbContext) -> Self {
+        UpgradeUnitOfWorkFactory {
+            context: db_context.clone(),
+        }
+    }
+}
+
+impl UpgradeUnitOfWorkFactoryTrait for UpgradeUnitOfWorkFactory {
+    fn create(&self) -> Box<dyn UpgradeUnitOfWorkTrait> {
+        Box::new(UpgradeUnitOfWork::new(&self.context))
+    }
+}
@@ -3,8 +3,10 @@
 
 pub(crate) mod check_uc;
 pub(crate) mod close_uc;
-mod common;
+pub(crate) mod common;
 pub(crate) mod create_uc;
 pub(crate) mod export_to_mermaid_uc;
 pub(crate) mod load_uc;
+pub(crate) mod refactor_uc;
 pub(crate) mod save_uc;
+pub(crate) mod upgrade_uc;
@@ -1,12 +1,18 @@
 // Generated by Qleany v0.0.1 from feature_use_case.tera
-use crate::CheckReturnDto;
+use crate::use_cases::common::includes;
+use crate::use_cases::common::source_map::{self, SourceMap};
+use crate::{CheckDiagnosticDto, CheckReturnDto};
 use anyhow::{Result, anyhow};
 use common::database::QueryUnitOfWork;
 use common::entities::{
-    Direction, Dto, DtoField, DtoFieldType, Entity, Feature, Field, FieldRelationshipType,
-    FieldType, Global, Relationship, Root, Strength, UseCase, UserInterface, Workspace,
+    CustomFile, Direction, Dto, DtoField, DtoFieldType, Entity, Feature, Field,
+    FieldRelationshipType, FieldType, Global, Relationship, Root, Strength, UseCase, UserInterface,
+    Workspace,
 };
 use common::enum_variant_parser;
+use common::generator::custom_files;
+use common::generator::field_constraints::{self, FieldConstraints, ValueKind};
+use common::generator::field_defaults::{DefaultKind, FieldDefault};
 use common::types::EntityId;
 use heck::{ToSnakeCase, ToUpperCamelCase};
 use std::collections::{HashMap, HashSet};
@@ -15,7 +21,8 @@ pub trait CheckUnitOfWorkFactoryTrait: Send + Sync {
     fn create(&self) -> Box<dyn CheckUnitOfWorkTrait>;
 }
 
-// Exactly the same macros must be set in ../units_of_work/check_uow.rs
+// The unit of work in ../units_of_work/check_uow.rs takes these actions through #[macros::uow_impl]
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetAllRO")]
 #[macros::uow_action(entity = "Workspace", action = "GetRO")]
 #[macros::uow_action(entity = "Workspace", action = "GetRelationshipRO")]
@@ -25,10 +32,12 @@ pub trait CheckUnitOfWorkFactoryTrait: Send + Sync {
 #[macros::uow_action(entity = "Feature", action = "GetMultiRO")]
 #[macros::uow_action(entity = "UseCase", action = "GetMultiRO")]
 #[macros::uow_action(entity = "Dto", action = "GetRO")]
+#[macros::uow_action(entity = "Dto", action = "GetMultiRO")]
 #[macros::uow_action(entity = "DtoField", action = "GetMultiRO")]
 #[macros::uow_action(entity = "Global", action = "GetRO")]
 #[macros::uow_action(entity = "Relationship", action = "GetMultiRO")]
 #[macros::uow_action(entity = "UserInterface", action = "GetRO")]
+#[macros::uow_action(entity = "CustomFile", action = "GetMultiRO")]
 pub trait CheckUnitOfWorkTrait: QueryUnitOfWork {}
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -281,7 +290,57 @@ pub const CRITICAL_RULES: &[Rule] = &[
     Rule {
         id: "C47",
         severity: "critical",
-        description: "The first enum value must be a simple variant (required for #[derive(Default)])",
+        description: "The first enum value must be a simple variant (required for #[derive(Default)]), unless the field's default names another simple variant",
+    },
+    Rule {
+        id: "C48",
+        severity: "critical",
+        description: "Global: storage must be 'memory' or 'sqlite'",
+    },
+    Rule {
+        id: "C49",
+        severity: "critical",
+        description: "Custom file: path and template must not be empty, scope and nature must be valid, and path placeholders must match the scope",
+    },
+    Rule {
+        id: "C50",
+        severity: "critical",
+        description: "Field/DtoField constraints must match the field type, pattern must be a valid regex, and unique_in_parent requires an entity owned through a strong to-many relationship",
+    },
+    Rule {
+        id: "C51",
+        severity: "critical",
+        description: "indexed and unique only apply to scalar fields that are not lists, floats, json or enums with data-carrying variants",
+    },
+    Rule {
+        id: "C52",
+        severity: "critical",
+        description: "A DTO must not contain itself through its dto-type fields",
+    },
+    Rule {
+        id: "C53",
+        severity: "critical",
+        description: "Field/DtoField: bytes and json fields cannot be lists, and json fields cannot be optional",
+    },
+    Rule {
+        id: "C54",
+        severity: "critical",
+        description: "Field/DtoField: default must fit the field type (a literal, a simple enum variant, 'now' for datetime, 'new_uuid' for uuid) and lists, relationships, bytes, json and dto fields cannot have one",
+    },
+    Rule {
+        id: "C55",
+        severity: "critical",
+        description: "The manifest and its included files must be readable, parse and match the manifest schema",
+    },
+    Rule {
+        id: "C56",
+        severity: "critical",
+        description: "A manifest must be loaded (the root must exist)",
+    },
+    Rule {
+        id: "C57",
+        severity: "critical",
+        description: "The root must have a workspace",
     },
 ];
 
@@ -302,6 +361,11 @@ pub const WARNING_RULES: &[Rule] = &[
         severity: "warning",
         description: "DTO should have at least one field",
     },
+    Rule {
+        id: "W05",
+        severity: "warning",
+        description: "undo_journal only applies to undoable entities generated in Rust",
+    },
 ];
 
 // Rust reserved keywords (2024 edition) + reserved for future use
@@ -504,17 +568,25 @@ fn is_forbidden_name(name: &str) -> Option<&'static str> {
 /// Validate enum_values for both entity fields and DTO fields.
 /// Supports simple variants (`Active`), tuple variants (`Text(String)`),
 /// and struct variants (`Image { name: String, width: i64 }`).
+#[allow(clippy::too_many_arguments)]
 fn validate_enum_values(
     vals: &[String],
+    has_default: bool,
     context: &str, // e.g. "Entity 'Foo', field 'bar'" or "DTO 'Baz', field 'qux'"
+    location: &str,
     entity_names: &HashSet<String>,
     enum_names: &HashSet<String>,
     has_cpp_target: bool,
-    critical_errors: &mut Vec<String>,
+    report: &mut Report,
 ) {
     for (idx, val) in vals.iter().enumerate() {
+        let location = format!("{}/enum_values/{}", location, idx);
         if val.is_empty() {
-            critical_errors.push(format!("{}: enum_values contains an empty value", context));
+            report.critical(
+                "C40",
+                location,
+                format!("{}: enum_values contains an empty value", context),
+            );
             continue;
         }
 
@@ -522,57 +594,326 @@ fn validate_enum_values(
             Ok(parsed) => {
                 // Variant name must be PascalCase
                 if !is_pascal_case(&parsed.name) {
-                    critical_errors.push(format!(
-                        "{}: enum variant name '{}' must be PascalCase (expected '{}')",
-                        context,
-                        parsed.name,
-                        parsed.name.to_upper_camel_case()
-                    ));
+                    report.critical(
+                        "C40",
+                        location.clone(),
+                        format!(
+                            "{}: enum variant name '{}' must be PascalCase (expected '{}')",
+                            context,
+                            parsed.name,
+                            parsed.name.to_upper_camel_case()
+                        ),
+                    );
                 }
 
                 // Complex variants (tuple/struct) are Rust-only
                 if has_cpp_target
                     && !matches!(parsed.kind, enum_variant_parser::EnumVariantKind::Simple)
                 {
-                    critical_errors.push(format!(
+                    report.critical("C46", location.clone(), format!(
                         "{}: complex enum variant '{}' (tuple/struct) is only supported for Rust targets, \
                          but C++ targets are enabled",
                         context, parsed.name
                     ));
                 }
 
-                // First variant must be simple (for #[derive(Default)] + #[default])
-                if idx == 0 && !matches!(parsed.kind, enum_variant_parser::EnumVariantKind::Simple)
+                // First variant must be simple (for #[derive(Default)] + #[default]),
+                // unless the field's default puts #[default] on another variant
+                if idx == 0
+                    && !has_default
+                    && !matches!(parsed.kind, enum_variant_parser::EnumVariantKind::Simple)
                 {
-                    critical_errors.push(format!(
-                        "{}: the first enum value must be a simple variant (no data) \
-                         for Default derive support, but '{}' has data",
-                        context, parsed.name
-                    ));
+                    report.critical(
+                        "C47",
+                        location.clone(),
+                        format!(
+                            "{}: the first enum value must be a simple variant (no data) \
+                         for Default derive support, but '{}' has data (or name a simple variant as default)",
+                            context, parsed.name
+                        ),
+                    );
                 }
 
                 // Validate references inside complex variants
                 let refs = enum_variant_parser::collect_references(&parsed);
                 for ref_name in &refs {
                     if !entity_names.contains(ref_name) && !enum_names.contains(ref_name) {
-                        critical_errors.push(format!(
-                            "{}: enum variant '{}' references unknown type '{}' \
+                        report.critical(
+                            "C45",
+                            location.clone(),
+                            format!(
+                                "{}: enum variant '{}' references unknown type '{}' \
                              (not a known entity or enum name)",
-                            context, parsed.name, ref_name
-                        ));
+                                context, parsed.name, ref_name
+                            ),
+                        );
                     }
                 }
             }
             Err(e) => {
-                critical_errors.push(format!(
-                    "{}: invalid enum variant syntax '{}': {}",
-                    context, val, e
-                ));
+                report.critical(
+                    "C44",
+                    location,
+                    format!("{}: invalid enum variant syntax '{}': {}", context, val, e),
+                );
             }
         }
     }
 }
 
+/// Validate the constraints of an entity field or a DTO field.
+#[allow(clippy::too_many_arguments)]
+fn validate_constraints(
+    constraints: Option<FieldConstraints>,
+    kind: ValueKind,
+    is_list: bool,
+    is_relationship: bool,
+    has_owner: bool,
+    context: &str, // e.g. "Entity 'Foo', field 'bar'" or "DTO 'Baz', field 'qux'"
+    location: &str,
+    report: &mut Report,
+) {
+    let Some(constraints) = constraints else {
+        return;
+    };
+    let location = format!("{}/constraints", location);
+    for error in field_constraints::constraint_errors(kind, is_list, is_relationship, &constraints)
+    {
+        report.critical("C50", location.clone(), format!("{}: {}", context, error));
+    }
+    if let Some(pattern) = &constraints.pattern
+        && let Err(e) = regex::Regex::new(pattern)
+    {
+        report.critical(
+            "C50",
+            format!("{}/pattern", location),
+            format!(
+                "{}: pattern '{}' is not a valid regular expression: {}",
+                context, pattern, e
+            ),
+        );
+    }
+    if constraints.unique_in_parent && !has_owner {
+        report.critical("C50", format!("{}/unique_in_parent", location), format!(
+            "{}: unique_in_parent requires an entity owned through a strong one_to_many or ordered_one_to_many relationship",
+            context
+        ));
+    }
+}
+
+/// Validate the `indexed` / `unique` options of an entity field: the store keys
+/// its secondary indexes by the field value, which must be a hashable scalar.
+fn validate_index(field: &Field, context: &str, location: &str, report: &mut Report) {
+    if !field.indexed && !field.unique {
+        return;
+    }
+    let option = if field.unique { "unique" } else { "indexed" };
+    let reason = if field.is_list || field.field_type == FieldType::Entity {
+        Some("lists and relationships cannot be indexed")
+    } else if field.field_type == FieldType::Float {
+        Some("float fields cannot be indexed")
+    } else if field.field_type == FieldType::Json {
+        Some("json fields cannot be indexed")
+    } else if field.field_type == FieldType::Enum
+        && field.enum_values.iter().any(|v| {
+            enum_variant_parser::parse_enum_variant(v)
+                .is_ok_and(|p| p.kind != enum_variant_parser::EnumVariantKind::Simple)
+        })
+    {
+        Some("only enums with plain variants can be indexed")
+    } else {
+        None
+    };
+    if let Some(reason) = reason {
+        report.critical(
+            "C51",
+            format!("{}/{}", location, option),
+            format!("{}: '{}' is not allowed, {}", context, option, reason),
+        );
+    }
+}
+
+/// Validate `is_list` / `optional` against the bytes and json types: a byte blob
+/// is already a sequence, and `null` is already a JSON value.
+fn validate_blob_options(
+    is_bytes: bool,
+    is_json: bool,
+    is_list: bool,
+    optional: bool,
+    context: &str,
+    location: &str,
+    report: &mut Report,
+) {
+    if is_list && (is_bytes || is_json) {
+        let type_name = if is_bytes { "bytes" } else { "json" };
+        report.critical(
+            "C53",
+            format!("{}/is_list", location),
+            format!("{}: {} fields cannot be lists", context, type_name),
+        );
+    }
+    if optional && is_json {
+        report.critical(
+            "C53",
+            format!("{}/optional", location),
+            format!(
+                "{}: json fields cannot be optional, null is already a JSON value",
+                context
+            ),
+        );
+    }
+}
+
+/// Validate the `default` of an entity field or a DTO field against its type.
+fn validate_default(
+    value: Option<&str>,
+    kind: DefaultKind,
+    is_list: bool,
+    enum_values: &[String],
+    context: &str,
+    location: &str,
+    report: &mut Report,
+) {
+    let Some(value) = value else {
+        return;
+    };
+    if let Err(error) = FieldDefault::parse(kind, is_list, value, enum_values) {
+        report.critical(
+            "C54",
+            format!("{}/default", location),
+            format!("{}: {}", context, error),
+        );
+    }
+}
+
+/// Diagnostics found by the check, in the order they were found.
+#[derive(Default)]
+struct Report {
+    diagnostics: Vec<CheckDiagnosticDto>,
+}
+
+impl Report {
+    fn critical(&mut self, rule: &str, location: String, message: String) {
+        self.push(rule, "critical", location, message);
+    }
+
+    fn warning(&mut self, rule: &str, location: String, message: String) {
+        self.push(rule, "warning", location, message);
+    }
+
+    fn push(&mut self, rule: &str, severity: &str, location: String, message: String) {
+        self.diagnostics.push(CheckDiagnosticDto {
+            rule: rule.to_string(),
+            severity: severity.to_string(),
+            message,
+            location,
+            file: None,
+            line: None,
+            column: None,
+        });
+    }
+
+    /// Attach the positions found in `source_map`, and split the messages by
+    /// severity.
+    fn into_return_dto(self, source_map: Option<&SourceMap>) -> CheckReturnDto {
+        let mut dto = CheckReturnDto::default();
+        for mut diagnostic in self.diagnostics {
+            if let Some(position) =
+                source_map.and_then(|source_map| source_map.position(&diagnostic.location))
+            {
+                diagnostic.file = position.file;
+                diagnostic.line = Some(position.line);
+                diagnostic.column = Some(position.column);
+            }
+            if diagnostic.severity == "critical" {
+                dto.critical_errors.push(diagnostic.message.clone());
+            } else {
+                dto.warnings.push(diagnostic.message.clone());
+            }
+            dto.diagnostics.push(diagnostic);
+        }
+        dto
+    }
+}
+
+/// The report of a manifest that does not load, positioned at the parse error
+/// when there is one.
+pub fn load_failure_report(error: &anyhow::Error) -> CheckReturnDto {
+    let mut report = Report::default();
+    report.critical("C55", String::new(), format!("{:#}", error));
+    let included = error.downcast_ref::<includes::IncludedFileError>();
+    let position = error.chain().find_map(|cause| {
+        if let Some(error) = cause.downcast_ref::<serde_yml::Error>() {
+            let location = error.location()?;
+            Some((location.line() as u64, location.column() as u64))
+        } else {
+            let error = cause.downcast_ref::<serde_json::Error>()?;
+            // Errors of the model structs have no position in the text
+            (error.line() > 0).then(|| (error.line() as u64, error.column() as u64))
+        }
+    });
+    let mut dto = report.into_return_dto(None);
+    let diagnostic = &mut dto.diagnostics[0];
+    diagnostic.file = included.map(|included| included.file.clone());
+    if let Some((line, column)) = position {
+        diagnostic.line = Some(line);
+        diagnostic.column = Some(column);
+    }
+    dto
+}
+
+/// Where an entity or a feature is defined, for messages.
+fn origin(source_file: &str) -> &str {
+    if source_file.is_empty() {
+        "the manifest"
+    } else {
+        source_file
+    }
+}
+
+fn entity_location(entity: &str) -> String {
+    source_map::location(&["entities", entity])
+}
+
+fn field_location(entity: &str, field: &str) -> String {
+    source_map::location(&["entities", entity, "fields", field])
+}
+
+/// The DTOs from `dto_id` back to itself through dto-type fields, if it contains itself.
+fn find_dto_cycle(
+    dto_id: EntityId,
+    nested_dtos: &HashMap<EntityId, Vec<EntityId>>,
+) -> Option<Vec<EntityId>> {
+    let mut path = vec![dto_id];
+    let mut visited = HashSet::new();
+    fn walk(
+        target: EntityId,
+        path: &mut Vec<EntityId>,
+        visited: &mut HashSet<EntityId>,
+        nested_dtos: &HashMap<EntityId, Vec<EntityId>>,
+    ) -> bool {
+        let current = *path.last().unwrap();
+        for &next in nested_dtos.get(&current).into_iter().flatten() {
+            path.push(next);
+            if next == target || (visited.insert(next) && walk(target, path, visited, nested_dtos))
+            {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+    walk(dto_id, &mut path, &mut visited, nested_dtos).then_some(path)
+}
+
+fn feature_location(feature: &str) -> String {
+    source_map::location(&["features", feature])
+}
+
+fn use_case_location(feature: &str, use_case: &str) -> String {
+    source_map::location(&["features", feature, "use_cases", use_case])
+}
+
 pub struct CheckUseCase {
     uow_factory: Box<dyn CheckUnitOfWorkFactoryTrait>,
 }
@@ -586,18 +927,18 @@ impl CheckUseCase {
         let uow = self.uow_factory.create();
         uow.begin_transaction()?;
 
-        let mut warnings: Vec<String> = vec![];
-        let mut critical_errors: Vec<String> = vec![];
+        let mut report = Report::default();
 
         // Get root
         let roots = uow.get_all_root()?;
         if roots.is_empty() {
-            critical_errors.push("No root found. Is a manifest loaded?".to_string());
+            report.critical(
+                "C56",
+                String::new(),
+                "No root found. Is a manifest loaded?".to_string(),
+            );
             uow.end_transaction()?;
-            return Ok(CheckReturnDto {
-                warnings,
-                critical_errors,
-            });
+            return Ok(report.into_return_dto(None));
         }
         let root = &roots[0];
 
@@ -605,12 +946,9 @@ impl CheckUseCase {
         let workspace_id = match root.workspace {
             Some(id) => id,
             None => {
-                critical_errors.push("Root has no workspace".to_string());
+                report.critical("C57", String::new(), "Root has no workspace".to_string());
                 uow.end_transaction()?;
-                return Ok(CheckReturnDto {
-                    warnings,
-                    critical_errors,
-                });
+                return Ok(report.into_return_dto(None));
             }
         };
         let workspace = uow
@@ -621,25 +959,59 @@ impl CheckUseCase {
         let global = uow.get_global(&workspace.global)?;
         if let Some(global) = &global {
             if global.application_name.is_empty() {
-                critical_errors.push("Global: application_name is empty".to_string());
+                report.critical(
+                    "C33",
+                    "/global/application_name".to_string(),
+                    "Global: application_name is empty".to_string(),
+                );
             } else if !is_pascal_case(&global.application_name) {
-                critical_errors.push(format!(
-                    "Global: application_name '{}' must be PascalCase (expected '{}')",
-                    global.application_name,
-                    global.application_name.to_upper_camel_case()
-                ));
+                report.critical(
+                    "C34",
+                    "/global/application_name".to_string(),
+                    format!(
+                        "Global: application_name '{}' must be PascalCase (expected '{}')",
+                        global.application_name,
+                        global.application_name.to_upper_camel_case()
+                    ),
+                );
             }
             if global.organisation_name.is_empty() {
-                critical_errors.push("Global: organisation_name is empty".to_string());
+                report.critical(
+                    "C35",
+                    "/global/organisation/name".to_string(),
+                    "Global: organisation_name is empty".to_string(),
+                );
             }
             if global.organisation_domain.is_empty() {
-                critical_errors.push("Global: organisation_domain is empty".to_string());
+                report.critical(
+                    "C36",
+                    "/global/organisation/domain".to_string(),
+                    "Global: organisation_domain is empty".to_string(),
+                );
             }
             if global.language.is_empty() {
-                critical_errors.push("Global: language is empty".to_string());
+                report.critical(
+                    "C01",
+                    "/global/language".to_string(),
+                    "Global: language is empty".to_string(),
+                );
+            }
+            if !matches!(global.storage.as_str(), "" | "memory" | "sqlite") {
+                report.critical(
+                    "C48",
+                    "/global/storage".to_string(),
+                    format!(
+                        "Global: storage '{}' is not supported (expected 'memory' or 'sqlite')",
+                        global.storage
+                    ),
+                );
             }
         } else {
-            critical_errors.push("Global settings not found".to_string());
+            report.critical(
+                "C02",
+                "/global".to_string(),
+                "Global settings not found".to_string(),
+            );
         }
 
         // Detect if C++ targets are active (complex enum variants are Rust-only).
@@ -671,28 +1043,54 @@ impl CheckUseCase {
         let entities = uow.get_entity_multi(&workspace.entities)?;
         let entities: Vec<Entity> = entities.into_iter().flatten().collect();
 
-        // Unique entity names (globally)
+        // Unique entity names (globally), across the included files
         let mut entity_names: HashSet<String> = HashSet::new();
+        let mut entity_origins: HashMap<&str, &str> = HashMap::new();
         for entity in &entities {
+            let location = entity_location(&entity.name);
             if entity.name.is_empty() {
-                critical_errors.push(format!("Entity with id {} has an empty name", entity.id));
+                report.critical(
+                    "C03",
+                    location.clone(),
+                    format!("Entity with id {} has an empty name", entity.id),
+                );
             } else if !entity_names.insert(entity.name.clone()) {
-                critical_errors.push(format!("Duplicate entity name: '{}'", entity.name));
+                let first = entity_origins.get(entity.name.as_str()).unwrap_or(&"");
+                report.critical(
+                    "C04",
+                    location.clone(),
+                    format!(
+                        "Duplicate entity name: '{}', defined in {} and in {}",
+                        entity.name,
+                        origin(first),
+                        origin(&entity.source_file)
+                    ),
+                );
+            } else {
+                entity_origins.insert(&entity.name, &entity.source_file);
             }
             // Forbidden names
             if let Some(reason) = is_forbidden_name(&entity.name) {
-                critical_errors.push(format!(
-                    "Entity '{}': name is a {} and cannot be used",
-                    entity.name, reason
-                ));
+                report.critical(
+                    "C05",
+                    location.clone(),
+                    format!(
+                        "Entity '{}': name is a {} and cannot be used",
+                        entity.name, reason
+                    ),
+                );
             }
             // PascalCase check
             if !entity.name.is_empty() && !is_pascal_case(&entity.name) {
-                critical_errors.push(format!(
-                    "Entity '{}': name must be PascalCase (expected '{}')",
-                    entity.name,
-                    entity.name.to_upper_camel_case()
-                ));
+                report.critical(
+                    "C37",
+                    location,
+                    format!(
+                        "Entity '{}': name must be PascalCase (expected '{}')",
+                        entity.name,
+                        entity.name.to_upper_camel_case()
+                    ),
+                );
             }
         }
 
@@ -702,21 +1100,27 @@ impl CheckUseCase {
         // Check inheritance cycles
         for entity in &entities {
             if let Some(parent_id) = entity.inherits_from {
+                let location = format!("{}/inherits_from", entity_location(&entity.name));
                 if !entity_by_id.contains_key(&parent_id) {
-                    critical_errors.push(format!(
-                        "Entity '{}': inherits_from references non-existent entity (id {})",
-                        entity.name, parent_id
-                    ));
+                    report.critical(
+                        "C06",
+                        location,
+                        format!(
+                            "Entity '{}': inherits_from references non-existent entity (id {})",
+                            entity.name, parent_id
+                        ),
+                    );
                 } else {
                     let mut visited = HashSet::new();
                     visited.insert(entity.id);
                     let mut current_id = Some(parent_id);
                     while let Some(cid) = current_id {
                         if !visited.insert(cid) {
-                            critical_errors.push(format!(
-                                "Entity '{}': inheritance cycle detected",
-                                entity.name
-                            ));
+                            report.critical(
+                                "C07",
+                                location.clone(),
+                                format!("Entity '{}': inheritance cycle detected", entity.name),
+                            );
                             break;
                         }
                         current_id = entity_by_id.get(&cid).and_then(|e| e.inherits_from);
@@ -732,6 +1136,20 @@ impl CheckUseCase {
         let fields: Vec<Field> = fields.into_iter().flatten().collect();
         let field_by_id: HashMap<EntityId, &Field> = fields.iter().map(|f| (f.id, f)).collect();
 
+        // Entities owned through a strong to-many relationship, the only ones with siblings
+        let owned_entities: HashSet<EntityId> = fields
+            .iter()
+            .filter(|f| {
+                f.field_type == FieldType::Entity
+                    && f.strong
+                    && matches!(
+                        f.relationship,
+                        FieldRelationshipType::OneToMany | FieldRelationshipType::OrderedOneToMany
+                    )
+            })
+            .filter_map(|f| f.entity)
+            .collect();
+
         // Collect all known enum names from entity fields (for cross-reference validation)
         let mut all_enum_names: HashSet<String> = fields
             .iter()
@@ -741,7 +1159,22 @@ impl CheckUseCase {
 
         for entity in &entities {
             if entity.fields.is_empty() && !entity.only_for_heritage {
-                warnings.push(format!("Entity '{}' has no fields", entity.name));
+                report.warning(
+                    "W02",
+                    entity_location(&entity.name),
+                    format!("Entity '{}' has no fields", entity.name),
+                );
+            }
+
+            if entity.undo_journal && (!entity.undoable || !is_rust_language) {
+                report.warning(
+                    "W05",
+                    format!("{}/undo_journal", entity_location(&entity.name)),
+                    format!(
+                        "Entity '{}': undo_journal has no effect, it needs an undoable entity and the Rust generator",
+                        entity.name
+                    ),
+                );
             }
 
             // Collect all fields for this entity (own + inherited)
@@ -773,46 +1206,67 @@ impl CheckUseCase {
             let mut field_names: HashSet<String> = HashSet::new();
             for field_id in &entity.fields {
                 if let Some(field) = field_by_id.get(field_id) {
+                    let location = field_location(&entity.name, &field.name);
                     if !field_names.insert(field.name.clone()) {
-                        critical_errors.push(format!(
-                            "Entity '{}': duplicate field name '{}'",
-                            entity.name, field.name
-                        ));
+                        report.critical(
+                            "C08",
+                            location.clone(),
+                            format!(
+                                "Entity '{}': duplicate field name '{}'",
+                                entity.name, field.name
+                            ),
+                        );
                     }
 
                     // Forbidden field names
                     if let Some(reason) = is_forbidden_name(&field.name) {
-                        critical_errors.push(format!(
-                            "Entity '{}', field '{}': name is a {} and cannot be used",
-                            entity.name, field.name, reason
-                        ));
+                        report.critical(
+                            "C09",
+                            location.clone(),
+                            format!(
+                                "Entity '{}', field '{}': name is a {} and cannot be used",
+                                entity.name, field.name, reason
+                            ),
+                        );
                     }
                     // snake_case check
                     if !field.name.is_empty() && !is_snake_case(&field.name) {
-                        critical_errors.push(format!(
-                            "Entity '{}', field '{}': name must be snake_case (expected '{}')",
-                            entity.name,
-                            field.name,
-                            field.name.to_snake_case()
-                        ));
+                        report.critical(
+                            "C38",
+                            location.clone(),
+                            format!(
+                                "Entity '{}', field '{}': name must be snake_case (expected '{}')",
+                                entity.name,
+                                field.name,
+                                field.name.to_snake_case()
+                            ),
+                        );
                     }
 
                     // A field cannot be both optional and is_list
                     if field.optional && field.is_list {
-                        critical_errors.push(format!(
-                            "Entity '{}', field '{}': cannot be both optional and is_list",
-                            entity.name, field.name
-                        ));
+                        report.critical(
+                            "C30",
+                            location.clone(),
+                            format!(
+                                "Entity '{}', field '{}': cannot be both optional and is_list",
+                                entity.name, field.name
+                            ),
+                        );
                     }
 
                     // is_list cannot be used with Entity or Enum types
                     if field.is_list
                         && matches!(field.field_type, FieldType::Entity | FieldType::Enum)
                     {
-                        critical_errors.push(format!(
-                            "Entity '{}', field '{}': is_list cannot be used with type {:?}",
-                            entity.name, field.name, field.field_type
-                        ));
+                        report.critical(
+                            "C30b",
+                            location.clone(),
+                            format!(
+                                "Entity '{}', field '{}': is_list cannot be used with type {:?}",
+                                entity.name, field.name, field.field_type
+                            ),
+                        );
                     }
 
                     // list_model / list_model_displayed_field require Entity type + to-many relationship
@@ -825,21 +1279,29 @@ impl CheckUseCase {
                     if (field.list_model || field.list_model_displayed_field.is_some())
                         && !(field.field_type == FieldType::Entity && is_to_many)
                     {
-                        critical_errors.push(format!(
-                            "Entity '{}', field '{}': list_model or list_model_displayed_field \
+                        report.critical(
+                            "C31",
+                            location.clone(),
+                            format!(
+                                "Entity '{}', field '{}': list_model or list_model_displayed_field \
                              requires field_type=Entity and relationship in \
                              {{OneToMany, OrderedOneToMany, ManyToMany}}",
-                            entity.name, field.name
-                        ));
+                                entity.name, field.name
+                            ),
+                        );
                     }
 
                     // To-many relationships cannot be optional
                     if is_to_many && field.optional {
-                        critical_errors.push(format!(
-                            "Entity '{}', field '{}': a to-many relationship \
+                        report.critical(
+                            "C32",
+                            location.clone(),
+                            format!(
+                                "Entity '{}', field '{}': a to-many relationship \
                              (OneToMany, OrderedOneToMany, ManyToMany) cannot be optional",
-                            entity.name, field.name
-                        ));
+                                entity.name, field.name
+                            ),
+                        );
                     }
 
                     // Weak OneToOne/ManyToOne must be optional (dangling pointer on delete)
@@ -852,36 +1314,55 @@ impl CheckUseCase {
                         && !field.strong
                         && !field.optional
                     {
-                        critical_errors.push(format!(
-                            "Entity '{}', field '{}': a weak {:?} relationship \
+                        report.critical(
+                            "C43",
+                            location.clone(),
+                            format!(
+                                "Entity '{}', field '{}': a weak {:?} relationship \
                              must be optional (risk of dangling pointer when the \
                              referenced entity is deleted)",
-                            entity.name, field.name, field.relationship
-                        ));
+                                entity.name, field.name, field.relationship
+                            ),
+                        );
                     }
 
                     // list_model requires a non-empty list_model_displayed_field
                     // that references an existing field of the target entity
                     if field.list_model {
                         let displayed = field.list_model_displayed_field.as_deref().unwrap_or("");
+                        let displayed_location = format!("{}/list_model_displayed_field", location);
                         if displayed.is_empty() {
-                            critical_errors.push(format!(
-                                "Entity '{}', field '{}': list_model is true but \
+                            report.critical(
+                                "C41",
+                                displayed_location,
+                                format!(
+                                    "Entity '{}', field '{}': list_model is true but \
                                  list_model_displayed_field is empty",
-                                entity.name, field.name
-                            ));
+                                    entity.name, field.name
+                                ),
+                            );
                         } else {
-                            // Check that the target entity has a field with this name
+                            // Check that the target entity has a field with this name,
+                            // its own or inherited
                             if let Some(target_entity_id) = field.entity
                                 && let Some(target_entity) = entity_by_id.get(&target_entity_id)
                             {
-                                let target_field_names: Vec<&str> = target_entity
-                                    .fields
-                                    .iter()
-                                    .filter_map(|fid| field_by_id.get(fid).map(|f| f.name.as_str()))
-                                    .collect();
+                                let mut target_field_names: Vec<&str> = vec![];
+                                let mut current = Some(*target_entity);
+                                let mut depth = 0;
+                                while let Some(e) = current
+                                    && depth <= entity_by_id.len()
+                                {
+                                    target_field_names.extend(e.fields.iter().filter_map(|fid| {
+                                        field_by_id.get(fid).map(|f| f.name.as_str())
+                                    }));
+                                    current = e
+                                        .inherits_from
+                                        .and_then(|id| entity_by_id.get(&id).copied());
+                                    depth += 1;
+                                }
                                 if !target_field_names.contains(&displayed) {
-                                    critical_errors.push(format!(
+                                    report.critical("C42", displayed_location, format!(
                                         "Entity '{}', field '{}': list_model_displayed_field '{}' \
                                          does not exist in target entity '{}'",
                                         entity.name, field.name, displayed, target_entity.name
@@ -894,46 +1375,84 @@ impl CheckUseCase {
                     // Enum-type fields must have a valid enum_name and enum_values
                     if field.field_type == FieldType::Enum {
                         let en = field.enum_name.as_deref().unwrap_or("");
+                        let enum_name_location = format!("{}/enum_name", location);
                         if en.is_empty() {
-                            critical_errors.push(format!(
+                            report.critical("C39", enum_name_location, format!(
                                 "Entity '{}', field '{}': field_type is Enum but enum_name is empty",
                                 entity.name, field.name
                             ));
                         } else if !is_pascal_case(en) {
-                            critical_errors.push(format!(
+                            report.critical("C39", enum_name_location, format!(
                                 "Entity '{}', field '{}': enum_name '{}' must be PascalCase (expected '{}')",
                                 entity.name, field.name, en, en.to_upper_camel_case()
                             ));
                         }
                         let vals = field.enum_values.as_slice();
                         if vals.is_empty() {
-                            critical_errors.push(format!(
+                            report.critical("C40", location.clone(), format!(
                                 "Entity '{}', field '{}': field_type is Enum but enum_values is empty",
                                 entity.name, field.name
                             ));
                         } else {
                             validate_enum_values(
                                 vals,
+                                FieldDefault::from_field(field).is_some(),
                                 &format!("Entity '{}', field '{}'", entity.name, field.name),
+                                &location,
                                 &entity_names,
                                 &all_enum_names,
                                 has_cpp_target,
-                                &mut critical_errors,
+                                &mut report,
                             );
                         }
                     }
 
+                    validate_constraints(
+                        FieldConstraints::from_field(field),
+                        ValueKind::from(&field.field_type),
+                        field.is_list,
+                        field.field_type == FieldType::Entity,
+                        owned_entities.contains(&entity.id),
+                        &format!("Entity '{}', field '{}'", entity.name, field.name),
+                        &location,
+                        &mut report,
+                    );
+                    validate_index(
+                        field,
+                        &format!("Entity '{}', field '{}'", entity.name, field.name),
+                        &location,
+                        &mut report,
+                    );
+                    validate_blob_options(
+                        field.field_type == FieldType::Bytes,
+                        field.field_type == FieldType::Json,
+                        field.is_list,
+                        field.optional,
+                        &format!("Entity '{}', field '{}'", entity.name, field.name),
+                        &location,
+                        &mut report,
+                    );
+                    validate_default(
+                        field.default_value.as_deref(),
+                        DefaultKind::from(&field.field_type),
+                        field.is_list,
+                        &field.enum_values,
+                        &format!("Entity '{}', field '{}'", entity.name, field.name),
+                        &location,
+                        &mut report,
+                    );
+
                     // Entity-type fields must reference valid entities
                     if field.field_type == FieldType::Entity {
                         if let Some(ref_entity_id) = field.entity {
                             if !entity_by_id.contains_key(&ref_entity_id) {
-                                critical_errors.push(format!(
+                                report.critical("C10", format!("{}/entity", location), format!(
                                     "Entity '{}', field '{}': references non-existent entity (id {})",
                                     entity.name, field.name, ref_entity_id
                                 ));
                             }
                         } else {
-                            critical_errors.push(format!(
+                            report.critical("C11", location.clone(), format!(
                                 "Entity '{}', field '{}': type is Entity but no entity reference set",
                                 entity.name, field.name
                             ));
@@ -953,26 +1472,39 @@ impl CheckUseCase {
                 .iter()
                 .any(|f| f.name == "updated_at" && f.field_type == FieldType::DateTime);
 
+            let location = entity_location(&entity.name);
             if !has_id {
-                critical_errors.push(format!(
-                    "Entity '{}': missing required field 'id' of type UInteger \
+                report.critical(
+                    "C12",
+                    location.clone(),
+                    format!(
+                        "Entity '{}': missing required field 'id' of type UInteger \
                      (either directly or via inheritance)",
-                    entity.name
-                ));
+                        entity.name
+                    ),
+                );
             }
             if !has_created_at {
-                critical_errors.push(format!(
-                    "Entity '{}': missing required field 'created_at' of type DateTime \
+                report.critical(
+                    "C12",
+                    location.clone(),
+                    format!(
+                        "Entity '{}': missing required field 'created_at' of type DateTime \
                      (either directly or via inheritance)",
-                    entity.name
-                ));
+                        entity.name
+                    ),
+                );
             }
             if !has_updated_at {
-                critical_errors.push(format!(
-                    "Entity '{}': missing required field 'updated_at' of type DateTime \
+                report.critical(
+                    "C12",
+                    location,
+                    format!(
+                        "Entity '{}': missing required field 'updated_at' of type DateTime \
                      (either directly or via inheritance)",
-                    entity.name
-                ));
+                        entity.name
+                    ),
+                );
             }
         }
 
@@ -994,16 +1526,24 @@ impl CheckUseCase {
                 let right_entity_id = &rel.right_entity.expect("Relationship missing right_entity");
 
                 if !entity_by_id.contains_key(left_entity_id) {
-                    critical_errors.push(format!(
-                        "Relationship '{}': left_entity (id {}) does not exist",
-                        rel.field_name, left_entity_id
-                    ));
+                    report.critical(
+                        "C13",
+                        "/entities".to_string(),
+                        format!(
+                            "Relationship '{}': left_entity (id {}) does not exist",
+                            rel.field_name, left_entity_id
+                        ),
+                    );
                 }
                 if !entity_by_id.contains_key(right_entity_id) {
-                    critical_errors.push(format!(
-                        "Relationship '{}': right_entity (id {}) does not exist",
-                        rel.field_name, right_entity_id
-                    ));
+                    report.critical(
+                        "C13",
+                        "/entities".to_string(),
+                        format!(
+                            "Relationship '{}': right_entity (id {}) does not exist",
+                            rel.field_name, right_entity_id
+                        ),
+                    );
                 }
 
                 // Only check Forward relationships to avoid double-counting
@@ -1015,11 +1555,15 @@ impl CheckUseCase {
                     ) && parent.undoable
                         && !child.undoable
                     {
-                        critical_errors.push(format!(
-                            "Entity '{}' is undoable but has a strong child '{}' \
+                        report.critical(
+                            "C14",
+                            field_location(&parent.name, &rel.field_name),
+                            format!(
+                                "Entity '{}' is undoable but has a strong child '{}' \
                              that is not undoable (relationship '{}')",
-                            parent.name, child.name, rel.field_name
-                        ));
+                                parent.name, child.name, rel.field_name
+                            ),
+                        );
                     }
 
                     // Track strong parent type for multiple-parent check
@@ -1045,12 +1589,16 @@ impl CheckUseCase {
                         .iter()
                         .map(|(name, rels)| format!("'{}' (via {})", name, rels.join(", ")))
                         .collect();
-                    critical_errors.push(format!(
-                        "Entity '{}' has multiple strong parents: {}. \
+                    report.critical(
+                        "C15",
+                        entity_location(child_name),
+                        format!(
+                            "Entity '{}' has multiple strong parents: {}. \
                          An entity can only have one strong owner",
-                        child_name,
-                        parent_list.join("; ")
-                    ));
+                            child_name,
+                            parent_list.join("; ")
+                        ),
+                    );
                 }
             }
 
@@ -1071,11 +1619,15 @@ impl CheckUseCase {
                             .get(left_entity_id)
                             .map(|e| e.name.as_str())
                             .unwrap_or("?");
-                        critical_errors.push(format!(
-                            "Entity '{}', field '{}': references heritage-only entity '{}'. \
+                        report.critical(
+                            "C16",
+                            field_location(source_name, &rel.field_name),
+                            format!(
+                                "Entity '{}', field '{}': references heritage-only entity '{}'. \
                              Only 'inherits_from' may reference a heritage-only entity",
-                            source_name, rel.field_name, target.name
-                        ));
+                                source_name, rel.field_name, target.name
+                            ),
+                        );
                     }
                 }
             }
@@ -1119,11 +1671,15 @@ impl CheckUseCase {
                                 .iter()
                                 .filter_map(|(id, _)| entity_by_id.get(id).map(|e| e.name.as_str()))
                                 .collect();
-                            critical_errors.push(format!(
-                                "Cyclic strong dependency detected: {} -> {}",
-                                cycle_names.join(" -> "),
-                                cycle_names.first().unwrap_or(&"?")
-                            ));
+                            report.critical(
+                                "C17",
+                                entity_location(cycle_names.first().unwrap_or(&"")),
+                                format!(
+                                    "Cyclic strong dependency detected: {} -> {}",
+                                    cycle_names.join(" -> "),
+                                    cycle_names.first().unwrap_or(&"?")
+                                ),
+                            );
                             has_cycle = true;
                             break;
                         }
@@ -1149,28 +1705,54 @@ impl CheckUseCase {
         let features = uow.get_feature_multi(&workspace.features)?;
         let features: Vec<Feature> = features.into_iter().flatten().collect();
 
-        // Unique feature names (globally)
+        // Unique feature names (globally), across the included files
         let mut feature_names: HashSet<String> = HashSet::new();
+        let mut feature_origins: HashMap<&str, &str> = HashMap::new();
         for feature in &features {
+            let location = feature_location(&feature.name);
             if feature.name.is_empty() {
-                critical_errors.push(format!("Feature with id {} has an empty name", feature.id));
+                report.critical(
+                    "C18",
+                    location.clone(),
+                    format!("Feature with id {} has an empty name", feature.id),
+                );
             } else if !feature_names.insert(feature.name.clone()) {
-                critical_errors.push(format!("Duplicate feature name: '{}'", feature.name));
+                let first = feature_origins.get(feature.name.as_str()).unwrap_or(&"");
+                report.critical(
+                    "C19",
+                    location.clone(),
+                    format!(
+                        "Duplicate feature name: '{}', defined in {} and in {}",
+                        feature.name,
+                        origin(first),
+                        origin(&feature.source_file)
+                    ),
+                );
+            } else {
+                feature_origins.insert(&feature.name, &feature.source_file);
             }
             // Forbidden names
             if let Some(reason) = is_forbidden_name(&feature.name) {
-                critical_errors.push(format!(
-                    "Feature '{}': name is a {} and cannot be used",
-                    feature.name, reason
-                ));
+                report.critical(
+                    "C20",
+                    location.clone(),
+                    format!(
+                        "Feature '{}': name is a {} and cannot be used",
+                        feature.name, reason
+                    ),
+                );
             }
             // snake_case check
             if !feature.name.is_empty() && !is_snake_case(&feature.name) {
-                critical_errors.push(format!(
-                    "Feature '{}': name must be snake_case (expected '{}')",
-                    feature.name,
-                    feature.name.to_snake_case()
-                ));
+                report.critical(
+                    "C38",
+                    location,
+                    format!(
+                        "Feature '{}': name must be snake_case (expected '{}')",
+                        feature.name,
+                        feature.name.to_snake_case()
+                    ),
+                );
             }
         }
 
@@ -1184,10 +1766,24 @@ impl CheckUseCase {
             let uc_by_id: HashMap<EntityId, &UseCase> =
                 use_cases.iter().map(|uc| (uc.id, uc)).collect();
 
+            // Build mapping: use case id -> feature name
+            let uc_to_feature: HashMap<EntityId, &str> = features
+                .iter()
+                .flat_map(|f| {
+                    f.use_cases
+                        .iter()
+                        .map(move |&uc_id| (uc_id, f.name.as_str()))
+                })
+                .collect();
+
             // Unique use case names within each feature
             for feature in &features {
                 if feature.use_cases.is_empty() {
-                    warnings.push(format!("Feature '{}' has no use cases", feature.name));
+                    report.warning(
+                        "W03",
+                        feature_location(&feature.name),
+                        format!("Feature '{}' has no use cases", feature.name),
+                    );
                     continue;
                 }
                 let mut uc_names_in_feature: HashSet<String> = HashSet::new();
@@ -1196,202 +1792,334 @@ impl CheckUseCase {
                         && !uc.name.is_empty()
                         && !uc_names_in_feature.insert(uc.name.clone())
                     {
-                        critical_errors.push(format!(
-                            "Feature '{}': duplicate use case name '{}' \
+                        report.critical(
+                            "C21",
+                            use_case_location(&feature.name, &uc.name),
+                            format!(
+                                "Feature '{}': duplicate use case name '{}' \
                              (must be unique within its feature)",
-                            feature.name, uc.name
-                        ));
+                                feature.name, uc.name
+                            ),
+                        );
                     }
                 }
             }
 
             for uc in &use_cases {
+                let feature_name = uc_to_feature.get(&uc.id).copied().unwrap_or("");
+                let location = use_case_location(feature_name, &uc.name);
                 // Forbidden names
                 if let Some(reason) = is_forbidden_name(&uc.name) {
-                    critical_errors.push(format!(
-                        "Use case '{}': name is a {} and cannot be used",
-                        uc.name, reason
-                    ));
+                    report.critical(
+                        "C22",
+                        location.clone(),
+                        format!(
+                            "Use case '{}': name is a {} and cannot be used",
+                            uc.name, reason
+                        ),
+                    );
                 }
                 // snake_case check
                 if !uc.name.is_empty() && !is_snake_case(&uc.name) {
-                    critical_errors.push(format!(
-                        "Use case '{}': name must be snake_case (expected '{}')",
-                        uc.name,
-                        uc.name.to_snake_case()
-                    ));
+                    report.critical(
+                        "C38",
+                        location.clone(),
+                        format!(
+                            "Use case '{}': name must be snake_case (expected '{}')",
+                            uc.name,
+                            uc.name.to_snake_case()
+                        ),
+                    );
                 }
-            }
-
-            // Check use case entity references
-            for uc in &use_cases {
+                // Entity references
                 for entity_id in &uc.entities {
                     if !entity_by_id.contains_key(entity_id) {
-                        critical_errors.push(format!(
-                            "Use case '{}': references non-existent entity (id {})",
-                            uc.name, entity_id
-                        ));
+                        report.critical(
+                            "C23",
+                            format!("{}/entities", location),
+                            format!(
+                                "Use case '{}': references non-existent entity (id {})",
+                                uc.name, entity_id
+                            ),
+                        );
                     }
                 }
             }
 
             // ── DTOs and DtoFields ──
 
-            // Build mapping: use case id -> feature name
-            let uc_to_feature: HashMap<EntityId, &str> = features
-                .iter()
-                .flat_map(|f| {
-                    f.use_cases
-                        .iter()
-                        .map(move |&uc_id| (uc_id, f.name.as_str()))
-                })
-                .collect();
-
-            // Unique DTO names per feature
-            let mut dto_names_per_feature: HashMap<&str, HashSet<String>> = HashMap::new();
-
+            // Each DTO once: the shared DTOs of the features, then the inline ones of
+            // their use cases, with (feature name, location, owner) for the reports
+            let mut dto_sites: Vec<(&str, String, String, EntityId)> = vec![];
+            for feature in &features {
+                let shared_dtos = uow.get_dto_multi(&feature.dtos)?;
+                for (dto_id, dto) in feature.dtos.iter().zip(shared_dtos) {
+                    let dto_name = dto.map(|dto| dto.name).unwrap_or_default();
+                    dto_sites.push((
+                        feature.name.as_str(),
+                        format!(
+                            "{}{}",
+                            feature_location(&feature.name),
+                            source_map::location(&["dtos", &dto_name])
+                        ),
+                        format!("Feature '{}': shared DTO", feature.name),
+                        *dto_id,
+                    ));
+                }
+            }
             for uc in &use_cases {
-                let dto_ids: Vec<EntityId> =
-                    [uc.dto_in, uc.dto_out].iter().flatten().copied().collect();
                 let feature_name = uc_to_feature.get(&uc.id).copied().unwrap_or("?");
+                for (which, dto_id) in [("dto_in", uc.dto_in), ("dto_out", uc.dto_out)] {
+                    if let Some(dto_id) = dto_id {
+                        dto_sites.push((
+                            feature_name,
+                            format!("{}/{}", use_case_location(feature_name, &uc.name), which),
+                            format!("Use case '{}': {}", uc.name, which),
+                            dto_id,
+                        ));
+                    }
+                }
+            }
 
-                for dto_id in dto_ids {
-                    if let Some(dto) = uow.get_dto(&dto_id)? {
-                        if dto.fields.is_empty() {
-                            let which = if uc.dto_in == Some(dto_id) {
-                                "dto_in"
-                            } else {
-                                "dto_out"
-                            };
-                            warnings.push(format!(
-                                "Use case '{}': {} '{}' has no fields",
-                                uc.name, which, dto.name
-                            ));
-                        }
+            // Unique DTO names per feature
+            let mut dto_names_per_feature: HashMap<&str, HashSet<String>> = HashMap::new();
+            // Nested DTO references, to find nesting cycles
+            let mut nested_dtos: HashMap<EntityId, Vec<EntityId>> = HashMap::new();
+
+            for (feature_name, location, owner, dto_id) in dto_sites {
+                if let Some(dto) = uow.get_dto(&dto_id)? {
+                    if dto.fields.is_empty() {
+                        report.warning(
+                            "W04",
+                            location.clone(),
+                            format!("{} '{}' has no fields", owner, dto.name),
+                        );
+                    }
 
-                        // Unique DTO names within their feature
-                        if !dto.name.is_empty() {
-                            let names = dto_names_per_feature.entry(feature_name).or_default();
-                            if !names.insert(dto.name.clone()) {
-                                critical_errors.push(format!(
+                    // Unique DTO names within their feature
+                    if !dto.name.is_empty() {
+                        let names = dto_names_per_feature.entry(feature_name).or_default();
+                        if !names.insert(dto.name.clone()) {
+                            report.critical(
+                                "C25",
+                                location.clone(),
+                                format!(
                                     "Feature '{}': duplicate DTO name '{}' \
                                      (must be unique within its feature)",
                                     feature_name, dto.name
-                                ));
-                            }
+                                ),
+                            );
                         }
+                    }
 
-                        // Forbidden DTO names
-                        if let Some(reason) = is_forbidden_name(&dto.name) {
-                            critical_errors.push(format!(
+                    // Forbidden DTO names
+                    if let Some(reason) = is_forbidden_name(&dto.name) {
+                        report.critical(
+                            "C26",
+                            location.clone(),
+                            format!(
                                 "DTO '{}': name is a {} and cannot be used",
                                 dto.name, reason
-                            ));
-                        }
-                        // PascalCase check
-                        if !dto.name.is_empty() && !is_pascal_case(&dto.name) {
-                            critical_errors.push(format!(
+                            ),
+                        );
+                    }
+                    // PascalCase check
+                    if !dto.name.is_empty() && !is_pascal_case(&dto.name) {
+                        report.critical(
+                            "C37",
+                            location.clone(),
+                            format!(
                                 "DTO '{}': name must be PascalCase (expected '{}')",
                                 dto.name,
                                 dto.name.to_upper_camel_case()
-                            ));
-                        }
+                            ),
+                        );
+                    }
 
-                        // DtoField checks
-                        if !dto.fields.is_empty() {
-                            let dto_fields = uow.get_dto_field_multi(&dto.fields)?;
-                            let dto_fields: Vec<DtoField> =
-                                dto_fields.into_iter().flatten().collect();
+                    // DtoField checks
+                    if !dto.fields.is_empty() {
+                        let dto_fields = uow.get_dto_field_multi(&dto.fields)?;
+                        let dto_fields: Vec<DtoField> = dto_fields.into_iter().flatten().collect();
 
-                            // Collect enum names from DTO fields
-                            for df in &dto_fields {
-                                if df.field_type == DtoFieldType::Enum
-                                    && let Some(ref en) = df.enum_name
-                                {
-                                    all_enum_names.insert(en.clone());
-                                }
+                        // Collect enum names from DTO fields
+                        for df in &dto_fields {
+                            if df.field_type == DtoFieldType::Enum
+                                && let Some(ref en) = df.enum_name
+                            {
+                                all_enum_names.insert(en.clone());
                             }
+                        }
 
-                            // Unique DtoField names within their owning DTO
-                            let mut df_names: HashSet<String> = HashSet::new();
-                            for df in &dto_fields {
-                                if !df_names.insert(df.name.clone()) {
-                                    critical_errors.push(format!(
+                        // Unique DtoField names within their owning DTO
+                        let mut df_names: HashSet<String> = HashSet::new();
+                        for df in &dto_fields {
+                            let location = format!(
+                                "{}{}",
+                                location,
+                                source_map::location(&["fields", &df.name])
+                            );
+                            if !df_names.insert(df.name.clone()) {
+                                report.critical(
+                                    "C27",
+                                    location.clone(),
+                                    format!(
                                         "DTO '{}': duplicate field name '{}'",
                                         dto.name, df.name
-                                    ));
-                                }
-                                // Forbidden DtoField names
-                                if let Some(reason) = is_forbidden_name(&df.name) {
-                                    critical_errors.push(format!(
+                                    ),
+                                );
+                            }
+                            // Forbidden DtoField names
+                            if let Some(reason) = is_forbidden_name(&df.name) {
+                                report.critical(
+                                    "C28",
+                                    location.clone(),
+                                    format!(
                                         "DTO '{}', field '{}': name is a {} and cannot be used",
                                         dto.name, df.name, reason
-                                    ));
-                                }
-                                // snake_case check
-                                if !df.name.is_empty() && !is_snake_case(&df.name) {
-                                    critical_errors.push(format!(
+                                    ),
+                                );
+                            }
+                            // snake_case check
+                            if !df.name.is_empty() && !is_snake_case(&df.name) {
+                                report.critical("C38", location.clone(), format!(
                                         "DTO '{}', field '{}': name must be snake_case (expected '{}')",
                                         dto.name,
                                         df.name,
                                         df.name.to_snake_case()
                                     ));
-                                }
-                                // A DtoField cannot be both optional and is_list
-                                if df.optional && df.is_list {
-                                    critical_errors.push(format!(
+                            }
+                            if let Some(nested_id) = df.dto {
+                                nested_dtos.entry(dto.id).or_default().push(nested_id);
+                            }
+                            // A DtoField cannot be both optional and is_list
+                            if df.optional && df.is_list {
+                                report.critical(
+                                    "C29",
+                                    location.clone(),
+                                    format!(
                                         "DTO '{}', field '{}': cannot be both optional and is_list",
                                         dto.name, df.name
-                                    ));
-                                }
-                                // Enum-type DtoFields must have a valid enum_name and enum_values
-                                if df.field_type == DtoFieldType::Enum {
-                                    let en = df.enum_name.as_deref().unwrap_or("");
-                                    if en.is_empty() {
-                                        critical_errors.push(format!(
+                                    ),
+                                );
+                            }
+                            // Enum-type DtoFields must have a valid enum_name and enum_values
+                            if df.field_type == DtoFieldType::Enum {
+                                let en = df.enum_name.as_deref().unwrap_or("");
+                                let enum_name_location = format!("{}/enum_name", location);
+                                if en.is_empty() {
+                                    report.critical("C39", enum_name_location, format!(
                                             "DTO '{}', field '{}': field_type is Enum but enum_name is empty",
                                             dto.name, df.name
                                         ));
-                                    } else if !is_pascal_case(en) {
-                                        critical_errors.push(format!(
+                                } else if !is_pascal_case(en) {
+                                    report.critical("C39", enum_name_location, format!(
                                             "DTO '{}', field '{}': enum_name '{}' must be PascalCase (expected '{}')",
                                             dto.name, df.name, en, en.to_upper_camel_case()
                                         ));
-                                    }
-                                    let vals = df.enum_values.as_slice();
-                                    if vals.is_empty() {
-                                        critical_errors.push(format!(
+                                }
+                                let vals = df.enum_values.as_slice();
+                                if vals.is_empty() {
+                                    report.critical("C40", location.clone(), format!(
                                             "DTO '{}', field '{}': field_type is Enum but enum_values is empty",
                                             dto.name, df.name
                                         ));
-                                    } else {
-                                        validate_enum_values(
-                                            vals,
-                                            &format!("DTO '{}', field '{}'", dto.name, df.name),
-                                            &entity_names,
-                                            &all_enum_names,
-                                            has_cpp_target,
-                                            &mut critical_errors,
-                                        );
-                                    }
+                                } else {
+                                    validate_enum_values(
+                                        vals,
+                                        FieldDefault::from_dto_field(df).is_some(),
+                                        &format!("DTO '{}', field '{}'", dto.name, df.name),
+                                        &location,
+                                        &entity_names,
+                                        &all_enum_names,
+                                        has_cpp_target,
+                                        &mut report,
+                                    );
                                 }
                             }
+                            validate_constraints(
+                                FieldConstraints::from_dto_field(df),
+                                ValueKind::from(&df.field_type),
+                                df.is_list,
+                                false,
+                                false,
+                                &format!("DTO '{}', field '{}'", dto.name, df.name),
+                                &location,
+                                &mut report,
+                            );
+                            validate_blob_options(
+                                df.field_type == DtoFieldType::Bytes,
+                                df.field_type == DtoFieldType::Json,
+                                df.is_list,
+                                df.optional,
+                                &format!("DTO '{}', field '{}'", dto.name, df.name),
+                                &location,
+                                &mut report,
+                            );
+                            validate_default(
+                                df.default_value.as_deref(),
+                                DefaultKind::from(&df.field_type),
+                                df.is_list,
+                                &df.enum_values,
+                                &format!("DTO '{}', field '{}'", dto.name, df.name),
+                                &location,
+                                &mut report,
+                            );
                         }
-                    } else {
-                        critical_errors.push(format!(
-                            "Use case '{}': DTO (id {}) not found",
-                            uc.name, dto_id
-                        ));
+                    }
+                } else {
+                    report.critical(
+                        "C24",
+                        location,
+                        format!("{} (id {}) not found", owner, dto_id),
+                    );
+                }
+            }
+
+            // A DTO must not contain itself, directly or through other DTOs
+            for feature in &features {
+                for dto_id in &feature.dtos {
+                    if let Some(path) = find_dto_cycle(*dto_id, &nested_dtos)
+                        && let Some(dto) = uow.get_dto(dto_id)?
+                    {
+                        let mut names = vec![];
+                        for id in path {
+                            names.push(uow.get_dto(&id)?.map(|d| d.name).unwrap_or_default());
+                        }
+                        report.critical(
+                            "C52",
+                            format!(
+                                "{}{}",
+                                feature_location(&feature.name),
+                                source_map::location(&["dtos", &dto.name])
+                            ),
+                            format!(
+                                "Feature '{}': DTO '{}' contains itself ({})",
+                                feature.name,
+                                dto.name,
+                                names.join(" -> ")
+                            ),
+                        );
                     }
                 }
             }
         }
 
+        // ── Custom files ──
+        let custom_files = uow.get_custom_file_multi(&workspace.custom_files)?;
+        for custom_file in custom_files.iter().flatten() {
+            for error in custom_files::validate_custom_file(custom_file) {
+                report.critical(
+                    "C49",
+                    source_map::location(&["custom_files", &custom_file.path]),
+                    format!("Custom file '{}': {}", custom_file.path, error),
+                );
+            }
+        }
+
         uow.end_transaction()?;
 
-        Ok(CheckReturnDto {
-            warnings,
-            critical_errors,
-        })
+        // The positions are those of the texts the workspace was loaded or saved from
+        let source_map = SourceMap::from_workspace(&workspace);
+        Ok(report.into_return_dto(Some(&source_map)))
     }
 }
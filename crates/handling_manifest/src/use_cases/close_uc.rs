@@ -8,6 +8,7 @@ pub trait CloseUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn CloseUnitOfWorkTrait>;
 }
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "System", action = "GetRelationship")]
 #[macros::uow_action(entity = "File", action = "RemoveMulti")]
 #[macros::uow_action(entity = "Workspace", action = "GetAll")]
@@ -1,3 +1,18 @@
+pub mod documents;
+pub mod includes;
+pub mod migration;
 pub mod model_structs;
+pub mod source_map;
+pub mod tools;
+pub mod yaml_edit;
 
 pub const CURRENT_SCHEMA_VERSION: i64 = 5;
+
+/// Parse a YAML or JSON manifest, depending on the extension of `filename`.
+pub fn parse(filename: &str, source: &str) -> anyhow::Result<serde_json::Value> {
+    match filename.split('.').next_back() {
+        Some("yaml") => Ok(serde_yml::from_str(source)?),
+        Some("json") => Ok(serde_json::from_str(source)?),
+        _ => Err(anyhow::anyhow!("File extension not supported")),
+    }
+}
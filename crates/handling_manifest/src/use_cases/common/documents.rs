@@ -0,0 +1,33 @@
+//! The text of the manifest files as they were last loaded or saved.
+//!
+//! The load and save use cases keep the texts in the workspace, and the save
+//! use case edits them instead of writing the manifest again, so that
+//! comments, blank lines and key order survive a save from the GUI.
+
+use common::entities::Workspace;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct Documents {
+    pub manifest: String,
+    /// Included files, by path relative to the manifest directory
+    pub included: HashMap<String, String>,
+}
+
+impl Documents {
+    /// Texts of the manifest of `workspace`, if it was loaded or saved.
+    pub fn from_workspace(workspace: &Workspace) -> Option<Documents> {
+        if workspace.manifest_text.is_empty() {
+            return None;
+        }
+        Some(Documents {
+            manifest: workspace.manifest_text.clone(),
+            included: workspace
+                .included_files
+                .iter()
+                .cloned()
+                .zip(workspace.included_texts.iter().cloned())
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,193 @@
+//! Files listed by the `includes` of a manifest.
+//!
+//! Patterns are relative to the manifest directory: `*` and `?` stay within a
+//! path component, `**` crosses directories.
+
+use anyhow::{Result, anyhow};
+use globset::GlobBuilder;
+use std::path::{Component, Path};
+use walkdir::WalkDir;
+
+/// A file listed by `includes` that cannot be read or does not parse.
+#[derive(Debug)]
+pub struct IncludedFileError {
+    /// Path relative to the manifest directory
+    pub file: String,
+    pub error: anyhow::Error,
+}
+
+impl std::fmt::Display for IncludedFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Included file {}", self.file)
+    }
+}
+
+impl std::error::Error for IncludedFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+/// The files matched by `patterns`, relative to `manifest_dir` with forward
+/// slashes. Files come in pattern order, sorted within a pattern, each once.
+/// A pattern without wildcard is a file, whether it exists or not.
+pub fn expand(manifest_dir: &Path, patterns: &[String]) -> Result<Vec<String>> {
+    let mut files: Vec<String> = vec![];
+    for pattern in patterns {
+        let pattern = pattern.trim_start_matches("./");
+        if Path::new(pattern)
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err(anyhow!(
+                "Include '{}' must be a relative path inside the manifest directory",
+                pattern
+            ));
+        }
+
+        let mut matched = vec![];
+        if !has_wildcard(pattern) {
+            matched.push(pattern.to_string());
+        } else {
+            let matcher = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| anyhow!("Invalid include pattern '{}': {}", pattern, e))?
+                .compile_matcher();
+            // Only walk the directory before the first wildcard
+            let base: Vec<&str> = pattern
+                .split('/')
+                .take_while(|component| !has_wildcard(component))
+                .collect();
+            let base_dir = manifest_dir.join(base.join("/"));
+            for entry in WalkDir::new(&base_dir).into_iter().flatten() {
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let Ok(relative) = entry.path().strip_prefix(manifest_dir) else {
+                    continue;
+                };
+                let relative = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if matcher.is_match(&relative) {
+                    matched.push(relative);
+                }
+            }
+            matched.sort();
+        }
+
+        for file in matched {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// The files included by the manifest at `manifest_path`, which is never one
+/// of them even when a pattern like `*.yaml` matches it.
+pub fn included_files(
+    manifest_dir: &Path,
+    manifest_path: &str,
+    patterns: &[String],
+) -> Result<Vec<String>> {
+    let manifest_name = Path::new(manifest_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string());
+    Ok(expand(manifest_dir, patterns)?
+        .into_iter()
+        .filter(|file| Some(file) != manifest_name.as_ref())
+        .collect())
+}
+
+/// The files included by the manifest file at `manifest_path`, read from the
+/// disk. Only its `includes` list is read: the manifest need not be valid.
+pub fn read_included_files(manifest_path: &Path) -> Result<Vec<String>> {
+    let source = std::fs::read_to_string(manifest_path)?;
+    let json_value = super::parse(&manifest_path.to_string_lossy(), &source)?;
+    let patterns: Vec<String> = json_value
+        .get("includes")
+        .and_then(|includes| includes.as_array())
+        .map(|includes| {
+            includes
+                .iter()
+                .filter_map(|pattern| pattern.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let manifest_dir = manifest_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    included_files(manifest_dir, &manifest_path.to_string_lossy(), &patterns)
+}
+
+fn has_wildcard(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let dir = std::env::temp_dir().join(format!("qleany_includes_{}", std::process::id()));
+        for file in [
+            "entities/car.yaml",
+            "entities/wheel.yaml",
+            "entities/notes.txt",
+            "features/sales/sales.yaml",
+        ] {
+            let path = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "entities: []\n").unwrap();
+        }
+
+        let files = expand(
+            &dir,
+            &[
+                "entities/wheel.yaml".to_string(),
+                "entities/*.yaml".to_string(),
+                "features/**/*.yaml".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            files,
+            vec![
+                "entities/wheel.yaml",
+                "entities/car.yaml",
+                "features/sales/sales.yaml"
+            ]
+        );
+
+        assert_eq!(
+            expand(&dir, &["entities/new.yaml".to_string()]).unwrap(),
+            vec!["entities/new.yaml"]
+        );
+        assert!(expand(&dir, &["../outside.yaml".to_string()]).is_err());
+        assert!(
+            expand(&dir, &["missing/*.yaml".to_string()])
+                .unwrap()
+                .is_empty()
+        );
+
+        let manifest = dir.join("app.yaml");
+        std::fs::write(
+            &manifest,
+            "includes:\n  - \"*.yaml\"\n  - entities/*.yaml\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_included_files(&manifest).unwrap(),
+            vec!["entities/car.yaml", "entities/wheel.yaml"]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,410 @@
+//! Migration of older manifests to the current schema version.
+//!
+//! Each step lists the keys it removes, renames or adds with a default value,
+//! so that migrating a manifest also gives the change log `qleany upgrade`
+//! reports, with the manifest location of every key it touched.
+
+use crate::use_cases::common::CURRENT_SCHEMA_VERSION;
+use crate::use_cases::common::source_map;
+use crate::{MigrationChangeDto, MigrationChangeKind};
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+/// The changes made to the keys of the manifest by one migration step.
+pub struct MigrationStep {
+    /// Schema version the step migrates to, from the version just before
+    pub to_version: i64,
+    pub keys: &'static [KeyMigration],
+}
+
+pub struct KeyMigration {
+    /// Manifest location of the key, where `*` stands for every item of a
+    /// sequence or every key of a mapping: `/entities/*/allow_direct_access`
+    pub pattern: &'static str,
+    pub kind: MigrationChangeKind,
+    /// New key name for `Renamed`, YAML value for `Defaulted`
+    pub argument: &'static str,
+}
+
+const STEPS: &[MigrationStep] = &[
+    // allow_direct_access is gone, every entity gets its direct access files
+    MigrationStep {
+        to_version: 3,
+        keys: &[KeyMigration {
+            pattern: "/entities/*/allow_direct_access",
+            kind: MigrationChangeKind::Removed,
+            argument: "",
+        }],
+    },
+    // validation is the responsibility of the developer
+    MigrationStep {
+        to_version: 4,
+        keys: &[KeyMigration {
+            pattern: "/features/*/use_cases/*/validator",
+            kind: MigrationChangeKind::Removed,
+            argument: "",
+        }],
+    },
+    // additive: is_list on entity fields
+    MigrationStep {
+        to_version: 5,
+        keys: &[],
+    },
+];
+
+/// Schema version of a manifest JSON value.
+pub fn schema_version(value: &Value) -> Result<i64> {
+    value
+        .get("schema")
+        .and_then(|s| s.get("version"))
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| anyhow!("Missing or invalid schema.version"))
+}
+
+/// Migrate a manifest JSON value to the current schema version and return
+/// the changes made to its keys, in order.
+/// Operates on raw `serde_json::Value` before schema validation.
+pub fn migrate_to_current(value: &mut Value) -> Result<Vec<MigrationChangeDto>> {
+    let version = schema_version(value)?;
+
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(anyhow!(
+            "Manifest schema version {} is newer than supported version {}",
+            version,
+            CURRENT_SCHEMA_VERSION
+        ));
+    }
+
+    let mut changes = vec![];
+    for step in STEPS.iter().filter(|step| step.to_version > version) {
+        changes.extend(apply_step(value, step)?);
+    }
+
+    Ok(changes)
+}
+
+/// Apply one migration step and bump the version to the step's.
+fn apply_step(value: &mut Value, step: &MigrationStep) -> Result<Vec<MigrationChangeDto>> {
+    let mut changes = vec![];
+    for key in step.keys {
+        let (parent_pattern, key_name) = key
+            .pattern
+            .rsplit_once('/')
+            .ok_or_else(|| anyhow!("Invalid migration pattern {}", key.pattern))?;
+        let parent_segments: Vec<&str> = parent_pattern.split('/').skip(1).collect();
+        let mut parents = vec![];
+        collect_mappings(value, &parent_segments, vec![], &mut parents);
+
+        for (segments, parent) in parents {
+            let mut key_segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+            key_segments.push(key_name);
+            let location = source_map::location(&key_segments);
+            let change = |new_key: Option<String>, value: &Value| MigrationChangeDto {
+                kind: key.kind.clone(),
+                location: location.clone(),
+                new_key,
+                value: to_yaml(value),
+                to_version: step.to_version,
+            };
+
+            match key.kind {
+                MigrationChangeKind::Removed => {
+                    if let Some(removed) = parent.shift_remove(key_name) {
+                        changes.push(change(None, &removed));
+                    }
+                }
+                MigrationChangeKind::Renamed => {
+                    if parent.contains_key(key.argument) {
+                        continue;
+                    }
+                    if let Some(index) = parent.keys().position(|k| k == key_name) {
+                        // rebuild the mapping to keep the key where it was
+                        let entries: Vec<(String, Value)> =
+                            std::mem::take(parent).into_iter().collect();
+                        for (i, (k, v)) in entries.into_iter().enumerate() {
+                            if i == index {
+                                changes.push(change(Some(key.argument.to_string()), &v));
+                                parent.insert(key.argument.to_string(), v);
+                            } else {
+                                parent.insert(k, v);
+                            }
+                        }
+                    }
+                }
+                MigrationChangeKind::Defaulted => {
+                    if !parent.contains_key(key_name) {
+                        let default: Value = serde_yml::from_str(key.argument)?;
+                        changes.push(change(None, &default));
+                        parent.insert(key_name.to_string(), default);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(schema) = value.get_mut("schema").and_then(|s| s.as_object_mut()) {
+        schema.insert("version".to_string(), Value::Number(step.to_version.into()));
+    }
+
+    Ok(changes)
+}
+
+/// Collect the mappings matching `pattern`, with the segments of their
+/// manifest location. Sequence items are named after their `name` (or `path`).
+fn collect_mappings<'a>(
+    value: &'a mut Value,
+    pattern: &[&str],
+    segments: Vec<String>,
+    found: &mut Vec<(Vec<String>, &'a mut serde_json::Map<String, Value>)>,
+) {
+    let Some((head, rest)) = pattern.split_first() else {
+        if let Value::Object(mapping) = value {
+            found.push((segments, mapping));
+        }
+        return;
+    };
+    if *head != "*" {
+        if let Value::Object(mapping) = value
+            && let Some(child_value) = mapping.get_mut(*head)
+        {
+            let mut child = segments;
+            child.push(head.to_string());
+            collect_mappings(child_value, rest, child, found);
+        }
+        return;
+    }
+    match value {
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                let name = ["name", "path"]
+                    .iter()
+                    .find_map(|k| item.get(*k).and_then(Value::as_str))
+                    .map_or_else(|| i.to_string(), str::to_string);
+                let mut child = segments.clone();
+                child.push(name);
+                collect_mappings(item, rest, child, found);
+            }
+        }
+        Value::Object(mapping) => {
+            for (key, child_value) in mapping.iter_mut() {
+                let mut child = segments.clone();
+                child.push(key.clone());
+                collect_mappings(child_value, rest, child, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A value as YAML, on one line for scalars.
+fn to_yaml(value: &Value) -> String {
+    serde_yml::to_string(value)
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_migrate_v2_strips_allow_direct_access() {
+        let mut value = json!({
+            "schema": { "version": 2 },
+            "global": { "language": "rust", "application_name": "Test", "organisation": { "name": "Test", "domain": "test.com" }, "prefix_path": "" },
+            "entities": [
+                { "name": "Foo", "allow_direct_access": true, "fields": [] },
+                { "name": "Bar", "allow_direct_access": false, "fields": [] }
+            ],
+            "features": [],
+            "ui": {}
+        });
+
+        migrate_to_current(&mut value).unwrap();
+
+        // Version bumped all the way to current
+        assert_eq!(value["schema"]["version"], CURRENT_SCHEMA_VERSION);
+
+        // allow_direct_access removed from all entities
+        for entity in value["entities"].as_array().unwrap() {
+            assert!(entity.get("allow_direct_access").is_none());
+        }
+    }
+
+    #[test]
+    fn test_v3_passes_through() {
+        let mut value = json!({
+            "schema": { "version": 3 },
+            "global": { "language": "rust", "application_name": "Test", "organisation": { "name": "Test", "domain": "test.com" }, "prefix_path": "" },
+            "entities": [
+                { "name": "Foo", "fields": [] }
+            ],
+            "features": [],
+            "ui": {}
+        });
+
+        migrate_to_current(&mut value).unwrap();
+        assert_eq!(value["schema"]["version"], CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_v3_strips_validator() {
+        let mut value = json!({
+            "schema": { "version": 3 },
+            "global": { "language": "rust", "application_name": "Test", "organisation": { "name": "Test", "domain": "test.com" }, "prefix_path": "" },
+            "entities": [
+            ],
+            "features": [
+                { "name": "Foo", "use_cases": [
+                    { "name": "Bar", "validator": true }
+                ]}
+            ],
+            "ui": {}
+        });
+
+        migrate_to_current(&mut value).unwrap();
+
+        // Version bumped all the way to current
+        assert_eq!(value["schema"]["version"], CURRENT_SCHEMA_VERSION);
+
+        // validator removed from all use cases
+        for feature in value["features"].as_array().unwrap() {
+            for uc in feature["use_cases"].as_array().unwrap() {
+                assert!(uc.get("validator").is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_migrate_v4_to_v5() {
+        let mut value = json!({
+            "schema": { "version": 4 },
+            "global": { "language": "rust", "application_name": "Test", "organisation": { "name": "Test", "domain": "test.com" }, "prefix_path": "" },
+            "entities": [],
+            "features": [
+                    { "name": "Foo", "use_cases": [
+                        { "name": "Bar" }
+                    ]}
+            ],
+            "ui": {}
+        });
+
+        migrate_to_current(&mut value).unwrap();
+        assert_eq!(value["schema"]["version"], CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_v5_passes_through() {
+        let mut value = json!({
+            "schema": { "version": 5 },
+            "global": { "language": "rust", "application_name": "Test", "organisation": { "name": "Test", "domain": "test.com" }, "prefix_path": "" },
+            "entities": [],
+            "features": [
+                    { "name": "Foo", "use_cases": [
+                        { "name": "Bar" }
+                    ]}
+            ],
+            "ui": {}
+        });
+
+        migrate_to_current(&mut value).unwrap();
+        assert_eq!(value["schema"]["version"], CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_future_version_errors() {
+        let mut value = json!({
+            "schema": { "version": 99 },
+            "global": {},
+            "entities": [],
+            "features": [],
+            "ui": {}
+        });
+
+        let result = migrate_to_current(&mut value);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("newer than supported")
+        );
+    }
+
+    #[test]
+    fn test_migration_reports_removed_keys() {
+        let mut value = json!({
+            "schema": { "version": 2 },
+            "entities": [
+                { "name": "Foo", "allow_direct_access": true, "fields": [] },
+                { "name": "Bar", "fields": [] }
+            ],
+            "features": [
+                { "name": "sales", "use_cases": [
+                    { "name": "buy", "validator": false }
+                ]}
+            ]
+        });
+
+        let changes = migrate_to_current(&mut value).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                MigrationChangeDto {
+                    kind: MigrationChangeKind::Removed,
+                    location: "/entities/Foo/allow_direct_access".to_string(),
+                    new_key: None,
+                    value: "true".to_string(),
+                    to_version: 3,
+                },
+                MigrationChangeDto {
+                    kind: MigrationChangeKind::Removed,
+                    location: "/features/sales/use_cases/buy/validator".to_string(),
+                    new_key: None,
+                    value: "false".to_string(),
+                    to_version: 4,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_step_renames_and_defaults_keys_in_place() {
+        let mut value = json!({
+            "schema": { "version": 5 },
+            "entities": [
+                { "name": "Foo", "old": 1, "fields": [] }
+            ]
+        });
+        let step = MigrationStep {
+            to_version: 6,
+            keys: &[
+                KeyMigration {
+                    pattern: "/entities/*/old",
+                    kind: MigrationChangeKind::Renamed,
+                    argument: "new",
+                },
+                KeyMigration {
+                    pattern: "/entities/*/kind",
+                    kind: MigrationChangeKind::Defaulted,
+                    argument: "plain",
+                },
+            ],
+        };
+
+        let changes = apply_step(&mut value, &step).unwrap();
+
+        let keys: Vec<&String> = value["entities"][0].as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["name", "new", "fields", "kind"]);
+        assert_eq!(value["entities"][0]["kind"], "plain");
+        assert_eq!(value["schema"]["version"], 6);
+        assert_eq!(changes[0].kind, MigrationChangeKind::Renamed);
+        assert_eq!(changes[0].location, "/entities/Foo/old");
+        assert_eq!(changes[0].new_key.as_deref(), Some("new"));
+        assert_eq!(changes[1].kind, MigrationChangeKind::Defaulted);
+        assert_eq!(changes[1].location, "/entities/Foo/kind");
+        assert_eq!(changes[1].value, "plain");
+    }
+}
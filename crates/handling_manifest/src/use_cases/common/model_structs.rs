@@ -25,6 +25,49 @@ pub struct Global {
     pub application_name: String,
     pub organisation: Organisation,
     pub prefix_path: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub templates_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Constraints {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<serde_json::Number>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<serde_json::Number>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<i64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<i64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub not_empty: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub unique_in_parent: bool,
+}
+
+impl Constraints {
+    pub fn is_empty(&self) -> bool {
+        self.min.is_none()
+            && self.max.is_none()
+            && self.min_length.is_none()
+            && self.max_length.is_none()
+            && self.pattern.is_none()
+            && !self.not_empty
+            && !self.unique_in_parent
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -58,6 +101,19 @@ pub struct Field {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub enum_values: Vec<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed: Option<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique: Option<bool>,
+    /// Literal value, enum variant, `now` or `new_uuid`
+    #[serde(default, rename = "default")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<serde_json::Value>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<Constraints>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -75,6 +131,9 @@ pub struct Entity {
     pub fields: Vec<Field>,
     #[serde(default = "serde_true")]
     pub undoable: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undo_journal: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -93,6 +152,16 @@ pub struct DtoField {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub enum_values: Vec<String>,
+    /// Name of one of the feature's shared DTOs, for a field of type `dto`
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dto: Option<String>,
+    #[serde(default, rename = "default")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<serde_json::Value>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<Constraints>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -101,6 +170,14 @@ pub struct Dto {
     pub fields: Vec<DtoField>,
 }
 
+/// A use case DTO: declared inline, or the name of one of the feature's shared DTOs.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DtoRef {
+    Shared(String),
+    Inline(Dto),
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct UseCase {
     pub name: String,
@@ -118,15 +195,19 @@ pub struct UseCase {
     pub long_operation: Option<bool>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub dto_in: Option<Dto>,
+    pub dto_in: Option<DtoRef>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub dto_out: Option<Dto>,
+    pub dto_out: Option<DtoRef>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Feature {
     pub name: String,
+    /// DTOs shared by the use cases of the feature
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dtos: Vec<Dto>,
     pub use_cases: Vec<UseCase>,
 }
 
@@ -151,11 +232,45 @@ pub struct Ui {
     pub rust_android: bool,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct CustomFile {
+    pub path: String,
+    pub template: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nature: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Manifest {
     pub schema: Schema,
     pub global: Global,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub includes: Vec<String>,
+    #[serde(default)]
     pub entities: Vec<Entity>,
+    #[serde(default)]
     pub features: Vec<Feature>,
     pub ui: Ui,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub custom_files: Vec<CustomFile>,
+}
+
+/// A file listed by the `includes` of the manifest.
+#[derive(Serialize, Deserialize, Default)]
+pub struct IncludedManifest {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<Entity>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<Feature>,
 }
@@ -0,0 +1,306 @@
+//! Positions of the manifest nodes in the YAML source.
+//!
+//! Nodes are addressed by a manifest location, a JSON Pointer where items of
+//! sequences are named after their `name` (or `path` for custom files) instead
+//! of their index: `/entities/Car/fields/name`, `/features/sales/use_cases/buy`.
+//! The check use case indexes the texts the workspace was loaded or saved
+//! from, with the files it includes, and attaches the positions to its
+//! diagnostics.
+
+use common::entities::Workspace;
+use serde_yml::de::{Event, Progress};
+use serde_yml::loader::Loader;
+use std::collections::HashMap;
+
+/// A 1-based line and column in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcePosition {
+    /// The included file, relative to the manifest directory, if not the manifest itself
+    pub file: Option<String>,
+    pub line: u64,
+    pub column: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    positions: HashMap<String, SourcePosition>,
+}
+
+enum NodeKind {
+    Scalar(String),
+    Sequence(Vec<Node>),
+    Mapping(Vec<(Node, Node)>),
+    Other,
+}
+
+struct Node {
+    position: SourcePosition,
+    kind: NodeKind,
+}
+
+impl SourceMap {
+    /// Index the nodes of a YAML (or JSON) manifest. Returns an empty map if it
+    /// does not parse; load reports the actual error.
+    pub fn from_yaml(text: &str) -> SourceMap {
+        let mut source_map = SourceMap::default();
+        let Ok(mut loader) = Loader::new(Progress::Str(text)) else {
+            return source_map;
+        };
+        let Some(document) = loader.next_document() else {
+            return source_map;
+        };
+        if document.error.is_some() {
+            return source_map;
+        }
+        let mut events = document.events.into_iter().map(|(event, mark)| {
+            let position = SourcePosition {
+                file: None,
+                line: mark.line() + 1,
+                column: mark.column() + 1,
+            };
+            (event, position)
+        });
+        if let Some(root) = parse_node(&mut events) {
+            source_map.index(&root, String::new());
+        }
+        source_map
+    }
+
+    /// Index the manifest and included files texts kept in `workspace`.
+    pub fn from_workspace(workspace: &Workspace) -> SourceMap {
+        let mut source_map = SourceMap::from_yaml(&workspace.manifest_text);
+        for (file, text) in workspace
+            .included_files
+            .iter()
+            .zip(&workspace.included_texts)
+        {
+            source_map.include(file, SourceMap::from_yaml(text));
+        }
+        source_map
+    }
+
+    /// Position of the node at `location`, or of its closest indexed ancestor.
+    pub fn position(&self, location: &str) -> Option<SourcePosition> {
+        let mut location = location;
+        loop {
+            if let Some(position) = self.positions.get(location) {
+                return Some(position.clone());
+            }
+            let (parent, _) = location.rsplit_once('/')?;
+            if parent.is_empty() {
+                return None;
+            }
+            location = parent;
+        }
+    }
+
+    /// Position of the node at exactly `location`: a mapping key, or the
+    /// start of a sequence item.
+    pub fn get(&self, location: &str) -> Option<&SourcePosition> {
+        self.positions.get(location)
+    }
+
+    /// Add the entities and features of a file included by the manifest.
+    pub fn include(&mut self, file: &str, included: SourceMap) {
+        for (location, position) in included.positions {
+            let is_item = ["/entities/", "/features/"]
+                .iter()
+                .any(|section| location.starts_with(section));
+            if is_item {
+                let position = SourcePosition {
+                    file: Some(file.to_string()),
+                    ..position
+                };
+                self.positions.insert(location, position);
+            }
+        }
+    }
+
+    fn index(&mut self, node: &Node, location: String) {
+        match &node.kind {
+            NodeKind::Mapping(entries) => {
+                for (key, value) in entries {
+                    let NodeKind::Scalar(key_name) = &key.kind else {
+                        continue;
+                    };
+                    let child = format!("{}/{}", location, escape(key_name));
+                    self.positions.insert(child.clone(), key.position.clone());
+                    self.index(value, child);
+                }
+            }
+            NodeKind::Sequence(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    let segment = item_name(item).unwrap_or_else(|| i.to_string());
+                    let child = format!("{}/{}", location, escape(&segment));
+                    // Later duplicates win: they are the ones the check reports
+                    self.positions.insert(child.clone(), item.position.clone());
+                    self.index(item, child);
+                }
+            }
+            NodeKind::Scalar(_) | NodeKind::Other => {}
+        }
+    }
+}
+
+/// Build a manifest location from its segments.
+pub fn location(segments: &[&str]) -> String {
+    segments
+        .iter()
+        .map(|segment| format!("/{}", escape(segment)))
+        .collect()
+}
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// A location segment as written in the manifest.
+pub fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// The name a sequence item is addressed by, if any.
+fn item_name(item: &Node) -> Option<String> {
+    let NodeKind::Mapping(entries) = &item.kind else {
+        return None;
+    };
+    ["name", "path"].iter().find_map(|wanted| {
+        entries
+            .iter()
+            .find_map(|(key, value)| match (&key.kind, &value.kind) {
+                (NodeKind::Scalar(k), NodeKind::Scalar(v)) if k == wanted => Some(v.clone()),
+                _ => None,
+            })
+    })
+}
+
+fn parse_node<'a>(events: &mut impl Iterator<Item = (Event<'a>, SourcePosition)>) -> Option<Node> {
+    let (event, position) = events.next()?;
+    let kind = match event {
+        Event::Scalar(scalar) => {
+            NodeKind::Scalar(String::from_utf8_lossy(&scalar.value).into_owned())
+        }
+        Event::SequenceStart(_) => {
+            let mut items = vec![];
+            while let Some(item) = parse_node(events) {
+                items.push(item);
+            }
+            NodeKind::Sequence(items)
+        }
+        Event::MappingStart(_) => {
+            let mut entries = vec![];
+            while let Some(key) = parse_node(events) {
+                let value = parse_node(events)?;
+                entries.push((key, value));
+            }
+            NodeKind::Mapping(entries)
+        }
+        // The end of the enclosing collection
+        Event::SequenceEnd | Event::MappingEnd => return None,
+        Event::Alias(_) | Event::Void => NodeKind::Other,
+    };
+    Some(Node { position, kind })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "\
+schema:
+  version: 5
+global:
+  language: rust
+entities:
+- name: Car
+  fields:
+  - name: id
+    type: uinteger
+  - name: brand
+    type: string
+custom_files:
+- path: src/a/b.rs
+  template: b.tera
+";
+
+    #[test]
+    fn test_positions_of_named_items_and_keys() {
+        let map = SourceMap::from_yaml(MANIFEST);
+        assert_eq!(
+            map.position("/global/language"),
+            Some(SourcePosition {
+                file: None,
+                line: 4,
+                column: 3
+            })
+        );
+        assert_eq!(
+            map.position(&location(&["entities", "Car"])),
+            Some(SourcePosition {
+                file: None,
+                line: 6,
+                column: 3
+            })
+        );
+        assert_eq!(
+            map.position(&location(&["entities", "Car", "fields", "brand"])),
+            Some(SourcePosition {
+                file: None,
+                line: 10,
+                column: 5
+            })
+        );
+        assert_eq!(
+            map.position(&location(&["custom_files", "src/a/b.rs"])),
+            Some(SourcePosition {
+                file: None,
+                line: 13,
+                column: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_location_falls_back_to_ancestor() {
+        let map = SourceMap::from_yaml(MANIFEST);
+        assert_eq!(
+            map.position(&location(&[
+                "entities",
+                "Car",
+                "fields",
+                "brand",
+                "constraints"
+            ])),
+            map.position(&location(&["entities", "Car", "fields", "brand"]))
+        );
+        assert_eq!(map.position(&location(&["features", "sales"])), None);
+    }
+
+    #[test]
+    fn test_included_items_carry_their_file() {
+        let mut map = SourceMap::from_yaml(MANIFEST);
+        map.include(
+            "entities/wheel.yaml",
+            SourceMap::from_yaml("entities:\n- name: Wheel\n  fields: []\n"),
+        );
+        assert_eq!(
+            map.position(&location(&["entities", "Wheel", "fields"])),
+            Some(SourcePosition {
+                file: Some("entities/wheel.yaml".to_string()),
+                line: 3,
+                column: 3
+            })
+        );
+        assert_eq!(
+            map.position("/entities").and_then(|p| p.file),
+            None,
+            "the section itself stays in the manifest"
+        );
+    }
+
+    #[test]
+    fn test_invalid_yaml_gives_empty_map() {
+        let map = SourceMap::from_yaml("entities: [unclosed");
+        assert_eq!(map.position("/entities"), None);
+    }
+}
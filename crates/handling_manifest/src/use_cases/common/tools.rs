@@ -145,6 +145,12 @@ pub fn str_to_field_type(s: &str) -> FieldType {
         "string" => FieldType::String,
         "uuid" => FieldType::Uuid,
         "datetime" => FieldType::DateTime,
+        "decimal" => FieldType::Decimal,
+        "date" => FieldType::Date,
+        "time" => FieldType::Time,
+        "duration" => FieldType::Duration,
+        "bytes" => FieldType::Bytes,
+        "json" => FieldType::Json,
         "entity" | "Entity" => FieldType::Entity,
         "enum" => FieldType::Enum,
         _ => FieldType::String,
@@ -160,7 +166,14 @@ pub fn str_to_dto_field_type(s: &str) -> DtoFieldType {
         "string" => DtoFieldType::String,
         "uuid" => DtoFieldType::Uuid,
         "datetime" => DtoFieldType::DateTime,
+        "decimal" => DtoFieldType::Decimal,
+        "date" => DtoFieldType::Date,
+        "time" => DtoFieldType::Time,
+        "duration" => DtoFieldType::Duration,
+        "bytes" => DtoFieldType::Bytes,
+        "json" => DtoFieldType::Json,
         "enum" => DtoFieldType::Enum,
+        "dto" => DtoFieldType::Dto,
         _ => DtoFieldType::String,
     }
 }
@@ -186,6 +199,8 @@ mod tests {
             relationships: vec![],
             single_model: false,
             undoable: true,
+            undo_journal: false,
+            source_file: String::new(),
         };
 
         // Create fields for the parent entity
@@ -206,6 +221,7 @@ mod tests {
                 list_model_displayed_field: None,
                 enum_name: None,
                 enum_values: vec![],
+                ..Default::default()
             },
             // An OrderedOneToMany relationship
             Field {
@@ -223,6 +239,7 @@ mod tests {
                 list_model_displayed_field: None,
                 enum_name: None,
                 enum_values: vec![],
+                ..Default::default()
             },
         ];
 
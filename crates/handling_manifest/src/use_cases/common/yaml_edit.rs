@@ -0,0 +1,582 @@
+//! Minimal edits of the manifest text, keeping its comments, blank lines and
+//! key order.
+//!
+//! Nodes are found by their manifest location through the [`SourceMap`], so
+//! the text is indexed again before each edit. The edits only understand
+//! block-style YAML: they return `None` when a node is written in flow style,
+//! and callers then fall back to serializing the manifest again.
+
+use crate::use_cases::common::source_map::{self, SourceMap, unescape};
+use serde_json::Value;
+
+/// Edit `text`, which reads as `old`, so that it reads as `new`. Only the
+/// nodes which differ are touched; sequence items are matched by name, and
+/// an item whose name changed in place is renamed rather than written again.
+pub fn apply(text: &str, old: &Value, new: &Value) -> Option<String> {
+    let mut text = text.to_string();
+    edit(&mut text, "", old, new)?;
+    Some(text)
+}
+
+/// Replace the scalar value of the key at `location`.
+pub fn replace_scalar(text: &str, location: &str, value: &str) -> Option<String> {
+    let mut lines = split_lines(text);
+    let (l, c) = key_position(text, location)?;
+    let line = &lines[l];
+    let colon = key_end(line, byte_index(line, c))?;
+    let after_colon = &line[colon + 1..];
+    let value_start = colon + 1 + (after_colon.len() - after_colon.trim_start().len());
+    let rest = &line[value_start..];
+    let value_len = match rest.chars().next() {
+        Some(quote @ ('"' | '\'')) => 1 + rest[1..].find(quote)? + 1,
+        // block scalars go on over the next lines
+        Some('|' | '>') => return None,
+        _ => rest.find(" #").unwrap_or(rest.len()),
+    };
+    let value_end = value_start + rest[..value_len].trim_end().len();
+    if value_start == value_end {
+        return None;
+    }
+    lines[l] = format!("{}{}{}", &line[..value_start], value, &line[value_end..]);
+    Some(lines.join("\n"))
+}
+
+/// Rename the key at `location`, keeping its value and position.
+pub fn rename_key(text: &str, location: &str, new_key: &str) -> Option<String> {
+    let mut lines = split_lines(text);
+    let (l, c) = key_position(text, location)?;
+    let line = &lines[l];
+    let start = byte_index(line, c);
+    let colon = key_end(line, start)?;
+    let key = line[start..colon].trim_end();
+    let renamed = match key.chars().next() {
+        Some(quote @ ('"' | '\'')) => format!("{}{}{}", quote, new_key, quote),
+        _ => new_key.to_string(),
+    };
+    lines[l] = format!(
+        "{}{}{}",
+        &line[..start],
+        renamed,
+        &line[start + key.len()..]
+    );
+    Some(lines.join("\n"))
+}
+
+/// Remove the key at `location` with its value.
+pub fn remove_key(text: &str, location: &str) -> Option<String> {
+    let mut lines = split_lines(text);
+    let (l, c) = key_position(text, location)?;
+    let start = byte_index(&lines[l], c);
+    let prefix = lines[l][..start].to_string();
+    let end = block_end(&lines, l, c)?;
+    if prefix.trim().is_empty() {
+        lines.drain(l..end);
+    } else {
+        // first key of a sequence item: the next key of the item takes its
+        // place after the dash
+        match next_sibling(&lines, end, c) {
+            Some(sibling) => {
+                let sibling_start = byte_index(&lines[sibling], c);
+                lines[l] = format!("{}{}", prefix, &lines[sibling][sibling_start..]);
+                lines.drain(l + 1..=sibling);
+            }
+            None => {
+                lines[l] = format!("{}{{}}", prefix);
+                lines.drain(l + 1..end);
+            }
+        }
+    }
+    Some(lines.join("\n"))
+}
+
+/// Replace the key at `location` and its whole value.
+pub fn replace_value(text: &str, location: &str, value: &Value) -> Option<String> {
+    let mut lines = split_lines(text);
+    let (l, c) = key_position(text, location)?;
+    let start = byte_index(&lines[l], c);
+    let end = block_end(&lines, l, c)?;
+    let (_, key) = location.rsplit_once('/')?;
+    let rendered = render_key(&unescape(key), value)?;
+    let mut replacement = vec![format!("{}{}", &lines[l][..start], rendered[0])];
+    replacement.extend(indented(&rendered[1..], c));
+    lines.splice(l..end, replacement);
+    Some(lines.join("\n"))
+}
+
+/// Add `key: value` at the end of the mapping at `parent_location`, the
+/// document itself when empty.
+pub fn insert_key(text: &str, parent_location: &str, key: &str, value: &Value) -> Option<String> {
+    let rendered = render_key(key, value)?;
+    let mut lines = split_lines(text);
+    if parent_location.is_empty() {
+        let end = lines
+            .iter()
+            .rposition(|line| !is_blank_or_comment(line))
+            .map_or(0, |last| last + 1);
+        lines.splice(end..end, rendered);
+        return Some(lines.join("\n"));
+    }
+    let (l, c) = key_position(text, parent_location)?;
+    let line = &lines[l];
+    let start = byte_index(line, c);
+    let colon = key_end(line, start)?;
+    let parent_key = parent_location
+        .rsplit_once('/')
+        .map(|(_, segment)| unescape(segment))?;
+    let is_parent_key = line[start..colon].trim_end().trim_matches(['"', '\'']) == parent_key
+        && is_blank_or_comment(&line[colon + 1..]);
+
+    // the first key of the mapping: the item itself, or the line below the key
+    let (mut k, column) = if is_parent_key {
+        let first = (l + 1..lines.len()).find(|&i| !is_blank_or_comment(&lines[i]))?;
+        let column = indent(&lines[first]);
+        if column <= c || lines[first].trim_start().starts_with('-') {
+            return None;
+        }
+        (first, column)
+    } else {
+        (l, c)
+    };
+    let mut end = block_end(&lines, k, column)?;
+    while let Some(sibling) = next_sibling(&lines, end, column) {
+        k = sibling;
+        end = block_end(&lines, k, column)?;
+    }
+    lines.splice(end..end, indented(&rendered, column));
+    Some(lines.join("\n"))
+}
+
+/// Remove the sequence item at `location`.
+pub fn remove_item(text: &str, location: &str) -> Option<String> {
+    let mut lines = split_lines(text);
+    let (l, c) = key_position(text, location)?;
+    if lines[l][..byte_index(&lines[l], c)].trim() != "-" {
+        return None;
+    }
+    let end = item_end(&lines, l, c);
+    lines.drain(l..end);
+    Some(lines.join("\n"))
+}
+
+/// Add `value` to the sequence of the key at `location`, before the item at
+/// `before`, or at the end of the sequence.
+pub fn insert_item(
+    text: &str,
+    location: &str,
+    before: Option<&str>,
+    value: &Value,
+) -> Option<String> {
+    let mut lines = split_lines(text);
+    let (l, c) = key_position(text, location)?;
+    let first = (l + 1..lines.len()).find(|&i| !is_blank_or_comment(&lines[i]))?;
+    if !lines[first].trim_start().starts_with('-') {
+        return None;
+    }
+    let dash = indent(&lines[first]);
+    let at = match before {
+        Some(before) => key_position(text, before)?.0,
+        None => block_end(&lines, l, c)?,
+    };
+    let rendered = render(&Value::Array(vec![value.clone()]))?;
+    lines.splice(at..at, indented(&rendered, dash));
+    Some(lines.join("\n"))
+}
+
+fn edit(text: &mut String, location: &str, old: &Value, new: &Value) -> Option<()> {
+    if old == new {
+        return Some(());
+    }
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                let child = child(location, key);
+                if exists(text, &child) {
+                    *text = remove_key(text, &child)?;
+                }
+            }
+            // the names last: they give the location of the other keys
+            let (names, others): (Vec<_>, Vec<_>) = new
+                .iter()
+                .partition(|(key, _)| *key == "name" || *key == "path");
+            for (key, value) in others.into_iter().chain(names) {
+                let child = child(location, key);
+                match old.get(key) {
+                    Some(old_value) if exists(text, &child) => {
+                        edit(text, &child, old_value, value)?
+                    }
+                    Some(old_value) if old_value == value => {}
+                    _ => *text = insert_key(text, location, key, value)?,
+                }
+            }
+            Some(())
+        }
+        (Value::Array(old), Value::Array(new)) if !old.is_empty() && !new.is_empty() => {
+            match edit_items(text, location, old, new) {
+                Some(edited) => *text = edited,
+                None => set(text, location, &Value::Array(new.clone()))?,
+            }
+            Some(())
+        }
+        _ => set(text, location, new),
+    }
+}
+
+/// Edit the items of a sequence of named items one by one. `None` when the
+/// items have no unique names or were reordered.
+fn edit_items(text: &str, location: &str, old: &[Value], new: &[Value]) -> Option<String> {
+    let old_names = item_names(old)?;
+    let new_names = item_names(new)?;
+
+    // the old item each new item comes from: the one with the same name, or
+    // the one at the same index if it was renamed
+    let sources: Vec<Option<usize>> = new_names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            old_names
+                .iter()
+                .position(|old_name| old_name == name)
+                .or_else(|| {
+                    old_names
+                        .get(i)
+                        .filter(|old_name| !new_names.contains(old_name))
+                        .map(|_| i)
+                })
+        })
+        .collect();
+    let kept: Vec<usize> = sources.iter().flatten().copied().collect();
+    if kept.windows(2).any(|pair| pair[0] > pair[1]) {
+        return None;
+    }
+
+    let mut text = text.to_string();
+    for (i, name) in old_names.iter().enumerate() {
+        if !kept.contains(&i) {
+            text = remove_item(&text, &child(location, name))?;
+        }
+    }
+    for (i, new_item) in new.iter().enumerate() {
+        match sources[i] {
+            Some(old_index) => edit(
+                &mut text,
+                &child(location, &old_names[old_index]),
+                &old[old_index],
+                new_item,
+            )?,
+            None => {
+                let before = sources[i + 1..]
+                    .iter()
+                    .flatten()
+                    .next()
+                    .map(|&old_index| child(location, &old_names[old_index]));
+                text = insert_item(&text, location, before.as_deref(), new_item)?;
+            }
+        }
+    }
+    Some(text)
+}
+
+/// Set the key at `location` to `value`, adding it if the text doesn't have it.
+fn set(text: &mut String, location: &str, value: &Value) -> Option<()> {
+    let (parent, key) = location.rsplit_once('/')?;
+    *text = if !exists(text, location) {
+        insert_key(text, parent, &unescape(key), value)?
+    } else if value.is_object() || value.is_array() {
+        replace_flow_sequence(text, location, value)
+            .or_else(|| replace_value(text, location, value))?
+    } else {
+        match render(value)?.as_slice() {
+            [scalar] => replace_scalar(text, location, scalar)
+                .or_else(|| replace_value(text, location, value))?,
+            _ => replace_value(text, location, value)?,
+        }
+    };
+    Some(())
+}
+
+/// Replace a one-line flow sequence of scalars, like `entities: [Car, Wheel]`,
+/// keeping it in flow style.
+fn replace_flow_sequence(text: &str, location: &str, value: &Value) -> Option<String> {
+    let (l, c) = key_position(text, location)?;
+    let line = &split_lines(text)[l];
+    let colon = key_end(line, byte_index(line, c))?;
+    let rest = line[colon + 1..].trim();
+    let rest = rest.split(" #").next()?.trim_end();
+    if !(rest.starts_with('[') && rest.ends_with(']')) {
+        return None;
+    }
+    let items = value
+        .as_array()?
+        .iter()
+        .map(|item| match render(item)?.as_slice() {
+            [scalar] if !item.is_array() && !item.is_object() => Some(scalar.clone()),
+            _ => None,
+        })
+        .collect::<Option<Vec<String>>>()?;
+    replace_scalar(text, location, &format!("[{}]", items.join(", ")))
+}
+
+/// The names sequence items are addressed by, if they all have a unique one.
+fn item_names(items: &[Value]) -> Option<Vec<String>> {
+    let names = items
+        .iter()
+        .map(|item| {
+            ["name", "path"]
+                .iter()
+                .find_map(|key| item.get(*key).and_then(Value::as_str))
+                .map(str::to_string)
+        })
+        .collect::<Option<Vec<String>>>()?;
+    let unique = names
+        .iter()
+        .enumerate()
+        .all(|(i, name)| !names[..i].contains(name));
+    unique.then_some(names)
+}
+
+fn child(location: &str, segment: &str) -> String {
+    format!("{}{}", location, source_map::location(&[segment]))
+}
+
+fn exists(text: &str, location: &str) -> bool {
+    location.is_empty() || SourceMap::from_yaml(text).get(location).is_some()
+}
+
+/// A value as block-style YAML lines.
+fn render(value: &Value) -> Option<Vec<String>> {
+    let yaml = serde_yml::to_string(value).ok()?;
+    Some(yaml.trim_end().split('\n').map(str::to_string).collect())
+}
+
+/// `key: value` as block-style YAML lines.
+fn render_key(key: &str, value: &Value) -> Option<Vec<String>> {
+    let mut mapping = serde_json::Map::new();
+    mapping.insert(key.to_string(), value.clone());
+    render(&Value::Object(mapping))
+}
+
+fn indented(lines: &[String], column: usize) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| format!("{}{}", " ".repeat(column), line))
+        .collect()
+}
+
+/// 0-based line and column of the node at exactly `location`, when it starts
+/// a block-style line, alone or after a sequence dash.
+fn key_position(text: &str, location: &str) -> Option<(usize, usize)> {
+    let position = SourceMap::from_yaml(text).get(location)?.clone();
+    let l = usize::try_from(position.line).ok()?.checked_sub(1)?;
+    let c = usize::try_from(position.column).ok()?.checked_sub(1)?;
+    let line = text.split('\n').nth(l)?;
+    let prefix = &line[..byte_index(line, c)];
+    let prefix = prefix.trim();
+    (prefix.is_empty() || prefix == "-").then_some((l, c))
+}
+
+/// Exclusive end of the lines of the key starting at `l`, column `c`: its
+/// value goes on while lines are more indented, or are the dashes of a
+/// sequence written at the key's indentation. Trailing comments stay out.
+fn block_end(lines: &[String], l: usize, c: usize) -> Option<usize> {
+    let colon = key_end(&lines[l], byte_index(&lines[l], c))?;
+    let value_on_line = !is_blank_or_comment(&lines[l][colon + 1..]);
+    let mut end = l + 1;
+    for (i, line) in lines.iter().enumerate().skip(l + 1) {
+        if is_blank_or_comment(line) {
+            continue;
+        }
+        let line_indent = indent(line);
+        let is_dash = line.trim_start().starts_with('-');
+        if line_indent > c || (line_indent == c && is_dash && !value_on_line) {
+            end = i + 1;
+        } else {
+            break;
+        }
+    }
+    Some(end)
+}
+
+/// Exclusive end of the lines of the sequence item starting at `l`, column
+/// `c` after its dash. Trailing comments stay out.
+fn item_end(lines: &[String], l: usize, c: usize) -> usize {
+    let mut end = l + 1;
+    for (i, line) in lines.iter().enumerate().skip(l + 1) {
+        if is_blank_or_comment(line) {
+            continue;
+        }
+        if indent(line) >= c {
+            end = i + 1;
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// The next key of the same mapping, at column `c`, from line `from`.
+fn next_sibling(lines: &[String], from: usize, c: usize) -> Option<usize> {
+    let next = (from..lines.len()).find(|&i| !is_blank_or_comment(&lines[i]))?;
+    let line = &lines[next];
+    (indent(line) == c && !line.trim_start().starts_with('-')).then_some(next)
+}
+
+/// Byte index of the `:` ending the key which starts at `start`.
+fn key_end(line: &str, start: usize) -> Option<usize> {
+    let key = &line[start..];
+    let after_key = match key.chars().next()? {
+        quote @ ('"' | '\'') => 1 + key[1..].find(quote)? + 1,
+        _ => 0,
+    };
+    let colon = key[after_key..].char_indices().find_map(|(i, ch)| {
+        let rest = &key[after_key + i + 1..];
+        (ch == ':' && (rest.is_empty() || rest.starts_with([' ', '\t', '\r'])))
+            .then_some(after_key + i)
+    })?;
+    Some(start + colon)
+}
+
+fn split_lines(text: &str) -> Vec<String> {
+    text.split('\n').map(str::to_string).collect()
+}
+
+fn byte_index(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column)
+        .map_or(line.len(), |(i, _)| i)
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_blank_or_comment(text: &str) -> bool {
+    let trimmed = text.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "\
+# Project manifest
+schema:
+  version: 2 # bumped by upgrade
+entities:
+- name: Car
+  # kept for now
+  allow_direct_access: true
+  fields:
+  - name: id
+    type: uinteger
+- allow_direct_access: false
+  name: Wheel
+  fields: []
+features:
+- name: sales
+  use_cases:
+  - name: buy
+    validator: true
+";
+
+    #[test]
+    fn test_edits_keep_comments_and_order() {
+        let text = replace_scalar(MANIFEST, "/schema/version", "5").unwrap();
+        let text = remove_key(&text, "/entities/Car/allow_direct_access").unwrap();
+        let text = remove_key(&text, "/entities/Wheel/allow_direct_access").unwrap();
+        let text = rename_key(&text, "/features/sales/use_cases/buy/validator", "checked").unwrap();
+        let text = insert_key(
+            &text,
+            "/entities/Car",
+            "only_for_heritage",
+            &Value::Bool(false),
+        )
+        .unwrap();
+
+        assert_eq!(
+            text,
+            "\
+# Project manifest
+schema:
+  version: 5 # bumped by upgrade
+entities:
+- name: Car
+  # kept for now
+  fields:
+  - name: id
+    type: uinteger
+  only_for_heritage: false
+- name: Wheel
+  fields: []
+features:
+- name: sales
+  use_cases:
+  - name: buy
+    checked: true
+"
+        );
+    }
+
+    #[test]
+    fn test_insert_under_a_key() {
+        let text = insert_key(MANIFEST, "/schema", "extra", &Value::from(1)).unwrap();
+        assert!(text.contains("  version: 2 # bumped by upgrade\n  extra: 1\nentities:"));
+    }
+
+    #[test]
+    fn test_apply_edits_only_changed_nodes() {
+        let old: Value = serde_yml::from_str(MANIFEST).unwrap();
+        let mut new = old.clone();
+        new["schema"]["version"] = Value::from(5);
+        let entities = new["entities"].as_array_mut().unwrap();
+        entities[0]["name"] = Value::from("Auto");
+        entities[0]["fields"][0]["type"] = Value::from("uuid");
+        entities.remove(1);
+        entities.push(serde_json::json!({ "name": "Door", "fields": [] }));
+        new["features"][0]["use_cases"] = Value::Array(vec![]);
+
+        let text = apply(MANIFEST, &old, &new).unwrap();
+
+        assert_eq!(
+            text,
+            "\
+# Project manifest
+schema:
+  version: 5 # bumped by upgrade
+entities:
+- name: Auto
+  # kept for now
+  allow_direct_access: true
+  fields:
+  - name: id
+    type: uuid
+- name: Door
+  fields: []
+features:
+- name: sales
+  use_cases: []
+"
+        );
+        assert_eq!(serde_yml::from_str::<Value>(&text).unwrap(), new);
+    }
+
+    #[test]
+    fn test_flow_sequences_of_scalars_stay_in_flow_style() {
+        let text = "use_cases:\n  - name: move\n    entities: [Car, Wheel] # moved\n";
+        let old: Value = serde_yml::from_str(text).unwrap();
+        let new: Value = serde_yml::from_str(
+            "use_cases:\n  - name: move\n    entities: [Vehicle, Wheel, Door]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            apply(text, &old, &new).unwrap(),
+            "use_cases:\n  - name: move\n    entities: [Vehicle, Wheel, Door] # moved\n"
+        );
+    }
+
+    #[test]
+    fn test_flow_style_is_not_edited() {
+        let text = "entities: [{ name: Car, allow_direct_access: true }]\n";
+        assert_eq!(remove_key(text, "/entities/Car/allow_direct_access"), None);
+    }
+}
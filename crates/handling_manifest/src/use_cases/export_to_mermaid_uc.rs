@@ -13,6 +13,7 @@ pub trait ExportToMermaidUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn ExportToMermaidUnitOfWorkTrait>;
 }
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Dto", action = "GetRO")]
 #[macros::uow_action(entity = "Dto", action = "GetMultiRO")]
 #[macros::uow_action(entity = "DtoField", action = "GetRO")]
@@ -152,6 +153,12 @@ impl ExportToMermaidUseCase {
             FieldType::String => "string".to_string(),
             FieldType::Uuid => "uuid".to_string(),
             FieldType::DateTime => "datetime".to_string(),
+            FieldType::Decimal => "decimal".to_string(),
+            FieldType::Date => "date".to_string(),
+            FieldType::Time => "time".to_string(),
+            FieldType::Duration => "duration".to_string(),
+            FieldType::Bytes => "bytes".to_string(),
+            FieldType::Json => "json".to_string(),
             FieldType::Entity => {
                 // Get referenced entity name
                 field
@@ -1,22 +1,26 @@
-mod migration;
-mod tools;
 mod validation_schema;
+use crate::use_cases::common::includes;
+use crate::use_cases::common::migration;
 use crate::use_cases::common::model_structs;
+use crate::use_cases::common::parse;
+use crate::use_cases::common::tools;
 use crate::{LoadDto, LoadReturnDto};
 use anyhow::Result;
 use common::types::EntityId;
 use common::{
     database::CommandUnitOfWork,
     entities::{
-        Dto, DtoField, Entity, Feature, Field, FieldRelationshipType, FieldType, Global,
-        Relationship, Root, System, UseCase, UserInterface, Workspace,
+        CustomFile, Dto, DtoField, DtoFieldType, Entity, Feature, Field, FieldRelationshipType,
+        FieldType, Global, Relationship, Root, System, UseCase, UserInterface, Workspace,
     },
 };
+use std::collections::HashMap;
 
 pub trait LoadUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn LoadUnitOfWorkTrait>;
 }
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "CreateOrphan")]
 #[macros::uow_action(entity = "Root", action = "Get")]
 #[macros::uow_action(entity = "Root", action = "UpdateWithRelationships")]
@@ -35,9 +39,12 @@ pub trait LoadUnitOfWorkFactoryTrait {
 #[macros::uow_action(entity = "Field", action = "CreateOrphan")]
 #[macros::uow_action(entity = "Field", action = "GetMulti")]
 #[macros::uow_action(entity = "Dto", action = "CreateOrphan")]
+#[macros::uow_action(entity = "Dto", action = "Get")]
+#[macros::uow_action(entity = "Dto", action = "UpdateWithRelationships")]
 #[macros::uow_action(entity = "DtoField", action = "CreateOrphan")]
 #[macros::uow_action(entity = "Relationship", action = "CreateOrphanMulti")]
 #[macros::uow_action(entity = "UserInterface", action = "CreateOrphan")]
+#[macros::uow_action(entity = "CustomFile", action = "CreateOrphan")]
 pub trait LoadUnitOfWorkTrait: CommandUnitOfWork {}
 
 pub struct LoadUseCase {
@@ -52,7 +59,6 @@ impl LoadUseCase {
     pub fn execute(&mut self, dto: &LoadDto) -> Result<LoadReturnDto> {
         // load file
         let path = &dto.manifest_path;
-        let filename = path.clone();
 
         // validate that the file exists
         if !std::path::Path::new(path).exists() {
@@ -63,6 +69,17 @@ impl LoadUseCase {
             return Err(anyhow::anyhow!("File is not a file"));
         }
 
+        let source = std::fs::read_to_string(path)?;
+        self.execute_with_source(dto, &source)
+    }
+
+    /// Load the manifest from `source` instead of reading `dto.manifest_path`,
+    /// which still gives the manifest directory and format. Used by editors
+    /// to load unsaved buffers.
+    pub fn execute_with_source(&mut self, dto: &LoadDto, source: &str) -> Result<LoadReturnDto> {
+        let path = &dto.manifest_path;
+        let filename = path.clone();
+
         // ensure that the path is absolute
         let path = if std::path::Path::new(path).is_absolute() {
             std::path::Path::new(path)
@@ -88,18 +105,7 @@ impl LoadUseCase {
         };
 
         // if yaml file, convert to json
-
-        let json_value: serde_json::Value = match filename.split('.').next_back() {
-            Some("yaml") => {
-                let yaml = std::fs::read_to_string(&filename)?;
-                serde_yml::from_str(&yaml)?
-            }
-            Some("json") => {
-                let json = std::fs::read_to_string(&filename)?;
-                serde_json::from_str(&json)?
-            }
-            _ => return Err(anyhow::anyhow!("File extension not supported")),
-        };
+        let json_value = parse(&filename, source)?;
 
         // migrate older schema versions to current
         let mut json_value = json_value;
@@ -115,7 +121,37 @@ impl LoadUseCase {
         }
 
         // apply the json to the model
-        let manifest: model_structs::Manifest = serde_json::from_value(json_value)?;
+        let mut manifest: model_structs::Manifest = serde_json::from_value(json_value)?;
+
+        // append the entities and features of the included files, remembering
+        // where each comes from and the text they were read from
+        let mut included_files = vec![];
+        let mut included_texts = vec![];
+        let mut entity_sources = vec![String::new(); manifest.entities.len()];
+        let mut feature_sources = vec![String::new(); manifest.features.len()];
+        for file in
+            includes::included_files(std::path::Path::new(&path), &filename, &manifest.includes)?
+        {
+            let included_path = std::path::Path::new(&path).join(&file);
+            let included_source = std::fs::read_to_string(&included_path).map_err(|e| {
+                includes::IncludedFileError {
+                    file: file.clone(),
+                    error: e.into(),
+                }
+            })?;
+            let included = parse_included(&included_path.to_string_lossy(), &included_source)
+                .map_err(|error| includes::IncludedFileError {
+                    file: file.clone(),
+                    error,
+                })?;
+
+            entity_sources.extend(std::iter::repeat_n(file.clone(), included.entities.len()));
+            feature_sources.extend(std::iter::repeat_n(file.clone(), included.features.len()));
+            manifest.entities.extend(included.entities);
+            manifest.features.extend(included.features);
+            included_files.push(file);
+            included_texts.push(included_source);
+        }
 
         let mut uow = self.uow_factory.create();
 
@@ -133,6 +169,11 @@ impl LoadUseCase {
             organisation_name: manifest.global.organisation.name,
             organisation_domain: manifest.global.organisation.domain,
             prefix_path: manifest.global.prefix_path,
+            storage: manifest
+                .global
+                .storage
+                .unwrap_or_else(|| "memory".to_string()),
+            templates_path: manifest.global.templates_path.unwrap_or_default(),
         })?;
         let global_id = global.id;
 
@@ -154,18 +195,23 @@ impl LoadUseCase {
             id: 0,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
-            manifest_absolute_path: path,
+            manifest_absolute_path: path.clone(),
             global: global_id,
             entities: vec![],
             features: vec![],
             user_interface: ui.id,
+            custom_files: vec![],
+            includes: manifest.includes.clone(),
+            manifest_text: source.to_string(),
+            included_files,
+            included_texts,
         })?;
         let workspace_id = workspace.id;
 
         // create entities
         let mut entity_ids: Vec<EntityId> = vec![];
         let mut entities = vec![];
-        for model_entity in manifest.entities.iter() {
+        for (model_entity, source_file) in manifest.entities.iter().zip(entity_sources) {
             let entity = uow.create_orphan_entity(&Entity {
                 id: 0,
                 created_at: chrono::Utc::now(),
@@ -177,6 +223,8 @@ impl LoadUseCase {
                 fields: vec![],        // will be filled in later
                 relationships: vec![], // will be filled in later
                 undoable: model_entity.undoable,
+                undo_journal: model_entity.undo_journal.unwrap_or_default(),
+                source_file,
             })?;
             entity_ids.push(entity.id);
             entities.push(entity);
@@ -185,14 +233,9 @@ impl LoadUseCase {
         // create fields
         let mut all_field_ids = vec![];
 
-        for model_entity in manifest.entities.iter() {
-            // find the entity id
-            let entity_id = entities
-                .iter()
-                .find(|e| e.name == model_entity.name)
-                .map(|e| e.id)
-                .ok_or(anyhow::anyhow!("Entity not found"))?;
-
+        // by position rather than by name: a name defined twice across files
+        // still gives two entities, each with its own fields
+        for (model_entity, &entity_id) in manifest.entities.iter().zip(&entity_ids) {
             let mut field_ids: Vec<EntityId> = vec![];
 
             for model_field in model_entity.fields.iter() {
@@ -231,6 +274,7 @@ impl LoadUseCase {
                 };
 
                 // create field
+                let constraints = model_field.constraints.as_ref();
                 let field = uow.create_orphan_field(&Field {
                     id: 0,
                     created_at: chrono::Utc::now(),
@@ -256,6 +300,20 @@ impl LoadUseCase {
                     list_model_displayed_field: model_field.list_model_displayed_field.clone(),
                     enum_name: model_field.enum_name.clone(),
                     enum_values: model_field.enum_values.clone(),
+                    min_value: constraints
+                        .and_then(|c| c.min.as_ref())
+                        .map(|n| n.to_string()),
+                    max_value: constraints
+                        .and_then(|c| c.max.as_ref())
+                        .map(|n| n.to_string()),
+                    min_length: constraints.and_then(|c| c.min_length),
+                    max_length: constraints.and_then(|c| c.max_length),
+                    pattern: constraints.and_then(|c| c.pattern.clone()),
+                    not_empty: constraints.is_some_and(|c| c.not_empty),
+                    unique_in_parent: constraints.is_some_and(|c| c.unique_in_parent),
+                    indexed: model_field.indexed.unwrap_or_default(),
+                    unique: model_field.unique.unwrap_or_default(),
+                    default_value: default_value(&model_field.default_value),
                 })?;
                 field_ids.push(field.id);
                 all_field_ids.push(field.id);
@@ -314,7 +372,31 @@ impl LoadUseCase {
 
         // create features
         let mut feature_ids = vec![];
-        for model_feature in manifest.features.iter() {
+        for (model_feature, source_file) in manifest.features.iter().zip(feature_sources) {
+            // create the shared DTOs first, empty, so that their fields can reference
+            // each other whatever the declaration order
+            let mut shared_dto_ids: HashMap<String, EntityId> = HashMap::new();
+            let mut dto_ids = vec![];
+            for model_dto in model_feature.dtos.iter() {
+                let dto = uow.create_orphan_dto(&Dto {
+                    id: 0,
+                    created_at: chrono::Utc::now(),
+                    updated_at: chrono::Utc::now(),
+                    name: model_dto.name.clone(),
+                    fields: vec![], // will be filled in below
+                })?;
+                // a duplicate name is reported by the check
+                shared_dto_ids.entry(dto.name.clone()).or_insert(dto.id);
+                dto_ids.push(dto.id);
+            }
+            for (model_dto, dto_id) in model_feature.dtos.iter().zip(&dto_ids) {
+                let dto = uow
+                    .get_dto(dto_id)?
+                    .ok_or(anyhow::anyhow!("Dto not found"))?;
+                let fields = create_dto_fields(uow.as_ref(), model_dto, &shared_dto_ids)?;
+                uow.update_dto_with_relationships(&Dto { fields, ..dto })?;
+            }
+
             // create use cases
             let mut use_case_ids = vec![];
             for model_use_case in model_feature.use_cases.iter() {
@@ -322,8 +404,14 @@ impl LoadUseCase {
                 let use_case_entity_names = model_use_case.entities.clone();
                 let mut use_case_entity_ids = vec![];
                 if let Some(use_case_entity_names) = use_case_entity_names {
+                    // a name defined twice across files refers to its first definition,
+                    // the duplicate is reported by the check
+                    let mut matched_names: Vec<&String> = vec![];
                     for entity in entities.iter() {
-                        if use_case_entity_names.contains(&entity.name) {
+                        if use_case_entity_names.contains(&entity.name)
+                            && !matched_names.contains(&&entity.name)
+                        {
+                            matched_names.push(&entity.name);
                             use_case_entity_ids.push(entity.id);
                         }
                     }
@@ -336,69 +424,19 @@ impl LoadUseCase {
                     }
                 }
 
-                // create dto_in
-                let mut dto_in_id = None;
-                if let Some(dto_in) = &model_use_case.dto_in {
-                    // create DtoFields
-                    let mut dto_field_ids = vec![];
-                    for model_dto_field in dto_in.fields.iter() {
-                        let field_type = tools::str_to_dto_field_type(&model_dto_field.r#type);
-
-                        let dto_field = uow.create_orphan_dto_field(&DtoField {
-                            id: 0,
-                            created_at: chrono::Utc::now(),
-                            updated_at: chrono::Utc::now(),
-                            name: model_dto_field.name.clone(),
-                            field_type,
-                            optional: model_dto_field.optional.unwrap_or_default(),
-                            is_list: model_dto_field.is_list.unwrap_or_default(),
-                            enum_name: model_dto_field.enum_name.clone(),
-                            enum_values: model_dto_field.enum_values.clone(),
-                        })?;
-                        dto_field_ids.push(dto_field.id);
-                    }
-
-                    let dto_in = uow.create_orphan_dto(&Dto {
-                        id: 0,
-                        created_at: chrono::Utc::now(),
-                        updated_at: chrono::Utc::now(),
-                        name: dto_in.name.clone(),
-                        fields: dto_field_ids,
-                    })?;
-                    dto_in_id = Some(dto_in.id);
-                }
-
-                // create dto_out
-                let mut dto_out_id = None;
-                if let Some(dto_out) = &model_use_case.dto_out {
-                    // create DtoFields
-                    let mut dto_field_ids = vec![];
-                    for model_dto_field in dto_out.fields.iter() {
-                        let field_type = tools::str_to_dto_field_type(&model_dto_field.r#type);
-
-                        let dto_field = uow.create_orphan_dto_field(&DtoField {
-                            id: 0,
-                            created_at: chrono::Utc::now(),
-                            updated_at: chrono::Utc::now(),
-                            name: model_dto_field.name.clone(),
-                            field_type,
-                            optional: model_dto_field.optional.unwrap_or_default(),
-                            is_list: model_dto_field.is_list.unwrap_or_default(),
-                            enum_name: model_dto_field.enum_name.clone(),
-                            enum_values: model_dto_field.enum_values.clone(),
-                        })?;
-                        dto_field_ids.push(dto_field.id);
-                    }
-
-                    let dto_out = uow.create_orphan_dto(&Dto {
-                        id: 0,
-                        created_at: chrono::Utc::now(),
-                        updated_at: chrono::Utc::now(),
-                        name: dto_out.name.clone(),
-                        fields: dto_field_ids,
-                    })?;
-                    dto_out_id = Some(dto_out.id);
-                }
+                // create dto_in and dto_out, or point to the shared ones
+                let (dto_in_id, shared_dto_in_id) = create_use_case_dto(
+                    uow.as_ref(),
+                    model_use_case.dto_in.as_ref(),
+                    &shared_dto_ids,
+                    &model_use_case.name,
+                )?;
+                let (dto_out_id, shared_dto_out_id) = create_use_case_dto(
+                    uow.as_ref(),
+                    model_use_case.dto_out.as_ref(),
+                    &shared_dto_ids,
+                    &model_use_case.name,
+                )?;
 
                 let use_case = uow.create_orphan_use_case(&UseCase {
                     id: 0,
@@ -411,6 +449,8 @@ impl LoadUseCase {
                     long_operation: model_use_case.long_operation.unwrap_or_default(),
                     dto_in: dto_in_id,
                     dto_out: dto_out_id,
+                    shared_dto_in: shared_dto_in_id,
+                    shared_dto_out: shared_dto_out_id,
                 })?;
                 use_case_ids.push(use_case.id);
             }
@@ -421,10 +461,37 @@ impl LoadUseCase {
                 updated_at: chrono::Utc::now(),
                 name: model_feature.name.clone(),
                 use_cases: use_case_ids,
+                dtos: dto_ids,
+                source_file,
             })?;
             feature_ids.push(feature.id);
         }
 
+        // create custom files
+        let mut custom_file_ids = vec![];
+        for model_custom_file in manifest.custom_files.iter() {
+            let custom_file = uow.create_orphan_custom_file(&CustomFile {
+                id: 0,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                path: model_custom_file.path.clone(),
+                template_name: model_custom_file.template.clone(),
+                group: model_custom_file
+                    .group
+                    .clone()
+                    .unwrap_or_else(|| "custom".to_string()),
+                nature: model_custom_file
+                    .nature
+                    .clone()
+                    .unwrap_or_else(|| "scaffold".to_string()),
+                scope: model_custom_file
+                    .scope
+                    .clone()
+                    .unwrap_or_else(|| "once".to_string()),
+            })?;
+            custom_file_ids.push(custom_file.id);
+        }
+
         // update workspace with all ids
         // good practice to get the workspace again, to make sure it is not stale
         let workspace = uow
@@ -434,6 +501,7 @@ impl LoadUseCase {
             id: workspace.id,
             entities: entity_ids,
             features: feature_ids.clone(),
+            custom_files: custom_file_ids,
             ..workspace
         };
         uow.update_workspace_with_relationships(&workspace)?;
@@ -460,3 +528,118 @@ impl LoadUseCase {
         })
     }
 }
+
+/// Create the fields of `model_dto`. Fields of type `dto` point to one of the
+/// feature's shared DTOs.
+/// Defaults are stored in their text form, whatever their YAML type.
+fn default_value(value: &Option<serde_json::Value>) -> Option<String> {
+    value.as_ref().map(|value| match value {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    })
+}
+
+fn create_dto_fields(
+    uow: &dyn LoadUnitOfWorkTrait,
+    model_dto: &model_structs::Dto,
+    shared_dto_ids: &HashMap<String, EntityId>,
+) -> Result<Vec<EntityId>> {
+    let mut dto_field_ids = vec![];
+    for model_dto_field in model_dto.fields.iter() {
+        let field_type = tools::str_to_dto_field_type(&model_dto_field.r#type);
+
+        let dto = match (&field_type, &model_dto_field.dto) {
+            (DtoFieldType::Dto, None) => {
+                return Err(anyhow::anyhow!(
+                    "Field {} of DTO {} is of type Dto but no dto name is provided",
+                    model_dto_field.name,
+                    model_dto.name
+                ));
+            }
+            (DtoFieldType::Dto, Some(dto_name)) => {
+                Some(*shared_dto_ids.get(dto_name).ok_or(anyhow::anyhow!(
+                    "DTO {} not found for field {} of DTO {}",
+                    dto_name,
+                    model_dto_field.name,
+                    model_dto.name
+                ))?)
+            }
+            _ => None,
+        };
+
+        let constraints = model_dto_field.constraints.as_ref();
+        let dto_field = uow.create_orphan_dto_field(&DtoField {
+            id: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            name: model_dto_field.name.clone(),
+            field_type,
+            optional: model_dto_field.optional.unwrap_or_default(),
+            is_list: model_dto_field.is_list.unwrap_or_default(),
+            enum_name: model_dto_field.enum_name.clone(),
+            enum_values: model_dto_field.enum_values.clone(),
+            dto,
+            min_value: constraints
+                .and_then(|c| c.min.as_ref())
+                .map(|n| n.to_string()),
+            max_value: constraints
+                .and_then(|c| c.max.as_ref())
+                .map(|n| n.to_string()),
+            min_length: constraints.and_then(|c| c.min_length),
+            max_length: constraints.and_then(|c| c.max_length),
+            pattern: constraints.and_then(|c| c.pattern.clone()),
+            not_empty: constraints.is_some_and(|c| c.not_empty),
+            default_value: default_value(&model_dto_field.default_value),
+        })?;
+        dto_field_ids.push(dto_field.id);
+    }
+    Ok(dto_field_ids)
+}
+
+/// Create an inline use case DTO, or resolve the name of a shared one.
+/// Returns the `(dto, shared_dto)` pair of the use case.
+fn create_use_case_dto(
+    uow: &dyn LoadUnitOfWorkTrait,
+    dto_ref: Option<&model_structs::DtoRef>,
+    shared_dto_ids: &HashMap<String, EntityId>,
+    use_case_name: &str,
+) -> Result<(Option<EntityId>, Option<EntityId>)> {
+    match dto_ref {
+        None => Ok((None, None)),
+        Some(model_structs::DtoRef::Shared(dto_name)) => {
+            let dto_id = shared_dto_ids.get(dto_name).ok_or(anyhow::anyhow!(
+                "DTO {} not found for use case {}",
+                dto_name,
+                use_case_name
+            ))?;
+            Ok((None, Some(*dto_id)))
+        }
+        Some(model_structs::DtoRef::Inline(model_dto)) => {
+            let fields = create_dto_fields(uow, model_dto, shared_dto_ids)?;
+            let dto = uow.create_orphan_dto(&Dto {
+                id: 0,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                name: model_dto.name.clone(),
+                fields,
+            })?;
+            Ok((Some(dto.id), None))
+        }
+    }
+}
+
+/// Parse and validate a file listed by `includes`. Included files have no
+/// schema version of their own: they follow the current one.
+fn parse_included(filename: &str, source: &str) -> Result<model_structs::IncludedManifest> {
+    let json_value = parse(filename, source)?;
+    // an empty file is an empty include
+    if json_value.is_null() {
+        return Ok(model_structs::IncludedManifest::default());
+    }
+    let validation_schema = validation_schema::included_json_validation_schema();
+    let validator = jsonschema::draft7::new(&validation_schema)?;
+    if let Err(e) = validator.validate(&json_value) {
+        return Err(anyhow::anyhow!("Json schema validation failed: {}", e));
+    }
+    Ok(serde_json::from_value(json_value)?)
+}
@@ -1,5 +1,91 @@
 use serde_json::json;
 
+/// Field constraints. `unique_in_parent` only makes sense for entity fields.
+fn constraints_schema(with_unique_in_parent: bool) -> serde_json::Value {
+    let mut schema = json!({
+        "type": "object",
+        "properties": {
+            "min": {
+                "type": "number"
+            },
+            "max": {
+                "type": "number"
+            },
+            "min_length": {
+                "type": "integer",
+                "minimum": 0
+            },
+            "max_length": {
+                "type": "integer",
+                "minimum": 0
+            },
+            "pattern": {
+                "type": "string"
+            },
+            "not_empty": {
+                "type": "boolean"
+            }
+        },
+        "additionalProperties": false
+    });
+    if with_unique_in_parent {
+        schema["properties"]["unique_in_parent"] = json!({ "type": "boolean" });
+    }
+    schema
+}
+
+/// A DTO, shared by the use cases of a feature or declared inline in one of them.
+fn dto_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "name": {
+                "type": "string"
+            },
+            "fields": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name": {
+                            "type": "string"
+                        },
+                        "type": {
+                            "type": "string"
+                        },
+                        "optional": {
+                            "type": "boolean"
+                        },
+                        "is_list": {
+                            "type": "boolean"
+                        },
+                        "enum_name": {
+                            "type": "string"
+                        },
+                        "enum_values": {
+                            "type": "array",
+                            "items": {
+                                "type": "string"
+                            }
+                        },
+                        "dto": {
+                            "type": "string"
+                        },
+                        "default": {
+                            "type": ["boolean", "number", "string"]
+                        },
+                        "constraints": constraints_schema(false),
+                    },
+                    "required": ["name", "type"],
+                    "additionalProperties": false
+                }
+            }
+        },
+        "required": ["name", "fields"],
+        "additionalProperties": false
+    })
+}
+
 pub fn json_validation_schema() -> serde_json::Value {
     json!({
         "type": "object",
@@ -41,6 +127,13 @@ pub fn json_validation_schema() -> serde_json::Value {
                     },
                     "prefix_path": {
                         "type": "string"
+                    },
+                    "storage": {
+                        "type": "string",
+                        "enum": ["memory", "sqlite"]
+                    },
+                    "templates_path": {
+                        "type": "string"
                     }
                 },
                 "required": ["language", "application_name", "organisation", "prefix_path"],
@@ -66,6 +159,9 @@ pub fn json_validation_schema() -> serde_json::Value {
                         "undoable": {
                             "type": "boolean"
                         },
+                        "undo_journal": {
+                            "type": "boolean"
+                        },
                         "fields": {
                             "type": "array",
                             "items": {
@@ -107,6 +203,16 @@ pub fn json_validation_schema() -> serde_json::Value {
                                             "type": "string"
                                         }
                                     },
+                                    "indexed": {
+                                        "type": "boolean"
+                                    },
+                                    "unique": {
+                                        "type": "boolean"
+                                    },
+                                    "default": {
+                                        "type": ["boolean", "number", "string"]
+                                    },
+                                    "constraints": constraints_schema(true),
                                 },
                                 "required": ["name", "type"],
                                 "additionalProperties": false
@@ -125,6 +231,10 @@ pub fn json_validation_schema() -> serde_json::Value {
                         "name": {
                             "type": "string"
                         },
+                        "dtos": {
+                            "type": "array",
+                            "items": dto_schema()
+                        },
                         "use_cases": {
                             "type": "array",
                             "items": {
@@ -148,87 +258,12 @@ pub fn json_validation_schema() -> serde_json::Value {
                                     "long_operation": {
                                         "type": "boolean"
                                     },
+                                    // inline, or the name of a shared DTO of the feature
                                     "dto_in": {
-                                        "type": "object",
-                                        "properties": {
-                                            "name": {
-                                                "type": "string"
-                                            },
-                                            "fields": {
-                                                "type": "array",
-                                                "items": {
-                                                    "type": "object",
-                                                    "properties": {
-                                                        "name": {
-                                                            "type": "string"
-                                                        },
-                                                        "type": {
-                                                            "type": "string"
-                                                        },
-                                                        "optional": {
-                                                            "type": "boolean"
-                                                        },
-                                                        "is_list": {
-                                                            "type": "boolean"
-                                                        },
-                                                        "enum_name": {
-                                                            "type": "string"
-                                                        },
-                                                        "enum_values": {
-                                                            "type": "array",
-                                                            "items": {
-                                                                "type": "string"
-                                                            }
-                                                        },
-                                                    },
-                                                    "required": ["name", "type"],
-                                                    "additionalProperties": false
-                                                }
-                                            }
-                                        },
-                                        "required": ["name", "fields"],
-                                        "additionalProperties": false
+                                        "oneOf": [{ "type": "string" }, dto_schema()]
                                     },
                                     "dto_out": {
-                                        "type": "object",
-                                        "properties": {
-                                            "name": {
-                                                "type": "string"
-                                            },
-                                            "fields": {
-                                                "type": "array",
-                                                "items": {
-                                                    "type": "object",
-                                                    "properties": {
-                                                        "name": {
-                                                            "type": "string"
-                                                        },
-                                                        "type": {
-                                                            "type": "string"
-                                                        },
-                                                        "optional": {
-                                                            "type": "boolean"
-                                                        },
-                                                        "is_list": {
-                                                            "type": "boolean"
-                                                        },
-                                                        "enum_name": {
-                                                            "type": "string"
-                                                        },
-                                                        "enum_values": {
-                                                            "type": "array",
-                                                            "items": {
-                                                                "type": "string"
-                                                            }
-                                                        },
-                                                    },
-                                                    "required": ["name", "type"],
-                                                    "additionalProperties": false
-                                                }
-                                            }
-                                        },
-                                        "required": ["name", "fields"],
-                                        "additionalProperties": false
+                                        "oneOf": [{ "type": "string" }, dto_schema()]
                                     }
                                 },
                                 "required": ["name"],
@@ -264,9 +299,55 @@ pub fn json_validation_schema() -> serde_json::Value {
 
                 },
                 "additionalProperties": false
+            },
+            "includes": {
+                "type": "array",
+                "items": {
+                    "type": "string"
+                }
+            },
+            "custom_files": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string"
+                        },
+                        "template": {
+                            "type": "string"
+                        },
+                        "scope": {
+                            "type": "string",
+                            "enum": ["once", "per_entity", "per_feature", "per_use_case"]
+                        },
+                        "nature": {
+                            "type": "string",
+                            "enum": ["infrastructure", "aggregate", "scaffold"]
+                        },
+                        "group": {
+                            "type": "string"
+                        }
+                    },
+                    "required": ["path", "template"],
+                    "additionalProperties": false
+                }
             }
         },
-        "required": ["schema", "global", "entities", "features", "ui"],
+        "required": ["schema", "global", "ui"],
+        "additionalProperties": false
+    })
+}
+
+/// Schema of a file listed by `includes`: entities and features only.
+pub fn included_json_validation_schema() -> serde_json::Value {
+    let manifest = json_validation_schema();
+    json!({
+        "type": "object",
+        "properties": {
+            "entities": manifest["properties"]["entities"],
+            "features": manifest["properties"]["features"]
+        },
         "additionalProperties": false
     })
 }
@@ -0,0 +1,669 @@
+// Generated by Qleany v0.0.1 from feature_use_case.tera
+use crate::use_cases::common::tools;
+use crate::{RefactorDto, RefactorOperation, RefactorReturnDto, RenamedFileDto};
+use anyhow::{Result, anyhow, bail};
+use common::database::CommandUnitOfWork;
+use common::entities::{
+    Entity, Feature, Field, File, Relationship, Root, System, UseCase, Workspace,
+};
+use common::snapshot::EntityTreeSnapshot;
+use common::types::EntityId;
+use common::undo_redo::UndoRedoCommand;
+use heck::{ToKebabCase, ToLowerCamelCase, ToPascalCase, ToSnakeCase};
+use std::any::Any;
+use std::path::Path;
+
+pub trait RefactorUnitOfWorkFactoryTrait: Send + Sync {
+    fn create(&self) -> Box<dyn RefactorUnitOfWorkTrait>;
+}
+
+#[macros::uow_trait]
+#[macros::uow_action(entity = "Root", action = "GetAll")]
+#[macros::uow_action(entity = "Workspace", action = "Get")]
+#[macros::uow_action(entity = "Workspace", action = "UpdateWithRelationships")]
+#[macros::uow_action(entity = "Workspace", action = "Snapshot")]
+#[macros::uow_action(entity = "Workspace", action = "Restore")]
+#[macros::uow_action(entity = "System", action = "Get")]
+#[macros::uow_action(entity = "File", action = "GetMulti")]
+#[macros::uow_action(entity = "Entity", action = "GetMulti")]
+#[macros::uow_action(entity = "Entity", action = "CreateOrphan")]
+#[macros::uow_action(entity = "Entity", action = "UpdateWithRelationshipsMulti")]
+#[macros::uow_action(entity = "Field", action = "GetMulti")]
+#[macros::uow_action(entity = "Field", action = "UpdateMulti")]
+#[macros::uow_action(entity = "Feature", action = "GetMulti")]
+#[macros::uow_action(entity = "Feature", action = "Update")]
+#[macros::uow_action(entity = "UseCase", action = "GetMulti")]
+#[macros::uow_action(entity = "UseCase", action = "Update")]
+#[macros::uow_action(entity = "Relationship", action = "RemoveMulti")]
+#[macros::uow_action(entity = "Relationship", action = "CreateOrphanMulti")]
+pub trait RefactorUnitOfWorkTrait: CommandUnitOfWork {}
+
+/// Renames and moves elements of the loaded manifest. The model references
+/// entities, features and use cases by id, so `entity:`, `inherits_from` and
+/// `use_cases[].entities` follow on their own; the references by name
+/// (`list_model_displayed_field`, relationships) are updated here.
+pub struct RefactorUseCase {
+    uow_factory: Box<dyn RefactorUnitOfWorkFactoryTrait>,
+    before: Option<EntityTreeSnapshot>,
+    after: Option<EntityTreeSnapshot>,
+    renamed: Option<FileOwner>,
+}
+
+/// A renamed element, to find the generated files named after it
+struct Rename {
+    owner: FileOwner,
+    old_name: String,
+    new_name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FileOwner {
+    Entity(EntityId),
+    Field(EntityId),
+    Feature(EntityId),
+    UseCase(EntityId),
+}
+
+impl RefactorUseCase {
+    pub fn new(uow_factory: Box<dyn RefactorUnitOfWorkFactoryTrait>) -> Self {
+        RefactorUseCase {
+            uow_factory,
+            before: None,
+            after: None,
+            renamed: None,
+        }
+    }
+
+    pub fn execute(&mut self, dto: &RefactorDto) -> Result<RefactorReturnDto> {
+        let mut uow = self.uow_factory.create();
+        uow.begin_transaction()?;
+
+        let root = uow
+            .get_all_root()?
+            .into_iter()
+            .next()
+            .ok_or(anyhow!("No root found"))?;
+        let workspace_id = root.workspace.ok_or(anyhow!("No manifest loaded"))?;
+        let workspace = uow
+            .get_workspace(&workspace_id)?
+            .ok_or(anyhow!("Workspace not found"))?;
+        let before = uow.snapshot_workspace(&[workspace_id])?;
+
+        let rename = match dto.operation {
+            RefactorOperation::RenameEntity => Some(rename_entity(&*uow, &workspace, dto)?),
+            RefactorOperation::RenameField => Some(rename_field(&*uow, &workspace, dto)?),
+            RefactorOperation::RenameFeature => Some(rename_feature(&*uow, &workspace, dto)?),
+            RefactorOperation::RenameUseCase => Some(rename_use_case(&*uow, &workspace, dto)?),
+            RefactorOperation::MoveFieldToParent => {
+                move_field_to_parent(&*uow, &workspace, dto)?;
+                None
+            }
+            RefactorOperation::ExtractBaseEntity => {
+                extract_base_entity(&*uow, workspace.clone(), dto)?;
+                None
+            }
+        };
+
+        if !matches!(
+            dto.operation,
+            RefactorOperation::RenameFeature | RefactorOperation::RenameUseCase
+        ) {
+            let workspace = uow
+                .get_workspace(&workspace_id)?
+                .ok_or(anyhow!("Workspace not found"))?;
+            update_relationships(&*uow, &workspace)?;
+        }
+
+        let return_dto = match &rename {
+            Some(rename) => report(&*uow, &root, &workspace, rename)?,
+            None => RefactorReturnDto::default(),
+        };
+
+        self.after = Some(uow.snapshot_workspace(&[workspace_id])?);
+        self.before = Some(before);
+        self.renamed = rename.map(|rename| rename.owner);
+        uow.commit()?;
+
+        Ok(return_dto)
+    }
+
+    fn restore(&self, snapshot: &Option<EntityTreeSnapshot>) -> Result<()> {
+        if let Some(snapshot) = snapshot {
+            let mut uow = self.uow_factory.create();
+            uow.begin_transaction()?;
+            uow.restore_workspace(snapshot)?;
+            uow.commit()?;
+        }
+        Ok(())
+    }
+}
+
+impl UndoRedoCommand for RefactorUseCase {
+    fn undo(&mut self) -> Result<()> {
+        self.restore(&self.before)
+    }
+
+    fn redo(&mut self) -> Result<()> {
+        self.restore(&self.after)
+    }
+
+    /// Successive renames of one element, as typed in the GUI, are undone at once.
+    fn can_merge(&self, other: &dyn UndoRedoCommand) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.renamed.is_some() && other.renamed == self.renamed)
+    }
+
+    fn merge(&mut self, other: &dyn UndoRedoCommand) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            Some(other) => {
+                self.after = other.after.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn rename_entity(
+    uow: &dyn RefactorUnitOfWorkTrait,
+    workspace: &Workspace,
+    dto: &RefactorDto,
+) -> Result<Rename> {
+    let entities = get_entities(uow, workspace)?;
+    let mut entity = find_entity(&entities, &dto.name)?;
+    check_new_name(
+        "Entity",
+        &dto.new_name,
+        entities
+            .iter()
+            .filter(|e| e.id != entity.id)
+            .map(|e| &e.name),
+    )?;
+
+    let rename = Rename {
+        owner: FileOwner::Entity(entity.id),
+        old_name: entity.name.clone(),
+        new_name: dto.new_name.clone(),
+    };
+    entity.name = dto.new_name.clone();
+    uow.update_entity_with_relationships_multi(&[entity])?;
+    Ok(rename)
+}
+
+fn rename_field(
+    uow: &dyn RefactorUnitOfWorkTrait,
+    workspace: &Workspace,
+    dto: &RefactorDto,
+) -> Result<Rename> {
+    let entities = get_entities(uow, workspace)?;
+    let owner = find_entity(&entities, &dto.owner_name)?;
+    let fields = get_fields(uow, &entities)?;
+    let field = fields
+        .iter()
+        .find(|f| owner.fields.contains(&f.id) && f.name == dto.name)
+        .ok_or(anyhow!("Field {}.{} not found", owner.name, dto.name))?;
+
+    // inherited fields share the namespace of the entity
+    let family = family(&entities, owner.id);
+    check_new_name(
+        "Field",
+        &dto.new_name,
+        entities
+            .iter()
+            .filter(|e| family.contains(&e.id))
+            .flat_map(|e| &e.fields)
+            .filter(|id| **id != field.id)
+            .filter_map(|id| fields.iter().find(|f| f.id == *id))
+            .map(|f| &f.name),
+    )?;
+
+    let rename = Rename {
+        owner: FileOwner::Field(field.id),
+        old_name: field.name.clone(),
+        new_name: dto.new_name.clone(),
+    };
+
+    // list models pointing to the owner, or to an entity inheriting it, may display the field
+    let heirs = heirs(&entities, owner.id);
+    let mut updated = vec![];
+    for mut f in fields.iter().cloned() {
+        let renamed = f.id == field.id;
+        let displays = f.entity.is_some_and(|e| heirs.contains(&e))
+            && f.list_model_displayed_field.as_deref() == Some(field.name.as_str());
+        if renamed {
+            f.name = dto.new_name.clone();
+        }
+        if displays {
+            f.list_model_displayed_field = Some(dto.new_name.clone());
+        }
+        if renamed || displays {
+            updated.push(f);
+        }
+    }
+    uow.update_field_multi(&updated)?;
+    Ok(rename)
+}
+
+fn rename_feature(
+    uow: &dyn RefactorUnitOfWorkTrait,
+    workspace: &Workspace,
+    dto: &RefactorDto,
+) -> Result<Rename> {
+    let features = get_features(uow, workspace)?;
+    let mut feature = find_feature(&features, &dto.name)?;
+    check_new_name(
+        "Feature",
+        &dto.new_name,
+        features
+            .iter()
+            .filter(|f| f.id != feature.id)
+            .map(|f| &f.name),
+    )?;
+
+    let rename = Rename {
+        owner: FileOwner::Feature(feature.id),
+        old_name: feature.name.clone(),
+        new_name: dto.new_name.clone(),
+    };
+    feature.name = dto.new_name.clone();
+    uow.update_feature(&feature)?;
+    Ok(rename)
+}
+
+fn rename_use_case(
+    uow: &dyn RefactorUnitOfWorkTrait,
+    workspace: &Workspace,
+    dto: &RefactorDto,
+) -> Result<Rename> {
+    let features = get_features(uow, workspace)?;
+    let feature = find_feature(&features, &dto.owner_name)?;
+    let use_cases: Vec<UseCase> = uow
+        .get_use_case_multi(&feature.use_cases)?
+        .into_iter()
+        .flatten()
+        .collect();
+    let mut use_case = use_cases
+        .iter()
+        .find(|uc| uc.name == dto.name)
+        .cloned()
+        .ok_or(anyhow!("Use case {}.{} not found", feature.name, dto.name))?;
+    check_new_name(
+        "Use case",
+        &dto.new_name,
+        use_cases
+            .iter()
+            .filter(|uc| uc.id != use_case.id)
+            .map(|uc| &uc.name),
+    )?;
+
+    let rename = Rename {
+        owner: FileOwner::UseCase(use_case.id),
+        old_name: use_case.name.clone(),
+        new_name: dto.new_name.clone(),
+    };
+    use_case.name = dto.new_name.clone();
+    uow.update_use_case(&use_case)?;
+    Ok(rename)
+}
+
+fn move_field_to_parent(
+    uow: &dyn RefactorUnitOfWorkTrait,
+    workspace: &Workspace,
+    dto: &RefactorDto,
+) -> Result<()> {
+    let entities = get_entities(uow, workspace)?;
+    let mut entity = find_entity(&entities, &dto.owner_name)?;
+    let parent_id = entity
+        .inherits_from
+        .ok_or(anyhow!("Entity {} has no parent entity", entity.name))?;
+    let mut parent = entities
+        .iter()
+        .find(|e| e.id == parent_id)
+        .cloned()
+        .ok_or(anyhow!("Parent entity of {} not found", entity.name))?;
+    let fields = get_fields(uow, &entities)?;
+
+    move_fields(
+        &entities,
+        &fields,
+        &mut entity,
+        &mut parent,
+        std::slice::from_ref(&dto.name),
+    )?;
+    uow.update_entity_with_relationships_multi(&[entity, parent])?;
+    Ok(())
+}
+
+fn extract_base_entity(
+    uow: &dyn RefactorUnitOfWorkTrait,
+    mut workspace: Workspace,
+    dto: &RefactorDto,
+) -> Result<()> {
+    let mut entities = get_entities(uow, &workspace)?;
+    let mut entity = find_entity(&entities, &dto.name)?;
+    check_new_name("Entity", &dto.new_name, entities.iter().map(|e| &e.name))?;
+    let fields = get_fields(uow, &entities)?;
+
+    let mut base = uow.create_orphan_entity(&Entity {
+        id: 0,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        name: dto.new_name.clone(),
+        inherits_from: entity.inherits_from,
+        only_for_heritage: true,
+        fields: vec![],
+        relationships: vec![],
+        single_model: false,
+        undoable: false,
+        undo_journal: false,
+        source_file: entity.source_file.clone(),
+    })?;
+    entity.inherits_from = Some(base.id);
+
+    // the manifest lists base entities first
+    let position = workspace
+        .entities
+        .iter()
+        .position(|id| *id == entity.id)
+        .unwrap_or(workspace.entities.len());
+    workspace.entities.insert(position, base.id);
+    uow.update_workspace_with_relationships(&workspace)?;
+
+    for e in entities.iter_mut().filter(|e| e.id == entity.id) {
+        e.inherits_from = entity.inherits_from;
+    }
+    entities.push(base.clone());
+    move_fields(&entities, &fields, &mut entity, &mut base, &dto.field_names)?;
+    uow.update_entity_with_relationships_multi(&[entity, base])?;
+    Ok(())
+}
+
+/// Move the named fields of `from` to `to`, an ancestor of `from`.
+fn move_fields(
+    entities: &[Entity],
+    fields: &[Field],
+    from: &mut Entity,
+    to: &mut Entity,
+    names: &[String],
+) -> Result<()> {
+    let mut moved = vec![];
+    for name in names {
+        let field = fields
+            .iter()
+            .find(|f| from.fields.contains(&f.id) && f.name == *name)
+            .ok_or(anyhow!("Field {}.{} not found", from.name, name))?;
+        moved.push(field.id);
+    }
+
+    // every entity inheriting `to` gets the fields
+    let family = family(entities, to.id);
+    let taken = entities
+        .iter()
+        .filter(|e| family.contains(&e.id))
+        .flat_map(|e| &e.fields)
+        .filter(|id| !moved.contains(id))
+        .filter_map(|id| fields.iter().find(|f| f.id == *id));
+    for field in taken {
+        if names.contains(&field.name) {
+            bail!(
+                "Field {} already exists in {} or in an entity inheriting it",
+                field.name,
+                to.name
+            );
+        }
+    }
+
+    from.fields.retain(|id| !moved.contains(id));
+    to.fields.extend(moved);
+    Ok(())
+}
+
+/// Entity names and field lists are part of the relationships, generate them again.
+fn update_relationships(uow: &dyn RefactorUnitOfWorkTrait, workspace: &Workspace) -> Result<()> {
+    let entities = get_entities(uow, workspace)?;
+    let fields = get_fields(uow, &entities)?;
+    let old_relationship_ids: Vec<EntityId> = entities
+        .iter()
+        .flat_map(|e| e.relationships.clone())
+        .collect();
+    uow.remove_relationship_multi(&old_relationship_ids)?;
+
+    let all_relationships = tools::generate_relationships(&entities, &fields);
+    let mut updated = vec![];
+    for mut entity in entities {
+        let relationships = all_relationships
+            .get(&entity.id)
+            .cloned()
+            .unwrap_or_default();
+        entity.relationships = uow
+            .create_orphan_relationship_multi(&relationships)?
+            .iter()
+            .map(|relationship| relationship.id)
+            .collect();
+        updated.push(entity);
+    }
+    uow.update_entity_with_relationships_multi(&updated)?;
+    Ok(())
+}
+
+/// List the generated files named after the renamed element, and those of them
+/// already written to disk, which the next generation leaves behind.
+fn report(
+    uow: &dyn RefactorUnitOfWorkTrait,
+    root: &Root,
+    workspace: &Workspace,
+    rename: &Rename,
+) -> Result<RefactorReturnDto> {
+    let mut return_dto = RefactorReturnDto::default();
+    let Some(system_id) = root.system else {
+        return Ok(return_dto);
+    };
+    let system = uow
+        .get_system(&system_id)?
+        .ok_or(anyhow!("System not found"))?;
+    let files: Vec<File> = uow
+        .get_file_multi(&system.files)?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let manifest_dir = Path::new(&workspace.manifest_absolute_path);
+    for file in files.iter().filter(|f| rename.owner.owns(f)) {
+        let old_path = Path::new(&file.relative_path)
+            .join(&file.name)
+            .to_string_lossy()
+            .to_string();
+        let new_path = rename_in_path(&old_path, &rename.old_name, &rename.new_name);
+        if new_path == old_path {
+            continue;
+        }
+        if manifest_dir.join(&old_path).exists() {
+            return_dto.orphaned_files.push(old_path.clone());
+        }
+        return_dto
+            .renamed_files
+            .push(RenamedFileDto { old_path, new_path });
+    }
+    Ok(return_dto)
+}
+
+impl FileOwner {
+    fn owns(&self, file: &File) -> bool {
+        match self {
+            FileOwner::Entity(id) => file.entity == Some(*id),
+            FileOwner::Field(id) => file.field == Some(*id),
+            FileOwner::Feature(id) => file.feature == Some(*id),
+            FileOwner::UseCase(id) => file.use_case == Some(*id),
+        }
+    }
+}
+
+/// Replace the old name by the new one in a generated file path, in each case
+/// the generators use (`car_part`, `car-part`, `CarPart`, `carPart`). Only whole
+/// words are replaced: renaming `Car` leaves `cart.rs` alone.
+fn rename_in_path(path: &str, old_name: &str, new_name: &str) -> String {
+    let cases = |name: &str| {
+        [
+            name.to_snake_case(),
+            name.to_kebab_case(),
+            name.to_pascal_case(),
+            name.to_lower_camel_case(),
+        ]
+    };
+    let replacements: Vec<(String, String)> =
+        cases(old_name).into_iter().zip(cases(new_name)).collect();
+    let continues_word = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit();
+
+    let mut result = String::with_capacity(path.len());
+    let mut previous: Option<char> = None;
+    let mut rest = path;
+    'outer: while let Some(c) = rest.chars().next() {
+        for (old, new) in &replacements {
+            let Some(after) = rest.strip_prefix(old.as_str()) else {
+                continue;
+            };
+            let starts_word = old.starts_with(|c: char| c.is_ascii_uppercase())
+                || !previous.is_some_and(continues_word);
+            let ends_word = !after.starts_with(continues_word);
+            if !old.is_empty() && starts_word && ends_word {
+                result.push_str(new);
+                previous = old.chars().last();
+                rest = after;
+                continue 'outer;
+            }
+        }
+        result.push(c);
+        previous = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+fn check_new_name<'a>(
+    kind: &str,
+    new_name: &str,
+    others: impl IntoIterator<Item = &'a String>,
+) -> Result<()> {
+    if new_name.trim().is_empty() {
+        bail!("{} name cannot be empty", kind);
+    }
+    // generated file and type names are derived from the snake case
+    let snake_name = new_name.to_snake_case();
+    if let Some(other) = others.into_iter().find(|n| n.to_snake_case() == snake_name) {
+        bail!("{} {} already exists", kind, other);
+    }
+    Ok(())
+}
+
+fn get_entities(uow: &dyn RefactorUnitOfWorkTrait, workspace: &Workspace) -> Result<Vec<Entity>> {
+    Ok(uow
+        .get_entity_multi(&workspace.entities)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+fn get_fields(uow: &dyn RefactorUnitOfWorkTrait, entities: &[Entity]) -> Result<Vec<Field>> {
+    let field_ids: Vec<EntityId> = entities.iter().flat_map(|e| e.fields.clone()).collect();
+    Ok(uow
+        .get_field_multi(&field_ids)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+fn get_features(uow: &dyn RefactorUnitOfWorkTrait, workspace: &Workspace) -> Result<Vec<Feature>> {
+    Ok(uow
+        .get_feature_multi(&workspace.features)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+fn find_entity(entities: &[Entity], name: &str) -> Result<Entity> {
+    entities
+        .iter()
+        .find(|e| e.name == name)
+        .cloned()
+        .ok_or(anyhow!("Entity {} not found", name))
+}
+
+fn find_feature(features: &[Feature], name: &str) -> Result<Feature> {
+    features
+        .iter()
+        .find(|f| f.name == name)
+        .cloned()
+        .ok_or(anyhow!("Feature {} not found", name))
+}
+
+/// The entity and its ancestors, closest first.
+fn lineage(entities: &[Entity], id: EntityId) -> Vec<EntityId> {
+    let mut lineage = vec![id];
+    let mut current = id;
+    // bounded, in case of an inheritance cycle
+    for _ in 0..entities.len() {
+        let parent = entities
+            .iter()
+            .find(|e| e.id == current)
+            .and_then(|e| e.inherits_from);
+        match parent {
+            Some(parent) if !lineage.contains(&parent) => {
+                lineage.push(parent);
+                current = parent;
+            }
+            _ => break,
+        }
+    }
+    lineage
+}
+
+/// The entity and every entity inheriting it, directly or not.
+fn heirs(entities: &[Entity], id: EntityId) -> Vec<EntityId> {
+    entities
+        .iter()
+        .filter(|e| lineage(entities, e.id).contains(&id))
+        .map(|e| e.id)
+        .collect()
+}
+
+/// Entities sharing fields with the entity: its ancestors and its heirs.
+fn family(entities: &[Entity], id: EntityId) -> Vec<EntityId> {
+    let mut family = lineage(entities, id);
+    family.extend(heirs(entities, id).into_iter().filter(|e| *e != id));
+    family
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_in_path_replaces_whole_words() {
+        assert_eq!(
+            rename_in_path(
+                "crates/direct_access/src/car/car_controller.rs",
+                "Car",
+                "Vehicle"
+            ),
+            "crates/direct_access/src/vehicle/vehicle_controller.rs"
+        );
+        assert_eq!(
+            rename_in_path("src/cart/CarPartModel.h", "CarPart", "Wheel"),
+            "src/cart/WheelModel.h"
+        );
+        assert_eq!(
+            rename_in_path("src/cart/cart_repository.rs", "Car", "Vehicle"),
+            "src/cart/cart_repository.rs"
+        );
+        assert_eq!(
+            rename_in_path("qml/ListCarPage.qml", "Car", "Vehicle"),
+            "qml/ListVehiclePage.qml"
+        );
+    }
+}
@@ -2,12 +2,17 @@ use std::path::PathBuf;
 
 use crate::SaveDto;
 use crate::use_cases::common::CURRENT_SCHEMA_VERSION;
+use crate::use_cases::common::documents::Documents;
+use crate::use_cases::common::includes;
+use crate::use_cases::common::migration;
 use crate::use_cases::common::model_structs;
+use crate::use_cases::common::yaml_edit;
 use anyhow::Result;
 use common::database::CommandUnitOfWork;
 use common::entities::UserInterface;
 use common::entities::{
-    Dto, DtoField, Entity, Feature, Field, FieldRelationshipType, Global, Root, UseCase, Workspace,
+    CustomFile, Dto, DtoField, DtoFieldType, Entity, Feature, Field, FieldRelationshipType,
+    FieldType, Global, Root, UseCase, Workspace,
 };
 use common::types::EntityId;
 
@@ -15,6 +20,7 @@ pub trait SaveUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn SaveUnitOfWorkTrait>;
 }
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetAll")]
 #[macros::uow_action(entity = "Root", action = "GetRelationship")]
 #[macros::uow_action(entity = "Workspace", action = "Update")]
@@ -33,8 +39,100 @@ pub trait SaveUnitOfWorkFactoryTrait {
 #[macros::uow_action(entity = "Dto", action = "GetMulti")]
 #[macros::uow_action(entity = "Dto", action = "GetRelationship")]
 #[macros::uow_action(entity = "DtoField", action = "GetMulti")]
+#[macros::uow_action(entity = "CustomFile", action = "GetMulti")]
 pub trait SaveUnitOfWorkTrait: CommandUnitOfWork {}
 
+/// Constraints are omitted from the manifest when none is set.
+fn model_constraints(
+    constraints: model_structs::Constraints,
+) -> Option<model_structs::Constraints> {
+    Some(constraints).filter(|c| !c.is_empty())
+}
+
+/// `original` edited to read as `new`, keeping its comments, blank lines and
+/// key order, or `None` if it can't be. `normalize` reads a text the way load
+/// does, so that keys the text leaves to their default aren't written.
+fn edit_document(
+    original: &str,
+    new: &serde_json::Value,
+    normalize: impl Fn(&str) -> Option<serde_json::Value>,
+) -> Option<String> {
+    let old = normalize(original)?;
+    let mut new = new.clone();
+    reconcile(&old, &mut new);
+    let edited = yaml_edit::apply(original, &old, &new)?;
+    // the edited text must read back as the saved manifest
+    (normalize(&edited)? == new).then_some(edited)
+}
+
+/// Spell the flags of `new` the way `old` does. Every optional flag of the
+/// manifest defaults to false, so a `false` the text writes out and the model
+/// leaves out, or the other way round, is not a change.
+fn reconcile(old: &serde_json::Value, new: &mut serde_json::Value) {
+    use serde_json::Value;
+
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                match new.get_mut(key) {
+                    Some(new_value) => reconcile(old_value, new_value),
+                    None if *old_value == Value::Bool(false) => {
+                        new.insert(key.clone(), Value::Bool(false));
+                    }
+                    None => {}
+                }
+            }
+            new.retain(|key, value| old.contains_key(key) || *value != Value::Bool(false));
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            // items by name, a renamed item by position
+            let name = |item: &Value| item.get("name").cloned();
+            for (i, new_item) in new.iter_mut().enumerate() {
+                let old_item = old
+                    .iter()
+                    .find(|item| name(item).is_some() && name(item) == name(new_item))
+                    .or_else(|| old.get(i));
+                if let Some(old_item) = old_item {
+                    reconcile(old_item, new_item);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn normalize_manifest(text: &str) -> Option<serde_json::Value> {
+    let mut value: serde_json::Value = serde_yml::from_str(text).ok()?;
+    migration::migrate_to_current(&mut value).ok()?;
+    let manifest: model_structs::Manifest = serde_json::from_value(value).ok()?;
+    serde_json::to_value(manifest).ok()
+}
+
+fn normalize_included(text: &str) -> Option<serde_json::Value> {
+    let value: serde_json::Value = serde_yml::from_str(text).ok()?;
+    let included: model_structs::IncludedManifest = if value.is_null() {
+        model_structs::IncludedManifest::default()
+    } else {
+        serde_json::from_value(value).ok()?
+    };
+    serde_json::to_value(included).ok()
+}
+
+fn parse_number(value: &Option<String>) -> Option<serde_json::Number> {
+    value.as_deref().and_then(|v| serde_json::from_str(v).ok())
+}
+
+/// Booleans and numbers of scalar fields are written unquoted, other defaults as strings.
+fn model_default(value: &Option<String>, unquoted: bool) -> Option<serde_json::Value> {
+    let value = value.as_deref()?;
+    Some(match serde_json::from_str(value) {
+        Ok(literal @ (serde_json::Value::Bool(_) | serde_json::Value::Number(_))) if unquoted => {
+            literal
+        }
+        _ => serde_json::Value::String(value.to_string()),
+    })
+}
+
 pub struct SaveUseCase {
     uow_factory: Box<dyn SaveUnitOfWorkFactoryTrait>,
 }
@@ -81,6 +179,13 @@ impl SaveUseCase {
         let features = uow.get_feature_multi(&workspace.features)?;
         let features = features.into_iter().flatten().collect::<Vec<Feature>>();
 
+        // Get custom files
+        let custom_files = uow.get_custom_file_multi(&workspace.custom_files)?;
+        let custom_files = custom_files
+            .into_iter()
+            .flatten()
+            .collect::<Vec<CustomFile>>();
+
         // Get all fields
         let field_ids = entities
             .iter()
@@ -97,19 +202,15 @@ impl SaveUseCase {
         let use_cases = uow.get_use_case_multi(&use_case_ids)?;
         let use_cases = use_cases.into_iter().flatten().collect::<Vec<UseCase>>();
 
-        // Get all DTOs
-        let dto_ids = use_cases
+        // Get all DTOs, shared by the features or declared inline in the use cases
+        let dto_ids = features
             .iter()
-            .flat_map(|uc| {
-                let mut ids = Vec::new();
-                if let Some(id) = uc.dto_in {
-                    ids.push(id);
-                }
-                if let Some(id) = uc.dto_out {
-                    ids.push(id);
-                }
-                ids
-            })
+            .flat_map(|f| f.dtos.clone())
+            .chain(
+                use_cases
+                    .iter()
+                    .flat_map(|uc| [uc.dto_in, uc.dto_out].into_iter().flatten()),
+            )
             .collect::<Vec<EntityId>>();
         let dtos = uow.get_dto_multi(&dto_ids)?;
         let dtos = dtos.into_iter().flatten().collect::<Vec<Dto>>();
@@ -129,10 +230,13 @@ impl SaveUseCase {
             .to_str()
             .ok_or(anyhow::anyhow!("Failed to convert path to string"))?
             .to_string();
+        // the texts of the manifest as loaded, to be edited
+        let loaded = Documents::from_workspace(&workspace);
         if workspace.manifest_absolute_path != new_manifest_path {
             workspace.manifest_absolute_path = new_manifest_path;
             uow.update_workspace(&workspace)?;
         }
+        let manifest_dir = workspace.manifest_absolute_path.clone();
 
         uow.commit()?;
 
@@ -145,6 +249,11 @@ impl SaveUseCase {
                 domain: global.organisation_domain.clone(),
             },
             prefix_path: global.prefix_path.clone(),
+            storage: match global.storage.as_str() {
+                "" | "memory" => None,
+                storage => Some(storage.to_string()),
+            },
+            templates_path: Some(global.templates_path.clone()).filter(|path| !path.is_empty()),
         };
 
         let model_ui = model_structs::Ui {
@@ -208,6 +317,28 @@ impl SaveUseCase {
                             list_model_displayed_field: field.list_model_displayed_field.clone(),
                             enum_name: field.enum_name.clone(),
                             enum_values: field.enum_values.clone(),
+                            indexed: if field.indexed { Some(true) } else { None },
+                            unique: if field.unique { Some(true) } else { None },
+                            default_value: model_default(
+                                &field.default_value,
+                                matches!(
+                                    field.field_type,
+                                    FieldType::Boolean
+                                        | FieldType::Integer
+                                        | FieldType::UInteger
+                                        | FieldType::Float
+                                        | FieldType::Duration
+                                ),
+                            ),
+                            constraints: model_constraints(model_structs::Constraints {
+                                min: parse_number(&field.min_value),
+                                max: parse_number(&field.max_value),
+                                min_length: field.min_length,
+                                max_length: field.max_length,
+                                pattern: field.pattern.clone(),
+                                not_empty: field.not_empty,
+                                unique_in_parent: field.unique_in_parent,
+                            }),
                         }
                     })
                     .collect::<Vec<model_structs::Field>>();
@@ -229,13 +360,86 @@ impl SaveUseCase {
                     },
                     fields: entity_fields,
                     undoable: entity.undoable,
+                    undo_journal: if entity.undo_journal {
+                        Some(true)
+                    } else {
+                        None
+                    },
                 }
             })
             .collect::<Vec<model_structs::Entity>>();
 
+        let model_dto = |dto: &Dto| {
+            let dto_fields = dto
+                .fields
+                .iter()
+                .filter_map(|field_id| dto_fields.iter().find(|f| f.id == *field_id))
+                .map(|field| {
+                    let field_type = format!("{:?}", field.field_type).to_lowercase();
+                    model_structs::DtoField {
+                        name: field.name.clone(),
+                        r#type: field_type,
+                        optional: if field.optional { Some(true) } else { None },
+                        is_list: if field.is_list { Some(true) } else { None },
+                        enum_name: field.enum_name.clone(),
+                        enum_values: field.enum_values.clone(),
+                        dto: field.dto.and_then(|dto_id| {
+                            dtos.iter().find(|d| d.id == dto_id).map(|d| d.name.clone())
+                        }),
+                        default_value: model_default(
+                            &field.default_value,
+                            matches!(
+                                field.field_type,
+                                DtoFieldType::Boolean
+                                    | DtoFieldType::Integer
+                                    | DtoFieldType::UInteger
+                                    | DtoFieldType::Float
+                                    | DtoFieldType::Duration
+                            ),
+                        ),
+                        constraints: model_constraints(model_structs::Constraints {
+                            min: parse_number(&field.min_value),
+                            max: parse_number(&field.max_value),
+                            min_length: field.min_length,
+                            max_length: field.max_length,
+                            pattern: field.pattern.clone(),
+                            not_empty: field.not_empty,
+                            unique_in_parent: false,
+                        }),
+                    }
+                })
+                .collect::<Vec<model_structs::DtoField>>();
+
+            model_structs::Dto {
+                name: dto.name.clone(),
+                fields: dto_fields,
+            }
+        };
+        // a shared DTO is written as its name, an inline one in full
+        let model_dto_ref = |dto_id: Option<EntityId>, shared_dto_id: Option<EntityId>| {
+            if let Some(shared_dto_id) = shared_dto_id {
+                dtos.iter()
+                    .find(|d| d.id == shared_dto_id)
+                    .map(|d| model_structs::DtoRef::Shared(d.name.clone()))
+            } else {
+                dto_id.and_then(|dto_id| {
+                    dtos.iter()
+                        .find(|d| d.id == dto_id)
+                        .map(|d| model_structs::DtoRef::Inline(model_dto(d)))
+                })
+            }
+        };
+
         let model_features = features
             .iter()
             .map(|feature| {
+                let feature_dtos = feature
+                    .dtos
+                    .iter()
+                    .filter_map(|dto_id| dtos.iter().find(|d| d.id == *dto_id))
+                    .map(model_dto)
+                    .collect::<Vec<model_structs::Dto>>();
+
                 let feature_use_cases = feature
                     .use_cases
                     .iter()
@@ -252,71 +456,8 @@ impl SaveUseCase {
                             })
                             .collect::<Vec<String>>();
 
-                        let dto_in = use_case.dto_in.and_then(|dto_id| {
-                            dtos.iter().find(|d| d.id == dto_id).map(|dto| {
-                                let dto_fields = dto
-                                    .fields
-                                    .iter()
-                                    .filter_map(|field_id| {
-                                        dto_fields.iter().find(|f| f.id == *field_id)
-                                    })
-                                    .map(|field| {
-                                        let field_type =
-                                            format!("{:?}", field.field_type).to_lowercase();
-                                        model_structs::DtoField {
-                                            name: field.name.clone(),
-                                            r#type: field_type,
-                                            optional: if field.optional {
-                                                Some(true)
-                                            } else {
-                                                None
-                                            },
-                                            is_list: if field.is_list { Some(true) } else { None },
-                                            enum_name: field.enum_name.clone(),
-                                            enum_values: field.enum_values.clone(),
-                                        }
-                                    })
-                                    .collect::<Vec<model_structs::DtoField>>();
-
-                                model_structs::Dto {
-                                    name: dto.name.clone(),
-                                    fields: dto_fields,
-                                }
-                            })
-                        });
-
-                        let dto_out = use_case.dto_out.and_then(|dto_id| {
-                            dtos.iter().find(|d| d.id == dto_id).map(|dto| {
-                                let dto_fields = dto
-                                    .fields
-                                    .iter()
-                                    .filter_map(|field_id| {
-                                        dto_fields.iter().find(|f| f.id == *field_id)
-                                    })
-                                    .map(|field| {
-                                        let field_type =
-                                            format!("{:?}", field.field_type).to_lowercase();
-                                        model_structs::DtoField {
-                                            name: field.name.clone(),
-                                            r#type: field_type,
-                                            optional: if field.optional {
-                                                Some(true)
-                                            } else {
-                                                None
-                                            },
-                                            is_list: if field.is_list { Some(true) } else { None },
-                                            enum_name: field.enum_name.clone(),
-                                            enum_values: field.enum_values.clone(),
-                                        }
-                                    })
-                                    .collect::<Vec<model_structs::DtoField>>();
-
-                                model_structs::Dto {
-                                    name: dto.name.clone(),
-                                    fields: dto_fields,
-                                }
-                            })
-                        });
+                        let dto_in = model_dto_ref(use_case.dto_in, use_case.shared_dto_in);
+                        let dto_out = model_dto_ref(use_case.dto_out, use_case.shared_dto_out);
 
                         model_structs::UseCase {
                             name: use_case.name.clone(),
@@ -344,30 +485,116 @@ impl SaveUseCase {
 
                 model_structs::Feature {
                     name: feature.name.clone(),
+                    dtos: feature_dtos,
                     use_cases: feature_use_cases,
                 }
             })
             .collect::<Vec<model_structs::Feature>>();
 
+        // Build custom files, omitting default values
+        let model_custom_files = custom_files
+            .iter()
+            .map(|custom_file| model_structs::CustomFile {
+                path: custom_file.path.clone(),
+                template: custom_file.template_name.clone(),
+                scope: Some(custom_file.scope.clone()).filter(|s| s != "once"),
+                nature: Some(custom_file.nature.clone()).filter(|n| n != "scaffold"),
+                group: Some(custom_file.group.clone()).filter(|g| g != "custom"),
+            })
+            .collect::<Vec<model_structs::CustomFile>>();
+
+        // Each entity and feature goes back to the included file it came from,
+        // or to the manifest if that file is no longer included. Every included
+        // file is written, so that it doesn't keep what was removed from it.
+        let included_files = includes::included_files(
+            std::path::Path::new(&manifest_dir),
+            &dto.manifest_path,
+            &workspace.includes,
+        )?;
+        let mut included_manifests: Vec<model_structs::IncludedManifest> = included_files
+            .iter()
+            .map(|_| model_structs::IncludedManifest::default())
+            .collect();
+        let included_index =
+            |source_file: &str| included_files.iter().position(|file| file == source_file);
+
+        let mut manifest_entities = vec![];
+        for (model_entity, entity) in model_entities.into_iter().zip(&entities) {
+            match included_index(&entity.source_file) {
+                Some(index) => included_manifests[index].entities.push(model_entity),
+                None => manifest_entities.push(model_entity),
+            }
+        }
+        let mut manifest_features = vec![];
+        for (model_feature, feature) in model_features.into_iter().zip(&features) {
+            match included_index(&feature.source_file) {
+                Some(index) => included_manifests[index].features.push(model_feature),
+                None => manifest_features.push(model_feature),
+            }
+        }
+
         // Create the manifest
         let manifest = model_structs::Manifest {
             schema: model_structs::Schema {
                 version: i32::try_from(CURRENT_SCHEMA_VERSION)?,
             },
             global: model_global,
-            entities: model_entities,
-            features: model_features,
+            includes: workspace.includes.clone(),
+            entities: manifest_entities,
+            features: manifest_features,
             ui: model_ui,
+            custom_files: model_custom_files,
         };
 
-        // Serialize to YAML
-        let yaml_content = serde_yml::to_string(&manifest)?;
-
-        // add "---\n" to the beginning of the YAML content
-        let yaml_content = format!("---\n{}", yaml_content);
+        // Edit the text as loaded where possible, else serialize to YAML
+        let manifest_value = serde_json::to_value(&manifest)?;
+        let yaml_content = match loaded
+            .as_ref()
+            .and_then(|loaded| edit_document(&loaded.manifest, &manifest_value, normalize_manifest))
+        {
+            Some(edited) => edited,
+            // add "---\n" to the beginning of the YAML content
+            None => format!("---\n{}", serde_yml::to_string(&manifest)?),
+        };
 
         // Write to file
-        std::fs::write(&dto.manifest_path, yaml_content)?;
+        std::fs::write(&dto.manifest_path, &yaml_content)?;
+
+        let mut included_texts = vec![];
+
+        for (file, included_manifest) in included_files.iter().zip(included_manifests) {
+            let edited = loaded
+                .as_ref()
+                .and_then(|loaded| loaded.included.get(file))
+                .filter(|_| !file.ends_with(".json"))
+                .and_then(|original| {
+                    let value = serde_json::to_value(&included_manifest).ok()?;
+                    edit_document(original, &value, normalize_included)
+                });
+            let content = match edited {
+                Some(edited) => edited,
+                None if file.ends_with(".json") => {
+                    serde_json::to_string_pretty(&included_manifest)?
+                }
+                None => format!("---\n{}", serde_yml::to_string(&included_manifest)?),
+            };
+            let path = std::path::Path::new(&manifest_dir).join(file);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, &content)?;
+            included_texts.push(content);
+        }
+
+        // The positions moved with the new layout: keep the texts as saved
+        uow.begin_transaction()?;
+        uow.update_workspace(&Workspace {
+            manifest_text: yaml_content,
+            included_files,
+            included_texts,
+            ..workspace
+        })?;
+        uow.commit()?;
 
         Ok(())
     }
@@ -0,0 +1,114 @@
+// Generated by Qleany v0.0.1 from feature_use_case.tera
+use crate::use_cases::common::migration::{migrate_to_current, schema_version};
+use crate::use_cases::common::source_map::unescape;
+use crate::use_cases::common::{parse, yaml_edit};
+use crate::{MigrationChangeDto, MigrationChangeKind, UpgradeDto, UpgradeReturnDto};
+use anyhow::{Result, anyhow};
+use common::database::QueryUnitOfWork;
+
+pub trait UpgradeUnitOfWorkFactoryTrait: Send + Sync {
+    fn create(&self) -> Box<dyn UpgradeUnitOfWorkTrait>;
+}
+
+pub trait UpgradeUnitOfWorkTrait: QueryUnitOfWork {}
+
+pub struct UpgradeUseCase {
+    uow_factory: Box<dyn UpgradeUnitOfWorkFactoryTrait>,
+}
+
+impl UpgradeUseCase {
+    pub fn new(uow_factory: Box<dyn UpgradeUnitOfWorkFactoryTrait>) -> Self {
+        UpgradeUseCase { uow_factory }
+    }
+
+    /// Migrate the manifest file to the current schema version, editing its
+    /// text in place, and report what the migration changed. A dry run only
+    /// reports.
+    pub fn execute(&mut self, dto: &UpgradeDto) -> Result<UpgradeReturnDto> {
+        let path = &dto.manifest_path;
+        if !std::path::Path::new(path).is_file() {
+            return Err(anyhow!("File does not exist"));
+        }
+
+        let uow = self.uow_factory.create();
+        uow.begin_transaction()?;
+        let result = upgrade(path, dto.dry_run);
+        uow.end_transaction()?;
+        result
+    }
+}
+
+/// Migrate the manifest at `path`, written back unless `dry_run`.
+fn upgrade(path: &str, dry_run: bool) -> Result<UpgradeReturnDto> {
+    let source = std::fs::read_to_string(path)?;
+
+    let mut value = parse(path, &source)?;
+    let from_version = schema_version(&value)?;
+    let changes = migrate_to_current(&mut value)?;
+    let to_version = schema_version(&value)?;
+
+    let mut return_dto = UpgradeReturnDto {
+        from_version,
+        to_version,
+        changes,
+        diff: String::new(),
+        formatting_preserved: true,
+    };
+    if from_version == to_version {
+        return Ok(return_dto);
+    }
+
+    // the edited text must read back as the migrated manifest
+    let edited = edit_in_place(&source, to_version, &return_dto.changes)
+        .filter(|text| parse(path, text).is_ok_and(|edited| edited == value));
+    let upgraded = match edited {
+        Some(text) => text,
+        None => {
+            return_dto.formatting_preserved = false;
+            serialize(path, &value)?
+        }
+    };
+
+    return_dto.diff = similar::TextDiff::from_lines(&source, &upgraded)
+        .unified_diff()
+        .context_radius(3)
+        .header(
+            &format!("{} (schema v{})", path, from_version),
+            &format!("{} (schema v{})", path, to_version),
+        )
+        .to_string();
+
+    if !dry_run {
+        std::fs::write(path, &upgraded)?;
+    }
+
+    Ok(return_dto)
+}
+
+/// Apply the version bump and the migration changes to the manifest text.
+fn edit_in_place(source: &str, to_version: i64, changes: &[MigrationChangeDto]) -> Option<String> {
+    let mut text = yaml_edit::replace_scalar(source, "/schema/version", &to_version.to_string())?;
+    for change in changes {
+        text = match change.kind {
+            MigrationChangeKind::Removed => yaml_edit::remove_key(&text, &change.location)?,
+            MigrationChangeKind::Renamed => {
+                yaml_edit::rename_key(&text, &change.location, change.new_key.as_deref()?)?
+            }
+            MigrationChangeKind::Defaulted => {
+                let (parent, key) = change.location.rsplit_once('/')?;
+                let value = serde_yml::from_str(&change.value).ok()?;
+                yaml_edit::insert_key(&text, parent, &unescape(key), &value)?
+            }
+        };
+    }
+    Some(text)
+}
+
+/// The whole manifest written again, for manifests which can't be edited in place.
+fn serialize(path: &str, value: &serde_json::Value) -> Result<String> {
+    if path.ends_with(".json") {
+        Ok(serde_json::to_string_pretty(value)? + "\n")
+    } else {
+        Ok(serde_yml::to_string(value)?)
+    }
+}
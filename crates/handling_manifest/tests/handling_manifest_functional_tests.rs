@@ -1,7 +1,10 @@
 use anyhow::Result;
 use common::database::db_context::DbContext;
-use common::event::EventHub;
-use handling_manifest::{LoadDto, SaveDto};
+use common::database::transactions::Transaction;
+use common::direct_access::repository_factory;
+use common::event::{EventBuffer, EventHub};
+use common::undo_redo::UndoRedoManager;
+use handling_manifest::{LoadDto, RefactorDto, RefactorOperation, SaveDto, UpgradeDto};
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
@@ -114,3 +117,938 @@ fn test_load_and_save_yaml() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_custom_files_round_trip_and_check() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_custom_files_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+    let saved_path = dir.join("saved.yaml");
+
+    let manifest = format!(
+        "{}\ncustom_files:\n  - path: \"{{prefix}}/{{feature}}/README.md\"\n    template: feature_readme\n    scope: per_feature\n  - path: \"{{entity}}.md\"\n    template: entity_doc\n",
+        fs::read_to_string("../../qleany.yaml")?
+    );
+    fs::write(&manifest_path, manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    let save_dto = SaveDto {
+        manifest_path: saved_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let saved = fs::read_to_string(&saved_path)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert!(saved.contains("template: feature_readme"));
+    assert!(saved.contains("scope: per_feature"));
+    // defaults are not written back
+    assert!(!saved.contains("scope: once"));
+    assert!(!saved.contains("nature: scaffold"));
+    // {entity} is not available for a file generated once
+    assert_eq!(
+        check
+            .critical_errors
+            .iter()
+            .filter(|e| e.starts_with("Custom file"))
+            .count(),
+        1
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_constraints_round_trip_and_check() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_constraints_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+    let saved_path = dir.join("saved.yaml");
+
+    let manifest = fs::read_to_string("../../qleany.yaml")?
+        .replacen(
+            "- name: Entity\n  inherits_from: EntityBase\n  fields:\n  - name: name\n    type: string\n",
+            "- name: Entity\n  inherits_from: EntityBase\n  fields:\n  - name: name\n    type: string\n    constraints:\n      not_empty: true\n      max_length: 64\n      pattern: \"^[A-Z]\"\n      unique_in_parent: true\n",
+            1,
+        )
+        .replacen(
+            "  - name: only_for_heritage\n    type: boolean\n",
+            "  - name: only_for_heritage\n    type: boolean\n    constraints:\n      min: 1\n",
+            1,
+        )
+        .replacen(
+            "      - name: manifest_path\n        type: string\n",
+            "      - name: manifest_path\n        type: string\n        constraints:\n          pattern: \"(\"\n",
+            1,
+        );
+    fs::write(&manifest_path, manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    let save_dto = SaveDto {
+        manifest_path: saved_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let saved = fs::read_to_string(&saved_path)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert!(saved.contains("unique_in_parent: true"));
+    assert!(saved.contains("max_length: 64"));
+    assert!(saved.contains("min: 1"));
+    // a minimum on a boolean, an invalid regex on a DTO field
+    let errors: Vec<&String> = check
+        .critical_errors
+        .iter()
+        .filter(|e| e.contains("only_for_heritage") || e.contains("manifest_path"))
+        .collect();
+    assert_eq!(errors.len(), 2, "{:?}", check.critical_errors);
+    assert!(errors.iter().any(|e| e.contains("'min' only applies")));
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.contains("not a valid regular expression"))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_indexes_round_trip_and_check() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_indexes_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+    let saved_path = dir.join("saved.yaml");
+
+    let manifest = fs::read_to_string("../../qleany.yaml")?
+        .replacen(
+            "  - name: name\n    type: string\n    indexed: true\n",
+            "  - name: name\n    type: string\n    unique: true\n",
+            1,
+        )
+        .replacen(
+            "  - name: enum_values\n    type: string\n    is_list: true\n",
+            "  - name: enum_values\n    type: string\n    is_list: true\n    indexed: true\n",
+            1,
+        );
+    fs::write(&manifest_path, manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    let save_dto = SaveDto {
+        manifest_path: saved_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let saved = fs::read_to_string(&saved_path)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert!(saved.contains("  - name: name\n    type: string\n    unique: true\n"));
+    assert!(saved.contains("  - name: relative_path\n    type: string\n    indexed: true\n"));
+    // an index on a list field
+    let diagnostics: Vec<_> = check
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "C51")
+        .collect();
+    assert_eq!(diagnostics.len(), 1, "{:?}", check.diagnostics);
+    assert_eq!(
+        diagnostics[0].location,
+        "/entities/Field/fields/enum_values/indexed"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rich_field_types_round_trip_and_check() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_rich_types_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+    let saved_path = dir.join("saved.yaml");
+
+    let manifest = fs::read_to_string("../../examples/rust/full/qleany.yaml")?
+        .replacen(
+            "      - name: attachment\n        type: bytes\n",
+            "      - name: attachment\n        type: bytes\n        is_list: true\n",
+            1,
+        )
+        .replacen(
+            "      - name: properties\n        type: json\n",
+            "      - name: properties\n        type: json\n        optional: true\n        indexed: true\n",
+            1,
+        );
+    fs::write(&manifest_path, manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    let save_dto = SaveDto {
+        manifest_path: saved_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let saved = fs::read_to_string(&saved_path)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    for field_type in ["decimal", "date", "time", "duration", "bytes", "json"] {
+        assert!(
+            saved.contains(&format!("    type: {field_type}\n")),
+            "{field_type}"
+        );
+    }
+    let mut locations: Vec<_> = check
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "C53")
+        .map(|d| d.location.as_str())
+        .collect();
+    locations.sort();
+    assert_eq!(
+        locations,
+        [
+            "/entities/Task/fields/attachment/is_list",
+            "/entities/Task/fields/properties/optional",
+        ],
+        "{:?}",
+        check.diagnostics
+    );
+    // json fields cannot be indexed either
+    assert!(
+        check
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "C51" && d.location == "/entities/Task/fields/properties/indexed")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_defaults_round_trip_and_check() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_defaults_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+    let saved_path = dir.join("saved.yaml");
+
+    let manifest = fs::read_to_string("../../examples/rust/full/qleany.yaml")?
+        .replacen(
+            "        type: float\n        default: 1.0\n",
+            "        type: float\n        default: heavy\n",
+            1,
+        )
+        .replacen(
+            "        type: time\n        is_list: true\n",
+            "        type: time\n        is_list: true\n        default: \"09:00\"\n",
+            1,
+        )
+        // a data-carrying first variant is fine once the default names a simple one
+        .replacen(
+            "          - None\n          - \"Description(String)\"\n",
+            "          - \"Description(String)\"\n          - None\n",
+            1,
+        )
+        .replacen(
+            "          - \"OptionalNote(Option<String>)\"\n",
+            "          - \"OptionalNote(Option<String>)\"\n        default: None\n",
+            1,
+        );
+    fs::write(&manifest_path, manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    let save_dto = SaveDto {
+        manifest_path: saved_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let saved = fs::read_to_string(&saved_path)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    // the layout of the manifest is kept
+    assert!(saved.contains("        type: uinteger\n        default: 1\n"));
+    assert!(saved.contains("        type: datetime\n        default: now\n"));
+    assert!(saved.contains("        type: uuid\n        default: new_uuid\n"));
+    assert!(saved.contains("    default: Medium\n"));
+    assert!(saved.contains("      default: Task\n"));
+    let mut locations: Vec<_> = check
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "C54")
+        .map(|d| d.location.as_str())
+        .collect();
+    locations.sort();
+    assert_eq!(
+        locations,
+        [
+            "/entities/Task/fields/reminder_times/default",
+            "/entities/Task/fields/weight/default",
+        ],
+        "{:?}",
+        check.diagnostics
+    );
+    assert!(
+        !check.diagnostics.iter().any(|d| d.rule == "C47"),
+        "{:?}",
+        check.diagnostics
+    );
+    Ok(())
+}
+
+#[test]
+fn test_undo_journal_round_trip_and_check() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_undo_journal_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+    let saved_path = dir.join("saved.yaml");
+
+    let manifest = fs::read_to_string("../../qleany.yaml")?
+        // Root is not undoable
+        .replacen(
+            "  undoable: false\n- name: Workspace\n",
+            "  undoable: false\n  undo_journal: true\n- name: Workspace\n",
+            1,
+        )
+        .replacen(
+            "    is_list: true\n  undoable: true\n- name: System\n",
+            "    is_list: true\n  undoable: true\n  undo_journal: true\n- name: System\n",
+            1,
+        );
+    fs::write(&manifest_path, manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    let save_dto = SaveDto {
+        manifest_path: saved_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let saved = fs::read_to_string(&saved_path)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert!(saved.contains("  undoable: true\n  undo_journal: true\n"));
+    assert_eq!(saved.matches("undo_journal: true").count(), 2);
+    let diagnostics: Vec<_> = check
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "W05")
+        .collect();
+    assert_eq!(diagnostics.len(), 1, "{:?}", check.diagnostics);
+    assert_eq!(diagnostics[0].location, "/entities/Root/undo_journal");
+    Ok(())
+}
+
+#[test]
+fn test_check_diagnostics_point_to_the_manifest() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_diagnostics_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+
+    let manifest = fs::read_to_string("../../qleany.yaml")?.replacen(
+        "- name: Entity\n  inherits_from: EntityBase\n  fields:\n",
+        "- name: Entity\n  inherits_from: EntityBase\n  fields:\n  - name: BadName\n    type: string\n",
+        1,
+    );
+    let expected_line = manifest
+        .lines()
+        .position(|line| line == "  - name: BadName")
+        .expect("field inserted")
+        + 1;
+    fs::write(&manifest_path, manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert_eq!(check.diagnostics.len(), 1, "{:?}", check.diagnostics);
+    let diagnostic = &check.diagnostics[0];
+    assert_eq!(diagnostic.rule, "C38");
+    assert_eq!(diagnostic.severity, "critical");
+    assert_eq!(diagnostic.location, "/entities/Entity/fields/BadName");
+    assert_eq!(diagnostic.line, Some(expected_line as u64));
+    assert_eq!(diagnostic.column, Some(5));
+    // the prose lists are still filled
+    assert_eq!(check.critical_errors, vec![diagnostic.message.clone()]);
+    Ok(())
+}
+
+#[test]
+fn test_check_without_manifest_has_its_own_rule() -> Result<()> {
+    // Arrange: nothing loaded
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+
+    // Act
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+
+    // Assert
+    assert_eq!(check.diagnostics.len(), 1, "{:?}", check.diagnostics);
+    assert_eq!(check.diagnostics[0].rule, "C56");
+    let rules = handling_manifest::handling_manifest_controller::get_check_rules();
+    assert!(rules.iter().any(|rule| rule.id == "C56"));
+    Ok(())
+}
+
+#[test]
+fn test_check_positions_stay_with_their_workspace() -> Result<()> {
+    // Arrange: two manifests in the same directory, with different layouts
+    let dir = std::env::temp_dir().join(format!("qleany_two_manifests_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest = fs::read_to_string("../../qleany.yaml")?.replacen(
+        "- name: Entity\n  inherits_from: EntityBase\n  fields:\n",
+        "- name: Entity\n  inherits_from: EntityBase\n  fields:\n  - name: BadName\n    type: string\n",
+        1,
+    );
+    let expected_line = manifest
+        .lines()
+        .position(|line| line == "  - name: BadName")
+        .expect("field inserted")
+        + 1;
+    fs::write(dir.join("qleany.yaml"), &manifest)?;
+    fs::write(dir.join("other.yaml"), format!("# other\n\n{}", manifest))?;
+
+    let load = |file: &str| -> Result<(DbContext, Arc<EventHub>)> {
+        let db_context = DbContext::new()?;
+        let event_hub = Arc::new(EventHub::new());
+        initialize_app(&db_context, &event_hub)?;
+        let load_dto = LoadDto {
+            manifest_path: dir.join(file).to_string_lossy().to_string(),
+        };
+        handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+        Ok((db_context, event_hub))
+    };
+
+    // Act
+    let (db_context, event_hub) = load("qleany.yaml")?;
+    let (other_db_context, other_event_hub) = load("other.yaml")?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    let other_check = handling_manifest::handling_manifest_controller::check(
+        &other_db_context,
+        &other_event_hub,
+    )?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert_eq!(check.diagnostics[0].line, Some(expected_line as u64));
+    assert_eq!(
+        other_check.diagnostics[0].line,
+        Some(expected_line as u64 + 2)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_load_failure_report_points_to_the_parse_error() -> Result<()> {
+    // Arrange: an included file with a broken mapping on its third line
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_load_failure_{}", std::process::id()));
+    fs::create_dir_all(dir.join("parts"))?;
+    let manifest_path = dir.join("qleany.yaml");
+
+    let manifest = format!(
+        "{}\nincludes:\n  - \"parts/*.yaml\"\n",
+        fs::read_to_string("../../qleany.yaml")?
+    );
+    fs::write(&manifest_path, manifest)?;
+    fs::write(
+        dir.join("parts/broken.yaml"),
+        "entities:\n- name: Broken\n  fields: [\n",
+    )?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    let error =
+        handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)
+            .unwrap_err();
+    let report = handling_manifest::handling_manifest_controller::get_load_failure_report(&error);
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert_eq!(report.diagnostics.len(), 1, "{:?}", report.diagnostics);
+    let diagnostic = &report.diagnostics[0];
+    assert_eq!(diagnostic.rule, "C55");
+    assert_eq!(diagnostic.severity, "critical");
+    assert_eq!(diagnostic.file.as_deref(), Some("parts/broken.yaml"));
+    assert!(
+        diagnostic.line.is_some_and(|line| line >= 3),
+        "{:?}",
+        diagnostic
+    );
+    assert!(
+        diagnostic
+            .message
+            .starts_with("Included file parts/broken.yaml: ")
+    );
+    assert_eq!(report.critical_errors, vec![diagnostic.message.clone()]);
+    Ok(())
+}
+
+#[test]
+fn test_includes_round_trip_and_duplicates() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_includes_{}", std::process::id()));
+    fs::create_dir_all(dir.join("parts"))?;
+    let manifest_path = dir.join("qleany.yaml");
+
+    let manifest = format!(
+        "{}\nincludes:\n  - \"parts/*.yaml\"\n",
+        fs::read_to_string("../../qleany.yaml")?
+    );
+    fs::write(&manifest_path, manifest)?;
+    fs::write(
+        dir.join("parts/extra.yaml"),
+        "entities:\n- name: Extra\n  inherits_from: EntityBase\n  fields:\n  - name: label\n    type: string\n- name: Entity\n  inherits_from: EntityBase\n  fields: []\n",
+    )?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    let save_dto = SaveDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let saved_root = fs::read_to_string(&manifest_path)?;
+    let saved_part = fs::read_to_string(dir.join("parts/extra.yaml"))?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert!(saved_root.contains("parts/*.yaml"));
+    assert!(!saved_root.contains("name: Extra"));
+    assert!(saved_part.contains("name: Extra"));
+    assert!(saved_part.contains("name: label"));
+    let duplicates: Vec<_> = check
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "C04")
+        .collect();
+    assert_eq!(duplicates.len(), 1, "{:?}", check.diagnostics);
+    assert_eq!(duplicates[0].file.as_deref(), Some("parts/extra.yaml"));
+    assert!(
+        duplicates[0]
+            .message
+            .contains("defined in the manifest and in parts/extra.yaml"),
+        "{}",
+        duplicates[0].message
+    );
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_entity_reports_only_the_duplicate() -> Result<()> {
+    // Arrange
+    let dir = std::env::temp_dir().join(format!("qleany_duplicate_{}", std::process::id()));
+    fs::create_dir_all(dir.join("parts"))?;
+    let plain_path = dir.join("plain.yaml");
+    let manifest_path = dir.join("qleany.yaml");
+    let root = fs::read_to_string("../../qleany.yaml")?;
+    fs::write(&plain_path, &root)?;
+    fs::write(
+        &manifest_path,
+        format!("{}\nincludes:\n  - \"parts/*.yaml\"\n", root),
+    )?;
+    // same name as an entity of the manifest, both with fields
+    fs::write(
+        dir.join("parts/extra.yaml"),
+        "entities:\n- name: Entity\n  inherits_from: EntityBase\n  fields:\n  - name: label\n    type: string\n",
+    )?;
+
+    // Act
+    let mut rules = vec![];
+    for path in [&plain_path, &manifest_path] {
+        let db_context = DbContext::new()?;
+        let event_hub = Arc::new(EventHub::new());
+        initialize_app(&db_context, &event_hub)?;
+        let load_dto = LoadDto {
+            manifest_path: path.to_string_lossy().to_string(),
+        };
+        handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+        let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+        rules.push(
+            check
+                .diagnostics
+                .into_iter()
+                .map(|d| d.rule)
+                .collect::<Vec<_>>(),
+        );
+    }
+    fs::remove_dir_all(&dir)?;
+
+    // Assert: the include adds the duplicate, and nothing else
+    let (plain, included) = (&rules[0], &rules[1]);
+    let mut added = included.clone();
+    for rule in plain {
+        if let Some(index) = added.iter().position(|r| r == rule) {
+            added.remove(index);
+        }
+    }
+    assert_eq!(added, vec!["C04".to_string()], "{:?}", included);
+    Ok(())
+}
+
+#[test]
+fn test_shared_dtos_round_trip_and_cycles() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_shared_dtos_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+    let saved_path = dir.join("saved.yaml");
+
+    // TaskRefDto now holds a TaskSelectionDto, which holds TaskRefDto
+    let manifest = fs::read_to_string("../../examples/rust/full/qleany.yaml")?.replacen(
+        "            constraints:\n              not_empty: true\n",
+        "            constraints:\n              not_empty: true\n          - name: selection\n            type: dto\n            dto: TaskSelectionDto\n            optional: true\n",
+        1,
+    );
+    fs::write(&manifest_path, manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    let save_dto = SaveDto {
+        manifest_path: saved_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let saved = fs::read_to_string(&saved_path)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    // the layout of the manifest is kept
+    assert!(saved.contains("    dtos:\n      - name: TaskRefDto\n"));
+    assert!(saved.contains(
+        "            type: dto\n            dto: TaskRefDto\n            is_list: true\n"
+    ));
+    assert!(
+        saved.contains("        dto_in: TaskSelectionDto\n        dto_out: TaskSelectionDto\n")
+    );
+    // inline DTOs stay inline
+    assert!(saved.contains("        dto_in:\n          name: BatchAssignTasksDto\n"));
+    let diagnostics: Vec<_> = check
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "C52")
+        .collect();
+    assert_eq!(diagnostics.len(), 2, "{:?}", check.diagnostics);
+    assert_eq!(
+        diagnostics[0].location,
+        "/features/task_management/dtos/TaskRefDto"
+    );
+    assert!(
+        diagnostics[0]
+            .message
+            .contains("TaskRefDto -> TaskSelectionDto -> TaskRefDto")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_save_keeps_comments_and_layout() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let dir = std::env::temp_dir().join(format!("qleany_layout_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+
+    let manifest = fs::read_to_string("../../qleany.yaml")?
+        .replacen(
+            "entities:\n",
+            "\n# Entities of the model, base first\nentities:\n",
+            1,
+        )
+        .replacen("- name: Root\n", "- name: Root # the singleton\n", 1);
+    fs::write(&manifest_path, &manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let save_dto = SaveDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let unchanged = fs::read_to_string(&manifest_path)?;
+
+    // edit the Root entity, as the GUI would
+    let mut transaction = Transaction::begin_write_transaction(&db_context)?;
+    {
+        let mut repository = repository_factory::write::create_entity_repository(&transaction)?;
+        let mut root = repository.get_by_name("Root")?.remove(0);
+        root.undoable = true;
+        root.single_model = true;
+        repository.update(&mut EventBuffer::new(), &root)?;
+    }
+    transaction.commit()?;
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let edited = fs::read_to_string(&manifest_path)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert_eq!(unchanged, manifest);
+    assert_eq!(
+        edited,
+        manifest.replacen(
+            "  undoable: false\n- name: Workspace\n",
+            "  undoable: true\n  single_model: true\n- name: Workspace\n",
+            1,
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_save_keeps_the_layout_of_its_own_manifest() -> Result<()> {
+    // Arrange: two manifests in the same directory, with different comments
+    let dir = std::env::temp_dir().join(format!("qleany_two_layouts_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let base = fs::read_to_string("../../qleany.yaml")?;
+    let manifest = base.replacen("entities:\n", "# first\nentities:\n", 1);
+    let other = base.replacen("entities:\n", "# second\nentities:\n", 1);
+    fs::write(dir.join("qleany.yaml"), &manifest)?;
+    fs::write(dir.join("other.yaml"), &other)?;
+
+    let load = |file: &str| -> Result<(DbContext, Arc<EventHub>)> {
+        let db_context = DbContext::new()?;
+        let event_hub = Arc::new(EventHub::new());
+        initialize_app(&db_context, &event_hub)?;
+        let load_dto = LoadDto {
+            manifest_path: dir.join(file).to_string_lossy().to_string(),
+        };
+        handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+        Ok((db_context, event_hub))
+    };
+
+    // Act: the second load must not replace the text the first one saves from
+    let (db_context, event_hub) = load("qleany.yaml")?;
+    let (other_db_context, other_event_hub) = load("other.yaml")?;
+    for (file, db_context, event_hub) in [
+        ("qleany.yaml", &db_context, &event_hub),
+        ("other.yaml", &other_db_context, &other_event_hub),
+    ] {
+        let save_dto = SaveDto {
+            manifest_path: dir.join(file).to_string_lossy().to_string(),
+        };
+        handling_manifest::handling_manifest_controller::save(db_context, event_hub, &save_dto)?;
+    }
+    let saved = fs::read_to_string(dir.join("qleany.yaml"))?;
+    let other_saved = fs::read_to_string(dir.join("other.yaml"))?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert_eq!(saved, manifest);
+    assert_eq!(other_saved, other);
+    Ok(())
+}
+
+#[test]
+fn test_refactor_updates_references_and_undoes() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    let mut undo_redo_manager = UndoRedoManager::new();
+    let dir = std::env::temp_dir().join(format!("qleany_refactor_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let manifest_path = dir.join("qleany.yaml");
+    let manifest = fs::read_to_string("../../examples/rust/full/qleany.yaml")?;
+    fs::write(&manifest_path, &manifest)?;
+
+    initialize_app(&db_context, &event_hub)?;
+    let load_dto = LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest::handling_manifest_controller::load(&db_context, &event_hub, &load_dto)?;
+    let save_dto = SaveDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    let refactor = |dto: RefactorDto, undo_redo_manager: &mut UndoRedoManager| {
+        handling_manifest::handling_manifest_controller::refactor(
+            &db_context,
+            &event_hub,
+            undo_redo_manager,
+            None,
+            &dto,
+        )
+    };
+
+    // Act
+    refactor(
+        RefactorDto {
+            operation: RefactorOperation::RenameEntity,
+            name: "Project".to_string(),
+            new_name: "Initiative".to_string(),
+            ..Default::default()
+        },
+        &mut undo_redo_manager,
+    )?;
+    refactor(
+        RefactorDto {
+            operation: RefactorOperation::RenameField,
+            owner_name: "Initiative".to_string(),
+            name: "title".to_string(),
+            new_name: "name".to_string(),
+            ..Default::default()
+        },
+        &mut undo_redo_manager,
+    )?;
+    let conflict = refactor(
+        RefactorDto {
+            operation: RefactorOperation::RenameEntity,
+            name: "Task".to_string(),
+            new_name: "Comment".to_string(),
+            ..Default::default()
+        },
+        &mut undo_redo_manager,
+    );
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let renamed = fs::read_to_string(&manifest_path)?;
+
+    undo_redo_manager.undo(None)?;
+    undo_redo_manager.undo(None)?;
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let undone = fs::read_to_string(&manifest_path)?;
+
+    refactor(
+        RefactorDto {
+            operation: RefactorOperation::ExtractBaseEntity,
+            name: "Task".to_string(),
+            new_name: "Trackable".to_string(),
+            field_names: vec!["title".to_string(), "is_done".to_string()],
+            ..Default::default()
+        },
+        &mut undo_redo_manager,
+    )?;
+    let check = handling_manifest::handling_manifest_controller::check(&db_context, &event_hub)?;
+    handling_manifest::handling_manifest_controller::save(&db_context, &event_hub, &save_dto)?;
+    let extracted = fs::read_to_string(&manifest_path)?;
+    fs::remove_dir_all(&dir)?;
+
+    // Assert
+    assert!(conflict.is_err());
+    assert!(renamed.contains("  - name: Initiative\n    inherits_from: EntityBase\n"));
+    assert!(renamed.contains("        entity: Initiative\n"));
+    assert!(renamed.contains("entities: [Workspace, Initiative, ProjectSettings]"));
+    // the list model of Workspace displays the renamed field, the one of Initiative
+    // displays a field of Task
+    assert!(renamed.contains("        entity: Initiative\n        relationship: ordered_one_to_many\n        strong: true\n        list_model: true\n        list_model_displayed_field: name\n"));
+    assert!(renamed.contains("        entity: Task\n        relationship: ordered_one_to_many\n        strong: true\n        list_model: true\n        list_model_displayed_field: title\n"));
+    assert_eq!(undone, manifest);
+    assert!(
+        check.critical_errors.is_empty(),
+        "{:?}",
+        check.critical_errors
+    );
+    assert!(extracted.contains("- name: Trackable"));
+    assert!(extracted.contains("  - name: Task\n    inherits_from: Trackable\n"));
+    Ok(())
+}
+
+#[test]
+fn test_upgrade_missing_file_leaves_the_database_usable() -> Result<()> {
+    // Arrange
+    let db_context = DbContext::new()?;
+    let event_hub = Arc::new(EventHub::new());
+    initialize_app(&db_context, &event_hub)?;
+
+    // Act
+    let err = handling_manifest::handling_manifest_controller::upgrade(
+        &db_context,
+        &UpgradeDto {
+            manifest_path: "../../does_not_exist.yaml".to_string(),
+            dry_run: true,
+        },
+    )
+    .unwrap_err();
+
+    // Assert
+    assert_eq!(err.to_string(), "File does not exist");
+    let upgraded = handling_manifest::handling_manifest_controller::upgrade(
+        &db_context,
+        &UpgradeDto {
+            manifest_path: "../../qleany.yaml".to_string(),
+            dry_run: true,
+        },
+    )?;
+    assert_eq!(upgraded.from_version, upgraded.to_version);
+    Ok(())
+}
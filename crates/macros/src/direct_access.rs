@@ -735,6 +735,210 @@ pub fn uow_action_impl(args: TokenStream, input: TokenStream) -> TokenStream {
         }
     }
 
+    fn query_action(
+        entity_ident: &syn::Ident,
+        entity_snake_ident: &syn::Ident,
+        function_ident: &syn::Ident,
+        item_type: &ItemType,
+        thread_safe: bool,
+    ) -> proc_macro2::TokenStream {
+        let create_entity_repo = format_ident!("create_{}_repository", entity_snake_ident);
+        let query_ident = format_ident!("{}Query", entity_ident);
+        let query_path = quote! { common::direct_access::#entity_snake_ident::#query_ident };
+
+        match item_type {
+            ItemType::Trait(_) => {
+                quote! {
+                    fn #function_ident(&self, query: &#query_path) -> Result<Vec<#entity_ident>>;
+                }
+            }
+            ItemType::Impl(_) => {
+                if thread_safe {
+                    quote! {
+                        fn #function_ident(&self, query: &#query_path) -> Result<Vec<#entity_ident>> {
+                            use common::entities::#entity_ident;
+                            use common::direct_access::repository_factory;
+
+                            let borrowed_transaction = self.transaction.lock().unwrap();
+                            let repo = repository_factory::write::#create_entity_repo(
+                                &borrowed_transaction.as_ref().expect("Transaction not started"),
+                            )?;
+                            let value = repo.query(query)?;
+                            Ok(value)
+                        }
+                    }
+                } else {
+                    quote! {
+                        fn #function_ident(&self, query: &#query_path) -> Result<Vec<#entity_ident>> {
+                            use common::entities::#entity_ident;
+                            use common::direct_access::repository_factory;
+                            use std::borrow::Borrow;
+
+                            let borrowed_transaction = self.transaction.borrow();
+                            let repo = repository_factory::write::#create_entity_repo(
+                                &borrowed_transaction.as_ref().expect("Transaction not started"),
+                            )?;
+                            let value = repo.query(query)?;
+                            Ok(value)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn count_action(
+        entity_ident: &syn::Ident,
+        entity_snake_ident: &syn::Ident,
+        function_ident: &syn::Ident,
+        item_type: &ItemType,
+        thread_safe: bool,
+    ) -> proc_macro2::TokenStream {
+        let create_entity_repo = format_ident!("create_{}_repository", entity_snake_ident);
+        let query_ident = format_ident!("{}Query", entity_ident);
+        let query_path = quote! { common::direct_access::#entity_snake_ident::#query_ident };
+
+        match item_type {
+            ItemType::Trait(_) => {
+                quote! {
+                    fn #function_ident(&self, query: &#query_path) -> Result<usize>;
+                }
+            }
+            ItemType::Impl(_) => {
+                if thread_safe {
+                    quote! {
+                        fn #function_ident(&self, query: &#query_path) -> Result<usize> {
+                            use common::direct_access::repository_factory;
+
+                            let borrowed_transaction = self.transaction.lock().unwrap();
+                            let repo = repository_factory::write::#create_entity_repo(
+                                &borrowed_transaction.as_ref().expect("Transaction not started"),
+                            )?;
+                            let value = repo.count(query)?;
+                            Ok(value)
+                        }
+                    }
+                } else {
+                    quote! {
+                        fn #function_ident(&self, query: &#query_path) -> Result<usize> {
+                            use common::direct_access::repository_factory;
+                            use std::borrow::Borrow;
+
+                            let borrowed_transaction = self.transaction.borrow();
+                            let repo = repository_factory::write::#create_entity_repo(
+                                &borrowed_transaction.as_ref().expect("Transaction not started"),
+                            )?;
+                            let value = repo.count(query)?;
+                            Ok(value)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn query_ro_action(
+        entity_ident: &syn::Ident,
+        entity_snake_ident: &syn::Ident,
+        function_ident: &syn::Ident,
+        item_type: &ItemType,
+        thread_safe: bool,
+    ) -> proc_macro2::TokenStream {
+        let create_entity_repo = format_ident!("create_{}_repository", entity_snake_ident);
+        let query_ident = format_ident!("{}Query", entity_ident);
+        let query_path = quote! { common::direct_access::#entity_snake_ident::#query_ident };
+
+        match item_type {
+            ItemType::Trait(_) => {
+                quote! {
+                    fn #function_ident(&self, query: &#query_path) -> Result<Vec<#entity_ident>>;
+                }
+            }
+            ItemType::Impl(_) => {
+                if thread_safe {
+                    quote! {
+                        fn #function_ident(&self, query: &#query_path) -> Result<Vec<#entity_ident>> {
+                            use common::entities::#entity_ident;
+                            use common::direct_access::repository_factory;
+
+                            let borrowed_transaction = self.transaction.lock().unwrap();
+                            let repo = repository_factory::read::#create_entity_repo(
+                                &borrowed_transaction.as_ref().expect("Transaction not started"),
+                            )?;
+                            let value = repo.query(query)?;
+                            Ok(value)
+                        }
+                    }
+                } else {
+                    quote! {
+                        fn #function_ident(&self, query: &#query_path) -> Result<Vec<#entity_ident>> {
+                            use common::entities::#entity_ident;
+                            use common::direct_access::repository_factory;
+                            use std::borrow::Borrow;
+
+                            let borrowed_transaction = self.transaction.borrow();
+                            let repo = repository_factory::read::#create_entity_repo(
+                                &borrowed_transaction.as_ref().expect("Transaction not started"),
+                            )?;
+                            let value = repo.query(query)?;
+                            Ok(value)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn count_ro_action(
+        entity_ident: &syn::Ident,
+        entity_snake_ident: &syn::Ident,
+        function_ident: &syn::Ident,
+        item_type: &ItemType,
+        thread_safe: bool,
+    ) -> proc_macro2::TokenStream {
+        let create_entity_repo = format_ident!("create_{}_repository", entity_snake_ident);
+        let query_ident = format_ident!("{}Query", entity_ident);
+        let query_path = quote! { common::direct_access::#entity_snake_ident::#query_ident };
+
+        match item_type {
+            ItemType::Trait(_) => {
+                quote! {
+                    fn #function_ident(&self, query: &#query_path) -> Result<usize>;
+                }
+            }
+            ItemType::Impl(_) => {
+                if thread_safe {
+                    quote! {
+                        fn #function_ident(&self, query: &#query_path) -> Result<usize> {
+                            use common::direct_access::repository_factory;
+
+                            let borrowed_transaction = self.transaction.lock().unwrap();
+                            let repo = repository_factory::read::#create_entity_repo(
+                                &borrowed_transaction.as_ref().expect("Transaction not started"),
+                            )?;
+                            let value = repo.count(query)?;
+                            Ok(value)
+                        }
+                    }
+                } else {
+                    quote! {
+                        fn #function_ident(&self, query: &#query_path) -> Result<usize> {
+                            use common::direct_access::repository_factory;
+                            use std::borrow::Borrow;
+
+                            let borrowed_transaction = self.transaction.borrow();
+                            let repo = repository_factory::read::#create_entity_repo(
+                                &borrowed_transaction.as_ref().expect("Transaction not started"),
+                            )?;
+                            let value = repo.count(query)?;
+                            Ok(value)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     fn update_multi_action(
         entity_ident: &syn::Ident,
         entity_snake_ident: &syn::Ident,
@@ -1654,6 +1858,10 @@ pub fn uow_action_impl(args: TokenStream, input: TokenStream) -> TokenStream {
         "GetRO" => format!("get_{}", entity_name_snake_case),
         "GetMultiRO" => format!("get_{}_multi", entity_name_snake_case),
         "GetAllRO" => format!("get_all_{}", entity_name_snake_case),
+        "Query" => format!("query_{}", entity_name_snake_case),
+        "QueryRO" => format!("query_{}", entity_name_snake_case),
+        "Count" => format!("count_{}", entity_name_snake_case),
+        "CountRO" => format!("count_{}", entity_name_snake_case),
         "GetRelationship" => format!("get_{}_relationship", entity_name_snake_case),
         "GetRelationshipRO" => format!("get_{}_relationship", entity_name_snake_case),
         "GetRelationshipsFromRightIds" => {
@@ -1800,6 +2008,34 @@ pub fn uow_action_impl(args: TokenStream, input: TokenStream) -> TokenStream {
             &item_type,
             thread_safe,
         ),
+        "Query" => query_action(
+            &entity_ident,
+            &entity_snake_ident,
+            &function_ident,
+            &item_type,
+            thread_safe,
+        ),
+        "QueryRO" => query_ro_action(
+            &entity_ident,
+            &entity_snake_ident,
+            &function_ident,
+            &item_type,
+            thread_safe,
+        ),
+        "Count" => count_action(
+            &entity_ident,
+            &entity_snake_ident,
+            &function_ident,
+            &item_type,
+            thread_safe,
+        ),
+        "CountRO" => count_ro_action(
+            &entity_ident,
+            &entity_snake_ident,
+            &function_ident,
+            &item_type,
+            thread_safe,
+        ),
         "GetRelationship" => get_relationship_action(
             &entity_ident,
             &entity_snake_ident,
@@ -1,6 +1,7 @@
 // Generated by Qleany v0.0.1 from macros_lib.tera
 
 mod direct_access;
+mod uow_registry;
 
 use proc_macro::TokenStream;
 
@@ -11,3 +12,20 @@ use proc_macro::TokenStream;
 pub fn uow_action(args: TokenStream, input: TokenStream) -> TokenStream {
     direct_access::uow_action_impl(args, input)
 }
+
+/// This macro records the `#[uow_action]` attributes of a unit of work trait, so that
+/// `#[uow_impl]` can generate the same actions on the implementation.
+/// Place it above the `#[uow_action]` attributes.
+/// ex: #[macros::uow_trait]
+#[proc_macro_attribute]
+pub fn uow_trait(args: TokenStream, input: TokenStream) -> TokenStream {
+    uow_registry::uow_trait_impl(args, input)
+}
+
+/// This macro generates the unit of work actions declared with `#[uow_trait]` on the trait
+/// of an impl block. `for` defaults to the implemented trait.
+/// ex: #[macros::uow_impl(for = FooUnitOfWorkTrait, thread_safe = true)]
+#[proc_macro_attribute]
+pub fn uow_impl(args: TokenStream, input: TokenStream) -> TokenStream {
+    uow_registry::uow_impl_impl(args, input)
+}
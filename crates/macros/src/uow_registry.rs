@@ -0,0 +1,175 @@
+// Generated by Qleany v1.7.0 from macros_uow_registry.tera
+
+// Single-source unit of work actions.
+//
+// `#[uow_trait]` reads the `#[uow_action]` attributes of a unit of work trait and records
+// them in a hidden `macro_rules!` named after the trait (macros and types live in different
+// namespaces, so importing the trait also imports its registry). `#[uow_impl]` expands the
+// impl block through that registry, so the action list is written only once, on the trait.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Error, Ident, ItemImpl, ItemTrait, LitBool, Meta, Path, Token};
+
+fn is_uow_action(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "uow_action")
+}
+
+/// Arguments of a `#[uow_action]`, without `thread_safe` which belongs to the impl side.
+fn action_args(attr: &Attribute) -> syn::Result<Vec<Meta>> {
+    let args = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+    Ok(args
+        .into_iter()
+        .filter(|meta| !meta.path().is_ident("thread_safe"))
+        .collect())
+}
+
+pub fn uow_trait_impl(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return Error::new(Span::call_site(), "uow_trait takes no argument")
+            .to_compile_error()
+            .into();
+    }
+    let item_trait = match syn::parse::<ItemTrait>(input) {
+        Ok(item_trait) => item_trait,
+        Err(_) => {
+            return Error::new(
+                Span::call_site(),
+                "uow_trait must be placed on a unit of work trait",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let mut paths = Vec::new();
+    let mut actions = Vec::new();
+    for attr in item_trait.attrs.iter().filter(|attr| is_uow_action(attr)) {
+        match action_args(attr) {
+            Ok(args) => {
+                paths.push(attr.path().clone());
+                actions.push(args);
+            }
+            Err(err) => return err.to_compile_error().into(),
+        }
+    }
+    let trait_ident = &item_trait.ident;
+    let registry_mod = format_ident!(
+        "__{}_uow_actions",
+        heck::AsSnakeCase(trait_ident.to_string()).to_string()
+    );
+    let plain = paths.iter().zip(&actions).map(|(path, args)| {
+        quote! { #[#path(#(#args),*)] }
+    });
+    let thread_safe = paths.iter().zip(&actions).map(|(path, args)| {
+        quote! { #[#path(#(#args,)* thread_safe = true)] }
+    });
+
+    quote! {
+        #item_trait
+
+        #[doc(hidden)]
+        mod #registry_mod {
+            macro_rules! #trait_ident {
+                (thread_safe, $($item:tt)*) => {
+                    #(#thread_safe)*
+                    $($item)*
+                };
+                ($($item:tt)*) => {
+                    #(#plain)*
+                    $($item)*
+                };
+            }
+            pub(crate) use #trait_ident;
+        }
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use self::#registry_mod::#trait_ident;
+    }
+    .into()
+}
+
+/// `for = Trait` (defaults to the implemented trait) and `thread_safe` / `thread_safe = bool`.
+struct UowImplArgs {
+    trait_path: Option<Path>,
+    thread_safe: bool,
+}
+
+impl Parse for UowImplArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = UowImplArgs {
+            trait_path: None,
+            thread_safe: false,
+        };
+        while !input.is_empty() {
+            // `for` is a keyword, so a plain `Meta` can't carry it
+            let key = input.call(Ident::parse_any)?;
+            if key == "for" {
+                input.parse::<Token![=]>()?;
+                args.trait_path = Some(input.parse()?);
+            } else if key == "thread_safe" {
+                args.thread_safe = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    input.parse::<LitBool>()?.value()
+                } else {
+                    true
+                };
+            } else {
+                return Err(Error::new_spanned(
+                    key,
+                    "expected `for = Trait` or `thread_safe`",
+                ));
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+pub fn uow_impl_impl(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = match syn::parse::<UowImplArgs>(args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let item_impl = match syn::parse::<ItemImpl>(input) {
+        Ok(item_impl) => item_impl,
+        Err(_) => {
+            return Error::new(
+                Span::call_site(),
+                "uow_impl must be placed on an `impl Trait for UnitOfWork` block",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+    let trait_path = match args
+        .trait_path
+        .or_else(|| item_impl.trait_.as_ref().map(|(_, path, _)| path.clone()))
+    {
+        Some(path) => path,
+        None => {
+            return Error::new_spanned(
+                &item_impl.self_ty,
+                "uow_impl needs `for = Trait` on an inherent impl block",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    // the trait must be declared with #[uow_trait], which defines the registry macro
+    if args.thread_safe {
+        quote! { #trait_path! { thread_safe, #item_impl } }.into()
+    } else {
+        quote! { #trait_path! { #item_impl } }.into()
+    }
+}
@@ -44,3 +44,7 @@ pub struct FillRustFilesReturnDto {
     pub file_names: Vec<String>,
     pub file_ids: Vec<u64>,
 }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FillCodeInRustFilesReturnDto {
+    pub orphaned_user_regions: Vec<String>,
+}
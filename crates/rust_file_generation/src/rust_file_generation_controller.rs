@@ -1,6 +1,7 @@
 // Generated by Qleany. Edit at your own risk! Be careful when regenerating this file
 // as changes will be lost.
 
+use crate::FillCodeInRustFilesReturnDto;
 use crate::FillRustFilesReturnDto;
 use crate::GenerateRustCodeDto;
 use crate::GenerateRustCodeReturnDto;
@@ -119,7 +120,7 @@ pub fn get_fill_code_in_rust_files_progress(
 pub fn get_fill_code_in_rust_files_result(
     long_operation_manager: &LongOperationManager,
     operation_id: &str,
-) -> Result<Option<()>> {
+) -> Result<Option<FillCodeInRustFilesReturnDto>> {
     // Get the operation result as a JSON string
     let result_json = long_operation_manager.get_operation_result(operation_id);
 
@@ -127,7 +128,10 @@ pub fn get_fill_code_in_rust_files_result(
     if result_json.is_none() {
         return Ok(None);
     }
-    Ok(Some(()))
+    // Parse the JSON string into a FillCodeInRustFilesReturnDto
+    let result_dto: FillCodeInRustFilesReturnDto = serde_json::from_str(&result_json.unwrap())?;
+
+    Ok(Some(result_dto))
 }
 
 pub fn generate_rust_prompt(
@@ -44,49 +44,13 @@ impl QueryUnitOfWork for GenerateRustFilesUnitOfWork {
     }
 }
 
-#[macros::uow_action(entity = "Root", action = "GetRelationshipRO", thread_safe = true)]
-#[macros::uow_action(entity = "Root", action = "GetAllRO", thread_safe = true)]
-#[macros::uow_action(entity = "System", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Workspace", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Workspace", action = "GetRelationshipRO", thread_safe = true)]
-#[macros::uow_action(entity = "File", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Global", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "UserInterface", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Feature", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Feature", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "UseCase", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "UseCase", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "Dto", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "DtoField", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "Entity", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Entity", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "Field", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "Relationship", action = "GetMultiRO", thread_safe = true)]
+#[macros::uow_impl(thread_safe = true)]
 impl GenerationReadOps for GenerateRustFilesUnitOfWork {}
 
-#[macros::uow_action(entity = "Root", action = "GetRelationshipRO", thread_safe = true)]
-#[macros::uow_action(entity = "Root", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "Global", action = "GetMultiRO", thread_safe = true)]
+#[macros::uow_impl(thread_safe = true)]
 impl GenerateRustFilesUnitOfWorkTrait for GenerateRustFilesUnitOfWork {}
 
-#[macros::uow_action(entity = "Root", action = "GetRelationshipRO", thread_safe = true)]
-#[macros::uow_action(entity = "Root", action = "GetAllRO", thread_safe = true)]
-#[macros::uow_action(entity = "System", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Workspace", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Workspace", action = "GetRelationshipRO", thread_safe = true)]
-#[macros::uow_action(entity = "File", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Global", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "UserInterface", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Feature", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Feature", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "UseCase", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "UseCase", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "Dto", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "DtoField", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "Entity", action = "GetRO", thread_safe = true)]
-#[macros::uow_action(entity = "Entity", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "Field", action = "GetMultiRO", thread_safe = true)]
-#[macros::uow_action(entity = "Relationship", action = "GetMultiRO", thread_safe = true)]
+#[macros::uow_impl(for = GenerationReadOps, thread_safe = true)]
 impl GenerationOps for GenerateRustFilesUnitOfWork {}
 
 pub struct GenerateRustFilesUnitOfWorkFactory {
@@ -1,8 +1,9 @@
 mod direct_access_lib_tests;
 mod rust_code_generator_tests;
+mod storage_backend_tests;
 
 use crate::use_cases::common::tools;
-use anyhow::{Result, anyhow, bail};
+use anyhow::{Context as _, Result, anyhow, bail};
 use common::database::{CommandUnitOfWork, QueryUnitOfWork};
 use common::entities::{
     Dto, DtoField, DtoFieldType, Entity, Feature, Field, FieldRelationshipType, FieldType, File,
@@ -10,15 +11,23 @@ use common::entities::{
     Workspace,
 };
 use common::enum_variant_parser;
+use common::generator::field_constraints::FieldConstraints;
+use common::generator::field_defaults::FieldDefault;
+use common::generator::template_overrides;
 use common::types::EntityId;
 use include_dir::{Dir, include_dir};
 use indexmap::IndexMap;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::OnceLock;
 use tera::{Context, Tera};
 
 // Shared read-API for snapshot building across code and files generation
+// The read-only units of work implement GenerationOps with these actions too, through
+// #[macros::uow_impl(for = GenerationReadOps)]
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetRelationshipRO")]
 #[macros::uow_action(entity = "Root", action = "GetAllRO")]
 #[macros::uow_action(entity = "System", action = "GetRO")]
@@ -60,6 +69,7 @@ pub(crate) trait GenerationReadOps: QueryUnitOfWork + GenerationOps {}
 #[allow(dead_code)]
 pub(crate) trait GenerationWriteOps: CommandUnitOfWork + GenerationOps {}
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetRelationship")]
 #[macros::uow_action(entity = "Root", action = "GetAll")]
 #[macros::uow_action(entity = "System", action = "Get")]
@@ -124,6 +134,8 @@ struct OwnerChainEntry {
     pub snake_name: String,
     pub pascal_name: String,
     pub undoable: bool,
+    /// Whether some field of the ancestor has constraints rejecting default values
+    pub has_checks: bool,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -149,6 +161,8 @@ struct EntityVM {
 struct FeatureVM {
     pub inner: Feature,
     pub use_cases: IndexMap<EntityId, UseCaseVM>,
+    /// Every DTO of the feature once, shared or inline, nested ones before the DTOs holding them
+    pub dtos: Vec<DtoVM>,
     pub snake_name: String,
     pub pascal_name: String,
 }
@@ -157,6 +171,7 @@ struct FeatureVM {
 struct UseCaseVM {
     pub inner: UseCase,
     pub entities: IndexMap<EntityId, EntityVM>,
+    /// Inline or shared DTO, whichever the use case declares
     pub dto_in: Option<DtoVM>,
     pub dto_out: Option<DtoVM>,
     pub snake_name: String,
@@ -168,6 +183,10 @@ struct DtoVM {
     pub inner: Dto,
     pub fields: Vec<DtoFieldVM>,
     pub pascal_name: String,
+    /// Whether some field of the DTO, or of a DTO nested in it, has constraints
+    pub has_checks: bool,
+    /// Whether the DTO can derive `Eq`: no float field in it nor in its nested DTOs
+    pub derives_eq: bool,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -202,6 +221,8 @@ struct FieldVM {
     pub rust_enum_variants: Vec<String>,
     /// Structured parsed variants for templates that need more control (mobile bridge)
     pub parsed_variants: Vec<ParsedVariantVM>,
+    /// Variant marked `#[default]`: the field's default, or else the first variant
+    pub enum_default_variant: String,
     /// Whether any complex variant uses uuid::Uuid
     pub enum_needs_uuid: bool,
     /// Whether any complex variant uses chrono::DateTime
@@ -210,6 +231,18 @@ struct FieldVM {
     pub enum_needs_entity_id: bool,
     /// Whether any complex variant uses f32/f64
     pub enum_needs_float: bool,
+    /// Whether every enum variant is a plain name (the enum can then be ordered)
+    pub enum_is_simple: bool,
+    /// Scalar field usable in the generated query filters and sort keys
+    pub queryable: bool,
+    /// Whether the store maintains a secondary index on the field (`indexed` or `unique`)
+    pub has_index: bool,
+    /// Borrowed type taken by the `get_by_<field>` lookups, e.g. `&str` for a `String`
+    pub rust_ref_type: String,
+    /// Validation constraints, `None` when the field has none
+    pub constraints: Option<FieldConstraints>,
+    /// Expression of the declared default, `None` when the field has none
+    pub rust_default: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -223,6 +256,8 @@ struct DtoFieldVM {
     pub rust_enum_variants: Vec<String>,
     /// Structured parsed variants for templates that need more control
     pub parsed_variants: Vec<ParsedVariantVM>,
+    /// Variant marked `#[default]`: the field's default, or else the first variant
+    pub enum_default_variant: String,
     /// Whether any complex variant uses uuid::Uuid
     pub enum_needs_uuid: bool,
     /// Whether any complex variant uses chrono::DateTime
@@ -233,6 +268,14 @@ struct DtoFieldVM {
     pub enum_needs_float: bool,
     /// External enum types referenced in complex variants (e.g. TaskMetadata in ProjectSummary)
     pub external_enum_references: Vec<String>,
+    /// Name of the nested DTO (only populated when field_type == Dto)
+    pub dto_pascal_name: Option<String>,
+    /// Whether the nested DTO has constraints, to validate it with its holder
+    pub dto_has_checks: bool,
+    /// Validation constraints, `None` when the field has none
+    pub constraints: Option<FieldConstraints>,
+    /// Expression of the declared default, `None` when the field has none
+    pub rust_default: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -260,11 +303,24 @@ fn camel_case_filter(
     Ok(tera::Value::String(camel))
 }
 
+/// Tera filter that renders a string as a Rust string literal, quotes included.
+/// Example: `a"#b` → `"a\"#b"`
+fn rust_string_filter(
+    value: &tera::Value,
+    _args: &std::collections::HashMap<String, tera::Value>,
+) -> tera::Result<tera::Value> {
+    let s = value
+        .as_str()
+        .ok_or_else(|| tera::Error::msg("rust_string filter expects a string"))?;
+    Ok(tera::Value::String(format!("{s:?}")))
+}
+
 fn get_rust_tera() -> &'static Tera {
     RUST_TERA.get_or_init(|| {
         let mut tera = Tera::default();
         load_templates_from_dir(&mut tera, &RUST_TEMPLATES_DIR);
         tera.register_filter("camelCase", camel_case_filter);
+        tera.register_filter("rust_string", rust_string_filter);
         tera
     })
 }
@@ -295,8 +351,64 @@ fn load_templates_from_dir(tera: &mut Tera, dir: &Dir) {
     }
 }
 
-pub(crate) fn generate_code_with_snapshot(snapshot: &GenerationSnapshot) -> Result<String> {
-    let tera = get_rust_tera();
+/// Names of the built-in templates, sorted.
+pub fn builtin_template_names() -> Vec<String> {
+    let mut names: Vec<String> = RUST_TEMPLATES_DIR
+        .files()
+        .chain(RUST_TEMPLATES_DIR.dirs().flat_map(|dir| dir.files()))
+        .filter_map(|file| template_overrides::template_name(file.path()))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Content of the built-in template `name`.
+pub fn builtin_template(name: &str) -> Option<&'static str> {
+    RUST_TEMPLATES_DIR
+        .files()
+        .chain(RUST_TEMPLATES_DIR.dirs().flat_map(|dir| dir.files()))
+        .find(|file| template_overrides::template_name(file.path()).as_deref() == Some(name))
+        .and_then(|file| file.contents_utf8())
+}
+
+/// Templates to render with: the built-in ones, overlaid with the project-local
+/// templates of the manifest's `templates_path`, if any.
+pub(crate) fn get_tera_for_workspace(uow: &dyn GenerationOps) -> Result<Cow<'static, Tera>> {
+    let workspace = tools::get_workspace(uow)?;
+    let global = tools::get_global(uow)?;
+    let Some(templates_dir) = template_overrides::resolve_templates_dir(
+        Path::new(&workspace.manifest_absolute_path),
+        &global.templates_path,
+    ) else {
+        return Ok(Cow::Borrowed(get_rust_tera()));
+    };
+
+    let overrides = template_overrides::load_template_overrides(&templates_dir)?;
+    for stale in template_overrides::find_stale_overrides(&overrides, builtin_template) {
+        log::warn!(
+            "Template override {} ({}) is out of date: its built-in counterpart {}",
+            stale.name,
+            stale.path.display(),
+            match stale.reason {
+                template_overrides::StaleReason::BuiltinChanged => "changed upstream",
+                template_overrides::StaleReason::BuiltinRemoved => "no longer exists",
+            }
+        );
+    }
+
+    let mut tera = get_rust_tera().clone();
+    for template in &overrides {
+        tera.add_raw_template(&template.name, &template.content)
+            .with_context(|| format!("Invalid template {}", template.path.display()))?;
+    }
+    Ok(Cow::Owned(tera))
+}
+
+pub(crate) fn generate_code_with_snapshot(
+    tera: &Tera,
+    snapshot: &GenerationSnapshot,
+) -> Result<String> {
     let mut context = Context::new();
     context.insert("s", snapshot);
 
@@ -413,6 +525,36 @@ fn build_parsed_variants(
     )
 }
 
+/// Name of the enum variant to mark `#[default]`.
+fn enum_default_variant(default: Option<&FieldDefault>, variants: &[ParsedVariantVM]) -> String {
+    match default {
+        Some(FieldDefault::Variant(name)) => name.clone(),
+        _ => variants.first().map(|v| v.name.clone()).unwrap_or_default(),
+    }
+}
+
+/// Rust expression of a field default, for the generated `Default` impls.
+fn rust_default_expr(default: &FieldDefault, rust_base_type: &str, optional: bool) -> String {
+    let expr = match default {
+        FieldDefault::Boolean(value) => value.to_string(),
+        FieldDefault::Number(literal) => literal.clone(),
+        FieldDefault::Text(text) => format!("String::from({:?})", text),
+        FieldDefault::Parsed(text) => format!(
+            "{:?}.parse::<{}>().expect(\"default checked by qleany\")",
+            text, rust_base_type
+        ),
+        FieldDefault::Variant(name) => format!("{}::{}", rust_base_type, name),
+        FieldDefault::Millis(millis) => format!("std::time::Duration::from_millis({})", millis),
+        FieldDefault::Now => "chrono::Utc::now()".to_string(),
+        FieldDefault::NewUuid => "uuid::Uuid::new_v4()".to_string(),
+    };
+    if optional {
+        format!("Some({})", expr)
+    } else {
+        expr
+    }
+}
+
 // Snapshot builder to compose consistent data for templates
 pub(crate) struct SnapshotBuilder;
 
@@ -455,6 +597,12 @@ impl SnapshotBuilder {
                 FieldType::String => "String".to_string(),
                 FieldType::Uuid => "uuid::Uuid".to_string(),
                 FieldType::DateTime => "chrono::DateTime<chrono::Utc>".to_string(),
+                FieldType::Decimal => "rust_decimal::Decimal".to_string(),
+                FieldType::Date => "chrono::NaiveDate".to_string(),
+                FieldType::Time => "chrono::NaiveTime".to_string(),
+                FieldType::Duration => "std::time::Duration".to_string(),
+                FieldType::Bytes => "Vec<u8>".to_string(),
+                FieldType::Json => "serde_json::Value".to_string(),
                 FieldType::Entity => "EntityId".to_string(),
                 FieldType::Enum => f
                     .enum_name
@@ -501,6 +649,20 @@ impl SnapshotBuilder {
                 (vec![], vec![], false, false, false, false, vec![])
             };
 
+            let enum_is_simple = parsed_variants.iter().all(|v| v.is_simple);
+            let queryable = !is_list
+                && !matches!(
+                    f.field_type,
+                    FieldType::Entity | FieldType::Bytes | FieldType::Json
+                )
+                && (f.field_type != FieldType::Enum || enum_is_simple);
+
+            let field_default = FieldDefault::from_field(f);
+            let enum_default_variant =
+                enum_default_variant(field_default.as_ref(), &parsed_variants);
+            let rust_default =
+                field_default.map(|d| rust_default_expr(&d, &rust_base_type, f.optional));
+
             fields_vm_vec.push(FieldVM {
                 inner: f.clone(),
                 pascal_name: heck::AsPascalCase(&f.name).to_string(),
@@ -509,19 +671,29 @@ impl SnapshotBuilder {
                 optional: f.optional,
                 is_list,
                 rust_base_type,
-                rust_type,
                 rust_enum_variants,
                 parsed_variants,
+                enum_default_variant,
                 enum_needs_uuid,
                 enum_needs_chrono,
                 enum_needs_entity_id,
                 enum_needs_float,
+                enum_is_simple,
+                queryable,
+                has_index: f.indexed || f.unique,
+                rust_ref_type: if rust_type == "String" {
+                    "&str".to_string()
+                } else {
+                    format!("&{}", rust_type)
+                },
+                rust_default,
+                rust_type,
+                constraints: FieldConstraints::from_field(f),
             });
         }
 
         // Build relationships maps.
         // We want to include both forward and backward relationships where this entity is involved.
-        use std::collections::HashSet;
         let mut relationships_map: IndexMap<EntityId, Relationship> = IndexMap::new();
 
         // Start with relationships explicitly listed on this entity
@@ -660,6 +832,7 @@ impl SnapshotBuilder {
                         snake_name: heck::AsSnakeCase(&ancestor.name).to_string(),
                         pascal_name: heck::AsPascalCase(&ancestor.name).to_string(),
                         undoable: ancestor.undoable,
+                        has_checks: Self::entity_has_checks(uow, &ancestor),
                     });
                     cursor = Self::get_entity_owner(uow, &oid);
                 } else {
@@ -699,8 +872,23 @@ impl SnapshotBuilder {
         })
     }
 
-    fn get_dto_field_rust_type(dto_field: &DtoField) -> String {
-        let base_type = Self::get_dto_field_rust_base_type(dto_field);
+    /// Whether a field of `entity`, own or inherited, has constraints other than unique_in_parent.
+    fn entity_has_checks(uow: &dyn GenerationOps, entity: &Entity) -> bool {
+        let own = uow
+            .get_field_multi(&entity.fields)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .filter_map(|f| FieldConstraints::from_field(&f))
+            .any(|c| c.has_checks);
+        own || entity
+            .inherits_from
+            .and_then(|parent_id| uow.get_entity(&parent_id).ok().flatten())
+            .is_some_and(|parent| Self::entity_has_checks(uow, &parent))
+    }
+
+    fn get_dto_field_rust_type(dto_field: &DtoField, dtos: &HashMap<EntityId, Dto>) -> String {
+        let base_type = Self::get_dto_field_rust_base_type(dto_field, dtos);
         if dto_field.optional {
             format!("Option<{}>", base_type)
         } else if dto_field.is_list {
@@ -709,7 +897,7 @@ impl SnapshotBuilder {
             base_type
         }
     }
-    fn get_dto_field_rust_base_type(dto_field: &DtoField) -> String {
+    fn get_dto_field_rust_base_type(dto_field: &DtoField, dtos: &HashMap<EntityId, Dto>) -> String {
         match dto_field.field_type {
             DtoFieldType::Boolean => "bool".to_string(),
             DtoFieldType::Integer => "i64".to_string(),
@@ -718,14 +906,171 @@ impl SnapshotBuilder {
             DtoFieldType::String => "String".to_string(),
             DtoFieldType::Uuid => "uuid::Uuid".to_string(),
             DtoFieldType::DateTime => "chrono::DateTime<chrono::Utc>".to_string(),
+            DtoFieldType::Decimal => "rust_decimal::Decimal".to_string(),
+            DtoFieldType::Date => "chrono::NaiveDate".to_string(),
+            DtoFieldType::Time => "chrono::NaiveTime".to_string(),
+            DtoFieldType::Duration => "std::time::Duration".to_string(),
+            DtoFieldType::Bytes => "Vec<u8>".to_string(),
+            DtoFieldType::Json => "serde_json::Value".to_string(),
             DtoFieldType::Enum => dto_field
                 .enum_name
                 .clone()
                 .unwrap_or("enum_name not set".to_string()),
+            DtoFieldType::Dto => dto_field
+                .dto
+                .and_then(|dto_id| dtos.get(&dto_id))
+                .map(|dto| heck::AsPascalCase(&dto.name).to_string())
+                .unwrap_or("dto not set".to_string()),
+        }
+    }
+
+    /// Whether a field of the DTO, or of the DTOs nested in it, matches `predicate`.
+    fn dto_tree_any(
+        dto_id: EntityId,
+        dtos: &HashMap<EntityId, Dto>,
+        dto_fields: &HashMap<EntityId, DtoField>,
+        predicate: &dyn Fn(&DtoField) -> bool,
+        visited: &mut HashSet<EntityId>,
+    ) -> bool {
+        if !visited.insert(dto_id) {
+            return false;
+        }
+        let Some(dto) = dtos.get(&dto_id) else {
+            return false;
+        };
+        dto.fields
+            .iter()
+            .filter_map(|field_id| dto_fields.get(field_id))
+            .any(|df| {
+                predicate(df)
+                    || df.dto.is_some_and(|nested_id| {
+                        Self::dto_tree_any(nested_id, dtos, dto_fields, predicate, visited)
+                    })
+            })
+    }
+
+    fn dto_has_checks(
+        dto_id: EntityId,
+        dtos: &HashMap<EntityId, Dto>,
+        dto_fields: &HashMap<EntityId, DtoField>,
+    ) -> bool {
+        Self::dto_tree_any(
+            dto_id,
+            dtos,
+            dto_fields,
+            &|df| FieldConstraints::from_dto_field(df).is_some(),
+            &mut HashSet::new(),
+        )
+    }
+
+    fn build_dto_vm(
+        dto: &Dto,
+        dtos: &HashMap<EntityId, Dto>,
+        dto_fields: &HashMap<EntityId, DtoField>,
+    ) -> DtoVM {
+        DtoVM {
+            inner: dto.clone(),
+            fields: dto
+                .fields
+                .iter()
+                .filter_map(|field_id| dto_fields.get(field_id))
+                .map(|df| Self::build_dto_field_vm(df, dtos, dto_fields))
+                .collect(),
+            pascal_name: heck::AsPascalCase(&dto.name).to_string(),
+            has_checks: Self::dto_has_checks(dto.id, dtos, dto_fields),
+            derives_eq: !Self::dto_tree_any(
+                dto.id,
+                dtos,
+                dto_fields,
+                &|df| df.field_type == DtoFieldType::Float,
+                &mut HashSet::new(),
+            ),
+        }
+    }
+
+    /// Load a DTO with its fields and the DTOs nested in them.
+    fn load_dto(
+        uow: &dyn GenerationOps,
+        dto_id: &EntityId,
+        dtos: &mut HashMap<EntityId, Dto>,
+        dto_fields: &mut HashMap<EntityId, DtoField>,
+    ) -> Result<()> {
+        if dtos.contains_key(dto_id) {
+            return Ok(());
+        }
+        let dto = uow
+            .get_dto(dto_id)?
+            .ok_or_else(|| anyhow!("DTO missing for use case"))?;
+        let fields_vec: Vec<DtoField> = uow
+            .get_dto_field_multi(&dto.fields)?
+            .into_iter()
+            .flatten()
+            .collect();
+        dtos.insert(dto.id, dto);
+        for f in fields_vec {
+            if let Some(nested_id) = f.dto {
+                Self::load_dto(uow, &nested_id, dtos, dto_fields)?;
+            }
+            dto_fields.insert(f.id, f);
+        }
+        Ok(())
+    }
+
+    /// The DTOs of a feature: the shared ones, nested ones first, then the
+    /// inline ones of its use cases.
+    fn feature_dto_vms(
+        feature: &Feature,
+        use_cases: &[UseCase],
+        dtos: &HashMap<EntityId, Dto>,
+        dto_fields: &HashMap<EntityId, DtoField>,
+    ) -> Vec<DtoVM> {
+        // post-order: nested DTOs come before their holder
+        fn visit(
+            dto_id: EntityId,
+            dtos: &HashMap<EntityId, Dto>,
+            dto_fields: &HashMap<EntityId, DtoField>,
+            visited: &mut HashSet<EntityId>,
+            ordered: &mut Vec<EntityId>,
+        ) {
+            if !visited.insert(dto_id) {
+                return;
+            }
+            let Some(dto) = dtos.get(&dto_id) else {
+                return;
+            };
+            for df in dto.fields.iter().filter_map(|id| dto_fields.get(id)) {
+                if let Some(nested_id) = df.dto {
+                    visit(nested_id, dtos, dto_fields, visited, ordered);
+                }
+            }
+            ordered.push(dto_id);
+        }
+
+        let mut visited: HashSet<EntityId> = HashSet::new();
+        let mut ordered: Vec<EntityId> = vec![];
+        for dto_id in &feature.dtos {
+            visit(*dto_id, dtos, dto_fields, &mut visited, &mut ordered);
+        }
+        for uc in use_cases {
+            for dto_id in [uc.dto_in, uc.dto_out, uc.shared_dto_in, uc.shared_dto_out]
+                .into_iter()
+                .flatten()
+            {
+                visit(dto_id, dtos, dto_fields, &mut visited, &mut ordered);
+            }
         }
+        ordered
+            .iter()
+            .filter_map(|dto_id| dtos.get(dto_id))
+            .map(|dto| Self::build_dto_vm(dto, dtos, dto_fields))
+            .collect()
     }
 
-    fn build_dto_field_vm(df: &DtoField) -> DtoFieldVM {
+    fn build_dto_field_vm(
+        df: &DtoField,
+        dtos: &HashMap<EntityId, Dto>,
+        dto_fields: &HashMap<EntityId, DtoField>,
+    ) -> DtoFieldVM {
         let (
             rust_enum_variants,
             parsed_variants,
@@ -739,19 +1084,37 @@ impl SnapshotBuilder {
         } else {
             (vec![], vec![], false, false, false, false, vec![])
         };
+        let field_default = FieldDefault::from_dto_field(df);
         DtoFieldVM {
             inner: df.clone(),
             pascal_name: heck::AsPascalCase(&df.name).to_string(),
             snake_name: heck::AsSnakeCase(&df.name).to_string(),
-            rust_base_type: Self::get_dto_field_rust_base_type(df),
-            rust_type: Self::get_dto_field_rust_type(df),
+            rust_base_type: Self::get_dto_field_rust_base_type(df, dtos),
+            rust_type: Self::get_dto_field_rust_type(df, dtos),
             rust_enum_variants,
+            enum_default_variant: enum_default_variant(field_default.as_ref(), &parsed_variants),
             parsed_variants,
             enum_needs_uuid,
             enum_needs_chrono,
             enum_needs_entity_id,
             enum_needs_float,
             external_enum_references,
+            dto_pascal_name: df
+                .dto
+                .filter(|_| df.field_type == DtoFieldType::Dto)
+                .and_then(|dto_id| dtos.get(&dto_id))
+                .map(|dto| heck::AsPascalCase(&dto.name).to_string()),
+            dto_has_checks: df
+                .dto
+                .is_some_and(|dto_id| Self::dto_has_checks(dto_id, dtos, dto_fields)),
+            constraints: FieldConstraints::from_dto_field(df),
+            rust_default: field_default.map(|d| {
+                rust_default_expr(
+                    &d,
+                    &Self::get_dto_field_rust_base_type(df, dtos),
+                    df.optional,
+                )
+            }),
         }
     }
 
@@ -952,6 +1315,9 @@ impl SnapshotBuilder {
                     .flatten()
                     .collect();
 
+                for dto_id in &feat_opt.dtos {
+                    Self::load_dto(uow, dto_id, &mut dtos, &mut dto_fields)?;
+                }
                 features.insert(feat_opt.id, feat_opt);
 
                 for use_case in feature_use_cases {
@@ -965,34 +1331,17 @@ impl SnapshotBuilder {
                         entities.insert(e.id, e);
                     }
 
-                    // DTOs
-                    if let Some(dto_in) = use_case.dto_in {
-                        let dto = uow
-                            .get_dto(&dto_in)?
-                            .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                        let fields_vec: Vec<DtoField> = uow
-                            .get_dto_field_multi(&dto.fields)?
-                            .into_iter()
-                            .flatten()
-                            .collect();
-                        for f in fields_vec {
-                            dto_fields.insert(f.id, f);
-                        }
-                        dtos.insert(dto.id, dto);
-                    }
-                    if let Some(dto_out) = use_case.dto_out {
-                        let dto = uow
-                            .get_dto(&dto_out)?
-                            .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                        let fields_vec: Vec<DtoField> = uow
-                            .get_dto_field_multi(&dto.fields)?
-                            .into_iter()
-                            .flatten()
-                            .collect();
-                        for f in fields_vec {
-                            dto_fields.insert(f.id, f);
-                        }
-                        dtos.insert(dto.id, dto);
+                    // DTOs, inline or shared, with the DTOs nested in them
+                    for dto_id in [
+                        use_case.dto_in,
+                        use_case.dto_out,
+                        use_case.shared_dto_in,
+                        use_case.shared_dto_out,
+                    ]
+                    .into_iter()
+                    .flatten()
+                    {
+                        Self::load_dto(uow, &dto_id, &mut dtos, &mut dto_fields)?;
                     }
 
                     use_cases.insert(use_case.id, use_case);
@@ -1011,6 +1360,9 @@ impl SnapshotBuilder {
                 .flatten()
                 .collect();
 
+            for dto_id in &feature.dtos {
+                Self::load_dto(uow, dto_id, &mut dtos, &mut dto_fields)?;
+            }
             features.insert(feature.id, feature);
 
             for use_case in feature_use_cases {
@@ -1026,34 +1378,17 @@ impl SnapshotBuilder {
                     }
                 }
 
-                // DTOs
-                if let Some(dto_in) = use_case.dto_in {
-                    let dto = uow
-                        .get_dto(&dto_in)?
-                        .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                    let fields_vec: Vec<DtoField> = uow
-                        .get_dto_field_multi(&dto.fields)?
-                        .into_iter()
-                        .flatten()
-                        .collect();
-                    for f in fields_vec {
-                        dto_fields.insert(f.id, f);
-                    }
-                    dtos.insert(dto.id, dto);
-                }
-                if let Some(dto_out) = use_case.dto_out {
-                    let dto = uow
-                        .get_dto(&dto_out)?
-                        .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                    let fields_vec: Vec<DtoField> = uow
-                        .get_dto_field_multi(&dto.fields)?
-                        .into_iter()
-                        .flatten()
-                        .collect();
-                    for f in fields_vec {
-                        dto_fields.insert(f.id, f);
-                    }
-                    dtos.insert(dto.id, dto);
+                // DTOs, inline or shared, with the DTOs nested in them
+                for dto_id in [
+                    use_case.dto_in,
+                    use_case.dto_out,
+                    use_case.shared_dto_in,
+                    use_case.shared_dto_out,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    Self::load_dto(uow, &dto_id, &mut dtos, &mut dto_fields)?;
                 }
 
                 use_cases.insert(use_case.id, use_case);
@@ -1077,33 +1412,17 @@ impl SnapshotBuilder {
                 }
             }
 
-            if let Some(dto_in) = use_case.dto_in {
-                let dto = uow
-                    .get_dto(&dto_in)?
-                    .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                let fields_vec: Vec<DtoField> = uow
-                    .get_dto_field_multi(&dto.fields)?
-                    .into_iter()
-                    .flatten()
-                    .collect();
-                for f in fields_vec {
-                    dto_fields.insert(f.id, f);
-                }
-                dtos.insert(dto.id, dto);
-            }
-            if let Some(dto_out) = use_case.dto_out {
-                let dto = uow
-                    .get_dto(&dto_out)?
-                    .ok_or_else(|| anyhow!("DTO missing for use case"))?;
-                let fields_vec: Vec<DtoField> = uow
-                    .get_dto_field_multi(&dto.fields)?
-                    .into_iter()
-                    .flatten()
-                    .collect();
-                for f in fields_vec {
-                    dto_fields.insert(f.id, f);
-                }
-                dtos.insert(dto.id, dto);
+            // DTOs, inline or shared, with the DTOs nested in them
+            for dto_id in [
+                use_case.dto_in,
+                use_case.dto_out,
+                use_case.shared_dto_in,
+                use_case.shared_dto_out,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                Self::load_dto(uow, &dto_id, &mut dtos, &mut dto_fields)?;
             }
             use_cases.insert(use_case.id, use_case);
         }
@@ -1220,20 +1539,9 @@ impl SnapshotBuilder {
         let mut dto_keys: Vec<EntityId> = dtos.keys().copied().collect();
         dto_keys.sort();
         for did in &dto_keys {
-            let d = &dtos[did];
-            let mut df_vec: Vec<DtoFieldVM> = Vec::new();
-            for dfid in &d.fields {
-                if let Some(df) = dto_fields.get(dfid) {
-                    df_vec.push(SnapshotBuilder::build_dto_field_vm(df));
-                }
-            }
             dtos_vm.insert(
                 *did,
-                DtoVM {
-                    inner: d.clone(),
-                    fields: df_vec,
-                    pascal_name: heck::AsPascalCase(&d.name).to_string(),
-                },
+                SnapshotBuilder::build_dto_vm(&dtos[did], &dtos, &dto_fields),
             );
         }
 
@@ -1246,6 +1554,16 @@ impl SnapshotBuilder {
                     k,
                     FeatureVM {
                         inner: v.clone(),
+                        dtos: SnapshotBuilder::feature_dto_vms(
+                            &v,
+                            &uow.get_use_case_multi(&v.use_cases)
+                                .unwrap_or_default()
+                                .into_iter()
+                                .flatten()
+                                .collect::<Vec<UseCase>>(),
+                            &dtos,
+                            &dto_fields,
+                        ),
                         use_cases: {
                             let use_cases_ids = v.use_cases;
                             let use_cases: IndexMap<EntityId, UseCase> = uow
@@ -1305,38 +1623,16 @@ impl SnapshotBuilder {
                                                 entity_vms
                                                     }
                                             },
-                                            dto_in: uc.dto_in.and_then(|dto_id| {
-                                                dtos.get(&dto_id).map(|d| DtoVM {
-                                                    inner: d.clone(),
-                                                    fields: {
-                                                        let mut df_vec: Vec<DtoFieldVM> = Vec::new();
-                                                        for dfid in &d.fields {
-                                                            if let Some(df) = dto_fields.get(dfid) {
-                                                                df_vec.push(SnapshotBuilder::build_dto_field_vm(df));
-                                                            }
-                                                        }
-                                                        df_vec
-                                                    },
-                                                    pascal_name: heck::AsPascalCase(&d.name)
-                                                        .to_string(),
-                                                })
-                                            }),
-                                            dto_out: uc.dto_out.and_then(|dto_id| {
-                                                dtos.get(&dto_id).map(|d| DtoVM {
-                                                    inner: d.clone(),
-                                                    fields: {
-                                                        let mut df_vec: Vec<DtoFieldVM> = Vec::new();
-                                                        for dfid in &d.fields {
-                                                            if let Some(df) = dto_fields.get(dfid) {
-                                                                df_vec.push(SnapshotBuilder::build_dto_field_vm(df));
-                                                            }
-                                                        }
-                                                        df_vec
-                                                    },
-                                                    pascal_name: heck::AsPascalCase(&d.name)
-                                                        .to_string(),
-                                                })
-                                            }),
+                                            dto_in: uc
+.dto_in
+.or(uc.shared_dto_in)
+.and_then(|dto_id| dtos.get(&dto_id))
+.map(|d| SnapshotBuilder::build_dto_vm(d, &dtos, &dto_fields)),
+                                            dto_out: uc
+.dto_out
+.or(uc.shared_dto_out)
+.and_then(|dto_id| dtos.get(&dto_id))
+.map(|d| SnapshotBuilder::build_dto_vm(d, &dtos, &dto_fields)),
                                             pascal_name: heck::AsPascalCase(&uc.name).to_string(),
                                             snake_name: heck::AsSnakeCase(&uc.name).to_string(),
                                         },
@@ -1396,36 +1692,16 @@ impl SnapshotBuilder {
                                     .collect()
                             }
                         },
-                        dto_in: uc.dto_in.and_then(|dto_id| {
-                            dtos.get(&dto_id).map(|d| DtoVM {
-                                inner: d.clone(),
-                                fields: {
-                                    let mut df_vec: Vec<DtoFieldVM> = Vec::new();
-                                    for dfid in &d.fields {
-                                        if let Some(df) = dto_fields.get(dfid) {
-                                            df_vec.push(SnapshotBuilder::build_dto_field_vm(df));
-                                        }
-                                    }
-                                    df_vec
-                                },
-                                pascal_name: heck::AsPascalCase(&d.name).to_string(),
-                            })
-                        }),
-                        dto_out: uc.dto_out.and_then(|dto_id| {
-                            dtos.get(&dto_id).map(|d| DtoVM {
-                                inner: d.clone(),
-                                fields: {
-                                    let mut df_vec: Vec<DtoFieldVM> = Vec::new();
-                                    for dfid in &d.fields {
-                                        if let Some(df) = dto_fields.get(dfid) {
-                                            df_vec.push(SnapshotBuilder::build_dto_field_vm(df));
-                                        }
-                                    }
-                                    df_vec
-                                },
-                                pascal_name: heck::AsPascalCase(&d.name).to_string(),
-                            })
-                        }),
+                        dto_in: uc
+                            .dto_in
+                            .or(uc.shared_dto_in)
+                            .and_then(|dto_id| dtos.get(&dto_id))
+                            .map(|d| SnapshotBuilder::build_dto_vm(d, &dtos, &dto_fields)),
+                        dto_out: uc
+                            .dto_out
+                            .or(uc.shared_dto_out)
+                            .and_then(|dto_id| dtos.get(&dto_id))
+                            .map(|d| SnapshotBuilder::build_dto_vm(d, &dtos, &dto_fields)),
                         pascal_name: heck::AsPascalCase(&uc.name).to_string(),
                         snake_name: heck::AsSnakeCase(&uc.name).to_string(),
                     },
@@ -1493,6 +1769,8 @@ mod tests {
                     organisation_name: "test".into(),
                     organisation_domain: "test".into(),
                     prefix_path: "".into(),
+                    storage: "memory".into(),
+                    templates_path: String::new(),
                 },
                 application_kebab_name: "test".into(),
                 application_snake_name: "test".into(),
@@ -1560,6 +1838,8 @@ mod tests {
             organisation_name: "".to_string(),
             organisation_domain: "".to_string(),
             prefix_path: "".to_string(),
+            storage: "memory".to_string(),
+            templates_path: String::new(),
         };
         let entity = Entity {
             id: entity_id,
@@ -1573,6 +1853,8 @@ mod tests {
             fields: vec![100, 101],
             relationships: vec![],
             undoable: true,
+            undo_journal: false,
+            source_file: String::new(),
         };
         let field_relationship = Field {
             id: 100,
@@ -1589,6 +1871,7 @@ mod tests {
             list_model_displayed_field: None,
             enum_name: None,
             enum_values: vec![],
+            ..Default::default()
         };
         let field_tags = Field {
             id: 101,
@@ -1605,6 +1888,7 @@ mod tests {
             list_model_displayed_field: None,
             enum_name: None,
             enum_values: vec![],
+            ..Default::default()
         };
 
         let snapshot = GenerationSnapshot {
@@ -1649,10 +1933,17 @@ mod tests {
                         rust_type: "String".to_string(),
                         rust_enum_variants: vec![],
                         parsed_variants: vec![],
+                        enum_default_variant: String::new(),
                         enum_needs_uuid: false,
                         enum_needs_chrono: false,
                         enum_needs_entity_id: false,
                         enum_needs_float: false,
+                        enum_is_simple: true,
+                        queryable: false,
+                        has_index: false,
+                        rust_ref_type: "&str".to_string(),
+                        constraints: None,
+                        rust_default: None,
                     },
                     FieldVM {
                         inner: field_tags.clone(),
@@ -1665,10 +1956,17 @@ mod tests {
                         rust_type: "Vec<String>".to_string(),
                         rust_enum_variants: vec![],
                         parsed_variants: vec![],
+                        enum_default_variant: String::new(),
                         enum_needs_uuid: false,
                         enum_needs_chrono: false,
                         enum_needs_entity_id: false,
                         enum_needs_float: false,
+                        enum_is_simple: true,
+                        queryable: false,
+                        has_index: false,
+                        rust_ref_type: "&Vec<String>".to_string(),
+                        constraints: None,
+                        rust_default: None,
                     },
                 ];
                 m.insert(
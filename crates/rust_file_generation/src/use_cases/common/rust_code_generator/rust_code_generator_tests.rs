@@ -119,6 +119,11 @@ impl GenerationOps for DummyGenerationReadOps {
                 .get(id)
                 .map(|w| vec![w.user_interface])
                 .unwrap_or_default()),
+            WorkspaceRelationshipField::CustomFiles => Ok(self
+                .workspaces
+                .get(id)
+                .map(|w| w.custom_files.clone())
+                .unwrap_or_default()),
         }
     }
 
@@ -283,6 +288,8 @@ fn for_file_feature_without_use_cases_errors() {
         updated_at: chrono::Utc::now(),
         name: "Feat".into(),
         use_cases: vec![],
+        dtos: vec![],
+        source_file: String::new(),
     };
     uow.features.insert(10, feature);
     let global = Global {
@@ -294,6 +301,8 @@ fn for_file_feature_without_use_cases_errors() {
         organisation_name: "Org".into(),
         organisation_domain: "org.com".into(),
         prefix_path: "".into(),
+        storage: "memory".into(),
+        templates_path: String::new(),
     };
     uow.globals.insert(3, global);
     let user_interface = UserInterface {
@@ -317,6 +326,11 @@ fn for_file_feature_without_use_cases_errors() {
         entities: vec![],
         features: vec![10],
         user_interface: 1,
+        custom_files: vec![],
+        includes: vec![],
+        manifest_text: String::new(),
+        included_files: vec![],
+        included_texts: vec![],
     };
     uow.workspaces.insert(2, workspace);
     uow.workspace_features.insert(2, vec![10]);
@@ -368,6 +382,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         long_operation: false,
         dto_in: Some(200),
         dto_out: Some(201),
+        shared_dto_in: None,
+        shared_dto_out: None,
     };
     let feature = Feature {
         id: 10,
@@ -375,6 +391,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         updated_at: chrono::Utc::now(),
         name: "Feat".into(),
         use_cases: vec![100],
+        dtos: vec![],
+        source_file: String::new(),
     };
     uow.features.insert(10, feature);
     uow.use_cases.insert(100, uc.clone());
@@ -391,6 +409,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         fields: vec![400],
         relationships: vec![],
         undoable: false,
+        undo_journal: false,
+        source_file: String::new(),
     };
     let field = Field {
         id: 400,
@@ -407,6 +427,7 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         list_model_displayed_field: None,
         enum_name: None,
         enum_values: vec![],
+        ..Default::default()
     };
     uow.entities.insert(300, ent);
     uow.fields.insert(400, field);
@@ -437,6 +458,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         is_list: false,
         enum_name: None,
         enum_values: vec![],
+        dto: None,
+        ..Default::default()
     };
     let df_out = DtoField {
         id: 501,
@@ -448,6 +471,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         is_list: false,
         enum_name: None,
         enum_values: vec![],
+        dto: None,
+        ..Default::default()
     };
     uow.dto_fields.insert(500, df_in);
     uow.dto_fields.insert(501, df_out);
@@ -460,6 +485,8 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         organisation_name: "Org".into(),
         organisation_domain: "org.com".into(),
         prefix_path: "".into(),
+        storage: "memory".into(),
+        templates_path: String::new(),
     };
     uow.globals.insert(3, global);
     let user_interface = UserInterface {
@@ -483,6 +510,11 @@ fn for_file_happy_path_feature_with_use_case_and_dtos() {
         entities: vec![],
         features: vec![10],
         user_interface: 1,
+        custom_files: vec![],
+        includes: vec![],
+        manifest_text: String::new(),
+        included_files: vec![],
+        included_texts: vec![],
     };
     uow.workspaces.insert(2, workspace);
     uow.workspace_features.insert(2, vec![10]);
@@ -528,6 +560,8 @@ fn for_file_various_combinations_generate_expected_items() {
         fields: vec![],
         relationships: vec![],
         undoable: false,
+        undo_journal: false,
+        source_file: String::new(),
     };
     let ent_b = Entity {
         id: 2,
@@ -541,6 +575,8 @@ fn for_file_various_combinations_generate_expected_items() {
         fields: vec![],
         relationships: vec![],
         undoable: false,
+        undo_journal: false,
+        source_file: String::new(),
     };
     uow.entities.insert(1, ent_a.clone());
     uow.entities.insert(2, ent_b.clone());
@@ -577,6 +613,8 @@ fn for_file_various_combinations_generate_expected_items() {
         long_operation: false,
         dto_in: Some(10),
         dto_out: Some(11),
+        shared_dto_in: None,
+        shared_dto_out: None,
     };
     uow.use_cases.insert(100, uc.clone());
 
@@ -587,6 +625,8 @@ fn for_file_various_combinations_generate_expected_items() {
         updated_at: chrono::Utc::now(),
         name: "Feat".into(),
         use_cases: vec![100],
+        dtos: vec![],
+        source_file: String::new(),
     };
     uow.features.insert(200, feat.clone());
     let global = Global {
@@ -598,6 +638,8 @@ fn for_file_various_combinations_generate_expected_items() {
         organisation_name: "Org".into(),
         organisation_domain: "org.com".into(),
         prefix_path: "".into(),
+        storage: "memory".into(),
+        templates_path: String::new(),
     };
     uow.globals.insert(3, global);
     let user_interface = UserInterface {
@@ -621,6 +663,11 @@ fn for_file_various_combinations_generate_expected_items() {
         entities: vec![1, 2],
         features: vec![200],
         user_interface: 1,
+        custom_files: vec![],
+        includes: vec![],
+        manifest_text: String::new(),
+        included_files: vec![],
+        included_texts: vec![],
     };
     uow.workspaces.insert(2, workspace);
     uow.workspace_features.insert(2, vec![200]);
@@ -805,3 +852,369 @@ fn for_file_various_combinations_generate_expected_items() {
     // entities from UC plus explicitly provided entity
     assert!(snap.entities.contains_key(&1) && snap.entities.contains_key(&2));
 }
+
+#[test]
+fn templates_path_overlays_builtin_templates() {
+    let manifest_dir =
+        std::env::temp_dir().join(format!("qleany_templates_path_{}", std::process::id()));
+    std::fs::create_dir_all(manifest_dir.join("templates")).unwrap();
+    std::fs::write(
+        manifest_dir.join("templates/root_cargo.tera"),
+        "custom {{ 1 + 1 }}",
+    )
+    .unwrap();
+
+    let mut uow = DummyGenerationReadOps::new();
+    uow.globals.insert(
+        3,
+        Global {
+            id: 3,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            application_name: "App".into(),
+            language: "rust".into(),
+            organisation_name: "Org".into(),
+            organisation_domain: "org.com".into(),
+            prefix_path: "".into(),
+            storage: "memory".into(),
+            templates_path: "templates".into(),
+        },
+    );
+    uow.workspaces.insert(
+        2,
+        Workspace {
+            id: 2,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            manifest_absolute_path: manifest_dir.to_string_lossy().to_string(),
+            global: 3,
+            entities: vec![],
+            features: vec![],
+            user_interface: 1,
+            custom_files: vec![],
+            includes: vec![],
+            manifest_text: String::new(),
+            included_files: vec![],
+            included_texts: vec![],
+        },
+    );
+    uow.roots.insert(
+        1,
+        Root {
+            id: 1,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            workspace: Some(2),
+            system: Some(4),
+        },
+    );
+
+    let tera = super::get_tera_for_workspace(&uow);
+    std::fs::remove_dir_all(&manifest_dir).unwrap();
+    let tera = tera.expect("templates with overrides");
+
+    let context = tera::Context::new();
+    assert_eq!(tera.render("root_cargo", &context).unwrap(), "custom 2");
+    // Other templates stay the built-in ones
+    assert!(tera.get_template("entity_dtos").is_ok());
+    assert!(super::builtin_template("root_cargo").is_some());
+}
+
+#[test]
+fn rust_string_filter_escapes_patterns() {
+    let mut context = tera::Context::new();
+    context.insert("pattern", r##"^"#\d+$"##);
+    let rendered = super::get_rust_tera()
+        .clone()
+        .render_str("{{ pattern | rust_string }}", &context)
+        .unwrap();
+    assert_eq!(rendered, r##""^\"#\\d+$""##);
+}
+
+// Seeds an in-memory database with a "Library" owning "Book" entities, a "catalog"
+// feature with a use case and a long operation returning a count, and the given UI
+fn seed_library_workspace(
+    db_context: &common::database::db_context::DbContext,
+    ui: UserInterface,
+) -> Result<()> {
+    use common::database::transactions::Transaction;
+    use common::direct_access::repository_factory::write;
+    use common::event::EventBuffer;
+    use heck::ToUpperCamelCase;
+
+    let mut transaction = Transaction::begin_write_transaction(db_context)?;
+    let mut events = EventBuffer::new();
+
+    let global = write::create_global_repository(&transaction)?.create_orphan(
+        &mut events,
+        &Global {
+            application_name: "Library App".into(),
+            language: "rust".into(),
+            organisation_name: "Org".into(),
+            organisation_domain: "org.com".into(),
+            storage: "memory".into(),
+            ..Default::default()
+        },
+    )?;
+    let ui =
+        write::create_user_interface_repository(&transaction)?.create_orphan(&mut events, &ui)?;
+    let mut fields = write::create_field_repository(&transaction)?;
+    let books = fields.create_orphan(
+        &mut events,
+        &Field {
+            name: "books".into(),
+            field_type: FieldType::Entity,
+            is_list: true,
+            strong: true,
+            ..Default::default()
+        },
+    )?;
+    let title = fields.create_orphan(
+        &mut events,
+        &Field {
+            name: "title".into(),
+            field_type: FieldType::String,
+            ..Default::default()
+        },
+    )?;
+    let pages = fields.create_orphan(
+        &mut events,
+        &Field {
+            name: "pages".into(),
+            field_type: FieldType::Integer,
+            ..Default::default()
+        },
+    )?;
+    let mut entities = write::create_entity_repository(&transaction)?;
+    let book = entities.create_orphan(
+        &mut events,
+        &Entity {
+            name: "Book".into(),
+            fields: vec![title.id, pages.id],
+            undoable: true,
+            ..Default::default()
+        },
+    )?;
+    fields.update_with_relationships(
+        &mut events,
+        &Field {
+            entity: Some(book.id),
+            ..books
+        },
+    )?;
+    let library = entities.create_orphan(
+        &mut events,
+        &Entity {
+            name: "Library".into(),
+            fields: vec![books.id],
+            undoable: true,
+            ..Default::default()
+        },
+    )?;
+    let mut dto_fields = write::create_dto_field_repository(&transaction)?;
+    let mut dtos = write::create_dto_repository(&transaction)?;
+    let mut use_cases = write::create_use_case_repository(&transaction)?;
+    let mut use_case_ids = vec![];
+    for (name, long_operation) in [("count_books", false), ("export_books", true)] {
+        let count = dto_fields.create_orphan(
+            &mut events,
+            &DtoField {
+                name: "count".into(),
+                field_type: common::entities::DtoFieldType::Integer,
+                ..Default::default()
+            },
+        )?;
+        let dto_out = dtos.create_orphan(
+            &mut events,
+            &Dto {
+                name: format!("{}_dto", name).to_upper_camel_case(),
+                fields: vec![count.id],
+                ..Default::default()
+            },
+        )?;
+        let use_case = use_cases.create_orphan(
+            &mut events,
+            &UseCase {
+                name: name.into(),
+                entities: vec![book.id],
+                read_only: true,
+                long_operation,
+                dto_out: Some(dto_out.id),
+                ..Default::default()
+            },
+        )?;
+        use_case_ids.push(use_case.id);
+    }
+    let catalog = write::create_feature_repository(&transaction)?.create_orphan(
+        &mut events,
+        &Feature {
+            name: "catalog".into(),
+            use_cases: use_case_ids,
+            ..Default::default()
+        },
+    )?;
+    let workspace = write::create_workspace_repository(&transaction)?.create_orphan(
+        &mut events,
+        &Workspace {
+            global: global.id,
+            user_interface: ui.id,
+            entities: vec![library.id, book.id],
+            features: vec![catalog.id],
+            ..Default::default()
+        },
+    )?;
+    let system = write::create_system_repository(&transaction)?
+        .create_orphan(&mut events, &System::default())?;
+    write::create_root_repository(&transaction)?.create_orphan(
+        &mut events,
+        &Root {
+            workspace: Some(workspace.id),
+            system: Some(system.id),
+            ..Default::default()
+        },
+    )?;
+    drop((fields, entities, dto_fields, dtos, use_cases));
+    transaction.commit()
+}
+
+// Fills the file list and renders every listed file of the given template names
+fn fill_and_render(ui: UserInterface, template_names: &[&str]) -> Result<Vec<(String, String)>> {
+    use crate::rust_file_generation_controller;
+    use crate::{FillRustFilesDto, GenerateRustCodeDto};
+
+    let db_context = common::database::db_context::DbContext::new()?;
+    let event_hub = std::sync::Arc::new(common::event::EventHub::new());
+    seed_library_workspace(&db_context, ui)?;
+
+    let filled = rust_file_generation_controller::fill_rust_files(
+        &db_context,
+        &event_hub,
+        &FillRustFilesDto {
+            only_list_already_existing: false,
+        },
+    )?;
+
+    let transaction =
+        common::database::transactions::Transaction::begin_read_transaction(&db_context)?;
+    let file_repository =
+        common::direct_access::repository_factory::write::create_file_repository(&transaction)?;
+    let mut rendered = Vec::new();
+    for (file_id, file_name) in filled.file_ids.iter().zip(&filled.file_names) {
+        let file = file_repository
+            .get(file_id)?
+            .ok_or_else(|| anyhow::anyhow!("File not found"))?;
+        if !template_names.contains(&file.template_name.as_str()) {
+            continue;
+        }
+        let code = rust_file_generation_controller::generate_rust_code(
+            &db_context,
+            &event_hub,
+            &GenerateRustCodeDto { file_id: *file_id },
+        )?;
+        rendered.push((file_name.clone(), code.generated_code));
+    }
+    Ok(rendered)
+}
+
+#[test]
+fn cli_main_has_subcommands_for_each_entity() {
+    let rendered = fill_and_render(
+        UserInterface {
+            rust_cli: true,
+            ..Default::default()
+        },
+        &["cli_cargo", "cli_main"],
+    )
+    .expect("cli files");
+    let names: Vec<&str> = rendered.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["crates/cli/Cargo.toml", "crates/cli/src/main.rs"]);
+
+    let main = &rendered[1].1;
+    for entity in ["Library", "Book"] {
+        assert!(
+            main.contains(&format!("enum {entity}Commands")),
+            "no {entity} subcommand"
+        );
+    }
+    assert!(main.contains("title"));
+}
+
+#[test]
+fn slint_views_are_listed_per_entity() {
+    let rendered = fill_and_render(
+        UserInterface {
+            rust_slint: true,
+            ..Default::default()
+        },
+        &["slint_entity_view", "slint_entity_view_ui", "slint_views"],
+    )
+    .expect("slint files");
+    let mut names: Vec<&str> = rendered.iter().map(|(name, _)| name.as_str()).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            "crates/slint_ui/src/views.rs",
+            "crates/slint_ui/src/views/book_view.rs",
+            "crates/slint_ui/src/views/library_view.rs",
+            "crates/slint_ui/ui/views/book_view.slint",
+            "crates/slint_ui/ui/views/library_view.slint",
+        ]
+    );
+
+    for (name, code) in &rendered {
+        if name.ends_with("book_view.slint") {
+            assert!(code.contains("BookView"), "{name}");
+            assert!(code.contains("title"), "{name}");
+        }
+        if name.ends_with("views.rs") {
+            assert!(code.contains("book_view") && code.contains("library_view"));
+        }
+    }
+}
+
+#[test]
+fn controller_publishes_use_case_outputs() {
+    let rendered = fill_and_render(
+        UserInterface::default(),
+        &["feature_controller", "feature_use_case"],
+    )
+    .expect("feature files");
+    let find = |suffix: &str| {
+        rendered
+            .iter()
+            .find(|(name, _)| name.ends_with(suffix))
+            .map(|(_, code)| code.as_str())
+            .unwrap_or_else(|| panic!("no {suffix}"))
+    };
+
+    let controller = find("catalog_controller.rs");
+    assert!(controller.contains("data: Some(serde_json::to_string(&return_dto)?)"));
+    assert!(controller.contains("PublishOutput::new("));
+    // the scaffolded use cases fail until implemented, and publish no output
+    for use_case in [find("count_books_uc.rs"), find("export_books_uc.rs")] {
+        assert!(use_case.contains("Err(anyhow!(\"Not implemented\"))"));
+        assert!(!use_case.contains("serde_json"));
+    }
+}
+
+#[test]
+fn text_conditions_are_only_offered_on_string_fields() {
+    let rendered = fill_and_render(UserInterface::default(), &["common_entity_repository"])
+        .expect("repository files");
+    let find = |suffix: &str| {
+        rendered
+            .iter()
+            .find(|(name, _)| name.ends_with(suffix))
+            .map(|(_, code)| code.as_str())
+            .unwrap_or_else(|| panic!("no {suffix}"))
+    };
+
+    let book = find("book_repository.rs");
+    assert!(book.contains("types::{Condition, EntityId, SortOrder, TextCondition},"));
+    assert!(book.contains("    Title(TextCondition),"));
+    assert!(book.contains("    Pages(Condition<i64>),"));
+    // no queryable field, nothing to import
+    let library = find("library_repository.rs");
+    assert!(library.contains("types::{EntityId, SortOrder},"));
+}
@@ -0,0 +1,141 @@
+#![cfg(test)]
+
+use super::{
+    EntityVM, FileVM, GenerationSnapshot, GlobalVM, SystemVM, UserInterfaceVM, get_rust_tera,
+};
+use common::entities::{Entity, File, FileStatus, Global, System, UserInterface};
+use indexmap::IndexMap;
+use tera::Context;
+
+fn render_with_storage(template_name: &str, storage: &str) -> String {
+    let file = File {
+        id: 1,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        name: format!("{}.rs", template_name),
+        relative_path: "crates/common/src/database/".into(),
+        group: "base".into(),
+        template_name: template_name.into(),
+        generated_code: None,
+        status: FileStatus::New,
+        nature: Default::default(),
+        feature: None,
+        all_features: false,
+        entity: None,
+        all_entities: true,
+        use_case: None,
+        all_use_cases: false,
+        field: None,
+    };
+
+    let global = Global {
+        id: 50,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        language: "rust".to_string(),
+        application_name: "App".to_string(),
+        organisation_name: "Org".to_string(),
+        organisation_domain: "org.com".to_string(),
+        prefix_path: "".to_string(),
+        storage: storage.to_string(),
+        templates_path: String::new(),
+    };
+
+    let entity = Entity {
+        id: 10,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        name: "Order".into(),
+        only_for_heritage: false,
+        inherits_from: None,
+        single_model: false,
+        fields: vec![],
+        relationships: vec![],
+        undoable: false,
+        undo_journal: false,
+        source_file: String::new(),
+    };
+
+    let mut entities = IndexMap::new();
+    entities.insert(
+        10,
+        EntityVM {
+            inner: entity,
+            relationships: IndexMap::new(),
+            forward_relationships: IndexMap::new(),
+            backward_relationships: IndexMap::new(),
+            snake_name: "order".into(),
+            pascal_name: "Order".into(),
+            fields: vec![],
+            normal_fields: vec![],
+            owner: None,
+            owner_pascal_name: None,
+            owner_snake_name: None,
+            owner_relationship_field_pascal_name: None,
+            owner_relationship_field_snake_name: None,
+            owner_relationship_type: None,
+            ownership_chain: vec![],
+        },
+    );
+
+    let snapshot = GenerationSnapshot {
+        file: FileVM { inner: file },
+        global: GlobalVM {
+            inner: global,
+            application_kebab_name: "app".to_string(),
+            application_snake_name: "app".to_string(),
+            prefix: "crates".to_string(),
+        },
+        ui: UserInterfaceVM {
+            inner: UserInterface::default(),
+        },
+        system: SystemVM {
+            inner: System::default(),
+        },
+        entities,
+        features: IndexMap::new(),
+        use_cases: IndexMap::new(),
+        dtos: IndexMap::new(),
+    };
+
+    let tera = get_rust_tera();
+    let mut context = Context::new();
+    context.insert("s", &snapshot);
+    tera.render(template_name, &context).unwrap()
+}
+
+#[test]
+fn render_memory_storage_has_no_sqlite() {
+    for template_name in ["database", "db_context", "transactions", "common_cargo"] {
+        let code = render_with_storage(template_name, "memory");
+        assert!(
+            !code.contains("sqlite"),
+            "{} must not reference sqlite",
+            template_name
+        );
+    }
+}
+
+#[test]
+fn render_sqlite_storage_wires_sqlite_store() {
+    let code = render_with_storage("sqlite_store", "sqlite");
+    // Table names are quoted, entity names may be SQL keywords
+    assert!(code.contains(r#"CREATE TABLE IF NOT EXISTS "order""#));
+    assert!(code.contains("*store.orders.write().unwrap() = load_table(&connection, \"order\")?;"));
+    assert!(code.contains(r#""ROLLBACK TO sp_{0}; RELEASE sp_{0}""#));
+
+    let code = render_with_storage("database", "sqlite");
+    assert!(code.contains("pub mod sqlite_store;"));
+
+    let code = render_with_storage("db_context", "sqlite");
+    assert!(code.contains("pub fn open("));
+
+    let code = render_with_storage("transactions", "sqlite");
+    assert!(code.contains("sqlite.begin()?;"));
+    assert!(code.contains("self.sqlite.commit(&self.store)?;"));
+    assert!(code.contains("self.sqlite.create_savepoint(&self.store, savepoint)?;"));
+    assert!(code.contains("self.sqlite.restore_savepoint(&self.store, savepoint)?;"));
+
+    let code = render_with_storage("common_cargo", "sqlite");
+    assert!(code.contains("rusqlite"));
+}
@@ -5,7 +5,8 @@ use common::direct_access::feature::FeatureRelationshipField;
 use common::direct_access::system::SystemRelationshipField;
 use common::direct_access::workspace::WorkspaceRelationshipField;
 use common::entities::UserInterface;
-use common::entities::{Entity, FileNature};
+use common::entities::{CustomFile, Entity, FileNature};
+use common::generator::custom_files;
 use common::generator::file_list_builder::FileListBuilder;
 use common::types::EntityId;
 use common::{
@@ -17,6 +18,7 @@ pub trait FillRustFilesUnitOfWorkFactoryTrait {
     fn create(&self) -> Box<dyn FillRustFilesUnitOfWorkTrait>;
 }
 
+#[macros::uow_trait]
 #[macros::uow_action(entity = "Root", action = "GetAll")]
 #[macros::uow_action(entity = "Root", action = "GetRelationship")]
 #[macros::uow_action(entity = "Workspace", action = "GetRelationship")]
@@ -30,6 +32,7 @@ pub trait FillRustFilesUnitOfWorkFactoryTrait {
 #[macros::uow_action(entity = "Feature", action = "GetMulti")]
 #[macros::uow_action(entity = "Feature", action = "GetRelationship")]
 #[macros::uow_action(entity = "UseCase", action = "GetMulti")]
+#[macros::uow_action(entity = "CustomFile", action = "GetMulti")]
 #[macros::uow_action(entity = "File", action = "CreateOrphan")]
 #[macros::uow_action(entity = "File", action = "CreateOrphanMulti")]
 #[macros::uow_action(entity = "File", action = "RemoveMulti")]
@@ -115,14 +118,15 @@ impl FillRustFilesUseCase {
             uow.remove_file_multi(&all_previous_files)?;
         }
 
-        b.add(
+        let f = b.add(
             "Cargo.toml",
             "",
             "base",
             "root_cargo",
             FileNature::Aggregate,
-        )
-        .all_features = true;
+        );
+        f.all_features = true;
+        f.all_entities = true; // rust_decimal dependency for decimal fields
 
         b.add(
             "Cargo.toml",
@@ -130,7 +134,8 @@ impl FillRustFilesUseCase {
             "base",
             "common_cargo",
             FileNature::Infrastructure,
-        );
+        )
+        .all_entities = true; // regex dependency for field patterns
 
         b.add(
             "lib.rs",
@@ -223,6 +228,17 @@ impl FillRustFilesUseCase {
         )
         .all_entities = true;
 
+        if global.storage == "sqlite" {
+            b.add(
+                "sqlite_store.rs",
+                format!("{}/common/src/database/", prefix),
+                "base",
+                "sqlite_store",
+                FileNature::Infrastructure,
+            )
+            .all_entities = true;
+        }
+
         b.add(
             "transactions.rs",
             format!("{}/common/src/database/", prefix),
@@ -255,6 +271,15 @@ impl FillRustFilesUseCase {
             FileNature::Infrastructure,
         );
 
+        b.add(
+            "journal_tests.rs",
+            format!("{}/common/tests/", prefix),
+            "base",
+            "journal_tests",
+            FileNature::Infrastructure,
+        )
+        .all_entities = true;
+
         // direct access entities
 
         b.add(
@@ -293,7 +318,8 @@ impl FillRustFilesUseCase {
             "entities",
             "direct_access_cargo",
             FileNature::Infrastructure,
-        );
+        )
+        .all_entities = true; // rust_decimal and serde_json dependencies for field types
 
         b.add(
             "lib.rs",
@@ -581,6 +607,14 @@ impl FillRustFilesUseCase {
             FileNature::Infrastructure,
         );
 
+        b.add(
+            "uow_registry.rs",
+            format!("{}/macros/src/", prefix),
+            "base",
+            "macros_uow_registry",
+            FileNature::Infrastructure,
+        );
+
         // Frontend crate (always generated)
         {
             let relative_path = format!("{}/frontend/", prefix);
@@ -616,13 +650,17 @@ impl FillRustFilesUseCase {
                 FileNature::Infrastructure,
             );
 
-            b.add(
-                "event_hub_client.rs",
-                relative_path_src.clone(),
-                "frontend",
-                "frontend_event_hub_client",
-                FileNature::Infrastructure,
-            );
+            {
+                let f = b.add(
+                    "event_hub_client.rs",
+                    relative_path_src.clone(),
+                    "frontend",
+                    "frontend_event_hub_client",
+                    FileNature::Infrastructure,
+                );
+                f.all_features = true;
+                f.all_entities = true;
+            }
 
             {
                 let f = b.add(
@@ -694,21 +732,29 @@ impl FillRustFilesUseCase {
         }
 
         if ui.rust_cli {
-            b.add(
-                "Cargo.toml",
-                format!("{}/cli/", prefix),
-                "cli",
-                "cli_cargo",
-                FileNature::Scaffold,
-            );
+            {
+                let f = b.add(
+                    "Cargo.toml",
+                    format!("{}/cli/", prefix),
+                    "cli",
+                    "cli_cargo",
+                    FileNature::Scaffold,
+                );
+                f.all_features = true;
+                f.all_entities = true; // chrono, uuid and rust_decimal for field arguments
+            }
 
-            b.add(
-                "main.rs",
-                format!("{}/cli/src/", prefix),
-                "cli",
-                "cli_main",
-                FileNature::Scaffold,
-            );
+            {
+                let f = b.add(
+                    "main.rs",
+                    format!("{}/cli/src/", prefix),
+                    "cli",
+                    "cli_main",
+                    FileNature::Scaffold,
+                );
+                f.all_features = true;
+                f.all_entities = true;
+            }
         }
 
         if ui.rust_slint {
@@ -739,21 +785,68 @@ impl FillRustFilesUseCase {
                 FileNature::Scaffold,
             );
 
-            b.add(
-                "app.slint",
-                format!("{}/slint_ui/ui/", prefix),
-                "slint",
-                "slint_app",
-                FileNature::Scaffold,
-            );
+            {
+                let f = b.add(
+                    "views.rs",
+                    relative_path.clone(),
+                    "slint",
+                    "slint_views",
+                    FileNature::Scaffold,
+                );
+                f.all_features = true;
+                f.all_entities = true;
+            }
 
-            b.add(
-                "globals.slint",
-                format!("{}/slint_ui/ui/", prefix),
-                "slint",
-                "slint_globals",
-                FileNature::Scaffold,
-            );
+            {
+                let f = b.add(
+                    "app.slint",
+                    format!("{}/slint_ui/ui/", prefix),
+                    "slint",
+                    "slint_app",
+                    FileNature::Scaffold,
+                );
+                f.all_features = true;
+                f.all_entities = true;
+            }
+
+            {
+                let f = b.add(
+                    "globals.slint",
+                    format!("{}/slint_ui/ui/", prefix),
+                    "slint",
+                    "slint_globals",
+                    FileNature::Scaffold,
+                );
+                f.all_features = true;
+                f.all_entities = true;
+            }
+
+            // One list/detail view per entity, its Slint component and the Rust wiring
+            for entity in &entities {
+                let entity = entity.as_ref().ok_or(anyhow!("Entity not found"))?;
+                if entity.only_for_heritage {
+                    continue;
+                }
+                let snake_name = heck::AsSnakeCase(&entity.name).to_string();
+
+                b.add(
+                    format!("{}_view.rs", snake_name),
+                    format!("{}/slint_ui/src/views/", prefix),
+                    "slint",
+                    "slint_entity_view",
+                    FileNature::Scaffold,
+                )
+                .entity = Some(entity.id);
+
+                b.add(
+                    format!("{}_view.slint", snake_name),
+                    format!("{}/slint_ui/ui/views/", prefix),
+                    "slint",
+                    "slint_entity_view_ui",
+                    FileNature::Scaffold,
+                )
+                .entity = Some(entity.id);
+            }
         }
 
         // Mobile bridge crate (generated when rust_ios or rust_android is enabled)
@@ -769,6 +862,7 @@ impl FillRustFilesUseCase {
                     FileNature::Aggregate,
                 );
                 f.all_features = true;
+                f.all_entities = true;
             }
 
             b.add(
@@ -809,13 +903,17 @@ impl FillRustFilesUseCase {
                 FileNature::Infrastructure,
             );
 
-            b.add(
-                "custom_types.rs",
-                relative_path_src.clone(),
-                "mobile_bridge",
-                "mobile_bridge_custom_types",
-                FileNature::Infrastructure,
-            );
+            {
+                let f = b.add(
+                    "custom_types.rs",
+                    relative_path_src.clone(),
+                    "mobile_bridge",
+                    "mobile_bridge_custom_types",
+                    FileNature::Infrastructure,
+                );
+                f.all_features = true;
+                f.all_entities = true;
+            }
 
             b.add(
                 "errors.rs",
@@ -1007,6 +1105,36 @@ impl FillRustFilesUseCase {
             }
         }
 
+        // custom files declared in the manifest
+        let custom_files = uow
+            .get_workspace_relationship(&workspace_id, &WorkspaceRelationshipField::CustomFiles)?;
+        let custom_files = uow
+            .get_custom_file_multi(&custom_files)?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<CustomFile>>();
+        if !custom_files.is_empty() {
+            let entities = entities.into_iter().flatten().collect::<Vec<Entity>>();
+            let mut features_with_use_cases = vec![];
+            for feature in features.into_iter().flatten() {
+                let use_cases =
+                    uow.get_feature_relationship(&feature.id, &FeatureRelationshipField::UseCases)?;
+                let use_cases = uow
+                    .get_use_case_multi(&use_cases)?
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<UseCase>>();
+                features_with_use_cases.push((feature, use_cases));
+            }
+            custom_files::add_custom_files(
+                &mut b,
+                &custom_files,
+                &prefix,
+                &entities,
+                &features_with_use_cases,
+            );
+        }
+
         let files = if dto.only_list_already_existing {
             b.build_filtered(|file| {
                 let full_path = format!("{}{}", file.relative_path, file.name);
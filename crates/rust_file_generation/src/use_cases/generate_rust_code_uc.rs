@@ -1,5 +1,5 @@
 use crate::use_cases::common::rust_code_generator::{
-    GenerationReadOps, SnapshotBuilder, generate_code_with_snapshot,
+    GenerationReadOps, SnapshotBuilder, generate_code_with_snapshot, get_tera_for_workspace,
 };
 use crate::use_cases::common::rust_formatter::rustfmt_string;
 use crate::{GenerateRustCodeDto, GenerateRustCodeReturnDto};
@@ -31,9 +31,10 @@ impl GenerateRustCodeUseCase {
         let uow_ref: &dyn GenerationReadOps = &*uow;
         let (snapshot, _from_cache) =
             SnapshotBuilder::for_file_id(uow_ref, dto.file_id, &Vec::new())?;
+        let tera = get_tera_for_workspace(uow_ref)?;
         uow.end_transaction()?;
 
-        let generated_code = generate_code_with_snapshot(&snapshot)?;
+        let generated_code = generate_code_with_snapshot(&tera, &snapshot)?;
 
         let formatted_code = rustfmt_string(generated_code.as_str(), None);
 
@@ -54,7 +54,7 @@ pub enum Commands {
     Docs(DocsArgs),
 
     /// Upgrade manifest to the latest schema version
-    Upgrade,
+    Upgrade(UpgradeArgs),
 
     /// LLM Prompt
     Prompt(PromptArgs),
@@ -62,6 +62,15 @@ pub enum Commands {
     /// Show unified diff between generated and on-disk file
     Diff(DiffArgs),
 
+    /// Inspect, eject and check project-local template overrides
+    Templates(TemplatesArgs),
+
+    /// Regenerate in memory each time the manifest or the templates change
+    Watch(WatchArgs),
+
+    /// Language server for qleany.yaml over stdio
+    Lsp,
+
     /// Run Slint GUI to edit manifests
     Gui,
 }
@@ -148,6 +157,18 @@ pub struct CheckArgs {
     /// List all checked rules instead of running validation
     #[arg(long)]
     pub rules: bool,
+
+    /// Output format of the diagnostics
+    #[arg(short, long, value_enum, default_value = "plain")]
+    pub format: CheckFormat,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum CheckFormat {
+    Plain,
+    Json,
+    /// SARIF 2.1.0, for code scanning and pull request annotations
+    Sarif,
 }
 
 // ─────────────────────────────────────────────────────────────
@@ -205,6 +226,10 @@ pub struct ListArgs {
     /// Output format
     #[arg(short, long, value_enum, default_value = "plain")]
     pub format: OutputFormat,
+
+    /// Directory of project-local templates, overriding the manifest's templates_path
+    #[arg(long, value_name = "DIR")]
+    pub templates: Option<PathBuf>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
@@ -255,6 +280,14 @@ pub struct GenerateArgs {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Three-way merge files edited since their last generation instead of overwriting them
+    #[arg(long)]
+    pub merge: bool,
+
+    /// Directory of project-local templates, overriding the manifest's templates_path
+    #[arg(long, value_name = "DIR")]
+    pub templates: Option<PathBuf>,
+
     /// Write all files (all statuses + all natures)
     #[arg(long)]
     pub all: bool,
@@ -451,6 +484,17 @@ pub enum DocsTarget {
     MobileBridgeDevelopment,
 }
 
+// ─────────────────────────────────────────────────────────────
+// UPGRADE
+// ─────────────────────────────────────────────────────────────
+
+#[derive(Args)]
+pub struct UpgradeArgs {
+    /// Print the changes and the resulting diff without writing the manifest
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
 // ─────────────────────────────────────────────────────────────
 // Prompt
 // ─────────────────────────────────────────────────────────────
@@ -478,6 +522,64 @@ pub struct PromptArgs {
 pub struct DiffArgs {
     /// File path (relative to output) or numeric file ID from `list files`
     pub target: String,
+
+    /// Directory of project-local templates, overriding the manifest's templates_path
+    #[arg(long, value_name = "DIR")]
+    pub templates: Option<PathBuf>,
+}
+
+// ─────────────────────────────────────────────────────────────
+// TEMPLATES
+// ─────────────────────────────────────────────────────────────
+
+#[derive(Args)]
+pub struct TemplatesArgs {
+    /// What to do with the templates
+    #[command(subcommand)]
+    pub action: TemplatesAction,
+
+    /// Directory of project-local templates, overriding the manifest's templates_path
+    #[arg(long, value_name = "DIR", global = true)]
+    pub templates: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum TemplatesAction {
+    /// List the built-in templates, marking the overridden ones
+    List,
+
+    /// Copy built-in template(s) to the templates directory for customisation
+    Eject {
+        /// Template name(s), as shown by `templates list`
+        #[arg(required = true)]
+        names: Vec<String>,
+
+        /// Overwrite existing overrides
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Report overrides whose built-in template changed upstream since they were ejected
+    Check,
+}
+
+// ─────────────────────────────────────────────────────────────
+// WATCH
+// ─────────────────────────────────────────────────────────────
+
+#[derive(Args)]
+pub struct WatchArgs {
+    /// Write the files that changed if their content on disk is still the previous generation
+    #[arg(long)]
+    pub write: bool,
+
+    /// Polling interval in milliseconds
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    pub interval: u64,
+
+    /// Directory of project-local templates, overriding the manifest's templates_path
+    #[arg(long, value_name = "DIR")]
+    pub templates: Option<PathBuf>,
 }
 
 /// Run the CLI with the given application context.
@@ -510,7 +612,7 @@ pub fn run_cli(app_context: &Arc<AppContext>) -> Option<()> {
                 return None;
             }
             let path = manifest_path.expect("Check requires a manifest");
-            cli_handlers::check::execute(app_context, &path, &output)
+            cli_handlers::check::execute(app_context, &path, &args, &output)
         }
         Commands::List(args) => {
             let path = manifest_path.expect("List requires a manifest");
@@ -529,9 +631,9 @@ pub fn run_cli(app_context: &Arc<AppContext>) -> Option<()> {
             cli_handlers::export::execute(app_context, &path, &args, &output)
         }
         Commands::Docs(args) => cli_handlers::docs::execute(app_context, &args, &output),
-        Commands::Upgrade => {
+        Commands::Upgrade(args) => {
             let path = manifest_path.expect("Upgrade requires a manifest");
-            cli_handlers::upgrade::execute(app_context, &path, &output)
+            cli_handlers::upgrade::execute(app_context, &path, &args, &output)
         }
         Commands::Prompt(args) => {
             let path = manifest_path.expect("Prompt requires a manifest");
@@ -541,7 +643,16 @@ pub fn run_cli(app_context: &Arc<AppContext>) -> Option<()> {
             let path = manifest_path.expect("Diff requires a manifest");
             cli_handlers::diff::execute(app_context, &path, &args, &output)
         }
+        Commands::Templates(args) => {
+            let path = manifest_path.expect("Templates requires a manifest");
+            cli_handlers::templates::execute(app_context, &path, &args, &output)
+        }
+        Commands::Watch(args) => {
+            let path = manifest_path.expect("Watch requires a manifest");
+            cli_handlers::watch::execute(app_context, &path, &args, &output)
+        }
         Commands::Demo(args) => cli_handlers::demo::execute(app_context, &args, &output),
+        Commands::Lsp => cli_handlers::lsp::execute(app_context),
         Commands::Gui => return Some(()),
     };
 
@@ -557,10 +668,11 @@ pub fn run_cli(app_context: &Arc<AppContext>) -> Option<()> {
 
 /// Resolves the manifest path from CLI arguments or discovers it in the current directory.
 fn resolve_manifest_path(explicit: &Option<PathBuf>, command: &Commands) -> Option<PathBuf> {
-    // New, Demo, and Doc commands don't need an existing manifest
+    // New, Demo, and Doc commands don't need an existing manifest, the language
+    // server gets the manifests from the editor
     if matches!(
         command,
-        Commands::New(_) | Commands::Demo(_) | Commands::Docs(_)
+        Commands::New(_) | Commands::Demo(_) | Commands::Docs(_) | Commands::Lsp
     ) {
         return None;
     }
@@ -6,7 +6,10 @@ pub mod docs;
 pub mod export;
 pub mod generate;
 pub mod list;
+pub mod lsp;
 pub mod new;
 pub mod prompt;
 pub mod show;
+pub mod templates;
 pub mod upgrade;
+pub mod watch;
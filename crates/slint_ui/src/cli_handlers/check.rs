@@ -1,14 +1,19 @@
 use crate::app_context::AppContext;
-use crate::cli::OutputContext;
-use crate::cli_handlers::common::run_checks;
+use crate::cli::{CheckArgs, CheckFormat, OutputContext};
+use crate::cli_handlers::common::{get_target_language, run_checks, warn_stale_template_overrides};
 use anyhow::Result;
-use handling_manifest::handling_manifest_controller;
+use handling_manifest::{
+    CheckDiagnosticDto, CheckReturnDto, CheckRuleDto, handling_manifest_controller,
+};
 use std::path::Path;
 use std::sync::Arc;
 
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
 pub fn execute(
     app_context: &Arc<AppContext>,
     manifest_path: &Path,
+    args: &CheckArgs,
     output: &OutputContext,
 ) -> Result<()> {
     output.verbose(&format!("Validating {}", manifest_path.display()));
@@ -18,16 +23,156 @@ pub fn execute(
     };
 
     // Load validates the manifest structure
-    handling_manifest_controller::load(&app_context.db_context, &app_context.event_hub, &load_dto)?;
+    let loaded = handling_manifest_controller::load(
+        &app_context.db_context,
+        &app_context.event_hub,
+        &load_dto,
+    );
+
+    if args.format != CheckFormat::Plain {
+        // A manifest that does not load is reported like the other diagnostics
+        let check_result = match loaded {
+            Ok(_) => handling_manifest_controller::check(
+                &app_context.db_context,
+                &app_context.event_hub,
+            )?,
+            Err(error) => handling_manifest_controller::get_load_failure_report(&error),
+        };
+        return print_diagnostics(manifest_path, &check_result, args.format);
+    }
+    loaded?;
 
     // Run semantic checks
     run_checks(app_context, output)?;
 
+    // Template overrides missing upstream changes are worth a warning, not a failure
+    let target_language = get_target_language(app_context)?;
+    warn_stale_template_overrides(app_context, manifest_path, &target_language, output)?;
+
     output.success("Manifest is valid");
 
     Ok(())
 }
 
+/// Print the diagnostics as JSON or SARIF on stdout. Fails like the plain
+/// output when there are critical errors, once the report is printed.
+fn print_diagnostics(
+    manifest_path: &Path,
+    check_result: &CheckReturnDto,
+    format: CheckFormat,
+) -> Result<()> {
+    let uri = artifact_uri(manifest_path);
+
+    let report = match format {
+        CheckFormat::Sarif => sarif_report(
+            manifest_path,
+            &check_result.diagnostics,
+            &handling_manifest_controller::get_check_rules(),
+        ),
+        _ => serde_json::json!({
+            "manifest": uri,
+            "valid": check_result.critical_errors.is_empty(),
+            "critical_errors": check_result.critical_errors.len(),
+            "warnings": check_result.warnings.len(),
+            "diagnostics": check_result.diagnostics,
+        }),
+    };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    if !check_result.critical_errors.is_empty() {
+        anyhow::bail!(
+            "Manifest has {} critical error(s)",
+            check_result.critical_errors.len()
+        );
+    }
+    Ok(())
+}
+
+/// The manifest path as CI tools expect it: relative to the working directory
+/// when possible, with forward slashes.
+fn artifact_uri(manifest_path: &Path) -> String {
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| manifest_path.strip_prefix(cwd).ok())
+        .unwrap_or(manifest_path);
+    let uri = relative.to_string_lossy().replace('\\', "/");
+    uri.strip_prefix("./").unwrap_or(&uri).to_string()
+}
+
+fn sarif_report(
+    manifest_path: &Path,
+    diagnostics: &[CheckDiagnosticDto],
+    rules: &[CheckRuleDto],
+) -> serde_json::Value {
+    let sarif_level = |severity: &str| {
+        if severity == "critical" {
+            "error"
+        } else {
+            "warning"
+        }
+    };
+
+    let sarif_rules: Vec<_> = rules
+        .iter()
+        .map(|rule| {
+            serde_json::json!({
+                "id": rule.id,
+                "shortDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": sarif_level(&rule.severity) },
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            // Diagnostics of included files point to them
+            let uri = match &diagnostic.file {
+                Some(file) => artifact_uri(&manifest_path.with_file_name(file)),
+                None => artifact_uri(manifest_path),
+            };
+            let mut physical_location = serde_json::json!({
+                "artifactLocation": { "uri": uri },
+            });
+            if let Some(line) = diagnostic.line {
+                physical_location["region"] = serde_json::json!({
+                    "startLine": line,
+                    "startColumn": diagnostic.column.unwrap_or(1),
+                });
+            }
+            let mut result = serde_json::json!({
+                "ruleId": diagnostic.rule,
+                "level": sarif_level(&diagnostic.severity),
+                "message": { "text": diagnostic.message },
+                "locations": [{
+                    "physicalLocation": physical_location,
+                    "logicalLocations": [{ "fullyQualifiedName": diagnostic.location }],
+                }],
+            });
+            if let Some(index) = rules.iter().position(|rule| rule.id == diagnostic.rule) {
+                result["ruleIndex"] = serde_json::json!(index);
+            }
+            result
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "qleany",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/jacquetc/qleany",
+                    "rules": sarif_rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
 pub fn list_rules(output: &OutputContext) {
     let rules = handling_manifest_controller::get_check_rules();
 
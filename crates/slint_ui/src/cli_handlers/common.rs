@@ -1,8 +1,12 @@
 use crate::app_context::AppContext;
 use crate::cli::{LanguageOption, OutputContext};
 use anyhow::{Result, bail};
+use common::generator::template_overrides::{self, StaleReason};
+use cpp_qt_file_generation::cpp_qt_file_generation_controller;
 use handling_manifest::handling_manifest_controller;
+use rust_file_generation::rust_file_generation_controller;
 use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Check that stdin is a terminal. Call this before any interactive prompt
@@ -65,17 +69,159 @@ pub fn get_target_language(app_context: &Arc<AppContext>) -> Result<TargetLangua
     })
 }
 
+/// Names of the built-in templates of the target language.
+pub fn builtin_template_names(target_language: &TargetLanguage) -> Vec<String> {
+    match target_language {
+        TargetLanguage::Rust => rust_file_generation::builtin_template_names(),
+        TargetLanguage::CppQt => cpp_qt_file_generation::builtin_template_names(),
+    }
+}
+
+/// Content of a built-in template of the target language.
+pub fn builtin_template(target_language: &TargetLanguage, name: &str) -> Option<&'static str> {
+    match target_language {
+        TargetLanguage::Rust => rust_file_generation::builtin_template(name),
+        TargetLanguage::CppQt => cpp_qt_file_generation::builtin_template(name),
+    }
+}
+
+/// Point the loaded manifest to the templates directory given with `--templates`,
+/// in place of its own `templates_path`. Does nothing if the flag is absent.
+pub fn apply_templates_override(
+    app_context: &Arc<AppContext>,
+    templates: Option<&Path>,
+) -> Result<()> {
+    use direct_access::{UpdateGlobalDto, global_controller};
+
+    let Some(templates) = templates else {
+        return Ok(());
+    };
+    if !templates.is_dir() {
+        bail!("Templates directory not found: {}", templates.display());
+    }
+    let templates = std::path::absolute(templates)?;
+
+    let global_dto = global_controller::get_all(&app_context.db_context)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No global configuration found"))?;
+    crate::commands::global_commands::update_global(
+        app_context,
+        None,
+        &UpdateGlobalDto {
+            templates_path: templates.to_string_lossy().to_string(),
+            ..UpdateGlobalDto::from(global_dto)
+        },
+    )?;
+    Ok(())
+}
+
+/// Templates directory of the loaded manifest, resolved against the manifest folder.
+/// Returns `None` when the manifest has no `templates_path`.
+pub fn get_templates_dir(
+    app_context: &Arc<AppContext>,
+    manifest_path: &Path,
+) -> Result<Option<PathBuf>> {
+    use direct_access::global_controller;
+
+    let global_dto = global_controller::get_all(&app_context.db_context)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No global configuration found"))?;
+    let manifest_dir = manifest_path.parent().unwrap_or(Path::new("."));
+    Ok(template_overrides::resolve_templates_dir(
+        manifest_dir,
+        &global_dto.templates_path,
+    ))
+}
+
+/// Warn about the template overrides whose built-in counterpart changed or
+/// disappeared since they were ejected. Returns the number of such overrides.
+pub fn warn_stale_template_overrides(
+    app_context: &Arc<AppContext>,
+    manifest_path: &Path,
+    target_language: &TargetLanguage,
+    output: &OutputContext,
+) -> Result<usize> {
+    let Some(templates_dir) = get_templates_dir(app_context, manifest_path)? else {
+        return Ok(0);
+    };
+    let overrides = template_overrides::load_template_overrides(&templates_dir)?;
+    let stale = template_overrides::find_stale_overrides(&overrides, |name| {
+        builtin_template(target_language, name)
+    });
+
+    for s in &stale {
+        output.warn(&format!(
+            "Template override '{}' ({}) is out of date: {}",
+            s.name,
+            s.path.display(),
+            match s.reason {
+                StaleReason::BuiltinChanged => "the built-in template changed since it was ejected",
+                StaleReason::BuiltinRemoved => "the built-in template no longer exists",
+            }
+        ));
+    }
+
+    Ok(stale.len())
+}
+
+/// Warn about the user regions of the files on disk that the last fill_code
+/// operation could not re-inject because their anchor no longer exists.
+pub fn warn_orphaned_user_regions(
+    app_context: &Arc<AppContext>,
+    target_language: &TargetLanguage,
+    operation_id: &str,
+    output: &OutputContext,
+) -> Result<()> {
+    let long_op_manager = app_context
+        .long_operation_manager
+        .lock()
+        .map_err(|e| anyhow::anyhow!("Failed to acquire lock on long operation manager: {e}"))?;
+
+    let orphaned_user_regions = match target_language {
+        TargetLanguage::Rust => {
+            rust_file_generation_controller::get_fill_code_in_rust_files_result(
+                &long_op_manager,
+                operation_id,
+            )?
+            .map(|result| result.orphaned_user_regions)
+        }
+        TargetLanguage::CppQt => {
+            cpp_qt_file_generation_controller::get_fill_code_in_cpp_qt_files_result(
+                &long_op_manager,
+                operation_id,
+            )?
+            .map(|result| result.orphaned_user_regions)
+        }
+    }
+    .unwrap_or_default();
+
+    if !orphaned_user_regions.is_empty() {
+        output.warn(&format!(
+            "{} user region(s) no longer match the generated code and will be lost on overwrite:",
+            orphaned_user_regions.len()
+        ));
+        for region in &orphaned_user_regions {
+            output.warn(&format!("  {}", region));
+        }
+    }
+
+    Ok(())
+}
+
 /// Run semantic checks on the loaded manifest. Prints warnings/errors and
 /// returns an error if any critical errors are found.
 pub fn run_checks(app_context: &Arc<AppContext>, output: &OutputContext) -> Result<()> {
     let check_result =
         handling_manifest_controller::check(&app_context.db_context, &app_context.event_hub)?;
 
-    for warning in &check_result.warnings {
-        output.warn(&format!("Warning: {}", warning));
-    }
-    for error in &check_result.critical_errors {
-        eprintln!("✗ Error: {}", error);
+    for diagnostic in &check_result.diagnostics {
+        if diagnostic.severity == "critical" {
+            eprintln!("✗ Error: {}", describe_diagnostic(diagnostic));
+        } else {
+            output.warn(&format!("Warning: {}", describe_diagnostic(diagnostic)));
+        }
     }
 
     if !check_result.critical_errors.is_empty() {
@@ -88,6 +234,22 @@ pub fn run_checks(app_context: &Arc<AppContext>, output: &OutputContext) -> Resu
     Ok(())
 }
 
+/// The message of a check diagnostic, followed by its rule and its position in the manifest.
+fn describe_diagnostic(diagnostic: &handling_manifest::CheckDiagnosticDto) -> String {
+    let file = diagnostic
+        .file
+        .as_ref()
+        .map(|file| format!("{}, ", file))
+        .unwrap_or_default();
+    match (diagnostic.line, diagnostic.column) {
+        (Some(line), Some(column)) => format!(
+            "{} [{}, {}line {}, column {}]",
+            diagnostic.message, diagnostic.rule, file, line, column
+        ),
+        _ => format!("{} [{}]", diagnostic.message, diagnostic.rule),
+    }
+}
+
 pub fn detect_and_warn_of_missing_formatters(
     target_language: &TargetLanguage,
     output: &OutputContext,
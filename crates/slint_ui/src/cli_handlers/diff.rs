@@ -1,6 +1,9 @@
 use crate::app_context::AppContext;
 use crate::cli::{DiffArgs, OutputContext};
-use crate::cli_handlers::common::{TargetLanguage, get_target_language, run_checks};
+use crate::cli_handlers::common::{
+    TargetLanguage, apply_templates_override, get_target_language, run_checks,
+    warn_orphaned_user_regions,
+};
 use anyhow::Result;
 use common::direct_access::system::SystemRelationshipField;
 use common::long_operation::OperationStatus;
@@ -27,6 +30,7 @@ pub fn execute(
         manifest_path: manifest_path.to_string_lossy().to_string(),
     };
     handling_manifest_controller::load(&app_context.db_context, &app_context.event_hub, &load_dto)?;
+    apply_templates_override(app_context, args.templates.as_deref())?;
     run_checks(app_context, output)?;
 
     let target_language = get_target_language(app_context)?;
@@ -77,6 +81,7 @@ pub fn execute(
     };
 
     poll_long_operation(app_context, &operation_id, output)?;
+    warn_orphaned_user_regions(app_context, &target_language, &operation_id, output)?;
 
     // Step 3: Fill status by comparing generated code vs disk
     output.verbose("Comparing with files on disk...");
@@ -1,13 +1,17 @@
 use crate::app_context::AppContext;
 use crate::cli::{GenerateArgs, GenerateTarget, OutputContext};
-use crate::cli_handlers::common::{TargetLanguage, get_target_language, run_checks};
+use crate::cli_handlers::common::{
+    TargetLanguage, apply_templates_override, get_target_language, run_checks,
+    warn_orphaned_user_regions, warn_stale_template_overrides,
+};
 use anyhow::{Result, bail};
 use common::direct_access::system::SystemRelationshipField;
 use common::entities::{FileNature, FileStatus};
+use common::generator::baseline_store;
 use common::long_operation::OperationStatus;
 use cpp_qt_file_generation::cpp_qt_file_generation_controller;
 use direct_access::{FileDto, file_controller, system_controller};
-use file_generation_shared_steps::file_generation_shared_steps_controller;
+use file_generation_shared_steps::{MergeFileDto, file_generation_shared_steps_controller};
 use handling_manifest::handling_manifest_controller;
 use rust_file_generation::rust_file_generation_controller;
 use std::path::{Path, PathBuf};
@@ -18,7 +22,8 @@ use std::time::Duration;
 const ROOT_SYSTEM_ID: u64 = 1;
 
 /// Resolve status flags into the set of FileStatus values to include.
-/// Default (no flags): Modified + New.
+/// Default (no flags): Modified + New. Merged and Conflict files are modified
+/// files too, and follow the Modified flag.
 fn resolve_status_filter(
     all: bool,
     all_status: bool,
@@ -29,6 +34,8 @@ fn resolve_status_filter(
     if all || all_status {
         return vec![
             FileStatus::Modified,
+            FileStatus::Merged,
+            FileStatus::Conflict,
             FileStatus::New,
             FileStatus::Unchanged,
             FileStatus::Unknown,
@@ -36,11 +43,18 @@ fn resolve_status_filter(
     }
     if !modified && !new && !unchanged {
         // Default: Modified + New
-        return vec![FileStatus::Modified, FileStatus::New];
+        return vec![
+            FileStatus::Modified,
+            FileStatus::Merged,
+            FileStatus::Conflict,
+            FileStatus::New,
+        ];
     }
     let mut statuses = Vec::new();
     if modified {
         statuses.push(FileStatus::Modified);
+        statuses.push(FileStatus::Merged);
+        statuses.push(FileStatus::Conflict);
     }
     if new {
         statuses.push(FileStatus::New);
@@ -91,9 +105,11 @@ pub fn execute(
         manifest_path: manifest_path.to_string_lossy().to_string(),
     };
     handling_manifest_controller::load(&app_context.db_context, &app_context.event_hub, &load_dto)?;
+    apply_templates_override(app_context, args.templates.as_deref())?;
     run_checks(app_context, output)?;
 
     let target_language = get_target_language(app_context)?;
+    warn_stale_template_overrides(app_context, manifest_path, &target_language, output)?;
 
     crate::cli_handlers::common::detect_and_warn_of_missing_formatters(
         &target_language,
@@ -147,6 +163,7 @@ pub fn execute(
     };
 
     poll_long_operation(app_context, &operation_id, output)?;
+    warn_orphaned_user_regions(app_context, &target_language, &operation_id, output)?;
 
     // Step 3: Fill status by comparing generated code vs disk
     output.verbose("Comparing with files on disk...");
@@ -277,6 +294,8 @@ pub fn execute(
             let prefix = match file.status {
                 FileStatus::New => "[N]",
                 FileStatus::Modified => "[M]",
+                FileStatus::Merged => "[A]",
+                FileStatus::Conflict => "[C]",
                 FileStatus::Unchanged => "[U]",
                 FileStatus::Unknown => "[?]",
             };
@@ -292,9 +311,40 @@ pub fn execute(
         output_path.display()
     ));
 
+    // Baselines only make sense for files written in place, next to the manifest
+    let manifest_dir = manifest_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+    let record_baselines = is_same_dir(&output_path, &manifest_dir);
+
     // Step 7: Write generated_code to disk
     let mut written = 0;
     let mut skipped = 0;
+    let mut merged = 0;
+    let mut conflicted: Vec<String> = Vec::new();
+
+    // Files identical to their generation get a baseline even when not written,
+    // so that later edits to them can be merged
+    if record_baselines {
+        for file in all_files
+            .iter()
+            .filter(|f| f.status == FileStatus::Unchanged)
+        {
+            if let Some(ref code) = file.generated_code
+                && baseline_store::read_baseline(&manifest_dir, &file.relative_path, &file.name)
+                    .is_none()
+            {
+                baseline_store::write_baseline(
+                    &manifest_dir,
+                    &file.relative_path,
+                    &file.name,
+                    code,
+                )?;
+            }
+        }
+    }
 
     for file in &files {
         let Some(ref code) = file.generated_code else {
@@ -313,7 +363,28 @@ pub fn execute(
         std::fs::create_dir_all(&file_path)?;
         file_path = file_path.join(&file.name);
 
-        std::fs::write(&file_path, code)?;
+        let needs_merge =
+            args.merge && matches!(file.status, FileStatus::Merged | FileStatus::Conflict);
+        if needs_merge {
+            let merge_result = file_generation_shared_steps_controller::merge_file(
+                &app_context.db_context,
+                &app_context.event_hub,
+                &MergeFileDto { file_id: file.id },
+            )?;
+            std::fs::write(&file_path, &merge_result.merged_code)?;
+            if merge_result.conflict_count > 0 {
+                conflicted.push(format!("{}{}", file.relative_path, file.name));
+            } else {
+                merged += 1;
+            }
+        } else {
+            std::fs::write(&file_path, code)?;
+        }
+
+        if record_baselines {
+            baseline_store::write_baseline(&manifest_dir, &file.relative_path, &file.name, code)?;
+        }
+
         output.verbose(&format!("  {}{}", file.relative_path, file.name));
         written += 1;
     }
@@ -328,11 +399,24 @@ pub fn execute(
         }
     ));
 
+    if merged > 0 {
+        output.success(&format!("Merged {} files with your edits", merged));
+    }
+    if !conflicted.is_empty() {
+        output.warn(&format!(
+            "{} files have merge conflicts, resolve the conflict markers before building:",
+            conflicted.len()
+        ));
+        for path in &conflicted {
+            output.warn(&format!("  {}", path));
+        }
+    }
+
     Ok(())
 }
 
 /// Polls a long operation until it completes, reporting progress if verbose.
-fn poll_long_operation(
+pub(crate) fn poll_long_operation(
     app_context: &Arc<AppContext>,
     operation_id: &str,
     output: &OutputContext,
@@ -390,3 +474,11 @@ fn determine_output_path(args: &GenerateArgs) -> Result<PathBuf> {
     std::fs::create_dir_all(&path)?;
     Ok(path)
 }
+
+/// Compare two directories, resolving symlinks and relative components when possible.
+fn is_same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
@@ -2,7 +2,10 @@ mod file_tree;
 
 use crate::app_context::AppContext;
 use crate::cli::{ListArgs, ListTarget, OutputContext, OutputFormat};
-use crate::cli_handlers::common::{TargetLanguage, get_target_language, run_checks};
+use crate::cli_handlers::common::{
+    TargetLanguage, apply_templates_override, get_target_language, run_checks,
+    warn_orphaned_user_regions,
+};
 use anyhow::Result;
 use common::direct_access::system::SystemRelationshipField;
 use common::entities::{FileNature, FileStatus};
@@ -21,7 +24,8 @@ use std::time::Duration;
 const ROOT_SYSTEM_ID: u64 = 1;
 
 /// Resolve status flags into the set of FileStatus values to include.
-/// Default (no flags): Modified + New.
+/// Default (no flags): Modified + New. Merged and Conflict files are modified
+/// files too, and follow the Modified flag.
 fn resolve_status_filter(
     all: bool,
     all_status: bool,
@@ -32,6 +36,8 @@ fn resolve_status_filter(
     if all || all_status {
         return vec![
             FileStatus::Modified,
+            FileStatus::Merged,
+            FileStatus::Conflict,
             FileStatus::New,
             FileStatus::Unchanged,
             FileStatus::Unknown,
@@ -39,11 +45,18 @@ fn resolve_status_filter(
     }
     if !modified && !new && !unchanged {
         // Default: Modified + New
-        return vec![FileStatus::Modified, FileStatus::New];
+        return vec![
+            FileStatus::Modified,
+            FileStatus::Merged,
+            FileStatus::Conflict,
+            FileStatus::New,
+        ];
     }
     let mut statuses = Vec::new();
     if modified {
         statuses.push(FileStatus::Modified);
+        statuses.push(FileStatus::Merged);
+        statuses.push(FileStatus::Conflict);
     }
     if new {
         statuses.push(FileStatus::New);
@@ -94,6 +107,7 @@ pub fn execute(
         manifest_path: manifest_path.to_string_lossy().to_string(),
     };
     handling_manifest_controller::load(&app_context.db_context, &app_context.event_hub, &load_dto)?;
+    apply_templates_override(app_context, args.templates.as_deref())?;
     run_checks(app_context, output)?;
 
     match args.target {
@@ -163,6 +177,7 @@ fn list_files(
     };
 
     poll_long_operation(app_context, &operation_id, output)?;
+    warn_orphaned_user_regions(app_context, &target_language, &operation_id, output)?;
 
     // Step 3: Fill status by comparing generated code vs disk
     output.verbose("Comparing with files on disk...");
@@ -259,8 +274,29 @@ fn display_file_list(files: &[&FileDto], args: &ListArgs, output: &OutputContext
     let count_new = files.iter().filter(|f| f.status == FileStatus::New).count();
     let count_modified = files
         .iter()
-        .filter(|f| f.status == FileStatus::Modified)
+        .filter(|f| {
+            matches!(
+                f.status,
+                FileStatus::Modified | FileStatus::Merged | FileStatus::Conflict
+            )
+        })
+        .count();
+    let count_merged = files
+        .iter()
+        .filter(|f| f.status == FileStatus::Merged)
         .count();
+    let count_conflict = files
+        .iter()
+        .filter(|f| f.status == FileStatus::Conflict)
+        .count();
+    let merge_summary = if count_merged + count_conflict > 0 {
+        format!(
+            " ({} auto-mergeable, {} conflicting)",
+            count_merged, count_conflict
+        )
+    } else {
+        String::new()
+    };
     let count_unchanged = files
         .iter()
         .filter(|f| f.status == FileStatus::Unchanged)
@@ -274,6 +310,8 @@ fn display_file_list(files: &[&FileDto], args: &ListArgs, output: &OutputContext
                     let prefix = match file.status {
                         FileStatus::New => "[N]",
                         FileStatus::Modified => "[M]",
+                        FileStatus::Merged => "[A]",
+                        FileStatus::Conflict => "[C]",
                         FileStatus::Unchanged => "[U]",
                         FileStatus::Unknown => "[?]",
                     };
@@ -287,6 +325,8 @@ fn display_file_list(files: &[&FileDto], args: &ListArgs, output: &OutputContext
                     match file.status {
                         FileStatus::New => md.push_str(&format!("**~~[N]~~** **{}**\n", path)),
                         FileStatus::Modified => md.push_str(&format!("*[M]* *{}*\n", path)),
+                        FileStatus::Merged => md.push_str(&format!("*[A]* *{}*\n", path)),
+                        FileStatus::Conflict => md.push_str(&format!("*[C]* *{}*\n", path)),
                         FileStatus::Unchanged => md.push_str(&format!("[U] {}\n", path)),
                         FileStatus::Unknown => md.push_str(&format!("[?] {}\n", path)),
                     }
@@ -300,9 +340,10 @@ fn display_file_list(files: &[&FileDto], args: &ListArgs, output: &OutputContext
                 skin.print_text(&md);
             }
             output.info(&format!(
-                "\n{} new, {} modified, {} unchanged — {} total",
+                "\n{} new, {} modified{}, {} unchanged — {} total",
                 count_new,
                 count_modified,
+                merge_summary,
                 count_unchanged,
                 files.len()
             ));
@@ -323,6 +364,8 @@ fn display_file_list(files: &[&FileDto], args: &ListArgs, output: &OutputContext
                 "count": files.len(),
                 "new": count_new,
                 "modified": count_modified,
+                "merged": count_merged,
+                "conflict": count_conflict,
                 "unchanged": count_unchanged,
             });
             println!("{}", serde_json::to_string_pretty(&wrapper)?);
@@ -334,9 +377,10 @@ fn display_file_list(files: &[&FileDto], args: &ListArgs, output: &OutputContext
                 .collect();
             file_tree::print_file_tree(&paths);
             output.info(&format!(
-                "\n{} new, {} modified, {} unchanged — {} total",
+                "\n{} new, {} modified{}, {} unchanged — {} total",
                 count_new,
                 count_modified,
+                merge_summary,
                 count_unchanged,
                 files.len()
             ));
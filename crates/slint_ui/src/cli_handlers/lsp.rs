@@ -0,0 +1,711 @@
+//! `qleany lsp`: a language server for qleany.yaml, over stdio.
+//!
+//! Diagnostics come from loading the buffer and running the same checks as
+//! `qleany check`; files included by a manifest get the diagnostics of the
+//! saved manifest including them. Completion, go to definition and rename work on a
+//! line-based index of the manifest and its included files, open buffers first,
+//! so they keep working while the buffer doesn't parse.
+
+mod manifest_index;
+mod transport;
+
+use crate::app_context::AppContext;
+use crate::cli_handlers::show::find_entity;
+use anyhow::Result;
+use handling_manifest::handling_manifest_controller;
+use manifest_index::{
+    CompletionContext, FIELD_TYPES, ManifestIndex, Occurrence, RELATIONSHIPS, utf16_len,
+};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// LSP completion item kinds
+const KIND_CLASS: u64 = 7;
+const KIND_MODULE: u64 = 9;
+const KIND_ENUM: u64 = 13;
+const KIND_KEYWORD: u64 = 14;
+
+/// A file of the manifest being edited, with its index.
+struct WorkspaceFile {
+    uri: String,
+    index: ManifestIndex,
+}
+
+struct Server<'a> {
+    app_context: &'a Arc<AppContext>,
+    /// Text of the open documents, by URI
+    documents: HashMap<String, String>,
+}
+
+/// Serve the client on stdin and stdout until it sends `exit`. Nothing else
+/// may be printed on stdout meanwhile.
+pub fn execute(app_context: &Arc<AppContext>) -> Result<()> {
+    let mut input = std::io::stdin().lock();
+    let mut output = std::io::stdout().lock();
+    let mut server = Server {
+        app_context,
+        documents: HashMap::new(),
+    };
+
+    while let Some(message) = transport::read_message(&mut input)? {
+        let method = message["method"].as_str().unwrap_or_default();
+        if method == "exit" {
+            break;
+        }
+        let params = &message["params"];
+        match message.get("id") {
+            // A request
+            Some(id) if !method.is_empty() => {
+                let response = match server.handle_request(method, params) {
+                    Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+                    Err((code, error)) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {"code": code, "message": error},
+                    }),
+                };
+                transport::write_message(&mut output, &response)?;
+            }
+            // A response to one of our requests, we send none
+            Some(_) => {}
+            None => {
+                for notification in server.handle_notification(method, params) {
+                    transport::write_message(&mut output, &notification)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Server<'_> {
+    fn handle_request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": {
+                        "openClose": true,
+                        "change": 1,
+                        "save": {"includeText": false},
+                    },
+                    "completionProvider": {"triggerCharacters": [" ", "[", ","]},
+                    "definitionProvider": true,
+                    "renameProvider": true,
+                    "hoverProvider": true,
+                },
+                "serverInfo": {"name": "qleany", "version": env!("CARGO_PKG_VERSION")},
+            })),
+            "shutdown" => Ok(Value::Null),
+            "textDocument/completion" => Ok(self.completion(params)),
+            "textDocument/definition" => Ok(self.definition(params)),
+            "textDocument/rename" => self.rename(params),
+            "textDocument/hover" => Ok(self.hover(params)),
+            _ => Err((METHOD_NOT_FOUND, format!("Unsupported method: {}", method))),
+        }
+    }
+
+    /// Returns the notifications to send back.
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.clone(), text.to_string());
+            }
+            "textDocument/didChange" => {
+                // Full synchronization: the last change is the whole text
+                let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                else {
+                    return vec![];
+                };
+                self.documents.insert(uri.clone(), text.to_string());
+            }
+            "textDocument/didSave" => {}
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish_diagnostics(&uri, vec![])];
+            }
+            _ => return vec![],
+        }
+        let diagnostics = self.diagnostics(&uri);
+        vec![publish_diagnostics(&uri, diagnostics)]
+    }
+
+    /// Load the buffer, as `qleany check` would load the file, and check it.
+    fn diagnostics(&self, uri: &str) -> Vec<Value> {
+        let Some(text) = self.documents.get(uri) else {
+            return vec![];
+        };
+        let file = match self.load(uri) {
+            Ok(file) => file,
+            Err(e) => {
+                let (line, column) = match e
+                    .downcast_ref::<serde_yml::Error>()
+                    .and_then(|e| e.location())
+                {
+                    Some(location) => (location.line() as u64, location.column() as u64),
+                    None => (1, 1),
+                };
+                return vec![diagnostic(text, line, column, 1, None, &e.to_string())];
+            }
+        };
+        match handling_manifest_controller::check(
+            &self.app_context.db_context,
+            &self.app_context.event_hub,
+        ) {
+            Ok(check) => check
+                .diagnostics
+                .iter()
+                .filter(|d| d.file == file)
+                .map(|d| {
+                    let severity = if d.severity == "critical" { 1 } else { 2 };
+                    diagnostic(
+                        text,
+                        d.line.unwrap_or(1),
+                        d.column.unwrap_or(1),
+                        severity,
+                        Some(&d.rule),
+                        &d.message,
+                    )
+                })
+                .collect(),
+            Err(e) => vec![diagnostic(text, 1, 1, 1, None, &e.to_string())],
+        }
+    }
+
+    /// Load the manifest of the document. A file included by a manifest has no
+    /// `schema` section: the manifest including it is loaded from the disk, and
+    /// its path relative to the manifest is returned.
+    fn load(&self, uri: &str) -> Result<Option<String>> {
+        let path = uri_to_path(uri)
+            .ok_or_else(|| anyhow::anyhow!("Only file:// manifests are supported: {}", uri))?;
+        let text = self
+            .documents
+            .get(uri)
+            .map(String::as_str)
+            .unwrap_or_default();
+
+        if is_manifest(text) {
+            handling_manifest_controller::load_source(
+                &self.app_context.db_context,
+                &self.app_context.event_hub,
+                &handling_manifest::LoadDto {
+                    manifest_path: path.to_string_lossy().to_string(),
+                },
+                text,
+            )?;
+            return Ok(None);
+        }
+
+        let manifest_path = including_manifest(&path)
+            .ok_or_else(|| anyhow::anyhow!("No manifest includes this file"))?;
+        handling_manifest_controller::load(
+            &self.app_context.db_context,
+            &self.app_context.event_hub,
+            &handling_manifest::LoadDto {
+                manifest_path: manifest_path.to_string_lossy().to_string(),
+            },
+        )?;
+        let relative = manifest_path
+            .parent()
+            .and_then(|dir| path.strip_prefix(dir).ok())
+            .map(|relative| {
+                relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            });
+        Ok(relative)
+    }
+
+    /// The document and the other files of its manifest: the manifest itself
+    /// and the files it includes, from their open buffer or else from the disk.
+    /// The document comes first.
+    fn workspace_files(&self, uri: &str) -> Vec<WorkspaceFile> {
+        let Some(text) = self.documents.get(uri) else {
+            return vec![];
+        };
+        let mut files = vec![WorkspaceFile {
+            uri: uri.to_string(),
+            index: ManifestIndex::parse(text),
+        }];
+        let Some(path) = uri_to_path(uri) else {
+            return files;
+        };
+        let manifest_path = if is_manifest(text) {
+            Some(path.clone())
+        } else {
+            including_manifest(&path)
+        };
+        let Some((manifest_path, dir)) = manifest_path
+            .and_then(|manifest| Some((manifest.clone(), manifest.parent()?.to_path_buf())))
+        else {
+            return files;
+        };
+        let included =
+            handling_manifest_controller::included_files(&manifest_path.to_string_lossy())
+                .unwrap_or_default();
+
+        for other in std::iter::once(manifest_path).chain(included.iter().map(|f| dir.join(f))) {
+            if other == path {
+                continue;
+            }
+            let open = self
+                .documents
+                .iter()
+                .find(|(open_uri, _)| uri_to_path(open_uri).as_ref() == Some(&other));
+            let (other_uri, text) = match open {
+                Some((open_uri, text)) => (open_uri.clone(), text.clone()),
+                None => match std::fs::read_to_string(&other) {
+                    Ok(text) => (path_to_uri(&other), text),
+                    Err(_) => continue,
+                },
+            };
+            files.push(WorkspaceFile {
+                uri: other_uri,
+                index: ManifestIndex::parse(&text),
+            });
+        }
+        files
+    }
+
+    fn completion(&self, params: &Value) -> Value {
+        let Some((text, line, character)) = self.position(params) else {
+            return Value::Null;
+        };
+        let line_text = text.lines().nth(line).unwrap_or_default();
+        let prefix = &line_text[..byte_offset(line_text, character)];
+        let Some(context) = ManifestIndex::parse(text).completion_context(line, prefix) else {
+            return Value::Null;
+        };
+
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let files = match context {
+            CompletionContext::FieldType | CompletionContext::Relationship => vec![],
+            _ => self.workspace_files(uri),
+        };
+        let (mut labels, kind): (Vec<&str>, u64) = match context {
+            CompletionContext::EntityName => (
+                files.iter().flat_map(|f| f.index.entity_names()).collect(),
+                KIND_CLASS,
+            ),
+            CompletionContext::EnumName => (
+                files
+                    .iter()
+                    .flat_map(|f| f.index.enum_names.iter().map(String::as_str))
+                    .collect(),
+                KIND_ENUM,
+            ),
+            CompletionContext::FeatureName => (
+                files
+                    .iter()
+                    .enumerate()
+                    // the name being typed is not a feature yet
+                    .flat_map(|(i, f)| f.index.feature_names((i == 0).then_some(line)))
+                    .collect(),
+                KIND_MODULE,
+            ),
+            CompletionContext::FieldType => (FIELD_TYPES.to_vec(), KIND_KEYWORD),
+            CompletionContext::Relationship => (RELATIONSHIPS.to_vec(), KIND_KEYWORD),
+        };
+        let mut seen = std::collections::HashSet::new();
+        labels.retain(|label| seen.insert(*label));
+        let items: Vec<Value> = labels
+            .into_iter()
+            .map(|label| json!({"label": label, "kind": kind}))
+            .collect();
+        json!({"isIncomplete": false, "items": items})
+    }
+
+    fn definition(&self, params: &Value) -> Value {
+        let Some((text, line, character)) = self.position(params) else {
+            return Value::Null;
+        };
+        let index = ManifestIndex::parse(text);
+        let Some(occurrence) = index.entity_at(line, character) else {
+            return Value::Null;
+        };
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        self.workspace_files(uri)
+            .iter()
+            .find_map(|file| {
+                let declaration = file.index.entity_declaration(&occurrence.name)?;
+                Some(json!({"uri": file.uri, "range": range(declaration)}))
+            })
+            .unwrap_or(Value::Null)
+    }
+
+    /// Rename an entity in the document and in the other files of its manifest.
+    fn rename(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let Some((text, line, character)) = self.position(params) else {
+            return Ok(Value::Null);
+        };
+        let new_name = params["newName"].as_str().unwrap_or_default();
+        if !is_pascal_case(new_name) {
+            return Err((
+                INVALID_PARAMS,
+                format!("Entity names must be PascalCase: {}", new_name),
+            ));
+        }
+        let index = ManifestIndex::parse(text);
+        let Some(occurrence) = index.entity_at(line, character) else {
+            return Ok(Value::Null);
+        };
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let files = self.workspace_files(uri);
+        if files
+            .iter()
+            .any(|file| file.index.entity_declaration(new_name).is_some())
+        {
+            return Err((
+                INVALID_PARAMS,
+                format!("Entity {} already exists", new_name),
+            ));
+        }
+        let mut changes = serde_json::Map::new();
+        for file in &files {
+            let edits: Vec<Value> = file
+                .index
+                .entity_occurrences(&occurrence.name)
+                .map(|o| json!({"range": range(o), "newText": new_name}))
+                .collect();
+            if !edits.is_empty() {
+                changes.insert(file.uri.clone(), Value::Array(edits));
+            }
+        }
+        Ok(json!({"changes": changes}))
+    }
+
+    /// What `qleany show entity` prints about the entity under the cursor.
+    fn hover(&self, params: &Value) -> Value {
+        let Some((text, line, character)) = self.position(params) else {
+            return Value::Null;
+        };
+        let index = ManifestIndex::parse(text);
+        let Some(occurrence) = index.entity_at(line, character) else {
+            return Value::Null;
+        };
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        if self.load(uri).is_err() {
+            return Value::Null;
+        }
+        let Ok(Some((entity, fields))) = find_entity(self.app_context, &occurrence.name) else {
+            return Value::Null;
+        };
+
+        let mut markdown = format!("**Entity: {}**\n\n", entity.name);
+        if entity.only_for_heritage {
+            markdown.push_str("(heritage only)\n\n");
+        }
+        markdown.push_str(&format!("undoable: {}\n\n", entity.undoable));
+        markdown.push_str(&format!("Fields ({}):\n", fields.len()));
+        for field in &fields {
+            markdown.push_str(&format!("- `{}`: {:?}\n", field.name, field.field_type));
+        }
+        json!({
+            "contents": {"kind": "markdown", "value": markdown},
+            "range": range(occurrence),
+        })
+    }
+
+    /// The open document and the position of a `textDocument/*` request.
+    fn position(&self, params: &Value) -> Option<(&str, usize, usize)> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let text = self.documents.get(uri)?;
+        let line = params["position"]["line"].as_u64()? as usize;
+        let character = params["position"]["character"].as_u64()? as usize;
+        Some((text, line, character))
+    }
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {"uri": uri, "diagnostics": diagnostics},
+    })
+}
+
+/// A diagnostic from the 1-based position of the manifest to the end of its line.
+fn diagnostic(
+    text: &str,
+    line: u64,
+    column: u64,
+    severity: u64,
+    rule: Option<&str>,
+    message: &str,
+) -> Value {
+    let line = line.saturating_sub(1) as usize;
+    let line_text = text.lines().nth(line).unwrap_or_default();
+    let start: usize = line_text
+        .chars()
+        .take(column.saturating_sub(1) as usize)
+        .map(char::len_utf16)
+        .sum();
+    let end = utf16_len(line_text).max(start);
+    let mut diagnostic = json!({
+        "range": {
+            "start": {"line": line, "character": start},
+            "end": {"line": line, "character": end},
+        },
+        "severity": severity,
+        "source": "qleany",
+        "message": message,
+    });
+    if let Some(rule) = rule {
+        diagnostic["code"] = json!(rule);
+    }
+    diagnostic
+}
+
+fn range(occurrence: &Occurrence) -> Value {
+    json!({
+        "start": {"line": occurrence.line, "character": occurrence.start},
+        "end": {"line": occurrence.line, "character": occurrence.end},
+    })
+}
+
+/// Byte offset in `line` of a position in UTF-16 code units.
+fn byte_offset(line: &str, character: usize) -> usize {
+    let mut units = 0;
+    for (offset, c) in line.char_indices() {
+        if units >= character {
+            return offset;
+        }
+        units += c.len_utf16();
+    }
+    line.len()
+}
+
+/// A manifest has a `schema` section, the files it includes do not.
+fn is_manifest(text: &str) -> bool {
+    text.lines().any(|line| line.starts_with("schema:"))
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// The closest manifest, in the directory of `path` or above, whose `includes`
+/// cover `path`, whatever its name.
+fn including_manifest(path: &Path) -> Option<PathBuf> {
+    path.ancestors().skip(1).find_map(|dir| {
+        let relative = path
+            .strip_prefix(dir)
+            .ok()?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let mut candidates: Vec<PathBuf> = std::fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|candidate| {
+                candidate.is_file()
+                    && candidate != path
+                    && candidate
+                        .extension()
+                        .is_some_and(|extension| extension == "yaml" || extension == "json")
+            })
+            .collect();
+        candidates.sort();
+        candidates.into_iter().find(|candidate| {
+            handling_manifest_controller::included_files(&candidate.to_string_lossy())
+                .is_ok_and(|files| files.contains(&relative))
+        })
+    })
+}
+
+/// Path of a `file://` URI, percent-decoded.
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = path
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    let path = String::from_utf8(decoded).ok()?;
+    // file:///C:/project/qleany.yaml
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if cfg!(windows) && drive.is_ascii_alphabetic() => &path[1..],
+        _ => path.as_str(),
+    };
+    Some(PathBuf::from(path))
+}
+
+/// `file://` URI of a path, percent-encoded.
+fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    // file:///C:/project/qleany.yaml
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_including_manifest() {
+        let dir = std::env::temp_dir().join(format!("qleany_lsp_includes_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("model")).unwrap();
+        std::fs::write(
+            dir.join("app.yaml"),
+            "schema:\n  version: 5\nincludes:\n  - model/*.yaml\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("other.yaml"), "schema:\n  version: 5\n").unwrap();
+        std::fs::write(dir.join("model/car.yaml"), "entities: []\n").unwrap();
+        std::fs::write(dir.join("notes.yaml"), "entities: []\n").unwrap();
+
+        assert_eq!(
+            including_manifest(&dir.join("model/car.yaml")),
+            Some(dir.join("app.yaml"))
+        );
+        assert_eq!(including_manifest(&dir.join("notes.yaml")), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rename_across_files() {
+        let dir = std::env::temp_dir().join(format!("qleany_lsp_rename_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("model")).unwrap();
+        let manifest =
+            "schema:\n  version: 5\nincludes:\n  - model/*.yaml\nentities:\n- name: Car\n";
+        std::fs::write(dir.join("app.yaml"), manifest).unwrap();
+        std::fs::write(
+            dir.join("model/wheel.yaml"),
+            "entities:\n- name: Wheel\n  fields:\n  - name: car\n    type: entity\n    entity: Car\n",
+        )
+        .unwrap();
+        let app_context = Arc::new(AppContext::new());
+        let manifest_uri = path_to_uri(&dir.join("app.yaml"));
+        let mut server = Server {
+            app_context: &app_context,
+            documents: HashMap::new(),
+        };
+        server
+            .documents
+            .insert(manifest_uri.clone(), manifest.to_string());
+
+        let edit = server
+            .rename(&json!({
+                "textDocument": {"uri": manifest_uri},
+                "position": {"line": 5, "character": 9},
+                "newName": "Truck",
+            }))
+            .unwrap();
+        let taken = server.rename(&json!({
+            "textDocument": {"uri": manifest_uri},
+            "position": {"line": 5, "character": 9},
+            "newName": "Wheel",
+        }));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let wheel_uri = path_to_uri(&dir.join("model/wheel.yaml"));
+        assert_eq!(
+            edit["changes"][&manifest_uri],
+            json!([{
+                "range": {"start": {"line": 5, "character": 8}, "end": {"line": 5, "character": 11}},
+                "newText": "Truck",
+            }])
+        );
+        assert_eq!(
+            edit["changes"][&wheel_uri][0]["range"]["start"],
+            json!({"line": 5, "character": 12})
+        );
+        assert!(taken.is_err());
+    }
+
+    #[test]
+    fn test_feature_completion_in_a_single_file() {
+        let app_context = Arc::new(AppContext::new());
+        let uri = "file:///nowhere/qleany.yaml";
+        let mut server = Server {
+            app_context: &app_context,
+            documents: HashMap::new(),
+        };
+        server.documents.insert(
+            uri.to_string(),
+            "schema:\n  version: 5\nfeatures:\n- name: garage\n- name: ga\n".to_string(),
+        );
+
+        let completion = server.completion(&json!({
+            "textDocument": {"uri": uri},
+            "position": {"line": 4, "character": 10},
+        }));
+
+        assert_eq!(
+            completion["items"],
+            json!([{"label": "garage", "kind": KIND_MODULE}])
+        );
+    }
+
+    #[test]
+    fn test_path_to_uri() {
+        let path = PathBuf::from("/home/me/my app/qleany.yaml");
+        assert_eq!(path_to_uri(&path), "file:///home/me/my%20app/qleany.yaml");
+        assert_eq!(uri_to_path(&path_to_uri(&path)), Some(path));
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///home/me/my%20app/qleany.yaml"),
+            Some(PathBuf::from("/home/me/my app/qleany.yaml"))
+        );
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+    }
+
+    #[test]
+    fn test_diagnostic_range_is_in_utf16() {
+        let text = "entities:\n- name: Caré\n  fields: 3\n";
+        let diagnostic = diagnostic(text, 2, 9, 1, Some("C38"), "Invalid name");
+        assert_eq!(
+            diagnostic["range"]["start"],
+            json!({"line": 1, "character": 8})
+        );
+        assert_eq!(
+            diagnostic["range"]["end"],
+            json!({"line": 1, "character": 12})
+        );
+        assert_eq!(diagnostic["code"], "C38");
+    }
+}
@@ -0,0 +1,374 @@
+//! Line-based index of the names of a manifest being edited.
+//!
+//! The buffer is often not valid YAML while typing, so the index does not
+//! parse it: it follows the indentation and the keys line by line, which is
+//! enough for the layout of qleany.yaml.
+
+/// Field types accepted by the manifest schema.
+pub const FIELD_TYPES: &[&str] = &[
+    "boolean", "integer", "uinteger", "float", "string", "uuid", "datetime", "entity", "enum",
+    "decimal", "date", "time", "duration", "bytes", "json",
+];
+
+/// Relationship kinds accepted by the manifest schema.
+pub const RELATIONSHIPS: &[&str] = &[
+    "one_to_one",
+    "many_to_one",
+    "one_to_many",
+    "ordered_one_to_many",
+    "many_to_many",
+];
+
+/// An entity or feature name in the manifest. Columns are in UTF-16 code units, as LSP expects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    pub name: String,
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    /// The `name` of an item of `entities` or `features`, the others are references
+    pub declaration: bool,
+}
+
+/// What the value being typed at the cursor refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionContext {
+    EntityName,
+    EnumName,
+    FeatureName,
+    FieldType,
+    Relationship,
+}
+
+#[derive(Debug, Default)]
+pub struct ManifestIndex {
+    pub entities: Vec<Occurrence>,
+    pub enum_names: Vec<String>,
+    /// Feature declarations, features have no references
+    pub features: Vec<Occurrence>,
+    /// Lines that are items of a use case `entities` list
+    entity_list_lines: Vec<usize>,
+    /// Lines holding the `name` of an item of `features`
+    feature_name_lines: Vec<usize>,
+}
+
+impl ManifestIndex {
+    pub fn parse(text: &str) -> ManifestIndex {
+        let mut index = ManifestIndex::default();
+        let mut section = "";
+        let mut first_item_indent: Option<usize> = None;
+        // Indentation of an open use case `entities:` block list
+        let mut entity_list_indent: Option<usize> = None;
+
+        for (line_number, line) in text.lines().enumerate() {
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            let content = line[indent..].trim_end();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            if indent == 0 && !content.starts_with('-') {
+                section = content.split(':').next().unwrap_or("").trim();
+                first_item_indent = None;
+                entity_list_indent = None;
+                continue;
+            }
+
+            let is_item = content == "-" || content.starts_with("- ");
+            let (rest, rest_start) = if is_item {
+                let after_dash = &content[1..];
+                let rest = after_dash.trim_start();
+                (rest, indent + 1 + after_dash.len() - rest.len())
+            } else {
+                (content, indent)
+            };
+
+            if let Some(list_indent) = entity_list_indent {
+                if is_item && indent >= list_indent && !rest.contains(':') {
+                    index.entity_list_lines.push(line_number);
+                    if let Some((name, start)) = scalar(rest, rest_start) {
+                        index.push_entity(line, line_number, name, start, false);
+                    }
+                    continue;
+                }
+                if indent <= list_indent {
+                    entity_list_indent = None;
+                }
+            }
+
+            if is_item && first_item_indent.is_none() {
+                first_item_indent = Some(indent);
+            }
+            let is_top_item = is_item && first_item_indent == Some(indent);
+
+            let Some((key, value, value_start)) = key_value(rest, rest_start) else {
+                continue;
+            };
+
+            match (section, key) {
+                ("entities", "name") if is_top_item => {
+                    if let Some((name, start)) = scalar(value, value_start) {
+                        index.push_entity(line, line_number, name, start, true);
+                    }
+                }
+                ("features", "name") if is_top_item => {
+                    index.feature_name_lines.push(line_number);
+                    if let Some((name, start)) = scalar(value, value_start) {
+                        let feature = occurrence(line, line_number, name, start, true);
+                        index.features.push(feature);
+                    }
+                }
+                ("entities", "entity" | "inherits_from") => {
+                    if let Some((name, start)) = scalar(value, value_start) {
+                        index.push_entity(line, line_number, name, start, false);
+                    }
+                }
+                ("features", "entities") => {
+                    if value.is_empty() {
+                        entity_list_indent = Some(indent);
+                    } else if let Some(inner) = value.strip_prefix('[') {
+                        let mut offset = value_start + 1;
+                        for part in inner.trim_end_matches(']').split(',') {
+                            if let Some((name, start)) = scalar(part, offset) {
+                                index.push_entity(line, line_number, name, start, false);
+                            }
+                            offset += part.len() + 1;
+                        }
+                    }
+                }
+                (_, "enum_name") => {
+                    if let Some((name, _)) = scalar(value, value_start)
+                        && !index.enum_names.iter().any(|n| n == name)
+                    {
+                        index.enum_names.push(name.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        index
+    }
+
+    /// The entity name under the cursor.
+    pub fn entity_at(&self, line: usize, character: usize) -> Option<&Occurrence> {
+        self.entities
+            .iter()
+            .find(|o| o.line == line && o.start <= character && character <= o.end)
+    }
+
+    pub fn entity_declaration(&self, name: &str) -> Option<&Occurrence> {
+        self.entities
+            .iter()
+            .find(|o| o.declaration && o.name == name)
+    }
+
+    /// The declaration and all the references of an entity.
+    pub fn entity_occurrences<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Occurrence> {
+        self.entities.iter().filter(move |o| o.name == name)
+    }
+
+    pub fn entity_names(&self) -> Vec<&str> {
+        self.entities
+            .iter()
+            .filter(|o| o.declaration)
+            .map(|o| o.name.as_str())
+            .collect()
+    }
+
+    /// Feature names, except the one declared on `skipped_line`, e.g. being typed.
+    pub fn feature_names(&self, skipped_line: Option<usize>) -> Vec<&str> {
+        self.features
+            .iter()
+            .filter(|o| Some(o.line) != skipped_line)
+            .map(|o| o.name.as_str())
+            .collect()
+    }
+
+    /// What the value being typed on `line`, before `prefix`, refers to.
+    pub fn completion_context(&self, line: usize, prefix: &str) -> Option<CompletionContext> {
+        let content = prefix.trim_start();
+        let content = content
+            .strip_prefix('-')
+            .map(str::trim_start)
+            .unwrap_or(content);
+
+        if let Some((key, value)) = content.split_once(':') {
+            let value = value.trim_start();
+            let is_word = |v: &str| v.chars().all(|c| c.is_alphanumeric() || c == '_');
+            return match key.trim() {
+                "entity" | "inherits_from" if is_word(value) => Some(CompletionContext::EntityName),
+                "entities" if value.starts_with('[') => Some(CompletionContext::EntityName),
+                "enum_name" if is_word(value) => Some(CompletionContext::EnumName),
+                "type" if is_word(value) => Some(CompletionContext::FieldType),
+                "relationship" if is_word(value) => Some(CompletionContext::Relationship),
+                "name" if is_word(value) && self.feature_name_lines.contains(&line) => {
+                    Some(CompletionContext::FeatureName)
+                }
+                _ => None,
+            };
+        }
+        self.entity_list_lines
+            .contains(&line)
+            .then_some(CompletionContext::EntityName)
+    }
+
+    fn push_entity(
+        &mut self,
+        line: &str,
+        line_number: usize,
+        name: &str,
+        start: usize,
+        declaration: bool,
+    ) {
+        let entity = occurrence(line, line_number, name, start, declaration);
+        self.entities.push(entity);
+    }
+}
+
+/// The occurrence of `name`, found at byte offset `start` of `line`.
+fn occurrence(
+    line: &str,
+    line_number: usize,
+    name: &str,
+    start: usize,
+    declaration: bool,
+) -> Occurrence {
+    Occurrence {
+        name: name.to_string(),
+        line: line_number,
+        start: utf16_len(&line[..start]),
+        end: utf16_len(&line[..start + name.len()]),
+        declaration,
+    }
+}
+
+/// Split `key: value`, returning the byte offset of the value in the line.
+fn key_value(rest: &str, rest_start: usize) -> Option<(&str, &str, usize)> {
+    let (key, value) = rest.split_once(':')?;
+    if key.is_empty() || key.contains(' ') {
+        return None;
+    }
+    let trimmed = value.trim_start();
+    Some((
+        key,
+        trimmed,
+        rest_start + key.len() + 1 + value.len() - trimmed.len(),
+    ))
+}
+
+/// The scalar in `value`, without quotes nor trailing comment, and its byte offset.
+fn scalar(value: &str, value_start: usize) -> Option<(&str, usize)> {
+    let trimmed = value.trim_start();
+    let start = value_start + value.len() - trimmed.len();
+    let (name, start) = match trimmed.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let inner = &trimmed[1..];
+            (&inner[..inner.find(quote)?], start + 1)
+        }
+        _ => (
+            trimmed.split(" #").next().unwrap_or(trimmed).trim_end(),
+            start,
+        ),
+    };
+    (!name.is_empty()).then_some((name, start))
+}
+
+pub fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "\
+schema:
+  version: 5
+entities:
+- name: EntityBase
+  only_for_heritage: true
+  fields:
+  - name: id
+    type: uinteger
+- name: Car
+  inherits_from: EntityBase
+  fields:
+  - name: name
+    type: string
+  - name: wheels
+    type: entity
+    entity: \"Wheel\"
+    relationship: ordered_one_to_many
+  - name: color
+    type: enum
+    enum_name: Color
+    enum_values:
+    - Red
+- name: Wheel
+  inherits_from: EntityBase
+features:
+- name: garage
+  use_cases:
+  - name: park
+    entities:
+    - Car
+    - Wheel
+  - name: wash
+    entities: [Car, Wheel]
+";
+
+    #[test]
+    fn test_declarations_and_references() {
+        let index = ManifestIndex::parse(MANIFEST);
+        assert_eq!(index.entity_names(), vec!["EntityBase", "Car", "Wheel"]);
+        assert_eq!(index.entity_occurrences("EntityBase").count(), 3);
+        assert_eq!(index.entity_occurrences("Car").count(), 3);
+        assert_eq!(index.entity_occurrences("Wheel").count(), 4);
+        assert_eq!(index.enum_names, vec!["Color"]);
+        assert_eq!(index.feature_names(None), vec!["garage"]);
+        assert!(index.feature_names(Some(25)).is_empty());
+        // fields named "name" are not entities
+        assert!(index.entities.iter().all(|o| o.name != "name"));
+    }
+
+    #[test]
+    fn test_positions() {
+        let index = ManifestIndex::parse(MANIFEST);
+        let quoted = index.entity_at(15, 13).expect("quoted reference");
+        assert_eq!(
+            (quoted.name.as_str(), quoted.start, quoted.end),
+            ("Wheel", 13, 18)
+        );
+        let inline = index.entity_at(32, 20).expect("inline list reference");
+        assert_eq!((inline.name.as_str(), inline.start), ("Wheel", 20));
+        let declaration = index.entity_declaration("Car").expect("declaration");
+        assert_eq!((declaration.line, declaration.start), (8, 8));
+    }
+
+    #[test]
+    fn test_completion_context() {
+        let index = ManifestIndex::parse(MANIFEST);
+        assert_eq!(
+            index.completion_context(9, "  inherits_from: Ent"),
+            Some(CompletionContext::EntityName)
+        );
+        assert_eq!(
+            index.completion_context(19, "    enum_name: "),
+            Some(CompletionContext::EnumName)
+        );
+        assert_eq!(
+            index.completion_context(12, "    type: st"),
+            Some(CompletionContext::FieldType)
+        );
+        assert_eq!(
+            index.completion_context(29, "    - C"),
+            Some(CompletionContext::EntityName)
+        );
+        assert_eq!(
+            index.completion_context(25, "- name: ga"),
+            Some(CompletionContext::FeatureName)
+        );
+        assert_eq!(index.completion_context(11, "  - name: na"), None);
+        assert_eq!(index.completion_context(27, "  - name: pa"), None);
+    }
+}
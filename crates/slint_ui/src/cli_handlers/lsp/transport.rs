@@ -0,0 +1,58 @@
+//! Base protocol of LSP: JSON-RPC messages framed by a `Content-Length` header.
+
+use anyhow::{Context, Result};
+use std::io::{BufRead, Write};
+
+/// Read the next message. Returns `None` at the end of the input.
+pub fn read_message(input: &mut impl BufRead) -> Result<Option<serde_json::Value>> {
+    let mut content_length: Option<usize> = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            content_length = Some(value.trim().parse().context("Invalid Content-Length")?);
+        }
+    }
+    let content_length = content_length.context("Missing Content-Length header")?;
+    let mut content = vec![0; content_length];
+    input.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+pub fn write_message(output: &mut impl Write, message: &serde_json::Value) -> Result<()> {
+    let content = serde_json::to_string(message)?;
+    write!(
+        output,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    output.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let message = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"});
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &message).unwrap();
+        write_message(&mut buffer, &message).unwrap();
+
+        let mut input = buffer.as_slice();
+        assert_eq!(read_message(&mut input).unwrap(), Some(message.clone()));
+        assert_eq!(read_message(&mut input).unwrap(), Some(message));
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+}
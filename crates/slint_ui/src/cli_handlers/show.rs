@@ -96,6 +96,44 @@ fn show_config(app_context: &Arc<AppContext>, args: &ShowArgs) -> Result<()> {
 }
 
 fn show_entity(app_context: &Arc<AppContext>, name: &str, args: &ShowArgs) -> Result<()> {
+    let (entity, fields) = find_entity(app_context, name)?
+        .ok_or_else(|| anyhow::anyhow!("Entity not found: {}", name))?;
+
+    match args.format {
+        OutputFormat::Plain | OutputFormat::Tree => {
+            println!("Entity: {}", entity.name);
+            if entity.only_for_heritage {
+                println!("  (heritage only)");
+            }
+            println!("  undoable: {}", entity.undoable);
+            println!("\nFields ({}):", fields.len());
+            for field in &fields {
+                println!("  - {}: {:?}", field.name, field.field_type);
+            }
+        }
+        OutputFormat::Json => {
+            let json = serde_json::json!({
+                "name": entity.name,
+                "only_for_heritage": entity.only_for_heritage,
+                "undoable": entity.undoable,
+                "fields": fields.iter().map(|f| serde_json::json!({
+                    "name": f.name,
+                    "field_type": format!("{:?}", f.field_type),
+                    "optional": f.optional,
+                })).collect::<Vec<_>>()
+            });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Find an entity of the loaded manifest by name (case-insensitive), with its own fields.
+pub(crate) fn find_entity(
+    app_context: &Arc<AppContext>,
+    name: &str,
+) -> Result<Option<(direct_access::EntityDto, Vec<direct_access::FieldDto>)>> {
     use common::direct_access::entity::EntityRelationshipField;
     use common::direct_access::workspace::WorkspaceRelationshipField;
     use direct_access::{entity_controller, field_controller, workspace_controller};
@@ -122,9 +160,9 @@ fn show_entity(app_context: &Arc<AppContext>, name: &str, args: &ShowArgs) -> Re
             break;
         }
     }
-
-    let (entity_id, entity) =
-        found_entity.ok_or_else(|| anyhow::anyhow!("Entity not found: {}", name))?;
+    let Some((entity_id, entity)) = found_entity else {
+        return Ok(None);
+    };
 
     // Get fields
     let field_ids = entity_controller::get_relationship(
@@ -140,34 +178,7 @@ fn show_entity(app_context: &Arc<AppContext>, name: &str, args: &ShowArgs) -> Re
         }
     }
 
-    match args.format {
-        OutputFormat::Plain | OutputFormat::Tree => {
-            println!("Entity: {}", entity.name);
-            if entity.only_for_heritage {
-                println!("  (heritage only)");
-            }
-            println!("  undoable: {}", entity.undoable);
-            println!("\nFields ({}):", fields.len());
-            for field in &fields {
-                println!("  - {}: {:?}", field.name, field.field_type);
-            }
-        }
-        OutputFormat::Json => {
-            let json = serde_json::json!({
-                "name": entity.name,
-                "only_for_heritage": entity.only_for_heritage,
-                "undoable": entity.undoable,
-                "fields": fields.iter().map(|f| serde_json::json!({
-                    "name": f.name,
-                    "field_type": format!("{:?}", f.field_type),
-                    "optional": f.optional,
-                })).collect::<Vec<_>>()
-            });
-            println!("{}", serde_json::to_string_pretty(&json)?);
-        }
-    }
-
-    Ok(())
+    Ok(Some((entity, fields)))
 }
 
 fn show_feature(app_context: &Arc<AppContext>, name: &str, args: &ShowArgs) -> Result<()> {
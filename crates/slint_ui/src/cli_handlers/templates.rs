@@ -0,0 +1,126 @@
+use crate::app_context::AppContext;
+use crate::cli::{OutputContext, TemplatesAction, TemplatesArgs};
+use crate::cli_handlers::common::{
+    TargetLanguage, apply_templates_override, builtin_template, builtin_template_names,
+    get_target_language, get_templates_dir, warn_stale_template_overrides,
+};
+use anyhow::{Result, bail};
+use common::generator::template_overrides;
+use handling_manifest::handling_manifest_controller;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Templates directory used by `eject` when the manifest has no templates_path
+const DEFAULT_TEMPLATES_PATH: &str = "templates";
+
+pub fn execute(
+    app_context: &Arc<AppContext>,
+    manifest_path: &Path,
+    args: &TemplatesArgs,
+    output: &OutputContext,
+) -> Result<()> {
+    // Load manifest
+    let load_dto = handling_manifest::LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest_controller::load(&app_context.db_context, &app_context.event_hub, &load_dto)?;
+    apply_templates_override(app_context, args.templates.as_deref())?;
+
+    let target_language = get_target_language(app_context)?;
+    let templates_dir = get_templates_dir(app_context, manifest_path)?;
+
+    match &args.action {
+        TemplatesAction::List => list(&target_language, templates_dir.as_deref()),
+        TemplatesAction::Eject { names, force } => eject(
+            manifest_path,
+            &target_language,
+            templates_dir,
+            names,
+            *force,
+            output,
+        ),
+        TemplatesAction::Check => {
+            if templates_dir.is_none() {
+                output.info("No templates_path in the manifest, nothing to check");
+                return Ok(());
+            }
+            let stale = warn_stale_template_overrides(
+                app_context,
+                manifest_path,
+                &target_language,
+                output,
+            )?;
+            if stale > 0 {
+                bail!("{} template override(s) are out of date", stale);
+            }
+            output.success("Template overrides are up to date");
+            Ok(())
+        }
+    }
+}
+
+fn list(target_language: &TargetLanguage, templates_dir: Option<&Path>) -> Result<()> {
+    let overrides = match templates_dir {
+        Some(dir) => template_overrides::load_template_overrides(dir)?,
+        None => vec![],
+    };
+
+    for name in builtin_template_names(target_language) {
+        match overrides.iter().find(|o| o.name == name) {
+            Some(o) => println!("  {} (overridden by {})", name, o.path.display()),
+            None => println!("  {}", name),
+        }
+    }
+    Ok(())
+}
+
+fn eject(
+    manifest_path: &Path,
+    target_language: &TargetLanguage,
+    templates_dir: Option<PathBuf>,
+    names: &[String],
+    force: bool,
+    output: &OutputContext,
+) -> Result<()> {
+    let has_templates_path = templates_dir.is_some();
+    let templates_dir = templates_dir.unwrap_or_else(|| {
+        manifest_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(DEFAULT_TEMPLATES_PATH)
+    });
+
+    // Check everything before writing anything
+    for name in names {
+        if builtin_template(target_language, name).is_none() {
+            bail!(
+                "Unknown template '{}'. Check the name with: templates list",
+                name
+            );
+        }
+        let path = templates_dir.join(format!("{}.tera", name));
+        if path.exists() && !force {
+            bail!(
+                "{} already exists. Use --force to overwrite it",
+                path.display()
+            );
+        }
+    }
+
+    std::fs::create_dir_all(&templates_dir)?;
+    for name in names {
+        let builtin = builtin_template(target_language, name).unwrap_or_default();
+        let path = templates_dir.join(format!("{}.tera", name));
+        std::fs::write(&path, template_overrides::ejected_content(name, builtin))?;
+        output.success(&format!("Ejected {} to {}", name, path.display()));
+    }
+
+    if !has_templates_path {
+        output.info(&format!(
+            "Add `templates_path: {}` to the global section of the manifest to use the ejected templates",
+            DEFAULT_TEMPLATES_PATH
+        ));
+    }
+
+    Ok(())
+}
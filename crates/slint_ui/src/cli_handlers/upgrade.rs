@@ -1,34 +1,80 @@
 use crate::app_context::AppContext;
-use crate::cli::OutputContext;
+use crate::cli::{OutputContext, UpgradeArgs};
 use crate::cli_handlers::common::run_checks;
 use anyhow::Result;
 use handling_manifest::handling_manifest_controller;
+use handling_manifest::{MigrationChangeDto, MigrationChangeKind};
 use std::path::Path;
 use std::sync::Arc;
 
 pub fn execute(
     app_context: &Arc<AppContext>,
     manifest_path: &Path,
+    args: &UpgradeArgs,
     output: &OutputContext,
 ) -> Result<()> {
     output.verbose(&format!("Upgrading {}", manifest_path.display()));
 
-    // Load (triggers automatic migration to current schema version)
+    // Load (migrates in memory) to validate the upgraded manifest before writing it
     let load_dto = handling_manifest::LoadDto {
         manifest_path: manifest_path.to_string_lossy().to_string(),
     };
-
     handling_manifest_controller::load(&app_context.db_context, &app_context.event_hub, &load_dto)?;
     run_checks(app_context, output)?;
 
-    // Save back with current schema version
-    let save_dto = handling_manifest::SaveDto {
+    let upgrade_dto = handling_manifest::UpgradeDto {
         manifest_path: manifest_path.to_string_lossy().to_string(),
+        dry_run: args.dry_run,
     };
+    let upgraded = handling_manifest_controller::upgrade(&app_context.db_context, &upgrade_dto)?;
+
+    if upgraded.from_version == upgraded.to_version {
+        output.success(&format!(
+            "{} is already at schema version {}",
+            manifest_path.display(),
+            upgraded.to_version
+        ));
+        return Ok(());
+    }
 
-    handling_manifest_controller::save(&app_context.db_context, &app_context.event_hub, &save_dto)?;
+    output.info(&format!(
+        "Schema version {} → {}",
+        upgraded.from_version, upgraded.to_version
+    ));
+    for change in &upgraded.changes {
+        output.info(&format!("  {}", describe(change)));
+    }
+    if !upgraded.formatting_preserved {
+        output.warn("The manifest could not be edited in place: comments and formatting are lost");
+    }
 
-    output.success(&format!("Upgraded {}", manifest_path.display()));
+    if args.dry_run {
+        print!("{}", upgraded.diff);
+        output.info("Dry run, manifest not written");
+    } else {
+        output.verbose(&upgraded.diff);
+        output.success(&format!("Upgraded {}", manifest_path.display()));
+    }
 
     Ok(())
 }
+
+/// One line of the change log, e.g. `removed /entities/Car/allow_direct_access (was: true) [v3]`
+fn describe(change: &MigrationChangeDto) -> String {
+    match change.kind {
+        MigrationChangeKind::Renamed => format!(
+            "renamed {} to {} [v{}]",
+            change.location,
+            change.new_key.as_deref().unwrap_or_default(),
+            change.to_version
+        ),
+        MigrationChangeKind::Removed => format!(
+            "removed {} (was: {}) [v{}]",
+            change.location, change.value, change.to_version
+        ),
+        MigrationChangeKind::Defaulted => format!(
+            "added {} = {} [v{}]",
+            change.location, change.value, change.to_version
+        ),
+    }
+}
@@ -0,0 +1,457 @@
+use crate::app_context::AppContext;
+use crate::cli::{OutputContext, WatchArgs};
+use crate::cli_handlers::common::{
+    TargetLanguage, apply_templates_override, detect_and_warn_of_missing_formatters,
+    get_target_language, get_templates_dir, run_checks, warn_orphaned_user_regions,
+    warn_stale_template_overrides,
+};
+use crate::cli_handlers::generate::poll_long_operation;
+use anyhow::Result;
+use common::direct_access::system::SystemRelationshipField;
+use common::entities::FileStatus;
+use common::generator::baseline_store;
+use cpp_qt_file_generation::cpp_qt_file_generation_controller;
+use direct_access::{FileDto, file_controller, system_controller};
+use file_generation_shared_steps::file_generation_shared_steps_controller;
+use handling_manifest::handling_manifest_controller;
+use rust_file_generation::rust_file_generation_controller;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// The root system entity ID (singleton in the database)
+const ROOT_SYSTEM_ID: u64 = 1;
+
+/// What the previous run generated for a file.
+struct PreviousFile {
+    status: FileStatus,
+    code: String,
+}
+
+pub fn execute(
+    app_context: &Arc<AppContext>,
+    manifest_path: &Path,
+    args: &WatchArgs,
+    output: &OutputContext,
+) -> Result<()> {
+    let manifest_dir = manifest_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+    let interval = Duration::from_millis(args.interval.max(50));
+
+    output.info(&format!(
+        "Watching {} (Ctrl+C to stop)",
+        manifest_path.display()
+    ));
+
+    let mut previous: Option<HashMap<String, PreviousFile>> = None;
+    let mut templates_dir: Option<PathBuf> = None;
+    let mut last_fingerprint: Option<Vec<(PathBuf, Option<SystemTime>)>> = None;
+
+    loop {
+        let current = fingerprint(manifest_path, templates_dir.as_deref());
+        if last_fingerprint.as_ref() != Some(&current) {
+            let is_first_run = previous.is_none();
+            match regenerate(app_context, manifest_path, args, output, is_first_run) {
+                Ok(files) => {
+                    let mut previous_files = previous.take().unwrap_or_default();
+                    report_and_write(
+                        &files,
+                        &mut previous_files,
+                        &manifest_dir,
+                        is_first_run,
+                        args.write,
+                        output,
+                    );
+                    previous = Some(previous_files);
+                }
+                // Keep watching: the next save will probably fix it
+                Err(e) => eprintln!("✗ {}", e),
+            }
+            // The manifest may now point to another templates directory
+            templates_dir = get_templates_dir(app_context, manifest_path).ok().flatten();
+            last_fingerprint = Some(fingerprint(manifest_path, templates_dir.as_deref()));
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Load and check the manifest, then generate every file in memory and compare
+/// it with the disk. Returns the files with their new status.
+fn regenerate(
+    app_context: &Arc<AppContext>,
+    manifest_path: &Path,
+    args: &WatchArgs,
+    output: &OutputContext,
+    is_first_run: bool,
+) -> Result<Vec<FileDto>> {
+    let load_dto = handling_manifest::LoadDto {
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+    };
+    handling_manifest_controller::load(&app_context.db_context, &app_context.event_hub, &load_dto)?;
+    apply_templates_override(app_context, args.templates.as_deref())?;
+    run_checks(app_context, output)?;
+
+    let target_language = get_target_language(app_context)?;
+    warn_stale_template_overrides(app_context, manifest_path, &target_language, output)?;
+    if is_first_run {
+        detect_and_warn_of_missing_formatters(&target_language, output, false)?;
+    }
+
+    match target_language {
+        TargetLanguage::Rust => {
+            rust_file_generation_controller::fill_rust_files(
+                &app_context.db_context,
+                &app_context.event_hub,
+                &rust_file_generation::FillRustFilesDto {
+                    only_list_already_existing: false,
+                },
+            )?;
+        }
+        TargetLanguage::CppQt => {
+            cpp_qt_file_generation_controller::fill_cpp_qt_files(
+                &app_context.db_context,
+                &app_context.event_hub,
+                &cpp_qt_file_generation::FillCppQtFilesDto {
+                    only_list_already_existing: false,
+                },
+            )?;
+        }
+    }
+
+    let operation_id = {
+        let mut long_op_manager = app_context.long_operation_manager.lock().map_err(|e| {
+            anyhow::anyhow!("Failed to acquire lock on long operation manager: {e}")
+        })?;
+        match target_language {
+            TargetLanguage::Rust => rust_file_generation_controller::fill_code_in_rust_files(
+                &app_context.db_context,
+                &app_context.event_hub,
+                &mut long_op_manager,
+            )?,
+            TargetLanguage::CppQt => cpp_qt_file_generation_controller::fill_code_in_cpp_qt_files(
+                &app_context.db_context,
+                &app_context.event_hub,
+                &mut long_op_manager,
+            )?,
+        }
+    };
+    poll_long_operation(app_context, &operation_id, output)?;
+    warn_orphaned_user_regions(app_context, &target_language, &operation_id, output)?;
+
+    file_generation_shared_steps_controller::fill_status_in_files(
+        &app_context.db_context,
+        &app_context.event_hub,
+    )?;
+
+    let file_ids = system_controller::get_relationship(
+        &app_context.db_context,
+        &ROOT_SYSTEM_ID,
+        &SystemRelationshipField::Files,
+    )?;
+    Ok(
+        file_controller::get_multi(&app_context.db_context, &file_ids)?
+            .into_iter()
+            .flatten()
+            .collect(),
+    )
+}
+
+/// Print the files that flipped to Modified or New since the previous run and,
+/// when `write` is set, write those that nobody touched since the previous
+/// generation. The first run only records the generation. `previous` is
+/// updated to this run. A file that cannot be written is reported and stays
+/// pending, like a failed regeneration.
+fn report_and_write(
+    files: &[FileDto],
+    previous: &mut HashMap<String, PreviousFile>,
+    manifest_dir: &Path,
+    is_first_run: bool,
+    write: bool,
+    output: &OutputContext,
+) {
+    let mut flipped = 0;
+    let mut written = 0;
+    let mut current: HashMap<String, PreviousFile> = HashMap::new();
+
+    for file in files {
+        let Some(ref code) = file.generated_code else {
+            continue;
+        };
+        let path = format!("{}{}", file.relative_path, file.name);
+        let mut status = file.status.clone();
+        let same_as_previous = previous
+            .get(&path)
+            .is_some_and(|p| p.status == status && p.code == *code);
+
+        let is_pending = matches!(
+            status,
+            FileStatus::Modified | FileStatus::Merged | FileStatus::Conflict | FileStatus::New
+        );
+        if is_pending && !same_as_previous && !is_first_run {
+            flipped += 1;
+            let tag = match status {
+                FileStatus::New => "[N]",
+                FileStatus::Merged => "[A]",
+                FileStatus::Conflict => "[C]",
+                _ => "[M]",
+            };
+            let mut line = format!("  {} {}", tag, path);
+            if write && is_untouched(file, previous.get(&path), manifest_dir) {
+                match write_file(file, code, manifest_dir) {
+                    Ok(()) => {
+                        status = FileStatus::Unchanged;
+                        written += 1;
+                        line.push_str(" (written)");
+                    }
+                    Err(e) => eprintln!("✗ {}: {}", path, e),
+                }
+            }
+            output.info(&line);
+        }
+
+        current.insert(
+            path,
+            PreviousFile {
+                status,
+                code: code.clone(),
+            },
+        );
+    }
+
+    if is_first_run {
+        let pending = current
+            .values()
+            .filter(|f| f.status != FileStatus::Unchanged)
+            .count();
+        output.success(&format!(
+            "{} files generated in memory, {} differ from the disk",
+            current.len(),
+            pending
+        ));
+    } else if flipped == 0 {
+        output.success("No file changed");
+    } else {
+        output.success(&format!(
+            "{} file(s) changed{}",
+            flipped,
+            if write {
+                format!(", {} written", written)
+            } else {
+                String::new()
+            }
+        ));
+    }
+
+    *previous = current;
+}
+
+/// Write the generated code of a file and record it as its baseline.
+fn write_file(file: &FileDto, code: &str, manifest_dir: &Path) -> Result<()> {
+    let file_dir = manifest_dir.join(&file.relative_path);
+    std::fs::create_dir_all(&file_dir)?;
+    std::fs::write(file_dir.join(&file.name), code)?;
+    baseline_store::write_baseline(manifest_dir, &file.relative_path, &file.name, code)
+}
+
+/// Whether a file on disk is still what Qleany generated for it, either in the
+/// previous run or when it was last written (its baseline). Files missing on
+/// disk are untouched only if the previous run didn't generate them either.
+fn is_untouched(file: &FileDto, previous: Option<&PreviousFile>, manifest_dir: &Path) -> bool {
+    let disk_path = manifest_dir.join(&file.relative_path).join(&file.name);
+    let Ok(on_disk) = std::fs::read_to_string(&disk_path) else {
+        return !disk_path.exists() && previous.is_none();
+    };
+    previous.is_some_and(|p| p.code == on_disk)
+        || baseline_store::read_baseline(manifest_dir, &file.relative_path, &file.name)
+            .is_some_and(|baseline| baseline == on_disk)
+}
+
+/// Modification times of the manifest, of the files it includes and of every
+/// file of the templates directory. Includes are expanded again each time, so
+/// that a new file matching a glob counts as a change.
+fn fingerprint(
+    manifest_path: &Path,
+    templates_dir: Option<&Path>,
+) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut paths = vec![manifest_path.to_path_buf()];
+    let manifest_dir = manifest_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    // A manifest that doesn't parse is still watched through its own time
+    if let Ok(included) =
+        handling_manifest_controller::included_files(&manifest_path.to_string_lossy())
+    {
+        paths.extend(included.iter().map(|file| manifest_dir.join(file)));
+    }
+    if let Some(dir) = templates_dir {
+        collect_files(dir, &mut paths);
+    }
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, paths);
+        } else {
+            paths.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUIET: OutputContext = OutputContext {
+        verbose: false,
+        quiet: true,
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("qleany_watch_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file(name: &str, code: &str, status: FileStatus) -> FileDto {
+        FileDto {
+            name: name.to_string(),
+            relative_path: "src/".to_string(),
+            generated_code: Some(code.to_string()),
+            status,
+            ..Default::default()
+        }
+    }
+
+    fn previous_file(code: &str) -> PreviousFile {
+        PreviousFile {
+            status: FileStatus::Unchanged,
+            code: code.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_untouched() {
+        let dir = temp_dir("untouched");
+        let generated = file("lib.rs", "new", FileStatus::Modified);
+
+        // missing on disk: untouched only if the previous run didn't generate it
+        assert!(is_untouched(&generated, None, &dir));
+        assert!(!is_untouched(&generated, Some(&previous_file("old")), &dir));
+
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "old").unwrap();
+        // first run, no baseline: the file is not known to be generated
+        assert!(!is_untouched(&generated, None, &dir));
+        // disk == previous run
+        assert!(is_untouched(&generated, Some(&previous_file("old")), &dir));
+        // disk == baseline
+        baseline_store::write_baseline(&dir, "src/", "lib.rs", "old").unwrap();
+        assert!(is_untouched(&generated, None, &dir));
+        // edited by the user since
+        std::fs::write(dir.join("src/lib.rs"), "edited").unwrap();
+        assert!(!is_untouched(&generated, Some(&previous_file("old")), &dir));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let dir = temp_dir("fingerprint");
+        std::fs::create_dir_all(dir.join("model")).unwrap();
+        std::fs::create_dir_all(dir.join("templates/nested")).unwrap();
+        let manifest_path = dir.join("qleany.yaml");
+        std::fs::write(
+            &manifest_path,
+            "schema:\n  version: 5\nincludes:\n  - model/*.yaml\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("model/car.yaml"), "entities: []\n").unwrap();
+        std::fs::write(dir.join("templates/nested/a.tera"), "").unwrap();
+
+        let paths: Vec<PathBuf> = fingerprint(&manifest_path, Some(&dir.join("templates")))
+            .into_iter()
+            .map(|(path, modified)| {
+                assert!(modified.is_some());
+                path
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("model/car.yaml"),
+                manifest_path.clone(),
+                dir.join("templates/nested/a.tera"),
+            ]
+        );
+
+        // a new file matching an include glob is a change
+        let before = fingerprint(&manifest_path, None);
+        std::fs::write(dir.join("model/wheel.yaml"), "entities: []\n").unwrap();
+        assert_ne!(fingerprint(&manifest_path, None), before);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_report_and_write() {
+        let dir = temp_dir("report");
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/generated.rs"), "v1").unwrap();
+        std::fs::write(dir.join("src/edited.rs"), "v1").unwrap();
+        let mut previous = HashMap::new();
+
+        // first run: records the generation, writes nothing
+        let first = [
+            file("generated.rs", "v1", FileStatus::Unchanged),
+            file("edited.rs", "v1", FileStatus::Unchanged),
+            file("new.rs", "v1", FileStatus::New),
+        ];
+        report_and_write(&first, &mut previous, &dir, true, true, &QUIET);
+        assert_eq!(previous.len(), 3);
+        assert!(!dir.join("src/new.rs").exists());
+
+        // the user edits one file, then the manifest changes all of them
+        std::fs::write(dir.join("src/edited.rs"), "mine").unwrap();
+        let second = [
+            file("generated.rs", "v2", FileStatus::Modified),
+            file("edited.rs", "v2", FileStatus::Modified),
+            file("new.rs", "v2", FileStatus::New),
+        ];
+        report_and_write(&second, &mut previous, &dir, false, true, &QUIET);
+
+        let read = |name: &str| std::fs::read_to_string(dir.join("src").join(name)).unwrap();
+        assert_eq!(read("generated.rs"), "v2");
+        assert_eq!(
+            baseline_store::read_baseline(&dir, "src/", "generated.rs").as_deref(),
+            Some("v2")
+        );
+        assert_eq!(previous["src/generated.rs"].status, FileStatus::Unchanged);
+        // the user's edit is kept and the file stays pending
+        assert_eq!(read("edited.rs"), "mine");
+        assert_eq!(previous["src/edited.rs"].status, FileStatus::Modified);
+        // new.rs was listed by the previous run, it is not written over nothing
+        assert!(!dir.join("src/new.rs").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,6 +3,7 @@
 //! Commands module - cleanly separated command handlers for Slint UI
 //!
 
+pub mod custom_file_commands;
 pub mod dto_commands;
 pub mod dto_field_commands;
 pub mod entity_commands;
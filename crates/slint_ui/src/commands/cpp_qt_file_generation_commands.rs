@@ -5,9 +5,10 @@
 
 use crate::app_context::AppContext;
 use cpp_qt_file_generation::{
-    FillCppQtFilesDto, FillCppQtFilesReturnDto, GenerateCppQtCodeDto, GenerateCppQtCodeReturnDto,
-    GenerateCppQtFilesDto, GenerateCppQtFilesReturnDto, GenerateCppQtPromptDto,
-    GenerateCppQtPromptReturnDto, cpp_qt_file_generation_controller,
+    FillCodeInCppQtFilesReturnDto, FillCppQtFilesDto, FillCppQtFilesReturnDto,
+    GenerateCppQtCodeDto, GenerateCppQtCodeReturnDto, GenerateCppQtFilesDto,
+    GenerateCppQtFilesReturnDto, GenerateCppQtPromptDto, GenerateCppQtPromptReturnDto,
+    cpp_qt_file_generation_controller,
 };
 
 use common::long_operation::OperationProgress;
@@ -94,7 +95,7 @@ pub fn get_fill_code_in_cpp_qt_files_progress(
 pub fn get_fill_code_in_cpp_qt_files_result(
     ctx: &AppContext,
     operation_id: &str,
-) -> Result<Option<()>, String> {
+) -> Result<Option<FillCodeInCppQtFilesReturnDto>, String> {
     cpp_qt_file_generation_controller::get_fill_code_in_cpp_qt_files_result(
         &ctx.long_operation_manager.lock().unwrap(),
         operation_id,
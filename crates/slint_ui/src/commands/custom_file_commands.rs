@@ -0,0 +1,202 @@
+// Generated by Qleany v1.7.0 from frontend_entity_commands.tera
+
+//! CustomFile entity commands
+#![allow(unused_imports, dead_code)]
+
+use crate::app_context::AppContext;
+use anyhow::{Context, Result};
+use common::types::EntityId;
+use direct_access::{
+    CreateCustomFileDto, CustomFileDto, UpdateCustomFileDto, custom_file_controller,
+};
+
+/// Create a new custom_file entity (orphan, no parent)
+pub fn create_orphan_custom_file(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    dto: &CreateCustomFileDto,
+) -> Result<CustomFileDto> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::create_orphan(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        dto,
+    )
+    .context("creating custom_file")
+}
+/// Create a new custom_file entity as child of owner
+pub fn create_custom_file(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    dto: &CreateCustomFileDto,
+    owner_id: EntityId,
+    index: i32,
+) -> Result<CustomFileDto> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::create(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        dto,
+        owner_id,
+        index,
+    )
+    .context("creating custom_file")
+}
+/// Create multiple custom_file entities (orphan, no parent)
+pub fn create_orphan_custom_file_multi(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    dtos: &[CreateCustomFileDto],
+) -> Result<Vec<CustomFileDto>> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::create_orphan_multi(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        dtos,
+    )
+    .context("creating custom_file entities")
+}
+/// Create multiple custom_file entities as children of owner
+pub fn create_custom_file_multi(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    dtos: &[CreateCustomFileDto],
+    owner_id: EntityId,
+    index: i32,
+) -> Result<Vec<CustomFileDto>> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::create_multi(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        dtos,
+        owner_id,
+        index,
+    )
+    .context("creating custom_file entities")
+}
+/// Get a custom_file entity by ID
+pub fn get_custom_file(ctx: &AppContext, id: &EntityId) -> Result<Option<CustomFileDto>> {
+    custom_file_controller::get(&ctx.db_context, id).context("getting custom_file")
+}
+
+/// Get multiple custom_file entities by IDs
+pub fn get_custom_file_multi(
+    ctx: &AppContext,
+    ids: &[EntityId],
+) -> Result<Vec<Option<CustomFileDto>>> {
+    custom_file_controller::get_multi(&ctx.db_context, ids).context("getting custom_file entities")
+}
+
+/// Get all custom_file entities.
+/// Note: returns entities in database key order (by EntityId), not insertion order
+/// or any user-defined sort. For ordered collections, use relationship-based
+/// retrieval (e.g. get_*_relationship for ordered_one_to_many fields).
+pub fn get_all_custom_file(ctx: &AppContext) -> Result<Vec<CustomFileDto>> {
+    custom_file_controller::get_all(&ctx.db_context).context("getting all custom_file entities")
+}
+
+/// Update a custom_file entity
+pub fn update_custom_file(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    dto: &UpdateCustomFileDto,
+) -> Result<CustomFileDto> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::update(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        dto,
+    )
+    .context("updating custom_file")
+}
+
+/// Update multiple custom_file entities
+pub fn update_custom_file_multi(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    dtos: &[UpdateCustomFileDto],
+) -> Result<Vec<CustomFileDto>> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::update_multi(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        dtos,
+    )
+    .context("updating custom_file entities")
+}
+
+/// Update a custom_file entity with relationships
+pub fn update_custom_file_with_relationships(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    dto: &CustomFileDto,
+) -> Result<CustomFileDto> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::update_with_relationships(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        dto,
+    )
+    .context("updating custom_file with relationships")
+}
+
+/// Update multiple custom_file entities with relationships
+pub fn update_custom_file_with_relationships_multi(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    dtos: &[CustomFileDto],
+) -> Result<Vec<CustomFileDto>> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::update_with_relationships_multi(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        dtos,
+    )
+    .context("updating custom_file entities with relationships")
+}
+
+/// Remove a custom_file entity by ID
+pub fn remove_custom_file(ctx: &AppContext, stack_id: Option<u64>, id: &EntityId) -> Result<()> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::remove(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        id,
+    )
+    .context("removing custom_file")
+}
+
+/// Remove multiple custom_file entities by IDs
+pub fn remove_custom_file_multi(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    ids: &[EntityId],
+) -> Result<()> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    custom_file_controller::remove_multi(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        ids,
+    )
+    .context("removing custom_file entities")
+}
@@ -5,7 +5,8 @@
 
 use crate::app_context::AppContext;
 use file_generation_shared_steps::{
-    GetDiffDto, GetDiffReturnDto, file_generation_shared_steps_controller,
+    GetDiffDto, GetDiffReturnDto, MergeFileDto, MergeFileReturnDto,
+    file_generation_shared_steps_controller,
 };
 
 pub fn fill_status_in_files(ctx: &AppContext) -> Result<(), String> {
@@ -17,3 +18,8 @@ pub fn get_file_diff(ctx: &AppContext, dto: &GetDiffDto) -> Result<GetDiffReturn
     file_generation_shared_steps_controller::get_file_diff(&ctx.db_context, &ctx.event_hub, dto)
         .map_err(|e| format!("Error while get_file_diff: {:?}", e))
 }
+
+pub fn merge_file(ctx: &AppContext, dto: &MergeFileDto) -> Result<MergeFileReturnDto, String> {
+    file_generation_shared_steps_controller::merge_file(&ctx.db_context, &ctx.event_hub, dto)
+        .map_err(|e| format!("Error while merge_file: {:?}", e))
+}
@@ -6,7 +6,7 @@
 use crate::app_context::AppContext;
 use handling_manifest::{
     CheckReturnDto, CreateDto, CreateReturnDto, ExportToMermaidReturnDto, LoadDto, LoadReturnDto,
-    SaveDto, handling_manifest_controller,
+    RefactorDto, RefactorReturnDto, SaveDto, handling_manifest_controller,
 };
 
 pub fn load(ctx: &AppContext, dto: &LoadDto) -> Result<LoadReturnDto, String> {
@@ -38,3 +38,19 @@ pub fn check(ctx: &AppContext) -> Result<CheckReturnDto, String> {
     handling_manifest_controller::check(&ctx.db_context, &ctx.event_hub)
         .map_err(|e| format!("Error while check: {:?}", e))
 }
+
+pub fn refactor(
+    ctx: &AppContext,
+    stack_id: Option<u64>,
+    dto: &RefactorDto,
+) -> Result<RefactorReturnDto, String> {
+    let mut undo_redo_manager = ctx.undo_redo_manager.lock().unwrap();
+    handling_manifest_controller::refactor(
+        &ctx.db_context,
+        &ctx.event_hub,
+        &mut undo_redo_manager,
+        stack_id,
+        dto,
+    )
+    .map_err(|e| format!("Error while refactor: {:?}", e))
+}
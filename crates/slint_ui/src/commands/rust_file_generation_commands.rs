@@ -5,9 +5,9 @@
 
 use crate::app_context::AppContext;
 use rust_file_generation::{
-    FillRustFilesDto, FillRustFilesReturnDto, GenerateRustCodeDto, GenerateRustCodeReturnDto,
-    GenerateRustFilesDto, GenerateRustFilesReturnDto, GenerateRustPromptDto,
-    GenerateRustPromptReturnDto, rust_file_generation_controller,
+    FillCodeInRustFilesReturnDto, FillRustFilesDto, FillRustFilesReturnDto, GenerateRustCodeDto,
+    GenerateRustCodeReturnDto, GenerateRustFilesDto, GenerateRustFilesReturnDto,
+    GenerateRustPromptDto, GenerateRustPromptReturnDto, rust_file_generation_controller,
 };
 
 use common::long_operation::OperationProgress;
@@ -47,7 +47,7 @@ pub fn get_fill_code_in_rust_files_progress(
 pub fn get_fill_code_in_rust_files_result(
     ctx: &AppContext,
     operation_id: &str,
-) -> Result<Option<()>, String> {
+) -> Result<Option<FillCodeInRustFilesReturnDto>, String> {
     rust_file_generation_controller::get_fill_code_in_rust_files_result(
         &ctx.long_operation_manager.lock().unwrap(),
         operation_id,
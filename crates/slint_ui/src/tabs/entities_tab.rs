@@ -14,8 +14,9 @@ use common::direct_access::workspace::WorkspaceRelationshipField;
 use common::entities::{FieldRelationshipType, FieldType};
 use common::event::{DirectAccessEntity, EntityEvent, HandlingManifestEvent, Origin};
 use common::types::EntityId;
+use direct_access::EntityRelationshipDto;
 use direct_access::WorkspaceRelationshipDto;
-use direct_access::{EntityRelationshipDto, UpdateEntityDto};
+use handling_manifest::{RefactorDto, RefactorOperation, RefactorReturnDto};
 use slint::{ComponentHandle, Model, Timer};
 use std::sync::Arc;
 
@@ -708,6 +709,12 @@ fn field_type_to_string(field_type: &FieldType) -> &'static str {
         FieldType::DateTime => "DateTime",
         FieldType::Entity => "Entity",
         FieldType::Enum => "Enum",
+        FieldType::Decimal => "Decimal",
+        FieldType::Date => "Date",
+        FieldType::Time => "Time",
+        FieldType::Duration => "Duration",
+        FieldType::Bytes => "Bytes",
+        FieldType::Json => "Json",
     }
 }
 
@@ -722,6 +729,12 @@ fn string_to_field_type(s: &str) -> FieldType {
         "DateTime" => FieldType::DateTime,
         "Entity" => FieldType::Entity,
         "Enum" => FieldType::Enum,
+        "Decimal" => FieldType::Decimal,
+        "Date" => FieldType::Date,
+        "Time" => FieldType::Time,
+        "Duration" => FieldType::Duration,
+        "Bytes" => FieldType::Bytes,
+        "Json" => FieldType::Json,
         _ => FieldType::String,
     }
 }
@@ -1017,11 +1030,42 @@ fn setup_field_name_callback(app: &App, app_context: &Arc<AppContext>) {
         move |new_name| {
             if let Some(app) = app_weak.upgrade() {
                 let field_id = app.global::<EntitiesTabState>().get_selected_field_id();
-                let name_str = new_name.to_string();
-                if !name_str.is_empty() {
-                    update_field_helper(&app, &ctx, field_id, |field| {
-                        field.name = name_str;
-                    });
+                let entity_id = app.global::<EntitiesTabState>().get_selected_entity_id();
+                if new_name.is_empty() || field_id < 0 || entity_id < 0 {
+                    return;
+                }
+                let field_res = field_commands::get_field(&ctx, &(field_id as EntityId));
+                let entity_res = entity_commands::get_entity(&ctx, &(entity_id as EntityId));
+
+                // Rename, updating the list models displaying the field
+                if let (Ok(Some(field)), Ok(Some(entity))) = (field_res, entity_res) {
+                    if new_name == field.name {
+                        return;
+                    }
+                    let dto = RefactorDto {
+                        operation: RefactorOperation::RenameField,
+                        owner_name: entity.name,
+                        name: field.name,
+                        new_name: new_name.to_string(),
+                        ..Default::default()
+                    };
+
+                    match handling_manifest_commands::refactor(
+                        &ctx,
+                        Some(
+                            app.global::<EntitiesTabState>()
+                                .get_entities_undo_stack_id() as u64,
+                        ),
+                        &dto,
+                    ) {
+                        Ok(report) => {
+                            log::info!("Field name updated successfully");
+                            log_refactor_report(&report);
+                        }
+                        Err(e) => {
+                            log::error!("Failed to update field name: {}", e);
+                        }
+                    }
                 }
             }
         }
@@ -1330,25 +1374,31 @@ fn setup_entity_name_callbacks(app: &App, app_context: &Arc<AppContext>) {
                     &(current_entity_id as common::types::EntityId),
                 );
 
-                // Update
-                if let Ok(Some(mut entity)) = entity_res.map(|e| e.map(UpdateEntityDto::from)) {
+                // Rename, updating the references to the entity
+                if let Ok(Some(entity)) = entity_res {
                     if new_entity_name == entity.name {
                         return;
                     }
-                    entity.name = new_entity_name.to_string();
+                    let dto = RefactorDto {
+                        operation: RefactorOperation::RenameEntity,
+                        name: entity.name,
+                        new_name: new_entity_name.to_string(),
+                        ..Default::default()
+                    };
 
-                    let result = entity_commands::update_entity(
+                    let result = handling_manifest_commands::refactor(
                         &ctx,
                         Some(
                             app.global::<EntitiesTabState>()
                                 .get_entities_undo_stack_id() as u64,
                         ),
-                        &entity,
+                        &dto,
                     );
 
                     match result {
-                        Ok(_) => {
+                        Ok(report) => {
                             log::info!("Entity name updated successfully");
+                            log_refactor_report(&report);
                         }
                         Err(e) => {
                             log::error!("Failed to update entity name: {}", e);
@@ -1360,6 +1410,20 @@ fn setup_entity_name_callbacks(app: &App, app_context: &Arc<AppContext>) {
     });
 }
 
+/// Tell which generated files the rename affects on disk.
+fn log_refactor_report(report: &RefactorReturnDto) {
+    for file in &report.renamed_files {
+        log::info!(
+            "Generated file {} will be renamed to {}",
+            file.old_path,
+            file.new_path
+        );
+    }
+    for file in &report.orphaned_files {
+        log::warn!("{} will be orphaned on disk by the next generation", file);
+    }
+}
+
 fn setup_entity_only_for_heritage_callback(app: &App, app_context: &Arc<AppContext>) {
     app.global::<EntitiesTabState>()
         .on_entity_only_for_heritage_changed({
@@ -1443,6 +1507,8 @@ fn setup_entity_addition_callback(app: &App, app_context: &Arc<AppContext>) {
                         fields: vec![],
                         relationships: vec![],
                         undoable: true,
+                        undo_journal: false,
+                        source_file: String::new(),
                     };
 
                     match entity_commands::create_orphan_entity(
@@ -1534,6 +1600,16 @@ fn setup_field_addition_callback(app: &App, app_context: &Arc<AppContext>) {
                     list_model_displayed_field: None,
                     enum_name: None,
                     enum_values: vec![],
+                    min_value: None,
+                    max_value: None,
+                    min_length: None,
+                    max_length: None,
+                    pattern: None,
+                    not_empty: false,
+                    unique_in_parent: false,
+                    indexed: false,
+                    unique: false,
+                    default_value: None,
                 };
 
                 match field_commands::create_orphan_field(
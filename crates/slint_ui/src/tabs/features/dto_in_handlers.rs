@@ -148,6 +148,13 @@ pub fn dto_field_type_to_string(field_type: &DtoFieldType) -> &'static str {
         DtoFieldType::Uuid => "Uuid",
         DtoFieldType::DateTime => "DateTime",
         DtoFieldType::Enum => "Enum",
+        DtoFieldType::Dto => "Dto",
+        DtoFieldType::Decimal => "Decimal",
+        DtoFieldType::Date => "Date",
+        DtoFieldType::Time => "Time",
+        DtoFieldType::Duration => "Duration",
+        DtoFieldType::Bytes => "Bytes",
+        DtoFieldType::Json => "Json",
     }
 }
 
@@ -162,6 +169,13 @@ pub fn dto_field_type_to_index(field_type: &DtoFieldType) -> i32 {
         DtoFieldType::Uuid => 5,
         DtoFieldType::DateTime => 6,
         DtoFieldType::Enum => 7,
+        DtoFieldType::Dto => 8,
+        DtoFieldType::Decimal => 9,
+        DtoFieldType::Date => 10,
+        DtoFieldType::Time => 11,
+        DtoFieldType::Duration => 12,
+        DtoFieldType::Bytes => 13,
+        DtoFieldType::Json => 14,
     }
 }
 
@@ -176,6 +190,13 @@ pub fn index_to_dto_field_type(index: i32) -> DtoFieldType {
         5 => DtoFieldType::Uuid,
         6 => DtoFieldType::DateTime,
         7 => DtoFieldType::Enum,
+        8 => DtoFieldType::Dto,
+        9 => DtoFieldType::Decimal,
+        10 => DtoFieldType::Date,
+        11 => DtoFieldType::Time,
+        12 => DtoFieldType::Duration,
+        13 => DtoFieldType::Bytes,
+        14 => DtoFieldType::Json,
         _ => DtoFieldType::String,
     }
 }
@@ -822,6 +843,14 @@ pub fn setup_dto_in_field_addition_callback(app: &App, app_context: &Arc<AppCont
                         is_list: false,
                         enum_name: None,
                         enum_values: vec![],
+                        dto: None,
+                        min_value: None,
+                        max_value: None,
+                        min_length: None,
+                        max_length: None,
+                        pattern: None,
+                        not_empty: false,
+                        default_value: None,
                     };
 
                     match dto_field_commands::create_orphan_dto_field(
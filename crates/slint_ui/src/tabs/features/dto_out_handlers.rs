@@ -627,6 +627,14 @@ pub fn setup_dto_out_field_addition_callback(app: &App, app_context: &Arc<AppCon
                         is_list: false,
                         enum_name: None,
                         enum_values: vec![],
+                        dto: None,
+                        min_value: None,
+                        max_value: None,
+                        min_length: None,
+                        max_length: None,
+                        pattern: None,
+                        not_empty: false,
+                        default_value: None,
                     };
 
                     match dto_field_commands::create_orphan_dto_field(
@@ -496,6 +496,8 @@ pub fn setup_feature_addition_callback(app: &App, app_context: &Arc<AppContext>)
                         updated_at: chrono::Utc::now(),
                         name: "new_feature".to_string(),
                         use_cases: vec![],
+                        dtos: vec![],
+                        source_file: String::new(),
                     };
 
                     match feature_commands::create_orphan_feature(&ctx, Some(stack_id), &create_dto)
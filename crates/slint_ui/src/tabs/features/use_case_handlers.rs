@@ -658,6 +658,8 @@ pub fn setup_use_case_addition_callback(app: &App, app_context: &Arc<AppContext>
                         long_operation: false,
                         dto_in: None,
                         dto_out: None,
+                        shared_dto_in: None,
+                        shared_dto_out: None,
                         entities: vec![],
                     };
 
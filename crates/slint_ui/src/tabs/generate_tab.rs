@@ -86,6 +86,8 @@ fn read_files_from_db(app_context: &Arc<AppContext>) -> Result<Vec<FileDisplayDa
 fn status_to_color(status: &FileStatus) -> slint::Color {
     match status {
         FileStatus::Modified => slint::Color::from_rgb_u8(255, 152, 0), // orange
+        FileStatus::Merged => slint::Color::from_rgb_u8(33, 150, 243),  // blue
+        FileStatus::Conflict => slint::Color::from_rgb_u8(244, 67, 54), // red
         FileStatus::New => slint::Color::from_rgb_u8(76, 175, 80),      // green
         FileStatus::Unchanged => slint::Color::from_rgb_u8(158, 158, 158), // grey
         FileStatus::Unknown => slint::Color::default(),                 // transparent
@@ -225,7 +227,7 @@ fn update_file_display_from_db(app: &App, app_context: &Arc<AppContext>) {
     let filtered: Vec<&FileDisplayData> = files
         .iter()
         .filter(|f| match f.status {
-            FileStatus::Modified => show_modified,
+            FileStatus::Modified | FileStatus::Merged | FileStatus::Conflict => show_modified,
             FileStatus::New => show_new,
             FileStatus::Unchanged => show_unchanged,
             FileStatus::Unknown => true, // always show Unknown status
@@ -303,7 +305,7 @@ fn update_full_display(app: &App, app_context: &Arc<AppContext>) {
     let filtered: Vec<&FileDisplayData> = files
         .iter()
         .filter(|f| match f.status {
-            FileStatus::Modified => show_modified,
+            FileStatus::Modified | FileStatus::Merged | FileStatus::Conflict => show_modified,
             FileStatus::New => show_new,
             FileStatus::Unchanged => show_unchanged,
             FileStatus::Unknown => true,
@@ -371,22 +373,24 @@ fn get_fill_code_progress(
     }
 }
 
-/// Get fill_code result
+/// Get fill_code result: the orphaned user regions, once the operation is done
 fn get_fill_code_result(
     app: &App,
     app_context: &Arc<AppContext>,
     operation_id: &str,
-) -> Result<Option<()>, String> {
-    match determine_language(app, app_context)? {
+) -> Result<Option<Vec<String>>, String> {
+    Ok(match determine_language(app, app_context)? {
         Language::Rust => rust_file_generation_commands::get_fill_code_in_rust_files_result(
             app_context,
             operation_id,
-        ),
+        )?
+        .map(|result| result.orphaned_user_regions),
         Language::CppQt => cpp_qt_file_generation_commands::get_fill_code_in_cpp_qt_files_result(
             app_context,
             operation_id,
-        ),
-    }
+        )?
+        .map(|result| result.orphaned_user_regions),
+    })
 }
 
 /// Poll fill_code operation, then fill status and update display when done
@@ -408,9 +412,15 @@ fn poll_fill_code_result(app_weak: slint::Weak<App>, ctx: Arc<AppContext>, opera
 
         // Check result
         match get_fill_code_result(&app, &ctx, &operation_id) {
-            Ok(Some(())) => {
+            Ok(Some(orphaned_user_regions)) => {
                 // Fill code complete → fill status → update display
                 log::info!("Fill code complete, computing status...");
+                if !orphaned_user_regions.is_empty() {
+                    app.global::<AppState>().set_error_message(SharedString::from(format!(
+                        "User regions no longer matching the generated code, lost on overwrite: {}",
+                        orphaned_user_regions.join(", ")
+                    )));
+                }
                 app.global::<AppState>()
                     .set_fill_code_message(SharedString::from("Comparing with disk..."));
 
@@ -169,6 +169,39 @@ if $RUN_RUST; then
     cd "$RUST_TEST_PROJECT"
     "$REPO_ROOT/target/debug/qleany" gen -m "$RUST_MANIFEST"
 
+    # The generated frontends are not members of tests/rust/Cargo.toml: build
+    # them in the generated workspace, before its Cargo.toml is removed below
+    if ! $GENERATE_ONLY; then
+        RUST_CLI_CARGO="$RUST_TEST_PROJECT/crates/cli/Cargo.toml"
+        if [ -f "$RUST_CLI_CARGO" ]; then
+            RUST_CLI_PACKAGE=$(grep -oP '^name = "\K[^"]+' "$RUST_CLI_CARGO")
+
+            echo ""
+            echo "--- Rust: cargo build (generated CLI) ---"
+            cargo build --manifest-path "$RUST_TEST_PROJECT/Cargo.toml" -p "$RUST_CLI_PACKAGE"
+
+            echo ""
+            echo "--- Rust: smoke test (generated CLI) ---"
+            CLI_OUTPUT=$(printf 'root create\nroot get 1\nexit\n' \
+                | "$RUST_TEST_PROJECT/target/debug/$RUST_CLI_PACKAGE" --format json shell)
+            if [ "$(grep -c '"id": 1' <<< "$CLI_OUTPUT")" -ne 2 ]; then
+                echo "Generated CLI did not create and get the root entity:"
+                echo "$CLI_OUTPUT"
+                exit 1
+            fi
+            echo "Generated CLI created and got the root entity"
+        fi
+
+        RUST_SLINT_CARGO="$RUST_TEST_PROJECT/crates/slint_ui/Cargo.toml"
+        if [ -f "$RUST_SLINT_CARGO" ]; then
+            RUST_SLINT_PACKAGE=$(grep -oP '^name = "\K[^"]+' "$RUST_SLINT_CARGO")
+
+            echo ""
+            echo "--- Rust: cargo build (generated Slint UI) ---"
+            cargo build --manifest-path "$RUST_TEST_PROJECT/Cargo.toml" -p "$RUST_SLINT_PACKAGE"
+        fi
+    fi
+
     # Remove the generated workspace Cargo.toml to avoid nested workspace conflict
     # (the outer tests/rust/Cargo.toml is the real workspace root)
     rm -f "$RUST_TEST_PROJECT/Cargo.toml"
@@ -182,6 +215,24 @@ if $RUN_RUST; then
         echo ""
         echo "--- Rust: cargo test (functional tests) ---"
         cargo test --workspace
+
+        # Same example with the SQLite storage backend: the generated transaction
+        # and undo/redo tests, then the functional tests, run against it
+        echo ""
+        echo "--- Rust: regenerate with storage: sqlite ---"
+        SQLITE_MANIFEST_DIR="$(mktemp -d)"
+        cp -r "$(dirname "$RUST_MANIFEST")/." "$SQLITE_MANIFEST_DIR/"
+        sed -i 's/^  language: rust$/  language: rust\n  storage: sqlite/' "$SQLITE_MANIFEST_DIR/qleany.yaml"
+        rm -rf "${RUST_TEST_PROJECT:?}"/*
+        cd "$RUST_TEST_PROJECT"
+        "$REPO_ROOT/target/debug/qleany" gen -m "$SQLITE_MANIFEST_DIR/qleany.yaml"
+        rm -f "$RUST_TEST_PROJECT/Cargo.toml"
+        rm -rf "$SQLITE_MANIFEST_DIR"
+
+        echo ""
+        echo "--- Rust: cargo test (SQLite backend) ---"
+        cd "$REPO_ROOT/tests/rust"
+        cargo test --workspace
     fi
 
     cd "$REPO_ROOT"
@@ -224,11 +275,15 @@ if $RUN_CPPQT; then
 
         echo ""
         echo "--- C++/Qt: offscreen smoke test (QtWidgets UI) ---"
-        if [ -x ./tested_project/src/qtwidgets_app/FullCppQtAppDesktop ]; then
-            QT_QPA_PLATFORM=offscreen timeout 5 ./tested_project/src/qtwidgets_app/FullCppQtAppDesktop || true
+        QTWIDGETS_CMAKE="$CPPQT_TEST_PROJECT/src/qtwidgets_app/CMakeLists.txt"
+        if [ -f "$QTWIDGETS_CMAKE" ]; then
+            # The generated table models and editors must build when the manifest asks for them
+            QTWIDGETS_APP=$(grep -oP '^set\(APP_NAME \K\w+' "$QTWIDGETS_CMAKE")
+            cmake --build . --target "$QTWIDGETS_APP" -j"$(nproc)"
+            QT_QPA_PLATFORM=offscreen timeout 5 "./tested_project/src/qtwidgets_app/$QTWIDGETS_APP" || true
             echo "QtWidgets UI launched and exited (offscreen)"
         else
-            echo "QtWidgets executable not found, skipping"
+            echo "QtWidgets UI not generated, skipping"
         fi
 
         echo ""
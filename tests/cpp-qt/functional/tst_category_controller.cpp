@@ -1,5 +1,6 @@
 // Functional tests for CategoryController (leaf entity, target of many_to_one relationships)
 #include <QCoreApplication>
+#include <QSignalSpy>
 #include <QTest>
 #include <QCoro/QCoroTask>
 #include <QCoro/QCoroTest>
@@ -45,6 +46,10 @@ class TestCategoryController : public QObject
     // update
     void testUpdateFields();
 
+    // unique_in_parent
+    void testCreateDuplicateNameFails();
+    void testUpdateToSiblingNameFails();
+
     // remove
     void testRemove();
 
@@ -59,6 +64,7 @@ class TestCategoryController : public QObject
     DA::TeamMember::TeamMemberController *m_teamMemberCtrl = nullptr;
     DA::Root::RootController *m_rootCtrl = nullptr;
     DA::Workspace::WorkspaceController *m_workspaceCtrl = nullptr;
+    FullCppQtApp::Common::DirectAccess::EventRegistry *m_eventRegistry = nullptr;
 };
 
 void TestCategoryController::initTestCase()
@@ -67,7 +73,8 @@ void TestCategoryController::initTestCase()
 
     auto *locator = new FullCppQtApp::Common::ServiceLocator(this);
     locator->setDbContext(new FullCppQtApp::Common::Database::DbContext(this));
-    locator->setEventRegistry(new FullCppQtApp::Common::DirectAccess::EventRegistry(this));
+    m_eventRegistry = new FullCppQtApp::Common::DirectAccess::EventRegistry(this);
+    locator->setEventRegistry(m_eventRegistry);
     locator->setFeatureEventRegistry(new FullCppQtApp::Common::Features::FeatureEventRegistry(this));
     locator->setUndoRedoSystem(new FullCppQtApp::Common::UndoRedo::UndoRedoSystem(this));
     locator->setLongOperationManager(new FullCppQtApp::Common::LongOperation::LongOperationManager(this));
@@ -202,6 +209,54 @@ void TestCategoryController::testUpdateFields()
     QCOMPARE(updated.first().icon, u"folder"_s);
 }
 
+// ---------------------------------------------------------------------------
+// unique_in_parent
+// ---------------------------------------------------------------------------
+
+void TestCategoryController::testCreateDuplicateNameFails()
+{
+    int wsId = createWorkspace();
+
+    DA::Category::CreateCategoryDto dto;
+    dto.name = u"Dup"_s;
+    QCoro::waitFor(m_categoryCtrl->create({dto}, wsId));
+
+    QSignalSpy errorSpy(m_eventRegistry, &FullCppQtApp::Common::DirectAccess::EventRegistry::errorOccurred);
+    auto created = QCoro::waitFor(m_categoryCtrl->create({dto}, wsId));
+    QVERIFY(created.isEmpty());
+    QCOMPARE(errorSpy.count(), 1);
+}
+
+void TestCategoryController::testUpdateToSiblingNameFails()
+{
+    int wsId = createWorkspace();
+
+    DA::Category::CreateCategoryDto takenDto, freeDto;
+    takenDto.name = u"Taken"_s;
+    freeDto.name = u"Free"_s;
+    auto created = QCoro::waitFor(m_categoryCtrl->create({takenDto, freeDto}, wsId));
+    auto cat = created[1];
+
+    DA::Category::UpdateCategoryDto updateCat;
+    updateCat.id = cat.id;
+    updateCat.createdAt = cat.createdAt;
+    updateCat.updatedAt = cat.updatedAt;
+    updateCat.name = u"Taken"_s;
+
+    QSignalSpy errorSpy(m_eventRegistry, &FullCppQtApp::Common::DirectAccess::EventRegistry::errorOccurred);
+    auto updated = QCoro::waitFor(m_categoryCtrl->update({updateCat}));
+    QVERIFY(updated.isEmpty());
+    QCOMPARE(errorSpy.count(), 1);
+    auto fetched = QCoro::waitFor(m_categoryCtrl->get({cat.id}));
+    QCOMPARE(fetched.first().name, u"Free"_s);
+
+    // keeping its own name is not a conflict
+    updateCat.name = u"Free"_s;
+    updateCat.icon = u"folder"_s;
+    updated = QCoro::waitFor(m_categoryCtrl->update({updateCat}));
+    QCOMPARE(updated.first().icon, u"folder"_s);
+}
+
 // ---------------------------------------------------------------------------
 // remove
 // ---------------------------------------------------------------------------
@@ -56,6 +56,7 @@ class TestFeatureUseCases : public QObject
     void testBatchAssignTasks();
     void testGetTaskSummary();
     void testCleanupCompleted();
+    void testSelectTasks();
 
   private:
     struct ScaffoldIds
@@ -275,5 +276,27 @@ void TestFeatureUseCases::testCleanupCompleted()
     QCoro::waitFor(m_tmCtrl->cleanupCompleted());
 }
 
+void TestFeatureUseCases::testSelectTasks()
+{
+    auto scaffold = createScaffold();
+
+    DA::Task::CreateTaskDto taskDto;
+    taskDto.title = u"Select1"_s;
+    taskDto.dueDate = QDateTime::currentDateTimeUtc();
+    auto tasks = QCoro::waitFor(m_taskCtrl->create({taskDto}, scaffold.projectId));
+
+    TM::TaskRefDto task;
+    task.taskId = static_cast<uint>(tasks[0].id);
+    task.label = u"Select1"_s;
+
+    TM::TaskSelectionDto dto;
+    dto.tasks = {task};
+    dto.focus = task;
+
+    // Should not throw: the nested DTOs satisfy their constraints
+    auto result = QCoro::waitFor(m_tmCtrl->selectTasks(dto));
+    Q_UNUSED(result);
+}
+
 QTEST_MAIN(TestFeatureUseCases)
 #include "tst_feature_use_cases.moc"
@@ -49,10 +49,18 @@ class TestTagController : public QObject
     // getAll
     void testGetAll();
 
+    // query / count
+    void testQueryFilterSortAndPage();
+    void testCountIgnoresPage();
+
     // update
     void testUpdateFields();
     void testUpdateMultiple();
 
+    // unique name
+    void testCreateWithUsedUniqueValueFails();
+    void testUpdateToUsedUniqueValueFails();
+
     // remove
     void testRemove();
     void testRemoveMultiple();
@@ -257,6 +265,49 @@ void TestTagController::testGetAll()
     QVERIFY(all.size() >= 1);
 }
 
+// ---------------------------------------------------------------------------
+// query / count
+// ---------------------------------------------------------------------------
+
+void TestTagController::testQueryFilterSortAndPage()
+{
+    using Field = DA::Tag::TagQueryField;
+    using FullCppQtApp::Common::DirectAccess::QueryOperator;
+    using FullCppQtApp::Common::DirectAccess::SortOrder;
+
+    int wsId = createWorkspaceForTags();
+    QCoro::waitFor(m_tagCtrl->create({makeTagDto(u"qry-b"_s, u"#FF0000"_s), makeTagDto(u"other"_s, u"#FF0000"_s),
+                                      makeTagDto(u"qry-a"_s, u"#00FF00"_s), makeTagDto(u"qry-c"_s, u"#FF0000"_s)},
+                                     wsId));
+
+    DA::Tag::TagQuery query;
+    query.filter(Field::Name, QueryOperator::StartsWith, u"qry-"_s).sortBy(Field::Name, SortOrder::Descending);
+    auto found = QCoro::waitFor(m_tagCtrl->query(query));
+    QCOMPARE(found.size(), 3);
+    QCOMPARE(found[0].name, u"qry-c"_s);
+    QCOMPARE(found[1].name, u"qry-b"_s);
+    QCOMPARE(found[2].name, u"qry-a"_s);
+
+    query.filter(Field::Color, QueryOperator::Equal, u"#FF0000"_s).page(1, 1);
+    auto paged = QCoro::waitFor(m_tagCtrl->query(query));
+    QCOMPARE(paged.size(), 1);
+    QCOMPARE(paged.first().name, u"qry-b"_s);
+}
+
+void TestTagController::testCountIgnoresPage()
+{
+    using Field = DA::Tag::TagQueryField;
+    using FullCppQtApp::Common::DirectAccess::QueryOperator;
+
+    int wsId = createWorkspaceForTags();
+    QCoro::waitFor(m_tagCtrl->create({makeTagDto(u"cnt-1"_s), makeTagDto(u"cnt-2"_s), makeTagDto(u"cnt-3"_s)}, wsId));
+
+    DA::Tag::TagQuery query;
+    query.filter(Field::Name, QueryOperator::In, QVariantList{u"cnt-1"_s, u"cnt-3"_s, u"missing"_s}).page(0, 1);
+    QCOMPARE(QCoro::waitFor(m_tagCtrl->count(query)), 2);
+    QCOMPARE(QCoro::waitFor(m_tagCtrl->query(query)).size(), 1);
+}
+
 // ---------------------------------------------------------------------------
 // update
 // ---------------------------------------------------------------------------
@@ -313,6 +364,52 @@ void TestTagController::testUpdateMultiple()
     QCOMPARE(updated[1].name, u"T2-updated"_s);
 }
 
+// ---------------------------------------------------------------------------
+// unique name
+// ---------------------------------------------------------------------------
+
+void TestTagController::testCreateWithUsedUniqueValueFails()
+{
+    using Field = DA::Tag::TagQueryField;
+    using FullCppQtApp::Common::DirectAccess::QueryOperator;
+
+    int wsId = createWorkspaceForTags();
+    QCoro::waitFor(m_tagCtrl->create({makeTagDto(u"uq-taken"_s)}, wsId));
+
+    QSignalSpy errorSpy(m_eventRegistry, &FullCppQtApp::Common::DirectAccess::EventRegistry::errorOccurred);
+    auto created = QCoro::waitFor(m_tagCtrl->create({makeTagDto(u"uq-taken"_s)}, wsId));
+    QVERIFY(created.isEmpty());
+    QCOMPARE(errorSpy.count(), 1);
+    QVERIFY(errorSpy.first().at(1).toString().contains(u"Tag.name is already used"_s));
+
+    DA::Tag::TagQuery query;
+    query.filter(Field::Name, QueryOperator::Equal, u"uq-taken"_s);
+    QCOMPARE(QCoro::waitFor(m_tagCtrl->count(query)), 1);
+}
+
+void TestTagController::testUpdateToUsedUniqueValueFails()
+{
+    int wsId = createWorkspaceForTags();
+    auto created = QCoro::waitFor(m_tagCtrl->create({makeTagDto(u"uq-first"_s), makeTagDto(u"uq-second"_s)}, wsId));
+    auto second = created[1];
+
+    DA::Tag::UpdateTagDto updateTag;
+    updateTag.id = second.id;
+    updateTag.createdAt = second.createdAt;
+    updateTag.updatedAt = second.updatedAt;
+    updateTag.name = u"uq-first"_s;
+    updateTag.color = second.color;
+
+    QSignalSpy errorSpy(m_eventRegistry, &FullCppQtApp::Common::DirectAccess::EventRegistry::errorOccurred);
+    auto updated = QCoro::waitFor(m_tagCtrl->update({updateTag}));
+    QVERIFY(updated.isEmpty());
+    QCOMPARE(errorSpy.count(), 1);
+
+    // The update was rolled back
+    auto fetched = QCoro::waitFor(m_tagCtrl->get({second.id}));
+    QCOMPARE(fetched.first().name, u"uq-second"_s);
+}
+
 // ---------------------------------------------------------------------------
 // remove
 // ---------------------------------------------------------------------------
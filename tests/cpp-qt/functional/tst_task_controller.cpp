@@ -1,5 +1,6 @@
 // Functional tests for TaskController (undoable entity — with owner, children, single model, list model)
 #include <QCoreApplication>
+#include <QJsonObject>
 #include <QSignalSpy>
 #include <QTest>
 #include <QUuid>
@@ -45,6 +46,7 @@ class TestTaskController : public QObject
 
     // create (with owner)
     void testCreateWithOwner();
+    void testCreateUsesDeclaredDefaults();
     void testCreateMultipleWithOwner();
     void testCreateAtIndex();
 
@@ -62,6 +64,7 @@ class TestTaskController : public QObject
     void testUpdateStringFields();
     void testUpdateBoolField();
     void testUpdateNumericFields();
+    void testUpdateRichScalarFields();
     void testUpdateEnumField();
     void testUpdateDateTimeField();
 
@@ -91,6 +94,8 @@ class TestTaskController : public QObject
     void testUpdateEmitsUpdatedEvent();
     void testRemoveEmitsRemovedEvent();
     void testRelationshipChangeEmitsEvent();
+    void testUpdateEmitsChangedFields();
+    void testRelationshipChangeEmitsAddedAndRemovedIds();
 
     // SingleTask model
     void testSingleTaskLoadsOnSetId();
@@ -105,6 +110,7 @@ class TestTaskController : public QObject
     void testListModelReactsToReorderByMove();
     void testListModelReactsToReorderBySetRelationshipIds();
     void testListModelReactsToReorderByUpdateParent();
+    void testListModelRefreshesChangedRolesOnly();
 
   private:
     static DA::Task::CreateTaskDto makeTaskDto(const QString &title, const QString &content = u""_s)
@@ -229,6 +235,25 @@ void TestTaskController::testCreateWithOwner()
     QCOMPARE(results.first().difficulty, DA::Task::TaskDifficulty::Medium);
 }
 
+void TestTaskController::testCreateUsesDeclaredDefaults()
+{
+    auto scaffold = createProjectScaffold();
+    const auto before = QDateTime::currentDateTimeUtc().addSecs(-1);
+
+    auto results = QCoro::waitFor(m_taskCtrl->create({DA::Task::CreateTaskDto{}}, scaffold.projectId));
+    QCOMPARE(results.size(), 1);
+    const auto &task = results.first();
+    QCOMPARE(task.title, u"Untitled"_s);
+    QCOMPARE(task.weight, 1.0f);
+    QCOMPARE(task.effortPoints, 1u);
+    QCOMPARE(task.budget, u"0.00"_s);
+    QCOMPARE(task.difficulty, DA::Task::TaskDifficulty::Medium);
+    QVERIFY(task.dueDate >= before);
+    // fields without a declared default keep the type's default
+    QCOMPARE(task.isDone, false);
+    QVERIFY(task.content.isEmpty());
+}
+
 void TestTaskController::testCreateMultipleWithOwner()
 {
     auto scaffold = createProjectScaffold();
@@ -397,6 +422,43 @@ void TestTaskController::testUpdateNumericFields()
     QCOMPARE(fetched.first().effortPoints, 42u);
 }
 
+void TestTaskController::testUpdateRichScalarFields()
+{
+    auto scaffold = createProjectScaffold();
+    auto created = QCoro::waitFor(m_taskCtrl->create({makeTaskDto(u"RichTest"_s)}, scaffold.projectId));
+    auto task = created.first();
+    QCOMPARE(task.budget, u"0"_s);
+    QVERIFY(!task.startDay.has_value());
+    QVERIFY(task.reminderTimes.isEmpty());
+
+    DA::Task::UpdateTaskDto updateTask;
+    updateTask.id = task.id;
+    updateTask.createdAt = task.createdAt;
+    updateTask.updatedAt = task.updatedAt;
+    updateTask.title = task.title;
+    updateTask.content = task.content;
+    updateTask.isDone = task.isDone;
+    updateTask.dueDate = task.dueDate;
+    updateTask.weight = task.weight;
+    updateTask.effortPoints = task.effortPoints;
+    updateTask.budget = u"1234.56"_s;
+    updateTask.startDay = QDate(2026, 3, 14);
+    updateTask.reminderTimes = {QTime(9, 30), QTime(17, 0, 15)};
+    updateTask.timeSpent = 5400000;
+    updateTask.attachment = QByteArray("\x00\x01\xff", 3);
+    updateTask.properties = QJsonObject{{u"color"_s, u"red"_s}, {u"pinned"_s, true}};
+    updateTask.difficulty = task.difficulty;
+    QCoro::waitFor(m_taskCtrl->update({updateTask}));
+
+    auto fetched = QCoro::waitFor(m_taskCtrl->get({task.id})).first();
+    QCOMPARE(fetched.budget, u"1234.56"_s);
+    QCOMPARE(fetched.startDay, std::optional<QDate>(QDate(2026, 3, 14)));
+    QCOMPARE(fetched.reminderTimes, (QList<QTime>{QTime(9, 30), QTime(17, 0, 15)}));
+    QCOMPARE(fetched.timeSpent, qint64(5400000));
+    QCOMPARE(fetched.attachment, QByteArray("\x00\x01\xff", 3));
+    QCOMPARE(fetched.properties, updateTask.properties);
+}
+
 void TestTaskController::testUpdateEnumField()
 {
     auto scaffold = createProjectScaffold();
@@ -832,6 +894,62 @@ void TestTaskController::testRelationshipChangeEmitsEvent()
     QTRY_VERIFY(spy.count() >= 1);
 }
 
+void TestTaskController::testUpdateEmitsChangedFields()
+{
+    auto scaffold = createProjectScaffold();
+    auto created = QCoro::waitFor(m_taskCtrl->create({makeTaskDto(u"Fields"_s, u"Same"_s)}, scaffold.projectId));
+    auto task = created.first();
+
+    auto taskEvents = m_eventRegistry->taskEvents();
+    QSignalSpy spy(taskEvents.data(), &FullCppQtApp::Common::DirectAccess::Task::TaskEvents::fieldsUpdated);
+
+    DA::Task::UpdateTaskDto updateTask;
+    updateTask.id = task.id;
+    updateTask.createdAt = task.createdAt;
+    updateTask.updatedAt = task.updatedAt;
+    updateTask.title = u"Fields2"_s;
+    updateTask.content = task.content;
+    updateTask.isDone = task.isDone;
+    updateTask.dueDate = task.dueDate;
+    updateTask.weight = task.weight;
+    updateTask.effortPoints = task.effortPoints;
+    updateTask.difficulty = task.difficulty;
+    QCoro::waitFor(m_taskCtrl->update({updateTask}));
+
+    QTRY_VERIFY(spy.count() >= 1);
+    QVERIFY(spy.last().at(0).value<QList<int>>().contains(task.id));
+    auto fields = spy.last().at(1).value<QStringList>();
+    QVERIFY(fields.contains(u"title"_s));
+    QVERIFY(!fields.contains(u"content"_s));
+    QVERIFY(!fields.contains(u"isDone"_s));
+}
+
+void TestTaskController::testRelationshipChangeEmitsAddedAndRemovedIds()
+{
+    auto scaffold = createProjectScaffold();
+    auto task = QCoro::waitFor(m_taskCtrl->create({makeTaskDto(u"RelIds"_s)}, scaffold.projectId));
+    int taskId = task.first().id;
+
+    DA::Tag::CreateTagDto tagA, tagB, tagC;
+    tagA.name = u"RelIdsA"_s;
+    tagB.name = u"RelIdsB"_s;
+    tagC.name = u"RelIdsC"_s;
+    auto tags = QCoro::waitFor(m_tagCtrl->create({tagA, tagB, tagC}, scaffold.workspaceId));
+    QCOMPARE(tags.size(), 3);
+
+    QCoro::waitFor(m_taskCtrl->setRelationshipIds(taskId, DA::Task::TaskRelationshipField::Tags, {tags[0].id, tags[1].id}));
+
+    auto taskEvents = m_eventRegistry->taskEvents();
+    QSignalSpy spy(taskEvents.data(), &FullCppQtApp::Common::DirectAccess::Task::TaskEvents::relationshipIdsChanged);
+
+    QCoro::waitFor(m_taskCtrl->setRelationshipIds(taskId, DA::Task::TaskRelationshipField::Tags, {tags[1].id, tags[2].id}));
+
+    QTRY_VERIFY(spy.count() >= 1);
+    QCOMPARE(spy.last().at(0).toInt(), taskId);
+    QCOMPARE(spy.last().at(2).value<QList<int>>(), QList<int>{tags[2].id});
+    QCOMPARE(spy.last().at(3).value<QList<int>>(), QList<int>{tags[0].id});
+}
+
 // ---------------------------------------------------------------------------
 // SingleTask model
 // ---------------------------------------------------------------------------
@@ -1128,4 +1246,39 @@ void TestTaskController::testListModelReactsToReorderByUpdateParent()
 }
 
 QTEST_MAIN(TestTaskController)
+void TestTaskController::testListModelRefreshesChangedRolesOnly()
+{
+    auto scaffold = createProjectScaffold();
+
+    auto created = QCoro::waitFor(m_taskCtrl->create({makeTaskDto(u"Roles"_s, u"Unchanged"_s)}, scaffold.projectId));
+
+    DA::Project::ProjectTasksListModel model(this);
+    model.setProjectId(scaffold.projectId);
+    QTRY_COMPARE(model.rowCount(), 1);
+
+    QSignalSpy spy(&model, &QAbstractItemModel::dataChanged);
+
+    auto task = created.first();
+    DA::Task::UpdateTaskDto updateTask;
+    updateTask.id = task.id;
+    updateTask.createdAt = task.createdAt;
+    updateTask.updatedAt = task.updatedAt;
+    updateTask.title = task.title;
+    updateTask.content = u"Changed"_s;
+    updateTask.isDone = task.isDone;
+    updateTask.dueDate = task.dueDate;
+    updateTask.weight = task.weight;
+    updateTask.effortPoints = task.effortPoints;
+    updateTask.difficulty = task.difficulty;
+    QCoro::waitFor(m_taskCtrl->update({updateTask}));
+
+    QTRY_VERIFY(spy.count() >= 1);
+    auto roles = spy.last().at(2).value<QList<int>>();
+    QVERIFY(roles.contains(DA::Project::ProjectTasksListModel::Roles::ContentRole));
+    // title is the displayed field: it did not change
+    QVERIFY(!roles.contains(DA::Project::ProjectTasksListModel::Roles::TitleRole));
+    QVERIFY(!roles.contains(Qt::DisplayRole));
+    QCOMPARE(model.data(model.index(0), DA::Project::ProjectTasksListModel::Roles::ContentRole).toString(), u"Changed"_s);
+}
+
 #include "tst_task_controller.moc"
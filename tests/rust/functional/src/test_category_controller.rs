@@ -1,6 +1,7 @@
 // Functional tests for Category controller (leaf entity, target of many_to_one relationships)
 
 use crate::helpers::{self, TestContext};
+use common::error::{ValidationError, ValidationErrorKind};
 use direct_access::*;
 
 fn setup() -> (TestContext, helpers::Scaffold) {
@@ -93,6 +94,116 @@ fn test_update_fields() {
     assert_eq!(updated.icon, "folder");
 }
 
+// ---------------------------------------------------------------------------
+// unique_in_parent
+// ---------------------------------------------------------------------------
+
+fn is_not_unique(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<ValidationError>(),
+            Some(ValidationError { field: "name", kind: ValidationErrorKind::NotUnique, .. })
+        )
+    })
+}
+
+#[test]
+fn test_create_duplicate_name_rejected() {
+    let (mut ctx, s) = setup();
+    helpers::create_category(&mut ctx, s.workspace_id, "Dup");
+    let err = category_controller::create(
+        &ctx.db, &ctx.hub, &mut ctx.undo, None,
+        &CreateCategoryDto { name: "Dup".into(), ..Default::default() },
+        s.workspace_id, -1,
+    ).unwrap_err();
+    assert!(is_not_unique(&err), "unexpected error: {err:#}");
+}
+
+#[test]
+fn test_update_to_sibling_name_rejected() {
+    let (mut ctx, s) = setup();
+    helpers::create_category(&mut ctx, s.workspace_id, "Taken");
+    let id = helpers::create_category(&mut ctx, s.workspace_id, "Free");
+    let dto = category_controller::get(&ctx.db, &id).unwrap().unwrap();
+    let mut update_dto: UpdateCategoryDto = dto.into();
+    update_dto.name = "Taken".into();
+    let err = category_controller::update(&ctx.db, &ctx.hub, &mut ctx.undo, None, &update_dto).unwrap_err();
+    assert!(is_not_unique(&err), "unexpected error: {err:#}");
+    assert_eq!(category_controller::get(&ctx.db, &id).unwrap().unwrap().name, "Free");
+
+    // keeping its own name is not a conflict
+    update_dto.name = "Free".into();
+    update_dto.icon = "folder".into();
+    category_controller::update(&ctx.db, &ctx.hub, &mut ctx.undo, None, &update_dto).unwrap();
+}
+
+#[test]
+fn test_update_multi_swapping_names() {
+    let (mut ctx, s) = setup();
+    let a = helpers::create_category(&mut ctx, s.workspace_id, "A");
+    let b = helpers::create_category(&mut ctx, s.workspace_id, "B");
+    let mut a_dto: UpdateCategoryDto = category_controller::get(&ctx.db, &a).unwrap().unwrap().into();
+    let mut b_dto: UpdateCategoryDto = category_controller::get(&ctx.db, &b).unwrap().unwrap().into();
+    // the updated siblings are compared by their new values
+    a_dto.name = "B".into();
+    b_dto.name = "A".into();
+    category_controller::update_multi(&ctx.db, &ctx.hub, &mut ctx.undo, None, &[a_dto.clone(), b_dto.clone()]).unwrap();
+    a_dto.name = "C".into();
+    b_dto.name = "C".into();
+    let err = category_controller::update_multi(&ctx.db, &ctx.hub, &mut ctx.undo, None, &[a_dto, b_dto]).unwrap_err();
+    assert!(is_not_unique(&err), "unexpected error: {err:#}");
+}
+
+#[test]
+fn test_set_relationship_duplicate_name_rejected() {
+    let (mut ctx, s) = setup();
+    let kept = helpers::create_category(&mut ctx, s.workspace_id, "Dup");
+    let orphan = category_controller::create_orphan(
+        &ctx.db, &ctx.hub, &mut ctx.undo, None,
+        &CreateCategoryDto { name: "Dup".into(), ..Default::default() },
+    ).unwrap();
+    let err = workspace_controller::set_relationship(
+        &ctx.db, &ctx.hub, &mut ctx.undo, None,
+        &WorkspaceRelationshipDto {
+            id: s.workspace_id,
+            field: WorkspaceRelationshipField::Categories,
+            right_ids: vec![kept, orphan.id],
+        },
+    ).unwrap_err();
+    assert!(is_not_unique(&err), "unexpected error: {err:#}");
+    let categories = workspace_controller::get_relationship(
+        &ctx.db, &s.workspace_id, &WorkspaceRelationshipField::Categories,
+    ).unwrap();
+    assert_eq!(categories, vec![kept]);
+}
+
+#[test]
+fn test_move_relationship_duplicate_name_rejected() {
+    let (mut ctx, s) = setup();
+    let kept = helpers::create_category(&mut ctx, s.workspace_id, "Dup");
+    let orphan = category_controller::create_orphan(
+        &ctx.db, &ctx.hub, &mut ctx.undo, None,
+        &CreateCategoryDto { name: "Dup".into(), ..Default::default() },
+    ).unwrap();
+    let err = workspace_controller::move_relationship(
+        &ctx.db, &ctx.hub, &mut ctx.undo, None,
+        &s.workspace_id, &WorkspaceRelationshipField::Categories, &[orphan.id], 0,
+    ).unwrap_err();
+    assert!(is_not_unique(&err), "unexpected error: {err:#}");
+    let categories = workspace_controller::get_relationship(
+        &ctx.db, &s.workspace_id, &WorkspaceRelationshipField::Categories,
+    ).unwrap();
+    assert_eq!(categories, vec![kept]);
+
+    // reordering the children without a new one is not a conflict
+    let other = helpers::create_category(&mut ctx, s.workspace_id, "Other");
+    let moved = workspace_controller::move_relationship(
+        &ctx.db, &ctx.hub, &mut ctx.undo, None,
+        &s.workspace_id, &WorkspaceRelationshipField::Categories, &[other], 0,
+    ).unwrap();
+    assert_eq!(moved, vec![other, kept]);
+}
+
 // ---------------------------------------------------------------------------
 // remove
 // ---------------------------------------------------------------------------
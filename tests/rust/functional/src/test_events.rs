@@ -0,0 +1,122 @@
+// Functional tests for the events reaching the frontend.
+// Events go through the EventHub to a running EventHubClient, and the
+// typed subscriptions get them back.
+
+use crate::helpers::{self, TestContext};
+use common::event::{
+    DirectAccessEntity, EntityChanges, EntityEvent, Event, Origin, ProjectManagementEvent,
+};
+use direct_access::*;
+use frontend::EventHubClient;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+
+// ---------------------------------------------------------------------------
+// Use case outputs
+// ---------------------------------------------------------------------------
+
+// The generated use cases are `unimplemented!()` stubs, so the events are sent
+// the way a run publishes them: the use case's own event without data, then
+// the controller's with the output serialized as JSON.
+#[test]
+fn test_typed_use_case_output_reaches_frontend() {
+    let ctx = TestContext::new();
+    let client = EventHubClient::new(&ctx.hub);
+    let (sender, receiver) = mpsc::channel();
+    let _token = client.on_get_project_stats_completed(move |output| {
+        sender.send(output).unwrap();
+    });
+    let quit = Arc::new(AtomicBool::new(false));
+    client.start(Arc::clone(&quit));
+
+    let output = project_management::ProjectStatsResultDto {
+        total_tasks: 4,
+        completed_tasks: 1,
+        completion_rate: 0.25,
+        member_names: vec!["Ada".into()],
+        ..Default::default()
+    };
+    for data in [None, Some(serde_json::to_string(&output).unwrap())] {
+        ctx.hub.send_event(Event {
+            origin: Origin::ProjectManagement(ProjectManagementEvent::GetProjectStats),
+            ids: vec![],
+            data,
+            changes: None,
+        });
+    }
+
+    let received = receiver.recv_timeout(Duration::from_secs(5));
+    let extra = receiver.recv_timeout(Duration::from_millis(200));
+    quit.store(true, Ordering::Relaxed);
+    assert_eq!(received.expect("the typed subscriber should be called"), output);
+    assert!(extra.is_err(), "the event without data is not an output");
+}
+
+// ---------------------------------------------------------------------------
+// Entity changes
+// ---------------------------------------------------------------------------
+
+// No event loop runs here: the events wait in the hub channel until drained.
+fn drain_updated(ctx: &TestContext, entity: DirectAccessEntity) -> Vec<Event> {
+    let origin = Origin::DirectAccess(entity);
+    ctx.hub
+        .subscribe_receiver()
+        .try_iter()
+        .filter(|event| event.origin == origin)
+        .collect()
+}
+
+#[test]
+fn test_update_reports_changed_fields() {
+    let mut ctx = TestContext::new();
+    let s = helpers::create_scaffold(&mut ctx);
+    let id = helpers::create_tag(&mut ctx, s.workspace_id, "Tag", "#000");
+    drain_updated(&ctx, DirectAccessEntity::Tag(EntityEvent::Updated));
+
+    let mut update_dto: UpdateTagDto = tag_controller::get(&ctx.db, &id).unwrap().unwrap().into();
+    update_dto.color = "#FFF".into();
+    tag_controller::update(&ctx.db, &ctx.hub, &mut ctx.undo, None, &update_dto).unwrap();
+
+    let events = drain_updated(&ctx, DirectAccessEntity::Tag(EntityEvent::Updated));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].ids, vec![id]);
+    let changes = events[0].changes.as_ref().expect("an update reports its changes");
+    assert!(changes.touches("color"));
+    assert!(!changes.touches("name"));
+    assert!(events[0].touches_any(&["color"]));
+    assert!(!events[0].touches_any(&["name"]));
+}
+
+#[test]
+fn test_set_relationship_reports_added_and_removed_ids() {
+    let mut ctx = TestContext::new();
+    let s = helpers::create_scaffold(&mut ctx);
+    let tag_a = helpers::create_tag(&mut ctx, s.workspace_id, "TagA", "#F00");
+    let tag_b = helpers::create_tag(&mut ctx, s.workspace_id, "TagB", "#0F0");
+    let tag_c = helpers::create_tag(&mut ctx, s.workspace_id, "TagC", "#00F");
+    let set_tags = |ctx: &mut TestContext, right_ids: Vec<u64>| {
+        project_controller::set_relationship(
+            &ctx.db, &ctx.hub, &mut ctx.undo, None,
+            &ProjectRelationshipDto { id: s.project_id, field: ProjectRelationshipField::Tags, right_ids },
+        ).unwrap();
+    };
+    set_tags(&mut ctx, vec![tag_a, tag_b]);
+    drain_updated(&ctx, DirectAccessEntity::Project(EntityEvent::Updated));
+
+    set_tags(&mut ctx, vec![tag_b, tag_c]);
+    let events = drain_updated(&ctx, DirectAccessEntity::Project(EntityEvent::Updated));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].ids, vec![s.project_id]);
+    assert_eq!(
+        events[0].changes,
+        Some(EntityChanges::Relationship { field: "tags".into(), added: vec![tag_c], removed: vec![tag_a] })
+    );
+
+    // the undo restores a snapshot: no field-level changes, so it touches every field
+    ctx.undo.undo(None).unwrap();
+    let events = drain_updated(&ctx, DirectAccessEntity::Project(EntityEvent::Updated));
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|event| event.touches_any(&["tags"])));
+}
@@ -133,3 +133,27 @@ fn test_cleanup_completed() {
         &ctx.db, &ctx.hub,
     );
 }
+
+#[test]
+#[should_panic(expected = "unimplemented")]
+fn test_select_tasks() {
+    let (mut ctx, s) = setup();
+    let t1 = helpers::create_task(&mut ctx, s.project_id, "Select1");
+
+    let task = task_management::TaskRefDto { task_id: t1, label: "Select1".into() };
+    let dto = task_management::TaskSelectionDto { tasks: vec![task.clone()], focus: Some(task) };
+    let _ = task_management::task_management_controller::select_tasks(&ctx.db, &ctx.hub, &dto);
+}
+
+#[test]
+fn test_count_selected_tasks_rejects_invalid_nested_dto() {
+    let (ctx, _s) = setup();
+    let dto = task_management::TaskSelectionDto {
+        tasks: vec![task_management::TaskRefDto { task_id: 1, label: String::new() }],
+        focus: None,
+    };
+    let err = task_management::task_management_controller::count_selected_tasks(
+        &ctx.db, &ctx.hub, &dto,
+    ).unwrap_err();
+    assert!(err.to_string().contains("TaskRefDto"), "{err}");
+}
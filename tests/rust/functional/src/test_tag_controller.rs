@@ -1,6 +1,9 @@
 // Functional tests for Tag controller (entity with owner, no children)
 
 use crate::helpers::{self, TestContext};
+use common::database::transactions::Transaction;
+use common::direct_access::repository_factory;
+use common::error::RepositoryError;
 use direct_access::*;
 
 fn setup() -> (TestContext, helpers::Scaffold) {
@@ -121,3 +124,109 @@ fn test_remove_multiple() {
         assert!(tag_controller::get(&ctx.db, id).unwrap().is_none());
     }
 }
+
+#[test]
+fn test_query_filter_sort_and_page() {
+    let (mut ctx, s) = setup();
+    tag_controller::create_multi(
+        &ctx.db, &ctx.hub, &mut ctx.undo, None,
+        &[
+            CreateTagDto { name: "bug-b".into(), color: "#F00".into(), ..Default::default() },
+            CreateTagDto { name: "feature".into(), color: "#0F0".into(), ..Default::default() },
+            CreateTagDto { name: "bug-a".into(), color: "#F00".into(), ..Default::default() },
+            CreateTagDto { name: "bug-c".into(), color: "#00F".into(), ..Default::default() },
+        ],
+        s.workspace_id, -1,
+    ).unwrap();
+
+    let query = TagQuery::new()
+        .filter(TagFilter::Name(TextCondition::StartsWith("bug".into())))
+        .sort_by(TagSortField::Name, SortOrder::Descending);
+    let names: Vec<String> = tag_controller::query(&ctx.db, &query).unwrap().into_iter().map(|t| t.name).collect();
+    assert_eq!(names, vec!["bug-c", "bug-b", "bug-a"]);
+
+    let red = TagQuery::new().filter(TagFilter::Color(TextCondition::Eq("#F00".into())));
+    assert_eq!(tag_controller::count(&ctx.db, &red).unwrap(), 2);
+
+    let page = query.clone().offset(1).limit(1);
+    let paged = tag_controller::query(&ctx.db, &page).unwrap();
+    assert_eq!(paged.len(), 1);
+    assert_eq!(paged[0].name, "bug-b");
+    // count ignores the page
+    assert_eq!(tag_controller::count(&ctx.db, &page).unwrap(), 3);
+}
+
+#[test]
+fn test_query_no_match() {
+    let (ctx, _) = setup();
+    let query = TagQuery::new().filter(TagFilter::Name(TextCondition::In(vec!["nope".into()])));
+    assert!(tag_controller::query(&ctx.db, &query).unwrap().is_empty());
+    assert_eq!(tag_controller::count(&ctx.db, &query).unwrap(), 0);
+}
+
+fn create_named(ctx: &mut TestContext, ws_id: u64, name: &str) -> anyhow::Result<TagDto> {
+    tag_controller::create(
+        &ctx.db, &ctx.hub, &mut ctx.undo, None,
+        &CreateTagDto { name: name.into(), color: "#000".into(), ..Default::default() },
+        ws_id, -1,
+    )
+}
+
+fn is_unique_violation(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::UniqueViolation { field: "name", .. })
+        )
+    })
+}
+
+#[test]
+fn test_unique_name_rejected() {
+    let (mut ctx, s) = setup();
+    let first = create_named(&mut ctx, s.workspace_id, "Dup").unwrap();
+    let err = create_named(&mut ctx, s.workspace_id, "Dup").unwrap_err();
+    assert!(is_unique_violation(&err), "unexpected error: {err:#}");
+
+    // the rejected create left nothing behind
+    let all = tag_controller::get_all(&ctx.db).unwrap();
+    assert_eq!(all.iter().filter(|t| t.name == "Dup").count(), 1);
+
+    // renaming frees the value
+    let mut update_dto: UpdateTagDto = first.into();
+    update_dto.name = "Renamed".into();
+    tag_controller::update(&ctx.db, &ctx.hub, &mut ctx.undo, None, &update_dto).unwrap();
+    create_named(&mut ctx, s.workspace_id, "Dup").unwrap();
+}
+
+#[test]
+fn test_unique_name_follows_undo_redo() {
+    let (mut ctx, s) = setup();
+    create_named(&mut ctx, s.workspace_id, "Once").unwrap();
+    ctx.undo.undo(None).unwrap();
+    // the undone create released the name
+    let again = create_named(&mut ctx, s.workspace_id, "Once").unwrap();
+    ctx.undo.undo(None).unwrap();
+    ctx.undo.redo(None).unwrap();
+    // the redone create holds it again
+    let err = create_named(&mut ctx, s.workspace_id, "Once").unwrap_err();
+    assert!(is_unique_violation(&err), "unexpected error: {err:#}");
+    assert!(tag_controller::get(&ctx.db, &again.id).unwrap().is_some());
+}
+
+#[test]
+fn test_repository_get_by_indexed_fields() {
+    let (mut ctx, s) = setup();
+    let a = helpers::create_tag(&mut ctx, s.workspace_id, "a", "#F00");
+    helpers::create_tag(&mut ctx, s.workspace_id, "b", "#F00");
+    helpers::create_tag(&mut ctx, s.workspace_id, "c", "#00F");
+    tag_controller::remove(&ctx.db, &ctx.hub, &mut ctx.undo, None, &a).unwrap();
+
+    let transaction = Transaction::begin_read_transaction(&ctx.db).unwrap();
+    let repository = repository_factory::read::create_tag_repository(&transaction).unwrap();
+    assert_eq!(repository.get_by_name("b").unwrap().map(|t| t.name), Some("b".to_string()));
+    assert!(repository.get_by_name("a").unwrap().is_none());
+    let red = repository.get_by_color("#F00").unwrap();
+    assert_eq!(red.len(), 1);
+    assert_eq!(red[0].name, "b");
+}
@@ -25,6 +25,26 @@ fn test_create_with_owner() {
     assert_eq!(task.title, "MyTask");
 }
 
+#[test]
+fn test_create_uses_declared_defaults() {
+    let (mut ctx, s) = setup();
+    let before = chrono::Utc::now();
+    let task = task_controller::create(
+        &ctx.db, &ctx.hub, &mut ctx.undo, None,
+        &CreateTaskDto::default(),
+        s.project_id, -1,
+    ).unwrap();
+    assert_eq!(task.title, "Untitled");
+    assert_eq!(task.weight, 1.0);
+    assert_eq!(task.effort_points, 1);
+    assert_eq!(task.budget.to_string(), "0.00");
+    assert_eq!(task.difficulty, common::entities::TaskDifficulty::Medium);
+    assert!(task.due_date >= before);
+    // fields without a declared default keep the type's default
+    assert!(!task.is_done);
+    assert!(task.content.is_empty());
+}
+
 #[test]
 fn test_create_multiple() {
     let (mut ctx, s) = setup();
@@ -125,6 +145,31 @@ fn test_update_numeric_fields() {
     assert_eq!(updated.effort_points, 42);
 }
 
+#[test]
+fn test_update_rich_scalar_fields() {
+    let (mut ctx, s) = setup();
+    let id = helpers::create_task(&mut ctx, s.project_id, "Rich");
+    let dto = task_controller::get(&ctx.db, &id).unwrap().unwrap();
+    assert_eq!(dto.budget, rust_decimal::Decimal::ZERO);
+    assert_eq!(dto.start_day, None);
+    assert_eq!(dto.properties, serde_json::Value::Null);
+    let mut update_dto: UpdateTaskDto = dto.into();
+    update_dto.budget = "1234.56".parse().unwrap();
+    update_dto.start_day = chrono::NaiveDate::from_ymd_opt(2026, 3, 14);
+    update_dto.reminder_times = vec![chrono::NaiveTime::from_hms_opt(9, 30, 0).unwrap()];
+    update_dto.time_spent = std::time::Duration::from_secs(5400);
+    update_dto.attachment = vec![0, 1, 2, 255];
+    update_dto.properties = serde_json::json!({ "color": "red", "sizes": [1, 2] });
+    task_controller::update(&ctx.db, &ctx.hub, &mut ctx.undo, None, &update_dto).unwrap();
+    let fetched = task_controller::get(&ctx.db, &id).unwrap().unwrap();
+    assert_eq!(fetched.budget.to_string(), "1234.56");
+    assert_eq!(fetched.start_day, update_dto.start_day);
+    assert_eq!(fetched.reminder_times, update_dto.reminder_times);
+    assert_eq!(fetched.time_spent, update_dto.time_spent);
+    assert_eq!(fetched.attachment, update_dto.attachment);
+    assert_eq!(fetched.properties, update_dto.properties);
+}
+
 #[test]
 fn test_update_enum_field() {
     let (mut ctx, s) = setup();